@@ -112,9 +112,11 @@ export default defineConfig({
       {
         text: "Alternative backends",
         items: [
+          { text: "iTerm2", link: "/guide/iterm2" },
           { text: "kitty", link: "/guide/kitty" },
           { text: "WezTerm", link: "/guide/wezterm" },
           { text: "Zellij", link: "/guide/zellij" },
+          { text: "Headless (no multiplexer)", link: "/guide/headless" },
         ],
       },
       {
@@ -124,7 +126,11 @@ export default defineConfig({
           { text: "direnv", link: "/guide/direnv" },
           { text: "Monorepos", link: "/guide/monorepos" },
           { text: "Git worktree caveats", link: "/guide/git-worktree-caveats" },
+          { text: "Jujutsu (jj)", link: "/guide/jujutsu" },
           { text: "Nix", link: "/guide/nix" },
+          { text: "Windows", link: "/guide/windows" },
+          { text: "Plugins", link: "/guide/plugins" },
+          { text: "Remote hosts", link: "/guide/remote" },
         ],
       },
       {
@@ -134,13 +140,25 @@ export default defineConfig({
           { text: "merge", link: "/reference/commands/merge" },
           { text: "remove", link: "/reference/commands/remove" },
           { text: "list", link: "/reference/commands/list" },
+          { text: "status", link: "/reference/commands/status" },
+          { text: "report", link: "/reference/commands/report" },
           { text: "open", link: "/reference/commands/open" },
           { text: "close", link: "/reference/commands/close" },
           { text: "sync-files", link: "/reference/commands/sync-files" },
+          { text: "run", link: "/reference/commands/run" },
+          { text: "wait", link: "/reference/commands/wait" },
+          { text: "watch", link: "/reference/commands/watch" },
+          { text: "serve", link: "/reference/commands/serve" },
+          { text: "peek", link: "/reference/commands/peek" },
+          { text: "prompts", link: "/reference/commands/prompts" },
+          { text: "session", link: "/reference/commands/session" },
           { text: "path", link: "/reference/commands/path" },
           { text: "dashboard", link: "/reference/commands/dashboard" },
           { text: "sidebar", link: "/reference/commands/sidebar" },
           { text: "init", link: "/reference/commands/init" },
+          { text: "doctor", link: "/reference/commands/doctor" },
+          { text: "logs", link: "/reference/commands/logs" },
+          { text: "audit", link: "/reference/commands/audit" },
           { text: "claude prune", link: "/reference/commands/claude" },
           { text: "sandbox", link: "/reference/commands/sandbox" },
           { text: "completions", link: "/reference/commands/completions" },
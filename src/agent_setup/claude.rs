@@ -13,7 +13,7 @@ use super::StatusCheck;
 /// Hooks extracted from `.claude-plugin/plugin.json` at compile time.
 const PLUGIN_JSON: &str = include_str!("../../.claude-plugin/plugin.json");
 
-fn settings_path() -> Option<PathBuf> {
+pub(super) fn settings_path() -> Option<PathBuf> {
     home::home_dir().map(|h| h.join(".claude/settings.json"))
 }
 
@@ -218,6 +218,7 @@ mod tests {
     fn test_load_hooks_from_plugin() {
         let hooks = load_hooks_from_plugin().unwrap();
         let obj = hooks.as_object().unwrap();
+        assert!(obj.contains_key("SessionStart"));
         assert!(obj.contains_key("UserPromptSubmit"));
         assert!(obj.contains_key("Notification"));
         assert!(obj.contains_key("PostToolUse"));
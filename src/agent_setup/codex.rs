@@ -23,7 +23,7 @@ fn codex_dir() -> Option<PathBuf> {
     home::home_dir().map(|h| h.join(".codex"))
 }
 
-fn hooks_path() -> Option<PathBuf> {
+pub(super) fn hooks_path() -> Option<PathBuf> {
     codex_dir().map(|d| d.join("hooks.json"))
 }
 
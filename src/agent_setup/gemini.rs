@@ -17,7 +17,7 @@ fn gemini_dir() -> Option<PathBuf> {
     home::home_dir().map(|h| h.join(".gemini"))
 }
 
-fn settings_path() -> Option<PathBuf> {
+pub(super) fn settings_path() -> Option<PathBuf> {
     gemini_dir().map(|d| d.join("settings.json"))
 }
 
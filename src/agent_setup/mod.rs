@@ -14,9 +14,10 @@ pub mod pi;
 use anyhow::{Context, Result};
 use console::style;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::BTreeSet;
 use std::fs;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 use std::path::PathBuf;
 
 /// An agent that supports status tracking.
@@ -42,6 +43,18 @@ impl Agent {
             Agent::Pi => "pi",
         }
     }
+
+    /// Lowercase identifier, as in the setup state file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Agent::Claude => "claude",
+            Agent::Codex => "codex",
+            Agent::Copilot => "copilot",
+            Agent::Gemini => "gemini",
+            Agent::OpenCode => "opencode",
+            Agent::Pi => "pi",
+        }
+    }
 }
 
 /// Result of verifying an agent's status tracking.
@@ -156,6 +169,76 @@ pub fn install(agent: Agent) -> Result<String> {
     }
 }
 
+/// File outside the repository that holds the agent's status tracking
+/// hooks. Copilot's hooks live in the repository, so it has none.
+pub fn hooks_path(agent: Agent) -> Option<PathBuf> {
+    match agent {
+        Agent::Claude => claude::settings_path(),
+        Agent::Codex => codex::hooks_path(),
+        Agent::Copilot => None,
+        Agent::Gemini => gemini::settings_path(),
+        Agent::OpenCode => opencode::plugin_path(),
+        Agent::Pi => pi::extension_path(),
+    }
+}
+
+/// Remove status tracking for the given agent: the plugin file for OpenCode
+/// and pi, the hook groups running `workmux set-window-status` from the
+/// other agents' settings. Returns a description of what was done.
+pub fn uninstall(agent: Agent) -> Result<String> {
+    let path = hooks_path(agent)
+        .with_context(|| format!("{} has no hooks outside the repository", agent.name()))?;
+    if matches!(agent, Agent::OpenCode | Agent::Pi) {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        return Ok(format!("Removed {}", path.display()));
+    }
+
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut settings: Value = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+    if !remove_workmux_hooks(&mut settings) {
+        return Ok(format!("No workmux hooks in {}", path.display()));
+    }
+    let output = serde_json::to_string_pretty(&settings)?;
+    fs::write(&path, output + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(format!("Removed hooks from {}", path.display()))
+}
+
+/// Drop hook groups that run `workmux set-window-status`, and events left
+/// without any groups. Returns whether anything was removed.
+fn remove_workmux_hooks(settings: &mut Value) -> bool {
+    let Some(hooks) = settings.get_mut("hooks").and_then(|v| v.as_object_mut()) else {
+        return false;
+    };
+
+    let mut removed = false;
+    for groups in hooks.values_mut() {
+        let Some(groups_arr) = groups.as_array_mut() else {
+            continue;
+        };
+        let before = groups_arr.len();
+        groups_arr.retain(|group| !runs_workmux(group));
+        removed |= groups_arr.len() != before;
+    }
+    hooks.retain(|_, groups| groups.as_array().is_none_or(|arr| !arr.is_empty()));
+    removed
+}
+
+fn runs_workmux(group: &Value) -> bool {
+    group
+        .get("hooks")
+        .and_then(|v| v.as_array())
+        .is_some_and(|hook_list| {
+            hook_list.iter().any(|hook| {
+                hook.get("command")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|cmd| cmd.contains("workmux set-window-status"))
+            })
+        })
+}
+
 // --- State persistence (declined agents) ---
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -278,7 +361,7 @@ fn install_agents(agents: &[&AgentCheck]) {
 /// Only prompts for detected agents that are NOT installed and NOT
 /// previously declined. Designed to be called after the nerdfont wizard.
 pub fn prompt_wizard() -> Result<()> {
-    if !io::stdin().is_terminal() {
+    if !crate::interactive::can_prompt() {
         return Ok(());
     }
 
@@ -484,4 +567,33 @@ mod tests {
         let deserialized: SetupState = serde_json::from_str("{}").unwrap();
         assert!(deserialized.declined.is_empty());
     }
+
+    #[test]
+    fn test_remove_workmux_hooks() {
+        let mut settings = serde_json::json!({
+            "model": "opus",
+            "hooks": {
+                "Stop": [
+                    {"hooks": [{"type": "command", "command": "workmux set-window-status done"}]},
+                    {"hooks": [{"type": "command", "command": "afplay done.aiff"}]}
+                ],
+                "UserPromptSubmit": [
+                    {"hooks": [{"type": "command", "command": "workmux set-window-status working"}]}
+                ]
+            }
+        });
+        assert!(remove_workmux_hooks(&mut settings));
+        assert_eq!(
+            settings,
+            serde_json::json!({
+                "model": "opus",
+                "hooks": {
+                    "Stop": [
+                        {"hooks": [{"type": "command", "command": "afplay done.aiff"}]}
+                    ]
+                }
+            })
+        );
+        assert!(!remove_workmux_hooks(&mut settings));
+    }
 }
@@ -28,7 +28,7 @@ pub fn opencode_config_dir() -> Option<PathBuf> {
     home::home_dir().map(|h| h.join(".config/opencode"))
 }
 
-fn plugin_path() -> Option<PathBuf> {
+pub(super) fn plugin_path() -> Option<PathBuf> {
     opencode_config_dir().map(|d| d.join("plugins/workmux-status.ts"))
 }
 
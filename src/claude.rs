@@ -102,3 +102,100 @@ pub fn prune_stale_entries() -> Result<usize> {
 
     Ok(removed_count)
 }
+
+/// Project entries in ~/.claude.json for workmux worktrees: paths inside a
+/// `<project>__worktrees` directory or one of `worktree_dirs` (the configured
+/// `worktree_dir` of each repository), whether or not they still exist.
+pub fn worktree_entries(worktree_dirs: &[PathBuf]) -> Result<Vec<String>> {
+    let Some(config_path) = get_config_path().filter(|p| p.exists()) else {
+        return Ok(Vec::new());
+    };
+    let contents = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read Claude config: {:?}", config_path))?;
+    let config_value: serde_json::Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse Claude config: {:?}", config_path))?;
+
+    let Some(projects) = config_value.get("projects").and_then(|p| p.as_object()) else {
+        return Ok(Vec::new());
+    };
+    Ok(projects
+        .keys()
+        .filter(|path_str| is_workmux_worktree(Path::new(path_str), worktree_dirs))
+        .cloned()
+        .collect())
+}
+
+/// Whether `path` (or the directory it's a subdirectory of) is where workmux
+/// creates worktrees.
+fn is_workmux_worktree(path: &Path, worktree_dirs: &[PathBuf]) -> bool {
+    if !path.is_absolute() {
+        return false;
+    }
+    path.ancestors().skip(1).any(|dir| {
+        dir.file_name()
+            .is_some_and(|name| name.to_string_lossy().ends_with("__worktrees"))
+            || worktree_dirs.iter().any(|configured| configured == dir)
+    })
+}
+
+/// Removes the given project entries from ~/.claude.json, keeping a backup
+/// at ~/.claude.json.bak. Returns the number of entries removed.
+pub fn remove_entries(paths: &[String]) -> Result<usize> {
+    let config_path = get_config_path().context("Could not determine home directory")?;
+    let contents = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read Claude config: {:?}", config_path))?;
+    let mut config_value: serde_json::Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse Claude config: {:?}", config_path))?;
+
+    let Some(projects) = config_value
+        .get_mut("projects")
+        .and_then(|projects| projects.as_object_mut())
+    else {
+        return Ok(0);
+    };
+    let removed_count = paths
+        .iter()
+        .filter(|path_str| projects.remove(path_str.as_str()).is_some())
+        .count();
+    if removed_count == 0 {
+        return Ok(0);
+    }
+
+    let backup_path = config_path.with_extension("json.bak");
+    fs::copy(&config_path, &backup_path).with_context(|| {
+        format!(
+            "Failed to create backup of Claude config at {:?}",
+            backup_path
+        )
+    })?;
+    let new_contents = serde_json::to_string_pretty(&config_value)?;
+    fs::write(&config_path, new_contents)
+        .with_context(|| format!("Failed to write updated Claude config to {:?}", config_path))?;
+    Ok(removed_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_paths_in_worktree_directories_are_worktrees() {
+        let configured = vec![PathBuf::from("/home/me/.worktrees/api")];
+        for path in [
+            "/src/app__worktrees/feature",
+            "/src/app__worktrees/feature/web",
+            "/home/me/.worktrees/api/fix-login",
+        ] {
+            assert!(is_workmux_worktree(Path::new(path), &configured), "{path}");
+        }
+        for path in [
+            "/src/app",
+            "/src/app__worktrees",
+            "/src/other-checkout",
+            "/home/me/.worktrees/api",
+            "relative/app__worktrees/feature",
+        ] {
+            assert!(!is_workmux_worktree(Path::new(path), &configured), "{path}");
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::command::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
 use crate::config::MuxMode;
-use crate::{claude, command, config, git, nerdfont};
-use anyhow::{Context, Result};
+use crate::multiplexer::BackendType;
+use crate::{claude, command, config, git, nerdfont, vcs, workflow};
+use anyhow::{Context, Result, bail};
 use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{Shell, generate};
@@ -21,7 +22,7 @@ impl WorktreeBranchParser {
             return Vec::new();
         }
 
-        let worktrees = match git::list_worktrees() {
+        let worktrees = match vcs::list_workspaces() {
             Ok(wt) => wt,
             // Fail silently on completion; don't disrupt the user's shell.
             Err(_) => return Vec::new(),
@@ -57,7 +58,7 @@ impl clap::builder::TypedValueParser for WorktreeBranchParser {
         &self,
     ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
         // Return None to avoid running git operations during completion script generation.
-        // Dynamic completions are handled by the _complete-branches subcommand,
+        // Dynamic completions are handled by `_complete branches`,
         // which is called by the shell only when the user presses TAB.
         None
     }
@@ -78,7 +79,7 @@ impl WorktreeHandleParser {
             return Vec::new();
         }
 
-        let worktrees = match git::list_worktrees() {
+        let worktrees = match vcs::list_workspaces() {
             Ok(wt) => wt,
             // Fail silently on completion; don't disrupt the user's shell.
             Err(_) => return Vec::new(),
@@ -118,7 +119,7 @@ impl clap::builder::TypedValueParser for WorktreeHandleParser {
         &self,
     ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
         // Return None to avoid running git operations during completion script generation.
-        // Dynamic completions are handled by the _complete-handles subcommand,
+        // Dynamic completions are handled by `_complete handles`,
         // which is called by the shell only when the user presses TAB.
         None
     }
@@ -198,7 +199,7 @@ impl clap::builder::TypedValueParser for AgentTargetParser {
     fn possible_values(
         &self,
     ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
-        // Dynamic completions handled by _complete-agent-targets subcommand
+        // Dynamic completions handled by `_complete agent-targets`
         None
     }
 }
@@ -239,7 +240,7 @@ impl clap::builder::TypedValueParser for GitBranchParser {
         &self,
     ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
         // Return None to avoid running git operations during completion script generation.
-        // Dynamic completions are handled by the _complete-git-branches subcommand,
+        // Dynamic completions are handled by `_complete git-branches`,
         // which is called by the shell only when the user presses TAB.
         None
     }
@@ -255,7 +256,7 @@ impl clap::builder::TypedValueParser for GitBranchParser {
 {usage-heading} {usage}
 
 Worktree lifecycle:
-  add          Create a new worktree and tmux window
+  add          Create a new worktree and tmux window [new]
   remove       Remove a worktree, tmux window, and branch without merging [rm]
   rename       Rename a worktree, tmux window/session, and optionally branch
   merge        Merge a branch, then clean up the worktree and tmux window
@@ -269,10 +270,14 @@ Monitoring:
   list         List all worktrees [ls]
   path         Get the filesystem path of a worktree
   status       Query agent status for worktrees
+  report       Show active time per branch and project
 
 Setup and configuration:
-  init         Generate example .workmux.yaml configuration file
+  init         Generate .workmux.yaml for this repository
   setup        Set up agent status tracking hooks and install skills
+  doctor       Check dependencies, agent hooks, and configuration
+  logs         Show workmux's log file
+  audit        List or revert changes workmux made outside the repo
   config       Manage global configuration
   sandbox      Manage sandbox settings
   sync-files   Re-apply file operations (copy/symlink) to worktrees
@@ -281,8 +286,11 @@ Setup and configuration:
 Agent interaction:
   send         Send a prompt or instruction to a running agent
   capture      Capture terminal output from a running agent
+  peek         Show an agent's pane in a popup without leaving yours
   wait         Wait for agents to reach a target status
   run          Run a command in a worktree's window
+  runs         List and re-run past run commands
+  prompts      Manage the prompt template library
 
 Help and updates:
   docs         Show detailed documentation (renders README.md)
@@ -292,12 +300,48 @@ Help and updates:
   help         Print help for a command
 
 Options:
-  -h, --help     Print help
-  -V, --version  Print version
+      --repo <REPO>        Run in another repository, by name or path
+      --remote <HOST>      Drive tmux and git on another machine over SSH
+      --backend <BACKEND>  Multiplexer to use instead of detecting one
+      --socket <PATH>      tmux or WezTerm server socket to talk to
+  -y, --yes                Never prompt (for scripts and CI) [non-interactive]
+      --timings            Print where the command spent its time
+  -h, --help               Print help
+  -V, --version            Print version
 
 Run 'workmux docs' for detailed documentation.
 ")]
 struct Cli {
+    /// Run in another repository: a path, or the name of a repository
+    /// workmux has seen agents or runs in
+    #[arg(long, global = true, value_name = "REPO")]
+    repo: Option<String>,
+
+    /// Run tmux and git on another machine over SSH. `--repo` is then a path
+    /// on that machine
+    #[arg(long, global = true, value_name = "HOST")]
+    remote: Option<String>,
+
+    /// Multiplexer to use (tmux, wezterm, kitty, zellij, iterm2 or none) instead of
+    /// detecting one. Same as WORKMUX_BACKEND
+    #[arg(long, global = true, value_name = "BACKEND")]
+    backend: Option<BackendType>,
+
+    /// Socket of the tmux server or WezTerm mux to talk to, instead of the
+    /// one this terminal is in
+    #[arg(long, global = true, value_name = "PATH")]
+    socket: Option<PathBuf>,
+
+    /// Never prompt: confirmations proceed, anything that would lose work is
+    /// refused. Also set by WORKMUX_NONINTERACTIVE=1
+    #[arg(short = 'y', long, visible_alias = "non-interactive", global = true)]
+    yes: bool,
+
+    /// Print how long the command spent on config, git, multiplexer and
+    /// forge calls when it finishes
+    #[arg(long, global = true)]
+    timings: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -320,12 +364,17 @@ impl From<CliMuxMode> for MuxMode {
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new worktree and tmux window
+    #[command(visible_alias = "new")]
     Add {
         /// Name of the branch (creates if it doesn't exist) or remote ref (e.g., origin/feature).
         /// When used with --pr, this becomes the custom local branch name.
-        #[arg(required_unless_present_any = ["pr", "auto_name"], value_parser = GitBranchParser::new())]
+        #[arg(required_unless_present_any = ["pr", "auto_name", "interactive", "ticket"], value_parser = GitBranchParser::new())]
         branch_name: Option<String>,
 
+        /// Ask for the branch name, base, agent, prompt and other settings step by step
+        #[arg(short = 'i', long, conflicts_with_all = ["branch_name", "pr", "auto_name"])]
+        interactive: bool,
+
         /// Pull request number to checkout
         #[arg(long, conflicts_with_all = ["base", "auto_name"])]
         pr: Option<u32>,
@@ -334,6 +383,10 @@ enum Commands {
         #[arg(short = 'A', long = "auto-name", conflicts_with = "pr")]
         auto_name: bool,
 
+        /// Issue tracker ticket (e.g. PROJ-123) to name the branch after and seed the prompt from
+        #[arg(long, value_name = "KEY", conflicts_with_all = ["pr", "auto_name", "interactive"])]
+        ticket: Option<String>,
+
         /// Base branch/commit/tag to branch from (overrides config base_branch, defaults to current branch)
         #[arg(long)]
         base: Option<String>,
@@ -382,8 +435,9 @@ enum Commands {
 
     /// Open a tmux window for an existing worktree
     Open {
-        /// Worktree name(s) (directory name, visible in tmux window). Optional with --new.
-        #[arg(value_parser = WorktreeHandleParser::new(), required_unless_present = "new")]
+        /// Worktree name(s) (directory name, visible in tmux window). Picked
+        /// interactively if omitted, or the current worktree with --new.
+        #[arg(value_parser = WorktreeHandleParser::new())]
         names: Vec<String>,
 
         /// Re-run post-create hooks (e.g., pnpm install)
@@ -410,6 +464,15 @@ enum Commands {
         #[arg(short = 'c', long = "continue")]
         continue_session: bool,
 
+        /// Resume an earlier conversation on the branch, by its number in
+        /// `workmux session history` (1 is the latest)
+        #[arg(long, value_name = "N", conflicts_with = "continue_session")]
+        resume_session: Option<usize>,
+
+        /// Use a named pane layout from config (remembered for later opens)
+        #[arg(short = 'l', long)]
+        layout: Option<String>,
+
         #[command(flatten)]
         prompt: PromptArgs,
 
@@ -472,6 +535,14 @@ enum Commands {
         /// Show a system notification on successful merge
         #[arg(long)]
         notification: bool,
+
+        /// Print a tab-separated result line for scripts instead of messages
+        #[arg(long)]
+        porcelain: bool,
+
+        /// Confirm a human approved this merge (for merge_policy.require_approval)
+        #[arg(long)]
+        approve: bool,
     },
 
     /// Rename a worktree, its tmux window/session, and (optionally) its branch
@@ -520,6 +591,10 @@ enum Commands {
         #[arg(long)]
         json: bool,
 
+        /// Show disk usage for each worktree (including untracked build artifacts)
+        #[arg(long)]
+        du: bool,
+
         /// Filter by worktree name or branch (supports multiple)
         #[arg(value_parser = WorktreeBranchParser::new())]
         filter: Vec<String>,
@@ -534,9 +609,10 @@ enum Commands {
 
     /// Send a prompt or instruction to a running agent
     Send {
-        /// Worktree name (supports cross-project with project:handle syntax)
+        /// Worktree name (supports cross-project with project:handle syntax).
+        /// Picked interactively if omitted
         #[arg(value_parser = AgentTargetParser::new())]
-        name: String,
+        name: Option<String>,
 
         /// Text to send (reads from --file or stdin if omitted)
         #[arg(conflicts_with = "file")]
@@ -558,6 +634,13 @@ enum Commands {
         lines: u16,
     },
 
+    /// Show an agent's pane in a popup without leaving yours
+    Peek {
+        /// Worktree name (supports cross-project with project:handle syntax)
+        #[arg(value_parser = AgentTargetParser::new())]
+        name: String,
+    },
+
     /// Query agent status for worktrees
     Status {
         /// Worktree names (supports cross-project with project:handle syntax)
@@ -571,17 +654,33 @@ enum Commands {
         /// Include git info (staged/unstaged changes, unmerged commits)
         #[arg(long)]
         git: bool,
+
+        /// Keep refreshing the table in place, with PR checks
+        #[arg(short = 'w', long, conflicts_with = "json")]
+        watch: bool,
+
+        /// Seconds between refreshes with --watch
+        #[arg(long, default_value_t = 2, requires = "watch", value_name = "SECS")]
+        interval: u64,
     },
 
     /// Wait for agents to reach a target status
     Wait {
         /// Worktree names (supports cross-project with project:handle syntax)
-        #[arg(required = true, value_parser = AgentTargetParser::new())]
+        #[arg(
+            required_unless_present = "all",
+            conflicts_with = "all",
+            value_parser = AgentTargetParser::new()
+        )]
         worktrees: Vec<String>,
 
-        /// Target status to wait for
-        #[arg(long, default_value = "done")]
-        status: String,
+        /// Wait for every worktree that has an agent running
+        #[arg(long)]
+        all: bool,
+
+        /// Target status to wait for (comma-separated to accept any of several)
+        #[arg(long, value_delimiter = ',', default_value = "done")]
+        status: Vec<String>,
 
         /// Maximum wait time in seconds
         #[arg(long)]
@@ -590,13 +689,133 @@ enum Commands {
         /// Return when ANY worktree reaches target (default: wait for ALL)
         #[arg(long)]
         any: bool,
+
+        /// Print a tab-separated result line per worktree for scripts
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Show a desktop notification whenever an agent starts waiting or
+    /// finishes, until interrupted
+    Watch {
+        /// Only watch agents in this project, by repository name (repeatable)
+        #[arg(long = "project", value_name = "NAME")]
+        projects: Vec<String>,
+
+        /// Seconds between checks
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+
+        /// Seconds a status must last before notifying, so brief waits
+        /// don't notify
+        #[arg(long, default_value_t = 5)]
+        debounce: u64,
+    },
+
+    /// Serve agent, worktree and PR status over HTTP, with a web page for
+    /// checking on agents from a phone or another machine
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to listen on. Use 0.0.0.0 to allow other devices; any
+        /// address other than loopback requires the printed access token
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: std::net::IpAddr,
+    },
+
+    /// Print a compact status for the current directory, for shell prompts
+    #[command(name = "prompt-segment")]
+    PromptSegment,
+
+    /// Summarize recent agent activity, optionally by email
+    Digest {
+        /// How many hours of activity to include
+        #[arg(long, default_value_t = 24)]
+        hours: u64,
+
+        /// Email the digest instead of printing it (see notifications.email)
+        #[arg(long)]
+        send: bool,
+    },
+
+    /// Show active time per branch and project (agent working or window
+    /// focused), or a Markdown work summary, for invoicing and retrospectives
+    Report {
+        /// Cover the last 7 days (default: the last 24 hours)
+        #[arg(long, conflicts_with = "since")]
+        week: bool,
+
+        /// Cover this window instead, e.g. 8h, 2d, 4w
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+
+        /// Print the times as JSON, in seconds
+        #[arg(long, conflicts_with_all = ["markdown", "summarize"])]
+        json: bool,
+
+        /// Print a Markdown report with merged branches, pull requests
+        /// opened, prompts and agent usage
+        #[arg(long)]
+        markdown: bool,
+
+        /// Start the Markdown report with an overview written by the
+        /// auto_name generator (implies --markdown)
+        #[arg(long)]
+        summarize: bool,
     },
 
     /// Run a command in a worktree's window
     Run {
         /// Worktree name (supports cross-project with project:handle syntax)
-        #[arg(value_parser = AgentTargetParser::new())]
-        name: String,
+        #[arg(
+            value_parser = AgentTargetParser::new(),
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        name: Option<String>,
+
+        /// Run the command directly in every worktree and print a pass/fail summary
+        #[arg(long, conflicts_with_all = ["background", "keep"])]
+        all: bool,
+
+        /// Number of worktrees to run at once with --all
+        #[arg(short = 'j', long, requires = "all")]
+        jobs: Option<usize>,
+
+        /// Set an environment variable for the command (repeatable)
+        #[arg(
+            short = 'e',
+            long = "env",
+            value_name = "KEY=VALUE",
+            value_parser = command::run::parse_env_assignment
+        )]
+        env: Vec<(String, String)>,
+
+        /// Load environment variables from a dotenv-style file (repeatable)
+        #[arg(long = "env-file", value_name = "PATH")]
+        env_file: Vec<PathBuf>,
+
+        /// Run on the host even if the worktree's agent is sandboxed
+        #[arg(long)]
+        host: bool,
+
+        /// Keep the pane open and re-run the command when worktree files change
+        #[arg(short = 'w', long, conflicts_with_all = ["all", "background", "timeout"])]
+        watch: bool,
+
+        /// With --watch, show a green/red status icon on the window after each run
+        #[arg(long, requires = "watch")]
+        status_icon: bool,
+
+        /// On failure, send the output to the agent (`prompt[:<message>]`) or run a command (`hook:<command>`)
+        #[arg(long, value_name = "ACTION", conflicts_with = "all")]
+        on_fail: Option<crate::state::run::OnFail>,
+
+        /// Show a desktop notification with the result when the command finishes
+        #[arg(long, conflicts_with_all = ["all", "watch"])]
+        notify: bool,
 
         /// Command to run (everything after --)
         #[arg(last = true, required = true)]
@@ -606,6 +825,18 @@ enum Commands {
         #[arg(short = 'b', long)]
         background: bool,
 
+        /// Wait for the command to finish (the default unless --background)
+        #[arg(long, conflicts_with_all = ["background", "watch"])]
+        wait: bool,
+
+        /// Print the result as JSON instead of streaming output (keeps artifacts)
+        #[arg(long, conflicts_with_all = ["all", "background", "watch"])]
+        json: bool,
+
+        /// Print a tab-separated result line instead of streaming output (keeps artifacts)
+        #[arg(long, conflicts_with_all = ["all", "background", "watch", "json"])]
+        porcelain: bool,
+
         /// Keep run artifacts after completion (for debugging)
         #[arg(long)]
         keep: bool,
@@ -615,6 +846,15 @@ enum Commands {
         timeout: Option<u64>,
     },
 
+    /// List and re-run past `workmux run` commands
+    Runs(command::runs::RunsArgs),
+
+    /// List, export and import the agent conversations recorded per branch
+    Session(command::session::SessionArgs),
+
+    /// Manage the prompt template library
+    Prompts(command::prompts::PromptsArgs),
+
     /// Re-apply file operations (copy/symlink) to worktrees
     #[command(name = "sync-files")]
     SyncFiles {
@@ -623,7 +863,7 @@ enum Commands {
         all: bool,
     },
 
-    /// Generate example .workmux.yaml configuration file
+    /// Generate .workmux.yaml for this repository from its build and test setup
     Init,
 
     /// Set up agent status tracking hooks and install skills
@@ -636,8 +876,37 @@ enum Commands {
         skills: bool,
     },
 
+    /// Check dependencies, agent hooks and config, with hints to fix problems
+    Doctor,
+
+    /// Show workmux's log file (level set by WORKMUX_LOG, e.g. debug)
+    Logs {
+        /// Keep printing new lines as they're logged
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Show everything logged in this window, e.g. 30m, 1h, 2d
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+
+        /// Number of recent lines to show when --since isn't given
+        #[arg(short = 'n', long, default_value_t = 100)]
+        lines: usize,
+    },
+
+    /// List everything workmux changed outside the repo (hooks, skills,
+    /// trusted projects, VMs, containers, state), or undo an item
+    Audit {
+        /// Undo the item with this ID (repeatable)
+        #[arg(long, value_name = "ITEM")]
+        revert: Vec<String>,
+    },
+
     /// Show detailed documentation (renders README.md)
-    Docs,
+    Docs {
+        #[command(subcommand)]
+        action: Option<DocsAction>,
+    },
 
     /// Show the changelog (what's new in each version)
     Changelog,
@@ -695,6 +964,14 @@ enum Commands {
         /// Open directly on the specified tab
         #[arg(long, short = 't', value_enum)]
         tab: Option<command::dashboard::DashboardTab>,
+
+        /// Open in a tmux popup over the current window
+        #[arg(long)]
+        popup: bool,
+
+        /// Print the agent list as JSON instead of opening the dashboard
+        #[arg(long, conflicts_with_all = ["preview_size", "diff", "tab", "popup"])]
+        json: bool,
     },
 
     /// Manage global configuration
@@ -714,6 +991,19 @@ enum Commands {
     SetWindowStatus {
         #[arg(value_enum)]
         command: command::set_window_status::SetWindowStatusCommand,
+
+        /// What the agent is asking for (waiting only). Use `-` to read it from
+        /// stdin, either as plain text or as hook JSON with a `message` field.
+        #[arg(long, value_name = "MSG")]
+        reason: Option<String>,
+    },
+
+    /// Record the conversation an agent started (used by hooks)
+    #[command(hide = true, name = "_session-started")]
+    SessionStarted {
+        /// Session ID. Read from the hook JSON on stdin if omitted.
+        #[arg(long)]
+        id: Option<String>,
     },
 
     /// Set the base branch for the current worktree (used after rebasing)
@@ -732,6 +1022,41 @@ enum Commands {
         run_dir: std::path::PathBuf,
     },
 
+    /// Run an agent pane's command with the configured secrets (internal use)
+    #[command(hide = true, name = "_with-secrets")]
+    WithSecrets {
+        /// Shell command to run
+        #[arg(last = true, required = true)]
+        command: String,
+    },
+
+    /// Record focus on a worktree's window (internal use, called by tmux hooks)
+    #[command(hide = true, name = "_focus")]
+    Focus {
+        /// Working directory of the focused pane
+        path: PathBuf,
+    },
+
+    /// Escalate if an agent is still waiting after a delay (internal use)
+    #[command(hide = true, name = "_escalate")]
+    Escalate {
+        /// Multiplexer backend of the agent's pane
+        #[arg(long)]
+        backend: String,
+
+        /// Multiplexer instance of the agent's pane
+        #[arg(long)]
+        instance: String,
+
+        /// Pane ID of the agent
+        #[arg(long)]
+        pane: String,
+
+        /// status_ts when the agent started waiting
+        #[arg(long)]
+        since: u64,
+    },
+
     /// Switch to the agent that most recently completed or is waiting for input
     #[command(hide = true, name = "last-done")]
     LastDone,
@@ -762,29 +1087,34 @@ enum Commands {
         shell: Shell,
     },
 
-    /// Output worktree branch names for shell completion (internal use)
-    #[command(hide = true, name = "_complete-branches")]
-    CompleteBranches,
-
-    /// Output worktree handles for shell completion (internal use)
-    #[command(hide = true, name = "_complete-handles")]
-    CompleteHandles,
-
-    /// Output git branches for shell completion (internal use)
-    #[command(hide = true, name = "_complete-git-branches")]
-    CompleteGitBranches,
-
-    /// Output agent targets for shell completion (internal use)
+    /// Output candidates for shell completion (internal use)
     ///
-    /// Includes local worktree handles plus active agents from other projects.
-    #[command(hide = true, name = "_complete-agent-targets")]
-    CompleteAgentTargets,
+    /// Called by the generated completion scripts when TAB is pressed, so
+    /// candidates reflect the current repo and running agents.
+    #[command(hide = true, name = "_complete")]
+    Complete {
+        #[arg(value_enum)]
+        kind: CompletionKind,
+    },
 
     /// Background update check (internal use)
     #[command(hide = true, name = "_check-update")]
     CheckUpdate,
 }
 
+/// What `_complete` lists
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum CompletionKind {
+    /// Worktree handles (directory names), excluding the main worktree
+    Handles,
+    /// Branches checked out in worktrees, excluding the main branch
+    Branches,
+    /// Local and remote git branches
+    GitBranches,
+    /// Worktree handles plus agents running in other projects
+    AgentTargets,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum SidebarAction {
     /// Switch to the next agent in sidebar order
@@ -799,6 +1129,20 @@ pub enum SidebarAction {
     },
 }
 
+#[derive(Subcommand)]
+enum DocsAction {
+    /// Generate a man page or markdown CLI reference from the command definitions
+    Generate {
+        /// Output a man page (roff), e.g. `workmux docs generate --man > workmux.1`
+        #[arg(long, conflicts_with = "md", required_unless_present = "md")]
+        man: bool,
+
+        /// Output a markdown reference of every command and option
+        #[arg(long)]
+        md: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum ClaudeCommands {
     /// Remove stale entries from ~/.claude.json for deleted worktrees
@@ -810,7 +1154,10 @@ enum ClaudeCommands {
 fn should_prompt_nerdfont(cmd: &Commands) -> bool {
     matches!(
         cmd,
-        Commands::Add { .. } | Commands::Init | Commands::Dashboard { .. } | Commands::List { .. }
+        Commands::Add { .. }
+            | Commands::Init
+            | Commands::Dashboard { json: false, .. }
+            | Commands::List { .. }
     )
 }
 
@@ -824,6 +1171,13 @@ fn should_prompt_status_setup(cmd: &Commands) -> bool {
     )
 }
 
+/// Check if the command works with `--remote`. Only `list` is supported: its
+/// git and tmux calls all go through the transport. Other commands also read
+/// or write state, worktree files or hooks, which stay on this machine.
+fn supports_remote_host(cmd: &Commands) -> bool {
+    matches!(cmd, Commands::List { .. })
+}
+
 /// Check if the command should trigger a background update check.
 fn should_check_update(cmd: &Commands) -> bool {
     matches!(cmd, Commands::Add { .. })
@@ -834,6 +1188,18 @@ pub fn run() -> Result<()> {
     let cli = match Cli::try_parse() {
         Ok(cli) => cli,
         Err(mut e) => {
+            // Unknown `workmux foo` runs a `workmux-foo` plugin from PATH, if any.
+            // Unknown nested subcommands (`workmux runs foo`) are plain errors.
+            let args: Vec<String> = std::env::args_os()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect();
+            if e.kind() == ErrorKind::InvalidSubcommand
+                && let Some(ContextValue::String(name)) = e.get(ContextKind::InvalidSubcommand)
+                && command::plugin::is_top_level(&args, name)
+                && let Some(program) = command::plugin::find(name)
+            {
+                return command::plugin::exec(program, name, &args);
+            }
             // Filter hidden (underscore-prefixed) commands from "similar subcommands" suggestions.
             // Workaround for https://github.com/clap-rs/clap/issues/4853
             if e.kind() == ErrorKind::InvalidSubcommand {
@@ -861,6 +1227,43 @@ pub fn run() -> Result<()> {
         }
     };
 
+    if cli.yes {
+        crate::interactive::enable();
+    }
+    if cli.timings {
+        crate::timings::enable();
+    }
+
+    if cli.remote.is_some() && !supports_remote_host(&cli.command) {
+        bail!("--remote only supports the list command");
+    }
+    if let Some(backend) = cli.backend {
+        if cli.remote.is_some() && backend != BackendType::Tmux {
+            bail!("--remote only works with tmux, not --backend {}", backend);
+        }
+        crate::multiplexer::set_backend(backend);
+    }
+    if let Some(socket) = &cli.socket {
+        // Absolute, so it matches the socket in $TMUX / $WEZTERM_UNIX_SOCKET
+        let socket = match (&cli.remote, socket.is_relative()) {
+            (None, true) => std::env::current_dir()?.join(socket),
+            _ => socket.clone(),
+        };
+        crate::transport::set_socket(socket);
+    }
+
+    // Everything below, config included, happens in the target repository
+    if let Some(host) = &cli.remote {
+        crate::transport::set(crate::transport::Transport::Ssh {
+            host: host.clone(),
+            dir: cli.repo.clone(),
+        });
+    } else if let Some(repo) = &cli.repo {
+        let root = workflow::repos::resolve_repo(repo)?;
+        std::env::set_current_dir(&root)
+            .with_context(|| format!("Failed to change to {}", root.display()))?;
+    }
+
     // Extract config override early so the side-effect loads (nerdfont, update
     // check) respect the user's explicit --config choice.
     let config_override = match &cli.command {
@@ -905,11 +1308,46 @@ pub fn run() -> Result<()> {
         command::update::check_and_notify(&cfg);
     }
 
+    // `add -i` asks for what the flags didn't say, then runs with the answers as flags
+    let answers = match &cli.command {
+        Commands::Add {
+            interactive: true,
+            base,
+            prompt,
+            multi,
+            layout,
+            setup,
+            config,
+            ..
+        } => Some(command::add_wizard::run(&command::add_wizard::Preset {
+            base: base.as_deref(),
+            prompt: prompt.prompt.as_deref(),
+            prompt_file: prompt.prompt_file.as_deref(),
+            prompt_editor: prompt.prompt_editor,
+            agent_given: !multi.agent.is_empty(),
+            layout_given: layout.is_some(),
+            sandbox: setup.sandbox,
+            config_override: config.as_deref(),
+        })?),
+        _ => None,
+    };
+    let cli = match answers {
+        Some(Some(answers)) => Cli::try_parse_from(
+            std::env::args()
+                .filter(|arg| arg != "-i" && arg != "--interactive")
+                .chain(answers),
+        )?,
+        Some(None) => return Ok(()),
+        None => cli,
+    };
+
     match cli.command {
         Commands::Add {
             branch_name,
+            interactive: _,
             pr,
             auto_name,
+            ticket,
             base,
             name,
             prompt,
@@ -930,6 +1368,7 @@ pub fn run() -> Result<()> {
                 branch_name.as_deref(),
                 pr,
                 auto_name,
+                ticket.as_deref(),
                 base.as_deref(),
                 name,
                 prompt,
@@ -951,6 +1390,8 @@ pub fn run() -> Result<()> {
             mode,
             session,
             continue_session,
+            resume_session,
+            layout,
             prompt,
             config,
         } => {
@@ -964,6 +1405,8 @@ pub fn run() -> Result<()> {
                 new,
                 mode_override,
                 continue_session,
+                resume_session,
+                layout,
                 prompt,
                 config.as_deref(),
             )
@@ -980,6 +1423,8 @@ pub fn run() -> Result<()> {
             no_verify,
             no_hooks,
             notification,
+            porcelain,
+            approve,
         } => command::merge::run(
             name.as_deref(),
             into.as_deref(),
@@ -990,6 +1435,8 @@ pub fn run() -> Result<()> {
             no_verify,
             no_hooks,
             notification,
+            porcelain,
+            approve,
         ),
         Commands::Remove {
             names,
@@ -999,35 +1446,128 @@ pub fn run() -> Result<()> {
             keep_branch,
         } => command::remove::run(names, gone, all, force, keep_branch),
         Commands::Rename { names, branch } => command::rename::run(names, branch),
-        Commands::List { pr, json, filter } => command::list::run(pr, json, &filter),
+        Commands::List {
+            pr,
+            json,
+            du,
+            filter,
+        } => command::list::run(pr, json, du, &filter),
         Commands::Path { name } => command::path::run(&name),
         Commands::Send { name, text, file } => {
-            command::send::run(&name, text.as_deref(), file.as_deref())
+            command::send::run(name.as_deref(), text.as_deref(), file.as_deref())
         }
         Commands::Capture { name, lines } => command::capture::run(&name, lines),
+        Commands::Peek { name } => command::peek::run(&name),
         Commands::Status {
             worktrees,
             json,
             git,
-        } => command::status::run(&worktrees, json, git),
+            watch,
+            interval,
+        } => {
+            if watch {
+                let interval = std::time::Duration::from_secs(interval.max(1));
+                command::status::watch(&worktrees, git, interval)
+            } else {
+                command::status::run(&worktrees, json, git)
+            }
+        }
         Commands::Wait {
             worktrees,
+            all,
             status,
             timeout,
             any,
-        } => command::wait::run(&worktrees, &status, timeout, any),
+            porcelain,
+        } => command::wait::run(&worktrees, all, &status, timeout, any, porcelain),
+        Commands::Watch {
+            projects,
+            interval,
+            debounce,
+        } => command::watch::run(&projects, interval, debounce),
+        Commands::Serve { port, bind } => command::serve::run(port, bind),
+        Commands::PromptSegment => command::prompt_segment::run(),
+        Commands::Digest { hours, send } => command::digest::run(hours, send),
+        Commands::Report {
+            week,
+            since,
+            json,
+            markdown,
+            summarize,
+        } => command::report::run(week, since.as_deref(), json, markdown, summarize),
+        Commands::Focus { path } => {
+            crate::state::activity::record(&path, crate::state::activity::ActivityKind::Focus);
+            Ok(())
+        }
         Commands::Run {
             name,
             command,
+            all: _,
+            jobs,
+            env,
+            env_file,
+            host,
+            watch,
+            status_icon,
+            on_fail,
+            notify,
             background,
+            wait: _,
+            json,
+            porcelain,
             keep,
             timeout,
-        } => command::run::run(&name, command, background, keep, timeout),
+        } => {
+            let env = command::run::collect_env(env, &env_file)?;
+            match name {
+                Some(name) => command::run::run(
+                    &name,
+                    command,
+                    command::run::RunOptions {
+                        env,
+                        host,
+                        background,
+                        keep,
+                        timeout,
+                        watch,
+                        status_icon,
+                        on_fail,
+                        notify,
+                        json,
+                        porcelain,
+                    },
+                ),
+                None => command::run_all::run(command, env, jobs, timeout),
+            }
+        }
+        Commands::Runs(args) => command::runs::run(args),
+        Commands::Session(args) => command::session::run(args),
+        Commands::Prompts(args) => command::prompts::run(args),
         Commands::Exec { run_dir } => command::exec::run(&run_dir),
+        Commands::WithSecrets {
+            command: shell_command,
+        } => command::with_secrets::run(&shell_command),
         Commands::SyncFiles { all } => command::sync_files::run(all),
-        Commands::Init => crate::config::Config::init(),
+        Commands::Init => command::init::run(),
         Commands::Setup { hooks, skills } => command::setup::run(hooks, skills),
-        Commands::Docs => command::docs::run(),
+        Commands::Doctor => command::doctor::run(),
+        Commands::Logs {
+            follow,
+            since,
+            lines,
+        } => command::logs::run(follow, since.as_deref(), lines),
+        Commands::Audit { revert } => command::audit::run(&revert),
+        Commands::Docs { action } => match action {
+            Some(DocsAction::Generate { man, .. }) => command::docs::generate(
+                Cli::command(),
+                if man {
+                    command::docs::Format::Man
+                } else {
+                    command::docs::Format::Markdown
+                },
+            ),
+            None => command::docs::run(),
+        },
         Commands::Changelog => command::changelog::run(),
         Commands::Update => command::update::run(),
         Commands::Sidebar { session, action } => match action {
@@ -1057,13 +1597,31 @@ pub fn run() -> Result<()> {
             diff,
             session,
             tab,
-        } => command::dashboard::run(preview_size, diff, session, tab),
+            popup,
+            json,
+        } => command::dashboard::run(preview_size, diff, session, tab, popup, json),
         Commands::Config(args) => command::config::run(args),
         Commands::Claude { command } => match command {
             ClaudeCommands::Prune => prune_claude_config(),
         },
         Commands::Sandbox(args) => command::sandbox::run(args),
-        Commands::SetWindowStatus { command } => command::set_window_status::run(command),
+        Commands::SetWindowStatus { command, reason } => {
+            command::set_window_status::run(command, reason)
+        }
+        Commands::Escalate {
+            backend,
+            instance,
+            pane,
+            since,
+        } => crate::notification::escalation::run(
+            crate::state::PaneKey {
+                backend,
+                instance,
+                pane_id: pane,
+            },
+            since,
+        ),
+        Commands::SessionStarted { id } => command::session_started::run(id),
         Commands::SetBase { base } => command::set_base::run(&base),
         Commands::LastDone => command::last_done::run(),
         Commands::LastAgent => command::last_agent::run(),
@@ -1082,27 +1640,15 @@ pub fn run() -> Result<()> {
             generate_completions(shell);
             Ok(())
         }
-        Commands::CompleteBranches => {
-            for branch in WorktreeBranchParser::new().get_branches() {
-                println!("{branch}");
-            }
-            Ok(())
-        }
-        Commands::CompleteHandles => {
-            for handle in WorktreeHandleParser::get_handles() {
-                println!("{handle}");
-            }
-            Ok(())
-        }
-        Commands::CompleteGitBranches => {
-            for branch in GitBranchParser::get_branches() {
-                println!("{branch}");
-            }
-            Ok(())
-        }
-        Commands::CompleteAgentTargets => {
-            for target in AgentTargetParser::get_targets() {
-                println!("{target}");
+        Commands::Complete { kind } => {
+            let candidates = match kind {
+                CompletionKind::Handles => WorktreeHandleParser::get_handles(),
+                CompletionKind::Branches => WorktreeBranchParser::new().get_branches(),
+                CompletionKind::GitBranches => GitBranchParser::get_branches(),
+                CompletionKind::AgentTargets => AgentTargetParser::get_targets(),
+            };
+            for candidate in candidates {
+                println!("{candidate}");
             }
             Ok(())
         }
@@ -1406,4 +1952,61 @@ mod tests {
         assert!(output.contains("__workmux_handles"));
         assert!(output.contains("__workmux_git_branches"));
     }
+
+    #[test]
+    fn dynamic_scripts_call_valid_complete_kinds() {
+        let scripts = [
+            include_str!("scripts/completions/bash_dynamic.bash"),
+            include_str!("scripts/completions/zsh_dynamic.zsh"),
+            include_str!("scripts/completions/fish_dynamic.fish"),
+        ];
+        for script in scripts {
+            let mut calls = 0;
+            for (i, _) in script.match_indices("workmux _complete ") {
+                let args: Vec<&str> = script[i..].split_whitespace().take(3).collect();
+                // Skip the `_complete <kind>` mention in the header comment
+                if args[2].starts_with('<') {
+                    continue;
+                }
+                assert!(
+                    Cli::try_parse_from(&args).is_ok(),
+                    "invalid completion call: {}",
+                    args.join(" ")
+                );
+                calls += 1;
+            }
+            assert!(calls >= 3);
+            assert!(!script.contains("_complete-"));
+        }
+    }
+
+    #[test]
+    fn remote_host_only_allows_list() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).unwrap().command;
+        assert!(supports_remote_host(&parse(&[
+            "workmux", "--remote", "box", "list"
+        ])));
+        assert!(!supports_remote_host(&parse(&[
+            "workmux", "status", "--remote", "box"
+        ])));
+        assert!(!supports_remote_host(&parse(&[
+            "workmux",
+            "--remote",
+            "box",
+            "dashboard"
+        ])));
+        assert!(!supports_remote_host(&parse(&[
+            "workmux", "--remote", "box", "add", "feat"
+        ])));
+        assert!(!supports_remote_host(&parse(&[
+            "workmux", "--remote", "box", "merge"
+        ])));
+    }
+
+    #[test]
+    fn run_host_does_not_clash_with_global_remote() {
+        let cli = Cli::try_parse_from(["workmux", "run", "wt", "--host", "--", "ls"]).unwrap();
+        assert!(cli.remote.is_none());
+        assert!(matches!(cli.command, Commands::Run { host: true, .. }));
+    }
 }
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result, anyhow};
 use std::path::Path;
-use std::process::{Command, Output};
+use std::process::{Child, Command, Output, Stdio};
 use tracing::{debug, trace};
 
+use crate::timings::{self, Phase, Timer};
+use crate::transport;
+
 /// A builder for executing shell commands with unified error handling
 pub struct Cmd<'a> {
     command: &'a str,
@@ -41,6 +44,13 @@ impl<'a> Cmd<'a> {
     /// Execute the command and return the output
     /// Returns an error if the command fails (non-zero exit code)
     pub fn run(self) -> Result<Output> {
+        self.spawn()?.wait()
+    }
+
+    /// Start the command without waiting for it to finish. Independent
+    /// commands can be started together and then waited on, so their
+    /// latencies overlap instead of adding up.
+    pub fn spawn(self) -> Result<Pending<'a>> {
         let Cmd {
             command,
             args,
@@ -50,11 +60,76 @@ impl<'a> Cmd<'a> {
 
         trace!(command, args = ?args, workdir = ?workdir_display, "cmd:run start");
 
-        let mut cmd = Command::new(command);
-        if let Some(dir) = workdir {
-            cmd.current_dir(dir);
-        }
-        let output = cmd.args(&args).output().with_context(|| {
+        let timer = phase_timer(command);
+        let child = transport::current()
+            .command(command, &args, workdir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!("Failed to execute command: {} {}", command, args.join(" "))
+            })?;
+
+        Ok(Pending {
+            command,
+            args,
+            child,
+            _timer: timer,
+        })
+    }
+
+    /// Execute the command and return stdout as a trimmed string
+    pub fn run_and_capture_stdout(self) -> Result<String> {
+        let output = self.run()?;
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    }
+
+    /// Execute the command, returning Ok(true) if it succeeds, Ok(false) if it fails
+    /// This is useful for commands that are used as checks (e.g., git rev-parse --verify)
+    pub fn run_as_check(self) -> Result<bool> {
+        let Cmd {
+            command,
+            args,
+            workdir,
+        } = self;
+        let workdir_display = workdir.map(|p| p.display().to_string());
+        trace!(command, args = ?args, workdir = ?workdir_display, "cmd:check start");
+        let _timer = phase_timer(command);
+
+        let output = transport::current()
+            .command(command, &args, workdir)
+            .output()
+            .with_context(|| {
+                format!("Failed to execute command: {} {}", command, args.join(" "))
+            })?;
+
+        let success = output.status.success();
+        trace!(command, success, "cmd:check result");
+        Ok(success)
+    }
+}
+
+/// A command started by [`Cmd::spawn`]. Call `wait` to collect its output.
+pub struct Pending<'a> {
+    command: &'a str,
+    args: Vec<&'a str>,
+    child: Child,
+    /// Stops when the output has been collected
+    _timer: Timer,
+}
+
+impl Pending<'_> {
+    /// Wait for the command and return the output
+    /// Returns an error if the command fails (non-zero exit code)
+    pub fn wait(self) -> Result<Output> {
+        let Pending {
+            command,
+            args,
+            child,
+            _timer,
+        } = self;
+        let output = child.wait_with_output().with_context(|| {
             format!("Failed to execute command: {} {}", command, args.join(" "))
         })?;
 
@@ -78,34 +153,18 @@ impl<'a> Cmd<'a> {
         Ok(output)
     }
 
-    /// Execute the command and return stdout as a trimmed string
-    pub fn run_and_capture_stdout(self) -> Result<String> {
-        let output = self.run()?;
+    /// Wait for the command and return stdout as a trimmed string
+    pub fn wait_for_stdout(self) -> Result<String> {
+        let output = self.wait()?;
         Ok(String::from_utf8(output.stdout)?.trim().to_string())
     }
+}
 
-    /// Execute the command, returning Ok(true) if it succeeds, Ok(false) if it fails
-    /// This is useful for commands that are used as checks (e.g., git rev-parse --verify)
-    pub fn run_as_check(self) -> Result<bool> {
-        let Cmd {
-            command,
-            args,
-            workdir,
-        } = self;
-        let workdir_display = workdir.map(|p| p.display().to_string());
-        trace!(command, args = ?args, workdir = ?workdir_display, "cmd:check start");
-
-        let mut cmd = Command::new(command);
-        if let Some(dir) = workdir {
-            cmd.current_dir(dir);
-        }
-        let output = cmd.args(&args).output().with_context(|| {
-            format!("Failed to execute command: {} {}", command, args.join(" "))
-        })?;
-
-        let success = output.status.success();
-        trace!(command, success, "cmd:check result");
-        Ok(success)
+/// Time a command under `--timings`, if its program belongs to a phase.
+fn phase_timer(program: &str) -> Timer {
+    match Phase::for_program(program) {
+        Some(phase) => timings::phase(phase),
+        None => Timer::none(),
     }
 }
 
@@ -115,7 +174,7 @@ pub fn shell_command_with_env(
     workdir: &Path,
     env_vars: &[(&str, &str)],
 ) -> Result<()> {
-    let mut cmd = Command::new("bash");
+    let mut cmd = Command::new(crate::platform::bash());
     cmd.arg("-c").arg(command).current_dir(workdir);
 
     for (key, value) in env_vars {
@@ -135,3 +194,36 @@ pub fn shell_command_with_env(
     }
     Ok(())
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawned_commands_overlap() {
+        let started = std::time::Instant::now();
+        let pending: Vec<_> = (0..4)
+            .map(|_| {
+                Cmd::new("sh")
+                    .args(&["-c", "sleep 0.5; echo done"])
+                    .spawn()
+                    .unwrap()
+            })
+            .collect();
+        for p in pending {
+            assert_eq!(p.wait_for_stdout().unwrap(), "done");
+        }
+        assert!(started.elapsed() < std::time::Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn pending_wait_reports_failure() {
+        let err = Cmd::new("sh")
+            .args(&["-c", "echo oops >&2; exit 3"])
+            .spawn()
+            .unwrap()
+            .wait()
+            .unwrap_err();
+        assert!(err.to_string().contains("oops"));
+    }
+}
@@ -9,7 +9,9 @@ use crate::template::{
 };
 use crate::workflow::SetupOptions;
 use crate::workflow::pr::detect_remote_branch;
-use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt, parse_prompt_with_frontmatter};
+use crate::workflow::prompt_loader::{
+    PromptLoadArgs, load_prompt, parse_prompt_with_frontmatter, parse_vars,
+};
 use crate::{config, git, workflow};
 use anyhow::{Context, Result, anyhow, bail};
 use serde_json::Value;
@@ -34,14 +36,37 @@ fn generate_branch_name_with_spinner(
     config: &config::Config,
 ) -> Result<String> {
     let prompt_text = prompt_text.ok_or_else(|| anyhow!("Prompt is required for --auto-name"))?;
+    let generated = suggest_branch_name(prompt_text, config)?;
+    println!("  Branch: {}", generated);
+
+    Ok(generated)
+}
 
+/// Generate a branch name for `prompt_text` with the configured auto-name
+/// command, showing a spinner while it runs.
+pub(super) fn suggest_branch_name(prompt_text: &str, config: &config::Config) -> Result<String> {
     let model = config.auto_name.as_ref().and_then(|c| c.model.as_deref());
     let system_prompt = config
         .auto_name
         .as_ref()
         .and_then(|c| c.system_prompt.as_deref());
 
-    // Resolve effective command: explicit config > agent profile > None (llm fallback)
+    let effective_command = generator_command(config);
+
+    // Extract program name from effective command for spinner message
+    let program_name = effective_command
+        .and_then(|cmd| cmd.split_whitespace().next())
+        .unwrap_or("llm");
+    let spinner_msg = format!("Generating branch name with {}", program_name);
+
+    spinner::with_spinner(&spinner_msg, || {
+        crate::llm::generate_branch_name(prompt_text, model, system_prompt, effective_command)
+    })
+}
+
+/// The command that generates text for `auto_name` (and report summaries):
+/// explicit config, then the agent profile's, then None for the `llm` CLI.
+pub(super) fn generator_command(config: &config::Config) -> Option<&str> {
     let config_command = config
         .auto_name
         .as_ref()
@@ -59,19 +84,7 @@ fn generate_branch_name_with_spinner(
         agent = config.agent.as_deref().unwrap_or("none"),
         "resolved auto-name command"
     );
-
-    // Extract program name from effective command for spinner message
-    let program_name = effective_command
-        .and_then(|cmd| cmd.split_whitespace().next())
-        .unwrap_or("llm");
-    let spinner_msg = format!("Generating branch name with {}", program_name);
-
-    let generated = spinner::with_spinner(&spinner_msg, || {
-        crate::llm::generate_branch_name(prompt_text, model, system_prompt, effective_command)
-    })?;
-    println!("  Branch: {}", generated);
-
-    Ok(generated)
+    effective_command
 }
 
 /// Check for and read lines from stdin if available.
@@ -128,37 +141,12 @@ fn check_preconditions() -> Result<()> {
     Err(anyhow!(errors.join("\n")))
 }
 
-/// Resolve a named layout by replacing `config.panes` with the layout's panes.
-fn resolve_layout(config: &mut config::Config, layout_name: &str) -> Result<()> {
-    let layouts = config.layouts.as_ref().ok_or_else(|| {
-        anyhow!(
-            "Layout '{}' requested but no layouts are defined in config",
-            layout_name
-        )
-    })?;
-    let layout = layouts.get(layout_name).ok_or_else(|| {
-        let mut available: Vec<_> = layouts.keys().collect();
-        available.sort();
-        anyhow!(
-            "Layout '{}' not found. Available layouts: {}",
-            layout_name,
-            available
-                .iter()
-                .map(|s| s.as_str())
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
-    })?;
-    config.panes = Some(layout.panes.clone());
-    config.windows = None; // Layout overrides session windows
-    Ok(())
-}
-
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     branch_name: Option<&str>,
     pr: Option<u32>,
     auto_name: bool,
+    ticket: Option<&str>,
     base: Option<&str>,
     name: Option<String>,
     prompt_args: PromptArgs,
@@ -182,6 +170,12 @@ pub fn run(
         if config_override.is_some() {
             bail!("--config is not supported from inside a sandbox");
         }
+        if ticket.is_some() {
+            bail!("--ticket is not supported from inside a sandbox");
+        }
+        if prompt_args.prompt_template.is_some() || !prompt_args.vars.is_empty() {
+            bail!("--prompt-template and --var are not supported from inside a sandbox");
+        }
         return run_add_via_rpc(
             branch_name,
             auto_name,
@@ -212,8 +206,9 @@ pub fn run(
     // Resolve fork source if --fork is set
     let fork_source = if let Some(ref fork_arg) = fork {
         let agent_name = initial_config.agent.as_deref().unwrap_or("claude");
-        let forker =
-            crate::multiplexer::conversation::resolve_forker(agent_name).ok_or_else(|| {
+        let forker = crate::multiplexer::conversation::resolve_forker(agent_name)
+            .filter(|forker| forker.can_fork())
+            .ok_or_else(|| {
                 anyhow!(
                     "Agent '{}' does not support conversation forking",
                     agent_name
@@ -252,7 +247,7 @@ pub fn run(
 
     // Validate layout early to fail fast before any LLM calls
     if let Some(layout_name) = &layout {
-        resolve_layout(&mut initial_config, layout_name)?;
+        initial_config.apply_layout(layout_name)?;
     }
 
     // Construct setup options from flags
@@ -260,6 +255,7 @@ pub fn run(
     options.focus_window = !setup.background;
     options.open_if_exists = setup.open_if_exists;
     options.mode = mode;
+    options.layout = layout.clone();
 
     // If using --auto-name and config has auto_name.background = true, run in background
     if auto_name && options.focus_window {
@@ -277,6 +273,9 @@ pub fn run(
         }
     }
 
+    // Prompt template variables from --var, available to every prompt
+    let prompt_vars = parse_vars(&prompt_args.vars)?;
+
     // Detect stdin input early
     let stdin_lines = read_stdin_lines()?;
     let has_stdin = !stdin_lines.is_empty();
@@ -285,12 +284,17 @@ pub fn run(
     let is_explicit_multi =
         has_stdin || multi.foreach.is_some() || multi.count.is_some() || multi.agent.len() > 1;
 
+    // Ticket key as the tracker reports it, recorded on the created branches
+    let mut ticket_key = None;
+
     // Handle auto-name: load prompt first, generate branch name
     // In multi-worktree mode with auto-name, we defer LLM generation to the loop
-    let (final_branch_name, preloaded_prompt, remote_branch_for_pr, deferred_auto_name) =
+    let (final_branch_name, preloaded_prompt, pr_checkout_resolved, deferred_auto_name) =
         if auto_name {
             // Use editor if no prompt source specified, otherwise use provided source
-            let use_editor = prompt_args.prompt.is_none() && prompt_args.prompt_file.is_none();
+            let use_editor = prompt_args.prompt.is_none()
+                && prompt_args.prompt_file.is_none()
+                && prompt_args.prompt_template.is_none();
 
             // Cannot use interactive editor when stdin is piped (editor can't read terminal)
             if has_stdin && (prompt_args.prompt_editor || use_editor) {
@@ -304,6 +308,7 @@ pub fn run(
                 prompt_editor: use_editor || prompt_args.prompt_editor,
                 prompt_inline: prompt_args.prompt.as_deref(),
                 prompt_file: prompt_args.prompt_file.as_ref(),
+                prompt_template: prompt_args.prompt_template.as_deref(),
             })?
             .ok_or_else(|| anyhow!("Prompt is required for --auto-name"))?;
 
@@ -328,7 +333,32 @@ pub fn run(
         } else if let Some(pr_number) = pr {
             // Handle PR checkout if --pr flag is provided
             let result = workflow::pr::resolve_pr_ref(pr_number, branch_name)?;
-            (result.local_branch, None, Some(result.remote_branch), false)
+            (result.local_branch.clone(), None, Some(result), false)
+        } else if let Some(key) = ticket {
+            // Name the branch after the ticket and seed the prompt with it,
+            // followed by any prompt given on the command line
+            let tracker = &initial_config.tracker;
+            let fetched = spinner::with_spinner(&format!("Fetching {}", key), || {
+                crate::tracker::fetch(tracker, key)
+            })?;
+            let branch = match branch_name {
+                Some(branch) => branch.to_string(),
+                None => crate::tracker::branch_name(tracker, &fetched)?,
+            };
+            println!("  Ticket: {} {}", fetched.key, fetched.title);
+            println!("  Branch: {}", branch);
+            let mut text = crate::tracker::prompt(tracker, &fetched)?;
+            if let Some(extra) = load_prompt(&PromptLoadArgs {
+                prompt_editor: prompt_args.prompt_editor,
+                prompt_inline: prompt_args.prompt.as_deref(),
+                prompt_file: prompt_args.prompt_file.as_ref(),
+                prompt_template: prompt_args.prompt_template.as_deref(),
+            })? {
+                text.push_str("\n\n");
+                text.push_str(extra.read_content()?.trim());
+            }
+            ticket_key = Some(fetched.key);
+            (branch, Some(Prompt::Inline(text)), None, false)
         } else {
             // Normal flow: use provided branch name
             (
@@ -343,7 +373,7 @@ pub fn run(
 
     // Use the determined branch name and override base if from PR
     let branch_name = &final_branch_name;
-    let cli_base = if remote_branch_for_pr.is_some() {
+    let cli_base = if pr_checkout_resolved.is_some() {
         None
     } else {
         base
@@ -379,7 +409,7 @@ pub fn run(
             rescue_config.sandbox.enabled = Some(true);
         }
         if let Some(layout_name) = &layout {
-            resolve_layout(&mut rescue_config, layout_name)?;
+            rescue_config.apply_layout(layout_name)?;
         }
         let mux = create_backend(detect_backend());
         let rescue_context = workflow::WorkflowContext::new(rescue_config, mux, rescue_location)?;
@@ -406,14 +436,17 @@ pub fn run(
             prompt_editor: prompt_args.prompt_editor,
             prompt_inline: prompt_args.prompt.as_deref(),
             prompt_file: prompt_args.prompt_file.as_ref(),
+            prompt_template: prompt_args.prompt_template.as_deref(),
         })?
     };
 
     // Parse prompt document to extract frontmatter (if applicable)
     let prompt_doc = if let Some(ref prompt_src) = prompt_template {
         // Account for implicit editor usage triggered by auto_name
-        let implicit_editor =
-            auto_name && prompt_args.prompt.is_none() && prompt_args.prompt_file.is_none();
+        let implicit_editor = auto_name
+            && prompt_args.prompt.is_none()
+            && prompt_args.prompt_file.is_none()
+            && prompt_args.prompt_template.is_none();
         let from_editor_or_file = prompt_args.prompt_editor
             || implicit_editor
             || matches!(prompt_src, Prompt::FromFile(_));
@@ -425,6 +458,36 @@ pub fn run(
         None
     };
 
+    // Frontmatter can describe the worktree's setup; command-line flags win
+    let meta = prompt_doc.as_ref().map(|doc| &doc.meta);
+    let cli_base = match meta.and_then(|m| m.base.as_deref()) {
+        Some(base) if cli_base.is_none() && pr_checkout_resolved.is_none() => Some(base),
+        _ => cli_base,
+    };
+    let layout = layout.or_else(|| meta.and_then(|m| m.layout.clone()));
+    if let Some(layout_name) = &layout {
+        initial_config.apply_layout(layout_name)?;
+    }
+    options.layout = layout.clone();
+    let sandbox = if sandbox_override {
+        Some(true)
+    } else {
+        meta.and_then(|m| m.sandbox)
+    };
+    let host_commands = meta.and_then(|m| m.host_commands.as_deref());
+    if let Some(subset) = host_commands {
+        let denied = initial_config
+            .sandbox
+            .clone()
+            .restrict_host_commands(subset);
+        if !denied.is_empty() {
+            eprintln!(
+                "Warning: host_commands not in your global config are ignored: {}",
+                denied.join(", ")
+            );
+        }
+    }
+
     // Validate multi-worktree arguments
     if multi.count.is_some() && multi.agent.len() > 1 {
         return Err(anyhow!(
@@ -452,12 +515,20 @@ pub fn run(
     // If we have a PR remote branch, use that; otherwise detect from branch_name
     // Only pass CLI --base to detect_remote_branch; config base_branch should not
     // interfere with remote/fork branch detection.
-    let (remote_branch, template_base_name) = if let Some(ref pr_remote) = remote_branch_for_pr {
-        (Some(pr_remote.clone()), branch_name.to_string())
+    let (remote_branch, template_base_name) = if let Some(ref pr) = pr_checkout_resolved {
+        (Some(pr.remote_branch.clone()), branch_name.to_string())
     } else {
         detect_remote_branch(branch_name, cli_base)?
     };
-    let resolved_base = if remote_branch.is_some() {
+    let is_fork = match pr_checkout_resolved {
+        Some(ref pr) => pr.is_fork,
+        None => git::parse_fork_branch_spec(branch_name).is_some(),
+    };
+    // For remote checkouts the base is not a starting point; for PRs it is the
+    // branch the PR targets, which fork checkouts track for pulls and merges.
+    let resolved_base = if let Some(ref pr) = pr_checkout_resolved {
+        pr.base_branch.as_deref()
+    } else if remote_branch.is_some() {
         None
     } else {
         cli_base.or(config_base)
@@ -468,7 +539,7 @@ pub fn run(
         determine_foreach_matrix(&multi, prompt_doc.as_ref(), stdin_lines)?;
 
     // Generate worktree specifications
-    let specs = generate_worktree_specs(
+    let mut specs = generate_worktree_specs(
         &template_base_name,
         &multi.agent,
         multi.count,
@@ -476,6 +547,20 @@ pub fn run(
         &env,
         &multi.branch_template,
     )?;
+    let frontmatter_agent = meta.and_then(|m| m.agent.as_deref());
+    for spec in &mut specs {
+        if spec.agent.is_none()
+            && let Some(agent) = frontmatter_agent
+        {
+            spec.agent = Some(agent.to_string());
+            spec.template_context["agent"] = Value::String(agent.to_string());
+        }
+        if let Value::Object(context) = &mut spec.template_context {
+            for (key, value) in &prompt_vars {
+                context.insert(key.clone(), Value::String(value.clone()));
+            }
+        }
+    }
 
     if specs.is_empty() {
         return Err(anyhow!("No worktree specifications were generated"));
@@ -500,6 +585,7 @@ pub fn run(
         resolved_base,
         remote_branch: remote_branch.as_deref(),
         pr_number: pr,
+        is_fork,
         prompt_doc: prompt_doc.as_ref(),
         options,
         mode_override,
@@ -508,13 +594,24 @@ pub fn run(
         wait,
         deferred_auto_name,
         max_concurrent: multi.max_concurrent,
-        sandbox_override,
+        sandbox,
+        host_commands,
         prompt_file_only,
         layout: layout.as_deref(),
         fork_source,
         config_override,
     };
-    plan.execute()
+    plan.execute()?;
+
+    // Remember the ticket so it gets a link once the branch has a pull request
+    if let Some(key) = &ticket_key {
+        for spec in &specs {
+            if let Err(e) = git::set_branch_ticket(&spec.branch_name, key) {
+                tracing::warn!(branch = %spec.branch_name, error = %e, "add:failed to record ticket");
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Handle the rescue flow (--with-changes).
@@ -634,6 +731,7 @@ struct CreationPlan<'a> {
     resolved_base: Option<&'a str>,
     remote_branch: Option<&'a str>,
     pr_number: Option<u32>,
+    is_fork: bool,
     prompt_doc: Option<&'a PromptDocument>,
     options: SetupOptions,
     mode_override: Option<MuxMode>,
@@ -642,7 +740,10 @@ struct CreationPlan<'a> {
     wait: bool,
     deferred_auto_name: bool,
     max_concurrent: Option<u32>,
-    sandbox_override: bool,
+    /// Run in the sandbox (Some(true)) or on the host (Some(false)),
+    /// overriding config
+    sandbox: Option<bool>,
+    host_commands: Option<&'a [String]>,
     prompt_file_only: bool,
     layout: Option<&'a str>,
     fork_source: Option<crate::workflow::types::ForkSource>,
@@ -694,13 +795,16 @@ impl<'a> CreationPlan<'a> {
             // Load config for this specific agent to ensure correct agent resolution
             let (mut config, config_location) =
                 config::Config::load_with_location(spec.agent.as_deref(), self.config_override)?;
-            if self.sandbox_override {
-                config.sandbox.enabled = Some(true);
+            if let Some(enabled) = self.sandbox {
+                config.sandbox.enabled = Some(enabled);
+            }
+            if let Some(subset) = self.host_commands {
+                config.sandbox.restrict_host_commands(subset);
             }
 
             // Resolve layout: replace top-level panes with layout's panes
             if let Some(layout_name) = self.layout {
-                resolve_layout(&mut config, layout_name)?;
+                config.apply_layout(layout_name)?;
             }
 
             // Render prompt first (needed for deferred auto-name)
@@ -748,12 +852,12 @@ impl<'a> CreationPlan<'a> {
                     .as_deref()
                     .or(config.agent.as_deref())
                     .unwrap_or("claude");
-                crate::multiplexer::conversation::resolve_forker(agent_name).map(|forker| {
-                    crate::workflow::types::ForkSource {
+                crate::multiplexer::conversation::resolve_forker(agent_name)
+                    .filter(|forker| forker.can_fork())
+                    .map(|forker| crate::workflow::types::ForkSource {
                         forker,
                         session: fork.session.clone(),
-                    }
-                })
+                    })
             } else {
                 None
             };
@@ -769,6 +873,7 @@ impl<'a> CreationPlan<'a> {
                     base_branch: self.resolved_base,
                     remote_branch: self.remote_branch,
                     pr_number: self.pr_number,
+                    is_fork: self.is_fork,
                     prompt: prompt_for_spec.as_ref(),
                     options: self.options.clone(),
                     mode_override,
@@ -776,6 +881,7 @@ impl<'a> CreationPlan<'a> {
                     is_explicit_name: self.explicit_name.is_some(),
                     prompt_file_only: self.prompt_file_only,
                     fork_source: fork_for_spec,
+                    host_commands: self.host_commands,
                 },
             )
             .with_context(|| {
@@ -801,11 +907,18 @@ impl<'a> CreationPlan<'a> {
                 println!("✓ Setup complete");
             }
 
-            println!(
-                "✓ Successfully created worktree and tmux {} for '{}'",
-                mode_label(mode),
-                result.branch_name
-            );
+            if mux.is_headless() {
+                println!(
+                    "✓ Successfully created worktree for '{}' (no multiplexer)",
+                    result.branch_name
+                );
+            } else {
+                println!(
+                    "✓ Successfully created worktree and tmux {} for '{}'",
+                    mode_label(mode),
+                    result.branch_name
+                );
+            }
             if let Some(ref base) = result.base_branch {
                 println!("  Base: {}", base);
             }
@@ -890,6 +1003,7 @@ fn run_add_via_rpc(
         prompt_editor: prompt_args.prompt_editor,
         prompt_inline: prompt_args.prompt.as_deref(),
         prompt_file: prompt_args.prompt_file.as_ref(),
+        prompt_template: prompt_args.prompt_template.as_deref(),
     })?;
     let prompt_text = match prompt_content {
         Some(Prompt::Inline(text)) => Some(text),
@@ -0,0 +1,287 @@
+//! `workmux add -i`: ask for the new worktree's settings step by step.
+//!
+//! The wizard only asks what the command line didn't already decide, then
+//! turns the answers into `workmux add` flags. The command is parsed again
+//! with those flags, so it behaves exactly like the equivalent command line,
+//! which is printed for next time.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use console::style;
+use edit::Builder;
+
+use super::run::shell_escape;
+use crate::config::Config;
+use crate::git;
+
+/// What the command line already decided, so the wizard skips those questions.
+pub struct Preset<'a> {
+    pub base: Option<&'a str>,
+    pub prompt: Option<&'a str>,
+    pub prompt_file: Option<&'a Path>,
+    /// `-e` was given: the editor opens after the wizard
+    pub prompt_editor: bool,
+    pub agent_given: bool,
+    pub layout_given: bool,
+    pub sandbox: bool,
+    pub config_override: Option<&'a Path>,
+}
+
+/// Run the wizard. Returns the `workmux add` arguments for the answers, or
+/// `None` if the user cancelled.
+pub fn run(preset: &Preset) -> Result<Option<Vec<String>>> {
+    if !crate::interactive::can_prompt() {
+        bail!("workmux add --interactive requires an interactive terminal");
+    }
+    let config = Config::load_with_override(None, preset.config_override)?;
+    let mut args = Vec::new();
+
+    println!();
+    println!("  {}", style("New worktree").bold().cyan());
+    println!();
+
+    // The prompt comes first so it can suggest a branch name
+    let prompt_text = if let Some(prompt) = preset.prompt {
+        Some(prompt.to_string())
+    } else if let Some(path) = preset.prompt_file {
+        std::fs::read_to_string(path).ok()
+    } else if preset.prompt_editor {
+        None
+    } else if confirm("Write a prompt for the agent?", true)? {
+        let written = edit::edit_with_builder("", Builder::new().suffix(".md"))
+            .context("Failed to open editor or read content")?;
+        let written = written.trim().to_string();
+        if written.is_empty() {
+            println!("    {}", style("Empty prompt, starting without one").dim());
+            None
+        } else {
+            args.push(format!("--prompt={}", written));
+            Some(written)
+        }
+    } else {
+        None
+    };
+
+    let suggestion = prompt_text.as_deref().and_then(|text| {
+        super::add::suggest_branch_name(text, &config)
+            .inspect_err(|e| tracing::debug!(error = %e, "branch name suggestion failed"))
+            .ok()
+    });
+    let branch = loop {
+        let answer = ask("Branch name", suggestion.as_deref())?;
+        if !answer.is_empty() {
+            break answer;
+        }
+        println!("    {}", style("A branch name is required").dim());
+    };
+
+    if preset.base.is_none() {
+        let default_base = config
+            .base_branch
+            .clone()
+            .or_else(|| git::get_current_branch().ok());
+        let base = ask("Base branch", default_base.as_deref())?;
+        if !base.is_empty() && Some(&base) != default_base.as_ref() {
+            args.push(format!("--base={}", base));
+        }
+    }
+
+    let mut layout_chosen = preset.layout_given;
+    if !preset.layout_given
+        && !preset.agent_given
+        && let Some(layouts) = config.layouts.as_ref().filter(|l| !l.is_empty())
+    {
+        let mut options = vec!["default panes".to_string()];
+        let mut names: Vec<&String> = layouts.keys().collect();
+        names.sort();
+        options.extend(names.iter().map(|n| n.to_string()));
+        let choice = choose("Layout", &options, 0)?;
+        if choice > 0 {
+            args.push(format!("--layout={}", options[choice]));
+            layout_chosen = true;
+        }
+    }
+
+    // A layout brings its own panes, so it can't be combined with --agent
+    if !preset.agent_given && !layout_chosen {
+        let options = agent_options(&config);
+        if options.len() > 1 {
+            let default = config
+                .agent
+                .as_deref()
+                .and_then(|agent| options.iter().position(|o| o == agent))
+                .unwrap_or(0);
+            let choice = choose("Agent", &options, default)?;
+            if choice != default {
+                args.push(format!("--agent={}", options[choice]));
+            }
+        }
+    }
+
+    if !preset.sandbox
+        && !config.sandbox.is_enabled()
+        && confirm("Run the agent in a sandbox?", false)?
+    {
+        args.push("--sandbox".to_string());
+    }
+
+    args.push(branch);
+
+    println!();
+    println!(
+        "  {} {}",
+        style("Equivalent command:").dim(),
+        equivalent_command(&args)
+    );
+    if !confirm("Create the worktree?", true)? {
+        return Ok(None);
+    }
+    println!();
+    Ok(Some(args))
+}
+
+/// Agents to offer: named agents from the config, the configured default,
+/// and the built-in agents that are installed.
+fn agent_options(config: &Config) -> Vec<String> {
+    let mut options: Vec<String> = config.agents.keys().cloned().collect();
+    if let Some(agent) = &config.agent
+        && !options.contains(agent)
+    {
+        options.insert(0, agent.clone());
+    }
+    for name in crate::multiplexer::agent::profile_names() {
+        if !options.iter().any(|o| o == name) && which::which(name).is_ok() {
+            options.push(name.to_string());
+        }
+    }
+    options
+}
+
+/// The answers as a command line, with the prompt shown as `-e` so the
+/// line stays short.
+fn equivalent_command(args: &[String]) -> String {
+    let mut parts = vec!["workmux".to_string(), "add".to_string()];
+    for arg in args {
+        if arg.starts_with("--prompt=") {
+            parts.push("-e".to_string());
+        } else {
+            parts.push(shell_escape(arg));
+        }
+    }
+    parts.join(" ")
+}
+
+pub(super) fn ask(label: &str, default: Option<&str>) -> Result<String> {
+    match default {
+        Some(default) => print!("  {} {} ", label, style(format!("[{}]", default)).dim()),
+        None => print!("  {}: ", label),
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(if answer.is_empty() {
+        default.unwrap_or_default().to_string()
+    } else {
+        answer.to_string()
+    })
+}
+
+pub(super) fn confirm(message: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let prompt = format!(
+        "  {} {}{}{} ",
+        message,
+        style("[").bold().cyan(),
+        style(hint).bold(),
+        style("]").bold().cyan(),
+    );
+
+    loop {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        match input.trim().to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("    {}", style("Please enter y or n").dim()),
+        }
+    }
+}
+
+/// Pick one of `options` by number or name. Enter picks `default`.
+fn choose(label: &str, options: &[String], default: usize) -> Result<usize> {
+    println!("  {}", label);
+    for (i, option) in options.iter().enumerate() {
+        let marker = if i == default { "*" } else { " " };
+        println!("   {} {}) {}", style(marker).cyan(), i + 1, option);
+    }
+
+    loop {
+        print!("  {} ", style(format!("[{}]", default + 1)).dim());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        match parse_choice(input.trim(), options, default) {
+            Some(choice) => return Ok(choice),
+            None => println!(
+                "    {}",
+                style(format!("Enter a number from 1 to {}", options.len())).dim()
+            ),
+        }
+    }
+}
+
+fn parse_choice(input: &str, options: &[String], default: usize) -> Option<usize> {
+    if input.is_empty() {
+        return Some(default);
+    }
+    if let Ok(n) = input.parse::<usize>() {
+        return (1..=options.len()).contains(&n).then(|| n - 1);
+    }
+    options.iter().position(|o| o == input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Vec<String> {
+        vec![
+            "claude".to_string(),
+            "codex".to_string(),
+            "gemini".to_string(),
+        ]
+    }
+
+    #[test]
+    fn parse_choice_by_number_name_or_default() {
+        assert_eq!(parse_choice("", &options(), 1), Some(1));
+        assert_eq!(parse_choice("3", &options(), 0), Some(2));
+        assert_eq!(parse_choice("codex", &options(), 0), Some(1));
+        assert_eq!(parse_choice("0", &options(), 0), None);
+        assert_eq!(parse_choice("4", &options(), 0), None);
+        assert_eq!(parse_choice("vim", &options(), 0), None);
+    }
+
+    #[test]
+    fn equivalent_command_hides_prompt() {
+        let args = vec![
+            "--prompt=Fix the login form".to_string(),
+            "--base=release 1".to_string(),
+            "--sandbox".to_string(),
+            "fix-login".to_string(),
+        ];
+        assert_eq!(
+            equivalent_command(&args),
+            "workmux add -e '--base=release 1' --sandbox fix-login"
+        );
+    }
+}
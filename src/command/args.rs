@@ -3,22 +3,35 @@ use std::path::PathBuf;
 #[derive(clap::Args, Debug)]
 pub struct PromptArgs {
     /// Inline prompt text to store in the new worktree
-    #[arg(short = 'p', long, conflicts_with_all = ["prompt_file", "prompt_editor"])]
+    #[arg(short = 'p', long, conflicts_with_all = ["prompt_file", "prompt_editor", "prompt_template"])]
     pub prompt: Option<String>,
 
     /// Path to a file whose contents should be used as the prompt
     #[arg(
         short = 'P',
         long = "prompt-file",
-        conflicts_with_all = ["prompt", "prompt_editor"],
+        conflicts_with_all = ["prompt", "prompt_editor", "prompt_template"],
         value_hint = clap::ValueHint::FilePath
     )]
     pub prompt_file: Option<PathBuf>,
 
     /// Open $EDITOR to write the prompt
-    #[arg(short = 'e', long = "prompt-editor", conflicts_with_all = ["prompt", "prompt_file"])]
+    #[arg(short = 'e', long = "prompt-editor", conflicts_with_all = ["prompt", "prompt_file", "prompt_template"])]
     pub prompt_editor: bool,
 
+    /// Use a template from the prompt library (~/.config/workmux/prompts/<NAME>.md)
+    #[arg(
+        short = 'T',
+        long = "prompt-template",
+        value_name = "NAME",
+        conflicts_with_all = ["prompt", "prompt_file", "prompt_editor"]
+    )]
+    pub prompt_template: Option<String>,
+
+    /// Set a prompt template variable (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE")]
+    pub vars: Vec<String>,
+
     /// Write the prompt file without injecting it into agent commands.
     /// The prompt is written to .workmux/PROMPT-<branch>.md in the worktree,
     /// but no agent pane is required. Useful when your editor has an embedded
@@ -29,7 +42,10 @@ pub struct PromptArgs {
 
 impl PromptArgs {
     pub fn has_any(&self) -> bool {
-        self.prompt.is_some() || self.prompt_file.is_some() || self.prompt_editor
+        self.prompt.is_some()
+            || self.prompt_file.is_some()
+            || self.prompt_editor
+            || self.prompt_template.is_some()
     }
 }
 
@@ -0,0 +1,312 @@
+//! `workmux audit`: everything workmux has changed outside the repository,
+//! with `--revert <ITEM>` to undo one item.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::agent_setup::{self, Agent, StatusCheck};
+use crate::config::{Config, SandboxRuntime};
+use crate::sandbox::SandboxPaths;
+use crate::sandbox::lima::{LimaInstance, VM_PREFIX};
+use crate::state::store::StateStore;
+use crate::util::expand_worktree_dir;
+use crate::workflow::repos;
+use crate::{claude, skills, xdg};
+
+/// One change workmux made on the host.
+struct Item {
+    id: String,
+    what: String,
+    location: String,
+    undo: Undo,
+}
+
+enum Undo {
+    RemovePaths(Vec<PathBuf>),
+    Hooks(Agent),
+    ClaudeProjects(Vec<String>),
+    Vm(String),
+    Container {
+        handle: String,
+        name: String,
+        runtime: SandboxRuntime,
+    },
+}
+
+#[derive(Tabled)]
+struct ItemRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "WHAT")]
+    what: String,
+    #[tabled(rename = "WHERE")]
+    location: String,
+}
+
+pub fn run(revert: &[String]) -> Result<()> {
+    let items = collect();
+
+    if revert.is_empty() {
+        if items.is_empty() {
+            println!("workmux has not modified anything outside your repositories");
+            return Ok(());
+        }
+        let rows: Vec<ItemRow> = items
+            .into_iter()
+            .map(|item| ItemRow {
+                id: item.id,
+                what: item.what,
+                location: item.location,
+            })
+            .collect();
+        let mut table = Table::new(rows);
+        table
+            .with(Style::blank())
+            .modify(Columns::new(0..3), Padding::new(0, 1, 0, 0));
+        println!("{table}");
+        println!("\nUndo an item with: workmux audit --revert <ID>");
+        return Ok(());
+    }
+
+    let selected = revert
+        .iter()
+        .map(|id| {
+            items.iter().find(|item| item.id == *id).with_context(|| {
+                format!("No audit item '{}' (run `workmux audit` to list them)", id)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for item in &selected {
+        println!("  {}  {} ({})", item.id, item.what, item.location);
+    }
+    if !crate::interactive::is_enabled() {
+        if !crate::interactive::can_prompt() {
+            bail!("Refusing to revert without confirmation; pass --yes");
+        }
+        print!("Revert {}? [y/N] ", plural(selected.len(), "item"));
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut failed = 0;
+    for item in selected {
+        match undo(&item.undo) {
+            Ok(msg) => println!("✓ {}: {}", item.id, msg),
+            Err(e) => {
+                eprintln!("✗ {}: {:#}", item.id, e);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{} could not be reverted", plural(failed, "item"));
+    }
+    Ok(())
+}
+
+fn collect() -> Vec<Item> {
+    let mut items = Vec::new();
+
+    for check in agent_setup::check_all() {
+        if matches!(check.status, StatusCheck::Installed)
+            && let Some(path) = agent_setup::hooks_path(check.agent)
+        {
+            items.push(Item {
+                id: format!("hooks:{}", check.agent.key()),
+                what: format!("{} status tracking hooks", check.agent.name()),
+                location: display(&path),
+                undo: Undo::Hooks(check.agent),
+            });
+        }
+        let skill_dirs = skills::installed_skill_dirs(check.agent);
+        if let Some(base_dir) = skills::skills_dir(check.agent)
+            && !skill_dirs.is_empty()
+        {
+            items.push(Item {
+                id: format!("skills:{}", check.agent.key()),
+                what: format!(
+                    "{} for {}",
+                    plural(skill_dirs.len(), "bundled skill"),
+                    check.agent.name()
+                ),
+                location: display(&base_dir),
+                undo: Undo::RemovePaths(skill_dirs),
+            });
+        }
+    }
+
+    match claude::worktree_entries(&configured_worktree_dirs()) {
+        Ok(entries) if !entries.is_empty() => items.push(Item {
+            id: "claude-projects".to_string(),
+            what: format!("{} for worktrees", plural(entries.len(), "trusted project")),
+            location: "~/.claude.json".to_string(),
+            undo: Undo::ClaudeProjects(entries),
+        }),
+        Ok(_) => {}
+        Err(e) => tracing::debug!(error = %e, "audit:failed to read claude config"),
+    }
+
+    if LimaInstance::is_lima_available() {
+        match LimaInstance::list() {
+            Ok(instances) => items.extend(
+                instances
+                    .into_iter()
+                    .filter(|vm| vm.name.starts_with(VM_PREFIX))
+                    .map(|vm| Item {
+                        id: format!("vm:{}", vm.name),
+                        what: format!("Lima VM ({})", vm.status.to_lowercase()),
+                        location: vm.dir.unwrap_or_else(|| "limactl".to_string()),
+                        undo: Undo::Vm(vm.name),
+                    }),
+            ),
+            Err(e) => tracing::debug!(error = %e, "audit:failed to list lima VMs"),
+        }
+    }
+
+    if let Ok(store) = StateStore::new() {
+        for (handle, name, runtime) in store.list_all_containers() {
+            items.push(Item {
+                id: format!("container:{}", name),
+                what: format!("sandbox container for {}", handle),
+                location: runtime.binary_name().to_string(),
+                undo: Undo::Container {
+                    handle,
+                    name,
+                    runtime,
+                },
+            });
+        }
+    }
+
+    if let Some(paths) = SandboxPaths::new() {
+        let existing: Vec<PathBuf> = [paths.config_file, paths.config_dir]
+            .into_iter()
+            .filter(|p| p.exists())
+            .collect();
+        if !existing.is_empty() {
+            items.push(Item {
+                id: "sandbox-config".to_string(),
+                what: "Claude config shared with sandboxes".to_string(),
+                location: existing
+                    .iter()
+                    .map(|p| display(p))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                undo: Undo::RemovePaths(existing),
+            });
+        }
+    }
+
+    for (id, what, dir) in [
+        ("cache", "caches", xdg::cache_dir()),
+        (
+            "state",
+            "window state, logs, run history, VM state and host-exec shims",
+            xdg::state_dir(),
+        ),
+    ] {
+        if let Ok(dir) = dir
+            && dir.exists()
+        {
+            items.push(Item {
+                id: id.to_string(),
+                what: what.to_string(),
+                location: display(&dir),
+                undo: Undo::RemovePaths(vec![dir]),
+            });
+        }
+    }
+
+    items
+}
+
+fn undo(undo: &Undo) -> Result<String> {
+    match undo {
+        Undo::RemovePaths(paths) => {
+            for path in paths {
+                let result = if path.is_dir() {
+                    std::fs::remove_dir_all(path)
+                } else {
+                    std::fs::remove_file(path)
+                };
+                result.with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+            Ok(format!("removed {}", plural(paths.len(), "path")))
+        }
+        Undo::Hooks(agent) => agent_setup::uninstall(*agent),
+        Undo::ClaudeProjects(entries) => {
+            let removed = claude::remove_entries(entries)?;
+            Ok(format!(
+                "removed {} (backup at ~/.claude.json.bak)",
+                plural(removed, "entry")
+            ))
+        }
+        Undo::Vm(name) => {
+            LimaInstance::delete_by_name(name)?;
+            Ok("deleted".to_string())
+        }
+        Undo::Container {
+            handle,
+            name,
+            runtime,
+        } => {
+            // The container may already be gone; only the registration is left
+            let _ = crate::cmd::Cmd::new(runtime.binary_name())
+                .args(&["rm", "-f", name.as_str()])
+                .run();
+            StateStore::new()?.unregister_container(handle, name);
+            Ok("removed".to_string())
+        }
+    }
+}
+
+/// `path` with the home directory shortened to `~`.
+fn display(path: &Path) -> String {
+    match home::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match (count, noun.strip_suffix('y')) {
+        (1, _) => format!("1 {}", noun),
+        (_, Some(stem)) => format!("{} {}ies", count, stem),
+        _ => format!("{} {}s", count, noun),
+    }
+}
+
+/// `worktree_dir` of each repository workmux knows, expanded.
+fn configured_worktree_dirs() -> Vec<PathBuf> {
+    repos::known_repos()
+        .into_iter()
+        .filter_map(|(_, root)| {
+            let (config, _) = Config::load_with_location_from(&root, None).ok()?;
+            expand_worktree_dir(config.worktree_dir.as_deref()?, &root).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluralizes_nouns() {
+        assert_eq!(plural(1, "entry"), "1 entry");
+        assert_eq!(plural(3, "entry"), "3 entries");
+        assert_eq!(plural(2, "bundled skill"), "2 bundled skills");
+    }
+}
@@ -1,6 +1,7 @@
 use crate::multiplexer::handle::mode_label;
 use crate::multiplexer::{MuxHandle, create_backend, detect_backend};
-use crate::{config, git, sandbox};
+use crate::workflow::types::TargetNotOpen;
+use crate::{config, git, sandbox, vcs};
 use anyhow::{Context, Result, anyhow};
 
 pub fn run(name: Option<&str>) -> Result<()> {
@@ -13,12 +14,8 @@ pub fn run(name: Option<&str>) -> Result<()> {
     // and branch lookups, then we extract the true handle from the path basename.
     let resolved_handle = match name {
         Some(n) => {
-            let (path, _branch) = git::find_worktree(n).map_err(|_| {
-                anyhow!(
-                    "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-                    n
-                )
-            })?;
+            let (path, _branch) =
+                vcs::find_workspace(n).map_err(|_| git::WorktreeNotFound(n.to_string()))?;
             path.file_name()
                 .ok_or_else(|| anyhow!("Invalid worktree path: no directory name"))?
                 .to_string_lossy()
@@ -64,12 +61,12 @@ pub fn run(name: Option<&str>) -> Result<()> {
     let target_exists = MuxHandle::exists_full(mux.as_ref(), mode, &full_target_name)?;
 
     if !target_exists {
-        return Err(anyhow!(
-            "No active {} found for '{}'. The worktree exists but has no open {}.",
+        return Err(TargetNotOpen {
             kind,
-            full_target_name,
-            kind
-        ));
+            target: full_target_name,
+            handle: resolved_handle,
+        }
+        .into());
     }
 
     // Stop any running containers for this worktree before killing the target.
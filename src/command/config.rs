@@ -3,6 +3,7 @@
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand};
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 
 #[derive(Debug, Args)]
@@ -46,6 +47,11 @@ fn run_edit() -> Result<()> {
         println!("Created {}", config_path.display());
     }
 
+    open_in_editor(&config_path)
+}
+
+/// Open `path` in $VISUAL, $EDITOR, or vi and wait for it to exit.
+pub(super) fn open_in_editor(path: &Path) -> Result<()> {
     // Determine editor: $VISUAL -> $EDITOR -> vi
     let editor = std::env::var("VISUAL")
         .or_else(|_| std::env::var("EDITOR"))
@@ -57,7 +63,7 @@ fn run_edit() -> Result<()> {
 
     let status = Command::new(cmd)
         .args(args)
-        .arg(&config_path)
+        .arg(path)
         .status()
         .with_context(|| format!("Failed to open editor '{}'", editor))?;
 
@@ -88,6 +94,9 @@ const DEFAULT_GLOBAL_CONFIG: &str = r#"# workmux global configuration
 # agent: claude
 # merge_strategy: rebase
 #
+# Which multiplexer wins when workmux runs inside several, e.g. tmux in WezTerm
+# backend_priority: [wezterm, tmux]
+#
 # panes:
 #   - command: <agent>
 #     focus: true
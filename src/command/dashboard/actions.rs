@@ -1,6 +1,6 @@
 //! Action enum and dispatcher for dashboard key handling.
 
-use super::app::{App, CommandPaletteState, DashboardTab, PaletteCommand, ViewMode};
+use super::app::{App, CommandPaletteState, DashboardTab, PaletteCommand, PreviewMode, ViewMode};
 use super::diff_ops::DiffOps;
 use super::keymap::Context;
 
@@ -33,10 +33,20 @@ pub enum Action {
     ScrollPreviewDown,
     IncreasePreviewSize,
     DecreasePreviewSize,
+    TogglePreviewMode,
     LoadWipDiff,
     SendCommitDashboard,
     TriggerMergeDashboard,
     KillSelected,
+    ToggleMark,
+    SendPrompt,
+    ClearStatus,
+    StopAgent,
+    RestartAgent,
+    CollapseProject,
+    ExpandProject,
+    ShowInbox,
+    ShowHistory,
 
     // Input mode
     SendKey(String),
@@ -71,6 +81,7 @@ pub enum Action {
     WorktreePrevious,
     WorktreeJumpToIndex(usize),
     RemoveSelectedWorktree,
+    MergeSelectedWorktree,
     CloseSelectedWorktreeWindow,
     StartSweep,
     CycleWorktreeSortMode,
@@ -108,7 +119,9 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
         Action::Quit => {
             match app.active_tab {
                 DashboardTab::Agents => {
-                    if !app.filter_text.is_empty() {
+                    if !app.marked_pane_ids.is_empty() {
+                        app.marked_pane_ids.clear();
+                    } else if !app.filter_text.is_empty() {
                         app.filter_text.clear();
                         app.apply_filters();
                     } else {
@@ -173,6 +186,10 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
         Action::EnterInputMode => {
             if app.table_state.selected().is_some() && !app.agents.is_empty() {
                 app.input_mode = true;
+                // Typing to the agent needs its output in view
+                if app.preview_mode == PreviewMode::Diff {
+                    app.toggle_preview_mode();
+                }
             }
             false
         }
@@ -196,6 +213,10 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.decrease_preview_size();
             false
         }
+        Action::TogglePreviewMode => {
+            app.toggle_preview_mode();
+            false
+        }
         Action::LoadWipDiff => {
             app.load_diff(false);
             false
@@ -212,6 +233,42 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.kill_selected();
             false
         }
+        Action::ToggleMark => {
+            app.toggle_mark();
+            false
+        }
+        Action::SendPrompt => {
+            app.start_prompt();
+            false
+        }
+        Action::ClearStatus => {
+            app.clear_status_of_targets();
+            false
+        }
+        Action::StopAgent => {
+            app.stop_targets();
+            false
+        }
+        Action::RestartAgent => {
+            app.restart_targets();
+            false
+        }
+        Action::CollapseProject => {
+            app.collapse_selected_project();
+            false
+        }
+        Action::ExpandProject => {
+            app.expand_selected_project();
+            false
+        }
+        Action::ShowInbox => {
+            app.show_inbox();
+            false
+        }
+        Action::ShowHistory => {
+            app.show_history();
+            false
+        }
 
         // Shared (both tabs)
         Action::OpenPr => {
@@ -246,6 +303,10 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.remove_selected_worktree();
             false
         }
+        Action::MergeSelectedWorktree => {
+            app.merge_selected_worktree();
+            false
+        }
         Action::CloseSelectedWorktreeWindow => {
             app.close_selected_worktree_window();
             false
@@ -511,6 +572,11 @@ fn palette_commands(ctx: Context, app: &App) -> Vec<PaletteCommand> {
                     key_hint: "d",
                     action: Action::LoadWipDiff,
                 },
+                PaletteCommand {
+                    label: "Toggle diff preview",
+                    key_hint: "v",
+                    action: Action::TogglePreviewMode,
+                },
                 PaletteCommand {
                     label: "Commit changes",
                     key_hint: "c",
@@ -541,6 +607,41 @@ fn palette_commands(ctx: Context, app: &App) -> Vec<PaletteCommand> {
                     key_hint: "X",
                     action: Action::KillSelected,
                 },
+                PaletteCommand {
+                    label: "Mark agent",
+                    key_hint: "Space",
+                    action: Action::ToggleMark,
+                },
+                PaletteCommand {
+                    label: "Send prompt",
+                    key_hint: "S",
+                    action: Action::SendPrompt,
+                },
+                PaletteCommand {
+                    label: "Clear status",
+                    key_hint: "C",
+                    action: Action::ClearStatus,
+                },
+                PaletteCommand {
+                    label: "Stop agent process",
+                    key_hint: "K",
+                    action: Action::StopAgent,
+                },
+                PaletteCommand {
+                    label: "Restart agent",
+                    key_hint: "A",
+                    action: Action::RestartAgent,
+                },
+                PaletteCommand {
+                    label: "Show inbox",
+                    key_hint: "I",
+                    action: Action::ShowInbox,
+                },
+                PaletteCommand {
+                    label: "Show agent history",
+                    key_hint: "H",
+                    action: Action::ShowHistory,
+                },
                 PaletteCommand {
                     label: "Remove worktree",
                     key_hint: "r",
@@ -556,6 +657,16 @@ fn palette_commands(ctx: Context, app: &App) -> Vec<PaletteCommand> {
                     key_hint: "s",
                     action: Action::CycleSortMode,
                 },
+                PaletteCommand {
+                    label: "Fold project",
+                    key_hint: "h/Left",
+                    action: Action::CollapseProject,
+                },
+                PaletteCommand {
+                    label: "Unfold project",
+                    key_hint: "l/Right",
+                    action: Action::ExpandProject,
+                },
                 PaletteCommand {
                     label: "Toggle session filter",
                     key_hint: "F",
@@ -633,6 +744,11 @@ fn palette_commands(ctx: Context, app: &App) -> Vec<PaletteCommand> {
                 key_hint: "r",
                 action: Action::RemoveSelectedWorktree,
             },
+            PaletteCommand {
+                label: "Merge worktree",
+                key_hint: "m",
+                action: Action::MergeSelectedWorktree,
+            },
             PaletteCommand {
                 label: "Close mux window",
                 key_hint: "c",
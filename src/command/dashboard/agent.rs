@@ -1,5 +1,9 @@
 //! Pure helper functions for agent data extraction and formatting.
 
+use std::collections::{HashMap, HashSet};
+
+use crate::multiplexer::AgentPane;
+
 // Re-export shared display helpers so existing `agent::extract_*` paths keep working.
 pub use crate::agent_display::{extract_project_name, extract_worktree_name};
 
@@ -15,6 +19,26 @@ pub fn elapsed_secs(status_ts: Option<u64>, now_secs: u64) -> Option<u64> {
     status_ts.map(|ts| now_secs.saturating_sub(ts))
 }
 
+/// Fold the collapsed projects of a list sorted by project, keeping only each
+/// one's first (most urgent) agent to stand for it. Returns how many agents
+/// each folded project has.
+pub fn fold_projects(
+    agents: &mut Vec<AgentPane>,
+    collapsed: &HashSet<String>,
+) -> HashMap<String, usize> {
+    let mut folded: HashMap<String, usize> = HashMap::new();
+    agents.retain(|agent| {
+        let project = extract_project_name(&agent.path);
+        if !collapsed.contains(&project) {
+            return true;
+        }
+        let count = folded.entry(project).or_insert(0);
+        *count += 1;
+        *count == 1
+    });
+    folded
+}
+
 /// Format a duration in seconds as HH:MM:SS.
 pub fn format_duration(secs: u64) -> String {
     let hours = secs / 3600;
@@ -53,6 +77,34 @@ mod tests {
         assert_eq!(elapsed_secs(None, 200), None);
     }
 
+    #[test]
+    fn test_fold_projects_keeps_first_agent() {
+        let agent = |pane_id: &str, path: &str| AgentPane {
+            session: "main".to_string(),
+            window_name: "wm-x".to_string(),
+            pane_id: pane_id.to_string(),
+            window_id: "@1".to_string(),
+            path: std::path::PathBuf::from(path),
+            pane_title: None,
+            waiting_reason: None,
+            status: None,
+            status_ts: None,
+            updated_ts: None,
+        };
+        let mut agents = vec![
+            agent("%1", "/src/api__worktrees/a"),
+            agent("%2", "/src/api__worktrees/b"),
+            agent("%3", "/src/api__worktrees/c"),
+            agent("%4", "/src/web__worktrees/a"),
+        ];
+        let collapsed = HashSet::from([extract_project_name(&agents[0].path)]);
+        let folded = fold_projects(&mut agents, &collapsed);
+
+        let pane_ids: Vec<_> = agents.iter().map(|a| a.pane_id.as_str()).collect();
+        assert_eq!(pane_ids, ["%1", "%4"]);
+        assert_eq!(folded.values().copied().collect::<Vec<_>>(), [3]);
+    }
+
     #[test]
     fn test_format_duration() {
         assert_eq!(format_duration(0), "00:00:00");
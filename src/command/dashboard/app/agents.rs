@@ -14,8 +14,10 @@ use super::DashboardTab;
 
 use super::super::agent;
 use super::super::ansi;
-use super::super::settings::{load_last_pane_id, save_hide_stale, save_last_pane_id};
-use super::super::sort::SortMode;
+use super::super::settings::{
+    load_last_pane_id, save_collapsed_projects, save_hide_stale, save_last_pane_id,
+};
+use super::super::sort::{SortMode, checks_rank};
 use super::super::spinner::SPINNER_FRAMES;
 use super::App;
 
@@ -59,10 +61,24 @@ impl App {
 
         self.sort_agents();
 
-        // Restore selection by pane_id to follow the item across reorders
+        // Fold collapsed projects, which only group in project sort mode
+        self.folded_projects = if self.sort_mode == SortMode::Project {
+            agent::fold_projects(&mut self.agents, &self.collapsed_projects)
+        } else {
+            HashMap::new()
+        };
+
+        // Restore selection by pane_id to follow the item across reorders,
+        // landing on its project's row if it's been folded away
         if let Some(ref pane_id) = self.selected_pane_id {
-            if let Some(new_idx) = self.agents.iter().position(|a| &a.pane_id == pane_id) {
+            let new_idx = self
+                .agents
+                .iter()
+                .position(|a| &a.pane_id == pane_id)
+                .or_else(|| self.folded_row_of(pane_id));
+            if let Some(new_idx) = new_idx {
                 self.table_state.select(Some(new_idx));
+                self.selected_pane_id = Some(self.agents[new_idx].pane_id.clone());
             } else {
                 self.selected_pane_id = None;
                 if self.agents.is_empty() {
@@ -171,6 +187,25 @@ impl App {
                 self.agents
                     .sort_by_cached_key(|a| (get_elapsed(a), pane_num(a)));
             }
+            SortMode::Checks => {
+                // Failing checks first, then by priority within each check state
+                let ranks: HashMap<String, u8> = self
+                    .agents
+                    .iter()
+                    .map(|a| {
+                        let checks = self.get_pr_for_agent(a).and_then(|pr| pr.checks.as_ref());
+                        (a.pane_id.clone(), checks_rank(checks))
+                    })
+                    .collect();
+                self.agents.sort_by_cached_key(|a| {
+                    (
+                        ranks.get(&a.pane_id).copied().unwrap_or(u8::MAX),
+                        get_priority(a),
+                        get_elapsed(a),
+                        pane_num(a),
+                    )
+                });
+            }
             SortMode::Natural => {
                 self.agents.sort_by_cached_key(pane_num);
             }
@@ -180,7 +215,56 @@ impl App {
     pub fn cycle_sort_mode(&mut self) {
         self.sort_mode = self.sort_mode.next();
         self.sort_mode.save();
-        self.sort_agents();
+        // Re-filter rather than just re-sort, since folding depends on the mode
+        self.apply_filters();
+    }
+
+    /// Fold the selected agent's project into a single row (project sort mode only).
+    pub fn collapse_selected_project(&mut self) {
+        if self.sort_mode != SortMode::Project {
+            return;
+        }
+        let Some(project) = self.selected_project() else {
+            return;
+        };
+        // The selection moves onto the folded row
+        if self.collapsed_projects.insert(project) {
+            save_collapsed_projects(&self.collapsed_projects);
+            self.apply_filters();
+        }
+    }
+
+    /// Unfold the selected project (project sort mode only).
+    pub fn expand_selected_project(&mut self) {
+        if self.sort_mode != SortMode::Project {
+            return;
+        }
+        let Some(project) = self.selected_project() else {
+            return;
+        };
+        if self.collapsed_projects.remove(&project) {
+            save_collapsed_projects(&self.collapsed_projects);
+            self.apply_filters();
+        }
+    }
+
+    /// Row of the folded project containing a hidden agent.
+    fn folded_row_of(&self, pane_id: &str) -> Option<usize> {
+        let agent = self.all_agents.iter().find(|a| a.pane_id == pane_id)?;
+        let project = Self::extract_project_name(agent);
+        if !self.folded_projects.contains_key(&project) {
+            return None;
+        }
+        self.agents
+            .iter()
+            .position(|a| Self::extract_project_name(a) == project)
+    }
+
+    fn selected_project(&self) -> Option<String> {
+        self.table_state
+            .selected()
+            .and_then(|idx| self.agents.get(idx))
+            .map(Self::extract_project_name)
     }
 
     /// Toggle between showing all agents or only the current session's agents
@@ -323,24 +407,26 @@ impl App {
         }
     }
 
-    /// Kill the selected agent's pane and remove it from the list.
-    /// Shows a confirmation popup for working agents.
+    /// Kill the marked agents' panes (or the selected one) and remove them
+    /// from the list. Shows a confirmation popup if any of them is working.
     pub fn kill_selected(&mut self) {
-        if let Some(selected) = self.table_state.selected()
-            && let Some(agent) = self.agents.get(selected)
+        let targets = self.target_agents();
+        if targets
+            .iter()
+            .any(|a| a.status == Some(AgentStatus::Working))
         {
-            if agent.status == Some(AgentStatus::Working) {
-                // Show confirmation popup
-                self.pending_kill_pane_id = Some(agent.pane_id.clone());
-            } else {
-                self.do_kill(&agent.pane_id.clone());
+            // Show confirmation popup
+            self.pending_kill_pane_ids = targets.into_iter().map(|a| a.pane_id).collect();
+        } else {
+            for agent in targets {
+                self.do_kill(&agent.pane_id);
             }
         }
     }
 
     /// Execute the pending kill confirmation.
     pub fn confirm_kill(&mut self) {
-        if let Some(pane_id) = self.pending_kill_pane_id.take() {
+        for pane_id in std::mem::take(&mut self.pending_kill_pane_ids) {
             self.do_kill(&pane_id);
         }
     }
@@ -354,6 +440,7 @@ impl App {
         // Remove from local lists immediately for responsive UI
         self.agents.retain(|a| a.pane_id != pane_id);
         self.all_agents.retain(|a| a.pane_id != pane_id);
+        self.marked_pane_ids.remove(pane_id);
 
         // Adjust selection
         if self.agents.is_empty() {
@@ -401,19 +488,22 @@ impl App {
 
     pub fn get_status_display(&self, agent: &AgentPane) -> Vec<(String, Style)> {
         let is_stale = self.is_stale(agent);
+        let is_stalled = self.stalled_pane_ids.contains(&agent.pane_id);
 
         // Map status enum to icon and color
         let (icon, base_color, is_working) = match agent.status {
-            Some(AgentStatus::Working) => {
-                (self.config.status_icons.working(), self.palette.info, true)
-            }
+            Some(AgentStatus::Working) => (
+                self.config.status_icons.working(),
+                self.palette.working(),
+                true,
+            ),
             Some(AgentStatus::Waiting) => (
                 self.config.status_icons.waiting(),
-                self.palette.accent,
+                self.palette.waiting(),
                 false,
             ),
             Some(AgentStatus::Done) => {
-                (self.config.status_icons.done(), self.palette.success, false)
+                (self.config.status_icons.done(), self.palette.done(), false)
             }
             None => ("", self.palette.text, false),
         };
@@ -423,11 +513,18 @@ impl App {
 
         if is_stale {
             // Override all styling for stale agents
-            let dimmed = Style::default().fg(self.palette.dimmed);
+            let stale = Style::default().fg(self.palette.stale());
             for span in &mut spans {
-                span.1 = dimmed;
+                span.1 = stale;
             }
-            spans.push((" \u{f051b}".to_string(), dimmed));
+            spans.push((" \u{f051b}".to_string(), stale));
+        } else if is_working && is_stalled {
+            // Output hasn't changed in a while: the agent may have stopped
+            // without firing its status hook
+            spans.push((
+                " stall".to_string(),
+                Style::default().fg(self.palette.warning),
+            ));
         } else if is_working {
             // Add animated spinner when agent is working
             let spinner = SPINNER_FRAMES[self.spinner_frame as usize];
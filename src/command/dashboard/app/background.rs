@@ -1,6 +1,7 @@
-//! Background thread spawning for git status and PR status fetches.
+//! Background thread spawning for agent, preview, git status and PR status
+//! fetches.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -8,10 +9,95 @@ use std::sync::{Arc, Mutex};
 use crate::git;
 
 use super::super::agent;
-use super::App;
-use super::types::AppEvent;
+use super::preview::{DIFF_PREVIEW_INTERVAL, diff_preview};
+use super::types::{AppEvent, DashboardTab, PreviewMode};
+use super::{App, load_agent_snapshot};
+
+/// Clears a fetch-in-progress flag when the fetch thread ends (even on panic)
+struct ResetFlag(Arc<AtomicBool>);
+
+impl Drop for ResetFlag {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
 
 impl App {
+    /// Reload agents on a background thread. The result arrives as
+    /// `AppEvent::AgentList`, so the UI keeps drawing while state files and
+    /// panes are read.
+    pub fn spawn_agent_fetch(&mut self) {
+        if self
+            .is_agent_fetching
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+
+        self.agents_requested += 1;
+        let generation = self.agents_requested;
+        let tx = self.event_tx.clone();
+        let is_fetching = self.is_agent_fetching.clone();
+        let mux = self.mux.clone();
+        let stall_tracker = self.stall_tracker.clone();
+
+        std::thread::spawn(move || {
+            let _reset = ResetFlag(is_fetching);
+            let snapshot = load_agent_snapshot(generation, mux.as_ref(), stall_tracker.as_deref());
+            let _ = tx.send(AppEvent::AgentList(snapshot));
+        });
+    }
+
+    /// Capture the selected agent's pane (or diff its worktree, in diff
+    /// preview mode) on a background thread. The result arrives as
+    /// `AppEvent::Preview`.
+    pub fn spawn_preview_fetch(&mut self) {
+        let Some(pane_id) = self.preview_pane_id.clone() else {
+            return;
+        };
+        if !self.mux.supports_preview() {
+            return;
+        }
+        let mode = self.preview_mode;
+        let diff_path = match mode {
+            PreviewMode::Output => None,
+            PreviewMode::Diff => {
+                if self.last_diff_preview_fetch.elapsed() < DIFF_PREVIEW_INTERVAL {
+                    return;
+                }
+                let Some(agent) = self.agents.iter().find(|a| a.pane_id == pane_id) else {
+                    return;
+                };
+                Some(agent.path.clone())
+            }
+        };
+        if self
+            .is_preview_fetching
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+        if diff_path.is_some() {
+            self.last_diff_preview_fetch = std::time::Instant::now();
+        }
+
+        let tx = self.event_tx.clone();
+        let is_fetching = self.is_preview_fetching.clone();
+        let mux = self.mux.clone();
+        let lines = self.config.dashboard.preview_lines();
+
+        std::thread::spawn(move || {
+            let _reset = ResetFlag(is_fetching);
+            let content = match diff_path {
+                Some(path) => diff_preview(&path),
+                None => mux.capture_pane(&pane_id, lines),
+            };
+            let _ = tx.send(AppEvent::Preview(mode, pane_id, content));
+        });
+    }
+
     /// Spawn a background thread to fetch git status for all agent worktrees
     pub(super) fn spawn_git_status_fetch(&self) {
         // Skip if a fetch is already in progress (prevents thread pile-up)
@@ -35,13 +121,6 @@ impl App {
         }
 
         std::thread::spawn(move || {
-            // Reset flag when thread completes (even on panic)
-            struct ResetFlag(Arc<AtomicBool>);
-            impl Drop for ResetFlag {
-                fn drop(&mut self) {
-                    self.0.store(false, Ordering::SeqCst);
-                }
-            }
             let _reset = ResetFlag(is_fetching);
 
             for path in paths {
@@ -125,6 +204,8 @@ impl App {
 
         let tx = self.event_tx.clone();
         let is_fetching = self.is_pr_fetching.clone();
+        let previous = Arc::new(self.pr_statuses.clone());
+        let visible = Arc::new(self.visible_branches());
 
         // Identify the priority repo (current project) so it fetches first
         let priority_repo = self
@@ -138,12 +219,6 @@ impl App {
             });
 
         std::thread::spawn(move || {
-            struct ResetFlag(Arc<AtomicBool>);
-            impl Drop for ResetFlag {
-                fn drop(&mut self) {
-                    self.0.store(false, Ordering::SeqCst);
-                }
-            }
             let _reset = ResetFlag(is_fetching);
 
             // Sort repos so the priority repo (current project) is fetched first
@@ -162,14 +237,23 @@ impl App {
                 for _ in 0..workers {
                     let queue = Arc::clone(&queue);
                     let tx = tx.clone();
+                    let previous = Arc::clone(&previous);
+                    let visible = Arc::clone(&visible);
                     s.spawn(move || {
                         loop {
                             let Some((repo_root, branches)) = queue.lock().unwrap().pop_front()
                             else {
                                 break;
                             };
-                            match crate::github::list_prs_for_branches(&repo_root, &branches) {
+                            let refresh = crate::github::PrRefresh {
+                                previous: previous.get(&repo_root),
+                                visible: visible.as_ref(),
+                            };
+                            match crate::github::list_prs_for_branches(
+                                &repo_root, &branches, &refresh,
+                            ) {
                                 Ok(prs) => {
+                                    crate::tracker::link_prs(&repo_root, &prs);
                                     let _ = tx.send(AppEvent::PrStatus(repo_root, prs));
                                 }
                                 Err(e) => {
@@ -188,4 +272,17 @@ impl App {
 
         true
     }
+    /// Branches shown in the active tab, which get check details on refresh.
+    /// None (every branch) until the first list has loaded.
+    fn visible_branches(&self) -> Option<HashSet<String>> {
+        let branches: HashSet<String> = match self.active_tab {
+            DashboardTab::Agents => self
+                .agents
+                .iter()
+                .filter_map(|a| self.git_statuses.get(&a.path)?.branch.clone())
+                .collect(),
+            DashboardTab::Worktrees => self.worktrees.iter().map(|w| w.branch.clone()).collect(),
+        };
+        (!branches.is_empty()).then_some(branches)
+    }
 }
@@ -0,0 +1,185 @@
+//! Multi-select and batch actions for the agent list.
+//!
+//! Actions that act on agents (kill, commit, merge, send prompt, clear
+//! status) apply to the marked agents, or to the selected one when none are
+//! marked.
+
+use super::super::lifecycle;
+use super::types::AppEvent;
+use crate::command::send;
+use crate::multiplexer::AgentPane;
+use crate::state::{PaneKey, StateStore, activity};
+
+use super::App;
+
+impl App {
+    /// Agents the next action applies to: the marked ones in list order, or
+    /// the selected one when none are marked.
+    pub fn target_agents(&self) -> Vec<AgentPane> {
+        if self.marked_pane_ids.is_empty() {
+            return self
+                .table_state
+                .selected()
+                .and_then(|idx| self.agents.get(idx))
+                .cloned()
+                .into_iter()
+                .collect();
+        }
+        self.agents
+            .iter()
+            .filter(|a| self.marked_pane_ids.contains(&a.pane_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Mark or unmark the selected agent and move to the next one.
+    pub fn toggle_mark(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.agents.get(idx))
+        else {
+            return;
+        };
+        let pane_id = agent.pane_id.clone();
+        if !self.marked_pane_ids.remove(&pane_id) {
+            self.marked_pane_ids.insert(pane_id);
+        }
+        self.next();
+    }
+
+    /// Send text to every target agent, then clear the marks. Returns the
+    /// agents that received it.
+    pub fn send_to_targets(&mut self, text: &str) -> Vec<AgentPane> {
+        let targets = self.target_agents();
+        let total = targets.len();
+        let mut delivered = Vec::new();
+        let mut failed = Vec::new();
+        for agent in targets {
+            if self.mux.requires_focus_for_input() {
+                let _ = self
+                    .mux
+                    .switch_to_pane(&agent.pane_id, Some(&agent.window_name));
+            }
+            match send::deliver(
+                self.mux.as_ref(),
+                &agent.pane_id,
+                text,
+                self.config.agent.as_deref(),
+            ) {
+                Ok(()) => delivered.push(agent),
+                Err(e) => failed.push(e.to_string()),
+            }
+        }
+        if let Some(error) = failed.first() {
+            self.status_message = Some((
+                format!("Failed to send to {} agent(s): {error}", failed.len()),
+                std::time::Instant::now(),
+            ));
+        } else if total > 1 {
+            self.status_message = Some((
+                format!("Sent to {} agents", total),
+                std::time::Instant::now(),
+            ));
+        }
+        self.marked_pane_ids.clear();
+        delivered
+    }
+
+    /// Open the prompt input for the target agents.
+    pub fn start_prompt(&mut self) {
+        if !self.target_agents().is_empty() {
+            self.pending_prompt = Some(String::new());
+        }
+    }
+
+    /// Send the typed prompt to the target agents and close the input. Like
+    /// `workmux send`, the prompt is recorded in each worktree's activity log.
+    pub fn confirm_prompt(&mut self) {
+        let Some(text) = self.pending_prompt.take() else {
+            return;
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        // Only worktrees whose agent actually got the prompt record it
+        for agent in self.send_to_targets(text) {
+            activity::record_prompt(&agent.path, text);
+        }
+    }
+
+    /// Stop the agent process in each target pane, leaving the panes open at
+    /// their shells, then clear the marks.
+    pub fn stop_targets(&mut self) {
+        let mut failed = Vec::new();
+        for agent in self.target_agents() {
+            if let Err(e) = lifecycle::stop(self.mux.as_ref(), &agent.pane_id) {
+                failed.push(e.to_string());
+            }
+        }
+        if let Some(error) = failed.first() {
+            self.status_message = Some((
+                format!("Failed to stop agent: {error}"),
+                std::time::Instant::now(),
+            ));
+        }
+        self.marked_pane_ids.clear();
+        self.refresh();
+    }
+
+    /// Restart the agent in each target pane, resuming its conversation, then
+    /// clear the marks. Runs in the background since agents take a moment to
+    /// exit and start.
+    pub fn restart_targets(&mut self) {
+        let targets = self.target_agents();
+        if targets.is_empty() {
+            return;
+        }
+        self.status_message = Some((
+            match targets.len() {
+                1 => "Restarting agent".to_string(),
+                n => format!("Restarting {n} agents"),
+            },
+            std::time::Instant::now(),
+        ));
+        for agent in targets {
+            let mux = self.mux.clone();
+            let tx = self.event_tx.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = lifecycle::restart(mux.as_ref(), &agent.pane_id, &agent.path) {
+                    let _ = tx.send(AppEvent::StatusMessage(format!(
+                        "Failed to restart agent: {e}"
+                    )));
+                }
+            });
+        }
+        self.marked_pane_ids.clear();
+    }
+
+    /// Clear the status of every target agent, in the multiplexer and the
+    /// state store, then clear the marks. Useful for agents that stopped
+    /// without reporting it and still show as working.
+    pub fn clear_status_of_targets(&mut self) {
+        let store = StateStore::new().ok();
+        for agent in self.target_agents() {
+            let _ = self.mux.clear_status(&agent.pane_id);
+            let Some(store) = &store else {
+                continue;
+            };
+            let key = PaneKey {
+                backend: self.mux.name().to_string(),
+                instance: self.mux.instance_id(),
+                pane_id: agent.pane_id,
+            };
+            if let Ok(Some(mut state)) = store.get_agent(&key) {
+                state.status = None;
+                state.status_ts = None;
+                state.waiting_reason = None;
+                let _ = store.upsert_agent(&state);
+            }
+        }
+        self.marked_pane_ids.clear();
+        self.refresh();
+    }
+}
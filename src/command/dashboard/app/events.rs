@@ -1,16 +1,28 @@
 //! Event dispatching for background events.
 
+use super::super::sort::SortMode;
 use super::App;
 use super::types::{AppEvent, PrListState};
 
 impl App {
     /// Apply a background event to app state.
     /// Called from the main loop when an AppEvent arrives on the unified channel.
-    pub fn apply_event(&mut self, event: AppEvent) {
+    /// Returns false if the event left the view unchanged.
+    pub fn apply_event(&mut self, event: AppEvent) -> bool {
         match event {
-            AppEvent::Terminal(_) => {} // handled separately in main loop
+            AppEvent::Terminal(_) => return false, // handled separately in main loop
+            AppEvent::AgentList(snapshot) => {
+                self.apply_agent_snapshot(snapshot);
+            }
+            AppEvent::Preview(mode, pane_id, content) => {
+                return self.apply_preview(mode, &pane_id, content);
+            }
             AppEvent::GitStatus(path, status) => {
                 self.git_statuses.insert(path, status);
+                // The branch decides which PR's checks an agent sorts by
+                if self.sort_mode == SortMode::Checks {
+                    self.apply_filters();
+                }
             }
             AppEvent::PrStatus(repo_root, prs) => {
                 // Clear stale state when a repo has no PRs, otherwise update
@@ -19,6 +31,11 @@ impl App {
                 } else {
                     self.pr_statuses.insert(repo_root, prs);
                 }
+                // Persist right away so `workmux list --pr` can serve it
+                crate::github::save_pr_cache(&self.pr_statuses);
+                if self.sort_mode == SortMode::Checks {
+                    self.apply_filters();
+                }
                 // Re-apply worktree filters to merge new PR data
                 if !self.all_worktrees.is_empty() {
                     self.apply_worktree_filters();
@@ -60,6 +77,25 @@ impl App {
                     handle,
                 });
             }
+            AppEvent::StatusMessage(message) => {
+                self.status_message = Some((message, std::time::Instant::now()));
+            }
+            AppEvent::MergeOutput(line) => {
+                if let Some(ref mut run) = self.merge_run {
+                    run.lines.push(line);
+                }
+            }
+            AppEvent::MergeComplete(result) => {
+                if let Some(ref mut run) = self.merge_run {
+                    let message = match result {
+                        Ok(()) => format!("Merged {}", run.handle),
+                        Err(ref e) => format!("Merge of {} failed: {e}", run.handle),
+                    };
+                    self.status_message = Some((message, std::time::Instant::now()));
+                    run.result = Some(result);
+                }
+                self.trigger_worktree_refetch();
+            }
             AppEvent::SweepComplete(result) => {
                 self.sweep_progress = None;
                 match result {
@@ -75,5 +111,6 @@ impl App {
                 self.trigger_worktree_refetch();
             }
         }
+        true
     }
 }
@@ -0,0 +1,57 @@
+//! Status history of the selected agent.
+//!
+//! Shows the transitions recorded in the agent's history log along with a
+//! summary: when it started, first asked for input, finished, and how long
+//! it has spent working.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::state::{PaneKey, StateStore, StatusTimeline};
+
+use super::App;
+use super::types::HistoryState;
+
+impl App {
+    /// Open the history view for the selected agent.
+    pub fn show_history(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.agents.get(idx))
+        else {
+            return;
+        };
+        let key = PaneKey {
+            backend: self.mux.name().to_string(),
+            instance: self.mux.instance_id(),
+            pane_id: agent.pane_id.clone(),
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut changes = StateStore::new()
+            .and_then(|store| store.load_status_history(&key))
+            .unwrap_or_default();
+        let timeline = StatusTimeline::from_changes(&changes, now);
+        changes.reverse();
+        self.pending_history = Some(HistoryState {
+            name: self.extract_worktree_name(agent).0,
+            timeline,
+            changes,
+            scroll: 0,
+        });
+    }
+
+    pub fn history_down(&mut self) {
+        if let Some(ref mut history) = self.pending_history {
+            history.scroll = (history.scroll + 1).min(history.changes.len().saturating_sub(1));
+        }
+    }
+
+    pub fn history_up(&mut self) {
+        if let Some(ref mut history) = self.pending_history {
+            history.scroll = history.scroll.saturating_sub(1);
+        }
+    }
+}
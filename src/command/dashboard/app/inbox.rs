@@ -0,0 +1,41 @@
+//! Inbox of recent agent notifications.
+//!
+//! Lists the waiting, done and merged events recorded in the notification
+//! history, so agents that asked for input while you were away still show
+//! up after their status has cleared.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::notification::history;
+
+use super::App;
+use super::types::InboxState;
+
+/// How far back the inbox goes
+const INBOX_HOURS: u64 = 24;
+
+impl App {
+    /// Open the inbox with the last day's events, newest first.
+    pub fn show_inbox(&mut self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut events =
+            history::load_since(now.saturating_sub(INBOX_HOURS * 3600)).unwrap_or_default();
+        events.reverse();
+        self.pending_inbox = Some(InboxState { events, scroll: 0 });
+    }
+
+    pub fn inbox_down(&mut self) {
+        if let Some(ref mut inbox) = self.pending_inbox {
+            inbox.scroll = (inbox.scroll + 1).min(inbox.events.len().saturating_sub(1));
+        }
+    }
+
+    pub fn inbox_up(&mut self) {
+        if let Some(ref mut inbox) = self.pending_inbox {
+            inbox.scroll = inbox.scroll.saturating_sub(1);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Merging the selected worktree from the worktrees tab.
+//!
+//! Runs `workmux merge` for the worktree in a child process after a
+//! confirmation, so the merge strategy, hooks and cleanup are exactly those
+//! of the command. Its output streams into a popup as it runs.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::config::Config;
+use crate::git;
+
+use super::App;
+use super::types::{AppEvent, MergePlan, MergeRun};
+
+impl App {
+    /// Show the merge confirmation for the selected worktree. Skips the main
+    /// worktree.
+    pub fn merge_selected_worktree(&mut self) {
+        let Some(worktree) = self
+            .worktree_table_state
+            .selected()
+            .and_then(|idx| self.worktrees.get(idx))
+        else {
+            return;
+        };
+        if worktree.is_main {
+            return;
+        }
+
+        // The merge runs with the worktree's own config
+        let strategy = Config::load_with_location_from(&worktree.path, None)
+            .map(|(config, _)| config.merge_strategy.unwrap_or_default())
+            .unwrap_or_default();
+        self.pending_merge = Some(MergePlan {
+            handle: worktree.handle.clone(),
+            branch: worktree.branch.clone(),
+            strategy,
+            is_dirty: git::has_uncommitted_changes(&worktree.path).unwrap_or(false),
+        });
+    }
+
+    /// Run the confirmed merge in the background, streaming its output.
+    pub fn confirm_merge(&mut self) {
+        let Some(plan) = self.pending_merge.take() else {
+            return;
+        };
+        // Run from the main worktree: the merged one is removed on success
+        let Some(repo) = self
+            .worktrees
+            .iter()
+            .find(|w| w.is_main)
+            .map(|w| w.path.clone())
+        else {
+            return;
+        };
+
+        self.merge_run = Some(MergeRun {
+            handle: plan.handle.clone(),
+            lines: Vec::new(),
+            result: None,
+        });
+
+        let tx = self.event_tx.clone();
+        thread::spawn(move || {
+            let exe = std::env::current_exe().unwrap_or_else(|_| "workmux".into());
+            // Confirming in the dashboard is a human approving the merge
+            let child = Command::new(exe)
+                .args(["merge", plan.handle.as_str(), "--approve"])
+                .current_dir(&repo)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(e) => {
+                    let _ = tx.send(AppEvent::MergeComplete(Err(e.to_string())));
+                    return;
+                }
+            };
+
+            let readers: Vec<_> = [
+                child
+                    .stdout
+                    .take()
+                    .map(|out| Box::new(out) as Box<dyn Read + Send>),
+                child
+                    .stderr
+                    .take()
+                    .map(|err| Box::new(err) as Box<dyn Read + Send>),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|output| {
+                let tx = tx.clone();
+                thread::spawn(move || forward_lines(output, &tx))
+            })
+            .collect();
+            for reader in readers {
+                let _ = reader.join();
+            }
+
+            let result = match child.wait() {
+                Ok(status) if status.success() => Ok(()),
+                Ok(status) => Err(format!("merge exited with {}", status)),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = tx.send(AppEvent::MergeComplete(result));
+        });
+    }
+
+    /// Close the merge output once the merge has finished.
+    pub fn close_merge_run(&mut self) {
+        if self
+            .merge_run
+            .as_ref()
+            .is_some_and(|run| run.result.is_some())
+        {
+            self.merge_run = None;
+        }
+    }
+}
+
+/// Send each line of a child's output to the dashboard.
+fn forward_lines(output: impl Read, tx: &mpsc::Sender<AppEvent>) {
+    for line in BufReader::new(output).lines() {
+        let Ok(line) = line else { break };
+        if tx.send(AppEvent::MergeOutput(line)).is_err() {
+            break;
+        }
+    }
+}
@@ -3,7 +3,11 @@
 mod agents;
 mod appearance;
 mod background;
+mod batch;
 mod events;
+mod history;
+mod inbox;
+mod merge;
 mod preview;
 mod types;
 mod worktrees;
@@ -11,11 +15,12 @@ mod worktrees;
 pub use types::*;
 
 use anyhow::Result;
+use ratatui::text::Text;
 use ratatui::widgets::TableState;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
-use std::sync::{Arc, mpsc};
+use std::sync::{Arc, Mutex, mpsc};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::config::Config;
@@ -25,12 +30,16 @@ use crate::multiplexer::{AgentPane, Multiplexer};
 use crate::state::StateStore;
 use crate::workflow::types::WorktreeInfo;
 
-use super::ui::theme::ThemePalette;
+use super::stall::StallTracker;
+use super::ui::theme::{ThemePalette, resolve_mode};
 
 const PR_FETCH_INTERVAL: Duration = Duration::from_secs(30);
 
 use super::scope::ScopeMode;
-use super::settings::{load_hide_stale, load_last_pane_id, load_preview_size};
+use super::settings::{
+    load_collapsed_projects, load_hide_stale, load_last_pane_id, load_preview_size,
+    stale_threshold_secs,
+};
 use super::sort::{SortMode, WorktreeSortMode};
 
 /// App state for the TUI
@@ -54,6 +63,14 @@ pub struct App {
     pub view_mode: ViewMode,
     /// Cached preview of the currently selected agent's terminal output
     pub preview: Option<String>,
+    /// `preview` with ANSI colors parsed, so frames don't re-parse it
+    pub preview_text: Option<Text<'static>>,
+    /// Whether the preview shows terminal output or the worktree diff
+    pub preview_mode: PreviewMode,
+    /// Last time the diff preview was fetched (diffs are slower than captures)
+    last_diff_preview_fetch: std::time::Instant,
+    /// Flag to prevent concurrent preview captures
+    is_preview_fetching: Arc<AtomicBool>,
     /// Track which pane_id the preview was captured from (to detect selection changes)
     preview_pane_id: Option<String>,
     /// Input mode: keystrokes are sent directly to the selected agent's pane
@@ -106,8 +123,16 @@ pub struct App {
     pub filter_active: bool,
     /// Text filter for filtering agents by name. Empty string means no filter.
     pub filter_text: String,
-    /// Pane ID awaiting kill confirmation (set when pressing x on a working agent)
-    pub pending_kill_pane_id: Option<String>,
+    /// Pane IDs awaiting kill confirmation (set when pressing X with a working agent among the targets)
+    pub pending_kill_pane_ids: Vec<String>,
+    /// Agents marked for a batch action
+    pub marked_pane_ids: std::collections::HashSet<String>,
+    /// Prompt being typed to send to the marked agents (or the selected one)
+    pub pending_prompt: Option<String>,
+    /// Projects folded in project sort mode
+    collapsed_projects: std::collections::HashSet<String>,
+    /// Agent counts of the projects currently folded, each shown as one row
+    pub folded_projects: HashMap<String, usize>,
     /// Which tab is active (Agents or Worktrees)
     pub active_tab: DashboardTab,
     /// Full worktree list from background fetch (baseline for filtering/sorting)
@@ -152,8 +177,26 @@ pub struct App {
     pub show_sidebar_tip: bool,
     /// Pane IDs of agents detected as interrupted by the sidebar daemon.
     pub interrupted_pane_ids: std::collections::HashSet<String>,
+    /// Pane content sampler for stall detection (None when disabled).
+    /// Shared with the background agent load, which does the sampling.
+    stall_tracker: Option<Arc<Mutex<StallTracker>>>,
+    /// Flag to prevent concurrent agent loads
+    is_agent_fetching: Arc<AtomicBool>,
+    /// Generation of the last agent snapshot requested / applied
+    agents_requested: u64,
+    agents_applied: u64,
+    /// Pane IDs of working agents whose output hasn't changed in a while
+    pub stalled_pane_ids: std::collections::HashSet<String>,
     /// Pending command palette state (shown in command palette modal)
     pub pending_command_palette: Option<CommandPaletteState>,
+    /// Recent notifications (shown in the inbox modal)
+    pub pending_inbox: Option<InboxState>,
+    /// Status history of the selected agent (shown in the history modal)
+    pub pending_history: Option<HistoryState>,
+    /// Merge awaiting confirmation (shown in the merge modal)
+    pub pending_merge: Option<MergePlan>,
+    /// Merge in progress or just finished (shown in the merge output popup)
+    pub merge_run: Option<MergeRun>,
 }
 
 impl App {
@@ -178,13 +221,7 @@ impl App {
             .clamp(10, 90);
 
         // Determine theme mode: config override or auto-detect from terminal
-        let theme_mode = config
-            .theme
-            .mode
-            .unwrap_or_else(|| match terminal_light::luma() {
-                Ok(luma) if luma > 0.6 => crate::config::ThemeMode::Light,
-                _ => crate::config::ThemeMode::Dark,
-            });
+        let theme_mode = resolve_mode(&config.theme);
         let scheme = config.theme.scheme;
         let palette = ThemePalette::from_config(&config.theme, theme_mode);
         let config_path = crate::config::global_config_path();
@@ -198,7 +235,12 @@ impl App {
         let git_statuses = git::load_status_cache();
         let pr_statuses = crate::github::load_pr_cache();
         let hide_stale = load_hide_stale();
+        let stale_threshold_secs = stale_threshold_secs(&config.dashboard);
         let last_pane_id = load_last_pane_id();
+        let stall_tracker = config
+            .dashboard
+            .stall_threshold()
+            .map(|threshold| Arc::new(Mutex::new(StallTracker::new(threshold))));
 
         let mut app = Self {
             mux,
@@ -207,13 +249,17 @@ impl App {
             table_state: TableState::default(),
             selected_pane_id: None,
             current_worktree,
-            stale_threshold_secs: 60 * 60, // 60 minutes
+            stale_threshold_secs,
             config,
             should_quit: false,
             should_jump: false,
             sort_mode,
             view_mode: ViewMode::default(),
             preview: None,
+            preview_text: None,
+            preview_mode: PreviewMode::default(),
+            last_diff_preview_fetch: std::time::Instant::now(),
+            is_preview_fetching: Arc::new(AtomicBool::new(false)),
             preview_pane_id: None,
             input_mode: false,
             preview_scroll: None,
@@ -242,7 +288,11 @@ impl App {
             launch_session,
             filter_active: false,
             filter_text: String::new(),
-            pending_kill_pane_id: None,
+            pending_kill_pane_ids: Vec::new(),
+            marked_pane_ids: std::collections::HashSet::new(),
+            pending_prompt: None,
+            collapsed_projects: load_collapsed_projects(),
+            folded_projects: HashMap::new(),
             active_tab: DashboardTab::Agents,
             all_worktrees: Vec::new(),
             worktrees: Vec::new(),
@@ -265,7 +315,16 @@ impl App {
             status_message: None,
             show_sidebar_tip: crate::tips::should_show_sidebar_tip(),
             interrupted_pane_ids: std::collections::HashSet::new(),
+            stall_tracker,
+            is_agent_fetching: Arc::new(AtomicBool::new(false)),
+            agents_requested: 0,
+            agents_applied: 0,
+            stalled_pane_ids: std::collections::HashSet::new(),
             pending_command_palette: None,
+            pending_inbox: None,
+            pending_history: None,
+            pending_merge: None,
+            merge_run: None,
             sweep_progress: None,
         };
 
@@ -286,27 +345,36 @@ impl App {
         Ok(app)
     }
 
+    /// Reload agents right away (e.g. after an action changed them). The
+    /// periodic refresh uses `spawn_agent_fetch` instead.
     pub fn refresh(&mut self) {
-        // Load agents from StateStore with reconciliation against live pane state
-        self.all_agents = StateStore::new()
-            .and_then(|store| store.load_reconciled_agents(self.mux.as_ref()))
-            .unwrap_or_default();
-
-        // Load interrupted pane IDs from daemon runtime state
-        if let Ok(store) = StateStore::new() {
-            let backend = self.mux.name();
-            let instance = self.mux.instance_id();
-            let runtime = store.read_runtime(backend, &instance);
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            // Ignore stale runtime state (daemon not running for >15s)
-            if now.saturating_sub(runtime.updated_ts) <= 15 {
-                self.interrupted_pane_ids = runtime.interrupted_pane_ids;
-            } else {
-                self.interrupted_pane_ids.clear();
-            }
+        self.agents_requested += 1;
+        let snapshot = load_agent_snapshot(
+            self.agents_requested,
+            self.mux.as_ref(),
+            self.stall_tracker.as_deref(),
+        );
+        self.apply_agent_snapshot(snapshot);
+    }
+
+    /// Apply agents loaded by `refresh` or a background load, then kick off
+    /// any background fetches that are due.
+    pub(super) fn apply_agent_snapshot(&mut self, snapshot: AgentSnapshot) {
+        // A synchronous refresh may have landed while this one was loading
+        if snapshot.generation <= self.agents_applied {
+            return;
+        }
+        self.agents_applied = snapshot.generation;
+        self.all_agents = snapshot.agents;
+        // Forget marks on agents that have gone away
+        let all_agents = &self.all_agents;
+        self.marked_pane_ids
+            .retain(|id| all_agents.iter().any(|a| a.pane_id == *id));
+        if let Some(interrupted) = snapshot.interrupted_pane_ids {
+            self.interrupted_pane_ids = interrupted;
+        }
+        if let Some(stalled) = snapshot.stalled_pane_ids {
+            self.stalled_pane_ids = stalled;
         }
 
         // Cache repo roots for ALL agents before filtering (project picker needs all projects)
@@ -376,3 +444,50 @@ impl App {
         self.apply_filters();
     }
 }
+
+/// Load agents reconciled against live panes, plus the daemon's interrupted
+/// panes and stall samples. Slow enough (state files, pane captures) that the
+/// periodic refresh runs it off the UI thread.
+fn load_agent_snapshot(
+    generation: u64,
+    mux: &dyn Multiplexer,
+    stall_tracker: Option<&Mutex<StallTracker>>,
+) -> AgentSnapshot {
+    let store = StateStore::new();
+    let agents = store
+        .as_ref()
+        .ok()
+        .and_then(|store| store.load_reconciled_agents(mux).ok())
+        .unwrap_or_default();
+
+    // Load interrupted pane IDs from daemon runtime state
+    let interrupted_pane_ids = store.ok().map(|store| {
+        let runtime = store.read_runtime(mux.name(), &mux.instance_id());
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        // Ignore stale runtime state (daemon not running for >15s)
+        if now.saturating_sub(runtime.updated_ts) <= 15 {
+            runtime.interrupted_pane_ids
+        } else {
+            Default::default()
+        }
+    });
+
+    // Sample pane content for agents that stopped without a status hook
+    let stalled_pane_ids = stall_tracker.and_then(|tracker| {
+        tracker.lock().unwrap_or_else(|e| e.into_inner()).sample(
+            &agents,
+            std::time::Instant::now(),
+            |pane_id| mux.capture_pane(pane_id, 20),
+        )
+    });
+
+    AgentSnapshot {
+        generation,
+        agents,
+        interrupted_pane_ids,
+        stalled_pane_ids,
+    }
+}
@@ -1,10 +1,20 @@
 //! Preview pane management for the dashboard.
 
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ansi_to_tui::IntoText;
+use ratatui::text::Text;
+
+use super::super::ansi::strip_ansi_escapes;
+use super::super::diff::get_diff_content;
 use super::super::settings::save_preview_size;
 use super::App;
+use super::types::PreviewMode;
 
-/// Number of lines to capture from the agent's terminal for preview (scrollable history)
-pub const PREVIEW_LINES: u16 = 200;
+/// How often the diff preview is refreshed. Running git (and delta) is much
+/// slower than a pane capture, so it doesn't follow the capture interval.
+pub const DIFF_PREVIEW_INTERVAL: Duration = Duration::from_secs(2);
 
 impl App {
     /// Update the preview for the currently selected agent.
@@ -22,23 +32,92 @@ impl App {
         // Only fetch if selection changed
         if current_pane_id != self.preview_pane_id {
             self.preview_pane_id = current_pane_id.clone();
-            self.preview = current_pane_id
-                .as_ref()
-                .and_then(|pane_id| self.mux.capture_pane(pane_id, PREVIEW_LINES));
-            // Reset scroll position when selection changes
-            self.preview_scroll = None;
+            match self.preview_mode {
+                PreviewMode::Output => {
+                    let lines = self.config.dashboard.preview_lines();
+                    let content = current_pane_id
+                        .as_ref()
+                        .and_then(|pane_id| self.mux.capture_pane(pane_id, lines));
+                    self.set_preview(content);
+                    // Reset scroll position when selection changes
+                    self.preview_scroll = None;
+                }
+                PreviewMode::Diff => {
+                    // Diffs are fetched in the background; read them from the top
+                    self.set_preview(None);
+                    self.preview_scroll = Some(0);
+                    self.last_diff_preview_fetch =
+                        std::time::Instant::now() - DIFF_PREVIEW_INTERVAL;
+                    self.spawn_preview_fetch();
+                }
+            }
         }
     }
 
+    /// Switch the preview between terminal output and the worktree diff.
+    pub fn toggle_preview_mode(&mut self) {
+        self.preview_mode = match self.preview_mode {
+            PreviewMode::Output => PreviewMode::Diff,
+            PreviewMode::Diff => PreviewMode::Output,
+        };
+        // Fetch for the new mode as if the selection had changed
+        self.preview_pane_id = None;
+        self.update_preview();
+    }
+
     /// Force refresh the preview (used on periodic refresh)
     pub fn refresh_preview(&mut self) {
-        if !self.mux.supports_preview() {
+        if !self.mux.supports_preview() || self.preview_mode != PreviewMode::Output {
             return;
         }
-        self.preview = self
+        let lines = self.config.dashboard.preview_lines();
+        let content = self
             .preview_pane_id
             .as_ref()
-            .and_then(|pane_id| self.mux.capture_pane(pane_id, PREVIEW_LINES));
+            .and_then(|pane_id| self.mux.capture_pane(pane_id, lines));
+        self.follow_preview(content);
+    }
+
+    /// Apply a background fetch for `pane_id`. Returns false if it's for a
+    /// pane no longer selected, a mode no longer shown, or nothing changed, so
+    /// no redraw is needed.
+    pub(super) fn apply_preview(
+        &mut self,
+        mode: PreviewMode,
+        pane_id: &str,
+        content: Option<String>,
+    ) -> bool {
+        if mode != self.preview_mode
+            || self.preview_pane_id.as_deref() != Some(pane_id)
+            || self.preview == content
+        {
+            return false;
+        }
+        match mode {
+            PreviewMode::Output => self.follow_preview(content),
+            PreviewMode::Diff => self.set_preview(content),
+        }
+        true
+    }
+
+    /// Show new output like `tail -f`: the view stays at the bottom, or when
+    /// scrolled back, stays on the lines being read as new ones push them up.
+    fn follow_preview(&mut self, content: Option<String>) {
+        if let (Some(offset), Some(old), Some(new)) = (
+            self.preview_scroll,
+            self.preview.as_deref(),
+            content.as_deref(),
+        ) {
+            self.preview_scroll = Some(anchor_scroll(old, new, offset));
+        }
+        self.set_preview(content);
+    }
+
+    /// Store captured output, parsing its colors once here rather than on
+    /// every frame.
+    fn set_preview(&mut self, content: Option<String>) {
+        self.preview_text = content.as_deref().map(parse_preview);
+        self.preview = content;
     }
 
     /// Scroll preview up (toward older content). Returns the amount to scroll by.
@@ -75,3 +154,79 @@ impl App {
         save_preview_size(self.preview_size);
     }
 }
+
+/// Uncommitted changes in a worktree for the diff preview: `git diff --stat`,
+/// then the full diff including untracked files, colored like the diff view.
+pub(super) fn diff_preview(path: &PathBuf) -> Option<String> {
+    let diff = match get_diff_content(path, "HEAD", true, false) {
+        Ok((content, ..)) => content,
+        Err(e) => return Some(e),
+    };
+    if diff.trim().is_empty() {
+        return Some("No uncommitted changes".to_string());
+    }
+
+    let stat = std::process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["--no-pager", "diff", "--stat", "--color=always", "HEAD"])
+        .output()
+        .ok()?;
+    let stat = String::from_utf8_lossy(&stat.stdout);
+    // Untracked files only show up in the full diff
+    if stat.trim().is_empty() {
+        return Some(diff);
+    }
+    Some(format!("{}\n\n{}", stat.trim_end(), diff))
+}
+
+/// Where the lines shown at `offset` in `old` are in `new`. Output only moves
+/// up as it streams, so this searches upward; lines that have scrolled out
+/// of the capture leave the view at the top.
+fn anchor_scroll(old: &str, new: &str, offset: u16) -> u16 {
+    // Match a few lines so a repeated line (a blank, a prompt) doesn't anchor
+    const ANCHOR_LINES: usize = 3;
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let start = offset as usize;
+    let Some(anchor) = old.get(start..(start + ANCHOR_LINES).min(old.len())) else {
+        return offset;
+    };
+    if anchor.is_empty() {
+        return offset;
+    }
+    (0..=start.min(new.len().saturating_sub(anchor.len())))
+        .rev()
+        .find(|&i| new[i..].starts_with(anchor))
+        .map_or(0, |i| i as u16)
+}
+
+/// Parse captured pane output into styled text.
+fn parse_preview(content: &str) -> Text<'static> {
+    let trimmed = content.trim_end();
+    if trimmed.is_empty() {
+        return Text::raw("(empty output)");
+    }
+    trimmed.into_text().unwrap_or_else(|_| {
+        // Fallback: strip ANSI escapes to prevent raw control
+        // sequences from corrupting the terminal display
+        Text::raw(strip_ansi_escapes(trimmed))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_scroll_follows_lines_pushed_up() {
+        let old = "a\nb\nc\nd\ne\nf";
+        // Two new lines pushed "a" and "b" out of the capture
+        let new = "c\nd\ne\nf\ng\nh";
+        assert_eq!(anchor_scroll(old, new, 3), 1);
+        // The lines being read scrolled out: stay at the top
+        assert_eq!(anchor_scroll(old, new, 0), 0);
+        // Unchanged output keeps the offset
+        assert_eq!(anchor_scroll(old, old, 2), 2);
+    }
+}
@@ -1,10 +1,15 @@
 //! Data types for the dashboard application state.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
+use crate::config::MergeStrategy;
 use crate::git::GitStatus;
 use crate::github::{PrListEntry, PrSummary};
+use crate::multiplexer::AgentPane;
+use crate::notification::webhook::WebhookPayload;
+use crate::state::{StatusChange, StatusTimeline};
+use crate::ui::fuzzy_match;
 use crate::workflow::types::WorktreeInfo;
 
 use super::super::diff::DiffView;
@@ -14,6 +19,10 @@ use super::super::diff::DiffView;
 pub enum AppEvent {
     /// Terminal input event (from dedicated input thread)
     Terminal(crossterm::event::Event),
+    /// Agent list reloaded from the state store
+    AgentList(AgentSnapshot),
+    /// Preview content for a pane (mode it was fetched for, pane_id, content)
+    Preview(PreviewMode, String, Option<String>),
     /// Git status update for a worktree path
     GitStatus(PathBuf, GitStatus),
     /// PR status update for a repo root
@@ -30,6 +39,23 @@ pub enum AppEvent {
     SweepProgressUpdate(usize, usize, String),
     /// Sweep operation completed
     SweepComplete(Result<(), String>),
+    /// Message from a background action, shown in the footer
+    StatusMessage(String),
+    /// Line of output from a running merge
+    MergeOutput(String),
+    /// Merge process exited
+    MergeComplete(Result<(), String>),
+}
+
+/// Agent state loaded off the UI thread, applied by `App::apply_agent_snapshot`.
+pub struct AgentSnapshot {
+    /// Orders snapshots so a slow background load can't overwrite a newer one
+    pub generation: u64,
+    pub agents: Vec<AgentPane>,
+    /// Interrupted pane IDs from the daemon, or None if the state store was unreadable
+    pub interrupted_pane_ids: Option<HashSet<String>>,
+    /// Stalled pane IDs, or None if it wasn't time to sample
+    pub stalled_pane_ids: Option<HashSet<String>>,
 }
 
 use clap::ValueEnum;
@@ -50,6 +76,16 @@ pub enum ViewMode {
     Diff(Box<DiffView>),
 }
 
+/// What the agent preview pane shows
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewMode {
+    /// The agent's terminal output
+    #[default]
+    Output,
+    /// Uncommitted changes in the agent's worktree: `git diff --stat`, then the full diff
+    Diff,
+}
+
 /// A candidate worktree for bulk sweep cleanup.
 pub struct SweepCandidate {
     pub handle: String,
@@ -92,6 +128,24 @@ pub struct SweepProgress {
     pub handle: String,
 }
 
+/// A merge awaiting confirmation.
+pub struct MergePlan {
+    pub handle: String,
+    pub branch: String,
+    /// Strategy from the worktree's config
+    pub strategy: MergeStrategy,
+    pub is_dirty: bool,
+}
+
+/// A merge running (or finished) in the background.
+pub struct MergeRun {
+    pub handle: String,
+    /// Output so far, stdout and stderr interleaved
+    pub lines: Vec<String>,
+    /// Set once the merge has exited
+    pub result: Option<Result<(), String>>,
+}
+
 /// An entry in the project picker.
 pub struct ProjectEntry {
     pub name: String,
@@ -195,18 +249,6 @@ pub struct AddWorktreeState {
     pub pr_request_counter: u64,
 }
 
-/// Fuzzy subsequence match: every character in `query` must appear in `target` in order.
-/// "mergefail" matches "merge-fail-delete" because m-e-r-g-e-f-a-i-l appear in sequence.
-pub fn fuzzy_match(query: &str, target: &str) -> bool {
-    let mut target_chars = target.chars();
-    for qc in query.chars() {
-        if !target_chars.any(|tc| tc == qc) {
-            return false;
-        }
-    }
-    true
-}
-
 impl AddWorktreeState {
     /// Return indices into `branches` that match the current filter.
     /// Uses fuzzy subsequence matching. Available branches appear first, occupied last.
@@ -294,6 +336,25 @@ pub struct PaletteCommand {
     pub action: super::super::actions::Action,
 }
 
+/// State for the inbox modal.
+pub struct InboxState {
+    /// Recent notification events, newest first
+    pub events: Vec<WebhookPayload>,
+    /// Index of the first event shown
+    pub scroll: usize,
+}
+
+/// State for the agent history modal.
+pub struct HistoryState {
+    /// Worktree name of the agent
+    pub name: String,
+    pub timeline: StatusTimeline,
+    /// Recorded status transitions, newest first
+    pub changes: Vec<StatusChange>,
+    /// Index of the first transition shown
+    pub scroll: usize,
+}
+
 /// State for the command palette modal.
 pub struct CommandPaletteState {
     /// Available commands for the current context
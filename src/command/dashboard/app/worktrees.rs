@@ -911,7 +911,7 @@ impl App {
             .iter()
             .enumerate()
             .filter(|(_, b)| {
-                crate::command::dashboard::app::types::fuzzy_match(&lower, &b.to_lowercase())
+                crate::ui::fuzzy_match(&lower, &b.to_lowercase())
                     && !state.occupied_branches.contains(*b)
             })
             .map(|(i, _)| i)
@@ -1018,9 +1018,7 @@ impl App {
             .branches
             .iter()
             .enumerate()
-            .filter(|(_, b)| {
-                crate::command::dashboard::app::types::fuzzy_match(&lower, &b.to_lowercase())
-            })
+            .filter(|(_, b)| crate::ui::fuzzy_match(&lower, &b.to_lowercase()))
             .map(|(i, _)| i)
             .collect();
         if candidates.is_empty() {
@@ -1175,9 +1173,10 @@ impl App {
                     workflow::CreateArgs {
                         branch_name: &local_branch,
                         handle: &handle,
-                        base_branch: None,
+                        base_branch: pr_details.base_ref_name.as_deref(),
                         remote_branch: Some(&remote_branch),
                         pr_number: None,
+                        is_fork,
                         prompt: None,
                         options,
                         mode_override: None,
@@ -1185,6 +1184,7 @@ impl App {
                         is_explicit_name: false,
                         prompt_file_only: false,
                         fork_source: None,
+                        host_commands: None,
                     },
                 )?;
                 Ok(result.branch_name)
@@ -1237,6 +1237,7 @@ impl App {
                         base_branch: base_branch.as_deref(),
                         remote_branch: None,
                         pr_number: None,
+                        is_fork: false,
                         prompt: None,
                         options,
                         mode_override: None,
@@ -1244,6 +1245,7 @@ impl App {
                         is_explicit_name: false,
                         prompt_file_only: false,
                         fork_source: None,
+                        host_commands: None,
                     },
                 )?;
                 Ok(result.branch_name)
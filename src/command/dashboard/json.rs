@@ -0,0 +1,157 @@
+//! `workmux dashboard --json`: the dashboard's agent list as JSON, for
+//! external status bars (waybar, sketchybar) and scripts. `workmux serve`
+//! sends the same entries.
+//!
+//! PR info comes from the cache the dashboard keeps, so this never waits on
+//! `gh` and is cheap enough to poll.
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::git;
+use crate::github::{self, PrSummary};
+use crate::multiplexer::{AgentPane, AgentStatus, Multiplexer};
+use crate::state::StateStore;
+
+use super::agent::{elapsed_secs, extract_project_name};
+
+#[derive(Serialize)]
+pub struct AgentEntry {
+    backend: &'static str,
+    instance: String,
+    pane_id: String,
+    session: String,
+    window: String,
+    project: String,
+    path: PathBuf,
+    branch: Option<String>,
+    status: Option<AgentStatus>,
+    status_ts: Option<u64>,
+    elapsed_secs: Option<u64>,
+    title: Option<String>,
+    /// What the agent is asking for, while waiting
+    waiting_reason: Option<String>,
+    pr: Option<PrSummary>,
+}
+
+/// Print the reconciled agents, limited to the current session when
+/// `session_filter` is set.
+pub fn print(mux: &dyn Multiplexer, session_filter: bool) -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&entries(mux, session_filter)?)?
+    );
+    Ok(())
+}
+
+/// The reconciled agents with their branch and cached PR.
+pub fn entries(mux: &dyn Multiplexer, session_filter: bool) -> Result<Vec<AgentEntry>> {
+    let mut agents = StateStore::new()?.load_reconciled_agents(mux)?;
+    if session_filter && let Some(session) = mux.current_session() {
+        agents.retain(|a| a.session == session);
+    }
+
+    // Branch and repo root for each agent, resolved in parallel
+    let locations: Vec<(Option<String>, Option<PathBuf>)> = std::thread::scope(|s| {
+        let handles: Vec<_> = agents
+            .iter()
+            .map(|agent| {
+                s.spawn(|| {
+                    (
+                        git::get_current_branch_in(&agent.path).ok(),
+                        git::get_repo_root_for(&agent.path).ok(),
+                    )
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or((None, None)))
+            .collect()
+    });
+
+    let pr_cache = github::load_pr_cache();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let instance = mux.instance_id();
+
+    Ok(agents
+        .into_iter()
+        .zip(locations)
+        .map(|(agent, (branch, repo_root))| {
+            let pr = match (&branch, &repo_root) {
+                // You merge INTO main, not FROM it
+                (Some(b), Some(root)) if b != "main" && b != "master" => {
+                    pr_cache.get(root).and_then(|prs| prs.get(b)).cloned()
+                }
+                _ => None,
+            };
+            entry(agent, mux.name(), &instance, branch, pr, now)
+        })
+        .collect())
+}
+
+fn entry(
+    agent: AgentPane,
+    backend: &'static str,
+    instance: &str,
+    branch: Option<String>,
+    pr: Option<PrSummary>,
+    now: u64,
+) -> AgentEntry {
+    AgentEntry {
+        backend,
+        instance: instance.to_string(),
+        project: extract_project_name(&agent.path),
+        elapsed_secs: elapsed_secs(agent.status_ts, now),
+        pane_id: agent.pane_id,
+        session: agent.session,
+        window: agent.window_name,
+        path: agent.path,
+        branch,
+        status: agent.status,
+        status_ts: agent.status_ts,
+        title: agent.pane_title,
+        waiting_reason: agent.waiting_reason,
+        pr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_serializes_status_and_elapsed() {
+        let agent = AgentPane {
+            session: "main".to_string(),
+            window_name: "wm-login".to_string(),
+            pane_id: "%3".to_string(),
+            window_id: "@2".to_string(),
+            path: PathBuf::from("/src/app__worktrees/login"),
+            pane_title: Some("Fix login".to_string()),
+            waiting_reason: None,
+            status: Some(AgentStatus::Waiting),
+            status_ts: Some(1_000),
+            updated_ts: Some(1_000),
+        };
+        let json = serde_json::to_value(entry(
+            agent,
+            "tmux",
+            "/tmp/tmux-501/default",
+            None,
+            None,
+            1_090,
+        ))
+        .unwrap();
+        assert_eq!(json["status"], "waiting");
+        assert_eq!(json["elapsed_secs"], 90);
+        assert_eq!(json["window"], "wm-login");
+        assert_eq!(json["pr"], serde_json::Value::Null);
+    }
+}
@@ -42,6 +42,8 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         }
         KeyCode::Char('j') | KeyCode::Down => Some(Action::Next),
         KeyCode::Char('k') | KeyCode::Up => Some(Action::Previous),
+        KeyCode::Char('h') | KeyCode::Left => Some(Action::CollapseProject),
+        KeyCode::Char('l') | KeyCode::Right => Some(Action::ExpandProject),
         KeyCode::Enter => Some(Action::JumpToSelected),
         KeyCode::Tab => Some(Action::SwitchTab),
         KeyCode::Backspace => Some(Action::JumpToLast),
@@ -59,6 +61,7 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('+') | KeyCode::Char('=') => Some(Action::IncreasePreviewSize),
         KeyCode::Char('-') | KeyCode::Char('_') => Some(Action::DecreasePreviewSize),
         KeyCode::Char('d') => Some(Action::LoadWipDiff),
+        KeyCode::Char('v') => Some(Action::TogglePreviewMode),
         KeyCode::Char('c') => Some(Action::SendCommitDashboard),
         KeyCode::Char('m') => Some(Action::TriggerMergeDashboard),
         KeyCode::Char('T') => Some(Action::CycleColorScheme),
@@ -67,6 +70,13 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('O') => Some(Action::OpenPrChecks),
         KeyCode::Char('b') => Some(Action::ShowBaseBranchPicker),
         KeyCode::Char('X') => Some(Action::KillSelected),
+        KeyCode::Char(' ') => Some(Action::ToggleMark),
+        KeyCode::Char('S') => Some(Action::SendPrompt),
+        KeyCode::Char('C') => Some(Action::ClearStatus),
+        KeyCode::Char('K') => Some(Action::StopAgent),
+        KeyCode::Char('A') => Some(Action::RestartAgent),
+        KeyCode::Char('I') => Some(Action::ShowInbox),
+        KeyCode::Char('H') => Some(Action::ShowHistory),
         KeyCode::Char('r') => Some(Action::RemoveSelectedWorktree),
         KeyCode::Char('R') => Some(Action::StartSweep),
         KeyCode::Char(':') => Some(Action::ShowCommandPalette),
@@ -104,6 +114,7 @@ fn worktree_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('o') => Some(Action::OpenPr),
         KeyCode::Char('O') => Some(Action::OpenPrChecks),
         KeyCode::Char('r') => Some(Action::RemoveSelectedWorktree),
+        KeyCode::Char('m') => Some(Action::MergeSelectedWorktree),
         KeyCode::Char('c') => Some(Action::CloseSelectedWorktreeWindow),
         KeyCode::Char('R') => Some(Action::StartSweep),
         KeyCode::Char('s') => Some(Action::CycleWorktreeSortMode),
@@ -206,11 +217,13 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("Bksp", "Last agent"),
             ("p", "Peek agent (keep popup)"),
             ("s", "Cycle sort mode"),
+            ("h/l", "Fold/unfold project"),
             ("F", "Toggle session filter"),
             ("f", "Toggle stale filter"),
             ("i", "Enter input mode"),
             ("Ctrl+u/d", "Scroll preview"),
             ("+/-", "Resize preview"),
+            ("v", "Toggle diff preview"),
             ("d", "View diff"),
             ("c", "Commit changes"),
             ("m", "Merge branch"),
@@ -218,6 +231,13 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("o", "Open PR in browser"),
             ("O", "Open PR checks in browser"),
             ("X", "Kill agent"),
+            ("Space", "Mark agent"),
+            ("S", "Send prompt"),
+            ("C", "Clear status"),
+            ("K", "Stop agent process"),
+            ("A", "Restart agent"),
+            ("I", "Show inbox"),
+            ("H", "Show agent history"),
             ("r", "Remove worktree"),
             ("R", "Sweep cleanup"),
             ("/", "Filter agents"),
@@ -241,6 +261,7 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("O", "Open PR checks in browser"),
             ("a", "Add worktree"),
             ("r", "Remove worktree"),
+            ("m", "Merge worktree"),
             ("c", "Close mux window"),
             ("R", "Sweep cleanup"),
             ("s", "Cycle sort mode"),
@@ -376,4 +397,88 @@ mod tests {
             Some(Action::ToggleScopeFilter)
         );
     }
+
+    #[test]
+    fn test_batch_keys() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key(' ')),
+            Some(Action::ToggleMark)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('S')),
+            Some(Action::SendPrompt)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('C')),
+            Some(Action::ClearStatus)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('K')),
+            Some(Action::StopAgent)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('A')),
+            Some(Action::RestartAgent)
+        );
+    }
+
+    #[test]
+    fn test_inbox_key() {
+        let shift_i = KeyEvent::new(KeyCode::Char('I'), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, shift_i),
+            Some(Action::ShowInbox)
+        );
+    }
+
+    #[test]
+    fn test_history_key() {
+        let shift_h = KeyEvent::new(KeyCode::Char('H'), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, shift_h),
+            Some(Action::ShowHistory)
+        );
+    }
+
+    #[test]
+    fn test_fold_project_keys() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key(KeyCode::Left)),
+            Some(Action::CollapseProject)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key(KeyCode::Char('l'))),
+            Some(Action::ExpandProject)
+        );
+        // Input mode still forwards arrows to the agent
+        assert_eq!(
+            action_for_key(Context::DashboardInput, key(KeyCode::Left)),
+            Some(Action::SendKey("Left".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_toggle_preview_mode_key() {
+        let v = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, v),
+            Some(Action::TogglePreviewMode)
+        );
+        assert_eq!(action_for_key(Context::WorktreeNormal, v), None);
+    }
+
+    #[test]
+    fn test_merge_key_per_tab() {
+        let m = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, m),
+            Some(Action::TriggerMergeDashboard)
+        );
+        assert_eq!(
+            action_for_key(Context::WorktreeNormal, m),
+            Some(Action::MergeSelectedWorktree)
+        );
+    }
 }
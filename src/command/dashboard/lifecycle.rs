@@ -0,0 +1,148 @@
+//! Stopping and restarting the agent process in a pane, leaving the pane
+//! itself open.
+//!
+//! Agents run as children of the pane's shell, so they're found with
+//! `pgrep -P` and stopped with SIGTERM. A restarted agent resumes its
+//! conversation the way `workmux open -c` does: the session recorded for the
+//! worktree's branch, falling back to the agent's continue flag.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow, bail};
+
+use crate::cmd::Cmd;
+use crate::config::Config;
+use crate::multiplexer::conversation::latest_session_args;
+use crate::multiplexer::{Multiplexer, agent, util};
+use crate::state::{PaneKey, StateStore};
+
+/// How long to wait for an agent to exit, or a restarted one to start
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Stop the agent in a pane with SIGTERM and forget its state, so it leaves
+/// the dashboard. The pane stays open at its shell.
+pub fn stop(mux: &dyn Multiplexer, pane_id: &str) -> Result<()> {
+    let pid = shell_pid(mux, pane_id)?;
+    if !terminate_children(&pid)? {
+        bail!("no agent process running in pane {}", pane_id);
+    }
+    forget(mux, pane_id);
+    Ok(())
+}
+
+/// Stop the agent in a pane, if it's running, and start it again in the
+/// pane's shell, resuming its latest conversation in `worktree`.
+pub fn restart(mux: &dyn Multiplexer, pane_id: &str, worktree: &Path) -> Result<()> {
+    let (config, _) = Config::load_with_location_from(worktree, None)?;
+    if config.sandbox.is_enabled() {
+        bail!("restarting sandboxed agents isn't supported");
+    }
+
+    let pid = shell_pid(mux, pane_id)?;
+    if terminate_children(&pid)? && !wait_until(|| !has_children(&pid)) {
+        bail!("agent in pane {} didn't exit", pane_id);
+    }
+    forget(mux, pane_id);
+
+    let mut command = resume_command(&config, worktree);
+    if !config.secrets.is_empty() {
+        command = crate::secrets::wrap_command(&command);
+    }
+    mux.send_keys_to_agent(pane_id, &command, config.agent.as_deref())?;
+
+    // List the new agent right away rather than at its first status hook
+    if wait_until(|| has_children(&pid)) {
+        crate::state::persist_agent_update(mux, pane_id, None, None, None);
+    }
+    Ok(())
+}
+
+/// The agent command with flags to resume its conversation, like `open -c`:
+/// the session recorded for the worktree's branch when there is one,
+/// otherwise the agent's continue flag.
+fn resume_command(config: &Config, worktree: &Path) -> String {
+    let agent_command = config.agent.as_deref().unwrap_or("claude");
+    let flag = latest_session_args(agent_command, worktree).or_else(|| {
+        agent::resolve_profile_with_type(Some(agent_command), config.agent_type.as_deref())
+            .continue_flag()
+            .map(str::to_string)
+    });
+
+    // Leading space keeps the command out of shell history
+    let command = format!(" {}", agent_command);
+    match flag {
+        Some(flag) => util::inject_skip_permissions_flag(&command, &flag),
+        None => command,
+    }
+}
+
+fn shell_pid(mux: &dyn Multiplexer, pane_id: &str) -> Result<String> {
+    let info = mux
+        .get_live_pane_info(pane_id)?
+        .ok_or_else(|| anyhow!("pane {} not found", pane_id))?;
+    let pid = info
+        .pid
+        .ok_or_else(|| anyhow!("{} doesn't report pane processes", mux.name()))?;
+    Ok(pid.to_string())
+}
+
+/// SIGTERM the children of a shell. Returns false if it had none.
+fn terminate_children(pid: &str) -> Result<bool> {
+    Cmd::new("pkill").args(&["-TERM", "-P", pid]).run_as_check()
+}
+
+fn has_children(pid: &str) -> bool {
+    Cmd::new("pgrep")
+        .args(&["-P", pid])
+        .run_as_check()
+        .unwrap_or(false)
+}
+
+fn wait_until(done: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + WAIT;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+/// Clear a pane's status and drop its agent state.
+fn forget(mux: &dyn Multiplexer, pane_id: &str) {
+    let _ = mux.clear_status(pane_id);
+    if let Ok(store) = StateStore::new() {
+        let _ = store.delete_agent(&PaneKey {
+            backend: mux.name().to_string(),
+            instance: mux.instance_id(),
+            pane_id: pane_id.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_agent(agent: &str) -> Config {
+        Config {
+            agent: Some(agent.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resume_command_uses_continue_flag() {
+        let worktree = Path::new("/nonexistent/worktree");
+        assert_eq!(
+            resume_command(&config_with_agent("gemini"), worktree),
+            " gemini --resume"
+        );
+        assert_eq!(
+            resume_command(&config_with_agent("my-agent --fast"), worktree),
+            " my-agent --fast"
+        );
+    }
+}
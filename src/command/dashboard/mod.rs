@@ -13,6 +13,7 @@
 //! - `agent`: Pure helper functions for agent data extraction
 //! - `ansi`: ANSI escape sequence parsing and stripping
 //! - `diff`: Diff domain types and helper functions
+//! - `json`: `--json` output for status bars and scripts
 //! - `keymap`: Key-to-action mapping per context with help text
 //! - `settings`: Tmux-persisted dashboard settings
 //! - `sort`: Sort mode enum and tmux persistence
@@ -29,19 +30,27 @@ mod ansi;
 mod app;
 mod diff;
 mod diff_ops;
+pub mod json;
 mod keymap;
+mod lifecycle;
+pub mod popup;
 mod scope;
-mod settings;
+pub mod settings;
 mod sort;
 pub mod spinner;
+mod stall;
 pub mod ui;
 pub use app::DashboardTab;
 
 use anyhow::Result;
+use clap::ValueEnum;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind, MouseEventKind},
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    execute, queue,
+    terminal::{
+        BeginSynchronizedUpdate, EndSynchronizedUpdate, EnterAlternateScreen, LeaveAlternateScreen,
+        disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::backend::CrosstermBackend;
 use std::io;
@@ -125,7 +134,29 @@ pub fn run(
     open_diff: bool,
     session_filter: bool,
     tab: Option<DashboardTab>,
+    popup: bool,
+    json: bool,
 ) -> Result<()> {
+    if json {
+        return json::print(create_backend(detect_backend()).as_ref(), session_filter);
+    }
+    if popup {
+        let mut args = Vec::new();
+        if let Some(size) = cli_preview_size {
+            args.extend(["--preview-size".to_string(), size.to_string()]);
+        }
+        if open_diff {
+            args.push("--diff".to_string());
+        }
+        if session_filter {
+            args.push("--session".to_string());
+        }
+        if let Some(tab) = tab.and_then(|t| t.to_possible_value()) {
+            args.extend(["--tab".to_string(), tab.get_name().to_string()]);
+        }
+        return popup::open(&args);
+    }
+
     let mux = create_backend(detect_backend());
 
     // Check if multiplexer is running
@@ -197,9 +228,15 @@ pub fn run(
     let preview_refresh_interval_normal = Duration::from_millis(500);
     let preview_refresh_interval_input = Duration::from_millis(100);
     let mut last_preview_refresh = std::time::Instant::now();
+    // Frames are drawn only when something changed. Data loads run on
+    // background threads and arrive as events, so drawing never waits on them.
+    let mut needs_redraw = true;
 
     loop {
-        terminal.draw(|f| ui(f, &mut app))?;
+        if needs_redraw {
+            draw(&mut terminal, &mut app)?;
+            needs_redraw = false;
+        }
 
         // Calculate timeout: wake for the earliest timer deadline
         let current_preview_interval = if app.input_mode {
@@ -215,11 +252,11 @@ pub fn run(
         // Block until an event arrives OR the timeout fires
         match event_rx.recv_timeout(timeout) {
             Ok(event) => {
-                handle_event(&mut app, event, &mut last_preview_refresh);
+                needs_redraw |= handle_event(&mut app, event, &mut last_preview_refresh);
 
                 // Drain any other pending events to coalesce bursts
                 while let Ok(event) = event_rx.try_recv() {
-                    handle_event(&mut app, event, &mut last_preview_refresh);
+                    needs_redraw |= handle_event(&mut app, event, &mut last_preview_refresh);
                 }
             }
             Err(mpsc::RecvTimeoutError::Timeout) => {}
@@ -230,11 +267,12 @@ pub fn run(
             last_tick = std::time::Instant::now();
             // Advance spinner animation frame (wrap at frame count to avoid skip artifact)
             app.spinner_frame = (app.spinner_frame + 1) % SPINNER_FRAME_COUNT;
+            needs_redraw = true;
         }
 
         // Auto-refresh agent list every 2 seconds
         if last_refresh.elapsed() >= refresh_interval {
-            app.refresh();
+            app.spawn_agent_fetch();
             last_refresh = std::time::Instant::now();
         }
 
@@ -242,7 +280,7 @@ pub fn run(
         // Uses faster refresh rate in input mode (set at top of loop)
         if app.mux.supports_preview() && last_preview_refresh.elapsed() >= current_preview_interval
         {
-            app.refresh_preview();
+            app.spawn_preview_fetch();
             last_preview_refresh = std::time::Instant::now();
         }
 
@@ -269,11 +307,29 @@ pub fn run(
     Ok(())
 }
 
+/// Draw one frame inside a synchronized update, so terminals that support
+/// it show the frame all at once instead of as it's written.
+fn draw(
+    terminal: &mut ratatui::Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+) -> Result<()> {
+    queue!(terminal.backend_mut(), BeginSynchronizedUpdate)?;
+    terminal.draw(|f| ui(f, app))?;
+    execute!(terminal.backend_mut(), EndSynchronizedUpdate)?;
+    Ok(())
+}
+
 /// Handle a single AppEvent, dispatching terminal input or applying background data.
-fn handle_event(app: &mut App, event: AppEvent, last_preview_refresh: &mut std::time::Instant) {
+/// Returns whether the view needs redrawing.
+fn handle_event(
+    app: &mut App,
+    event: AppEvent,
+    last_preview_refresh: &mut std::time::Instant,
+) -> bool {
     match event {
         AppEvent::Terminal(terminal_event) => {
             handle_terminal_event(app, terminal_event, last_preview_refresh);
+            true
         }
         bg_event => app.apply_event(bg_event),
     }
@@ -318,11 +374,74 @@ fn handle_terminal_event(
     }
 
     // Kill confirmation popup - y confirms, anything else cancels
-    if app.pending_kill_pane_id.is_some() {
+    if !app.pending_kill_pane_ids.is_empty() {
         if key.code == crossterm::event::KeyCode::Char('y') {
             app.confirm_kill();
         } else {
-            app.pending_kill_pane_id = None;
+            app.pending_kill_pane_ids.clear();
+        }
+        return;
+    }
+
+    // Prompt input for the marked agents
+    if app.pending_prompt.is_some() {
+        match key.code {
+            crossterm::event::KeyCode::Enter => app.confirm_prompt(),
+            crossterm::event::KeyCode::Esc => app.pending_prompt = None,
+            crossterm::event::KeyCode::Backspace => {
+                if let Some(ref mut prompt) = app.pending_prompt {
+                    prompt.pop();
+                }
+            }
+            crossterm::event::KeyCode::Char(c) => {
+                if let Some(ref mut prompt) = app.pending_prompt {
+                    prompt.push(c);
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Inbox modal - j/k scroll, anything else closes
+    if app.pending_inbox.is_some() {
+        match key.code {
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                app.inbox_down()
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => app.inbox_up(),
+            _ => app.pending_inbox = None,
+        }
+        return;
+    }
+
+    // History modal - j/k scroll, anything else closes
+    if app.pending_history.is_some() {
+        match key.code {
+            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
+                app.history_down()
+            }
+            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
+                app.history_up()
+            }
+            _ => app.pending_history = None,
+        }
+        return;
+    }
+
+    // Merge output - keys wait for the merge, then any key closes
+    if app.merge_run.is_some() {
+        app.close_merge_run();
+        return;
+    }
+
+    // Merge confirmation modal
+    if app.pending_merge.is_some() {
+        match key.code {
+            crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Enter => {
+                app.confirm_merge()
+            }
+            _ => app.pending_merge = None,
         }
         return;
     }
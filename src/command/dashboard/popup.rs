@@ -0,0 +1,71 @@
+//! `workmux dashboard --popup`: show the dashboard in a tmux popup over the
+//! current window instead of taking over the pane.
+
+use anyhow::{Context, Result, bail};
+
+use crate::cmd::Cmd;
+use crate::multiplexer::{BackendType, detect_backend};
+use crate::shell::shell_quote;
+
+/// Popup size in cells, also used by the suggested key binding
+const WIDTH: &str = "100";
+const HEIGHT: &str = "30";
+
+/// A `~/.tmux.conf` line that opens the dashboard in a popup.
+pub fn binding() -> String {
+    format!(
+        "bind C-s display-popup -w {} -h {} -E \"workmux dashboard\"",
+        WIDTH, HEIGHT
+    )
+}
+
+/// Run `workmux dashboard <args>` in a popup over the current tmux window.
+/// Jumping to an agent closes the dashboard, and with it the popup.
+pub fn open(args: &[String]) -> Result<()> {
+    if detect_backend() != BackendType::Tmux || std::env::var("TMUX").is_err() {
+        bail!("--popup needs to run inside tmux");
+    }
+    let exe = std::env::current_exe().context("Failed to find the workmux executable")?;
+    let cwd = std::env::current_dir()?;
+    let command = popup_command(&exe.to_string_lossy(), args);
+    let cwd = cwd.to_string_lossy();
+    Cmd::new("tmux")
+        .args(&[
+            "display-popup",
+            "-E",
+            "-w",
+            WIDTH,
+            "-h",
+            HEIGHT,
+            "-d",
+            &cwd,
+            &command,
+        ])
+        .run()
+        .context("Failed to open the dashboard popup")?;
+    Ok(())
+}
+
+/// The shell command the popup runs.
+fn popup_command(exe: &str, args: &[String]) -> String {
+    std::iter::once(exe)
+        .chain(std::iter::once("dashboard"))
+        .chain(args.iter().map(String::as_str))
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn popup_command_forwards_arguments() {
+        let args = vec!["--tab".to_string(), "worktrees".to_string()];
+        assert_eq!(
+            popup_command("/opt/my tools/workmux", &args),
+            "'/opt/my tools/workmux' dashboard --tab worktrees"
+        );
+    }
+}
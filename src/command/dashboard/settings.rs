@@ -1,7 +1,36 @@
 //! Dashboard settings persistence using StateStore.
 
+use std::collections::HashSet;
+
+use crate::config::DashboardConfig;
 use crate::state::StateStore;
 
+/// Staleness threshold when neither config nor settings set one.
+const DEFAULT_STALE_AFTER_SECS: u64 = 60 * 60;
+
+/// Resolve the staleness threshold in seconds. A configured
+/// `dashboard.stale_after` is persisted, so the sidebar and dashboards
+/// started outside the project share it; otherwise the persisted value is
+/// used, falling back to an hour.
+pub fn stale_threshold_secs(config: &DashboardConfig) -> u64 {
+    let store = StateStore::new().ok();
+    let mut settings = store.as_ref().and_then(|s| s.load_settings().ok());
+    match config.stale_after().map(|d| d.as_secs()) {
+        Some(secs) => {
+            if let (Some(store), Some(settings)) = (&store, settings.as_mut())
+                && settings.stale_after_secs != Some(secs)
+            {
+                settings.stale_after_secs = Some(secs);
+                let _ = store.save_settings(settings);
+            }
+            secs
+        }
+        None => settings
+            .and_then(|s| s.stale_after_secs)
+            .unwrap_or(DEFAULT_STALE_AFTER_SECS),
+    }
+}
+
 /// Load hide_stale filter state from StateStore.
 pub fn load_hide_stale() -> bool {
     StateStore::new()
@@ -40,6 +69,27 @@ pub fn save_preview_size(size: u8) {
     }
 }
 
+/// Load the projects folded in project sort mode from StateStore.
+pub fn load_collapsed_projects() -> HashSet<String> {
+    StateStore::new()
+        .ok()
+        .and_then(|store| store.load_settings().ok())
+        .map(|s| s.collapsed_projects.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Save the projects folded in project sort mode to StateStore.
+pub fn save_collapsed_projects(projects: &HashSet<String>) {
+    if let Ok(store) = StateStore::new()
+        && let Ok(mut settings) = store.load_settings()
+    {
+        let mut projects: Vec<String> = projects.iter().cloned().collect();
+        projects.sort();
+        settings.collapsed_projects = projects;
+        let _ = store.save_settings(&settings);
+    }
+}
+
 /// Load last visited pane_id from StateStore.
 pub fn load_last_pane_id() -> Option<String> {
     StateStore::new()
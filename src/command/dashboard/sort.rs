@@ -1,5 +1,6 @@
 //! Sort mode logic for the dashboard agent list.
 
+use crate::github::CheckState;
 use crate::state::StateStore;
 
 /// Available sort modes for the agent list
@@ -12,6 +13,8 @@ pub enum SortMode {
     Project,
     /// Sort by duration since last status change (newest first)
     Recency,
+    /// Sort by PR check state (failing > pending > passing > no checks)
+    Checks,
     /// Natural tmux order (by pane_id)
     Natural,
 }
@@ -22,7 +25,8 @@ impl SortMode {
         match self {
             SortMode::Priority => SortMode::Project,
             SortMode::Project => SortMode::Recency,
-            SortMode::Recency => SortMode::Natural,
+            SortMode::Recency => SortMode::Checks,
+            SortMode::Checks => SortMode::Natural,
             SortMode::Natural => SortMode::Priority,
         }
     }
@@ -33,6 +37,7 @@ impl SortMode {
             SortMode::Priority => "priority",
             SortMode::Project => "project",
             SortMode::Recency => "recency",
+            SortMode::Checks => "checks",
             SortMode::Natural => "natural",
         }
     }
@@ -43,6 +48,7 @@ impl SortMode {
             SortMode::Priority => "priority",
             SortMode::Project => "project",
             SortMode::Recency => "recency",
+            SortMode::Checks => "checks",
             SortMode::Natural => "natural",
         }
     }
@@ -52,6 +58,7 @@ impl SortMode {
         match s.trim().to_lowercase().as_str() {
             "project" => SortMode::Project,
             "recency" => SortMode::Recency,
+            "checks" => SortMode::Checks,
             "natural" => SortMode::Natural,
             _ => SortMode::Priority, // Default fallback
         }
@@ -77,6 +84,16 @@ impl SortMode {
     }
 }
 
+/// Rank of a PR's checks for the checks sort mode (lower sorts first).
+pub fn checks_rank(checks: Option<&CheckState>) -> u8 {
+    match checks {
+        Some(CheckState::Failure { .. }) => 0,
+        Some(CheckState::Pending { .. }) => 1,
+        Some(CheckState::Success) => 2,
+        None => 3,
+    }
+}
+
 /// Available sort modes for the worktree list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum WorktreeSortMode {
@@ -131,3 +148,24 @@ impl WorktreeSortMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_rank_puts_failing_first() {
+        let failing = CheckState::Failure {
+            passed: 3,
+            total: 4,
+        };
+        let pending = CheckState::Pending {
+            passed: 1,
+            total: 4,
+        };
+        assert!(checks_rank(Some(&failing)) < checks_rank(Some(&pending)));
+        assert!(checks_rank(Some(&pending)) < checks_rank(Some(&CheckState::Success)));
+        assert!(checks_rank(Some(&CheckState::Success)) < checks_rank(None));
+        assert_eq!(SortMode::from_str("checks"), SortMode::Checks);
+    }
+}
@@ -0,0 +1,151 @@
+//! Stall detection for agents that stop without firing a status hook.
+//!
+//! Working agents' pane content is hashed every [`SAMPLE_INTERVAL`]. An agent
+//! whose output and state haven't changed for the configured threshold is
+//! marked as possibly stalled until either changes again.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::multiplexer::{AgentPane, AgentStatus};
+
+/// How often pane content is captured and hashed.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct StallTracker {
+    /// How long output must be unchanged before an agent counts as stalled
+    threshold: Duration,
+    last_sample: Option<Instant>,
+    /// pane_id -> (content hash, updated_ts, when either last changed)
+    entries: HashMap<String, (u64, u64, Instant)>,
+}
+
+impl StallTracker {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last_sample: None,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Sample working agents if the sample interval has passed. Returns the
+    /// pane IDs that look stalled, or None if it wasn't time to sample yet.
+    pub fn sample(
+        &mut self,
+        agents: &[AgentPane],
+        now: Instant,
+        capture: impl Fn(&str) -> Option<String>,
+    ) -> Option<HashSet<String>> {
+        if self
+            .last_sample
+            .is_some_and(|last| now.duration_since(last) < SAMPLE_INTERVAL)
+        {
+            return None;
+        }
+        self.last_sample = Some(now);
+
+        let working: Vec<&AgentPane> = agents
+            .iter()
+            .filter(|a| a.status == Some(AgentStatus::Working))
+            .collect();
+        self.entries
+            .retain(|id, _| working.iter().any(|a| a.pane_id == *id));
+
+        let mut stalled = HashSet::new();
+        for agent in working {
+            let Some(content) = capture(&agent.pane_id) else {
+                continue;
+            };
+            let mut hasher = DefaultHasher::new();
+            console::strip_ansi_codes(&content).trim().hash(&mut hasher);
+            let hash = hasher.finish();
+            let updated_ts = agent.updated_ts.unwrap_or(0);
+
+            match self.entries.get(&agent.pane_id) {
+                Some(&(prev_hash, prev_ts, since))
+                    if prev_hash == hash && prev_ts == updated_ts =>
+                {
+                    if now.duration_since(since) >= self.threshold {
+                        stalled.insert(agent.pane_id.clone());
+                    }
+                }
+                _ => {
+                    self.entries
+                        .insert(agent.pane_id.clone(), (hash, updated_ts, now));
+                }
+            }
+        }
+        Some(stalled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn working_agent(pane_id: &str, updated_ts: u64) -> AgentPane {
+        AgentPane {
+            session: String::new(),
+            window_name: String::new(),
+            pane_id: pane_id.to_string(),
+            window_id: String::new(),
+            path: PathBuf::new(),
+            pane_title: None,
+            waiting_reason: None,
+            status: Some(AgentStatus::Working),
+            status_ts: Some(100),
+            updated_ts: Some(updated_ts),
+        }
+    }
+
+    #[test]
+    fn stalled_after_threshold_of_unchanged_output() {
+        let mut tracker = StallTracker::new(Duration::from_secs(300));
+        let agents = vec![working_agent("%1", 1)];
+        let t0 = Instant::now();
+        let same = |_: &str| Some("thinking...".to_string());
+
+        assert_eq!(tracker.sample(&agents, t0, same), Some(HashSet::new()));
+        // Not due yet
+        assert_eq!(
+            tracker.sample(&agents, t0 + Duration::from_secs(10), same),
+            None
+        );
+        assert_eq!(
+            tracker.sample(&agents, t0 + Duration::from_secs(240), same),
+            Some(HashSet::new())
+        );
+        let stalled = tracker
+            .sample(&agents, t0 + Duration::from_secs(300), same)
+            .unwrap();
+        assert!(stalled.contains("%1"));
+    }
+
+    #[test]
+    fn output_or_state_change_resets_the_window() {
+        let mut tracker = StallTracker::new(Duration::from_secs(60));
+        let t0 = Instant::now();
+        let same = |_: &str| Some("output".to_string());
+
+        tracker.sample(&[working_agent("%1", 1)], t0, same);
+        // New hook activity resets the window even though the output is the same
+        let resumed = [working_agent("%1", 2)];
+        let stalled = tracker.sample(&resumed, t0 + Duration::from_secs(60), same);
+        assert_eq!(stalled, Some(HashSet::new()));
+
+        // Changed output resets it too
+        let stalled = tracker.sample(&resumed, t0 + Duration::from_secs(120), |_| {
+            Some("\x1b[1mmore output\x1b[0m".to_string())
+        });
+        assert_eq!(stalled, Some(HashSet::new()));
+        let stalled = tracker
+            .sample(&resumed, t0 + Duration::from_secs(180), |_| {
+                Some("more output".to_string())
+            })
+            .unwrap();
+        assert!(stalled.contains("%1"));
+    }
+}
@@ -1,6 +1,5 @@
 //! Dashboard view rendering (table, preview, footer).
 
-use ansi_to_tui::IntoText;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
@@ -11,8 +10,11 @@ use ratatui::{
 use std::collections::{BTreeMap, HashSet};
 
 use crate::agent_display::strip_oc_title_prefix;
+use crate::config::DashboardColumn;
+use crate::multiplexer::AgentStatus;
 
-use super::super::app::{App, DashboardTab};
+use super::super::app::{App, DashboardTab, PreviewMode};
+use super::super::sort::SortMode;
 use super::super::spinner::SPINNER_FRAMES;
 use super::format;
 use super::format::{format_git_status, format_pr_status, truncate};
@@ -132,6 +134,8 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
             DashboardTab::Agents => {
                 if app.filter_active {
                     f.render_widget(render_footer_filter(app), footer_area);
+                } else if app.pending_prompt.is_some() {
+                    f.render_widget(render_footer_prompt(app), footer_area);
                 } else if app.input_mode {
                     f.render_widget(render_footer_input(app), footer_area);
                 } else {
@@ -149,61 +153,79 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
     }
 }
 
+/// Pre-computed display data for one agent row.
+struct AgentRowData {
+    jump_key: Span<'static>,
+    project: String,
+    worktree_display: String,
+    worktree_base: String,
+    worktree_suffix: String,
+    is_main: bool,
+    is_current: bool,
+    branch: String,
+    git_spans: Vec<(String, Style)>,
+    pr_spans: Option<Vec<(String, Style)>>,
+    status_spans: Vec<(String, Style)>,
+    duration: String,
+    title: String,
+}
+
+fn spans_line(spans: Vec<(String, Style)>) -> Line<'static> {
+    Line::from(
+        spans
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn spans_width(spans: &[(String, Style)]) -> usize {
+    // chars().count() rather than len(): Nerd Font icons are multi-byte
+    spans.iter().map(|(text, _)| text.chars().count()).sum()
+}
+
 fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     // Check if we should show the PR column (only when at least one agent has a PR)
     let show_pr_column = app.has_any_pr();
     let show_check_counts = app.config.dashboard.show_check_counts();
+    let columns: Vec<DashboardColumn> = app
+        .config
+        .dashboard
+        .columns()
+        .into_iter()
+        .filter(|&column| column != DashboardColumn::Pr || show_pr_column)
+        .collect();
 
     // Check if git data is being refreshed
     let is_git_fetching = app
         .is_git_fetching
         .load(std::sync::atomic::Ordering::Relaxed);
 
-    // Build header with spinner in Git column when fetching
-    let git_header = if is_git_fetching {
-        let spinner = SPINNER_FRAMES[app.spinner_frame as usize % SPINNER_FRAMES.len()];
-        Line::from(vec![
-            Span::styled("Git ", Style::default().fg(app.palette.header).bold()),
-            Span::styled(spinner.to_string(), Style::default().fg(app.palette.dimmed)),
-        ])
-    } else {
-        Line::from(Span::styled(
-            "Git",
-            Style::default().fg(app.palette.header).bold(),
-        ))
-    };
-
-    // Build PR header with spinner when fetching
-    let pr_header = if app.is_pr_fetching() {
-        let spinner = SPINNER_FRAMES[app.spinner_frame as usize % SPINNER_FRAMES.len()];
-        Line::from(vec![
-            Span::styled("PR ", Style::default().fg(app.palette.header).bold()),
-            Span::styled(spinner.to_string(), Style::default().fg(app.palette.dimmed)),
-        ])
-    } else {
-        Line::from(Span::styled(
-            "PR",
-            Style::default().fg(app.palette.header).bold(),
-        ))
-    };
-
     let header_style = Style::default().fg(app.palette.header).bold();
-    let mut header_cells = vec![
-        Cell::from("#").style(header_style),
-        Cell::from("Project").style(header_style),
-        Cell::from("Worktree").style(header_style),
-        Cell::from(git_header),
-    ];
-
-    if show_pr_column {
-        header_cells.push(Cell::from(pr_header));
-    }
+    // Git and PR headers show a spinner while their data is fetched
+    let fetching_header = |label: &str, fetching: bool| {
+        if fetching {
+            let spinner = SPINNER_FRAMES[app.spinner_frame as usize % SPINNER_FRAMES.len()];
+            Line::from(vec![
+                Span::styled(format!("{} ", label), header_style),
+                Span::styled(spinner.to_string(), Style::default().fg(app.palette.dimmed)),
+            ])
+        } else {
+            Line::from(Span::styled(label.to_string(), header_style))
+        }
+    };
 
-    header_cells.extend(vec![
-        Cell::from("Status").style(header_style),
-        Cell::from("Time").style(header_style),
-        Cell::from("Title").style(header_style),
-    ]);
+    let mut header_cells = vec![Cell::from("#").style(header_style)];
+    header_cells.extend(columns.iter().map(|column| match column {
+        DashboardColumn::Project => Cell::from("Project").style(header_style),
+        DashboardColumn::Worktree => Cell::from("Worktree").style(header_style),
+        DashboardColumn::Branch => Cell::from("Branch").style(header_style),
+        DashboardColumn::Git => Cell::from(fetching_header("Git", is_git_fetching)),
+        DashboardColumn::Pr => Cell::from(fetching_header("PR", app.is_pr_fetching())),
+        DashboardColumn::Status => Cell::from("Status").style(header_style),
+        DashboardColumn::Elapsed => Cell::from("Time").style(header_style),
+        DashboardColumn::Title => Cell::from("Title").style(header_style),
+    }));
 
     let header = Row::new(header_cells).height(1);
 
@@ -225,7 +247,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     let mut window_positions: BTreeMap<(String, String), usize> = BTreeMap::new();
 
     // Pre-compute row data to calculate max widths
-    let row_data: Vec<_> = app
+    let row_data: Vec<AgentRowData> = app
         .agents
         .iter()
         .enumerate()
@@ -241,10 +263,17 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 String::new()
             };
 
-            let jump_key = if idx < 9 {
-                format!("{}", idx + 1)
+            let jump_key = if app.marked_pane_ids.contains(&agent.pane_id) {
+                Span::styled(
+                    "\u{25cf}",
+                    Style::default()
+                        .fg(app.palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if idx < 9 {
+                Span::raw(format!("{}", idx + 1))
             } else {
-                String::new()
+                Span::raw("")
             };
 
             let project = App::extract_project_name(agent);
@@ -268,14 +297,22 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 25usize.saturating_sub(pane_suffix.chars().count()),
             );
             let worktree_suffix = pane_suffix;
-            let title = agent
-                .pane_title
+            // A waiting agent shows what it's asking for instead of its title
+            let waiting_reason = agent
+                .waiting_reason
                 .as_ref()
-                .map(|t| {
-                    let t = strip_oc_title_prefix(t);
-                    t.strip_prefix("... ").unwrap_or(t).to_string()
-                })
-                .unwrap_or_default();
+                .filter(|_| agent.status == Some(AgentStatus::Waiting));
+            let title = match waiting_reason {
+                Some(reason) => reason.clone(),
+                None => agent
+                    .pane_title
+                    .as_ref()
+                    .map(|t| {
+                        let t = strip_oc_title_prefix(t);
+                        t.strip_prefix("... ").unwrap_or(t).to_string()
+                    })
+                    .unwrap_or_default(),
+            };
             let status_spans = app.get_status_display(agent);
             let duration = app
                 .get_elapsed(agent)
@@ -285,6 +322,9 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
             // Get git status for this worktree (may be None if not yet fetched)
             let git_status = app.git_statuses.get(&agent.path);
             let git_spans = format_git_status(git_status, app.spinner_frame, &app.palette);
+            let branch = git_status
+                .and_then(|s| s.branch.clone())
+                .unwrap_or_else(|| "-".to_string());
 
             // Get PR status for this agent (only if column is shown)
             let pr_spans = if show_pr_column {
@@ -299,7 +339,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 None
             };
 
-            (
+            AgentRowData {
                 jump_key,
                 project,
                 worktree_display,
@@ -307,19 +347,26 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 worktree_suffix,
                 is_main,
                 is_current,
+                branch,
                 git_spans,
                 pr_spans,
                 status_spans,
                 duration,
                 title,
-            )
+            }
         })
         .collect();
 
     // Calculate max project name width (with padding, capped)
+    // Project headers prefix the name with a fold arrow
+    let fold_arrow_width = if app.sort_mode == SortMode::Project {
+        2
+    } else {
+        0
+    };
     let max_project_width = row_data
         .iter()
-        .map(|(_, project, _, _, _, _, _, _, _, _, _, _)| project.len())
+        .map(|row| row.project.len() + fold_arrow_width)
         .max()
         .unwrap_or(5)
         .clamp(5, 20) // min 5, max 20
@@ -329,155 +376,207 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     // Use at least 8 to fit the "Worktree" header, at most 25 to keep layout compact
     let max_worktree_width = row_data
         .iter()
-        .map(|(_, _, worktree_display, _, _, _, _, _, _, _, _, _)| worktree_display.len())
+        .map(|row| row.worktree_display.len())
         .max()
         .unwrap_or(8)
         .clamp(8, 25)
         + 1; // padding
 
+    let max_branch_width = row_data
+        .iter()
+        .map(|row| row.branch.chars().count())
+        .max()
+        .unwrap_or(6)
+        .clamp(6, 30)
+        + 1; // padding
+
     // Calculate max git status width (sum of all span character counts)
-    // Use chars().count() instead of len() because Nerd Font icons are multi-byte
     let max_git_width = row_data
         .iter()
-        .map(|(_, _, _, _, _, _, _, git_spans, _, _, _, _)| {
-            git_spans
-                .iter()
-                .map(|(text, _)| text.chars().count())
-                .sum::<usize>()
-        })
+        .map(|row| spans_width(&row.git_spans))
         .max()
         .unwrap_or(4)
         .clamp(4, 30) // min 4, max 30 (increased for base branch)
         + 1; // padding
 
     // Calculate max PR status width (only if showing PR column)
-    let max_pr_width = if show_pr_column {
-        row_data
-            .iter()
-            .filter_map(|(_, _, _, _, _, _, _, _, pr_spans, _, _, _)| pr_spans.as_ref())
-            .map(|spans| {
-                spans
-                    .iter()
-                    .map(|(text, _)| text.chars().count())
-                    .sum::<usize>()
-            })
-            .max()
-            .unwrap_or(4)
-            .clamp(4, 20) // Accommodate check icons + counts + inline timer
-            + 1
-    } else {
-        0
-    };
+    let max_pr_width = row_data
+        .iter()
+        .filter_map(|row| row.pr_spans.as_deref())
+        .map(spans_width)
+        .max()
+        .unwrap_or(4)
+        .clamp(4, 20) // Accommodate check icons + counts + inline timer
+        + 1;
+
+    let grouping = project_grouping(app);
+    let grouped = app.sort_mode == SortMode::Project;
+    let group_style = Style::default().fg(app.palette.header).bold();
+    let count_style = Style::default().fg(app.palette.dimmed);
+    // Project headers go in the Project column, or the first one without it
+    let label_column = columns
+        .iter()
+        .position(|&c| c == DashboardColumn::Project)
+        .unwrap_or(0);
+    let count_column = columns.iter().position(|&c| c == DashboardColumn::Worktree);
 
     let rows: Vec<Row> = row_data
         .into_iter()
-        .map(
-            |(
-                jump_key,
-                project,
-                _worktree_display,
-                worktree_base,
-                worktree_suffix,
-                is_main,
-                is_current,
-                git_spans,
-                pr_spans,
-                status_spans,
-                duration,
-                title,
-            )| {
-                let worktree_style = if is_current {
-                    Style::default().fg(app.palette.current_worktree_fg)
-                } else if is_main {
-                    Style::default().fg(app.palette.dimmed)
-                } else {
-                    Style::default()
-                };
-
-                // Worktree name with dimmed pane suffix
-                let worktree_line = if worktree_suffix.is_empty() {
-                    Line::from(Span::styled(worktree_base, worktree_style))
-                } else {
-                    Line::from(vec![
-                        Span::styled(worktree_base, worktree_style),
-                        Span::styled(worktree_suffix, Style::default().fg(app.palette.dimmed)),
-                    ])
-                };
-
-                // Convert git spans to a Line
-                let git_line = Line::from(
-                    git_spans
-                        .into_iter()
-                        .map(|(text, style)| Span::styled(text, style))
-                        .collect::<Vec<_>>(),
-                );
-
-                let mut cells = vec![
-                    Cell::from(jump_key).style(Style::default().fg(app.palette.keycap)),
-                    Cell::from(project),
-                    Cell::from(worktree_line),
-                    Cell::from(git_line),
-                ];
-
-                // Add PR cell if column is shown
-                if let Some(pr_spans) = pr_spans {
-                    let pr_line = Line::from(
-                        pr_spans
-                            .into_iter()
-                            .map(|(text, style)| Span::styled(text, style))
-                            .collect::<Vec<_>>(),
-                    );
-                    cells.push(Cell::from(pr_line));
-                }
+        .zip(grouping.iter().copied())
+        .flat_map(|(row, group)| {
+            let jump_cell = Cell::from(row.jump_key).style(Style::default().fg(app.palette.keycap));
+            let status_line = spans_line(row.status_spans);
+
+            // A folded project is one row showing its most urgent agent's status
+            if let Grouping::Folded(count) = group {
+                let mut cells = vec![jump_cell];
+                cells.extend(columns.iter().enumerate().map(|(i, column)| {
+                    if i == label_column {
+                        Cell::from(format!("\u{25b8} {}", row.project)).style(group_style)
+                    } else if Some(i) == count_column {
+                        Cell::from(agent_count(count)).style(count_style)
+                    } else {
+                        match column {
+                            DashboardColumn::Status => Cell::from(status_line.clone()),
+                            DashboardColumn::Elapsed => Cell::from(row.duration.clone()),
+                            _ => Cell::from(""),
+                        }
+                    }
+                }));
+                return vec![Row::new(cells)];
+            }
 
-                let status_line = Line::from(
-                    status_spans
-                        .into_iter()
-                        .map(|(text, style)| Span::styled(text, style))
-                        .collect::<Vec<_>>(),
-                );
-                cells.extend(vec![
-                    Cell::from(status_line),
-                    Cell::from(duration),
-                    Cell::from(title),
-                ]);
-
-                let row = Row::new(cells);
-                // Subtle background for the active worktree row
-                if is_current {
-                    row.style(Style::default().bg(app.palette.current_row_bg))
-                } else {
-                    row
-                }
-            },
-        )
-        .collect();
+            let mut rows = Vec::new();
+            if let Grouping::First(count) = group {
+                let mut cells = vec![Cell::from("")];
+                cells.extend((0..columns.len()).map(|i| {
+                    if i == label_column {
+                        Cell::from(format!("\u{25be} {}", row.project)).style(group_style)
+                    } else if Some(i) == count_column {
+                        Cell::from(agent_count(count)).style(count_style)
+                    } else {
+                        Cell::from("")
+                    }
+                }));
+                rows.push(Row::new(cells));
+            }
+            // Grouped agents are listed under their project's header
+            let project = if grouped { String::new() } else { row.project };
 
-    // Build column constraints conditionally based on whether PR column is shown
-    let mut constraints = vec![
-        Constraint::Length(2),                         // #: jump key
-        Constraint::Length(max_project_width as u16),  // Project: auto-sized
-        Constraint::Length(max_worktree_width as u16), // Worktree: auto-sized
-        Constraint::Length(max_git_width as u16),      // Git: auto-sized
-    ];
+            let worktree_style = if row.is_current {
+                Style::default().fg(app.palette.current_worktree_fg)
+            } else if row.is_main {
+                Style::default().fg(app.palette.dimmed)
+            } else {
+                Style::default()
+            };
 
-    if show_pr_column {
-        constraints.push(Constraint::Length(max_pr_width as u16)); // PR: auto-sized
-    }
+            // Worktree name with dimmed pane suffix
+            let worktree_line = if row.worktree_suffix.is_empty() {
+                Line::from(Span::styled(row.worktree_base, worktree_style))
+            } else {
+                Line::from(vec![
+                    Span::styled(row.worktree_base, worktree_style),
+                    Span::styled(row.worktree_suffix, Style::default().fg(app.palette.dimmed)),
+                ])
+            };
+            let git_line = spans_line(row.git_spans);
+            let pr_line = spans_line(row.pr_spans.unwrap_or_default());
+
+            let mut cells = vec![jump_cell];
+            cells.extend(columns.iter().map(|column| match column {
+                DashboardColumn::Project => Cell::from(project.clone()),
+                DashboardColumn::Worktree => Cell::from(worktree_line.clone()),
+                DashboardColumn::Branch => Cell::from(row.branch.clone()),
+                DashboardColumn::Git => Cell::from(git_line.clone()),
+                DashboardColumn::Pr => Cell::from(pr_line.clone()),
+                DashboardColumn::Status => Cell::from(status_line.clone()),
+                DashboardColumn::Elapsed => Cell::from(row.duration.clone()),
+                DashboardColumn::Title => Cell::from(row.title.clone()),
+            }));
+
+            let table_row = Row::new(cells);
+            // Subtle background for the active worktree row
+            rows.push(if row.is_current {
+                table_row.style(Style::default().bg(app.palette.current_row_bg))
+            } else {
+                table_row
+            });
+            rows
+        })
+        .collect();
 
-    constraints.extend(vec![
-        Constraint::Length(8),  // Status: fixed (icons)
-        Constraint::Length(10), // Time: HH:MM:SS + padding
-        Constraint::Fill(1),    // Title: takes remaining space
-    ]);
+    let mut constraints = vec![Constraint::Length(2)]; // #: jump key or mark
+    constraints.extend(columns.iter().map(|column| match column {
+        DashboardColumn::Project => Constraint::Length(max_project_width as u16),
+        DashboardColumn::Worktree => Constraint::Length(max_worktree_width as u16),
+        DashboardColumn::Branch => Constraint::Length(max_branch_width as u16),
+        DashboardColumn::Git => Constraint::Length(max_git_width as u16),
+        DashboardColumn::Pr => Constraint::Length(max_pr_width as u16),
+        DashboardColumn::Status => Constraint::Length(8), // fixed (icons)
+        DashboardColumn::Elapsed => Constraint::Length(10), // HH:MM:SS + padding
+        DashboardColumn::Title => Constraint::Fill(1),    // takes remaining space
+    }));
 
     let table = Table::new(rows, constraints)
         .header(header)
         .block(Block::default())
-        .row_highlight_style(Style::default().bg(app.palette.highlight_row_bg))
+        .row_highlight_style(app.palette.highlight_style())
         .highlight_symbol("> ");
 
-    f.render_stateful_widget(table, area, &mut app.table_state);
+    // Selection indexes agents; header rows shift them down the table
+    let mut table_state = app.table_state.clone();
+    table_state.select(app.table_state.selected().map(|idx| {
+        let headers = grouping
+            .iter()
+            .take(idx + 1)
+            .filter(|g| matches!(g, Grouping::First(_)))
+            .count();
+        idx + headers
+    }));
+    f.render_stateful_widget(table, area, &mut table_state);
+    *app.table_state.offset_mut() = table_state.offset();
+}
+
+/// Where an agent's row sits among the project groups of project sort mode.
+#[derive(Clone, Copy)]
+enum Grouping {
+    /// Ungrouped, or not the first agent of its project
+    None,
+    /// First agent of an expanded project with this many agents, under a header
+    First(usize),
+    /// Row standing for a folded project with this many agents
+    Folded(usize),
+}
+
+fn project_grouping(app: &App) -> Vec<Grouping> {
+    if app.sort_mode != SortMode::Project {
+        return vec![Grouping::None; app.agents.len()];
+    }
+    let projects: Vec<String> = app.agents.iter().map(App::extract_project_name).collect();
+    projects
+        .iter()
+        .enumerate()
+        .map(|(idx, project)| {
+            if idx > 0 && projects[idx - 1] == *project {
+                return Grouping::None;
+            }
+            match app.folded_projects.get(project) {
+                Some(&count) => Grouping::Folded(count),
+                None => {
+                    Grouping::First(projects[idx..].iter().take_while(|p| *p == project).count())
+                }
+            }
+        })
+        .collect()
+}
+
+fn agent_count(count: usize) -> String {
+    if count == 1 {
+        "1 agent".to_string()
+    } else {
+        format!("{} agents", count)
+    }
 }
 
 fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
@@ -500,8 +599,17 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
         )
     } else if let Some(agent) = selected_agent {
         let worktree_name = app.extract_worktree_name(agent).0;
+        let label = match app.preview_mode {
+            PreviewMode::Output => "Preview",
+            PreviewMode::Diff => "Diff",
+        };
+        // Output follows the pane unless scrolled back
+        let follow_hint = match (app.preview_mode, app.preview_scroll) {
+            (PreviewMode::Output, Some(_)) => "(scrolled, Ctrl+d to follow) ",
+            _ => "",
+        };
         (
-            format!(" Preview: {} ", worktree_name),
+            format!(" {}: {} {}", label, worktree_name, follow_hint),
             Style::default().fg(app.palette.header),
             Style::default().fg(app.palette.border),
         )
@@ -537,41 +645,36 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
     // Update preview height for scroll calculations
     app.preview_height = inner_area.height;
 
-    // Get preview content or show placeholder
-    let (text, line_count) = match (&app.preview, selected_agent) {
-        (Some(preview), Some(_)) => {
-            let trimmed = preview.trim_end();
-            if trimmed.is_empty() {
-                (Text::raw("(empty output)"), 1u16)
-            } else {
-                // Parse ANSI escape sequences to get colored text
-                match trimmed.into_text() {
-                    Ok(text) => {
-                        let count = text.lines.len() as u16;
-                        (text, count)
-                    }
-                    Err(_) => {
-                        // Fallback: strip ANSI escapes to prevent raw control
-                        // sequences from corrupting the terminal display
-                        let safe = super::super::ansi::strip_ansi_escapes(trimmed);
-                        let count = safe.lines().count() as u16;
-                        (Text::raw(safe), count)
-                    }
-                }
-            }
+    // Get preview content (parsed when captured) or show placeholder
+    let placeholder;
+    let lines: &[Line] = match (&app.preview_text, selected_agent) {
+        (Some(text), Some(_)) => &text.lines,
+        (None, Some(_)) => {
+            placeholder = Text::raw(match app.preview_mode {
+                PreviewMode::Output => "(pane not available)",
+                PreviewMode::Diff => "(loading diff...)",
+            });
+            &placeholder.lines
+        }
+        (_, None) => {
+            placeholder = Text::raw("(no agent selected)");
+            &placeholder.lines
         }
-        (None, Some(_)) => (Text::raw("(pane not available)"), 1),
-        (_, None) => (Text::raw("(no agent selected)"), 1),
     };
+    let line_count = lines.len() as u16;
 
     // Update line count for scroll calculations
     app.preview_line_count = line_count;
 
     // Calculate scroll offset: use manual scroll if set, otherwise auto-scroll to bottom
     let max_scroll = line_count.saturating_sub(inner_area.height);
-    let scroll_offset = app.preview_scroll.unwrap_or(max_scroll);
+    let scroll_offset = app.preview_scroll.unwrap_or(max_scroll).min(max_scroll) as usize;
 
-    let paragraph = Paragraph::new(text).block(block).scroll((scroll_offset, 0));
+    // Only the visible lines are handed to the widget; the full capture is
+    // up to `dashboard.preview_lines` long
+    let visible_end = (scroll_offset + inner_area.height as usize).min(lines.len());
+    let visible = lines[scroll_offset..visible_end].to_vec();
+    let paragraph = Paragraph::new(Text::from(visible)).block(block);
 
     f.render_widget(paragraph, area);
 }
@@ -597,6 +700,29 @@ fn render_footer_filter<'a>(app: &'a App) -> Paragraph<'a> {
     ]))
 }
 
+/// Prompt input footer, for sending one prompt to the marked agents
+fn render_footer_prompt<'a>(app: &'a App) -> Paragraph<'a> {
+    let label = match app.marked_pane_ids.len() {
+        0 => "  Send: ".to_string(),
+        n => format!("  Send to {n}: "),
+    };
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            label,
+            Style::default()
+                .fg(app.palette.keycap)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(app.pending_prompt.as_deref().unwrap_or_default()),
+        Span::styled("_", Style::default().fg(app.palette.keycap)),
+        Span::raw("  "),
+        Span::styled("Enter", Style::default().fg(app.palette.dimmed)),
+        Span::raw(" send  "),
+        Span::styled("Esc", Style::default().fg(app.palette.dimmed)),
+        Span::raw(" cancel"),
+    ]))
+}
+
 /// Input mode footer
 fn render_footer_input<'a>(app: &'a App) -> Paragraph<'a> {
     Paragraph::new(Line::from(vec![
@@ -646,6 +772,15 @@ fn render_footer_normal(f: &mut Frame, app: &App, area: Rect) {
     let stale_active = stale == "hidden";
 
     let mut s: Vec<Span<'static>> = vec![Span::raw("  ")];
+    if !app.marked_pane_ids.is_empty() {
+        s.push(Span::styled(
+            format!("{} marked", app.marked_pane_ids.len()),
+            active_style,
+        ));
+        s.push(pipe());
+        s.extend(cmd("S".into(), "Prompt".into()));
+        s.push(pipe());
+    }
     s.extend(cmd("i".into(), "Input".into()));
     s.push(pipe());
     s.extend(cmd("d".into(), "Diff".into()));
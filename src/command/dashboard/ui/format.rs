@@ -202,22 +202,34 @@ pub fn format_pr_status(
             if let Some(ref checks) = pr.checks {
                 let check_icons = nerdfont::check_icons();
                 let (check_icon, check_color, counts) = match checks {
-                    CheckState::Success => (check_icons.success.to_string(), palette.success, None),
+                    CheckState::Success => (
+                        check_icons.success.to_string(),
+                        palette.check_success(),
+                        None,
+                    ),
                     CheckState::Failure { passed, total } => (
                         check_icons.failure.to_string(),
-                        palette.danger,
+                        palette.check_failure(),
                         Some((*passed, *total)),
                     ),
                     CheckState::Pending { passed, total } => {
                         let frame = SPINNER_FRAMES[spinner_frame as usize % SPINNER_FRAMES.len()];
-                        (frame.to_string(), palette.accent, Some((*passed, *total)))
+                        (
+                            frame.to_string(),
+                            palette.check_pending(),
+                            Some((*passed, *total)),
+                        )
                     }
                 };
 
                 spans.push((" ".to_string(), Style::default()));
                 spans.push((check_icon, Style::default().fg(check_color)));
 
-                if show_check_counts && let Some((passed, total)) = counts {
+                // Counts are unknown (0/0) when only the overall state was fetched
+                if show_check_counts
+                    && let Some((passed, total)) = counts
+                    && total > 0
+                {
                     spans.push((
                         format!(" {}/{}", passed, total),
                         Style::default().fg(check_color),
@@ -8,6 +8,11 @@ use ratatui::{
     widgets::{Block, Cell, Clear, Paragraph, Row, Table},
 };
 
+use crate::config::{MergeStrategy, NotificationEvent};
+use crate::multiplexer::AgentStatus;
+
+use super::super::agent::{format_age, format_duration};
+use super::super::ansi;
 use super::super::app::{App, DashboardTab, ViewMode};
 use super::super::keymap::{Context, help_rows};
 
@@ -78,8 +83,12 @@ pub fn render_confirm_kill(f: &mut Frame, app: &App) {
         .border_type(ratatui::widgets::BorderType::Rounded)
         .border_style(Style::default().fg(palette.help_border));
 
+    let question = match app.pending_kill_pane_ids.len() {
+        1 => " Kill working agent? ".to_string(),
+        n => format!(" Kill {n} agents? "),
+    };
     let text = Line::from(vec![
-        Span::styled(" Kill working agent? ", Style::default().fg(palette.text)),
+        Span::styled(question, Style::default().fg(palette.text)),
         Span::styled(
             "y",
             Style::default()
@@ -230,6 +239,155 @@ pub fn render_confirm_remove(f: &mut Frame, app: &App) {
     f.render_widget(paragraph, popup_area);
 }
 
+/// Render the merge confirmation modal.
+pub fn render_confirm_merge(f: &mut Frame, app: &App) {
+    let Some(ref plan) = app.pending_merge else {
+        return;
+    };
+    let palette = &app.palette;
+
+    let bold = |s: &str| {
+        Span::styled(
+            s.to_string(),
+            Style::default()
+                .fg(palette.text)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let dim = |s: &str| Span::styled(s.to_string(), Style::default().fg(palette.dimmed));
+
+    let strategy = match plan.strategy {
+        MergeStrategy::Merge => "merge commit",
+        MergeStrategy::Rebase => "rebase",
+        MergeStrategy::Squash => "squash",
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!(" Merge {}?", plan.branch),
+            Style::default().fg(palette.text),
+        )]),
+        Line::from(""),
+        Line::from(dim(&format!(" Strategy: {}", strategy))),
+        Line::from(dim(" Worktree, window and branch are removed.")),
+    ];
+    if plan.is_dirty {
+        lines.push(Line::from(vec![Span::styled(
+            " Has uncommitted changes.",
+            Style::default().fg(palette.danger),
+        )]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::raw(" "),
+        bold("y"),
+        dim(" merge  "),
+        bold("n"),
+        dim(" cancel"),
+    ]));
+
+    let height = lines.len() as u16 + 2;
+    let width = lines
+        .iter()
+        .map(|l| l.width() as u16 + 3)
+        .max()
+        .unwrap_or(0)
+        .max(44);
+
+    let area = f.area();
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width: width.min(area.width),
+        height: height.min(area.height),
+    };
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(palette.help_border));
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+/// Render the output of a running or finished merge.
+pub fn render_merge_output(f: &mut Frame, app: &App) {
+    let Some(ref run) = app.merge_run else {
+        return;
+    };
+    let palette = &app.palette;
+
+    let area = f.area();
+    let width = area.width.saturating_sub(8).min(100);
+    let height = area.height.saturating_sub(6).min(24);
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let (status, color) = match run.result {
+        None => ("running", palette.info),
+        Some(Ok(())) => ("done", palette.success),
+        Some(Err(_)) => ("failed", palette.danger),
+    };
+    let footer = if run.result.is_some() {
+        Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("any key", Style::default().fg(palette.dimmed)),
+            Span::styled(" to close ", Style::default().fg(palette.help_muted)),
+        ])
+    } else {
+        Line::from(Span::styled(
+            " waiting for merge to finish ",
+            Style::default().fg(palette.help_muted),
+        ))
+    };
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(palette.help_border))
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!("Merge {}", run.handle),
+                Style::default()
+                    .fg(palette.header)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" {} ", status), Style::default().fg(color)),
+        ]))
+        .title_bottom(footer);
+
+    // Follow the output: show the last lines that fit
+    let visible = height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = run
+        .lines
+        .iter()
+        .skip(run.lines.len().saturating_sub(visible))
+        .map(|line| {
+            Line::from(Span::styled(
+                line.clone(),
+                Style::default().fg(palette.text),
+            ))
+        })
+        .collect();
+    if let Some(Err(ref e)) = run.result
+        && lines.len() < visible
+    {
+        lines.push(Line::from(Span::styled(
+            e.clone(),
+            Style::default().fg(palette.danger),
+        )));
+    }
+
+    let paragraph = Paragraph::new(Text::from(lines)).block(block);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
 /// Render the help overlay.
 pub fn render_help(f: &mut Frame, app: &App) {
     let ctx = get_help_context(app);
@@ -300,6 +458,224 @@ pub fn render_help(f: &mut Frame, app: &App) {
     f.render_widget(table, popup_area);
 }
 
+/// Render the inbox of recent agent notifications.
+pub fn render_inbox(f: &mut Frame, app: &App) {
+    let Some(ref inbox) = app.pending_inbox else {
+        return;
+    };
+    let palette = &app.palette;
+
+    let area = f.area();
+    let width = area.width.saturating_sub(8).min(100);
+    let height = (inbox.events.len().max(1) as u16 + 4).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(palette.help_border))
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                "Inbox",
+                Style::default()
+                    .fg(palette.header)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default()),
+        ]))
+        .title_bottom(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("j/k", Style::default().fg(palette.dimmed)),
+            Span::styled(" scroll  ", Style::default().fg(palette.help_muted)),
+            Span::styled("any key", Style::default().fg(palette.dimmed)),
+            Span::styled(" to close ", Style::default().fg(palette.help_muted)),
+        ]));
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut rows: Vec<Row> = vec![Row::new(vec![Cell::from("")])];
+    if inbox.events.is_empty() {
+        rows.push(Row::new(vec![
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(Span::styled(
+                "No notifications in the last day",
+                Style::default().fg(palette.dimmed),
+            )),
+        ]));
+    }
+    rows.extend(inbox.events.iter().skip(inbox.scroll).map(|event| {
+        let (icon, color) = match event.event {
+            NotificationEvent::Waiting => (app.config.status_icons.waiting(), palette.waiting()),
+            NotificationEvent::Done => (app.config.status_icons.done(), palette.done()),
+            NotificationEvent::Merged => ("\u{21c4}", palette.info),
+        };
+        let icon: Vec<Span> = ansi::parse_tmux_styles(icon, Style::default().fg(color))
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect();
+        let mut message = vec![Span::styled(
+            event.message.clone(),
+            Style::default().fg(palette.text),
+        )];
+        if !event.project.is_empty() {
+            message.push(Span::styled(
+                format!("  {}", event.project),
+                Style::default().fg(palette.dimmed),
+            ));
+        }
+        Row::new(vec![
+            Cell::from(Span::styled(
+                format!("{:>4}", format_age(now.saturating_sub(event.timestamp))),
+                Style::default().fg(palette.dimmed),
+            )),
+            Cell::from(Line::from(icon)),
+            Cell::from(Line::from(message)),
+        ])
+    }));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ],
+    )
+    .block(block);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(table, popup_area);
+}
+
+/// Render the selected agent's status history.
+pub fn render_history(f: &mut Frame, app: &App) {
+    let Some(ref history) = app.pending_history else {
+        return;
+    };
+    let palette = &app.palette;
+
+    let area = f.area();
+    let width = area.width.saturating_sub(8).min(60);
+    let height = (history.changes.len().max(1) as u16 + 10).min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(palette.help_border))
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!("History: {}", history.name),
+                Style::default()
+                    .fg(palette.header)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default()),
+        ]))
+        .title_bottom(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("j/k", Style::default().fg(palette.dimmed)),
+            Span::styled(" scroll  ", Style::default().fg(palette.help_muted)),
+            Span::styled("any key", Style::default().fg(palette.dimmed)),
+            Span::styled(" to close ", Style::default().fg(palette.help_muted)),
+        ]));
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let ago = |ts: Option<u64>, none: &str| {
+        ts.map(|ts| format!("{} ago", format_age(now.saturating_sub(ts))))
+            .unwrap_or_else(|| none.to_string())
+    };
+    let timeline = &history.timeline;
+    let summary = [
+        ("Started", ago(timeline.started, "unknown")),
+        ("First waiting", ago(timeline.first_waiting, "never")),
+        ("Done", ago(timeline.done, "not yet")),
+        ("Working time", format_duration(timeline.working_secs)),
+    ];
+
+    let mut rows: Vec<Row> = vec![Row::new(vec![Cell::from("")])];
+    rows.extend(summary.into_iter().map(|(label, value)| {
+        Row::new(vec![
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(Line::from(vec![
+                Span::styled(
+                    format!("{:<15}", label),
+                    Style::default().fg(palette.dimmed),
+                ),
+                Span::styled(value, Style::default().fg(palette.text)),
+            ])),
+        ])
+    }));
+    rows.push(Row::new(vec![Cell::from("")]));
+    if history.changes.is_empty() {
+        rows.push(Row::new(vec![
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(Span::styled(
+                "No status changes recorded",
+                Style::default().fg(palette.dimmed),
+            )),
+        ]));
+    }
+    rows.extend(history.changes.iter().skip(history.scroll).map(|change| {
+        let (icon, color, label) = match change.status {
+            AgentStatus::Working => (
+                app.config.status_icons.working(),
+                palette.working(),
+                "working",
+            ),
+            AgentStatus::Waiting => (
+                app.config.status_icons.waiting(),
+                palette.waiting(),
+                "waiting",
+            ),
+            AgentStatus::Done => (app.config.status_icons.done(), palette.done(), "done"),
+        };
+        let icon: Vec<Span> = ansi::parse_tmux_styles(icon, Style::default().fg(color))
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect();
+        Row::new(vec![
+            Cell::from(Span::styled(
+                format!("{:>4}", format_age(now.saturating_sub(change.ts))),
+                Style::default().fg(palette.dimmed),
+            )),
+            Cell::from(Line::from(icon)),
+            Cell::from(Span::styled(label, Style::default().fg(color))),
+        ])
+    }));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ],
+    )
+    .block(block);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(table, popup_area);
+}
+
 /// Render the sweep progress overlay.
 pub fn render_sweep_progress(f: &mut Frame, app: &App) {
     let Some(ref progress) = app.sweep_progress else {
@@ -29,7 +29,8 @@ pub use self::dashboard::render_dashboard;
 pub use self::diff::render_diff_view;
 pub use self::help::{
     render_add_worktree, render_base_picker, render_command_palette, render_confirm_kill,
-    render_confirm_remove, render_help, render_project_picker, render_sweep, render_sweep_progress,
+    render_confirm_merge, render_confirm_remove, render_help, render_history, render_inbox,
+    render_merge_output, render_project_picker, render_sweep, render_sweep_progress,
 };
 
 /// Main UI entry point - renders the appropriate view based on app state.
@@ -43,13 +44,17 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     // Render overlays on top
     let has_modal = app.sweep_progress.is_some()
         || app.show_help
-        || app.pending_kill_pane_id.is_some()
+        || !app.pending_kill_pane_ids.is_empty()
         || app.pending_remove.is_some()
         || app.pending_base_picker.is_some()
         || app.pending_project_picker.is_some()
         || app.pending_sweep.is_some()
         || app.pending_add_worktree.is_some()
-        || app.pending_command_palette.is_some();
+        || app.pending_command_palette.is_some()
+        || app.pending_inbox.is_some()
+        || app.pending_history.is_some()
+        || app.pending_merge.is_some()
+        || app.merge_run.is_some();
 
     if has_modal {
         dim_buffer(f);
@@ -59,12 +64,20 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         render_sweep_progress(f, app);
     } else if app.show_help {
         render_help(f, app);
-    } else if app.pending_kill_pane_id.is_some() {
+    } else if !app.pending_kill_pane_ids.is_empty() {
         render_confirm_kill(f, app);
     } else if app.pending_remove.is_some() {
         render_confirm_remove(f, app);
+    } else if app.merge_run.is_some() {
+        render_merge_output(f, app);
+    } else if app.pending_merge.is_some() {
+        render_confirm_merge(f, app);
     } else if app.pending_command_palette.is_some() {
         render_command_palette(f, app);
+    } else if app.pending_inbox.is_some() {
+        render_inbox(f, app);
+    } else if app.pending_history.is_some() {
+        render_history(f, app);
     } else if app.pending_base_picker.is_some() {
         render_base_picker(f, app);
     } else if app.pending_project_picker.is_some() {
@@ -338,7 +338,7 @@ pub fn render_worktree_table(f: &mut Frame, app: &mut App, area: Rect) {
     let table = Table::new(rows, constraints)
         .header(header)
         .block(Block::default())
-        .row_highlight_style(Style::default().bg(app.palette.highlight_row_bg))
+        .row_highlight_style(app.palette.highlight_style())
         .highlight_symbol("> ");
 
     f.render_stateful_widget(table, area, &mut app.worktree_table_state);
@@ -524,9 +524,15 @@ fn render_info_panel(
             use crate::github::CheckState;
             let check_icons = crate::nerdfont::check_icons();
             let (check_icon, check_color) = match checks {
-                CheckState::Success => (check_icons.success.to_string(), app.palette.success),
-                CheckState::Failure { .. } => (check_icons.failure.to_string(), app.palette.danger),
-                CheckState::Pending { .. } => (check_icons.pending.to_string(), app.palette.accent),
+                CheckState::Success => {
+                    (check_icons.success.to_string(), app.palette.check_success())
+                }
+                CheckState::Failure { .. } => {
+                    (check_icons.failure.to_string(), app.palette.check_failure())
+                }
+                CheckState::Pending { .. } => {
+                    (check_icons.pending.to_string(), app.palette.check_pending())
+                }
             };
             pr_spans.push(Span::styled(" ", text_style));
             pr_spans.push(Span::styled(check_icon, Style::default().fg(check_color)));
@@ -0,0 +1,260 @@
+//! `workmux digest`: a summary of recent agent activity, optionally emailed.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+
+use crate::config::{Config, NotificationEvent};
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
+use crate::notification::webhook::WebhookPayload;
+use crate::state::StateStore;
+use crate::util::format_compact_age;
+
+/// An agent currently waiting for input
+struct WaitingAgent {
+    handle: String,
+    reason: Option<String>,
+    /// Unix time the agent started waiting
+    since: Option<u64>,
+}
+
+pub fn run(hours: u64, send: bool) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let events = crate::notification::history::load_since(now.saturating_sub(hours * 3600))?;
+    let waiting = waiting_agents();
+    let (subject, body) = build_digest(&events, &waiting, hours, now);
+
+    if !send {
+        println!("{}\n\n{}", subject, body);
+        return Ok(());
+    }
+
+    let config = Config::load(None)?;
+    let email = config.notifications.email.as_ref().ok_or_else(|| {
+        anyhow!("No email configured. Set notifications.email in your global config")
+    })?;
+    if events.is_empty() && waiting.is_empty() {
+        println!("Nothing to report, no email sent");
+        return Ok(());
+    }
+    crate::notification::email::send(email, &subject, &body)?;
+    println!("Sent digest to {}", email.to);
+    Ok(())
+}
+
+fn waiting_agents() -> Vec<WaitingAgent> {
+    let mux = create_backend(detect_backend());
+    let Ok(store) = StateStore::new() else {
+        return Vec::new();
+    };
+    waiting_agents_in(&store, mux.as_ref())
+}
+
+fn waiting_agents_in(store: &StateStore, mux: &dyn Multiplexer) -> Vec<WaitingAgent> {
+    // Reconcile against live panes when a multiplexer is reachable, even if
+    // it has no agents: stored ones may belong to a crashed server or another
+    // one. From cron there is usually no multiplexer to ask, so report the
+    // stored agents instead.
+    let reachable = !mux.is_headless() && mux.is_running().unwrap_or(false);
+    let reconciled = if reachable {
+        store.load_reconciled_agents(mux).ok()
+    } else {
+        None
+    };
+    let agents: Vec<_> = match reconciled {
+        Some(agents) => agents
+            .into_iter()
+            .map(|a| (a.path, a.status, a.waiting_reason, a.status_ts))
+            .collect(),
+        None => store
+            .list_all_agents()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|a| (a.workdir, a.status, a.waiting_reason, a.status_ts))
+            .collect(),
+    };
+    agents
+        .into_iter()
+        .filter(|(_, status, _, _)| *status == Some(AgentStatus::Waiting))
+        .map(|(path, _, reason, since)| {
+            let root = crate::git::get_repo_root_for(&path).unwrap_or(path);
+            WaitingAgent {
+                handle: root
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                reason,
+                since,
+            }
+        })
+        .collect()
+}
+
+/// Subject and plain-text body summarizing `events` and `waiting` agents.
+fn build_digest(
+    events: &[WebhookPayload],
+    waiting: &[WaitingAgent],
+    hours: u64,
+    now: u64,
+) -> (String, String) {
+    let ago = |ts: u64| format_compact_age(now.saturating_sub(ts));
+    let with_project = |name: &str, project: &str| {
+        if project.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} ({})", name, project)
+        }
+    };
+
+    let completed: Vec<String> = events
+        .iter()
+        .filter(|e| e.event == NotificationEvent::Done)
+        .map(|e| {
+            format!(
+                "  {}, {} ago",
+                with_project(&e.handle, &e.project),
+                ago(e.timestamp)
+            )
+        })
+        .collect();
+    let merged: Vec<String> = events
+        .iter()
+        .filter(|e| e.event == NotificationEvent::Merged)
+        .map(|e| {
+            let what = match (&e.branch, &e.target) {
+                (Some(branch), Some(target)) => format!("{} into {}", branch, target),
+                _ => e.handle.clone(),
+            };
+            format!(
+                "  {}, {} ago",
+                with_project(&what, &e.project),
+                ago(e.timestamp)
+            )
+        })
+        .collect();
+    let waiting_lines: Vec<String> = waiting
+        .iter()
+        .map(|agent| {
+            let mut line = format!(
+                "  {}: {}",
+                agent.handle,
+                agent.reason.as_deref().unwrap_or("needs your input")
+            );
+            if let Some(since) = agent.since {
+                line.push_str(&format!(", waiting {}", ago(since)));
+            }
+            line
+        })
+        .collect();
+
+    let subject = format!(
+        "workmux digest: {} done, {} merged, {} waiting",
+        completed.len(),
+        merged.len(),
+        waiting_lines.len()
+    );
+    let mut sections = vec![format!("Agent activity in the last {}h.", hours)];
+    for (title, lines) in [
+        ("Completed", &completed),
+        ("Merged", &merged),
+        ("Waiting for input", &waiting_lines),
+    ] {
+        if !lines.is_empty() {
+            sections.push(format!("{} ({})\n{}", title, lines.len(), lines.join("\n")));
+        }
+    }
+    (subject, sections.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event: NotificationEvent, handle: &str, timestamp: u64) -> WebhookPayload {
+        WebhookPayload {
+            event,
+            handle: handle.to_string(),
+            project: "shop".to_string(),
+            branch: None,
+            target: None,
+            message: String::new(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn build_digest_groups_events() {
+        let mut merge = event(NotificationEvent::Merged, "api", 9_000);
+        merge.branch = Some("api".to_string());
+        merge.target = Some("main".to_string());
+        let events = vec![
+            event(NotificationEvent::Waiting, "api", 1_000),
+            event(NotificationEvent::Done, "api", 3_600),
+            merge,
+        ];
+        let waiting = vec![WaitingAgent {
+            handle: "web".to_string(),
+            reason: Some("Claude needs your permission to use Bash".to_string()),
+            since: Some(9_600),
+        }];
+
+        let (subject, body) = build_digest(&events, &waiting, 24, 10_800);
+        assert_eq!(subject, "workmux digest: 1 done, 1 merged, 1 waiting");
+        assert_eq!(
+            body,
+            "Agent activity in the last 24h.\n\n\
+             Completed (1)\n  api (shop), 2h ago\n\n\
+             Merged (1)\n  api into main (shop), 30m ago\n\n\
+             Waiting for input (1)\n  web: Claude needs your permission to use Bash, waiting 20m"
+        );
+    }
+
+    #[test]
+    fn build_digest_empty() {
+        let (subject, body) = build_digest(&[], &[], 12, 100);
+        assert_eq!(subject, "workmux digest: 0 done, 0 merged, 0 waiting");
+        assert_eq!(body, "Agent activity in the last 12h.");
+    }
+
+    #[test]
+    fn waiting_agents_without_a_live_multiplexer_uses_stored_agents() {
+        use crate::multiplexer::headless::HeadlessBackend;
+        use crate::state::{AgentState, PaneKey};
+
+        // As from cron: the agent was registered by a tmux server, and no
+        // multiplexer is reachable to reconcile it against
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = StateStore::with_path(dir.path().to_path_buf()).unwrap();
+        store
+            .upsert_agent(&AgentState {
+                pane_key: PaneKey {
+                    backend: "tmux".to_string(),
+                    instance: "/tmp/tmux-1000/default".to_string(),
+                    pane_id: "%3".to_string(),
+                },
+                workdir: std::path::PathBuf::from("/nonexistent/repo/api"),
+                status: Some(AgentStatus::Waiting),
+                status_ts: Some(100),
+                pane_title: None,
+                waiting_reason: Some("Claude needs your permission".to_string()),
+                pane_pid: 1,
+                command: "node".to_string(),
+                updated_ts: 100,
+                window_name: None,
+                session_name: None,
+                boot_id: None,
+            })
+            .unwrap();
+
+        let waiting = waiting_agents_in(&store, &HeadlessBackend::new());
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].handle, "api");
+        assert_eq!(
+            waiting[0].reason.as_deref(),
+            Some("Claude needs your permission")
+        );
+    }
+}
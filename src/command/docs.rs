@@ -1,4 +1,5 @@
 use anyhow::Result;
+use clap::{Arg, Command};
 
 const README: &str = include_str!("../../README.md");
 
@@ -6,3 +7,286 @@ pub fn run() -> Result<()> {
     crate::markdown::display(README, README);
     Ok(())
 }
+
+/// Output format for `workmux docs generate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Man,
+    Markdown,
+}
+
+/// Print a reference for every visible command, built from the clap
+/// definitions so it can't drift from the CLI.
+pub fn generate(mut cli: Command, format: Format) -> Result<()> {
+    // Building sets each subcommand's full name and propagates global args
+    cli.build();
+    match format {
+        Format::Man => print!("{}", to_man(&cli)),
+        Format::Markdown => {
+            let md = to_markdown(&cli);
+            crate::markdown::display(&md, &md);
+        }
+    }
+    Ok(())
+}
+
+/// Visible subcommands, depth first, with the top-level command first.
+fn commands(cli: &Command) -> Vec<&Command> {
+    fn walk<'a>(cmd: &'a Command, out: &mut Vec<&'a Command>) {
+        out.push(cmd);
+        for sub in cmd.get_subcommands() {
+            if !sub.is_hide_set() && sub.get_name() != "help" {
+                walk(sub, out);
+            }
+        }
+    }
+    let mut out = Vec::new();
+    walk(cli, &mut out);
+    out
+}
+
+fn full_name(cmd: &Command) -> String {
+    cmd.get_bin_name()
+        .unwrap_or_else(|| cmd.get_name())
+        .to_string()
+}
+
+fn usage(cmd: &Command) -> String {
+    let usage = cmd.clone().render_usage().to_string();
+    usage
+        .strip_prefix("Usage: ")
+        .unwrap_or(&usage)
+        .trim()
+        .to_string()
+}
+
+/// Arguments to document for `cmd`. Global options, `--help` and
+/// `--version` are only listed on the top-level command.
+fn visible_args(cmd: &Command, top_level: bool) -> Vec<&Arg> {
+    cmd.get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .filter(|arg| {
+            top_level
+                || !(arg.is_global_set() || matches!(arg.get_id().as_str(), "help" | "version"))
+        })
+        .collect()
+}
+
+fn arg_signature(arg: &Arg) -> String {
+    let value_names: Vec<String> = match arg.get_value_names() {
+        Some(names) => names.iter().map(|n| n.to_string()).collect(),
+        None => vec![arg.get_id().as_str().to_uppercase()],
+    };
+    if arg.is_positional() {
+        return value_names
+            .iter()
+            .map(|n| format!("<{}>", n))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("-{}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("--{}", long));
+    }
+    let mut signature = names.join(", ");
+    if arg.get_action().takes_values() {
+        for name in value_names {
+            signature.push_str(&format!(" <{}>", name));
+        }
+    }
+    signature
+}
+
+fn arg_help(arg: &Arg) -> String {
+    let mut help = arg
+        .get_long_help()
+        .or(arg.get_help())
+        .map(|h| h.to_string())
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let possible: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    // Flags report true/false, which isn't worth listing
+    if !possible.is_empty() && arg.get_action().takes_values() {
+        help.push_str(&format!(" [possible values: {}]", possible.join(", ")));
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|v| v.to_string_lossy().into_owned())
+        .collect();
+    if !defaults.is_empty() && arg.get_action().takes_values() {
+        help.push_str(&format!(" [default: {}]", defaults.join(", ")));
+    }
+    help.trim().to_string()
+}
+
+fn about(cmd: &Command) -> String {
+    cmd.get_long_about()
+        .or(cmd.get_about())
+        .map(|a| a.to_string())
+        .unwrap_or_default()
+}
+
+fn to_markdown(cli: &Command) -> String {
+    let mut out = String::new();
+    for (i, cmd) in commands(cli).into_iter().enumerate() {
+        let top_level = i == 0;
+        let heading = if top_level { "#" } else { "##" };
+        out.push_str(&format!("{} {}\n\n", heading, full_name(cmd)));
+        let about = about(cmd);
+        if !about.is_empty() {
+            out.push_str(&format!("{}\n\n", about));
+        }
+        out.push_str(&format!("```bash\n{}\n```\n\n", usage(cmd)));
+
+        let args = visible_args(cmd, top_level);
+        let (positional, options): (Vec<&Arg>, Vec<&Arg>) =
+            args.into_iter().partition(|arg| arg.is_positional());
+        for (title, list) in [("Arguments", positional), ("Options", options)] {
+            if list.is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "{} {}\n\n| Argument | Description |\n| --- | --- |\n",
+                if top_level { "##" } else { "###" },
+                title
+            ));
+            for arg in list {
+                out.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    arg_signature(arg).replace('|', "\\|"),
+                    arg_help(arg).replace('|', "\\|")
+                ));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Escape text for roff: backslashes, hyphens, and control characters at
+/// the start of a line.
+fn roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+            if escaped.starts_with('.') || escaped.starts_with('\'') {
+                format!("\\&{}", escaped)
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_man(cli: &Command) -> String {
+    let name = cli.get_name();
+    let version = cli.get_version().unwrap_or_default();
+    let mut out = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"\n",
+        name.to_uppercase(),
+        name,
+        version
+    );
+    out.push_str(&format!(
+        ".SH NAME\n{} \\- {}\n",
+        name,
+        roff(&cli.get_about().map(|a| a.to_string()).unwrap_or_default())
+    ));
+
+    out.push_str(&format!(".SH SYNOPSIS\n\\fB{}\\fR\n", roff(&usage(cli))));
+    out.push_str(".SH OPTIONS\n");
+    push_man_args(&mut out, cli, true);
+
+    out.push_str(".SH COMMANDS\n");
+    for cmd in commands(cli).into_iter().skip(1) {
+        out.push_str(&format!(".SS \"{}\"\n", roff(&full_name(cmd))));
+        let about = about(cmd);
+        if !about.is_empty() {
+            out.push_str(&format!("{}\n", roff(&about)));
+        }
+        out.push_str(&format!(".PP\n\\fB{}\\fR\n", roff(&usage(cmd))));
+        push_man_args(&mut out, cmd, false);
+    }
+    out
+}
+
+fn push_man_args(out: &mut String, cmd: &Command, top_level: bool) {
+    for arg in visible_args(cmd, top_level) {
+        out.push_str(&format!(
+            ".TP\n\\fB{}\\fR\n{}\n",
+            roff(&arg_signature(arg)),
+            roff(&arg_help(arg))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Command {
+        let mut cmd = Command::new("tool")
+            .version("1.2.3")
+            .about("A sample tool")
+            .arg(
+                Arg::new("repo")
+                    .long("repo")
+                    .global(true)
+                    .help("Target repository"),
+            )
+            .subcommand(
+                Command::new("add")
+                    .about("Add a thing")
+                    .arg(Arg::new("name").required(true).help("Thing name"))
+                    .arg(
+                        Arg::new("mode")
+                            .long("mode")
+                            .value_parser(["fast", "slow"])
+                            .default_value("fast")
+                            .help("How to add | quickly"),
+                    ),
+            )
+            .subcommand(Command::new("_internal").hide(true));
+        cmd.build();
+        cmd
+    }
+
+    #[test]
+    fn markdown_lists_visible_commands_and_args() {
+        let md = to_markdown(&sample());
+        assert!(md.starts_with("# tool\n\nA sample tool\n"));
+        assert!(md.contains("## tool add\n\nAdd a thing\n"));
+        assert!(md.contains("| `<NAME>` | Thing name |"));
+        assert!(md.contains(
+            "| `--mode <MODE>` | How to add \\| quickly [possible values: fast, slow] [default: fast] |"
+        ));
+        // Global options only on the top-level command
+        assert_eq!(md.matches("`--repo <REPO>`").count(), 1);
+        assert_eq!(md.matches("`-h, --help`").count(), 1);
+        assert!(!md.contains("_internal"));
+    }
+
+    #[test]
+    fn man_page_escapes_roff() {
+        let man = to_man(&sample());
+        assert!(man.starts_with(".TH TOOL 1 \"\" \"tool 1.2.3\" \"User Commands\"\n"));
+        assert!(man.contains(".SH NAME\ntool \\- A sample tool\n"));
+        assert!(man.contains(".SH OPTIONS\n.TP\n\\fB\\-\\-repo <REPO>\\fR\n"));
+        assert!(man.contains(".SS \"tool add\"\n"));
+        assert!(man.contains("\\fB\\-\\-mode <MODE>\\fR"));
+        assert!(!man.contains("_internal"));
+        assert_eq!(roff(".hidden\n\\x"), "\\&.hidden\n\\ex");
+    }
+}
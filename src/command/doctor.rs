@@ -0,0 +1,332 @@
+//! `workmux doctor`: check the tools and setup workmux depends on.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Result, bail};
+use console::style;
+
+use crate::agent_setup::{self, StatusCheck};
+use crate::config::{self, Config, SandboxBackend};
+use crate::multiplexer::{BackendType, create_backend, detect_backend_with_reason};
+
+/// Oldest git with every worktree subcommand workmux uses (`worktree move`)
+const MIN_GIT_VERSION: (u32, u32) = (2, 17);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Pass,
+    /// Not a problem, just information (e.g. an optional tool that isn't needed)
+    Info,
+    Warn,
+    Fail,
+}
+
+struct Check {
+    name: String,
+    level: Level,
+    detail: String,
+    /// How to fix a warning or failure
+    hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, level: Level, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            level,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+pub fn run() -> Result<()> {
+    let config = Config::load(None);
+
+    let mut checks = vec![check_multiplexer(), check_git()];
+    checks.extend(check_tools(config.as_ref().ok()));
+    checks.extend(check_agent_hooks());
+    checks.push(check_state_dir());
+    checks.push(check_config(config));
+
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in &checks {
+        let icon = match check.level {
+            Level::Pass => style("✓").green(),
+            Level::Info => style("-").dim(),
+            Level::Warn => style("!").yellow(),
+            Level::Fail => style("✗").red(),
+        };
+        println!("{} {:width$}  {}", icon, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("  {:width$}  {}", "", style(format!("→ {}", hint)).dim());
+        }
+    }
+
+    println!();
+    let (warnings, failures) = count_problems(&checks);
+    match (warnings, failures) {
+        (0, 0) => println!("All checks passed"),
+        (_, 0) => println!("{} warning(s), no failures", warnings),
+        _ => bail!("{} check(s) failed", failures),
+    }
+    Ok(())
+}
+
+fn count_problems(checks: &[Check]) -> (usize, usize) {
+    let count = |level| checks.iter().filter(|c| c.level == level).count();
+    (count(Level::Warn), count(Level::Fail))
+}
+
+fn check_multiplexer() -> Check {
+    let choice = detect_backend_with_reason();
+    let backend = choice.backend;
+    let name = backend.to_string();
+    if backend == BackendType::None {
+        return Check::new(
+            "multiplexer",
+            Level::Pass,
+            format!(
+                "none (headless: windows and panes are skipped; {})",
+                choice.reason
+            ),
+        );
+    }
+    // iTerm2 is driven through a Python script rather than a CLI
+    let program = match backend {
+        BackendType::ITerm2 => crate::multiplexer::iterm2::python(),
+        _ => name.clone(),
+    };
+    if which::which(&program).is_err() {
+        return Check::new("multiplexer", Level::Fail, format!("{} not found", program)).hint(
+            format!(
+                "Install {}, or set WORKMUX_BACKEND to tmux, wezterm, kitty, zellij, iterm2 or none",
+                program
+            ),
+        );
+    }
+    match create_backend(backend).is_running() {
+        Ok(true) => Check::new(
+            "multiplexer",
+            Level::Pass,
+            format!("{} is running ({})", name, choice.reason),
+        ),
+        _ if backend == BackendType::ITerm2 => Check::new(
+            "multiplexer",
+            Level::Warn,
+            "iterm2 is not reachable through its Python API",
+        )
+        .hint(format!(
+            "Enable the Python API in iTerm2 (Settings > General > Magic) and run `{} -m pip install iterm2`",
+            program
+        )),
+        _ => Check::new(
+            "multiplexer",
+            Level::Warn,
+            format!("{} is installed but not running", name),
+        )
+        .hint(format!(
+            "Start a {} session before creating worktrees",
+            name
+        )),
+    }
+}
+
+fn check_git() -> Check {
+    let output = match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            return Check::new("git", Level::Fail, "not found")
+                .hint("Install git from https://git-scm.com");
+        }
+    };
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let (min_major, min_minor) = MIN_GIT_VERSION;
+    match parse_git_version(&version) {
+        Some(found) if found >= MIN_GIT_VERSION => Check::new("git", Level::Pass, version),
+        Some(_) => Check::new("git", Level::Fail, format!("{} is too old", version)).hint(format!(
+            "Upgrade to git {}.{} or newer",
+            min_major, min_minor
+        )),
+        None => Check::new(
+            "git",
+            Level::Warn,
+            format!("couldn't parse version from '{}'", version),
+        ),
+    }
+}
+
+/// Major and minor version from `git --version` output, e.g.
+/// `git version 2.39.3 (Apple Git-145)`.
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output
+        .strip_prefix("git version ")?
+        .split_whitespace()
+        .next()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Optional tools: missing ones only matter when the config uses them.
+fn check_tools(config: Option<&Config>) -> Vec<Check> {
+    let found = |program: &str| which::which(program).is_ok();
+    let mut checks = Vec::new();
+
+    checks.push(if found("gh") {
+        Check::new("gh", Level::Pass, "installed")
+    } else {
+        Check::new("gh", Level::Warn, "not found")
+            .hint("Install the GitHub CLI (https://cli.github.com) for `add --pr` and PR status")
+    });
+
+    // Branch name generation runs `llm` unless auto_name.command replaces it
+    let auto_name = config
+        .and_then(|c| c.auto_name.as_ref())
+        .and_then(|a| a.command.as_deref())
+        .and_then(|command| command.split_whitespace().next());
+    checks.push(match auto_name {
+        Some(program) if found(program) => Check::new(
+            "auto-name",
+            Level::Pass,
+            format!("{} (auto_name.command) installed", program),
+        ),
+        Some(program) => Check::new(
+            "auto-name",
+            Level::Fail,
+            format!("{} (auto_name.command) not found", program),
+        )
+        .hint("Install it, or fix auto_name.command in your global config"),
+        None if found("llm") => Check::new("auto-name", Level::Pass, "llm installed"),
+        None => Check::new("auto-name", Level::Warn, "llm not found")
+            .hint("Install llm (pipx install llm) to use `add --auto-name`"),
+    });
+
+    let uses_lima = config
+        .is_some_and(|c| c.sandbox.is_enabled() && c.sandbox.backend() == SandboxBackend::Lima);
+    checks.push(match (found("limactl"), uses_lima) {
+        (true, _) => Check::new("limactl", Level::Pass, "installed"),
+        (false, true) => Check::new("limactl", Level::Fail, "not found")
+            .hint("Install Lima (https://lima-vm.io) for the lima sandbox backend"),
+        (false, false) => Check::new(
+            "limactl",
+            Level::Info,
+            "not found (only needed for the lima sandbox backend)",
+        ),
+    });
+
+    checks
+}
+
+fn check_agent_hooks() -> Vec<Check> {
+    let agents = agent_setup::check_all();
+    if agents.is_empty() {
+        return vec![
+            Check::new("agents", Level::Warn, "no agent CLI detected")
+                .hint("Install an agent CLI such as Claude Code, then run `workmux setup`"),
+        ];
+    }
+    agents
+        .into_iter()
+        .map(|check| {
+            let name = check.agent.name();
+            match check.status {
+                StatusCheck::Installed => {
+                    Check::new(name, Level::Pass, "status tracking hooks installed")
+                }
+                StatusCheck::NotInstalled => {
+                    Check::new(name, Level::Warn, "status tracking hooks not installed")
+                        .hint("Run `workmux setup --hooks`")
+                }
+                StatusCheck::Error(e) => Check::new(name, Level::Fail, e)
+                    .hint("Fix the agent's settings file, then run `workmux setup --hooks`"),
+            }
+        })
+        .collect()
+}
+
+fn check_state_dir() -> Check {
+    let dir = match crate::xdg::state_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            return Check::new("state dir", Level::Fail, format!("{:#}", e))
+                .hint("Set HOME or XDG_STATE_HOME");
+        }
+    };
+    match ensure_writable(&dir) {
+        Ok(()) => Check::new("state dir", Level::Pass, dir.display().to_string()),
+        Err(e) => Check::new(
+            "state dir",
+            Level::Fail,
+            format!("{} is not writable: {}", dir.display(), e),
+        )
+        .hint(format!(
+            "Make it writable by your user, e.g. chown -R \"$USER\" {}",
+            dir.display()
+        )),
+    }
+}
+
+fn ensure_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    tempfile::NamedTempFile::new_in(dir).map(drop)
+}
+
+fn check_config(config: Result<Config>) -> Check {
+    let validated = config.and_then(|config| {
+        if let Some(panes) = &config.panes {
+            config::validate_panes_config(panes)?;
+        }
+        if let Some(windows) = &config.windows {
+            config::validate_windows_config(windows)?;
+        }
+        if let Some(layouts) = &config.layouts {
+            config::validate_layouts_config(layouts)?;
+        }
+        Ok(())
+    });
+    match validated {
+        Ok(()) => Check::new("config", Level::Pass, "valid"),
+        Err(e) => Check::new("config", Level::Fail, format!("{:#}", e))
+            .hint("Fix the file, or open the global config with `workmux config edit`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_git_version_variants() {
+        assert_eq!(parse_git_version("git version 2.39.3"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-145)"),
+            Some((2, 39))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("hub version 2.14.2"), None);
+        assert!(parse_git_version("git version 2.16.0").unwrap() < MIN_GIT_VERSION);
+    }
+
+    #[test]
+    fn count_problems_by_level() {
+        let checks = [
+            Check::new("a", Level::Pass, ""),
+            Check::new("b", Level::Info, ""),
+            Check::new("c", Level::Warn, ""),
+            Check::new("d", Level::Fail, ""),
+            Check::new("e", Level::Warn, ""),
+        ];
+        assert_eq!(count_problems(&checks), (2, 1));
+    }
+}
@@ -1,21 +1,36 @@
 //! Hidden `_exec` subcommand for running commands in worktree panes.
 //!
 //! This is invoked by `workmux run` in a split pane to execute the command
-//! while capturing output to files.
+//! while capturing output to files. With `--watch`, it stays in the pane and
+//! re-runs the command whenever the worktree changes.
 
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc;
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use console::strip_ansi_codes;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{RecursiveMode, Watcher};
 
-use crate::state::run::{RunResult, read_spec, write_result};
+use crate::cmd;
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::redact::LineRedactingWriter;
+use crate::shell::shell_escape;
+use crate::state::run::{
+    OnFail, RunRecord, RunResult, RunSpec, clear_pid, read_spec, record_run, write_pid,
+    write_result,
+};
+use crate::util::format_elapsed_duration;
 
 pub fn run(run_dir: &Path) -> Result<()> {
     let result = try_run(run_dir);
@@ -28,6 +43,7 @@ pub fn run(run_dir: &Path) -> Result<()> {
             exit_code: Some(1),
             signal: None,
         };
+        clear_pid(run_dir);
         let _ = write_result(run_dir, &fail_result);
     }
 
@@ -36,7 +52,89 @@ pub fn run(run_dir: &Path) -> Result<()> {
 
 fn try_run(run_dir: &Path) -> Result<()> {
     let spec = read_spec(run_dir)?;
+    if let Err(e) = write_pid(run_dir) {
+        tracing::warn!(error = %e, "exec:failed to record pid");
+    }
+    // Also registers the configured redaction patterns for the output files
+    let config = crate::config::Config::load_with_location_from(&spec.worktree_path, None)
+        .map(|(config, _)| config)
+        .unwrap_or_default();
+
+    // PID of the running command (0 when idle), for SIGINT forwarding
+    let child_pid = Arc::new(AtomicU32::new(0));
+
+    // Setup signal handler to forward SIGINT to child. With nothing running
+    // (between watch iterations), Ctrl-C stops the pane.
+    #[cfg(unix)]
+    {
+        let pid = child_pid.clone();
+        let _ = ctrlc::set_handler(move || match pid.load(Ordering::SeqCst) {
+            0 => std::process::exit(130),
+            pid => unsafe {
+                libc::kill(pid as i32, libc::SIGINT);
+            },
+        });
+    }
+
+    if spec.watch {
+        return watch(run_dir, &spec, &child_pid);
+    }
+
+    let started_at = SystemTime::now();
+    let result = execute(run_dir, &spec, &child_pid)?;
+
+    // Before writing the result: the coordinator deletes the run dir (and
+    // with it the output the follow-up reads) once it sees the result
+    if result.exit_code != Some(0) {
+        handle_failure(run_dir, &spec, &result);
+    }
+
+    // Record history before the result so it's visible once the coordinator returns
+    // (best-effort: a failure here shouldn't change the exit code).
+    let record = RunRecord {
+        id: run_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        target: spec.target,
+        command: spec.command,
+        worktree_path: spec.worktree_path,
+        started_at: started_at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        duration_ms: started_at
+            .elapsed()
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        exit_code: result.exit_code,
+        signal: result.signal,
+    };
+    if let Err(e) = record_run(&record) {
+        tracing::warn!(error = %e, "failed to record run history");
+    }
 
+    if spec.notify {
+        use crate::notification::sound::SoundEvent;
+        let event = if record.exit_code == Some(0) {
+            SoundEvent::Done
+        } else {
+            SoundEvent::Failed
+        };
+        crate::notification::show_for(&config.notifications, &completion_message(&record), event);
+    }
+
+    // Write result
+    clear_pid(run_dir);
+    write_result(run_dir, &result)?;
+
+    // Exit with same code as child
+    std::process::exit(result.exit_code.unwrap_or(1));
+}
+
+/// Run the spec's command once, appending its output to the run directory
+/// files and echoing it to the pane.
+fn execute(run_dir: &Path, spec: &RunSpec, child_pid: &AtomicU32) -> Result<RunResult> {
     let stdout_path = run_dir.join("stdout");
     let stderr_path = run_dir.join("stderr");
 
@@ -54,31 +152,30 @@ fn try_run(run_dir: &Path) -> Result<()> {
         .context("Failed to open stderr file")?;
 
     // Spawn the command
-    let mut child = Command::new("bash")
-        .arg("-c")
-        .arg(&spec.command)
+    let mut command = if spec.sandbox {
+        // Go through the sandbox supervisor so the command sees the same
+        // VM/container, mounts and host-exec shims as the agent
+        let exe = std::env::current_exe().context("Failed to locate workmux executable")?;
+        let mut command = Command::new(exe);
+        command
+            .args(["sandbox", "run"])
+            .arg(&spec.worktree_path)
+            .arg("--")
+            .arg(sandbox_command(&spec.command, &spec.env));
+        command
+    } else {
+        let mut command = Command::new(crate::platform::bash());
+        command.arg("-c").arg(&spec.command).envs(&spec.env);
+        command
+    };
+    let mut child = command
         .current_dir(&spec.worktree_path)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .context("Failed to spawn command")?;
-
-    let child_pid = child.id();
-    let running = Arc::new(AtomicBool::new(true));
-
-    // Setup signal handler to forward SIGINT to child
-    #[cfg(unix)]
-    {
-        let r = running.clone();
-        let _ = ctrlc::set_handler(move || {
-            if r.load(Ordering::SeqCst) {
-                unsafe {
-                    libc::kill(child_pid as i32, libc::SIGINT);
-                }
-            }
-        });
-    }
+    child_pid.store(child.id(), Ordering::SeqCst);
 
     // Take ownership of child's stdout/stderr
     let child_stdout = child.stdout.take().unwrap();
@@ -86,36 +183,306 @@ fn try_run(run_dir: &Path) -> Result<()> {
 
     // Spawn thread to pump stdout (move owned handles into thread)
     let stdout_handle = thread::spawn(move || {
-        pump_output(child_stdout, stdout_file, std::io::stdout());
+        pump_output(
+            child_stdout,
+            LineRedactingWriter::new(stdout_file),
+            std::io::stdout(),
+        );
     });
 
     // Spawn thread to pump stderr (move owned handles into thread)
     let stderr_handle = thread::spawn(move || {
-        pump_output(child_stderr, stderr_file, std::io::stderr());
+        pump_output(
+            child_stderr,
+            LineRedactingWriter::new(stderr_file),
+            std::io::stderr(),
+        );
     });
 
     // Wait for child to complete
-    let status = child.wait().context("Failed to wait for command")?;
-    running.store(false, Ordering::SeqCst);
+    let status = child.wait();
+    child_pid.store(0, Ordering::SeqCst);
+    let status = status.context("Failed to wait for command")?;
 
     // Wait for IO threads to finish
     let _ = stdout_handle.join();
     let _ = stderr_handle.join();
 
-    // Write result
     #[cfg(unix)]
     let signal = status.signal();
     #[cfg(not(unix))]
     let signal = None;
 
-    let result = RunResult {
+    Ok(RunResult {
         exit_code: status.code(),
         signal,
+    })
+}
+
+/// Summary shown in the `--notify` desktop notification.
+fn completion_message(record: &RunRecord) -> String {
+    let handle = record
+        .worktree_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let status = match (record.exit_code, record.signal) {
+        (Some(0), _) => "✓ Passed".to_string(),
+        (Some(code), _) => format!("✗ Failed (exit {})", code),
+        (None, Some(signal)) => format!("✗ Killed (signal {})", signal),
+        (None, None) => "✗ Failed".to_string(),
     };
-    write_result(run_dir, &result)?;
+    format!(
+        "{} in {} after {}: {}",
+        status,
+        handle,
+        format_elapsed_duration(Duration::from_millis(record.duration_ms)),
+        record.command
+    )
+}
 
-    // Exit with same code as child
-    std::process::exit(status.code().unwrap_or(1));
+/// Trailing output lines included in an `--on-fail prompt` message
+const FAILURE_OUTPUT_LINES: usize = 80;
+
+/// Run the spec's `--on-fail` action. Best-effort: problems are reported in
+/// the pane but don't change the run's result.
+fn handle_failure(run_dir: &Path, spec: &RunSpec, result: &RunResult) {
+    let Some(on_fail) = &spec.on_fail else {
+        return;
+    };
+    let outcome = match on_fail {
+        OnFail::Prompt(message) => {
+            let stdout = std::fs::read_to_string(run_dir.join("stdout")).unwrap_or_default();
+            let stderr = std::fs::read_to_string(run_dir.join("stderr")).unwrap_or_default();
+            let prompt = failure_prompt(message, &spec.command, result, &stdout, &stderr);
+            match &spec.agent_pane {
+                Some(pane_id) => create_backend(detect_backend()).paste_multiline(pane_id, &prompt),
+                None => Err(anyhow!("no agent pane recorded for this run")),
+            }
+        }
+        OnFail::Hook(command) => {
+            let handle = spec
+                .worktree_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let worktree_path = spec.worktree_path.to_string_lossy();
+            let exit_code = result.exit_code.map(|c| c.to_string()).unwrap_or_default();
+            let stdout_path = run_dir.join("stdout").to_string_lossy().into_owned();
+            let stderr_path = run_dir.join("stderr").to_string_lossy().into_owned();
+            let hook_env = [
+                ("WM_HANDLE", handle.as_str()),
+                ("WM_WORKTREE_PATH", worktree_path.as_ref()),
+                ("WM_RUN_COMMAND", spec.command.as_str()),
+                ("WM_RUN_EXIT_CODE", exit_code.as_str()),
+                ("WM_RUN_STDOUT", stdout_path.as_str()),
+                ("WM_RUN_STDERR", stderr_path.as_str()),
+            ];
+            cmd::shell_command_with_env(command, &spec.worktree_path, &hook_env)
+        }
+    };
+    if let Err(e) = outcome {
+        eprintln!("workmux: --on-fail action failed: {:#}", e);
+    }
+}
+
+/// Build the prompt sent to the agent for `--on-fail prompt`.
+fn failure_prompt(
+    message: &str,
+    command: &str,
+    result: &RunResult,
+    stdout: &str,
+    stderr: &str,
+) -> String {
+    let message = if message.trim().is_empty() {
+        "A command I ran in your worktree failed. Please investigate and fix it."
+    } else {
+        message.trim()
+    };
+    let status = match (result.exit_code, result.signal) {
+        (Some(code), _) => format!("exit code {}", code),
+        (None, Some(signal)) => format!("killed by signal {}", signal),
+        (None, None) => "unknown exit status".to_string(),
+    };
+
+    let mut prompt = format!("{}\n\nCommand: {} ({})\n", message, command, status);
+    for (name, output) in [("stdout", stdout), ("stderr", stderr)] {
+        let output = strip_ansi_codes(output);
+        let lines: Vec<&str> = output.trim_end().lines().collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let start = lines.len().saturating_sub(FAILURE_OUTPUT_LINES);
+        let label = if start > 0 {
+            format!("{} (last {} lines)", name, FAILURE_OUTPUT_LINES)
+        } else {
+            name.to_string()
+        };
+        prompt.push_str(&format!(
+            "\n{}:\n```\n{}\n```\n",
+            label,
+            lines[start..].join("\n")
+        ));
+    }
+    prompt
+}
+
+/// Quiet period after the last file change before re-running
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Window status icons for `--watch --status-icon`
+const WATCH_PASS_ICON: &str = "🟢";
+const WATCH_FAIL_ICON: &str = "🔴";
+
+/// Re-run the command whenever files in the worktree change, until the pane
+/// is closed or interrupted.
+fn watch(run_dir: &Path, spec: &RunSpec, child_pid: &AtomicU32) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            let _ = tx.send(event);
+        },
+        notify::Config::default(),
+    )
+    .context("Failed to start file watcher")?;
+    watcher
+        .watch(&spec.worktree_path, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", spec.worktree_path.display()))?;
+    let ignore = WatchIgnore::new(&spec.worktree_path);
+
+    let mux = create_backend(detect_backend());
+    let pane_id = mux.current_pane_id();
+    let mut last_passed = None;
+
+    loop {
+        // Start each iteration from a clean screen and fresh output files
+        print!("\x1b[2J\x1b[H");
+        println!("\x1b[2m$ {}\x1b[0m\n", spec.command);
+        let _ = std::io::stdout().flush();
+        std::fs::write(run_dir.join("stdout"), "")?;
+        std::fs::write(run_dir.join("stderr"), "")?;
+
+        let started = Instant::now();
+        let result = execute(run_dir, spec, child_pid)?;
+        write_result(run_dir, &result)?;
+
+        let passed = result.exit_code == Some(0);
+        // Only follow up when a run starts failing, not on every failed re-run
+        if !passed && last_passed != Some(false) {
+            handle_failure(run_dir, spec, &result);
+        }
+        last_passed = Some(passed);
+        let summary = match result.exit_code {
+            Some(0) => "\x1b[32m✓ passed\x1b[0m".to_string(),
+            Some(code) => format!("\x1b[31m✗ failed (exit {})\x1b[0m", code),
+            None => "\x1b[31m✗ killed\x1b[0m".to_string(),
+        };
+        println!(
+            "\n{} in {} · watching for changes (Ctrl-C to stop)",
+            summary,
+            format_elapsed_duration(started.elapsed())
+        );
+
+        if spec.status_icon
+            && let Some(pane_id) = pane_id.as_deref()
+        {
+            let icon = if passed {
+                WATCH_PASS_ICON
+            } else {
+                WATCH_FAIL_ICON
+            };
+            let _ = mux.set_status(pane_id, icon, false);
+        }
+
+        // Changes made by the command itself (snapshots, caches) don't count
+        while rx.try_recv().is_ok() {}
+        wait_for_change(&rx, &ignore)?;
+    }
+}
+
+/// Block until a relevant change arrives, then until changes go quiet.
+fn wait_for_change(
+    rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+    ignore: &WatchIgnore,
+) -> Result<()> {
+    let is_relevant = |event: &notify::Result<notify::Event>| match event {
+        Ok(event) => {
+            !matches!(event.kind, notify::EventKind::Access(_))
+                && event.paths.iter().any(|p| !ignore.is_ignored(p))
+        }
+        Err(_) => false,
+    };
+
+    loop {
+        let event = rx
+            .recv()
+            .map_err(|_| anyhow!("File watcher stopped unexpectedly"))?;
+        if is_relevant(&event) {
+            break;
+        }
+    }
+    // Debounce: editors and formatters touch several files in a burst
+    while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+    Ok(())
+}
+
+/// Paths that never trigger a re-run: `.git` internals and anything the
+/// worktree's top-level `.gitignore` ignores.
+struct WatchIgnore {
+    /// The worktree root as given and canonicalized. Watchers report paths
+    /// under either (macOS reports `/private/var` for `/var`).
+    roots: Vec<PathBuf>,
+    gitignore: Gitignore,
+}
+
+impl WatchIgnore {
+    fn new(root: &Path) -> Self {
+        let mut roots = vec![root.to_path_buf()];
+        if let Ok(canonical) = root.canonicalize()
+            && canonical != root
+        {
+            roots.insert(0, canonical);
+        }
+        let mut builder = GitignoreBuilder::new(&roots[0]);
+        let _ = builder.add(root.join(".gitignore"));
+        Self {
+            roots,
+            gitignore: builder.build().unwrap_or_else(|_| Gitignore::empty()),
+        }
+    }
+
+    /// Paths outside the worktree aren't ignored, so an unexpected path form
+    /// re-runs too often rather than never.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let Some(rel) = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+        else {
+            return false;
+        };
+        if rel
+            .components()
+            .next()
+            .is_some_and(|c| c.as_os_str() == ".git")
+        {
+            return true;
+        }
+        self.gitignore
+            .matched_path_or_any_parents(rel, path.is_dir())
+            .is_ignore()
+    }
+}
+
+/// Build the shell command run inside the sandbox. Host environment variables
+/// don't cross into the guest, so `--env` values are exported inline.
+fn sandbox_command(command: &str, env: &BTreeMap<String, String>) -> String {
+    let mut script = String::new();
+    for (key, value) in env {
+        script.push_str(&format!("export {}='{}'; ", key, shell_escape(value)));
+    }
+    script.push_str(command);
+    script
 }
 
 fn pump_output<R: Read, F: Write, T: Write>(mut reader: R, mut file: F, mut terminal: T) {
@@ -134,3 +501,106 @@ fn pump_output<R: Read, F: Write, T: Write>(mut reader: R, mut file: F, mut term
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completion_message_summarizes_result() {
+        let record = RunRecord {
+            id: "abc-1".to_string(),
+            target: "api".to_string(),
+            command: "just test".to_string(),
+            worktree_path: PathBuf::from("/wt/api"),
+            started_at: 0,
+            duration_ms: 75_000,
+            exit_code: Some(1),
+            signal: None,
+        };
+        assert_eq!(
+            completion_message(&record),
+            "✗ Failed (exit 1) in api after 1m 15s: just test"
+        );
+    }
+
+    #[test]
+    fn failure_prompt_includes_status_and_output_tail() {
+        let result = RunResult {
+            exit_code: Some(2),
+            signal: None,
+        };
+        let stdout: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        let prompt = failure_prompt(
+            "tests failed, see output",
+            "just test",
+            &result,
+            &stdout,
+            "\x1b[31merror\x1b[0m: boom\n",
+        );
+
+        assert!(
+            prompt.starts_with("tests failed, see output\n\nCommand: just test (exit code 2)\n")
+        );
+        assert!(prompt.contains("stdout (last 80 lines):\n```\nline 21\n"));
+        assert!(!prompt.contains("line 20\n"));
+        assert!(prompt.contains("stderr:\n```\nerror: boom\n```"));
+    }
+
+    #[test]
+    fn failure_prompt_uses_default_message_and_skips_empty_output() {
+        let result = RunResult {
+            exit_code: None,
+            signal: Some(9),
+        };
+        let prompt = failure_prompt("", "make", &result, "", "");
+        assert!(prompt.starts_with("A command I ran in your worktree failed."));
+        assert!(prompt.contains("(killed by signal 9)"));
+        assert!(!prompt.contains("```"));
+    }
+
+    #[test]
+    fn watch_ignore_skips_git_dir_and_gitignored_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        let ignore = WatchIgnore::new(root);
+
+        assert!(ignore.is_ignored(&root.join(".git/index")));
+        assert!(ignore.is_ignored(&root.join("target/debug/app")));
+        assert!(ignore.is_ignored(&root.join("out.log")));
+        assert!(!ignore.is_ignored(Path::new("/elsewhere/file.rs")));
+        assert!(!ignore.is_ignored(&root.join("src/main.rs")));
+        assert!(!ignore.is_ignored(&root.join(".gitignore")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn watch_ignore_matches_paths_under_canonical_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("real");
+        std::fs::create_dir(&real).unwrap();
+        std::fs::write(real.join(".gitignore"), "target/\n").unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        let ignore = WatchIgnore::new(&link);
+
+        let canonical = real.canonicalize().unwrap();
+        assert!(ignore.is_ignored(&canonical.join("target/debug/app")));
+        assert!(ignore.is_ignored(&link.join("target/debug/app")));
+        assert!(!ignore.is_ignored(&canonical.join("src/main.rs")));
+    }
+
+    #[test]
+    fn sandbox_command_exports_env_before_command() {
+        let env = BTreeMap::from([
+            ("A".to_string(), "it's".to_string()),
+            ("B".to_string(), "2".to_string()),
+        ]);
+        assert_eq!(
+            sandbox_command("just test", &env),
+            "export A='it'\\''s'; export B='2'; just test"
+        );
+        assert_eq!(sandbox_command("ls", &BTreeMap::new()), "ls");
+    }
+}
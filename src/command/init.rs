@@ -0,0 +1,545 @@
+//! `workmux init`: generate a `.workmux.yaml` for the current repository.
+//!
+//! Looks at the files in the repo to work out how it's built and tested,
+//! proposes hooks and panes to match, and writes the accepted ones above a
+//! commented reference of every option.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use console::style;
+use serde::Serialize;
+
+use super::add_wizard::{ask, confirm};
+use crate::config::{EXAMPLE_PROJECT_CONFIG, global_config_path};
+use crate::git;
+use crate::sandbox::toolchain::{DetectedToolchain, detect_toolchain};
+
+const CONFIG_FILE: &str = ".workmux.yaml";
+
+/// Env files copied into new worktrees when they exist and are gitignored
+const ENV_FILES: &[&str] = &[".env", ".env.local", ".env.development.local"];
+
+/// What `npm init` puts in `scripts.test`, which isn't a real test command
+const NPM_PLACEHOLDER_TEST: &str = "echo \"Error: no test specified\" && exit 1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageManager {
+    Pnpm,
+    Yarn,
+    Bun,
+    Npm,
+    Cargo,
+    Go,
+    Uv,
+    Poetry,
+    Bundler,
+}
+
+impl PackageManager {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+            Self::Bun => "bun",
+            Self::Npm => "npm",
+            Self::Cargo => "cargo",
+            Self::Go => "go",
+            Self::Uv => "uv",
+            Self::Poetry => "poetry",
+            Self::Bundler => "bundler",
+        }
+    }
+
+    /// Dependency install for a fresh worktree. Cargo fetches on build.
+    fn install(self) -> Option<&'static str> {
+        match self {
+            Self::Pnpm => Some("pnpm install"),
+            Self::Yarn => Some("yarn install"),
+            Self::Bun => Some("bun install"),
+            Self::Npm => Some("npm install"),
+            Self::Cargo => None,
+            Self::Go => Some("go mod download"),
+            Self::Uv => Some("uv sync"),
+            Self::Poetry => Some("poetry install"),
+            Self::Bundler => Some("bundle install"),
+        }
+    }
+
+    /// Command running a package.json script (JS package managers only)
+    fn run_script(self, script: &str) -> Option<String> {
+        match self {
+            Self::Pnpm | Self::Yarn | Self::Bun | Self::Npm => {
+                Some(format!("{} run {}", self.name(), script))
+            }
+            _ => None,
+        }
+    }
+
+    /// Test command when there's no justfile, Makefile, or test script
+    fn default_test(self) -> Option<&'static str> {
+        match self {
+            Self::Cargo => Some("cargo test"),
+            Self::Go => Some("go test ./..."),
+            Self::Uv => Some("uv run pytest"),
+            Self::Poetry => Some("poetry run pytest"),
+            _ => None,
+        }
+    }
+}
+
+/// What `init` found in the repository.
+#[derive(Debug)]
+struct Detected {
+    toolchain: DetectedToolchain,
+    package_manager: Option<PackageManager>,
+    test_command: Option<String>,
+    dev_command: Option<String>,
+    env_files: Vec<String>,
+}
+
+impl Detected {
+    /// Run `command` inside the devbox or flake environment, if any.
+    fn wrap(&self, command: &str) -> String {
+        match self.toolchain {
+            DetectedToolchain::Devbox => format!("devbox run -- {}", command),
+            DetectedToolchain::Flake => format!("nix develop -c {}", command),
+            DetectedToolchain::None => command.to_string(),
+        }
+    }
+
+    fn summary(&self) -> Vec<String> {
+        let mut found = Vec::new();
+        match self.toolchain {
+            DetectedToolchain::Devbox => found.push("devbox".to_string()),
+            DetectedToolchain::Flake => found.push("nix flake".to_string()),
+            DetectedToolchain::None => {}
+        }
+        if let Some(pm) = self.package_manager {
+            found.push(pm.name().to_string());
+        }
+        found
+    }
+}
+
+fn detect(dir: &Path) -> Detected {
+    let package_manager = detect_package_manager(dir);
+    let scripts = package_json_scripts(dir);
+    let script = |name: &str| {
+        scripts
+            .iter()
+            .any(|(script, command)| script == name && command != NPM_PLACEHOLDER_TEST)
+    };
+
+    let test_command = if has_just_recipe(dir, "test") {
+        Some("just test".to_string())
+    } else if has_make_target(dir, "test") {
+        Some("make test".to_string())
+    } else if script("test") {
+        package_manager.and_then(|pm| pm.run_script("test"))
+    } else {
+        package_manager
+            .and_then(PackageManager::default_test)
+            .map(str::to_string)
+    };
+    let dev_command = if script("dev") {
+        package_manager.and_then(|pm| pm.run_script("dev"))
+    } else {
+        None
+    };
+    let env_files = ENV_FILES
+        .iter()
+        .filter(|f| dir.join(f).is_file() && git::is_path_ignored(dir, f))
+        .map(|f| f.to_string())
+        .collect();
+
+    Detected {
+        toolchain: detect_toolchain(dir),
+        package_manager,
+        test_command,
+        dev_command,
+        env_files,
+    }
+}
+
+fn detect_package_manager(dir: &Path) -> Option<PackageManager> {
+    let has = |file: &str| dir.join(file).exists();
+    let pm = if has("pnpm-lock.yaml") {
+        PackageManager::Pnpm
+    } else if has("bun.lock") || has("bun.lockb") {
+        PackageManager::Bun
+    } else if has("yarn.lock") {
+        PackageManager::Yarn
+    } else if has("package.json") {
+        PackageManager::Npm
+    } else if has("Cargo.toml") {
+        PackageManager::Cargo
+    } else if has("go.mod") {
+        PackageManager::Go
+    } else if has("uv.lock") {
+        PackageManager::Uv
+    } else if has("poetry.lock") {
+        PackageManager::Poetry
+    } else if has("Gemfile") {
+        PackageManager::Bundler
+    } else {
+        return None;
+    };
+    Some(pm)
+}
+
+/// `scripts` from package.json as (name, command) pairs.
+fn package_json_scripts(dir: &Path) -> Vec<(String, String)> {
+    let Ok(content) = fs::read_to_string(dir.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    json.get("scripts")
+        .and_then(|s| s.as_object())
+        .map(|scripts| {
+            scripts
+                .iter()
+                .filter_map(|(name, cmd)| Some((name.clone(), cmd.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn has_just_recipe(dir: &Path, recipe: &str) -> bool {
+    ["justfile", "Justfile", ".justfile"].iter().any(|name| {
+        fs::read_to_string(dir.join(name)).is_ok_and(|content| {
+            content.lines().any(|line| {
+                // `test:` or `test *args:`, not indented (recipe bodies are)
+                line.strip_prefix(recipe).is_some_and(|rest| {
+                    rest.starts_with([':', ' ']) && rest.contains(':') && !is_assignment(rest)
+                })
+            })
+        })
+    })
+}
+
+fn has_make_target(dir: &Path, target: &str) -> bool {
+    ["Makefile", "makefile", "GNUmakefile"].iter().any(|name| {
+        fs::read_to_string(dir.join(name)).is_ok_and(|content| {
+            content.lines().any(|line| {
+                line.strip_prefix(target)
+                    .is_some_and(|rest| rest.trim_start().starts_with(':') && !is_assignment(rest))
+            })
+        })
+    })
+}
+
+/// `test := value` defines a variable, not a recipe or target
+fn is_assignment(rest: &str) -> bool {
+    let rest = rest.trim_start();
+    rest.starts_with(":=") || rest.starts_with("::=")
+}
+
+/// The settings written to the top of the generated file.
+#[derive(Debug, Default, Serialize)]
+struct Proposal {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    post_create: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pre_merge: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    panes: Vec<Pane>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<Files>,
+}
+
+#[derive(Debug, Serialize)]
+struct Pane {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    focus: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    split: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct Files {
+    copy: Vec<String>,
+}
+
+impl Proposal {
+    fn is_empty(&self) -> bool {
+        self.post_create.is_empty()
+            && self.pre_merge.is_empty()
+            && self.panes.is_empty()
+            && self.files.is_none()
+    }
+}
+
+/// Build the proposal from what was detected. `accept` decides each item;
+/// `ask_test` supplies a test command when none was found.
+fn propose(
+    detected: &Detected,
+    mut accept: impl FnMut(&str) -> Result<bool>,
+    ask_test: impl FnOnce() -> Result<Option<String>>,
+) -> Result<Proposal> {
+    let mut proposal = Proposal::default();
+
+    if let Some(install) = detected.package_manager.and_then(PackageManager::install) {
+        let install = detected.wrap(install);
+        if accept(&format!("Run `{}` in each new worktree?", install))? {
+            proposal.post_create.push(install);
+        }
+    }
+
+    let test = match &detected.test_command {
+        Some(test) => Some(detected.wrap(test)),
+        None => ask_test()?,
+    };
+    if let Some(test) = test
+        && accept(&format!("Run `{}` before merging?", test))?
+    {
+        proposal.pre_merge.push(test);
+    }
+
+    if let Some(dev) = &detected.dev_command {
+        let dev = detected.wrap(dev);
+        if accept(&format!("Add a pane running `{}`?", dev))? {
+            proposal.panes = vec![
+                Pane {
+                    command: Some("<agent>".to_string()),
+                    focus: true,
+                    split: None,
+                },
+                Pane {
+                    command: None,
+                    focus: false,
+                    split: Some("horizontal"),
+                },
+                Pane {
+                    command: Some(dev),
+                    focus: false,
+                    split: Some("vertical"),
+                },
+            ];
+        }
+    }
+
+    let mut copy = Vec::new();
+    for file in &detected.env_files {
+        if accept(&format!("Copy {} into new worktrees?", file))? {
+            copy.push(file.clone());
+        }
+    }
+    if !copy.is_empty() {
+        proposal.files = Some(Files { copy });
+    }
+
+    Ok(proposal)
+}
+
+/// The file contents: accepted settings, then the commented reference.
+fn render(detected: &Detected, proposal: &Proposal) -> Result<String> {
+    if proposal.is_empty() {
+        return Ok(EXAMPLE_PROJECT_CONFIG.to_string());
+    }
+    let found = detected.summary();
+    let mut out = String::from("# workmux project configuration\n");
+    if !found.is_empty() {
+        out.push_str(&format!("# Generated for: {}\n", found.join(", ")));
+    }
+    out.push_str("# For global settings, edit ~/.config/workmux/config.yaml\n\n");
+    out.push_str(&serde_yaml::to_string(proposal)?);
+
+    // Skip the reference's own header, which is replaced by the one above
+    let reference = EXAMPLE_PROJECT_CONFIG
+        .split_once("\n\n")
+        .map(|(_, rest)| rest)
+        .unwrap_or(EXAMPLE_PROJECT_CONFIG);
+    out.push_str(
+        "\n#===============================================================================\n",
+    );
+    out.push_str("# All options (commented out - uncomment to override defaults)\n");
+    out.push_str(
+        "#===============================================================================\n\n",
+    );
+    out.push_str(reference);
+    Ok(out)
+}
+
+pub fn run() -> Result<()> {
+    let config_path = PathBuf::from(CONFIG_FILE);
+    if config_path.exists() {
+        bail!(
+            "{} already exists. Remove it first if you want to regenerate it.",
+            CONFIG_FILE
+        );
+    }
+
+    let detected = detect(Path::new("."));
+    let interactive = crate::interactive::can_prompt();
+    if interactive {
+        let found = detected.summary();
+        if !found.is_empty() {
+            println!("Detected: {}", style(found.join(", ")).bold());
+        }
+    }
+
+    let proposal = if interactive {
+        propose(
+            &detected,
+            |question| confirm(question, true),
+            || {
+                let test = ask("Test command to run before merging (empty to skip):", None)?;
+                Ok((!test.is_empty()).then_some(test))
+            },
+        )?
+    } else {
+        // Unattended: take everything that was detected
+        propose(&detected, |_| Ok(true), || Ok(None))?
+    };
+
+    fs::write(&config_path, render(&detected, &proposal)?)
+        .with_context(|| format!("Failed to write {}", CONFIG_FILE))?;
+
+    println!("✓ Created {}", CONFIG_FILE);
+    if proposal.is_empty() {
+        println!("\nThis file provides project-specific overrides.");
+    }
+    println!(
+        "For global settings, edit {}",
+        global_config_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "~/.config/workmux/config.yaml".to_string())
+    );
+    if std::env::var("TMUX").is_ok() && !has_dashboard_binding() {
+        println!(
+            "\nTo open the dashboard in a popup, add to ~/.tmux.conf:\n  {}",
+            super::dashboard::popup::binding()
+        );
+    }
+    Ok(())
+}
+
+/// Whether the tmux config already binds a key to the dashboard.
+fn has_dashboard_binding() -> bool {
+    let Some(home) = home::home_dir() else {
+        return false;
+    };
+    [".tmux.conf", ".config/tmux/tmux.conf"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(home.join(path)).ok())
+        .any(|conf| conf.contains("workmux dashboard"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn detected(pm: PackageManager) -> Detected {
+        Detected {
+            toolchain: DetectedToolchain::None,
+            package_manager: Some(pm),
+            test_command: None,
+            dev_command: None,
+            env_files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn detects_node_project_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"test": "vitest", "dev": "vite"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("devbox.json"), "{}").unwrap();
+
+        let found = detect(dir.path());
+        assert_eq!(found.package_manager, Some(PackageManager::Pnpm));
+        assert_eq!(found.test_command.as_deref(), Some("pnpm run test"));
+        assert_eq!(found.dev_command.as_deref(), Some("pnpm run dev"));
+        assert_eq!(found.wrap("pnpm install"), "devbox run -- pnpm install");
+    }
+
+    #[test]
+    fn prefers_just_and_make_over_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(
+            detect(dir.path()).test_command.as_deref(),
+            Some("cargo test")
+        );
+
+        fs::write(
+            dir.path().join("Makefile"),
+            "build:\n\tcc x.c\ntest : build\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect(dir.path()).test_command.as_deref(),
+            Some("make test")
+        );
+
+        // A variable named `test` isn't a recipe
+        fs::write(
+            dir.path().join("justfile"),
+            "test := 'x'\ntesting:\n  echo\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect(dir.path()).test_command.as_deref(),
+            Some("make test")
+        );
+
+        fs::write(
+            dir.path().join("justfile"),
+            "test := 'x'\ntest *args:\n  cargo nextest run\n",
+        )
+        .unwrap();
+        assert_eq!(
+            detect(dir.path()).test_command.as_deref(),
+            Some("just test")
+        );
+    }
+
+    #[test]
+    fn ignores_npm_placeholder_test_script() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            format!(
+                r#"{{"scripts": {{"test": {}}}}}"#,
+                serde_json::to_string(NPM_PLACEHOLDER_TEST).unwrap()
+            ),
+        )
+        .unwrap();
+        assert_eq!(detect(dir.path()).test_command, None);
+    }
+
+    #[test]
+    fn rendered_config_parses() {
+        let mut found = detected(PackageManager::Npm);
+        found.test_command = Some("npm run test".to_string());
+        found.dev_command = Some("npm run dev".to_string());
+        found.env_files = vec![".env".to_string()];
+        let proposal = propose(&found, |_| Ok(true), || Ok(None)).unwrap();
+        let yaml = render(&found, &proposal).unwrap();
+
+        let config: Config = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(config.post_create, Some(vec!["npm install".to_string()]));
+        assert_eq!(config.pre_merge, Some(vec!["npm run test".to_string()]));
+        assert_eq!(config.panes.unwrap().len(), 3);
+        assert!(yaml.contains("# Generated for: npm\n"));
+        assert!(yaml.contains("# post_create:"));
+    }
+
+    #[test]
+    fn declined_proposals_fall_back_to_reference() {
+        let found = detected(PackageManager::Cargo);
+        let proposal = propose(&found, |_| Ok(false), || Ok(None)).unwrap();
+        assert_eq!(render(&found, &proposal).unwrap(), EXAMPLE_PROJECT_CONFIG);
+    }
+}
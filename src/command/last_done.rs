@@ -166,6 +166,7 @@ mod tests {
             status: Some(status),
             status_ts: Some(status_ts),
             pane_title: None,
+            waiting_reason: None,
             pane_pid: 1000,
             command: "node".to_string(),
             updated_ts,
@@ -2,13 +2,17 @@ use std::io::IsTerminal;
 
 use crate::config;
 use crate::config::MuxMode;
+use crate::git::AheadBehind;
 use crate::multiplexer::{AgentStatus, create_backend, detect_backend};
-use crate::util::format_compact_age;
-use crate::workflow::types::AgentStatusSummary;
-use crate::{git, nerdfont, workflow};
+use crate::ui::theme::{ThemePalette, paint, resolve_mode};
+use crate::util::{disk_usage, format_compact_age, format_size, parallel_map};
+use crate::workflow::types::{AgentStatusSummary, WorktreeInfo};
+use crate::{git, nerdfont, spinner, workflow};
 use anyhow::Result;
+use console::{Term, measure_text_width};
 use pathdiff::diff_paths;
 use serde::Serialize;
+use std::path::{Path, PathBuf};
 use tabled::{
     Table, Tabled,
     settings::{Padding, Style, disable::Remove, location::ByColumnName, object::Columns},
@@ -28,34 +32,86 @@ struct WorktreeRow {
     mux_status: String,
     #[tabled(rename = "UNMERGED")]
     unmerged_status: String,
+    #[tabled(rename = "UPSTREAM")]
+    upstream_status: String,
+    #[tabled(rename = "MAIN")]
+    main_status: String,
+    #[tabled(rename = "SIZE")]
+    size: String,
     #[tabled(rename = "PATH")]
     path_str: String,
 }
 
-fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
+fn format_pr_status(
+    pr_info: Option<crate::github::PrSummary>,
+    palette: Option<&ThemePalette>,
+) -> String {
     pr_info
         .map(|pr| {
             let icons = nerdfont::pr_icons();
-            // GitHub-style colors: green for open, gray for draft, purple for merged, red for closed
+            let Some(palette) = palette else {
+                let icon = match pr.state.as_str() {
+                    "OPEN" if pr.is_draft => icons.draft,
+                    "MERGED" => icons.merged,
+                    "CLOSED" => icons.closed,
+                    _ => icons.open,
+                };
+                return format!("#{} {}", pr.number, icon);
+            };
+            // Same colors as the dashboard's PR column
             let (icon, color) = match pr.state.as_str() {
-                "OPEN" if pr.is_draft => (icons.draft, "\x1b[90m"), // gray
-                "OPEN" => (icons.open, "\x1b[32m"),                 // green
-                "MERGED" => (icons.merged, "\x1b[35m"),             // purple/magenta
-                "CLOSED" => (icons.closed, "\x1b[31m"),             // red
-                _ => (icons.open, "\x1b[32m"),
+                "OPEN" if pr.is_draft => (icons.draft, palette.dimmed),
+                "MERGED" => (icons.merged, palette.accent),
+                "CLOSED" => (icons.closed, palette.danger),
+                _ => (icons.open, palette.success),
             };
-            format!("#{} {}{}\x1b[0m", pr.number, color, icon)
+            format!("#{} {}", pr.number, paint(icon, color))
         })
         .unwrap_or_else(|| "-".to_string())
 }
 
+/// Format ahead/behind counts as e.g. "↑2 ↓5", "=" when in sync, or "-" when unknown.
+fn format_divergence(counts: Option<AheadBehind>) -> String {
+    let Some(counts) = counts else {
+        return "-".to_string();
+    };
+    let mut parts = Vec::new();
+    if counts.ahead > 0 {
+        parts.push(format!("↑{}", counts.ahead));
+    }
+    if counts.behind > 0 {
+        parts.push(format!("↓{}", counts.behind));
+    }
+    if parts.is_empty() {
+        "=".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
 /// Format a single agent status as either an icon (TTY) or text label (piped).
-fn format_status_label(status: AgentStatus, config: &config::Config, use_icons: bool) -> String {
+/// Icons are colored with `palette` when given.
+fn format_status_label(
+    status: AgentStatus,
+    config: &config::Config,
+    use_icons: bool,
+    palette: Option<&ThemePalette>,
+) -> String {
     if use_icons {
-        match status {
-            AgentStatus::Working => config.status_icons.working().to_string(),
-            AgentStatus::Waiting => config.status_icons.waiting().to_string(),
-            AgentStatus::Done => config.status_icons.done().to_string(),
+        let (icon, color) = match status {
+            AgentStatus::Working => (
+                config.status_icons.working(),
+                palette.map(ThemePalette::working),
+            ),
+            AgentStatus::Waiting => (
+                config.status_icons.waiting(),
+                palette.map(ThemePalette::waiting),
+            ),
+            AgentStatus::Done => (config.status_icons.done(), palette.map(ThemePalette::done)),
+        };
+        match color {
+            Some(color) => paint(icon, color),
+            None => icon.to_string(),
         }
     } else {
         match status {
@@ -66,10 +122,11 @@ fn format_status_label(status: AgentStatus, config: &config::Config, use_icons:
     }
 }
 
-fn format_agent_status(
+pub(super) fn format_agent_status(
     summary: Option<&AgentStatusSummary>,
     config: &config::Config,
     use_icons: bool,
+    palette: Option<&ThemePalette>,
 ) -> String {
     let summary = match summary {
         Some(s) if !s.statuses.is_empty() => s,
@@ -78,7 +135,7 @@ fn format_agent_status(
 
     let total = summary.statuses.len();
     if total == 1 {
-        format_status_label(summary.statuses[0], config, use_icons)
+        format_status_label(summary.statuses[0], config, use_icons, palette)
     } else {
         // Multiple agents: show breakdown
         let working = summary
@@ -99,15 +156,15 @@ fn format_agent_status(
 
         let mut parts = Vec::new();
         if working > 0 {
-            let label = format_status_label(AgentStatus::Working, config, use_icons);
+            let label = format_status_label(AgentStatus::Working, config, use_icons, palette);
             parts.push(format!("{}{}", working, label));
         }
         if waiting > 0 {
-            let label = format_status_label(AgentStatus::Waiting, config, use_icons);
+            let label = format_status_label(AgentStatus::Waiting, config, use_icons, palette);
             parts.push(format!("{}{}", waiting, label));
         }
         if done > 0 {
-            let label = format_status_label(AgentStatus::Done, config, use_icons);
+            let label = format_status_label(AgentStatus::Done, config, use_icons, palette);
             parts.push(format!("{}{}", done, label));
         }
         parts.join(" ")
@@ -124,13 +181,55 @@ struct JsonWorktree {
     has_uncommitted_changes: bool,
     is_open: bool,
     created_at: Option<u64>,
+    upstream: Option<AheadBehind>,
+    main: Option<AheadBehind>,
+    /// Disk usage in bytes (only with --du)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disk_usage: Option<u64>,
+}
+
+/// Compute disk usage for each worktree, skipping worktrees nested inside
+/// another (e.g. `worktree_dir: .worktrees`) so they aren't counted twice.
+fn compute_disk_usage(paths: &[PathBuf]) -> Vec<u64> {
+    paths
+        .iter()
+        .map(|path| {
+            let nested: Vec<PathBuf> = paths
+                .iter()
+                .filter(|other| *other != path && other.starts_with(path))
+                .cloned()
+                .collect();
+            disk_usage(path, &nested)
+        })
+        .collect()
 }
 
-pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
+pub fn run(show_pr: bool, json: bool, du: bool, filter: &[String]) -> Result<()> {
     let config = config::Config::load(None)?;
     let mux = create_backend(detect_backend());
     // Skip PR fetch when outputting JSON since it's not included in the JSON schema
-    let worktrees = workflow::list(&config, mux.as_ref(), show_pr && !json, filter)?;
+    // On a terminal, show cached PR status right away and update it below;
+    // piped output waits for fresh status
+    let (worktrees, pending_prs) = if show_pr && !json && std::io::stdout().is_terminal() {
+        workflow::list_with_cached_prs(&config, mux.as_ref(), filter)?
+    } else {
+        (
+            workflow::list(&config, mux.as_ref(), show_pr && !json, filter)?,
+            None,
+        )
+    };
+
+    let sizes: Vec<Option<u64>> = if du && !worktrees.is_empty() {
+        let paths: Vec<PathBuf> = worktrees.iter().map(|wt| wt.path.clone()).collect();
+        let sizes = if json {
+            compute_disk_usage(&paths)
+        } else {
+            spinner::with_spinner("Calculating disk usage", || Ok(compute_disk_usage(&paths)))?
+        };
+        sizes.into_iter().map(Some).collect()
+    } else {
+        vec![None; worktrees.len()]
+    };
 
     if worktrees.is_empty() {
         if json {
@@ -142,9 +241,16 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
     }
 
     if json {
+        // `git status` is the slowest per-worktree call, so run them in parallel
+        let paths: Vec<PathBuf> = worktrees.iter().map(|wt| wt.path.clone()).collect();
+        let dirty = parallel_map(paths, workflow::LIST_JOBS, |path| {
+            git::has_uncommitted_changes(&path).unwrap_or(false)
+        });
         let entries: Vec<JsonWorktree> = worktrees
             .into_iter()
-            .map(|wt| JsonWorktree {
+            .zip(sizes)
+            .zip(dirty)
+            .map(|((wt, disk_usage), dirty)| JsonWorktree {
                 handle: wt.handle,
                 branch: wt.branch,
                 path: wt.path.to_string_lossy().to_string(),
@@ -153,9 +259,12 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
                     MuxMode::Window => "window".to_string(),
                     MuxMode::Session => "session".to_string(),
                 },
-                has_uncommitted_changes: git::has_uncommitted_changes(&wt.path).unwrap_or(false),
+                has_uncommitted_changes: dirty,
                 is_open: wt.has_mux_window,
                 created_at: wt.created_at,
+                upstream: wt.divergence.upstream,
+                main: wt.divergence.main,
+                disk_usage,
             })
             .collect();
         println!("{}", serde_json::to_string(&entries)?);
@@ -164,16 +273,65 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
 
     // Use icons when outputting to a terminal, text labels when piped (for agents)
     let use_icons = std::io::stdout().is_terminal();
+    let palette =
+        use_icons.then(|| ThemePalette::from_config(&config.theme, resolve_mode(&config.theme)));
     let current_dir = std::env::current_dir()?;
+    let view = TableView {
+        config: &config,
+        show_pr,
+        du,
+        use_icons,
+        palette: palette.as_ref(),
+        current_dir: &current_dir,
+    };
+
+    let mut worktrees = worktrees;
+    let table = render_table(&worktrees, &sizes, &view);
+    print!("{table}");
+
+    // Cached PR status was shown; redraw once fresh status arrives, if it
+    // changed anything. An empty result means gh failed, so keep the cache.
+    if let Some(fetch) = pending_prs {
+        let prs = fetch.wait();
+        if !prs.is_empty() {
+            for wt in &mut worktrees {
+                wt.pr_info = workflow::pr_for_branch(&prs, &wt.branch, None);
+            }
+            let fresh = render_table(&worktrees, &sizes, &view);
+            if fresh != table {
+                let term = Term::stdout();
+                term.clear_last_lines(visual_rows(&table, term.size().1 as usize))?;
+                print!("{fresh}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Display settings for `render_table`.
+struct TableView<'a> {
+    config: &'a config::Config,
+    show_pr: bool,
+    du: bool,
+    use_icons: bool,
+    /// Colors for terminal output; None when piped
+    palette: Option<&'a ThemePalette>,
+    current_dir: &'a Path,
+}
+
+/// Render the worktree table (plus the disk usage total with `--du`).
+fn render_table(worktrees: &[WorktreeInfo], sizes: &[Option<u64>], view: &TableView) -> String {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
 
     let display_data: Vec<WorktreeRow> = worktrees
-        .into_iter()
-        .map(|wt| {
-            let path_str = diff_paths(&wt.path, &current_dir)
+        .iter()
+        .zip(sizes)
+        .map(|(wt, size)| {
+            let path_str = diff_paths(&wt.path, view.current_dir)
                 .map(|p| {
                     let s = p.display().to_string();
                     if s.is_empty() || s == "." {
@@ -193,10 +351,15 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
             };
 
             WorktreeRow {
-                branch: wt.branch,
+                branch: wt.branch.clone(),
                 age,
-                pr_status: format_pr_status(wt.pr_info),
-                agent_status: format_agent_status(wt.agent_status.as_ref(), &config, use_icons),
+                pr_status: format_pr_status(wt.pr_info.clone(), view.palette),
+                agent_status: format_agent_status(
+                    wt.agent_status.as_ref(),
+                    view.config,
+                    view.use_icons,
+                    view.palette,
+                ),
                 mux_status: if wt.has_mux_window {
                     "✓".to_string()
                 } else {
@@ -207,6 +370,9 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
                 } else {
                     "-".to_string()
                 },
+                upstream_status: format_divergence(wt.divergence.upstream),
+                main_status: format_divergence(wt.divergence.main),
+                size: size.map(format_size).unwrap_or_else(|| "-".to_string()),
                 path_str,
             }
         })
@@ -215,14 +381,69 @@ pub fn run(show_pr: bool, json: bool, filter: &[String]) -> Result<()> {
     let mut table = Table::new(display_data);
     table
         .with(Style::blank())
-        .modify(Columns::new(0..7), Padding::new(0, 1, 0, 0));
+        .modify(Columns::new(0..10), Padding::new(0, 1, 0, 0));
 
     // Hide PR column if --pr flag not used
-    if !show_pr {
+    if !view.show_pr {
         table.with(Remove::column(ByColumnName::new("PR")));
     }
 
-    println!("{table}");
+    // Hide SIZE column if --du flag not used
+    if !view.du {
+        table.with(Remove::column(ByColumnName::new("SIZE")));
+    }
 
-    Ok(())
+    let table = table.to_string();
+    let mut out = match (view.palette, table.split_once('\n')) {
+        (Some(palette), Some((header, rows))) => {
+            format!("{}\n{}\n", paint(header, palette.header), rows)
+        }
+        _ => format!("{table}\n"),
+    };
+    if view.du {
+        let total_size: u64 = sizes.iter().flatten().sum();
+        out.push_str(&format!("\nTotal: {}\n", format_size(total_size)));
+    }
+    out
+}
+
+/// Terminal rows `text` occupies, counting lines wider than the terminal
+/// as wrapping.
+fn visual_rows(text: &str, term_width: usize) -> usize {
+    let term_width = term_width.max(1);
+    text.lines()
+        .map(|line| measure_text_width(line).div_ceil(term_width).max(1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visual_rows_counts_wrapped_lines() {
+        assert_eq!(visual_rows("ab\ncd\n", 80), 2);
+        assert_eq!(visual_rows("abcdef\n\n", 4), 3);
+        assert_eq!(visual_rows("\x1b[32mabcd\x1b[0m\n", 4), 1);
+    }
+
+    #[test]
+    fn format_divergence_variants() {
+        assert_eq!(format_divergence(None), "-");
+        assert_eq!(format_divergence(Some(AheadBehind::default())), "=");
+        assert_eq!(
+            format_divergence(Some(AheadBehind {
+                ahead: 2,
+                behind: 0
+            })),
+            "↑2"
+        );
+        assert_eq!(
+            format_divergence(Some(AheadBehind {
+                ahead: 2,
+                behind: 15
+            })),
+            "↑2 ↓15"
+        );
+    }
 }
@@ -0,0 +1,201 @@
+//! `workmux logs`: show the log files written under the state directory.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use crate::logger;
+use crate::util::parse_compact_duration;
+
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn run(follow: bool, since: Option<&str>, lines: usize) -> Result<()> {
+    let files = logger::log_files()?;
+    let Some(newest) = files.last().cloned() else {
+        if !follow {
+            println!("No logs yet in {}", logger::log_dir()?.display());
+            return Ok(());
+        }
+        return follow_from(None);
+    };
+
+    let mut out = io::stdout().lock();
+    let printed = match since {
+        Some(since) => {
+            let cutoff = SystemTime::now()
+                .checked_sub(parse_compact_duration(since)?)
+                .unwrap_or(UNIX_EPOCH)
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            print_since(&mut out, &files, cutoff)
+        }
+        None => print_tail(&mut out, &newest, lines),
+    };
+    if ignore_broken_pipe(printed)? {
+        return Ok(());
+    }
+    drop(out);
+
+    if follow {
+        let offset = fs::metadata(&newest).map(|m| m.len()).unwrap_or(0);
+        return follow_from(Some((newest, offset)));
+    }
+    Ok(())
+}
+
+/// Print every line logged at or after `cutoff` (unix seconds). Lines
+/// without a timestamp (e.g. multi-line messages) follow the line before.
+fn print_since(out: &mut impl Write, files: &[PathBuf], cutoff: u64) -> io::Result<()> {
+    let mut include = false;
+    for path in files {
+        let Ok(file) = File::open(path) else {
+            continue;
+        };
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if let Some(ts) = line_timestamp(&line) {
+                include = ts >= cutoff;
+            }
+            if include {
+                writeln!(out, "{}", line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the last `count` lines of `path`.
+fn print_tail(out: &mut impl Write, path: &Path, count: usize) -> io::Result<()> {
+    let content = fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    for line in &lines[lines.len().saturating_sub(count)..] {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Print lines as they're appended, switching to the next file when the
+/// log rotates. `current` is the file and offset already printed up to.
+fn follow_from(mut current: Option<(PathBuf, u64)>) -> Result<()> {
+    loop {
+        if let Some(newest) = logger::log_files()?.pop() {
+            let mut offset = match current.take() {
+                Some((path, offset)) if path == newest => offset,
+                Some((path, offset)) => {
+                    // Rotated: finish the previous file before moving on
+                    if ignore_broken_pipe(copy_from(&path, offset).map(drop))? {
+                        return Ok(());
+                    }
+                    0
+                }
+                None => 0,
+            };
+            let len = fs::metadata(&newest).map(|m| m.len()).unwrap_or(0);
+            // Truncated (or replaced) since the last read
+            if len < offset {
+                offset = 0;
+            }
+            if len > offset {
+                match copy_from(&newest, offset) {
+                    Ok(copied) => offset += copied,
+                    Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+                    Err(e) => return Err(e).context("Failed to print logs"),
+                }
+            }
+            current = Some((newest, offset));
+        }
+        thread::sleep(FOLLOW_POLL_INTERVAL);
+    }
+}
+
+/// Copy `path` from `offset` to stdout, returning the bytes copied.
+fn copy_from(path: &Path, offset: u64) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut out = io::stdout().lock();
+    let copied = io::copy(&mut file, &mut out)?;
+    out.flush()?;
+    Ok(copied)
+}
+
+/// Turn a closed pipe (e.g. `workmux logs | head`) into a clean stop.
+/// Returns true if output should stop.
+fn ignore_broken_pipe(result: io::Result<()>) -> Result<bool> {
+    match result {
+        Ok(()) => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(true),
+        Err(e) => Err(e).context("Failed to print logs"),
+    }
+}
+
+/// Unix seconds of a line starting with an RFC 3339 UTC timestamp, as
+/// written by the tracing formatter (`2026-10-16T12:34:56.123456Z  INFO ...`).
+fn line_timestamp(line: &str) -> Option<u64> {
+    let ts = line.get(..19)?;
+    let bytes = ts.as_bytes();
+    if bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| ts.get(range)?.parse::<u64>().ok();
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (from 1970 on).
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146097 + day_of_era).saturating_sub(719468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_line_timestamps() {
+        assert_eq!(
+            line_timestamp("1970-01-01T00:00:00.000000Z  INFO x"),
+            Some(0)
+        );
+        assert_eq!(
+            line_timestamp("2026-10-16T12:34:56.123456Z  INFO workmux: started"),
+            Some(1_792_154_096)
+        );
+        assert_eq!(line_timestamp("2024-02-29T00:00:00Z"), Some(1_709_164_800));
+        assert_eq!(line_timestamp("    continuation of a message"), None);
+        assert_eq!(line_timestamp("short"), None);
+    }
+
+    #[test]
+    fn since_keeps_continuation_lines_with_their_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workmux.2026-10-16.log");
+        fs::write(
+            &path,
+            "2026-10-16T10:00:00Z  INFO old\n  old detail\n\
+             2026-10-16T12:00:00Z  INFO new\n  new detail\n",
+        )
+        .unwrap();
+        let cutoff = line_timestamp("2026-10-16T11:00:00Z").unwrap();
+        let mut out = Vec::new();
+        print_since(&mut out, &[path], cutoff).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2026-10-16T12:00:00Z  INFO new\n  new detail\n"
+        );
+    }
+}
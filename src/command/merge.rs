@@ -1,6 +1,7 @@
 use crate::config::MergeStrategy;
 use crate::multiplexer::{create_backend, detect_backend};
 use crate::workflow::WorkflowContext;
+use crate::workflow::types::MergeResult;
 use crate::{config, workflow};
 use anyhow::{Context, Result};
 
@@ -15,9 +16,18 @@ pub fn run(
     no_verify: bool,
     no_hooks: bool,
     notification: bool,
+    porcelain: bool,
+    approve: bool,
 ) -> Result<()> {
     // Inside a sandbox guest, route through RPC to the host supervisor
     if crate::sandbox::guest::is_sandbox_guest() {
+        if porcelain {
+            anyhow::bail!("--porcelain is not supported inside a sandbox");
+        }
+        // The agent could pass it itself
+        if approve {
+            anyhow::bail!("--approve is not accepted inside a sandbox");
+        }
         let name_to_merge = super::resolve_name(name)?;
         return run_via_rpc(
             &name_to_merge,
@@ -46,9 +56,9 @@ pub fn run(
         }
     }
 
-    // Resolve name from argument or current directory
+    // Resolve name from argument, current directory, or the picker
     // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
-    let name_to_merge = super::resolve_name(name)?;
+    let name_to_merge = super::resolve_name_or_pick(name, "merge")?;
 
     let mux = create_backend(detect_backend());
     let context = WorkflowContext::new(config, mux, None)?;
@@ -75,10 +85,16 @@ pub fn run(
         no_verify,
         no_hooks,
         notification,
+        approve,
         &context,
     )
     .context("Failed to merge worktree")?;
 
+    if porcelain {
+        println!("{}", porcelain_line(&result, keep));
+        return Ok(());
+    }
+
     if result.had_staged_changes {
         println!("✓ Committed staged changes");
     }
@@ -101,6 +117,16 @@ pub fn run(
     Ok(())
 }
 
+/// `merged<TAB>branch<TAB>target<TAB>kept|cleaned`, the `--porcelain` result.
+fn porcelain_line(result: &MergeResult, keep: bool) -> String {
+    format!(
+        "merged\t{}\t{}\t{}",
+        result.branch_merged,
+        result.main_branch,
+        if keep { "kept" } else { "cleaned" }
+    )
+}
+
 /// Run merge via RPC when inside a sandbox guest.
 #[allow(clippy::too_many_arguments)]
 fn run_via_rpc(
@@ -146,3 +172,25 @@ fn run_via_rpc(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_line_fields() {
+        let result = MergeResult {
+            branch_merged: "user-auth".to_string(),
+            main_branch: "main".to_string(),
+            had_staged_changes: false,
+        };
+        assert_eq!(
+            porcelain_line(&result, false),
+            "merged\tuser-auth\tmain\tcleaned"
+        );
+        assert_eq!(
+            porcelain_line(&result, true),
+            "merged\tuser-auth\tmain\tkept"
+        );
+    }
+}
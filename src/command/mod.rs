@@ -1,39 +1,66 @@
 pub mod add;
+pub mod add_wizard;
 pub mod args;
+pub mod audit;
 pub mod capture;
 pub mod changelog;
 pub mod clipboard_read;
 pub mod close;
 pub mod config;
 pub mod dashboard;
+pub mod digest;
 pub mod docs;
+pub mod doctor;
 pub mod exec;
 pub mod host_exec;
+pub mod init;
 pub mod last_agent;
 pub mod last_done;
 pub mod list;
+pub mod logs;
 pub mod merge;
 pub mod open;
 pub mod path;
+pub mod peek;
+pub mod plugin;
+pub mod prompt_segment;
+pub mod prompts;
 pub mod remove;
 pub mod rename;
+pub mod report;
 pub mod resurrect;
 pub mod run;
+pub mod run_all;
+pub mod runs;
 pub mod sandbox;
 pub mod sandbox_run;
 pub mod send;
+pub mod serve;
+pub mod session;
+pub mod session_started;
 pub mod set_base;
 pub mod set_window_status;
 pub mod setup;
+#[cfg(unix)]
+pub mod sidebar;
+#[cfg(windows)]
+#[path = "sidebar_windows.rs"]
 pub mod sidebar;
 pub mod status;
 pub mod sync_files;
 pub mod update;
 pub mod wait;
+pub mod watch;
+pub mod with_secrets;
+
+use std::io::IsTerminal;
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 
-use crate::{config::Config, workflow::SetupOptions};
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::ui::picker::{self, PickerItem};
+use crate::workflow::types::WorktreeInfo;
+use crate::{config::Config, spinner, workflow, workflow::SetupOptions};
 
 /// Represents the different phases where hooks can be executed
 pub enum HookPhase {
@@ -88,6 +115,94 @@ pub fn resolve_name(arg: Option<&str>) -> Result<String> {
     }
 }
 
+/// Like [`resolve_name`], but when no name is given and the current
+/// directory isn't a worktree, let the user pick one interactively.
+///
+/// Falls back to [`resolve_name`] when stdin or stdout isn't a terminal, so
+/// scripts keep the old behavior.
+pub fn resolve_name_or_pick(arg: Option<&str>, action: &str) -> Result<String> {
+    if arg.is_some()
+        || !crate::interactive::can_prompt()
+        || !std::io::stdout().is_terminal()
+        || in_linked_worktree()
+    {
+        return resolve_name(arg);
+    }
+    pick_worktree(action)
+}
+
+fn in_linked_worktree() -> bool {
+    match (
+        crate::git::get_repo_root(),
+        crate::git::get_main_worktree_root(),
+    ) {
+        (Ok(root), Ok(main)) => root != main,
+        _ => false,
+    }
+}
+
+/// Show a fuzzy picker of the repo's worktrees, annotated with agent and PR
+/// status, and return the chosen handle.
+pub fn pick_worktree(action: &str) -> Result<String> {
+    let config = Config::load(None)?;
+    let mux = create_backend(detect_backend());
+    let fetch_prs = which::which("gh").is_ok();
+    // Cached PR status is fine for a picker; a fetch still running refreshes
+    // the cache in the background while the picker is open
+    let worktrees: Vec<WorktreeInfo> = spinner::with_spinner("Loading worktrees", || {
+        if fetch_prs {
+            workflow::list_with_cached_prs(&config, mux.as_ref(), &[]).map(|(wts, _)| wts)
+        } else {
+            workflow::list(&config, mux.as_ref(), false, &[])
+        }
+    })?
+    .into_iter()
+    .filter(|wt| !wt.is_main)
+    .collect();
+    if worktrees.is_empty() {
+        bail!("No worktrees found. Create one with 'workmux add <branch>'.");
+    }
+
+    let items: Vec<PickerItem> = worktrees
+        .iter()
+        .map(|wt| PickerItem {
+            label: wt.handle.clone(),
+            detail: picker_detail(wt, &config),
+        })
+        .collect();
+    match picker::pick(&format!("Worktree to {}", action), &items)? {
+        Some(index) => Ok(worktrees[index].handle.clone()),
+        None => bail!("No worktree selected"),
+    }
+}
+
+fn picker_detail(wt: &WorktreeInfo, config: &Config) -> String {
+    let mut parts = Vec::new();
+    if wt.branch != wt.handle {
+        parts.push(wt.branch.clone());
+    }
+    if wt.agent_status.is_some() {
+        parts.push(list::format_agent_status(
+            wt.agent_status.as_ref(),
+            config,
+            false,
+            None,
+        ));
+    }
+    if let Some(pr) = &wt.pr_info {
+        let state = if pr.is_draft {
+            "draft".to_string()
+        } else {
+            pr.state.to_lowercase()
+        };
+        parts.push(format!("#{} {}", pr.number, state));
+    }
+    if !wt.has_mux_window {
+        parts.push("closed".to_string());
+    }
+    parts.join("  ")
+}
+
 /// Internal function to resolve worktree name from a path.
 /// Separated for testability.
 ///
@@ -1,7 +1,9 @@
+use std::io::IsTerminal;
+
 use crate::command::args::PromptArgs;
 use crate::config::MuxMode;
 use crate::multiplexer::{create_backend, detect_backend};
-use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt};
+use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt, parse_vars, render_with_vars};
 use crate::workflow::{SetupOptions, WorkflowContext};
 use crate::{config, workflow};
 use anyhow::{Context, Result, bail};
@@ -14,6 +16,8 @@ pub fn run(
     new_window: bool,
     mode_override: Option<MuxMode>,
     continue_session: bool,
+    resume_session: Option<usize>,
+    layout: Option<String>,
     prompt_args: PromptArgs,
     config_override: Option<&std::path::Path>,
 ) -> Result<()> {
@@ -21,13 +25,16 @@ pub fn run(
         bail!("--config is not supported from inside a sandbox");
     }
 
-    // Resolve names: use provided names, or infer from current directory with --new
+    // Resolve names: use provided names, infer from current directory with
+    // --new, or let the user pick one
     let resolved_names: Vec<String> = if names.is_empty() {
         if new_window {
             let inferred = super::resolve_name(None).context(
                 "Could not infer current worktree. Run inside a worktree or provide a name.",
             )?;
             vec![inferred]
+        } else if crate::interactive::can_prompt() && std::io::stdout().is_terminal() {
+            vec![super::pick_worktree("open")?]
         } else {
             bail!("Worktree name is required unless --new is provided")
         }
@@ -37,10 +44,14 @@ pub fn run(
 
     // Disallow prompt args when opening multiple worktrees
     if resolved_names.len() > 1 && prompt_args.has_any() {
-        bail!("Prompt arguments (-p, -P, -e) cannot be used when opening multiple worktrees");
+        bail!("Prompt arguments (-p, -P, -e, -T) cannot be used when opening multiple worktrees");
     }
 
     let (config, config_location) = config::Config::load_with_location(None, config_override)?;
+    // Fail fast on an unknown layout, before touching any worktree
+    if let Some(name) = &layout {
+        config.clone().apply_layout(name)?;
+    }
     let mux = create_backend(detect_backend());
     let context = WorkflowContext::new(config, mux, config_location)?;
 
@@ -51,11 +62,20 @@ pub fn run(
     }
 
     // Load prompt if any prompt argument is provided
+    let vars = parse_vars(&prompt_args.vars)?;
     let prompt = load_prompt(&PromptLoadArgs {
         prompt_editor: prompt_args.prompt_editor,
         prompt_inline: prompt_args.prompt.as_deref(),
         prompt_file: prompt_args.prompt_file.as_ref(),
+        prompt_template: prompt_args.prompt_template.as_deref(),
     })?;
+    // Templates are rendered here; other prompts only when --var is given
+    let prompt = match prompt {
+        Some(p) if prompt_args.prompt_template.is_some() || !vars.is_empty() => {
+            Some(render_with_vars(&p, &vars)?)
+        }
+        p => p,
+    };
 
     let prompt_file_only =
         prompt_args.prompt_file_only || context.config.prompt_file_only.unwrap_or(false);
@@ -90,7 +110,24 @@ pub fn run(
         options.prompt_file_path = prompt_file_path;
         if continue_session {
             options.resume_mode = crate::multiplexer::types::ResumeMode::Continue;
+        } else if let Some(n) = resume_session {
+            let (worktree_path, branch) = crate::git::find_worktree(resolved_name)?;
+            let repo = crate::git::get_main_worktree_root()?;
+            let record = crate::state::sessions::nth_session(&repo, &branch, n)?;
+            // Resume the agent's own copy of the conversation, not the recorded ID
+            let forker = crate::multiplexer::conversation::resolve_forker(&record.agent)
+                .with_context(|| format!("Agent '{}' can't resume sessions", record.agent))?;
+            let session = forker
+                .find_conversation(&worktree_path, &record.id)?
+                .with_context(|| {
+                    format!(
+                        "Session {} of branch '{}' is no longer in {}'s history",
+                        record.id, branch, record.agent
+                    )
+                })?;
+            options.resume_mode = crate::multiplexer::types::ResumeMode::ForkSession(session.id);
         }
+        options.layout = layout.clone();
 
         // Only announce hooks if we're forcing a new target (otherwise we might just switch)
         if new_window {
@@ -1,14 +1,10 @@
-use crate::git;
-use anyhow::{Result, anyhow};
+use crate::{git, vcs};
+use anyhow::Result;
 
 pub fn run(name: &str) -> Result<()> {
     // Smart resolution: try handle first, then branch name
-    let (path, _branch) = git::find_worktree(name).map_err(|_| {
-        anyhow!(
-            "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-            name
-        )
-    })?;
+    let (path, _branch) =
+        vcs::find_workspace(name).map_err(|_| git::WorktreeNotFound(name.to_string()))?;
     println!("{}", path.display());
     Ok(())
 }
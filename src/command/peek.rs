@@ -0,0 +1,10 @@
+use anyhow::Result;
+
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::workflow;
+
+pub fn run(name: &str) -> Result<()> {
+    let mux = create_backend(detect_backend());
+    let (_path, agent) = workflow::resolve_worktree_agent(name, mux.as_ref())?;
+    mux.peek_pane(&agent.pane_id, name)
+}
@@ -0,0 +1,139 @@
+//! External subcommands: `workmux foo` runs `workmux-foo` from PATH, like git.
+//!
+//! The plugin gets the remaining arguments and a few environment variables
+//! describing where it was run, so it doesn't have to rediscover them.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::{git, platform, workflow};
+
+/// Global options that take a value and may come before the subcommand
+const GLOBAL_VALUE_OPTIONS: &[&str] = &["--repo", "--remote", "--backend", "--socket"];
+
+/// Whether `name` is the top-level subcommand on the command line, rather
+/// than an unknown nested one like `workmux runs foo`.
+pub fn is_top_level(args: &[String], name: &str) -> bool {
+    let mut i = 1;
+    while let Some(arg) = args.get(i) {
+        if GLOBAL_VALUE_OPTIONS.contains(&arg.as_str()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return arg == name;
+        }
+    }
+    false
+}
+
+/// Find the `workmux-<name>` executable for an unknown subcommand.
+pub fn find(name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.starts_with(['-', '_']) || name.contains(['/', '\\']) {
+        return None;
+    }
+    which::which(format!("workmux-{}", name)).ok()
+}
+
+/// Replace this process with the plugin. Only returns on failure.
+pub fn exec(program: PathBuf, name: &str, args: &[String]) -> Result<()> {
+    let (repo, rest) = split_args(args, name);
+    if let Some(repo) = repo {
+        let root = workflow::repos::resolve_repo(repo)?;
+        std::env::set_current_dir(&root)
+            .with_context(|| format!("Failed to change to {}", root.display()))?;
+    }
+
+    let mut command = Command::new(&program);
+    command.args(rest);
+    if let Ok(exe) = std::env::current_exe() {
+        command.env("WORKMUX_BIN", exe);
+    }
+    if let Ok(dir) = crate::xdg::state_dir() {
+        command.env("WORKMUX_STATE_DIR", dir);
+    }
+    if let Ok(main_root) = git::get_main_worktree_root() {
+        command.env("WORKMUX_REPO_ROOT", &main_root);
+        if let Ok(root) = git::get_repo_root()
+            && root != main_root
+        {
+            if let Some(handle) = root.file_name() {
+                command.env("WORKMUX_HANDLE", handle);
+            }
+            command.env("WORKMUX_WORKTREE", root);
+        }
+    }
+
+    let err = platform::exec(&mut command);
+    Err(anyhow!("Failed to run {}: {}", program.display(), err))
+}
+
+/// Split the command line around the plugin name: the global `--repo`
+/// value given before it, and the arguments after it for the plugin.
+fn split_args<'a>(args: &'a [String], name: &str) -> (Option<&'a str>, &'a [String]) {
+    let mut repo = None;
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == name {
+            return (repo, &args[i + 1..]);
+        }
+        if arg == "--repo" {
+            repo = args.get(i + 1).map(String::as_str);
+            i += 1;
+        } else if let Some(value) = arg.strip_prefix("--repo=") {
+            repo = Some(value);
+        }
+        i += 1;
+    }
+    (repo, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn split_args_around_plugin_name() {
+        let a = args(&["workmux", "deploy", "--env", "staging"]);
+        assert_eq!(split_args(&a, "deploy"), (None, &a[2..]));
+
+        let a = args(&["workmux", "--repo", "api", "deploy", "deploy"]);
+        assert_eq!(split_args(&a, "deploy"), (Some("api"), &a[4..]));
+
+        let a = args(&["workmux", "--repo=web", "deploy"]);
+        assert_eq!(split_args(&a, "deploy"), (Some("web"), &a[3..]));
+    }
+
+    #[test]
+    fn is_top_level_skips_global_options() {
+        assert!(is_top_level(&args(&["workmux", "deploy"]), "deploy"));
+        assert!(is_top_level(
+            &args(&["workmux", "--repo", "api", "-y", "deploy"]),
+            "deploy"
+        ));
+        assert!(is_top_level(
+            &args(&["workmux", "--repo=api", "deploy"]),
+            "deploy"
+        ));
+        assert!(!is_top_level(&args(&["workmux", "runs", "foo"]), "foo"));
+        assert!(!is_top_level(
+            &args(&["workmux", "--repo", "foo", "runs", "foo"]),
+            "foo"
+        ));
+    }
+
+    #[test]
+    fn find_rejects_unsafe_names() {
+        assert_eq!(find(""), None);
+        assert_eq!(find("-x"), None);
+        assert_eq!(find("_complete"), None);
+        assert_eq!(find("../bin/sh"), None);
+    }
+}
@@ -0,0 +1,178 @@
+//! `workmux prompt-segment`: a compact status for shell prompts.
+//!
+//! Runs on every prompt, so it only reads files: `.git` and `HEAD` to find
+//! the worktree and branch, the agent state store, and the PR cache the
+//! dashboard writes. It never spawns git, gh, or the multiplexer.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::github;
+use crate::multiplexer::AgentStatus;
+use crate::state::StateStore;
+
+/// A checkout found by walking up from a directory.
+#[derive(Debug, PartialEq)]
+struct Checkout {
+    /// Directory containing `.git`
+    root: PathBuf,
+    /// Root of the main worktree (the PR cache key)
+    main_root: PathBuf,
+    /// Checked-out branch, if HEAD isn't detached
+    branch: Option<String>,
+    /// Whether this is a linked worktree rather than the main checkout
+    linked: bool,
+}
+
+pub fn run() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let Some(checkout) = find_checkout(&cwd) else {
+        return Ok(());
+    };
+
+    let status = agent_status(&checkout.root);
+    let pr = checkout
+        .branch
+        .as_ref()
+        .filter(|b| *b != "main" && *b != "master")
+        .and_then(|branch| {
+            github::load_pr_cache()
+                .remove(&checkout.main_root)?
+                .remove(branch)
+        });
+
+    let mut parts = Vec::new();
+    if let Some(status) = status {
+        // Global config only: finding the project config would spawn git
+        let config = Config::load_global().ok().flatten().unwrap_or_default();
+        let icon = match status {
+            AgentStatus::Working => config.status_icons.working(),
+            AgentStatus::Waiting => config.status_icons.waiting(),
+            AgentStatus::Done => config.status_icons.done(),
+        };
+        parts.push(icon.to_string());
+    }
+    if checkout.linked
+        && let Some(name) = checkout.root.file_name()
+    {
+        parts.push(name.to_string_lossy().into_owned());
+    }
+    if let Some(pr) = pr {
+        parts.push(super::status::pr_label(Some(&pr)));
+    }
+
+    if !parts.is_empty() {
+        println!("{}", parts.join(" "));
+    }
+    Ok(())
+}
+
+/// Status of the most recently updated agent working in `root`.
+fn agent_status(root: &Path) -> Option<AgentStatus> {
+    let agents = StateStore::new().ok()?.list_all_agents().ok()?;
+    agents
+        .into_iter()
+        .filter(|agent| agent.status.is_some())
+        .filter(|agent| find_checkout(&agent.workdir).is_some_and(|checkout| checkout.root == root))
+        .max_by_key(|agent| agent.updated_ts)
+        .and_then(|agent| agent.status)
+}
+
+/// Find the checkout containing `dir` by reading `.git` directly.
+fn find_checkout(dir: &Path) -> Option<Checkout> {
+    let root = dir.ancestors().find(|d| d.join(".git").exists())?;
+    let dot_git = root.join(".git");
+
+    if dot_git.is_dir() {
+        return Some(Checkout {
+            root: root.to_path_buf(),
+            main_root: root.to_path_buf(),
+            branch: read_branch(&dot_git),
+            linked: false,
+        });
+    }
+
+    // Linked worktree: `.git` is a file pointing at `<common>/worktrees/<name>`
+    let contents = fs::read_to_string(&dot_git).ok()?;
+    let gitdir = contents.trim().strip_prefix("gitdir:")?.trim();
+    let gitdir = root.join(gitdir);
+    let common_dir = match fs::read_to_string(gitdir.join("commondir")) {
+        Ok(common) => gitdir.join(common.trim()),
+        Err(_) => gitdir.parent()?.parent()?.to_path_buf(),
+    };
+    let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+    Some(Checkout {
+        root: root.to_path_buf(),
+        main_root: common_dir.parent()?.to_path_buf(),
+        branch: read_branch(&gitdir),
+        linked: true,
+    })
+}
+
+fn read_branch(gitdir: &Path) -> Option<String> {
+    let head = fs::read_to_string(gitdir.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn find_checkout_reads_linked_worktree() {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path().canonicalize().unwrap();
+        let main = base.join("shop");
+        let gitdir = main.join(".git/worktrees/api");
+        fs::create_dir_all(&gitdir).unwrap();
+        fs::write(main.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(gitdir.join("HEAD"), "ref: refs/heads/feature/api\n").unwrap();
+        fs::write(gitdir.join("commondir"), "../..\n").unwrap();
+
+        let worktree = base.join("shop__worktrees/api");
+        fs::create_dir_all(worktree.join("src")).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", gitdir.display()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_checkout(&worktree.join("src")),
+            Some(Checkout {
+                root: worktree.clone(),
+                main_root: main.clone(),
+                branch: Some("feature/api".to_string()),
+                linked: true,
+            })
+        );
+        assert_eq!(
+            find_checkout(&main),
+            Some(Checkout {
+                root: main.clone(),
+                main_root: main,
+                branch: Some("main".to_string()),
+                linked: false,
+            })
+        );
+    }
+
+    #[test]
+    fn find_checkout_detached_head() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+        fs::write(
+            temp.path().join(".git/HEAD"),
+            "0123456789abcdef0123456789abcdef01234567\n",
+        )
+        .unwrap();
+        let checkout = find_checkout(temp.path()).unwrap();
+        assert_eq!(checkout.branch, None);
+    }
+}
@@ -0,0 +1,158 @@
+//! `workmux prompts`: manage the prompt template library used by
+//! `--prompt-template`.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::prompt::{Prompt, parse_prompt_document};
+use crate::template::create_template_env;
+use crate::workflow::prompt_loader::{library_dir, list_templates, template_path};
+
+/// Longest description shown by `prompts list`
+const MAX_DESCRIPTION_LEN: usize = 60;
+
+/// Written to new templates by `prompts edit`
+const NEW_TEMPLATE: &str = "\
+Fix issue #{{ issue }}.
+
+Reproduce it with a failing test first, then fix it.
+";
+
+#[derive(Debug, Args)]
+pub struct PromptsArgs {
+    #[command(subcommand)]
+    pub command: PromptsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PromptsCommand {
+    /// List the templates in the library
+    #[command(visible_alias = "ls")]
+    List,
+    /// Open a template in your editor, creating it if needed
+    Edit {
+        /// Template name (the file name without .md)
+        name: String,
+    },
+    /// Print the path to the library directory
+    Path,
+}
+
+#[derive(Tabled)]
+struct TemplateRow {
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "VARIABLES")]
+    variables: String,
+    #[tabled(rename = "DESCRIPTION")]
+    description: String,
+}
+
+pub fn run(args: PromptsArgs) -> Result<()> {
+    match args.command {
+        PromptsCommand::List => run_list(),
+        PromptsCommand::Edit { name } => run_edit(&name),
+        PromptsCommand::Path => {
+            println!("{}", library_dir()?.display());
+            Ok(())
+        }
+    }
+}
+
+fn run_list() -> Result<()> {
+    let names = list_templates()?;
+    if names.is_empty() {
+        println!(
+            "No prompt templates in {}. Create one with: workmux prompts edit <name>",
+            library_dir()?.display()
+        );
+        return Ok(());
+    }
+
+    let rows = names
+        .into_iter()
+        .map(|name| {
+            let (variables, description) = match describe(&name) {
+                Ok(summary) => summary,
+                Err(e) => (String::new(), format!("(invalid: {})", e)),
+            };
+            TemplateRow {
+                name,
+                variables,
+                description,
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..3), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+/// The variables a template uses, comma-separated, and its first line.
+fn describe(name: &str) -> Result<(String, String)> {
+    let doc = parse_prompt_document(&Prompt::FromFile(template_path(name)?))?;
+    let env = create_template_env();
+    let template = env.template_from_str(&doc.body)?;
+    // Frontmatter foreach variables are filled in by `add` itself
+    let foreach = doc.meta.foreach.unwrap_or_default();
+    let mut variables: Vec<String> = template
+        .undeclared_variables(false)
+        .into_iter()
+        .filter(|var| !foreach.contains_key(var))
+        .collect();
+    variables.sort();
+    Ok((variables.join(", "), first_line(&doc.body)))
+}
+
+/// First non-empty line of `body`, shortened for the table.
+fn first_line(body: &str) -> String {
+    let line = body
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() > MAX_DESCRIPTION_LEN {
+        let cut: String = line.chars().take(MAX_DESCRIPTION_LEN - 3).collect();
+        format!("{}...", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+fn run_edit(name: &str) -> Result<()> {
+    let path = template_path(name)?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        fs::write(&path, NEW_TEMPLATE)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        println!("Created {}", path.display());
+    }
+    super::config::open_in_editor(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_DESCRIPTION_LEN, first_line};
+
+    #[test]
+    fn first_line_skips_blanks_and_heading_marks() {
+        assert_eq!(first_line("\n\n## Bug fix\nDetails"), "Bug fix");
+        assert_eq!(first_line(""), "");
+        let long = "word ".repeat(30);
+        let line = first_line(&long);
+        assert!(line.ends_with("..."));
+        assert!(line.chars().count() <= MAX_DESCRIPTION_LEN);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::multiplexer::{create_backend, detect_backend};
 use crate::workflow::WorkflowContext;
-use crate::{config, git, spinner, workflow};
+use crate::{config, git, spinner, vcs, workflow};
 use anyhow::{Context, Result, anyhow};
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -23,11 +23,11 @@ pub fn run(
     run_specified(names, force, keep_branch)
 }
 
-/// Remove specific worktrees provided by user (or current if empty)
+/// Remove specific worktrees provided by user (or current/picked if empty)
 fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<()> {
     // Normalize all inputs (handles "." and other special cases)
     let resolved_names: Vec<String> = if names.is_empty() {
-        vec![super::resolve_name(None)?]
+        vec![super::resolve_name_or_pick(None, "remove")?]
     } else {
         names
             .iter()
@@ -38,12 +38,8 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
     // 2. Resolve all targets and validate they exist
     let mut candidates: Vec<(String, PathBuf, String)> = Vec::new();
     for name in resolved_names {
-        let (worktree_path, branch_name) = git::find_worktree(&name).map_err(|_| {
-            anyhow!(
-                "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-                name
-            )
-        })?;
+        let (worktree_path, branch_name) =
+            vcs::find_workspace(&name).map_err(|_| git::WorktreeNotFound(name.to_string()))?;
 
         let handle = worktree_path
             .file_name()
@@ -118,6 +114,11 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
         for (_, branch, base) in &unmerged {
             println!("  - {} (base: {})", branch, base);
         }
+        if crate::interactive::is_enabled() {
+            return Err(anyhow!(
+                "Refusing to delete unmerged branches without a prompt. Use --force to override."
+            ));
+        }
         println!("\nThis will delete the worktree, tmux window, and local branch.");
         print!("Are you sure you want to continue? [y/N] ");
         io::stdout().flush().context("Failed to flush stdout")?;
@@ -176,7 +177,7 @@ fn is_unmerged(branch: &str) -> Result<Option<String>> {
 
 /// Remove all managed worktrees (except main)
 fn run_all(force: bool, keep_branch: bool) -> Result<()> {
-    let worktrees = git::list_worktrees()?;
+    let worktrees = vcs::list_workspaces()?;
     let main_branch = git::get_default_branch()?;
     let main_worktree_root = git::get_main_worktree_root()?;
 
@@ -279,8 +280,8 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
         }
     }
 
-    // Confirm with user unless --force
-    if !force {
+    // Confirm with user unless --force or --yes
+    if !force && !crate::interactive::is_enabled() {
         print!(
             "\nAre you sure you want to remove ALL {} worktree(s)? [y/N] ",
             to_remove.len()
@@ -329,7 +330,7 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
     // Fetch with prune to update remote-tracking refs
     spinner::with_spinner("Fetching from remote", git::fetch_prune)?;
 
-    let worktrees = git::list_worktrees()?;
+    let worktrees = vcs::list_workspaces()?;
     let main_branch = git::get_default_branch()?;
     let main_worktree_root = git::get_main_worktree_root()?;
 
@@ -406,8 +407,8 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
         }
     }
 
-    // Confirm with user unless --force
-    if !force {
+    // Confirm with user unless --force or --yes
+    if !force && !crate::interactive::is_enabled() {
         print!(
             "\nAre you sure you want to remove {} worktree(s)? [y/N] ",
             to_remove.len()
@@ -0,0 +1,405 @@
+//! `workmux report`: active time per branch and project, from the activity
+//! log (agent status changes, window focus and prompts), or a Markdown work
+//! summary that adds merges and pull requests.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::config::{Config, NotificationEvent};
+use crate::github::{self, OpenedPr};
+use crate::notification::webhook::WebhookPayload;
+use crate::state::activity::{self, ActivityEvent, ActivityKind, BranchTime};
+use crate::util::{format_utc_date, parse_compact_duration};
+
+const SUMMARY_PROMPT: &str = "Summarize this work report in 3 to 5 sentences for a status update: \
+what shipped, what is in progress, and where the time went. Output only the summary.";
+
+#[derive(Tabled)]
+struct TimeRow {
+    #[tabled(rename = "PROJECT")]
+    project: String,
+    #[tabled(rename = "BRANCH")]
+    branch: String,
+    #[tabled(rename = "ACTIVE")]
+    active: String,
+    #[tabled(rename = "AGENT")]
+    agent: String,
+    #[tabled(rename = "FOCUSED")]
+    focused: String,
+}
+
+/// What goes into a Markdown report
+struct Report<'a> {
+    since: u64,
+    until: u64,
+    times: &'a [BranchTime],
+    /// Activity events in the period, oldest first
+    activity: &'a [ActivityEvent],
+    /// `merged` events in the period
+    merged: &'a [WebhookPayload],
+    /// Pull requests opened, with their project
+    prs: &'a [(String, OpenedPr)],
+}
+
+pub fn run(
+    week: bool,
+    since: Option<&str>,
+    json: bool,
+    markdown: bool,
+    summarize: bool,
+) -> Result<()> {
+    let period_secs = match since {
+        Some(since) => parse_compact_duration(since)?.as_secs(),
+        None if week => 7 * 86_400,
+        None => 86_400,
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let start = now.saturating_sub(period_secs);
+    let mut events = activity::load_since(start)?;
+    let times = activity::summarize(&events, start, now);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&times)?);
+        return Ok(());
+    }
+    if markdown || summarize {
+        events.retain(|event| event.ts >= start);
+        let merged: Vec<WebhookPayload> = crate::notification::history::load_since(start)?
+            .into_iter()
+            .filter(|event| event.event == NotificationEvent::Merged)
+            .collect();
+        let prs = opened_prs(&projects(&times, &events, &merged), start);
+        let report = Report {
+            since: start,
+            until: now,
+            times: &times,
+            activity: &events,
+            merged: &merged,
+            prs: &prs,
+        };
+        let summary = if summarize {
+            Some(summarize_report(&render_markdown(&report, None))?)
+        } else {
+            None
+        };
+        print!("{}", render_markdown(&report, summary.as_deref()));
+        return Ok(());
+    }
+    if times.is_empty() {
+        println!("No activity recorded in this period");
+        return Ok(());
+    }
+
+    let rows: Vec<TimeRow> = times
+        .iter()
+        .map(|time| TimeRow {
+            project: time.project.clone(),
+            branch: time.branch.clone(),
+            active: format_hours(time.active_secs),
+            agent: format_hours(time.agent_secs),
+            focused: format_hours(time.focus_secs),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..5), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+
+    println!();
+    for (project, secs) in project_totals(&times) {
+        println!("{}: {}", project, format_hours(secs));
+    }
+    Ok(())
+}
+
+/// Projects with anything to report.
+fn projects(
+    times: &[BranchTime],
+    events: &[ActivityEvent],
+    merged: &[WebhookPayload],
+) -> BTreeSet<String> {
+    times
+        .iter()
+        .map(|time| &time.project)
+        .chain(events.iter().map(|event| &event.project))
+        .chain(merged.iter().map(|event| &event.project))
+        .filter(|project| !project.is_empty())
+        .cloned()
+        .collect()
+}
+
+/// Pull requests opened in `projects` since `since`. Projects workmux can't
+/// locate or query are left out.
+fn opened_prs(projects: &BTreeSet<String>, since: u64) -> Vec<(String, OpenedPr)> {
+    let date = format_utc_date(since);
+    let mut prs = Vec::new();
+    for (project, root) in crate::workflow::repos::known_repos() {
+        if !projects.contains(&project) {
+            continue;
+        }
+        match github::list_prs_opened_since(&root, &date) {
+            Ok(opened) => prs.extend(opened.into_iter().map(|pr| (project.clone(), pr))),
+            Err(e) => tracing::debug!(project = %project, error = %e, "report:pr lookup failed"),
+        }
+    }
+    prs
+}
+
+/// An overview of `markdown` from the configured generator (see `auto_name`).
+fn summarize_report(markdown: &str) -> Result<String> {
+    let config = Config::load(None)?;
+    let model = config.auto_name.as_ref().and_then(|c| c.model.as_deref());
+    let command = super::add::generator_command(&config);
+    let program = command
+        .and_then(|cmd| cmd.split_whitespace().next())
+        .unwrap_or("llm");
+    crate::spinner::with_spinner(&format!("Summarizing with {}", program), || {
+        crate::llm::complete(
+            &format!("{}\n\n{}", SUMMARY_PROMPT, markdown),
+            model,
+            command,
+        )
+    })
+}
+
+fn render_markdown(report: &Report, summary: Option<&str>) -> String {
+    let mut out = format!(
+        "# Work report, {} to {}\n",
+        format_utc_date(report.since),
+        format_utc_date(report.until)
+    );
+    if let Some(summary) = summary {
+        out.push_str(&format!("\n## Summary\n\n{}\n", summary));
+    }
+
+    out.push_str("\n## Time\n\n");
+    if report.times.is_empty() {
+        out.push_str("No activity recorded.\n");
+    } else {
+        out.push_str("| Project | Branch | Active | Agent | Focused |\n");
+        out.push_str("| --- | --- | --- | --- | --- |\n");
+        for time in report.times {
+            out.push_str(&format!(
+                "| {} | `{}` | {} | {} | {} |\n",
+                time.project,
+                time.branch,
+                format_hours(time.active_secs),
+                format_hours(time.agent_secs),
+                format_hours(time.focus_secs)
+            ));
+        }
+        let totals: Vec<String> = project_totals(report.times)
+            .into_iter()
+            .map(|(project, secs)| format!("{} {}", project, format_hours(secs)))
+            .collect();
+        out.push_str(&format!("\nTotal: {}\n", totals.join(", ")));
+    }
+
+    let merged: Vec<String> = report
+        .merged
+        .iter()
+        .map(|event| match (&event.branch, &event.target) {
+            (Some(branch), Some(target)) => {
+                format!("{}: `{}` into `{}`", event.project, branch, target)
+            }
+            _ => format!("{}: `{}`", event.project, event.handle),
+        })
+        .collect();
+    push_list(&mut out, "Merged branches", &merged);
+
+    let prs: Vec<String> = report
+        .prs
+        .iter()
+        .map(|(project, pr)| {
+            format!(
+                "{}: [#{} {}]({}) (`{}`)",
+                project, pr.number, pr.title, pr.url, pr.head_ref_name
+            )
+        })
+        .collect();
+    push_list(&mut out, "Pull requests opened", &prs);
+
+    let prompts: Vec<String> = report
+        .activity
+        .iter()
+        .filter_map(|event| {
+            let prompt = event.prompt.as_deref()?;
+            Some(format!("{}/`{}`: {}", event.project, event.branch, prompt))
+        })
+        .collect();
+    push_list(&mut out, "Prompts", &prompts);
+
+    let count = |kind| {
+        report
+            .activity
+            .iter()
+            .filter(|event| event.kind == kind)
+            .count()
+    };
+    let agent_secs: u64 = report.times.iter().map(|time| time.agent_secs).sum();
+    let focus_secs: u64 = report.times.iter().map(|time| time.focus_secs).sum();
+    let project_count = report
+        .times
+        .iter()
+        .map(|time| &time.project)
+        .collect::<BTreeSet<_>>()
+        .len();
+    out.push_str(&format!(
+        "\n## Agent usage\n\n\
+         - {} across {}\n\
+         - {} of agent time, {} focused\n\
+         - {} finished, {}\n",
+        plural(report.times.len(), "branch", "branches"),
+        plural(project_count, "project", "projects"),
+        format_hours(agent_secs),
+        format_hours(focus_secs),
+        plural(count(ActivityKind::Done), "task", "tasks"),
+        plural(
+            count(ActivityKind::Waiting),
+            "request for input",
+            "requests for input"
+        ),
+    ));
+    out
+}
+
+/// A `## title (n)` section listing `items`, skipped when empty.
+fn push_list(out: &mut String, title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n## {} ({})\n\n", title, items.len()));
+    for item in items {
+        out.push_str(&format!("- {}\n", item));
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+/// Active seconds per project. Branches active at the same time each count.
+fn project_totals(times: &[BranchTime]) -> BTreeMap<&str, u64> {
+    let mut totals = BTreeMap::new();
+    for time in times {
+        *totals.entry(time.project.as_str()).or_default() += time.active_secs;
+    }
+    totals
+}
+
+/// Hours and minutes, e.g. `3h 05m`, with decimal hours for invoicing.
+fn format_hours(secs: u64) -> String {
+    if secs == 0 {
+        return "-".to_string();
+    }
+    let mins = secs.div_ceil(60);
+    format!(
+        "{}h {:02}m ({:.1}h)",
+        mins / 60,
+        mins % 60,
+        secs as f64 / 3600.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_hours() {
+        assert_eq!(format_hours(0), "-");
+        assert_eq!(format_hours(59), "0h 01m (0.0h)");
+        assert_eq!(format_hours(3 * 3600 + 5 * 60), "3h 05m (3.1h)");
+    }
+
+    #[test]
+    fn totals_per_project() {
+        let time = |project: &str, secs| BranchTime {
+            project: project.to_string(),
+            active_secs: secs,
+            ..Default::default()
+        };
+        let times = [time("api", 600), time("api", 300), time("web", 60)];
+        let totals = project_totals(&times);
+        assert_eq!(totals["api"], 900);
+        assert_eq!(totals["web"], 60);
+    }
+
+    #[test]
+    fn renders_markdown_report() {
+        let times = [BranchTime {
+            project: "api".to_string(),
+            branch: "user-auth".to_string(),
+            active_secs: 7200,
+            agent_secs: 5400,
+            focus_secs: 3600,
+        }];
+        let event = |kind, prompt: Option<&str>| ActivityEvent {
+            ts: 100,
+            project: "api".to_string(),
+            branch: "user-auth".to_string(),
+            kind,
+            prompt: prompt.map(str::to_string),
+        };
+        let activity = [
+            event(ActivityKind::Prompt, Some("Add login with GitHub")),
+            event(ActivityKind::Working, None),
+            event(ActivityKind::Done, None),
+        ];
+        let merged = [WebhookPayload {
+            event: NotificationEvent::Merged,
+            handle: "user-auth".to_string(),
+            project: "api".to_string(),
+            branch: Some("user-auth".to_string()),
+            target: Some("main".to_string()),
+            message: String::new(),
+            timestamp: 200,
+        }];
+        let prs = [(
+            "api".to_string(),
+            OpenedPr {
+                number: 42,
+                title: "Add GitHub login".to_string(),
+                url: "https://github.com/acme/api/pull/42".to_string(),
+                head_ref_name: "user-auth".to_string(),
+            },
+        )];
+        let report = Report {
+            since: 0,
+            until: 7 * 86_400,
+            times: &times,
+            activity: &activity,
+            merged: &merged,
+            prs: &prs,
+        };
+
+        assert_eq!(
+            render_markdown(&report, Some("Shipped GitHub login.")),
+            "# Work report, 1970-01-01 to 1970-01-08\n\
+             \n## Summary\n\nShipped GitHub login.\n\
+             \n## Time\n\n\
+             | Project | Branch | Active | Agent | Focused |\n\
+             | --- | --- | --- | --- | --- |\n\
+             | api | `user-auth` | 2h 00m (2.0h) | 1h 30m (1.5h) | 1h 00m (1.0h) |\n\
+             \nTotal: api 2h 00m (2.0h)\n\
+             \n## Merged branches (1)\n\n- api: `user-auth` into `main`\n\
+             \n## Pull requests opened (1)\n\n\
+             - api: [#42 Add GitHub login](https://github.com/acme/api/pull/42) (`user-auth`)\n\
+             \n## Prompts (1)\n\n- api/`user-auth`: Add login with GitHub\n\
+             \n## Agent usage\n\n\
+             - 1 branch across 1 project\n\
+             - 1h 30m (1.5h) of agent time, 1h 00m (1.0h) focused\n\
+             - 1 task finished, 0 requests for input\n"
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use anyhow::{Result, anyhow};
 use tracing::info;
 
-use crate::config;
+use crate::config::{self, MuxMode};
 use crate::multiplexer::{create_backend, detect_backend};
 use crate::state::StateStore;
+use crate::util::parallel_map;
 use crate::workflow::resurrect::{ResurrectAction, plan};
-use crate::workflow::{self, SetupOptions, WorkflowContext};
+use crate::workflow::{self, SetupOptions, WindowTurns, WorkflowContext};
+
+/// Worktrees restored at once. Each mostly waits on its agent panes to
+/// come up, so a few in flight hide most of that latency.
+const RESURRECT_JOBS: usize = 4;
 
 pub fn run(dry_run: bool) -> Result<()> {
     let config = config::Config::load(None)?;
@@ -52,16 +57,19 @@ pub fn run(dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Execute restoration
+    // Execute restoration. Worktrees open concurrently; only window creation
+    // takes turns, so windows keep the plan's order.
     let context = WorkflowContext::new(config, mux, None)?;
-    let mut restored = Vec::new();
-    let mut failed = Vec::new();
-
-    for candidate in &plan.candidates {
-        if !matches!(candidate.action, ResurrectAction::Restore) {
-            continue;
-        }
-
+    let turns = WindowTurns::default();
+    let jobs: Vec<_> = to_restore.into_iter().enumerate().collect();
+    let backend = context.mux.name();
+    let instance = context.mux.instance_id();
+    let results = parallel_map(jobs, RESURRECT_JOBS, |(i, candidate)| {
+        // Rebuild the window as it was, if its layout was saved
+        let saved_layout = match candidate.mode {
+            MuxMode::Window => store.get_layout(backend, &instance, &candidate.worktree_path),
+            MuxMode::Session => None,
+        };
         let options = SetupOptions {
             run_hooks: false,
             run_file_ops: false,
@@ -73,6 +81,8 @@ pub fn run(dry_run: bool) -> Result<()> {
             open_if_exists: false,
             mode: candidate.mode,
             resume_mode: crate::multiplexer::types::ResumeMode::Continue,
+            layout: None,
+            saved_layout,
         };
 
         info!(
@@ -82,7 +92,14 @@ pub fn run(dry_run: bool) -> Result<()> {
             "resurrect:exec opening worktree"
         );
 
-        match workflow::open(&candidate.handle, &context, options, false, None, None) {
+        let result = workflow::open_in_turn(&candidate.handle, &context, options, &turns.turn(i));
+        (candidate, result)
+    });
+
+    let mut restored = Vec::new();
+    let mut failed = Vec::new();
+    for (candidate, result) in results {
+        match result {
             Ok(result) => {
                 info!(
                     handle = candidate.handle,
@@ -99,6 +116,10 @@ pub fn run(dry_run: bool) -> Result<()> {
                     );
                     let _ = store.delete_agent(key);
                 }
+                // Window mode saved a fresh layout; session mode doesn't use one
+                if candidate.mode == MuxMode::Session {
+                    store.delete_layout(backend, &instance, &candidate.worktree_path);
+                }
                 restored.push(candidate.handle.clone());
             }
             Err(e) => {
@@ -1,19 +1,26 @@
 //! Run a command in a worktree's tmux/wezterm window.
 
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
 
-use crate::config::SplitDirection;
+use crate::config::{Config, SplitDirection};
 use crate::multiplexer::{create_backend, detect_backend};
-use crate::state::run::{RunSpec, cleanup_run, create_run, generate_run_id, read_result};
-use crate::workflow;
+use crate::state::StateStore;
+use crate::state::run::{
+    OnFail, RunSpec, cleanup_run, create_run, generate_run_id, prune_runs, read_result,
+};
+use crate::{git, vcs, workflow};
 
 /// Escape a string for safe shell embedding.
-fn shell_escape(s: &str) -> String {
+pub(super) fn shell_escape(s: &str) -> String {
     if s.is_empty() {
         return "''".to_string();
     }
@@ -25,21 +32,88 @@ fn shell_escape(s: &str) -> String {
     format!("'{}'", s.replace('\'', "'\\''"))
 }
 
-pub fn run(
-    worktree_name: &str,
-    command_parts: Vec<String>,
-    background: bool,
-    keep: bool,
-    timeout: Option<u64>,
-) -> Result<()> {
-    if command_parts.is_empty() {
-        return Err(anyhow!("No command provided"));
+/// How `workmux run` executes a command and waits for it.
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    /// Extra environment variables for the command
+    pub env: BTreeMap<String, String>,
+    /// Run on the host even if the worktree is sandboxed
+    pub host: bool,
+    /// Return without waiting for the command
+    pub background: bool,
+    /// Keep run artifacts after completion
+    pub keep: bool,
+    /// Maximum wait time in seconds
+    pub timeout: Option<u64>,
+    /// Re-run on file changes until the pane is closed
+    pub watch: bool,
+    /// Show a pass/fail icon on the window while watching
+    pub status_icon: bool,
+    /// Follow-up action when the command fails
+    pub on_fail: Option<OnFail>,
+    /// Show a desktop notification when the command finishes
+    pub notify: bool,
+    /// Print a JSON result instead of streaming output (keeps artifacts)
+    pub json: bool,
+    /// Print a tab-separated result line instead of streaming output (keeps artifacts)
+    pub porcelain: bool,
+}
+
+/// Result of a waited run, printed by `--json` and `--porcelain`.
+#[derive(Debug, Serialize)]
+struct RunReport<'a> {
+    run_id: &'a str,
+    worktree: &'a str,
+    command: &'a str,
+    pane_id: &'a str,
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    timed_out: bool,
+    duration_ms: u64,
+    stdout: PathBuf,
+    stderr: PathBuf,
+}
+
+impl RunReport<'_> {
+    /// Print as JSON, or as the `--porcelain` line.
+    fn print(&self, json: bool) -> Result<()> {
+        if json {
+            println!("{}", serde_json::to_string(self)?);
+        } else {
+            println!("{}", self.porcelain_line());
+        }
+        Ok(())
     }
 
-    let mux = create_backend(detect_backend());
+    /// `result<TAB>exit_code<TAB>duration_ms<TAB>run_id<TAB>stdout<TAB>stderr`,
+    /// where result is `ok`, `failed` or `timeout` and a missing exit code is `-`.
+    fn porcelain_line(&self) -> String {
+        let result = if self.timed_out {
+            "timeout"
+        } else if self.exit_code == Some(0) {
+            "ok"
+        } else {
+            "failed"
+        };
+        let exit_code = self
+            .exit_code
+            .map_or_else(|| "-".to_string(), |code| code.to_string());
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            result,
+            exit_code,
+            self.duration_ms,
+            self.run_id,
+            self.stdout.display(),
+            self.stderr.display()
+        )
+    }
+}
 
-    // Resolve worktree to agent pane (consistent with send/capture)
-    let (worktree_path, agent) = workflow::resolve_worktree_agent(worktree_name, mux.as_ref())?;
+pub fn run(worktree_name: &str, command_parts: Vec<String>, options: RunOptions) -> Result<()> {
+    if command_parts.is_empty() {
+        return Err(anyhow!("No command provided"));
+    }
 
     // Build command string (preserve argument boundaries via shell escaping)
     let command = command_parts
@@ -48,11 +122,65 @@ pub fn run(
         .collect::<Vec<_>>()
         .join(" ");
 
+    run_command(worktree_name, command, options)
+}
+
+/// Run an already shell-escaped command line in a worktree's agent pane.
+pub fn run_command(worktree_name: &str, command: String, options: RunOptions) -> Result<()> {
+    let RunOptions {
+        env,
+        host,
+        background,
+        keep,
+        timeout,
+        watch,
+        status_icon,
+        on_fail,
+        notify,
+        json,
+        porcelain,
+    } = options;
+    let report_only = json || porcelain;
+    // The report points at the output files, so they have to outlive the run
+    let keep = keep || report_only;
+    let mux = create_backend(detect_backend());
+    let headless = mux.is_headless();
+    if headless && watch {
+        return Err(anyhow!("--watch needs a multiplexer pane to run in"));
+    }
+
+    // Resolve worktree to agent pane (consistent with send/capture). Without
+    // a multiplexer there are no agents, so just find the worktree.
+    let (worktree_path, agent_pane) = if headless {
+        let (path, _branch) = vcs::find_workspace(worktree_name)
+            .map_err(|_| git::WorktreeNotFound(worktree_name.to_string()))?;
+        (path, None)
+    } else {
+        let (path, agent) = workflow::resolve_worktree_agent(worktree_name, mux.as_ref())?;
+        (path, Some(agent.pane_id))
+    };
+    let sandbox = !host && uses_sandbox(&worktree_path);
+
+    // Enforce the retention policy so kept and abandoned runs don't pile up
+    if let Ok(config) = Config::load(None)
+        && let Err(e) = prune_runs(config.runs.retention())
+    {
+        tracing::warn!(error = %e, "failed to prune old runs");
+    }
+
     // Generate run ID and create spec
     let run_id = generate_run_id();
     let spec = RunSpec {
         command: command.clone(),
         worktree_path: worktree_path.clone(),
+        target: worktree_name.to_string(),
+        env,
+        sandbox,
+        watch,
+        status_icon,
+        on_fail,
+        notify,
+        agent_pane: agent_pane.clone(),
     };
     let run_dir = create_run(&run_id, &spec)?;
 
@@ -67,77 +195,97 @@ pub fn run(
         shell_escape(&exe_path),
         shell_escape(&run_dir.to_string_lossy())
     );
-    let new_pane_id = mux.split_pane(
-        &agent.pane_id,
-        &SplitDirection::Vertical,
-        &worktree_path,
-        None,
-        Some(30), // 30% for the command pane
-        Some(&exec_cmd),
-    )?;
+    let new_pane_id = match &agent_pane {
+        Some(agent_pane) => mux.split_pane(
+            agent_pane,
+            &SplitDirection::Vertical,
+            &worktree_path,
+            None,
+            Some(30), // 30% for the command pane
+            Some(&exec_cmd),
+        )?,
+        None => {
+            spawn_exec(&exe_path, &run_dir, &worktree_path)?;
+            String::new()
+        }
+    };
+
+    if watch {
+        eprintln!("Watching: {} (run_id: {})", command, run_id);
+        eprintln!(
+            "Pane: {} (close it or press Ctrl-C there to stop)",
+            new_pane_id
+        );
+        return Ok(());
+    }
 
     if background {
         eprintln!("Started: {} (run_id: {})", command, run_id);
-        eprintln!("Pane: {}", new_pane_id);
+        if !new_pane_id.is_empty() {
+            eprintln!("Pane: {}", new_pane_id);
+        }
         eprintln!("Artifacts: {}", run_dir.display());
         return Ok(());
     }
 
-    // Wait for completion, streaming output in real-time
+    // Wait for completion, tailing the output files as _exec appends to them
     let start = Instant::now();
     let timeout_duration = timeout.map(Duration::from_secs);
 
-    // Open files for streaming
-    let stdout_path = run_dir.join("stdout");
-    let stderr_path = run_dir.join("stderr");
-
-    // Wait briefly for files to be created
-    thread::sleep(Duration::from_millis(100));
-
-    let mut stdout_file = File::open(&stdout_path).ok();
-    let mut stderr_file = File::open(&stderr_path).ok();
-    let mut stdout_pos: u64 = 0;
-    let mut stderr_pos: u64 = 0;
+    let mut stdout_tail = Tail::new(run_dir.join("stdout"));
+    let mut stderr_tail = Tail::new(run_dir.join("stderr"));
+    let (run_id, command, pane_id) = (run_id.as_str(), command.as_str(), new_pane_id.as_str());
+    let report = |exit_code, signal, timed_out| RunReport {
+        run_id,
+        worktree: worktree_name,
+        command,
+        pane_id,
+        exit_code,
+        signal,
+        timed_out,
+        duration_ms: start.elapsed().as_millis() as u64,
+        stdout: run_dir.join("stdout"),
+        stderr: run_dir.join("stderr"),
+    };
 
     loop {
+        if !report_only {
+            stdout_tail.pump(&mut io::stdout());
+            stderr_tail.pump(&mut io::stderr());
+        }
+
         // Check timeout
         if let Some(max_duration) = timeout_duration
             && start.elapsed() > max_duration
         {
-            eprintln!("\nTimeout after {}s", timeout.unwrap());
-            if keep {
-                eprintln!("Artifacts kept at: {}", run_dir.display());
+            if report_only {
+                report(None, None, true).print(json)?;
             } else {
-                let _ = cleanup_run(&run_dir);
+                eprintln!("\nTimeout after {}s", timeout.unwrap());
+                if keep {
+                    eprintln!("Artifacts kept at: {}", run_dir.display());
+                } else {
+                    let _ = cleanup_run(&run_dir);
+                }
             }
             std::process::exit(124); // Standard timeout exit code
         }
 
-        // Stream new stdout content
-        if let Some(ref mut file) = stdout_file {
-            stdout_pos = stream_new_content(file, stdout_pos, &mut io::stdout());
-        }
-
-        // Stream new stderr content
-        if let Some(ref mut file) = stderr_file {
-            stderr_pos = stream_new_content(file, stderr_pos, &mut io::stderr());
-        }
-
         // Check if complete
         if let Some(result) = read_result(&run_dir)? {
-            // Final flush of any remaining output
-            if let Some(ref mut file) = stdout_file {
-                stream_new_content(file, stdout_pos, &mut io::stdout());
-            }
-            if let Some(ref mut file) = stderr_file {
-                stream_new_content(file, stderr_pos, &mut io::stderr());
-            }
-
-            // Cleanup unless --keep
-            if keep {
-                eprintln!("Artifacts kept at: {}", run_dir.display());
+            if report_only {
+                report(result.exit_code, result.signal, false).print(json)?;
             } else {
-                let _ = cleanup_run(&run_dir);
+                // Final flush of any remaining output
+                stdout_tail.pump(&mut io::stdout());
+                stderr_tail.pump(&mut io::stderr());
+
+                // Cleanup unless --keep
+                if keep {
+                    eprintln!("Artifacts kept at: {}", run_dir.display());
+                } else {
+                    let _ = cleanup_run(&run_dir);
+                }
             }
 
             // Exit with command's exit code
@@ -152,27 +300,237 @@ pub fn run(
     }
 }
 
-/// Stream new content from file starting at given position, return new position.
-fn stream_new_content<W: Write>(file: &mut File, pos: u64, out: &mut W) -> u64 {
-    if file.seek(SeekFrom::Start(pos)).is_err() {
-        return pos;
+/// Start `_exec` as a detached child process, for running without a
+/// multiplexer. It records its result in `run_dir` like it does in a pane, so
+/// waiting works the same way.
+fn spawn_exec(exe_path: &str, run_dir: &Path, worktree_path: &Path) -> Result<()> {
+    Command::new(exe_path)
+        .arg("_exec")
+        .arg("--run-dir")
+        .arg(run_dir)
+        .current_dir(worktree_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start command")?;
+    Ok(())
+}
+
+/// Whether the worktree's agent runs sandboxed: sandboxing is enabled in the
+/// worktree's config, or a sandbox container is running for it (`add --sandbox`).
+fn uses_sandbox(worktree_path: &Path) -> bool {
+    let enabled = Config::load_with_location_from(worktree_path, None)
+        .map(|(config, _)| config.sandbox.is_enabled())
+        .unwrap_or(false);
+    if enabled {
+        return true;
     }
+    let Some(handle) = worktree_path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    StateStore::new().is_ok_and(|store| !store.list_containers(handle).is_empty())
+}
 
-    let mut reader = BufReader::new(file);
-    let mut new_pos = pos;
+/// Parse a `KEY=VALUE` argument for `--env`.
+pub fn parse_env_assignment(s: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", s))?;
+    if !crate::shell::is_valid_env_name(key) {
+        return Err(format!("invalid environment variable name '{}'", key));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
 
-    loop {
-        let mut line = String::new();
-        match reader.read_line(&mut line) {
-            Ok(0) => break, // EOF
-            Ok(n) => {
-                let _ = out.write_all(line.as_bytes());
-                let _ = out.flush();
-                new_pos += n as u64;
+/// Parse dotenv-style content: `KEY=VALUE` lines, with blank lines, `#`
+/// comments, an optional `export ` prefix and single or double quoted values.
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) =
+            parse_env_assignment(line).map_err(|e| anyhow!("line {}: {}", index + 1, e))?;
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+            .unwrap_or(value);
+        vars.push((key, value.to_string()));
+    }
+    Ok(vars)
+}
+
+/// Combine `--env-file` contents and `--env` values into the environment for
+/// a run. Later files override earlier ones, and `--env` overrides both.
+pub fn collect_env(
+    assignments: Vec<(String, String)>,
+    env_files: &[PathBuf],
+) -> Result<BTreeMap<String, String>> {
+    let mut env = BTreeMap::new();
+    for path in env_files {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read env file '{}'", path.display()))?;
+        let vars = parse_env_file(&content)
+            .with_context(|| format!("Invalid env file '{}'", path.display()))?;
+        env.extend(vars);
+    }
+    env.extend(assignments);
+    Ok(env)
+}
+
+/// Follows a file that another process appends to.
+///
+/// Copies raw bytes rather than lines, so partial lines (progress output,
+/// prompts) show up immediately and invalid UTF-8 can't stall the stream.
+struct Tail {
+    path: PathBuf,
+    file: Option<File>,
+    pos: u64,
+}
+
+impl Tail {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: None,
+            pos: 0,
+        }
+    }
+
+    /// Copy everything appended since the last call to `out`.
+    fn pump<W: Write>(&mut self, out: &mut W) {
+        if self.file.is_none() {
+            self.file = File::open(&self.path).ok();
+        }
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        if file.seek(SeekFrom::Start(self.pos)).is_err() {
+            return;
+        }
+
+        let mut buf = [0u8; 8192];
+        loop {
+            match file.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if out.write_all(&buf[..n]).is_err() {
+                        break;
+                    }
+                    self.pos += n as u64;
+                }
             }
-            Err(_) => break,
         }
+        let _ = out.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_line_fields() {
+        let mut report = RunReport {
+            run_id: "r1",
+            worktree: "api",
+            command: "cargo test",
+            pane_id: "%3",
+            exit_code: Some(0),
+            signal: None,
+            timed_out: false,
+            duration_ms: 1500,
+            stdout: PathBuf::from("/runs/r1/stdout"),
+            stderr: PathBuf::from("/runs/r1/stderr"),
+        };
+        assert_eq!(
+            report.porcelain_line(),
+            "ok\t0\t1500\tr1\t/runs/r1/stdout\t/runs/r1/stderr"
+        );
+        report.exit_code = Some(101);
+        assert!(report.porcelain_line().starts_with("failed\t101\t"));
+        report.exit_code = None;
+        report.timed_out = true;
+        assert!(report.porcelain_line().starts_with("timeout\t-\t"));
+    }
+
+    #[test]
+    fn parse_env_assignment_validates_key() {
+        assert_eq!(
+            parse_env_assignment("API_URL=http://x?a=b").unwrap(),
+            ("API_URL".to_string(), "http://x?a=b".to_string())
+        );
+        assert_eq!(
+            parse_env_assignment("EMPTY=").unwrap(),
+            ("EMPTY".to_string(), String::new())
+        );
+        assert!(parse_env_assignment("NOVALUE").is_err());
+        assert!(parse_env_assignment("1BAD=x").is_err());
+        assert!(parse_env_assignment("=x").is_err());
     }
 
-    new_pos
+    #[test]
+    fn parse_env_file_handles_comments_export_and_quotes() {
+        let content = "# secrets\n\
+                       \n\
+                       export TOKEN=abc\n\
+                       GREETING=\"hello world\"\n\
+                       SINGLE='a b'\n\
+                       PLAIN= keep \n";
+        assert_eq!(
+            parse_env_file(content).unwrap(),
+            vec![
+                ("TOKEN".to_string(), "abc".to_string()),
+                ("GREETING".to_string(), "hello world".to_string()),
+                ("SINGLE".to_string(), "a b".to_string()),
+                ("PLAIN".to_string(), "keep".to_string()),
+            ]
+        );
+
+        let err = parse_env_file("A=1\nBAD KEY=2\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn collect_env_lets_flags_override_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".env.test");
+        std::fs::write(&file, "A=file\nB=file\n").unwrap();
+        let env = collect_env(vec![("B".to_string(), "flag".to_string())], &[file]).unwrap();
+        assert_eq!(env.get("A").map(String::as_str), Some("file"));
+        assert_eq!(env.get("B").map(String::as_str), Some("flag"));
+    }
+
+    #[test]
+    fn tail_copies_appended_bytes_including_partial_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stdout");
+        let mut tail = Tail::new(path.clone());
+        let mut out = Vec::new();
+
+        // Missing file is picked up once it appears
+        tail.pump(&mut out);
+        assert!(out.is_empty());
+
+        let mut writer = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writer.write_all(b"line one\nprogress ").unwrap();
+        tail.pump(&mut out);
+        assert_eq!(out, b"line one\nprogress ");
+
+        writer.write_all(b"\xff50%\n").unwrap();
+        tail.pump(&mut out);
+        assert_eq!(out, b"line one\nprogress \xff50%\n");
+
+        tail.pump(&mut out);
+        assert_eq!(out, b"line one\nprogress \xff50%\n");
+    }
 }
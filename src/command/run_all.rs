@@ -0,0 +1,344 @@
+//! Run a command in every worktree (`workmux run --all`).
+//!
+//! Unlike `workmux run`, this doesn't go through agent panes: each command is
+//! a direct child process whose output is streamed with a worktree prefix,
+//! followed by a pass/fail summary.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use super::run::shell_escape;
+use crate::util::format_elapsed_duration;
+use crate::{git, vcs};
+
+/// Worktrees run concurrently when `--jobs` isn't given
+const DEFAULT_JOBS: usize = 4;
+
+/// How one worktree's command ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Passed,
+    Failed(Option<i32>),
+    TimedOut,
+    Error(String),
+}
+
+impl Outcome {
+    fn is_success(&self) -> bool {
+        matches!(self, Outcome::Passed)
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Outcome::Passed => "✓ pass".to_string(),
+            Outcome::Failed(Some(code)) => format!("✗ fail ({})", code),
+            Outcome::Failed(None) => "✗ killed".to_string(),
+            Outcome::TimedOut => "✗ timeout".to_string(),
+            Outcome::Error(e) => format!("✗ error: {}", e),
+        }
+    }
+}
+
+struct WorktreeRun {
+    handle: String,
+    branch: String,
+    outcome: Outcome,
+    duration: Duration,
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "WORKTREE")]
+    handle: String,
+    #[tabled(rename = "BRANCH")]
+    branch: String,
+    #[tabled(rename = "RESULT")]
+    result: String,
+    #[tabled(rename = "TIME")]
+    time: String,
+}
+
+pub fn run(
+    command_parts: Vec<String>,
+    env: BTreeMap<String, String>,
+    jobs: Option<usize>,
+    timeout: Option<u64>,
+) -> Result<()> {
+    if command_parts.is_empty() {
+        return Err(anyhow!("No command provided"));
+    }
+
+    let command = command_parts
+        .iter()
+        .map(|s| shell_escape(s))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let main_root =
+        git::get_main_worktree_root().context("Could not find the main git worktree")?;
+    let targets: Vec<(PathBuf, String)> = vcs::list_workspaces()
+        .context("Failed to list worktrees")?
+        .into_iter()
+        .filter(|(path, _)| *path != main_root)
+        .collect();
+    if targets.is_empty() {
+        bail!("No worktrees found (besides main)");
+    }
+
+    let targets: Vec<(String, PathBuf, String)> = targets
+        .into_iter()
+        .map(|(path, branch)| (handle_of(&path), path, branch))
+        .collect();
+    let width = targets.iter().map(|(h, _, _)| h.len()).max().unwrap_or(0);
+    let workers = jobs.unwrap_or(DEFAULT_JOBS).clamp(1, targets.len());
+    let timeout = timeout.map(Duration::from_secs);
+
+    let queue = Mutex::new(targets.into_iter().enumerate().collect::<VecDeque<_>>());
+    let results = Mutex::new(Vec::new());
+
+    thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| {
+                loop {
+                    let Some((index, (handle, path, branch))) = queue.lock().unwrap().pop_front()
+                    else {
+                        break;
+                    };
+                    let prefix = format!("{:<width$} │ ", handle, width = width);
+                    let start = Instant::now();
+                    let outcome = run_in_worktree(&path, &command, &env, &prefix, timeout);
+                    results.lock().unwrap().push((
+                        index,
+                        WorktreeRun {
+                            handle,
+                            branch,
+                            outcome,
+                            duration: start.elapsed(),
+                        },
+                    ));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<WorktreeRun> = results.into_iter().map(|(_, r)| r).collect();
+
+    println!();
+    print_summary(&results);
+
+    let failed = results.iter().filter(|r| !r.outcome.is_success()).count();
+    if failed > 0 {
+        bail!("{} of {} worktrees failed", failed, results.len());
+    }
+    Ok(())
+}
+
+fn handle_of(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Run `command` in `path`, streaming its output with `prefix`.
+fn run_in_worktree(
+    path: &Path,
+    command: &str,
+    env: &BTreeMap<String, String>,
+    prefix: &str,
+    timeout: Option<Duration>,
+) -> Outcome {
+    let mut command_builder = Command::new(crate::platform::bash());
+    // Its own process group, so a timeout also stops the processes it starts
+    crate::platform::new_process_group(&mut command_builder);
+    let mut child = match command_builder
+        .arg("-c")
+        .arg(command)
+        .current_dir(path)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return Outcome::Error(e.to_string()),
+    };
+
+    let child_stdout = child.stdout.take().unwrap();
+    let child_stderr = child.stderr.take().unwrap();
+    let stdout_prefix = prefix.to_string();
+    let stderr_prefix = prefix.to_string();
+    let stdout_handle =
+        thread::spawn(move || pump_prefixed(child_stdout, &stdout_prefix, io::stdout));
+    let stderr_handle =
+        thread::spawn(move || pump_prefixed(child_stderr, &stderr_prefix, io::stderr));
+
+    let outcome = match wait_with_timeout(&mut child, timeout) {
+        Ok(Some(status)) if status.success() => Outcome::Passed,
+        Ok(Some(status)) => Outcome::Failed(status.code()),
+        Ok(None) => {
+            // Grandchildren (the actual build or test) hold the output pipes
+            // too, so the whole group has to go before the readers finish
+            crate::platform::kill_process_tree(&mut child);
+            let _ = child.wait();
+            Outcome::TimedOut
+        }
+        Err(e) => Outcome::Error(e.to_string()),
+    };
+
+    let _ = stdout_handle.join();
+    let _ = stderr_handle.join();
+    outcome
+}
+
+/// Wait for `child`, returning `None` if it outlives `timeout`.
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> io::Result<Option<std::process::ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if start.elapsed() > timeout {
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Copy lines from `reader` to the stream returned by `out`, each prefixed.
+///
+/// Each line is written under the stream's lock so concurrent worktrees never
+/// interleave within a line.
+fn pump_prefixed<R: Read, W: Write>(reader: R, prefix: &str, out: impl Fn() -> W) {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let _ = write_prefixed(&mut out(), prefix, &line);
+            }
+        }
+    }
+}
+
+fn write_prefixed<W: Write>(out: &mut W, prefix: &str, line: &[u8]) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(prefix.len() + line.len() + 1);
+    buf.extend_from_slice(prefix.as_bytes());
+    buf.extend_from_slice(line);
+    if !line.ends_with(b"\n") {
+        buf.push(b'\n');
+    }
+    out.write_all(&buf)?;
+    out.flush()
+}
+
+fn print_summary(results: &[WorktreeRun]) {
+    let rows: Vec<SummaryRow> = results
+        .iter()
+        .map(|r| SummaryRow {
+            handle: r.handle.clone(),
+            branch: r.branch.clone(),
+            result: r.outcome.label(),
+            time: format_elapsed_duration(r.duration),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..4), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_prefixed_terminates_partial_lines() {
+        let mut out = Vec::new();
+        write_prefixed(&mut out, "api │ ", b"ok\n").unwrap();
+        write_prefixed(&mut out, "api │ ", b"no newline").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "api │ ok\napi │ no newline\n"
+        );
+    }
+
+    #[test]
+    fn outcome_labels() {
+        assert_eq!(Outcome::Passed.label(), "✓ pass");
+        assert_eq!(Outcome::Failed(Some(2)).label(), "✗ fail (2)");
+        assert_eq!(Outcome::TimedOut.label(), "✗ timeout");
+        assert!(!Outcome::Failed(None).is_success());
+    }
+
+    #[test]
+    fn run_in_worktree_reports_exit_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let no_env = BTreeMap::new();
+        assert_eq!(
+            run_in_worktree(dir.path(), "true", &no_env, "", None),
+            Outcome::Passed
+        );
+        assert_eq!(
+            run_in_worktree(dir.path(), "exit 3", &no_env, "", None),
+            Outcome::Failed(Some(3))
+        );
+        assert_eq!(
+            run_in_worktree(
+                dir.path(),
+                "sleep 5",
+                &no_env,
+                "",
+                Some(Duration::from_millis(100))
+            ),
+            Outcome::TimedOut
+        );
+
+        let env = BTreeMap::from([("WM_FLAG".to_string(), "on".to_string())]);
+        assert_eq!(
+            run_in_worktree(dir.path(), "test \"$WM_FLAG\" = on", &env, "", None),
+            Outcome::Passed
+        );
+    }
+
+    #[test]
+    fn timeout_kills_processes_the_command_started() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Instant::now();
+        // The background sleep holds stdout open; the output readers only
+        // finish once it's killed along with bash
+        assert_eq!(
+            run_in_worktree(
+                dir.path(),
+                "sleep 30 & wait",
+                &BTreeMap::new(),
+                "",
+                Some(Duration::from_millis(100))
+            ),
+            Outcome::TimedOut
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,185 @@
+//! `workmux runs`: browse and repeat past `workmux run` commands.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use super::run::RunOptions;
+use crate::config::Config;
+use crate::state::run::{RunRecord, find_record, load_history, prune_runs};
+use crate::util::{format_compact_age, format_elapsed_duration};
+
+#[derive(Debug, Args)]
+pub struct RunsArgs {
+    #[command(subcommand)]
+    pub command: RunsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RunsCommand {
+    /// List recent runs, newest first
+    #[command(visible_alias = "ls")]
+    List {
+        /// Only show runs for this worktree
+        #[arg(short = 'w', long)]
+        worktree: Option<String>,
+
+        /// Maximum number of runs to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Run a previous command again in the same worktree
+    Rerun {
+        /// Run ID or unique prefix (defaults to the most recent run)
+        id: Option<String>,
+
+        /// Run on the host even if the worktree is sandboxed
+        #[arg(long)]
+        host: bool,
+
+        /// Run in background without waiting
+        #[arg(short = 'b', long)]
+        background: bool,
+
+        /// Keep run artifacts after completion
+        #[arg(long)]
+        keep: bool,
+
+        /// Maximum wait time in seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Remove run artifacts outside the retention policy
+    Clean {
+        /// Remove all finished runs, not just those past the limits
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Tabled)]
+struct RunRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "WORKTREE")]
+    worktree: String,
+    #[tabled(rename = "EXIT")]
+    exit: String,
+    #[tabled(rename = "AGE")]
+    age: String,
+    #[tabled(rename = "TIME")]
+    time: String,
+    #[tabled(rename = "COMMAND")]
+    command: String,
+}
+
+pub fn run(args: RunsArgs) -> Result<()> {
+    match args.command {
+        RunsCommand::List { worktree, limit } => run_list(worktree.as_deref(), limit),
+        RunsCommand::Rerun {
+            id,
+            host,
+            background,
+            keep,
+            timeout,
+        } => run_rerun(id.as_deref(), host, background, keep, timeout),
+        RunsCommand::Clean { all } => run_clean(all),
+    }
+}
+
+fn run_list(worktree: Option<&str>, limit: usize) -> Result<()> {
+    let history = load_history()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let rows: Vec<RunRow> = history
+        .iter()
+        .rev()
+        .filter(|r| worktree.is_none_or(|w| r.target == w || worktree_handle(r) == w))
+        .take(limit)
+        .map(|r| RunRow {
+            id: r.id.clone(),
+            worktree: worktree_handle(r),
+            exit: format_exit(r),
+            age: format_compact_age(now.saturating_sub(r.started_at)),
+            time: format_elapsed_duration(Duration::from_millis(r.duration_ms)),
+            command: r.command.clone(),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("No runs found");
+        return Ok(());
+    }
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..6), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+fn run_rerun(
+    id: Option<&str>,
+    host: bool,
+    background: bool,
+    keep: bool,
+    timeout: Option<u64>,
+) -> Result<()> {
+    let record = find_record(&load_history()?, id)?;
+    let target = if record.target.is_empty() {
+        worktree_handle(&record)
+    } else {
+        record.target.clone()
+    };
+    eprintln!("Re-running in {}: {}", target, record.command);
+    super::run::run_command(
+        &target,
+        record.command,
+        RunOptions {
+            host,
+            background,
+            keep,
+            timeout,
+            ..Default::default()
+        },
+    )
+}
+
+fn run_clean(all: bool) -> Result<()> {
+    let mut retention = Config::load(None)?.runs.retention();
+    if all {
+        retention.max_count = Some(0);
+    }
+    let removed = prune_runs(retention)?;
+    println!(
+        "Removed {} run director{}",
+        removed,
+        if removed == 1 { "y" } else { "ies" }
+    );
+    Ok(())
+}
+
+fn worktree_handle(record: &RunRecord) -> String {
+    record
+        .worktree_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| record.worktree_path.display().to_string())
+}
+
+fn format_exit(record: &RunRecord) -> String {
+    match (record.exit_code, record.signal) {
+        (Some(code), _) => code.to_string(),
+        (None, Some(signal)) => format!("sig {}", signal),
+        (None, None) => "-".to_string(),
+    }
+}
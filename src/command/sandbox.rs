@@ -2,10 +2,13 @@
 
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand};
+use serde::Serialize;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::SystemTime;
+use tabled::settings::{Padding, Style, object::Columns};
+use tabled::{Table, Tabled};
 use tracing::debug;
 
 use crate::config::Config;
@@ -28,6 +31,7 @@ Lima commands:
   prune            Delete unused Lima VMs to reclaim disk space
 
 General commands:
+  status           Show running sandbox containers and VMs
   agent            Run an agent inside a sandbox with RPC support
   shell            Start an interactive shell in a sandbox
   install-dev      Cross-compile and install workmux into sandboxes
@@ -84,12 +88,16 @@ pub enum SandboxCommand {
         /// VM name to stop (if not provided, show interactive list)
         #[arg(conflicts_with = "all")]
         name: Option<String>,
-        /// Stop all workmux VMs (wm-* prefix)
+        /// Stop all workmux VMs (wm-* prefix). Skip the confirmation with
+        /// the global --yes
         #[arg(long)]
         all: bool,
-        /// Skip confirmation prompt
-        #[arg(short, long)]
-        yes: bool,
+    },
+    /// Show running sandbox containers and workmux Lima VMs.
+    Status {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Run the configured agent inside a sandbox with full RPC support.
     /// Unlike `shell`, this starts an RPC server so the agent can call
@@ -166,7 +174,8 @@ pub fn run(args: SandboxArgs) -> Result<()> {
         } => run_install_dev(skip_build, release),
         SandboxCommand::Agent { command } => run_agent(command),
         SandboxCommand::Prune { force } => run_prune(force),
-        SandboxCommand::Stop { name, all, yes } => run_stop(name, all, yes),
+        SandboxCommand::Stop { name, all } => run_stop(name, all),
+        SandboxCommand::Status { json } => run_status(json),
         SandboxCommand::Shell { exec, command } => run_shell(exec, command),
     }
 }
@@ -679,8 +688,8 @@ fn run_prune(force: bool) -> Result<()> {
         println!();
     }
 
-    // Confirm deletion unless --force
-    if !force {
+    // Confirm deletion unless --force or --yes
+    if !force && !crate::interactive::is_enabled() {
         print!("Delete all these VMs? [y/N] ");
         io::stdout().flush().context("Failed to flush stdout")?;
 
@@ -795,9 +804,92 @@ fn format_duration_since(time: SystemTime) -> String {
     format!("{} year{} ago", years, if years == 1 { "" } else { "s" })
 }
 
-fn run_stop(name: Option<String>, all: bool, skip_confirm: bool) -> Result<()> {
+/// A sandbox in `sandbox status --json` output.
+#[derive(Serialize)]
+struct SandboxEntry {
+    /// "container" or "vm"
+    kind: &'static str,
+    name: String,
+    /// Worktree the container belongs to (containers only)
+    worktree: Option<String>,
+    /// Container runtime, or "lima"
+    runtime: String,
+    /// "running" for containers; the Lima status, lowercased, for VMs
+    status: String,
+}
+
+#[derive(Tabled)]
+struct SandboxRow {
+    #[tabled(rename = "KIND")]
+    kind: &'static str,
+    #[tabled(rename = "NAME")]
+    name: String,
+    #[tabled(rename = "WORKTREE")]
+    worktree: String,
+    #[tabled(rename = "RUNTIME")]
+    runtime: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+}
+
+fn run_status(json: bool) -> Result<()> {
+    use crate::sandbox::lima::VM_PREFIX;
+    use crate::state::StateStore;
+
+    // Containers are registered while running and unregistered on exit
+    let mut entries: Vec<SandboxEntry> = StateStore::new()
+        .map(|store| store.list_all_containers())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(handle, name, runtime)| SandboxEntry {
+            kind: "container",
+            name,
+            worktree: Some(handle),
+            runtime: runtime.serde_name().to_string(),
+            status: "running".to_string(),
+        })
+        .collect();
+    if LimaInstance::is_lima_available() {
+        entries.extend(
+            LimaInstance::list()?
+                .into_iter()
+                .filter(|vm| vm.name.starts_with(VM_PREFIX))
+                .map(|vm| SandboxEntry {
+                    kind: "vm",
+                    name: vm.name,
+                    worktree: None,
+                    runtime: "lima".to_string(),
+                    status: vm.status.to_lowercase(),
+                }),
+        );
+    }
+
+    if json {
+        println!("{}", serde_json::to_string(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No sandboxes found");
+        return Ok(());
+    }
+    let rows = entries.into_iter().map(|entry| SandboxRow {
+        kind: entry.kind,
+        name: entry.name,
+        worktree: entry.worktree.unwrap_or_else(|| "-".to_string()),
+        runtime: entry.runtime,
+        status: entry.status,
+    });
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(..), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+fn run_stop(name: Option<String>, all: bool) -> Result<()> {
     use crate::sandbox::lima::{LimaInstance, LimaInstanceInfo, VM_PREFIX};
-    use std::io::{self, IsTerminal, Write};
+    use std::io::{self, Write};
 
     // Check if limactl is available
     if !LimaInstance::is_lima_available() {
@@ -843,7 +935,7 @@ fn run_stop(name: Option<String>, all: bool, skip_confirm: bool) -> Result<()> {
         }
     } else {
         // Interactive mode: require TTY
-        if !std::io::stdin().is_terminal() {
+        if !crate::interactive::can_prompt() {
             anyhow::bail!("Non-interactive stdin detected. Use --all or specify a VM name.");
         }
 
@@ -867,7 +959,7 @@ fn run_stop(name: Option<String>, all: bool, skip_confirm: bool) -> Result<()> {
     }
 
     // Confirm unless --yes flag is provided
-    if !skip_confirm {
+    if !crate::interactive::is_enabled() {
         print!(
             "\nAre you sure you want to stop {} VM(s)? [y/N] ",
             vms_to_stop.len()
@@ -4,6 +4,7 @@
 //! command inside a sandbox (Lima VM or Docker/Podman container).
 
 use anyhow::{Context, Result, bail};
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
@@ -68,9 +69,31 @@ pub fn run(worktree: PathBuf, worktree_root: Option<PathBuf>, command: Vec<Strin
         bail!("No command specified. Usage: workmux sandbox run <worktree> -- <command...>");
     }
 
-    let config = Config::load(None)?;
+    let mut config = Config::load(None)?;
     let worktree = worktree.canonicalize().unwrap_or_else(|_| worktree.clone());
 
+    // A prompt's frontmatter can narrow host_commands for its worktree
+    let handle = worktree_root
+        .as_deref()
+        .unwrap_or(worktree.as_path())
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if let Some(subset) = crate::git::get_worktree_meta(handle, "host-commands") {
+        match serde_json::from_str::<Vec<String>>(&subset) {
+            Ok(subset) => {
+                config.sandbox.restrict_host_commands(&subset);
+            }
+            Err(e) => warn!(error = %e, "ignoring invalid host-commands worktree metadata"),
+        }
+    }
+
+    // Signing keys and agents are not forwarded, so guest commits are unsigned
+    // and `workmux merge` will reject them until they're re-signed on the host.
+    if config.require_signed_commits() {
+        warn!("require_signed_commits is enabled but commits made inside the sandbox are unsigned");
+    }
+
     match config.sandbox.backend() {
         SandboxBackend::Lima => run_lima(&config, &worktree, &command),
         SandboxBackend::Container => {
@@ -225,6 +248,10 @@ fn run_lima(config: &Config, worktree: &Path, command: &[String]) -> Result<i32>
         env_exports.push(format!("{}='{}'", key, crate::shell::shell_escape(val)));
     }
 
+    for (key, val) in crate::secrets::resolve_env(&config.secrets) {
+        env_exports.push(format!("{}='{}'", key, crate::shell::shell_escape(&val)));
+    }
+
     // Inject host git user config (user.name, user.email) for commits
     for (key, val) in git_user_config_envs(worktree) {
         env_exports.push(format!("{}='{}'", key, crate::shell::shell_escape(&val)));
@@ -395,6 +422,8 @@ fn run_container(
     // Inject host git user config (user.name, user.email) for commits
     owned_envs.extend(git_user_config_envs(worktree_root));
 
+    owned_envs.extend(crate::secrets::resolve_env(&config.secrets));
+
     // Borrow owned envs for call site
     let env_refs: Vec<(&str, &str)> = owned_envs
         .iter()
@@ -414,6 +443,13 @@ fn run_container(
         network_deny,
     )?;
 
+    // Non-interactive callers (`workmux run`) have no terminal to attach
+    if !std::io::stdin().is_terminal()
+        && let Some(arg) = docker_args.iter_mut().find(|a| *a == "-it")
+    {
+        *arg = "-i".to_string();
+    }
+
     // Insert --name after "run" (index 0 is "run")
     docker_args.insert(1, "--name".to_string());
     docker_args.insert(2, container_name.clone());
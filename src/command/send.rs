@@ -1,21 +1,35 @@
-use std::io::{IsTerminal, Read};
+use std::io::{IsTerminal, Read, Write};
 
 use anyhow::{Result, anyhow};
 
 use crate::config;
-use crate::multiplexer::{create_backend, detect_backend};
+use crate::multiplexer::{Multiplexer, create_backend, detect_backend};
 use crate::workflow;
 
-pub fn run(name: &str, text: Option<&str>, file: Option<&str>) -> Result<()> {
+pub fn run(name: Option<&str>, text: Option<&str>, file: Option<&str>) -> Result<()> {
+    let picked = name.is_none();
+    let name = match name {
+        Some(name) => name.to_string(),
+        None if crate::interactive::can_prompt() && std::io::stdout().is_terminal() => {
+            super::pick_worktree("send to")?
+        }
+        None => return Err(anyhow!("Worktree name is required")),
+    };
     let cfg = config::Config::load(None).unwrap_or_default();
     let mux = create_backend(detect_backend());
-    let (_path, agent) = workflow::resolve_worktree_agent(name, mux.as_ref())?;
+    let (path, agent) = workflow::resolve_worktree_agent(&name, mux.as_ref())?;
 
-    // Determine content: positional arg > --file > stdin
+    // Determine content: positional arg > --file > prompt (after picking) > stdin
     let content = if let Some(t) = text {
         t.to_string()
     } else if let Some(f) = file {
         std::fs::read_to_string(f)?
+    } else if picked {
+        print!("Message for {}: ", name);
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        line
     } else {
         // Guard: don't block on interactive TTY
         if std::io::stdin().is_terminal() {
@@ -35,13 +49,24 @@ pub fn run(name: &str, text: Option<&str>, file: Option<&str>) -> Result<()> {
         return Err(anyhow!("No content to send"));
     }
 
+    deliver(mux.as_ref(), &agent.pane_id, content, cfg.agent.as_deref())?;
+    crate::state::activity::record_prompt(&path, content);
+
+    Ok(())
+}
+
+/// Type `content` into an agent's pane and submit it.
+pub fn deliver(
+    mux: &dyn Multiplexer,
+    pane_id: &str,
+    content: &str,
+    agent: Option<&str>,
+) -> Result<()> {
     // Single-line: use send_keys_to_agent (handles Claude's ! prefix delay)
     // Multi-line: use paste_multiline (already sends Enter in both backends)
     if content.contains('\n') {
-        mux.paste_multiline(&agent.pane_id, content)?;
+        mux.paste_multiline(pane_id, content)
     } else {
-        mux.send_keys_to_agent(&agent.pane_id, content, cfg.agent.as_deref())?;
+        mux.send_keys_to_agent(pane_id, content, agent)
     }
-
-    Ok(())
 }
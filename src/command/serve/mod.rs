@@ -0,0 +1,412 @@
+//! `workmux serve`: agent, worktree and PR status over HTTP.
+//!
+//! A small HTTP server on std threads. `/api/agents` and `/api/worktrees`
+//! return JSON, `/api/events` streams the agent list as server-sent events,
+//! and `/` serves a single-page UI built on them. One poller thread reloads
+//! the agents and wakes the event streams when the list changes.
+//!
+//! Listening on anything but loopback requires a token, generated at startup
+//! and passed as a `token` query parameter. On loopback, requests must name
+//! the server by a loopback host, so a web page can't reach it through DNS
+//! rebinding.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::command::dashboard::json;
+use crate::config::Config;
+use crate::git::AheadBehind;
+use crate::github::PrSummary;
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
+use crate::sandbox::rpc::{constant_time_eq, generate_token};
+use crate::workflow;
+
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// How often the agent list is reloaded
+const POLL: Duration = Duration::from_secs(2);
+
+/// Event streams send a comment this often, so dead clients are noticed and
+/// proxies keep the stream open
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Max concurrent connections, event streams included
+const MAX_CONNECTIONS: usize = 32;
+
+/// Longest request line and headers accepted
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// The latest agent list as JSON, with a version bumped on every change.
+struct Agents {
+    state: Mutex<(u64, String)>,
+    changed: Condvar,
+}
+
+struct Server {
+    mux: Arc<dyn Multiplexer>,
+    agents: Agents,
+    /// Required on every request when listening beyond loopback. Without
+    /// one, the `Host` header must be a loopback name instead.
+    token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WorktreeEntry {
+    handle: String,
+    branch: String,
+    path: PathBuf,
+    is_main: bool,
+    is_open: bool,
+    /// Statuses of the worktree's agents
+    agents: Vec<AgentStatus>,
+    upstream: Option<AheadBehind>,
+    main: Option<AheadBehind>,
+    pr: Option<PrSummary>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    /// The `Host` header
+    host: Option<String>,
+}
+
+impl Request {
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub fn run(port: u16, bind: IpAddr) -> Result<()> {
+    let addr = SocketAddr::new(bind, port);
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("Failed to listen on {}", addr))?;
+    let server = Arc::new(Server {
+        mux: create_backend(detect_backend()),
+        agents: Agents {
+            state: Mutex::new((0, "[]".to_string())),
+            changed: Condvar::new(),
+        },
+        token: (!bind.is_loopback()).then(generate_token),
+    });
+
+    let poller = Arc::clone(&server);
+    thread::spawn(move || poller.poll_agents());
+
+    let query = server
+        .token
+        .as_ref()
+        .map(|token| format!("?token={}", token))
+        .unwrap_or_default();
+    println!("Serving agent status on http://{}/{}", addr, query);
+    if bind.is_unspecified() {
+        println!(
+            "Use this machine's address in place of {} from other devices",
+            bind
+        );
+    }
+
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!(error = %e, "serve: failed to accept connection");
+                continue;
+            }
+        };
+        if active.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+            let _ = respond(
+                &stream,
+                "503 Service Unavailable",
+                "text/plain",
+                "Too many connections",
+            );
+            continue;
+        }
+        active.fetch_add(1, Ordering::Relaxed);
+        let server = Arc::clone(&server);
+        let active = Arc::clone(&active);
+        thread::spawn(move || {
+            if let Err(e) = server.handle(stream) {
+                tracing::debug!(error = %e, "serve: connection closed");
+            }
+            active.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+    Ok(())
+}
+
+impl Server {
+    fn agent_state(&self) -> MutexGuard<'_, (u64, String)> {
+        self.agents.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn poll_agents(&self) {
+        loop {
+            let loaded = json::entries(self.mux.as_ref(), false)
+                .and_then(|entries| Ok(serde_json::to_string(&entries)?));
+            match loaded {
+                Ok(agents) => {
+                    let mut state = self.agent_state();
+                    if state.1 != agents {
+                        *state = (state.0 + 1, agents);
+                        self.agents.changed.notify_all();
+                    }
+                }
+                Err(e) => tracing::warn!(error = %e, "serve: failed to load agents"),
+            }
+            thread::sleep(POLL);
+        }
+    }
+
+    fn handle(&self, stream: TcpStream) -> Result<()> {
+        // A client that never finishes its request doesn't hold a thread
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let Some(request) = read_request(&stream)? else {
+            return Ok(());
+        };
+        if request.method != "GET" {
+            return respond(
+                &stream,
+                "405 Method Not Allowed",
+                "text/plain",
+                "Only GET is supported",
+            );
+        }
+        if self.token.is_none() && !request.host.as_deref().is_some_and(is_loopback_host) {
+            return respond(&stream, "403 Forbidden", "text/plain", "Host not allowed");
+        }
+        if let Some(ref token) = self.token
+            && !request
+                .query("token")
+                .is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()))
+        {
+            return respond(
+                &stream,
+                "401 Unauthorized",
+                "text/plain",
+                "Missing or invalid token",
+            );
+        }
+
+        match request.path.as_str() {
+            "/" => respond(&stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML),
+            "/api/agents" => {
+                let agents = self.agent_state().1.clone();
+                respond(&stream, "200 OK", "application/json", &agents)
+            }
+            "/api/worktrees" => match worktrees(self.mux.as_ref()) {
+                Ok(worktrees) => respond(&stream, "200 OK", "application/json", &worktrees),
+                Err(e) => respond(
+                    &stream,
+                    "500 Internal Server Error",
+                    "text/plain",
+                    &format!("{:#}", e),
+                ),
+            },
+            "/api/events" => self.stream_agents(stream),
+            _ => respond(&stream, "404 Not Found", "text/plain", "Not found"),
+        }
+    }
+
+    /// Send the agent list now and whenever it changes, until the client
+    /// goes away.
+    fn stream_agents(&self, mut stream: TcpStream) -> Result<()> {
+        stream.write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\n\r\n",
+        )?;
+        let mut sent = None;
+        loop {
+            let (state, _) = self
+                .agents
+                .changed
+                .wait_timeout_while(self.agent_state(), KEEPALIVE, |state| Some(state.0) == sent)
+                .unwrap_or_else(|e| e.into_inner());
+            let message = if Some(state.0) == sent {
+                ": keepalive\n\n".to_string()
+            } else {
+                sent = Some(state.0);
+                format!("data: {}\n\n", state.1)
+            };
+            drop(state);
+            stream.write_all(message.as_bytes())?;
+            stream.flush()?;
+        }
+    }
+}
+
+/// The worktrees of the repository `serve` was started in, with cached PR
+/// status. Each call also refreshes the PR cache in the background.
+fn worktrees(mux: &dyn Multiplexer) -> Result<String> {
+    let config = Config::load(None)?;
+    let (worktrees, _pending_prs) = workflow::list_with_cached_prs(&config, mux, &[])?;
+    let entries: Vec<WorktreeEntry> = worktrees
+        .into_iter()
+        .map(|wt| WorktreeEntry {
+            handle: wt.handle,
+            branch: wt.branch,
+            path: wt.path,
+            is_main: wt.is_main,
+            is_open: wt.has_mux_window,
+            agents: wt.agent_status.map(|s| s.statuses).unwrap_or_default(),
+            upstream: wt.divergence.upstream,
+            main: wt.divergence.main,
+            pr: wt.pr_info,
+        })
+        .collect();
+    Ok(serde_json::to_string(&entries)?)
+}
+
+/// Read a request's line and headers. None if the client sent nothing.
+fn read_request(stream: &TcpStream) -> Result<Option<Request>> {
+    let mut reader = BufReader::new(Read::take(stream, MAX_REQUEST_BYTES));
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let mut request = parse_request_line(line.trim_end()).context("Malformed request line")?;
+    // Only Host is used, but all headers are read so the client sees a clean close
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("host")
+        {
+            request.host = Some(value.trim().to_string());
+        }
+    }
+    Ok(Some(request))
+}
+
+/// Whether a `Host` header names this machine's loopback interface:
+/// `localhost`, `127.0.0.1` or `[::1]`, with or without a port.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((name, port)) if port.is_empty() || port.starts_with(':') => name,
+            _ => return false,
+        },
+        None => host.split_once(':').map_or(host, |(name, _)| name),
+    };
+    ["localhost", "127.0.0.1", "::1"]
+        .iter()
+        .any(|allowed| name.eq_ignore_ascii_case(allowed))
+}
+
+/// Parse "GET /path?key=value HTTP/1.1".
+fn parse_request_line(line: &str) -> Option<Request> {
+    let mut parts = line.split(' ');
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    parts.next()?.strip_prefix("HTTP/")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect();
+    Some(Request {
+        method,
+        path: path.to_string(),
+        query,
+        host: None,
+    })
+}
+
+fn respond(mut stream: &TcpStream, status: &str, content_type: &str, body: &str) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_request_line_splits_path_and_query() {
+        let request = parse_request_line("GET /api/agents?token=abc&x HTTP/1.1").unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/api/agents");
+        assert_eq!(request.query("token"), Some("abc"));
+        assert_eq!(request.query("x"), Some(""));
+        assert_eq!(request.query("y"), None);
+
+        assert!(parse_request_line("GET /").is_none());
+        assert!(parse_request_line("hello").is_none());
+    }
+
+    #[test]
+    fn is_loopback_host_accepts_only_loopback_names() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("localhost:8080"));
+        assert!(is_loopback_host("127.0.0.1:8080"));
+        assert!(is_loopback_host("[::1]:8080"));
+        assert!(is_loopback_host("[::1]"));
+        assert!(!is_loopback_host("evil.example.com"));
+        assert!(!is_loopback_host("evil.example.com:8080"));
+        assert!(!is_loopback_host("localhost.evil.example.com"));
+        assert!(!is_loopback_host("[::2]:8080"));
+    }
+
+    /// Send `request` to a loopback server without a token and return the
+    /// response's status line.
+    fn status_for(request: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let server = Server {
+            mux: Arc::new(crate::multiplexer::headless::HeadlessBackend::new()),
+            agents: Agents {
+                state: Mutex::new((0, "[]".to_string())),
+                changed: Condvar::new(),
+            },
+            token: None,
+        };
+        client.write_all(request.as_bytes()).unwrap();
+        server.handle(stream).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn loopback_server_rejects_foreign_host_header() {
+        assert_eq!(
+            status_for("GET /api/agents HTTP/1.1\r\nHost: evil.example.com:8080\r\n\r\n"),
+            "HTTP/1.1 403 Forbidden"
+        );
+        assert_eq!(
+            status_for("GET /api/agents HTTP/1.1\r\n\r\n"),
+            "HTTP/1.1 403 Forbidden"
+        );
+        assert_eq!(
+            status_for("GET /api/agents HTTP/1.1\r\nHost: localhost:8080\r\n\r\n"),
+            "HTTP/1.1 200 OK"
+        );
+    }
+}
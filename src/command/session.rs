@@ -0,0 +1,215 @@
+//! `workmux session`: the agent conversations recorded per branch.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::git;
+use crate::state::sessions::{self, BranchSessions, load_history};
+use crate::util::{format_compact_age, format_utc_date};
+
+#[derive(Debug, Args)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    pub command: SessionCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SessionCommand {
+    /// List the conversations started on a branch, newest first
+    History {
+        /// Branch name (defaults to the current branch)
+        branch: Option<String>,
+    },
+    /// Write every recorded session to stdout as JSON
+    Export,
+    /// Merge sessions from a file written by `export`
+    Import {
+        /// File to read, or `-` for stdin
+        file: PathBuf,
+
+        /// Move repositories under OLD to NEW (e.g. a different home
+        /// directory). Can be given multiple times.
+        #[arg(long, value_name = "OLD=NEW")]
+        map: Vec<String>,
+    },
+}
+
+/// The document `export` writes and `import` reads
+#[derive(Serialize, Deserialize)]
+struct SessionExport {
+    version: u32,
+    branches: Vec<BranchSessions>,
+}
+
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Tabled)]
+struct SessionRow {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "SESSION")]
+    id: String,
+    #[tabled(rename = "AGENT")]
+    agent: String,
+    #[tabled(rename = "STARTED")]
+    started: String,
+    #[tabled(rename = "AGE")]
+    age: String,
+}
+
+pub fn run(args: SessionArgs) -> Result<()> {
+    match args.command {
+        SessionCommand::History { branch } => run_history(branch),
+        SessionCommand::Export => run_export(),
+        SessionCommand::Import { file, map } => run_import(&file, &map),
+    }
+}
+
+fn run_history(branch: Option<String>) -> Result<()> {
+    let branch = match branch {
+        Some(branch) => branch,
+        None => git::get_current_branch().context("Could not determine the current branch")?,
+    };
+    if branch.is_empty() {
+        bail!("Not on a branch; pass the branch name");
+    }
+    let repo = git::get_main_worktree_root()?;
+    let history = load_history(&repo, &branch)?;
+    if history.is_empty() {
+        println!("No sessions recorded for branch '{}'", branch);
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let rows: Vec<SessionRow> = history
+        .into_iter()
+        .rev()
+        .enumerate()
+        .map(|(i, record)| SessionRow {
+            index: i + 1,
+            id: record.id,
+            agent: record.agent,
+            started: format_utc_date(record.ts),
+            age: format_compact_age(now.saturating_sub(record.ts)),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..5), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+fn run_export() -> Result<()> {
+    let export = SessionExport {
+        version: EXPORT_VERSION,
+        branches: sessions::export_all()?,
+    };
+    println!("{}", serde_json::to_string_pretty(&export)?);
+    Ok(())
+}
+
+fn run_import(file: &Path, map: &[String]) -> Result<()> {
+    let mappings = map
+        .iter()
+        .map(|m| parse_mapping(m))
+        .collect::<Result<Vec<_>>>()?;
+    let content = if file == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?
+    };
+    let mut export: SessionExport =
+        serde_json::from_str(&content).context("Not a workmux session export")?;
+    if export.version > EXPORT_VERSION {
+        bail!(
+            "Export is version {}; this workmux reads up to version {}",
+            export.version,
+            EXPORT_VERSION
+        );
+    }
+
+    for branch in &mut export.branches {
+        branch.repo = remap(&branch.repo, &mappings);
+    }
+    let added = sessions::import_all(&export.branches)?;
+    println!(
+        "✓ Imported {} session(s) across {} branch(es)",
+        added,
+        export.branches.len()
+    );
+    let mut missing: Vec<&Path> = export
+        .branches
+        .iter()
+        .map(|b| b.repo.as_path())
+        .filter(|repo| !repo.exists())
+        .collect();
+    missing.dedup();
+    if !missing.is_empty() {
+        eprintln!("Repositories not found on this machine (use --map to move them):");
+        for repo in missing {
+            eprintln!("  {}", repo.display());
+        }
+    }
+    Ok(())
+}
+
+fn parse_mapping(mapping: &str) -> Result<(PathBuf, PathBuf)> {
+    match mapping.split_once('=') {
+        Some((old, new)) if !old.is_empty() && !new.is_empty() => {
+            Ok((PathBuf::from(old), PathBuf::from(new)))
+        }
+        _ => bail!("Invalid --map '{}': expected OLD=NEW", mapping),
+    }
+}
+
+/// Apply the first mapping whose OLD contains `repo`.
+fn remap(repo: &Path, mappings: &[(PathBuf, PathBuf)]) -> PathBuf {
+    mappings
+        .iter()
+        .find_map(|(old, new)| {
+            let rest = repo.strip_prefix(old).ok()?;
+            Some(if rest.as_os_str().is_empty() {
+                new.clone()
+            } else {
+                new.join(rest)
+            })
+        })
+        .unwrap_or_else(|| repo.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remap_moves_repos_under_the_old_prefix() {
+        let mappings = vec![parse_mapping("/Users/me=/home/me").unwrap()];
+        assert_eq!(
+            remap(Path::new("/Users/me/code/app"), &mappings),
+            Path::new("/home/me/code/app")
+        );
+        assert_eq!(
+            remap(Path::new("/Users/meg/app"), &mappings),
+            Path::new("/Users/meg/app")
+        );
+        assert!(parse_mapping("/Users/me").is_err());
+    }
+}
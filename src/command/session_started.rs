@@ -0,0 +1,76 @@
+//! `workmux _session-started`: records the conversation an agent started in
+//! the current worktree, called from Claude Code's SessionStart hook.
+//!
+//! The hook passes its JSON on stdin, including the `session_id`; `--id`
+//! gives it directly instead. Outside a git worktree there's nothing to
+//! record and the hook does nothing.
+
+use std::io::Read;
+
+use anyhow::{Context, Result};
+use tracing::debug;
+
+use crate::git;
+use crate::multiplexer::{create_backend, detect_backend};
+use crate::state::sessions::{self, SessionRecord};
+
+pub fn run(id: Option<String>) -> Result<()> {
+    let id = match id {
+        Some(id) => Some(id),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            parse_session_id(&input)
+        }
+    };
+    let Some(id) = id else {
+        debug!("session-started: no session id given");
+        return Ok(());
+    };
+
+    // The sandbox guest has no access to the host's state directory
+    if crate::sandbox::guest::is_sandbox_guest() {
+        return Ok(());
+    }
+
+    let Ok(worktree) = git::get_repo_root() else {
+        return Ok(());
+    };
+    let branch = git::get_branch_for_worktree(&worktree)?;
+    if branch.is_empty() {
+        // Detached HEAD: no branch to file it under
+        return Ok(());
+    }
+    let repo = git::get_main_worktree_root()?;
+    let mut record = SessionRecord::new(id, "claude");
+    record.mux_session = mux_session();
+    sessions::store_session(&repo, &branch, &record).context("Failed to record session")
+}
+
+/// The multiplexer session of the pane the hook runs in.
+fn mux_session() -> Option<String> {
+    let mux = create_backend(detect_backend());
+    let pane_id = mux.current_pane_id()?;
+    mux.get_live_pane_info(&pane_id).ok()??.session
+}
+
+/// The `session_id` field of hook JSON.
+fn parse_session_id(input: &str) -> Option<String> {
+    let hook: serde_json::Value = serde_json::from_str(input).ok()?;
+    let id = hook.get("session_id")?.as_str()?.trim();
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_session_id;
+
+    #[test]
+    fn parse_session_id_from_hook_json() {
+        let hook =
+            r#"{"hook_event_name":"SessionStart","session_id":"9f2c-41","source":"startup"}"#;
+        assert_eq!(parse_session_id(hook).as_deref(), Some("9f2c-41"));
+        assert_eq!(parse_session_id(r#"{"session_id":""}"#), None);
+        assert_eq!(parse_session_id("not json"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::io::Read;
+
 use anyhow::Result;
 use clap::ValueEnum;
 use tracing::warn;
@@ -17,59 +19,112 @@ pub enum SetWindowStatusCommand {
     Clear,
 }
 
-pub fn run(cmd: SetWindowStatusCommand) -> Result<()> {
+pub fn run(cmd: SetWindowStatusCommand, reason: Option<String>) -> Result<()> {
+    // Only a waiting agent has something to ask for
+    let reason = match (&cmd, reason) {
+        (SetWindowStatusCommand::Waiting, Some(reason)) if reason == "-" => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            parse_reason(&input)
+        }
+        (SetWindowStatusCommand::Waiting, Some(reason)) => parse_reason(&reason),
+        _ => None,
+    };
+
     // Inside a sandbox guest, route through RPC to the host supervisor
     if crate::sandbox::guest::is_sandbox_guest() {
-        return run_via_rpc(cmd);
+        return run_via_rpc(cmd, reason);
     }
 
     let config = Config::load(None)?;
     let mux = create_backend(detect_backend());
 
-    // Fail silently if not in a multiplexer session
+    let (status, icon, auto_clear) = match cmd {
+        SetWindowStatusCommand::Working => (
+            Some(AgentStatus::Working),
+            config.status_icons.working(),
+            false,
+        ),
+        SetWindowStatusCommand::Waiting => (
+            Some(AgentStatus::Waiting),
+            config.status_icons.waiting(),
+            true,
+        ),
+        SetWindowStatusCommand::Done => (Some(AgentStatus::Done), config.status_icons.done(), true),
+        SetWindowStatusCommand::Clear => (None, "", false),
+    };
+
+    // Terminal escape sequences work with or without a multiplexer
+    let signal_osc = |changed: bool| {
+        let osc = &config.notifications.osc;
+        if !osc.any_enabled() {
+            return;
+        }
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let root = crate::git::get_repo_root().unwrap_or(cwd);
+        let handle = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        crate::notification::osc::signal_status(
+            osc,
+            &handle,
+            status,
+            icon,
+            reason.as_deref(),
+            changed,
+        );
+    };
+
+    // Fail silently if not in a multiplexer session. Without a pane there's
+    // no stored status to compare against, so every call counts as a change.
     let Some(pane_id) = mux.current_pane_id() else {
+        signal_osc(true);
         return Ok(());
     };
 
-    match cmd {
-        SetWindowStatusCommand::Clear => {
-            // Clear icon only - state file cleanup is handled by reconciliation
-            mux.clear_status(&pane_id)?;
-        }
-        SetWindowStatusCommand::Working
-        | SetWindowStatusCommand::Waiting
-        | SetWindowStatusCommand::Done => {
-            let (status, icon, auto_clear) = match cmd {
-                SetWindowStatusCommand::Working => {
-                    (AgentStatus::Working, config.status_icons.working(), false)
-                }
-                SetWindowStatusCommand::Waiting => {
-                    (AgentStatus::Waiting, config.status_icons.waiting(), true)
-                }
-                SetWindowStatusCommand::Done => {
-                    (AgentStatus::Done, config.status_icons.done(), true)
-                }
-                SetWindowStatusCommand::Clear => unreachable!(),
-            };
-
-            // Ensure the status format is applied so the icon actually shows up
-            if config.status_format.unwrap_or(true) {
-                let _ = mux.ensure_status_format(&pane_id);
-            }
-
-            // Update backend UI (status bar icon)
-            mux.set_status(&pane_id, icon, auto_clear)?;
-
-            // Persist to state store so the dashboard sees this agent
-            crate::state::persist_agent_update(&*mux, &pane_id, Some(status), None);
-        }
+    let Some(status) = status else {
+        // Clear icon only - state file cleanup is handled by reconciliation
+        signal_osc(true);
+        mux.clear_status(&pane_id)?;
+        return Ok(());
+    };
+
+    // Ensure the status format is applied so the icon actually shows up
+    if config.status_format.unwrap_or(true) {
+        let _ = mux.ensure_status_format(&pane_id);
+    }
+
+    // Update backend UI (status bar icon)
+    mux.set_status(&pane_id, icon, auto_clear)?;
+
+    // Persist to state store so the dashboard sees this agent
+    let previous =
+        crate::state::persist_agent_update(&*mux, &pane_id, Some(status), None, reason.clone());
+    signal_osc(previous != Some(status));
+
+    if let Ok(cwd) = std::env::current_dir() {
+        crate::notification::agent_status_changed(
+            &config.notifications,
+            &cwd,
+            previous,
+            status,
+            reason.as_deref(),
+        );
     }
+    crate::notification::escalation::schedule(
+        &config.notifications,
+        &*mux,
+        &pane_id,
+        previous,
+        status,
+    );
 
     Ok(())
 }
 
 /// Send a status update via RPC when running inside a sandbox guest.
-fn run_via_rpc(cmd: SetWindowStatusCommand) -> Result<()> {
+fn run_via_rpc(cmd: SetWindowStatusCommand, reason: Option<String>) -> Result<()> {
     use crate::sandbox::rpc::{RpcClient, RpcRequest, RpcResponse};
 
     let status = match cmd {
@@ -82,6 +137,7 @@ fn run_via_rpc(cmd: SetWindowStatusCommand) -> Result<()> {
     let mut client = RpcClient::from_env()?;
     let response = client.call(&RpcRequest::SetStatus {
         status: status.to_string(),
+        reason,
     })?;
 
     match response {
@@ -93,3 +149,34 @@ fn run_via_rpc(cmd: SetWindowStatusCommand) -> Result<()> {
         _ => Ok(()),
     }
 }
+
+/// Extract the waiting reason from `--reason` input: hook JSON with a
+/// `message` field (as Claude's Notification hook sends), or plain text.
+fn parse_reason(input: &str) -> Option<String> {
+    let text = match serde_json::from_str::<serde_json::Value>(input) {
+        Ok(serde_json::Value::Object(hook)) => hook.get("message")?.as_str()?.to_string(),
+        _ => input.to_string(),
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_reason;
+
+    #[test]
+    fn parse_reason_from_hook_json_or_text() {
+        let hook = r#"{"hook_event_name":"Notification","message":"Claude needs your permission to use Bash"}"#;
+        assert_eq!(
+            parse_reason(hook).as_deref(),
+            Some("Claude needs your permission to use Bash")
+        );
+        assert_eq!(
+            parse_reason("  Pick a database\n").as_deref(),
+            Some("Pick a database")
+        );
+        assert_eq!(parse_reason(r#"{"session_id":"abc"}"#), None);
+        assert_eq!(parse_reason(""), None);
+    }
+}
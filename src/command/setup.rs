@@ -1,12 +1,12 @@
 use anyhow::Result;
 use console::style;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 
 use crate::agent_setup::{self, Agent, StatusCheck};
 use crate::skills;
 
 pub fn run(hooks_only: bool, skills_only: bool) -> Result<()> {
-    if !io::stdin().is_terminal() {
+    if !crate::interactive::can_prompt() {
         anyhow::bail!("workmux setup requires an interactive terminal");
     }
 
@@ -10,12 +10,13 @@ use std::sync::Arc;
 
 use crate::agent_display::{extract_project_name, extract_worktree_name};
 use crate::cmd::Cmd;
+use crate::command::dashboard::settings::stale_threshold_secs;
 use crate::config::{Config, StatusIcons};
 use crate::git::GitStatus;
 
 use crate::multiplexer::{AgentPane, Multiplexer};
 
-use crate::ui::theme::ThemePalette;
+use crate::ui::theme::{ThemePalette, resolve_mode};
 
 use super::snapshot::SidebarSnapshot;
 
@@ -82,16 +83,11 @@ impl SidebarApp {
     pub fn new_client(mux: Arc<dyn Multiplexer>) -> Result<Self> {
         let config = Config::load(None)?;
 
-        let theme_mode = config
-            .theme
-            .mode
-            .unwrap_or_else(|| match terminal_light::luma() {
-                Ok(luma) if luma > 0.6 => crate::config::ThemeMode::Light,
-                _ => crate::config::ThemeMode::Dark,
-            });
+        let theme_mode = resolve_mode(&config.theme);
         let palette = ThemePalette::from_config(&config.theme, theme_mode);
         let window_prefix = config.window_prefix().to_string();
         let status_icons = config.status_icons.clone();
+        let stale_threshold_secs = stale_threshold_secs(&config.dashboard);
 
         let (host_session, host_window_id) = detect_host_window();
 
@@ -104,7 +100,7 @@ impl SidebarApp {
             palette,
             status_icons,
             spinner_frame: 0,
-            stale_threshold_secs: 60 * 60, // 60 minutes
+            stale_threshold_secs,
             layout_mode: SidebarLayoutMode::default(),
             list_area: Rect::default(),
             window_prefix,
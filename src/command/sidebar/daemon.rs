@@ -14,6 +14,7 @@ use std::thread;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::cmd::Cmd;
+use crate::command::dashboard::settings::stale_threshold_secs;
 use crate::config::Config;
 use crate::git::GitStatus;
 use crate::multiplexer::{Multiplexer, create_backend, detect_backend};
@@ -949,6 +950,7 @@ pub fn run() -> Result<()> {
     let instance_id = mux.instance_id();
     let config = Config::load(None)?;
     let status_icons = config.status_icons.clone();
+    let stale_threshold = stale_threshold_secs(&config.dashboard);
 
     tracing::info!(instance_id = %instance_id, "sidebar daemon starting");
 
@@ -993,6 +995,7 @@ pub fn run() -> Result<()> {
     let mut dirty_pending = false;
     let mut last_agent_list = String::new();
     let mut last_health_log = Instant::now();
+    let mut last_run_prune: Option<Instant> = None;
     let refresh_interval = Duration::from_secs(2);
     let debounce_interval = Duration::from_millis(50);
 
@@ -1059,7 +1062,6 @@ pub fn run() -> Result<()> {
             server.broadcast(&output.snapshot);
 
             // Update git worker with current agent paths and stale status
-            let stale_threshold = 60 * 60; // 1 hour, matches sidebar UI
             let entries: Vec<GitWorkerPath> = output
                 .snapshot
                 .agents
@@ -1111,6 +1113,14 @@ pub fn run() -> Result<()> {
             last_health_log = Instant::now();
         }
 
+        // Apply the run retention policy (at startup, then hourly)
+        if last_run_prune.is_none_or(|t| t.elapsed() >= Duration::from_secs(3600)) {
+            if let Err(e) = crate::state::run::prune_runs(config.runs.retention()) {
+                tracing::warn!(error = %e, "failed to prune old runs");
+            }
+            last_run_prune = Some(Instant::now());
+        }
+
         // Block until woken by a producer or next refresh is due.
         // SIGUSR1 sets dirty_flag (can't use channels from signal handlers),
         // so we cap the wait at 100ms to check it, but otherwise block fully.
@@ -1315,6 +1325,7 @@ mod tests {
             window_id: String::new(),
             path: PathBuf::new(),
             pane_title: None,
+            waiting_reason: None,
             status: Some(AgentStatus::Working),
             status_ts: Some(100),
             updated_ts: Some(updated_ts),
@@ -1649,6 +1660,7 @@ mod tests {
                 status: Some(AgentStatus::Working),
                 status_ts: Some(status_ts),
                 pane_title: None,
+                waiting_reason: None,
                 pane_pid: 1,
                 command: "node".to_string(),
                 updated_ts,
@@ -299,7 +299,7 @@ fn render_compact_list(f: &mut Frame, app: &mut SidebarApp, area: Rect) {
         })
         .collect();
 
-    let list = List::new(items).highlight_style(Style::default().bg(app.palette.highlight_row_bg));
+    let list = List::new(items).highlight_style(app.palette.highlight_style());
 
     f.render_stateful_widget(list, area, &mut app.list_state);
 }
@@ -623,7 +623,7 @@ fn status_icon_and_style(
     is_interrupted: bool,
 ) -> (Vec<(String, Style)>, Style) {
     if is_stale {
-        let style = Style::default().fg(app.palette.dimmed);
+        let style = Style::default().fg(app.palette.stale());
         return (vec![("💤".to_string(), style)], style);
     }
     if is_interrupted {
@@ -632,7 +632,7 @@ fn status_icon_and_style(
     }
     match status {
         Some(AgentStatus::Working) => {
-            let base_style = Style::default().fg(app.palette.info);
+            let base_style = Style::default().fg(app.palette.working());
             let spans = match &app.status_icons.working {
                 Some(custom) => tmux_style::parse_tmux_styles(custom, base_style),
                 None => {
@@ -647,12 +647,12 @@ fn status_icon_and_style(
             (spans, base_style)
         }
         Some(AgentStatus::Waiting) => {
-            let base_style = Style::default().fg(app.palette.accent);
+            let base_style = Style::default().fg(app.palette.waiting());
             let spans = tmux_style::parse_tmux_styles(app.status_icons.waiting(), base_style);
             (spans, base_style)
         }
         Some(AgentStatus::Done) => {
-            let base_style = Style::default().fg(app.palette.success);
+            let base_style = Style::default().fg(app.palette.done());
             let spans = tmux_style::parse_tmux_styles(app.status_icons.done(), base_style);
             (spans, base_style)
         }
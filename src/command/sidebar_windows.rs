@@ -0,0 +1,45 @@
+//! Stand-in for the sidebar on Windows. The sidebar lives in tmux panes and
+//! talks to its daemon over a Unix socket, neither of which exists there.
+
+use anyhow::{Result, bail};
+
+/// Navigation action for sidebar hotkeys.
+pub enum NavAction {
+    Next,
+    Prev,
+    Jump(usize),
+}
+
+fn unsupported() -> Result<()> {
+    bail!(
+        "The sidebar needs tmux, which isn't available on Windows. Use `workmux dashboard` instead."
+    )
+}
+
+pub fn toggle() -> Result<()> {
+    unsupported()
+}
+
+pub fn toggle_session() -> Result<()> {
+    unsupported()
+}
+
+pub fn sync(_window_id: Option<&str>) -> Result<()> {
+    unsupported()
+}
+
+pub fn reflow(_window_id: Option<&str>) -> Result<()> {
+    unsupported()
+}
+
+pub fn run_daemon() -> Result<()> {
+    unsupported()
+}
+
+pub fn run_sidebar() -> Result<()> {
+    unsupported()
+}
+
+pub fn navigate(_action: NavAction) -> Result<()> {
+    unsupported()
+}
@@ -1,18 +1,26 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use serde::Serialize;
 use tabled::{
     Table, Tabled,
-    settings::{Padding, Style, object::Columns},
+    settings::{Padding, Remove, Style, location::ByColumnName, object::Columns},
 };
 
 use crate::git;
-use crate::multiplexer::{AgentStatus, create_backend, detect_backend};
+use crate::github::{self, CheckState, PrSummary};
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
 use crate::state::StateStore;
 use crate::util;
 use crate::workflow;
 
+/// How often `status --watch` re-fetches PR checks
+const PR_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Serialize)]
 struct StatusEntry {
     worktree: String,
@@ -20,6 +28,8 @@ struct StatusEntry {
     status: String,
     elapsed_secs: Option<u64>,
     title: Option<String>,
+    /// What the agent is asking for, while waiting
+    waiting_reason: Option<String>,
     pane_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     git: Option<GitInfo>,
@@ -42,6 +52,8 @@ struct StatusRow {
     elapsed: String,
     #[tabled(rename = "GIT")]
     git: String,
+    #[tabled(rename = "PR")]
+    pr: String,
     #[tabled(rename = "TITLE")]
     title: String,
 }
@@ -103,26 +115,118 @@ fn compute_git_info(wt_path: &std::path::Path, branch: &str) -> GitInfo {
 
 pub fn run(worktrees: &[String], json: bool, show_git: bool) -> Result<()> {
     let mux = create_backend(detect_backend());
+    let entries = collect_entries(worktrees, show_git, mux.as_ref())?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else {
+        println!("{}", render_table(&entries, show_git, None));
+    }
+    Ok(())
+}
+
+/// `status --watch`: redraw the table in place every `interval` until interrupted.
+///
+/// PR checks are fetched in the background every `PR_REFRESH_INTERVAL`, so a
+/// slow `gh` call never holds up the agent statuses.
+pub fn watch(worktrees: &[String], show_git: bool, interval: Duration) -> Result<()> {
+    let mux = create_backend(detect_backend());
+    let prs = spawn_pr_fetcher();
+    let mut stdout = io::stdout();
+
+    loop {
+        let entries = collect_entries(worktrees, show_git, mux.as_ref())?;
+        let table = {
+            let prs = prs.lock().unwrap();
+            render_table(&entries, show_git, Some(&*prs))
+        };
+        let header = format!(
+            "Every {} · Ctrl-C to exit",
+            util::format_elapsed_duration(interval)
+        );
+        // Home the cursor and clear below it, so the view updates without flicker
+        write!(stdout, "\x1b[H\x1b[J{}\n\n{}\n", header, table)?;
+        stdout.flush()?;
+        thread::sleep(interval);
+    }
+}
+
+/// PRs for the current repo keyed by branch, starting from the on-disk cache
+/// and refreshed by a background thread.
+fn spawn_pr_fetcher() -> Arc<Mutex<HashMap<String, PrSummary>>> {
+    let repo_root = git::get_main_worktree_root().ok();
+    let cached = repo_root
+        .as_ref()
+        .and_then(|root| github::load_pr_cache().remove(root))
+        .unwrap_or_default();
+    let prs = Arc::new(Mutex::new(cached));
+
+    if let Some(repo_root) = repo_root {
+        let prs = Arc::clone(&prs);
+        thread::spawn(move || {
+            loop {
+                let branches: Vec<String> = git::list_worktrees()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(_, branch)| branch)
+                    .collect();
+                let previous = prs.lock().unwrap().clone();
+                let refresh = github::PrRefresh {
+                    previous: Some(&previous),
+                    visible: None,
+                };
+                match github::list_prs_for_branches(&repo_root, &branches, &refresh) {
+                    Ok(fetched) => {
+                        crate::tracker::link_prs(&repo_root, &fetched);
+                        *prs.lock().unwrap() = fetched;
+                    }
+                    Err(e) => tracing::debug!(error = %e, "status:pr fetch failed"),
+                }
+                thread::sleep(PR_REFRESH_INTERVAL);
+            }
+        });
+    }
+    prs
+}
+
+pub(super) fn pr_label(pr: Option<&PrSummary>) -> String {
+    let Some(pr) = pr else {
+        return "-".to_string();
+    };
+    let checks = match &pr.checks {
+        Some(CheckState::Success) => " ✓".to_string(),
+        // Counts are unknown (0/0) when only the overall state was fetched
+        Some(CheckState::Failure { total: 0, .. }) => " ✗".to_string(),
+        Some(CheckState::Pending { total: 0, .. }) => " …".to_string(),
+        Some(CheckState::Failure { passed, total }) => format!(" ✗ {}/{}", passed, total),
+        Some(CheckState::Pending { passed, total }) => format!(" … {}/{}", passed, total),
+        None => String::new(),
+    };
+    let state = match pr.state.as_str() {
+        "MERGED" => " merged",
+        "CLOSED" => " closed",
+        _ if pr.is_draft => " draft",
+        _ => "",
+    };
+    format!("#{}{}{}", pr.number, state, checks)
+}
 
-    let agent_panes =
-        StateStore::new().and_then(|store| store.load_reconciled_agents(mux.as_ref()))?;
+fn collect_entries(
+    worktrees: &[String],
+    show_git: bool,
+    mux: &dyn Multiplexer,
+) -> Result<Vec<StatusEntry>> {
+    let agent_panes = StateStore::new().and_then(|store| store.load_reconciled_agents(mux))?;
 
+    let mut entries: Vec<StatusEntry> = Vec::new();
     if agent_panes.is_empty() {
-        if json {
-            println!("[]");
-        } else {
-            println!("No active agents");
-        }
-        return Ok(());
+        return Ok(entries);
     }
 
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0);
-
-    let mut entries: Vec<StatusEntry> = Vec::new();
-
     if worktrees.is_empty() {
         // No specific targets: show all agents in the local repo (existing behavior)
         let all_worktrees = git::list_worktrees()?;
@@ -168,6 +272,7 @@ pub fn run(worktrees: &[String], json: bool, show_git: bool) -> Result<()> {
                     status: status_label(agent.status),
                     elapsed_secs,
                     title: agent.pane_title.clone(),
+                    waiting_reason: agent.waiting_reason.clone(),
                     pane_id: agent.pane_id.clone(),
                     git: git_info.clone(),
                 });
@@ -176,7 +281,7 @@ pub fn run(worktrees: &[String], json: bool, show_git: bool) -> Result<()> {
     } else {
         // Specific targets: resolve each via the cross-project-aware resolver
         for name in worktrees {
-            match workflow::resolve_worktree_agents(name, mux.as_ref()) {
+            match workflow::resolve_worktree_agents(name, mux) {
                 Ok((wt_path, matching)) => {
                     let worktree_name = wt_path
                         .file_name()
@@ -202,6 +307,7 @@ pub fn run(worktrees: &[String], json: bool, show_git: bool) -> Result<()> {
                             status: status_label(agent.status),
                             elapsed_secs,
                             title: agent.pane_title.clone(),
+                            waiting_reason: agent.waiting_reason.clone(),
                             pane_id: agent.pane_id.clone(),
                             git: git_info.clone(),
                         });
@@ -214,46 +320,99 @@ pub fn run(worktrees: &[String], json: bool, show_git: bool) -> Result<()> {
         }
     }
 
-    if json {
-        println!("{}", serde_json::to_string_pretty(&entries)?);
-    } else {
-        if entries.is_empty() {
-            println!("No active agents");
-            return Ok(());
-        }
+    Ok(entries)
+}
 
-        let rows: Vec<StatusRow> = entries
-            .iter()
-            .map(|e| {
-                let worktree = if e.branch != e.worktree {
-                    format!("{} ({})", e.worktree, e.branch)
-                } else {
-                    e.worktree.clone()
-                };
-                StatusRow {
-                    worktree,
-                    status: e.status.clone(),
-                    elapsed: e
-                        .elapsed_secs
-                        .map(util::format_elapsed_secs)
-                        .unwrap_or("-".to_string()),
-                    git: git_label(&e.git),
-                    title: e.title.clone().unwrap_or("-".to_string()),
-                }
-            })
-            .collect();
-
-        let mut table = Table::new(rows);
-        table
-            .with(Style::blank())
-            .modify(Columns::new(..), Padding::new(0, 1, 0, 0));
-        if !show_git {
-            table.with(tabled::settings::Remove::column(
-                tabled::settings::location::ByColumnName::new("GIT"),
-            ));
+/// Render entries as a table. The PR column is shown when `prs` is given.
+fn render_table(
+    entries: &[StatusEntry],
+    show_git: bool,
+    prs: Option<&HashMap<String, PrSummary>>,
+) -> String {
+    if entries.is_empty() {
+        return "No active agents".to_string();
+    }
+
+    let rows: Vec<StatusRow> = entries
+        .iter()
+        .map(|e| {
+            let worktree = if e.branch != e.worktree {
+                format!("{} ({})", e.worktree, e.branch)
+            } else {
+                e.worktree.clone()
+            };
+            StatusRow {
+                worktree,
+                status: e.status.clone(),
+                elapsed: e
+                    .elapsed_secs
+                    .map(util::format_elapsed_secs)
+                    .unwrap_or("-".to_string()),
+                git: git_label(&e.git),
+                pr: pr_label(prs.and_then(|prs| prs.get(&e.branch))),
+                title: e.title.clone().unwrap_or("-".to_string()),
+            }
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(..), Padding::new(0, 1, 0, 0));
+    if !show_git {
+        table.with(Remove::column(ByColumnName::new("GIT")));
+    }
+    if prs.is_none() {
+        table.with(Remove::column(ByColumnName::new("PR")));
+    }
+    table.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(state: &str, checks: Option<CheckState>) -> PrSummary {
+        PrSummary {
+            number: 42,
+            title: "Add login".to_string(),
+            state: state.to_string(),
+            is_draft: false,
+            checks,
+            check_meta: None,
+            url: None,
+            head_oid: None,
         }
-        println!("{table}");
     }
 
-    Ok(())
+    #[test]
+    fn pr_label_shows_state_and_checks() {
+        assert_eq!(pr_label(None), "-");
+        assert_eq!(pr_label(Some(&pr("OPEN", None))), "#42");
+        assert_eq!(
+            pr_label(Some(&pr("OPEN", Some(CheckState::Success)))),
+            "#42 ✓"
+        );
+        assert_eq!(
+            pr_label(Some(&pr(
+                "OPEN",
+                Some(CheckState::Failure {
+                    passed: 3,
+                    total: 5
+                })
+            ))),
+            "#42 ✗ 3/5"
+        );
+        assert_eq!(
+            pr_label(Some(&pr(
+                "OPEN",
+                Some(CheckState::Failure {
+                    passed: 0,
+                    total: 0
+                })
+            ))),
+            "#42 ✗"
+        );
+        assert_eq!(pr_label(Some(&pr("MERGED", None))), "#42 merged");
+    }
 }
@@ -1,13 +1,18 @@
-use std::collections::HashSet;
+use std::path::PathBuf;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
 
 use crate::git;
-use crate::multiplexer::{AgentStatus, create_backend, detect_backend};
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
 use crate::state::StateStore;
 use crate::util;
+use crate::vcs;
 use crate::workflow;
 
 /// Resolve a worktree name to its path, trying local git first then global agents.
@@ -15,13 +20,10 @@ use crate::workflow;
 /// Local resolution is preferred because it works even before an agent starts
 /// (the worktree directory exists from `workmux add`). Global resolution requires
 /// a running agent.
-fn resolve_worktree_path(
-    name: &str,
-    mux: &dyn crate::multiplexer::Multiplexer,
-) -> Result<std::path::PathBuf> {
+fn resolve_worktree_path(name: &str, mux: &dyn Multiplexer) -> Result<std::path::PathBuf> {
     // Try local git resolution first (supports waiting for unstarted agents)
     if git::is_git_repo().unwrap_or(false) {
-        match git::find_worktree(name) {
+        match vcs::find_workspace(name) {
             Ok((path, _branch)) => return Ok(path),
             Err(e) if e.downcast_ref::<git::WorktreeNotFound>().is_some() => {}
             Err(e) => return Err(e),
@@ -45,88 +47,242 @@ fn parse_status(s: &str) -> Result<AgentStatus> {
     }
 }
 
+fn status_name(status: AgentStatus) -> &'static str {
+    match status {
+        AgentStatus::Working => "working",
+        AgentStatus::Waiting => "waiting",
+        AgentStatus::Done => "done",
+    }
+}
+
+/// Worktrees (besides the main one) that currently have an agent.
+fn agent_worktrees(mux: &dyn Multiplexer) -> Result<Vec<(String, PathBuf)>> {
+    let main_root = git::get_main_worktree_root()?;
+    let agent_panes = StateStore::new().and_then(|store| store.load_reconciled_agents(mux))?;
+    let targets: Vec<(String, PathBuf)> = vcs::list_workspaces()?
+        .into_iter()
+        .filter(|(path, _)| *path != main_root)
+        .filter(|(path, _)| !workflow::match_agents_to_worktree(&agent_panes, path).is_empty())
+        .map(|(path, _)| {
+            let handle = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            (handle, path)
+        })
+        .collect();
+    if targets.is_empty() {
+        bail!("No worktrees with running agents found");
+    }
+    Ok(targets)
+}
+
+/// How a waited-on worktree ended up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// An agent reached one of the target statuses
+    Reached(AgentStatus),
+    /// The agent went away along with its worktree
+    Merged,
+    /// The agent went away but the worktree is still there
+    Exited,
+}
+
+struct Target {
+    name: String,
+    path: PathBuf,
+    seen_agent: bool,
+    outcome: Option<(Outcome, Duration)>,
+}
+
+#[derive(Tabled)]
+struct SummaryRow {
+    #[tabled(rename = "WORKTREE")]
+    name: String,
+    #[tabled(rename = "RESULT")]
+    result: String,
+    #[tabled(rename = "TIME")]
+    time: String,
+}
+
+/// Exit code for the final state: 0 when the wait is satisfied, 3 when it
+/// can no longer be because agents exited, otherwise 1 (timed out).
+fn exit_code(targets: &[Target], any: bool) -> i32 {
+    let outcomes: Vec<Option<Outcome>> =
+        targets.iter().map(|t| t.outcome.map(|(o, _)| o)).collect();
+    let ok = |o: &Option<Outcome>| matches!(o, Some(Outcome::Reached(_) | Outcome::Merged));
+    let satisfied = if any {
+        outcomes.iter().any(ok)
+    } else {
+        outcomes.iter().all(ok)
+    };
+    let exited = |o: &Option<Outcome>| *o == Some(Outcome::Exited);
+    let hopeless = if any {
+        outcomes.iter().all(exited)
+    } else {
+        outcomes.iter().any(exited)
+    };
+    if satisfied {
+        0
+    } else if hopeless {
+        3
+    } else {
+        1
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Reached(status) => status_name(status),
+        Outcome::Merged => "merged",
+        Outcome::Exited => "exited",
+    }
+}
+
+/// `worktree<TAB>result<TAB>elapsed_secs`, with `pending` and `-` for targets
+/// that never settled.
+fn porcelain_line(target: &Target) -> String {
+    match target.outcome {
+        Some((outcome, elapsed)) => format!(
+            "{}\t{}\t{}",
+            target.name,
+            outcome_name(outcome),
+            elapsed.as_secs()
+        ),
+        None => format!("{}\tpending\t-", target.name),
+    }
+}
+
+fn print_summary(targets: &[Target]) {
+    let rows: Vec<SummaryRow> = targets
+        .iter()
+        .map(|t| {
+            let (result, time) = match t.outcome {
+                Some((outcome, elapsed)) => (
+                    outcome_name(outcome).to_string(),
+                    util::format_elapsed_duration(elapsed),
+                ),
+                None => ("pending".to_string(), "-".to_string()),
+            };
+            SummaryRow {
+                name: t.name.clone(),
+                result,
+                time,
+            }
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..3), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+}
+
+/// Print the summary (when waiting on several worktrees, or for scripts) and exit.
+fn finish(targets: &[Target], any: bool, porcelain: bool) -> Result<()> {
+    if porcelain {
+        for target in targets {
+            println!("{}", porcelain_line(target));
+        }
+    } else if targets.len() > 1 {
+        print_summary(targets);
+    }
+    match exit_code(targets, any) {
+        0 => Ok(()),
+        code => std::process::exit(code),
+    }
+}
+
 pub fn run(
     worktree_names: &[String],
-    target_status: &str,
+    all: bool,
+    target_statuses: &[String],
     timeout_secs: Option<u64>,
     any: bool,
+    porcelain: bool,
 ) -> Result<()> {
-    let target = parse_status(target_status)?;
+    let targets = target_statuses
+        .iter()
+        .map(|s| parse_status(s))
+        .collect::<Result<Vec<_>>>()?;
     let mux = create_backend(detect_backend());
     let start = Instant::now();
 
     // Resolve worktree paths upfront (local git first, then global agents)
-    let worktree_paths: Vec<_> = worktree_names
-        .iter()
-        .map(|name| {
-            let path = resolve_worktree_path(name, mux.as_ref())?;
-            Ok((name.clone(), path))
+    let worktree_paths = if all {
+        agent_worktrees(mux.as_ref())?
+    } else {
+        worktree_names
+            .iter()
+            .map(|name| {
+                let path = resolve_worktree_path(name, mux.as_ref())?;
+                Ok((name.clone(), path))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    let mut waiting: Vec<Target> = worktree_paths
+        .into_iter()
+        .map(|(name, path)| Target {
+            name,
+            path,
+            seen_agent: false,
+            outcome: None,
         })
-        .collect::<Result<Vec<_>>>()?;
-
-    let mut reached: HashSet<String> = HashSet::new();
-    let mut seen_agent: HashSet<String> = HashSet::new();
+        .collect();
 
     loop {
         // Check timeout
         if let Some(timeout) = timeout_secs
             && start.elapsed() > Duration::from_secs(timeout)
         {
-            let remaining: Vec<_> = worktree_names
+            let remaining: Vec<&str> = waiting
                 .iter()
-                .filter(|n| !reached.contains(n.as_str()))
+                .filter(|t| t.outcome.is_none())
+                .map(|t| t.name.as_str())
                 .collect();
-            eprintln!(
-                "Timeout waiting for: {}",
-                remaining
-                    .iter()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-            std::process::exit(1);
+            eprintln!("Timeout waiting for: {}", remaining.join(", "));
+            return finish(&waiting, any, porcelain);
         }
 
         // Load current agent state
         let agent_panes =
             StateStore::new().and_then(|store| store.load_reconciled_agents(mux.as_ref()))?;
 
-        for (name, wt_path) in &worktree_paths {
-            if reached.contains(name) {
-                continue;
-            }
-
-            let matching = workflow::match_agents_to_worktree(&agent_panes, wt_path);
+        for target in waiting.iter_mut().filter(|t| t.outcome.is_none()) {
+            let matching = workflow::match_agents_to_worktree(&agent_panes, &target.path);
+            let elapsed = start.elapsed();
 
             if !matching.is_empty() {
-                seen_agent.insert(name.clone());
-
-                // Check if ANY agent in this worktree has reached the target status
-                let has_target = matching.iter().any(|a| a.status == Some(target));
-                if has_target {
-                    let elapsed = util::format_elapsed_duration(start.elapsed());
-                    eprintln!("{}: {} ({})", name, target_status, elapsed);
-                    reached.insert(name.clone());
-
-                    if any {
-                        return Ok(());
-                    }
+                target.seen_agent = true;
+
+                // Check if ANY agent in this worktree has reached a target status
+                if let Some(status) = matching
+                    .iter()
+                    .filter_map(|a| a.status)
+                    .find(|s| targets.contains(s))
+                {
+                    eprintln!(
+                        "{}: {} ({})",
+                        target.name,
+                        status_name(status),
+                        util::format_elapsed_duration(elapsed)
+                    );
+                    target.outcome = Some((Outcome::Reached(status), elapsed));
                 }
-            } else if seen_agent.contains(name) {
+            } else if target.seen_agent {
                 // Agent was previously running but disappeared
                 // Check if worktree still exists - if not, it was merged (success)
-                if !wt_path.exists() {
-                    let elapsed = util::format_elapsed_duration(start.elapsed());
-                    eprintln!("{}: merged ({})", name, elapsed);
-                    reached.insert(name.clone());
-
-                    if any {
-                        return Ok(());
-                    }
+                if !target.path.exists() {
+                    eprintln!(
+                        "{}: merged ({})",
+                        target.name,
+                        util::format_elapsed_duration(elapsed)
+                    );
+                    target.outcome = Some((Outcome::Merged, elapsed));
                 } else {
                     // Worktree exists but agent gone - crashed/exited unexpectedly
-                    eprintln!("{}: agent exited unexpectedly", name);
-                    std::process::exit(3);
+                    eprintln!("{}: agent exited unexpectedly", target.name);
+                    target.outcome = Some((Outcome::Exited, elapsed));
                 }
             }
             // If we haven't seen an agent yet and it's been > 10s, still wait --
@@ -134,11 +290,58 @@ pub fn run(
             // overall deadline.
         }
 
-        // Check if all have reached target
-        if reached.len() == worktree_paths.len() {
-            return Ok(());
+        // Done once the wait is satisfied or can't be anymore
+        let settled = waiting.iter().all(|t| t.outcome.is_some());
+        if settled || exit_code(&waiting, any) != 1 {
+            return finish(&waiting, any, porcelain);
         }
 
         thread::sleep(Duration::from_secs(2));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(outcome: Option<Outcome>) -> Target {
+        Target {
+            name: "wt".to_string(),
+            path: PathBuf::from("/wt"),
+            seen_agent: true,
+            outcome: outcome.map(|o| (o, Duration::ZERO)),
+        }
+    }
+
+    #[test]
+    fn exit_code_for_all_and_any() {
+        let done = Some(Outcome::Reached(AgentStatus::Done));
+        let both_done = [target(done), target(Some(Outcome::Merged))];
+        assert_eq!(exit_code(&both_done, false), 0);
+
+        let one_pending = [target(done), target(None)];
+        assert_eq!(exit_code(&one_pending, false), 1);
+        assert_eq!(exit_code(&one_pending, true), 0);
+
+        let one_exited = [target(Some(Outcome::Exited)), target(None)];
+        assert_eq!(exit_code(&one_exited, false), 3);
+        assert_eq!(exit_code(&one_exited, true), 1);
+
+        let all_exited = [target(Some(Outcome::Exited)), target(Some(Outcome::Exited))];
+        assert_eq!(exit_code(&all_exited, true), 3);
+    }
+
+    #[test]
+    fn porcelain_line_fields() {
+        let mut done = target(Some(Outcome::Reached(AgentStatus::Done)));
+        done.outcome = done
+            .outcome
+            .map(|(o, _)| (o, Duration::from_millis(75_400)));
+        assert_eq!(porcelain_line(&done), "wt\tdone\t75");
+        assert_eq!(
+            porcelain_line(&target(Some(Outcome::Merged))),
+            "wt\tmerged\t0"
+        );
+        assert_eq!(porcelain_line(&target(None)), "wt\tpending\t-");
+    }
+}
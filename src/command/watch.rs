@@ -0,0 +1,176 @@
+//! `workmux watch`: headless desktop notifications for agent status changes.
+//!
+//! Polls the reconciled agent state and notifies when an agent starts waiting
+//! or finishes. Unlike the notifications sent from status hooks, it needs no
+//! `notifications` config and can be limited to some projects.
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::agent_display::extract_project_name;
+use crate::config::Config;
+use crate::multiplexer::{AgentPane, AgentStatus, create_backend, detect_backend};
+use crate::notification::{self, sound::SoundEvent};
+use crate::state::StateStore;
+
+/// What the watcher last saw of one pane
+struct Seen {
+    status: Option<AgentStatus>,
+    status_ts: Option<u64>,
+    /// When the current status was first seen
+    since: Instant,
+    /// Whether the current status has been notified (or never will be)
+    settled: bool,
+}
+
+/// Tracks agent statuses across polls and decides what to notify.
+struct Watcher {
+    debounce: Duration,
+    panes: HashMap<String, Seen>,
+    /// Whether a poll has been observed yet; statuses present at startup
+    /// aren't notified
+    primed: bool,
+}
+
+impl Watcher {
+    fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            panes: HashMap::new(),
+            primed: false,
+        }
+    }
+
+    /// Record a poll and return the agents to notify about: those that
+    /// reached waiting or done and stayed there for the debounce period.
+    fn observe<'a>(&mut self, agents: &'a [AgentPane], now: Instant) -> Vec<&'a AgentPane> {
+        let primed = self.primed;
+        self.primed = true;
+        self.panes
+            .retain(|pane_id, _| agents.iter().any(|a| a.pane_id == *pane_id));
+
+        let mut due = Vec::new();
+        for agent in agents {
+            let seen = self
+                .panes
+                .entry(agent.pane_id.clone())
+                .or_insert_with(|| Seen {
+                    status: agent.status,
+                    status_ts: agent.status_ts,
+                    since: now,
+                    settled: !primed,
+                });
+            // A changed timestamp catches a round trip between polls
+            if seen.status != agent.status || seen.status_ts != agent.status_ts {
+                *seen = Seen {
+                    status: agent.status,
+                    status_ts: agent.status_ts,
+                    since: now,
+                    settled: false,
+                };
+            }
+            if seen.settled || now.duration_since(seen.since) < self.debounce {
+                continue;
+            }
+            seen.settled = true;
+            if matches!(agent.status, Some(AgentStatus::Waiting | AgentStatus::Done)) {
+                due.push(agent);
+            }
+        }
+        due
+    }
+}
+
+pub fn run(projects: &[String], interval: u64, debounce: u64) -> Result<()> {
+    let config = Config::load(None)?;
+    let mux = create_backend(detect_backend());
+    let store = StateStore::new()?;
+    let mut watcher = Watcher::new(Duration::from_secs(debounce));
+
+    println!("Watching agents for waiting and done (Ctrl-C to stop)");
+    loop {
+        match store.load_reconciled_agents(mux.as_ref()) {
+            Ok(mut agents) => {
+                if !projects.is_empty() {
+                    agents.retain(|a| projects.contains(&extract_project_name(&a.path)));
+                }
+                for agent in watcher.observe(&agents, Instant::now()) {
+                    notify(&config, agent);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "watch: failed to load agents"),
+        }
+        thread::sleep(Duration::from_secs(interval.max(1)));
+    }
+}
+
+fn notify(config: &Config, agent: &AgentPane) {
+    let (status, sound_event) = match agent.status {
+        Some(AgentStatus::Waiting) => (AgentStatus::Waiting, SoundEvent::Waiting),
+        Some(AgentStatus::Done) => (AgentStatus::Done, SoundEvent::Done),
+        _ => return,
+    };
+    let handle = notification::worktree_handle(&agent.path);
+    let message = notification::status_message(&handle, status, agent.waiting_reason.as_deref());
+    println!("{}", message);
+    notification::show_for(&config.notifications, &message, sound_event);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn agent(pane_id: &str, status: AgentStatus, status_ts: u64) -> AgentPane {
+        AgentPane {
+            session: "main".to_string(),
+            window_name: "wm-api".to_string(),
+            pane_id: pane_id.to_string(),
+            window_id: "@1".to_string(),
+            path: PathBuf::from("/src/app__worktrees/api"),
+            pane_title: None,
+            waiting_reason: None,
+            status: Some(status),
+            status_ts: Some(status_ts),
+            updated_ts: Some(status_ts),
+        }
+    }
+
+    #[test]
+    fn watcher_skips_startup_statuses_and_debounces() {
+        let debounce = Duration::from_secs(5);
+        let start = Instant::now();
+        let mut watcher = Watcher::new(debounce);
+
+        // Already waiting at startup: not notified
+        assert!(
+            watcher
+                .observe(&[agent("%1", AgentStatus::Waiting, 100)], start)
+                .is_empty()
+        );
+
+        // Starts working, then waits again: notified once the wait has lasted
+        let waiting = [agent("%1", AgentStatus::Waiting, 200)];
+        assert!(watcher.observe(&waiting, start + debounce).is_empty());
+        let due = watcher.observe(&waiting, start + debounce * 2);
+        assert_eq!(due.len(), 1);
+        assert!(watcher.observe(&waiting, start + debounce * 3).is_empty());
+    }
+
+    #[test]
+    fn watcher_ignores_short_lived_statuses() {
+        let debounce = Duration::from_secs(5);
+        let start = Instant::now();
+        let mut watcher = Watcher::new(debounce);
+        watcher.observe(&[agent("%1", AgentStatus::Working, 100)], start);
+
+        let tick = Duration::from_secs(2);
+        watcher.observe(&[agent("%1", AgentStatus::Waiting, 102)], start + tick);
+        let due = watcher.observe(&[agent("%1", AgentStatus::Working, 104)], start + tick * 4);
+        assert!(due.is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+//! Hidden `_with-secrets` subcommand: start an agent pane's command with the
+//! configured `secrets` in its environment.
+
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+
+use crate::config::Config;
+use crate::{platform, secrets};
+
+pub fn run(command: &str) -> Result<()> {
+    // A broken config shouldn't stop the agent from starting
+    let config = Config::load(None).unwrap_or_default();
+    let mut process = Command::new(platform::sh());
+    process
+        .arg("-c")
+        .arg(command)
+        .envs(secrets::resolve_env(&config.secrets));
+    let err = platform::exec(&mut process);
+    Err(anyhow!("Failed to run {}: {}", command, err))
+}
@@ -4,8 +4,10 @@ use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 use tracing::debug;
 
+use crate::multiplexer::{AgentStatus, BackendType};
 use crate::{cmd, git, nerdfont};
 use which::{which, which_in};
 
@@ -14,6 +16,58 @@ use which::{which, which_in};
 /// making the workmux remove command return almost instantly.
 const NODE_MODULES_CLEANUP_SCRIPT: &str = include_str!("scripts/cleanup_node_modules.sh");
 
+/// How a file rule brings matched paths into the worktree
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FileMode {
+    #[default]
+    Copy,
+    Symlink,
+}
+
+/// An entry in `files.include`: a bare glob (copied) or a glob with its own
+/// mode and exclusions.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FileRule {
+    Pattern(String),
+    Rule {
+        path: String,
+        #[serde(default)]
+        mode: FileMode,
+        #[serde(default)]
+        exclude: Vec<String>,
+    },
+}
+
+impl From<&str> for FileRule {
+    fn from(pattern: &str) -> Self {
+        FileRule::Pattern(pattern.to_string())
+    }
+}
+
+impl FileRule {
+    pub fn path(&self) -> &str {
+        match self {
+            FileRule::Pattern(path) | FileRule::Rule { path, .. } => path,
+        }
+    }
+
+    pub fn mode(&self) -> FileMode {
+        match self {
+            FileRule::Pattern(_) => FileMode::Copy,
+            FileRule::Rule { mode, .. } => *mode,
+        }
+    }
+
+    pub fn exclude(&self) -> &[String] {
+        match self {
+            FileRule::Pattern(_) => &[],
+            FileRule::Rule { exclude, .. } => exclude,
+        }
+    }
+}
+
 /// Configuration for file operations during worktree creation
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct FileConfig {
@@ -24,6 +78,34 @@ pub struct FileConfig {
     /// Glob patterns for files to symlink from the repo root into the new worktree
     #[serde(default)]
     pub symlink: Option<Vec<String>>,
+
+    /// Globs with a per-entry mode (copy or symlink) and exclusions
+    #[serde(default)]
+    pub include: Option<Vec<FileRule>>,
+
+    /// Gitignore-style patterns skipped by all file operations, including
+    /// files inside copied directories
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+}
+
+impl FileConfig {
+    /// All file rules in order: `copy`, then `symlink`, then `include`.
+    pub fn rules(&self) -> Vec<FileRule> {
+        let copy = self
+            .copy
+            .iter()
+            .flatten()
+            .map(|p| FileRule::from(p.as_str()));
+        let symlink = self.symlink.iter().flatten().map(|p| FileRule::Rule {
+            path: p.clone(),
+            mode: FileMode::Symlink,
+            exclude: Vec::new(),
+        });
+        copy.chain(symlink)
+            .chain(self.include.iter().flatten().cloned())
+            .collect()
+    }
 }
 
 /// Configuration for agent status icons displayed in tmux window bar
@@ -51,6 +133,240 @@ impl StatusIcons {
     }
 }
 
+/// Desktop notifications for agent status changes
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct NotificationsConfig {
+    /// Notify when an agent needs input. Default: false
+    pub waiting: Option<bool>,
+    /// Notify when an agent finishes. Default: false
+    pub done: Option<bool>,
+    /// Play the notification sound. Default: true
+    pub sound: Option<bool>,
+
+    /// Sound per event, overriding the default sound
+    #[serde(default)]
+    pub sounds: SoundsConfig,
+
+    /// Local time range when sounds are muted, e.g. "22:00-08:00"
+    pub quiet_hours: Option<String>,
+
+    /// Endpoints to POST to on status changes and merges. Global-only.
+    pub webhooks: Option<Vec<WebhookConfig>>,
+
+    /// Push notifications through ntfy. Global-only.
+    pub ntfy: Option<NtfyConfig>,
+
+    /// Email delivery for `workmux digest`. Global-only.
+    pub email: Option<EmailConfig>,
+
+    /// Follow-up push when an agent is left waiting
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+
+    /// Terminal escape sequences for status, for use without tmux
+    #[serde(default)]
+    pub osc: OscConfig,
+}
+
+/// Where and how to send email
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmailConfig {
+    pub to: String,
+
+    /// Sender address. Required with `smtp`; sendmail picks one otherwise.
+    pub from: Option<String>,
+
+    /// sendmail-compatible program. Default: sendmail
+    pub sendmail: Option<String>,
+
+    /// Send through an SMTP server instead of sendmail
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// An SMTP server, used through curl
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SmtpConfig {
+    /// `smtps://host:465`, or `smtp://host:587` for STARTTLS
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// Escalation for agents left waiting for input
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct EscalationConfig {
+    /// Minutes an agent must be waiting before escalating. Unset: disabled
+    pub minutes: Option<u64>,
+    /// Channels to escalate through. Default: ntfy
+    pub via: Option<Vec<EscalationChannel>>,
+}
+
+impl EscalationConfig {
+    /// How long an agent can wait before escalating, if enabled.
+    pub fn after(&self) -> Option<std::time::Duration> {
+        self.minutes
+            .filter(|&m| m > 0)
+            .map(|m| std::time::Duration::from_secs(m * 60))
+    }
+
+    pub fn via(&self) -> &[EscalationChannel] {
+        self.via.as_deref().unwrap_or(&[EscalationChannel::Ntfy])
+    }
+}
+
+/// A channel an escalation can be sent through
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EscalationChannel {
+    Ntfy,
+    Webhooks,
+    Email,
+}
+
+/// Sound for each kind of notification: "default", "none", a system sound
+/// name, or a path to a sound file
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct SoundsConfig {
+    /// Agent needs input
+    pub waiting: Option<String>,
+    /// Agent finished, or `run --notify` passed
+    pub done: Option<String>,
+    /// `run --notify` failed
+    pub failed: Option<String>,
+}
+
+/// Terminal escape sequences emitted on status changes
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct OscConfig {
+    /// Put the status icon in the terminal/tab title. Default: false
+    pub title: Option<bool>,
+    /// OSC 9 notification when an agent is waiting or done. Default: false
+    pub notify: Option<bool>,
+    /// iTerm2 badge with the status icon. Default: false
+    pub badge: Option<bool>,
+}
+
+impl OscConfig {
+    pub fn title(&self) -> bool {
+        self.title.unwrap_or(false)
+    }
+
+    pub fn notify(&self) -> bool {
+        self.notify.unwrap_or(false)
+    }
+
+    pub fn badge(&self) -> bool {
+        self.badge.unwrap_or(false)
+    }
+
+    pub fn any_enabled(&self) -> bool {
+        self.title() || self.notify() || self.badge()
+    }
+}
+
+/// An event that can trigger a webhook
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationEvent {
+    /// An agent needs input
+    Waiting,
+    /// An agent finished
+    Done,
+    /// A worktree was merged with `workmux merge`
+    Merged,
+}
+
+/// Payload shape for a webhook
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// `{"text": ...}`
+    Slack,
+    /// `{"content": ...}`
+    Discord,
+    /// All event fields as a JSON object
+    Json,
+}
+
+/// A webhook endpoint
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Events to send. Default: all
+    pub events: Option<Vec<NotificationEvent>>,
+
+    /// Payload shape. Default: inferred from the URL (Slack or Discord), else json
+    pub format: Option<WebhookFormat>,
+
+    /// Custom payload as a template, overriding `format`
+    pub template: Option<String>,
+}
+
+impl WebhookConfig {
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.events
+            .as_ref()
+            .is_none_or(|events| events.contains(&event))
+    }
+
+    pub fn format(&self) -> WebhookFormat {
+        self.format.unwrap_or_else(|| {
+            if self.url.contains("hooks.slack.com") {
+                WebhookFormat::Slack
+            } else if self.url.contains("discord.com/api/webhooks")
+                || self.url.contains("discordapp.com/api/webhooks")
+            {
+                WebhookFormat::Discord
+            } else {
+                WebhookFormat::Json
+            }
+        })
+    }
+}
+
+/// An ntfy topic to publish to
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NtfyConfig {
+    pub topic: String,
+
+    /// Server URL. Default: https://ntfy.sh
+    pub server: Option<String>,
+
+    /// Access token for protected topics
+    pub token: Option<String>,
+
+    /// Events to send. Default: all
+    pub events: Option<Vec<NotificationEvent>>,
+}
+
+impl NtfyConfig {
+    pub fn url(&self) -> String {
+        let server = self.server.as_deref().unwrap_or("https://ntfy.sh");
+        format!("{}/{}", server.trim_end_matches('/'), self.topic)
+    }
+
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.events
+            .as_ref()
+            .is_none_or(|events| events.contains(&event))
+    }
+}
+
+impl NotificationsConfig {
+    pub fn enabled_for(&self, status: AgentStatus) -> bool {
+        match status {
+            AgentStatus::Working => false,
+            AgentStatus::Waiting => self.waiting.unwrap_or(false),
+            AgentStatus::Done => self.done.unwrap_or(false),
+        }
+    }
+
+    pub fn sound(&self) -> bool {
+        self.sound.unwrap_or(true)
+    }
+}
+
 /// Configuration for LLM-based branch name generation
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct AutoNameConfig {
@@ -88,9 +404,58 @@ pub struct DashboardConfig {
     /// Default: 60 (60% for preview, 40% for table)
     pub preview_size: Option<u8>,
 
+    /// Lines of the selected agent's output kept in the preview (10-5000).
+    /// Default: 200
+    pub preview_lines: Option<u16>,
+
     /// Show check pass/total counts alongside check icon (default: false)
     #[serde(default)]
     pub show_check_counts: Option<bool>,
+
+    /// Minutes of unchanged pane output before a working agent is marked as
+    /// possibly stalled. Default: off
+    pub stall_minutes: Option<u64>,
+
+    /// How long since an agent's last status change before it's shown as
+    /// stale, as a duration like "30m" or "2h". Default: 1h
+    pub stale_after: Option<String>,
+
+    /// Columns of the agents table, in order. The jump key column is always
+    /// first. Default: project, worktree, git, pr, status, elapsed, title
+    pub columns: Option<Vec<DashboardColumn>>,
+}
+
+/// A column of the dashboard's agents table.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardColumn {
+    Project,
+    /// Worktree (window) name
+    #[serde(alias = "agent")]
+    Worktree,
+    /// Checked out branch
+    Branch,
+    /// Diff stats
+    Git,
+    /// PR state and checks, shown once any agent has a PR
+    Pr,
+    Status,
+    /// Time since the last status change
+    #[serde(alias = "time")]
+    Elapsed,
+    Title,
+}
+
+impl DashboardColumn {
+    pub const DEFAULT: [DashboardColumn; 7] = [
+        DashboardColumn::Project,
+        DashboardColumn::Worktree,
+        DashboardColumn::Git,
+        DashboardColumn::Pr,
+        DashboardColumn::Status,
+        DashboardColumn::Elapsed,
+        DashboardColumn::Title,
+    ];
 }
 
 impl DashboardConfig {
@@ -110,11 +475,270 @@ impl DashboardConfig {
         self.preview_size.unwrap_or(60).clamp(10, 90)
     }
 
+    /// Get the number of output lines the preview keeps (clamped to 10-5000).
+    /// Default: 200
+    pub fn preview_lines(&self) -> u16 {
+        self.preview_lines.unwrap_or(200).clamp(10, 5000)
+    }
+
+    /// The agents table's columns in order, without duplicates. An empty
+    /// list falls back to the default.
+    pub fn columns(&self) -> Vec<DashboardColumn> {
+        let mut columns: Vec<DashboardColumn> = Vec::new();
+        for &column in self.columns.as_deref().unwrap_or_default() {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+        if columns.is_empty() {
+            return DashboardColumn::DEFAULT.to_vec();
+        }
+        columns
+    }
+
     /// Whether to show check pass/total counts alongside check icons.
     /// Default: false
     pub fn show_check_counts(&self) -> bool {
         self.show_check_counts.unwrap_or(false)
     }
+
+    /// How long a working agent's output must be unchanged to count as
+    /// stalled. None (the default, or 0) disables stall detection.
+    pub fn stall_threshold(&self) -> Option<std::time::Duration> {
+        self.stall_minutes
+            .filter(|&m| m > 0)
+            .map(|m| std::time::Duration::from_secs(m * 60))
+    }
+
+    /// The configured staleness threshold. None when unset or invalid, in
+    /// which case the last persisted value (or 1h) applies.
+    pub fn stale_after(&self) -> Option<std::time::Duration> {
+        let value = self.stale_after.as_deref()?;
+        match crate::util::parse_compact_duration(value) {
+            Ok(duration) if !duration.is_zero() => Some(duration),
+            Ok(_) => {
+                tracing::warn!("dashboard.stale_after must be greater than zero");
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Invalid dashboard.stale_after: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Whether to run git maintenance in the background after merges and removals.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GitMaintenance {
+    /// Run `git maintenance run --auto`, which only does work once git's own
+    /// thresholds (loose objects, pack count) are exceeded
+    #[default]
+    Auto,
+    Off,
+}
+
+/// A git config value. YAML booleans and numbers are accepted as-is.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum GitConfigValue {
+    Bool(bool),
+    Int(i64),
+    String(String),
+}
+
+impl std::fmt::Display for GitConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitConfigValue::Bool(b) => write!(f, "{}", b),
+            GitConfigValue::Int(n) => write!(f, "{}", n),
+            GitConfigValue::String(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Git-related settings
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct GitConfig {
+    /// Background maintenance after merges and worktree removals. Default: auto
+    pub maintenance: Option<GitMaintenance>,
+
+    /// Git config applied to each new worktree only (`git config --worktree`),
+    /// keyed by name, e.g. `user.email`
+    pub config: Option<BTreeMap<String, GitConfigValue>>,
+}
+
+impl GitConfig {
+    pub fn maintenance(&self) -> GitMaintenance {
+        self.maintenance.unwrap_or_default()
+    }
+
+    /// Per-worktree git config as (key, value) pairs
+    pub fn worktree_config(&self) -> Vec<(String, String)> {
+        self.config
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.clone(), value.to_string()))
+            .collect()
+    }
+}
+
+/// Retention of `workmux run` artifacts
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct RunsConfig {
+    /// Remove run directories older than this many days (0 = never). Default: 7
+    pub max_age_days: Option<u64>,
+
+    /// Keep at most this many finished run directories (0 = unlimited). Default: 50
+    pub max_count: Option<usize>,
+}
+
+impl RunsConfig {
+    pub fn retention(&self) -> crate::state::run::RunRetention {
+        let max_age_days = self.max_age_days.unwrap_or(7);
+        let max_count = self.max_count.unwrap_or(50);
+        crate::state::run::RunRetention {
+            max_age: (max_age_days > 0)
+                .then(|| std::time::Duration::from_secs(max_age_days * 24 * 60 * 60)),
+            max_count: (max_count > 0).then_some(max_count),
+        }
+    }
+}
+
+/// Redaction of secrets from logs and `workmux run` output
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct RedactConfig {
+    /// Regexes redacted on top of the built-in token patterns. With a
+    /// `secret` capture group only that part is replaced.
+    pub patterns: Option<Vec<String>>,
+}
+
+/// Caps on concurrently running agents
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct LimitsConfig {
+    /// Most agents running at once across all repositories
+    pub max_agents: Option<usize>,
+
+    /// Most agents running at once in one repository
+    pub max_agents_per_repo: Option<usize>,
+
+    /// What `add` and `open` do when a limit is reached. Default: refuse
+    pub when_full: Option<WhenFull>,
+}
+
+/// What `add` and `open` do when an agent limit is reached
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WhenFull {
+    /// Fail with an error
+    #[default]
+    Refuse,
+    /// Wait until an agent finishes
+    Queue,
+}
+
+impl LimitsConfig {
+    pub fn is_set(&self) -> bool {
+        self.max_agents.is_some() || self.max_agents_per_repo.is_some()
+    }
+
+    pub fn when_full(&self) -> WhenFull {
+        self.when_full.unwrap_or_default()
+    }
+
+    /// Which limit, if any, `total` agents running (`in_repo` of them in
+    /// this repository) already reach.
+    pub fn reached(&self, total: usize, in_repo: usize) -> Option<String> {
+        if let Some(max) = self.max_agents_per_repo
+            && in_repo >= max
+        {
+            return Some(format!(
+                "{} running in this repository (limits.max_agents_per_repo: {})",
+                in_repo, max
+            ));
+        }
+        if let Some(max) = self.max_agents
+            && total >= max
+        {
+            return Some(format!(
+                "{} running across all repositories (limits.max_agents: {})",
+                total, max
+            ));
+        }
+        None
+    }
+}
+
+/// Issue tracker for `workmux add --ticket`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct TrackerConfig {
+    /// Which tracker to talk to. Global-only.
+    pub backend: Option<TrackerBackend>,
+
+    /// Base URL of the Jira site, e.g. https://acme.atlassian.net. Global-only.
+    pub url: Option<String>,
+
+    /// Jira Cloud account email, sent with the token. Global-only.
+    pub email: Option<String>,
+
+    /// Secret reference for the API token, as in `secrets` (e.g. `keychain:jira`).
+    /// Default: JIRA_API_TOKEN or LINEAR_API_KEY. Global-only.
+    pub token: Option<String>,
+
+    /// Branch name template. Default: `{{ key | lower }}-{{ slug }}`
+    pub branch: Option<String>,
+
+    /// Prompt template seeded from the ticket
+    pub prompt: Option<String>,
+
+    /// Comment on the ticket when the branch's pull request opens. Default: true
+    pub link_pr: Option<bool>,
+}
+
+/// A supported issue tracker
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerBackend {
+    Jira,
+    Linear,
+}
+
+/// Checks `workmux merge` makes before merging
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct MergePolicyConfig {
+    /// Require an open pull request for the branch
+    pub require_pr: Option<bool>,
+
+    /// Require the pull request's checks to have passed
+    pub require_checks: Option<bool>,
+
+    /// Require `--approve` on the command line
+    pub require_approval: Option<bool>,
+
+    /// Target branches (glob patterns) that can't be merged into
+    pub protected_branches: Option<Vec<String>>,
+}
+
+impl MergePolicyConfig {
+    pub fn require_pr(&self) -> bool {
+        self.require_pr.unwrap_or(false) || self.require_checks()
+    }
+
+    pub fn require_checks(&self) -> bool {
+        self.require_checks.unwrap_or(false)
+    }
+
+    pub fn require_approval(&self) -> bool {
+        self.require_approval.unwrap_or(false)
+    }
+
+    /// Whether merging into `branch` is forbidden.
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.protected_branches.iter().flatten().any(|pattern| {
+            glob::Pattern::new(pattern).map_or(pattern == branch, |p| p.matches(branch))
+        })
+    }
 }
 
 /// Configuration for the sidebar.
@@ -274,6 +898,23 @@ pub struct Config {
     #[serde(default)]
     pub merge_strategy: Option<MergeStrategy>,
 
+    /// Require every commit merged by `workmux merge` to be signed, and sign
+    /// merge/squash commits even when `commit.gpgsign` is not set. Default: false
+    #[serde(default)]
+    pub require_signed_commits: Option<bool>,
+
+    /// Checks `workmux merge` makes before merging
+    #[serde(default)]
+    pub merge_policy: MergePolicyConfig,
+
+    /// Caps on concurrently running agents
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// Issue tracker for `workmux add --ticket`
+    #[serde(default)]
+    pub tracker: TrackerConfig,
+
     /// Strategy for deriving worktree/window names from branch names
     #[serde(default)]
     pub worktree_naming: WorktreeNaming,
@@ -295,6 +936,10 @@ pub struct Config {
     #[serde(default)]
     pub status_icons: StatusIcons,
 
+    /// Desktop notifications for agent status changes
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
     /// Configuration for LLM-based branch name generation
     #[serde(default)]
     pub auto_name: Option<AutoNameConfig>,
@@ -307,6 +952,18 @@ pub struct Config {
     #[serde(default)]
     pub sidebar: SidebarConfig,
 
+    /// Git-related settings
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Retention of `workmux run` artifacts
+    #[serde(default)]
+    pub runs: RunsConfig,
+
+    /// Extra secret patterns to redact
+    #[serde(default)]
+    pub redact: RedactConfig,
+
     /// Whether to use nerdfont icons (None = prompt user on first run)
     #[serde(default)]
     pub nerdfont: Option<bool>,
@@ -320,6 +977,16 @@ pub struct Config {
     #[serde(default)]
     pub mode: Option<MuxMode>,
 
+    /// Which tmux session worktree windows go in (window mode only):
+    /// single (default), per-repo or per-branch-prefix
+    #[serde(default)]
+    pub session_strategy: Option<SessionStrategy>,
+
+    /// Which multiplexer wins when workmux runs inside more than one, e.g.
+    /// tmux inside WezTerm. Global-only.
+    #[serde(default)]
+    pub backend_priority: Option<Vec<BackendType>>,
+
     /// Automatically check for updates in the background. Default: true
     #[serde(default)]
     pub auto_update_check: Option<bool>,
@@ -334,6 +1001,12 @@ pub struct Config {
     #[serde(default)]
     pub agents: BTreeMap<String, AgentEntry>,
 
+    /// Environment variables for agent panes and sandbox guests, mapped to
+    /// secret references (`keychain:`, `pass:`, `op://`, `env-file:`).
+    /// Global-only for security.
+    #[serde(default)]
+    pub secrets: BTreeMap<String, String>,
+
     /// Resolved agent type override from the agents map.
     /// Set internally during config loading, not deserialized.
     #[serde(skip)]
@@ -427,6 +1100,15 @@ pub struct PaneConfig {
     /// Implies `focus: true`.
     #[serde(default)]
     pub zoom: bool,
+
+    /// Environment variables set in this pane only.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// Directory the pane starts in, relative to the worktree unless absolute.
+    /// `~` expands to the home directory. Default: the worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
 }
 
 /// A named pane layout, selectable with `-l/--layout` at add-time.
@@ -487,10 +1169,13 @@ pub enum ThemeScheme {
     GraphiteCode,
     FestivalCircuit,
     TealDrift,
+    /// Terminal default colors only, with reverse video for the selection
+    NoColor,
+    HighContrast,
 }
 
 impl ThemeScheme {
-    pub const ALL: [ThemeScheme; 12] = [
+    pub const ALL: [ThemeScheme; 14] = [
         ThemeScheme::Default,
         ThemeScheme::Emberforge,
         ThemeScheme::GlacierSignal,
@@ -503,6 +1188,8 @@ impl ThemeScheme {
         ThemeScheme::GraphiteCode,
         ThemeScheme::FestivalCircuit,
         ThemeScheme::TealDrift,
+        ThemeScheme::NoColor,
+        ThemeScheme::HighContrast,
     ];
 
     pub fn next(self) -> Self {
@@ -525,6 +1212,8 @@ impl ThemeScheme {
             ThemeScheme::GraphiteCode => "Graphite Code",
             ThemeScheme::FestivalCircuit => "Festival Circuit",
             ThemeScheme::TealDrift => "Teal Drift",
+            ThemeScheme::NoColor => "No Color",
+            ThemeScheme::HighContrast => "High Contrast",
         }
     }
 
@@ -542,6 +1231,8 @@ impl ThemeScheme {
             ThemeScheme::GraphiteCode => "graphite-code",
             ThemeScheme::FestivalCircuit => "festival-circuit",
             ThemeScheme::TealDrift => "teal-drift",
+            ThemeScheme::NoColor => "no-color",
+            ThemeScheme::HighContrast => "high-contrast",
         }
     }
 
@@ -560,6 +1251,7 @@ impl<'de> serde::Deserialize<'de> for ThemeScheme {
 /// Custom color overrides for the theme palette.
 /// Each field corresponds to a `ThemePalette` field and accepts a CSS hex color (e.g. "#51afef").
 /// Shorthand aliases: `bg` for `current_row_bg`, `fg` for `text`, `error` for `danger`.
+/// The status, stale and check colors default to the semantic color they're drawn with.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct CustomThemeColors {
     #[serde(default, alias = "bg")]
@@ -592,6 +1284,20 @@ pub struct CustomThemeColors {
     pub danger: Option<String>,
     #[serde(default)]
     pub accent: Option<String>,
+    #[serde(default)]
+    pub working: Option<String>,
+    #[serde(default)]
+    pub waiting: Option<String>,
+    #[serde(default)]
+    pub done: Option<String>,
+    #[serde(default)]
+    pub stale: Option<String>,
+    #[serde(default)]
+    pub check_success: Option<String>,
+    #[serde(default)]
+    pub check_failure: Option<String>,
+    #[serde(default)]
+    pub check_pending: Option<String>,
 }
 
 /// Theme configuration: scheme + optional mode override + custom color overrides.
@@ -692,6 +1398,44 @@ pub enum MuxMode {
     Session,
 }
 
+/// Which tmux session worktree windows are created in, in window mode
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionStrategy {
+    /// The session workmux is run from (default)
+    #[default]
+    Single,
+    /// One session per repository, named `<prefix><repo>`
+    PerRepo,
+    /// One session per branch prefix, named `<prefix><repo>-<prefix>`, so
+    /// `frontend/login` and `backend/api` land in separate sessions.
+    /// Branches without a `/` go in the per-repo session.
+    PerBranchPrefix,
+}
+
+impl SessionStrategy {
+    /// The session to create a worktree's window in, or None for the
+    /// current session.
+    pub fn session_name(self, window_prefix: &str, repo: &str, branch: &str) -> Option<String> {
+        let repo_session = format!("{}{}", window_prefix, sanitize_session_name(repo));
+        match self {
+            SessionStrategy::Single => None,
+            SessionStrategy::PerRepo => Some(repo_session),
+            SessionStrategy::PerBranchPrefix => match branch.split_once('/') {
+                Some((group, _)) if !group.is_empty() => {
+                    Some(format!("{}-{}", repo_session, sanitize_session_name(group)))
+                }
+                _ => Some(repo_session),
+            },
+        }
+    }
+}
+
+/// tmux treats `.` and `:` in session names as target separators.
+fn sanitize_session_name(name: &str) -> String {
+    name.replace(['.', ':'], "-")
+}
+
 /// Strategy for deriving worktree/window names from branch names
 #[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -1478,6 +2222,25 @@ impl SandboxConfig {
         self.host_commands.as_deref().unwrap_or(&[])
     }
 
+    /// Keep only the host commands also in `subset`, returning the commands in
+    /// `subset` that aren't allowed to begin with. Never widens the list.
+    pub fn restrict_host_commands(&mut self, subset: &[String]) -> Vec<String> {
+        let allowed = self.host_commands();
+        let denied = subset
+            .iter()
+            .filter(|cmd| !allowed.contains(cmd))
+            .cloned()
+            .collect();
+        self.host_commands = Some(
+            allowed
+                .iter()
+                .filter(|cmd| subset.contains(cmd))
+                .cloned()
+                .collect(),
+        );
+        denied
+    }
+
     pub fn extra_mounts(&self) -> &[ExtraMount] {
         self.extra_mounts.as_deref().unwrap_or(&[])
     }
@@ -1679,6 +2442,18 @@ pub fn validate_panes_config(panes: &[PaneConfig]) -> anyhow::Result<()> {
                 i.saturating_sub(1)
             );
         }
+
+        if let Some(key) = pane
+            .env
+            .keys()
+            .find(|k| !crate::shell::is_valid_env_name(k))
+        {
+            anyhow::bail!(
+                "Pane {} has invalid environment variable name '{}' in 'env'.",
+                i,
+                key
+            );
+        }
     }
 
     // Only one pane can have zoom
@@ -1742,6 +2517,28 @@ pub fn global_config_path() -> Option<PathBuf> {
 }
 
 impl Config {
+    /// Use the named layout's panes in place of `panes` and `windows`.
+    pub fn apply_layout(&mut self, name: &str) -> anyhow::Result<()> {
+        let layouts = self.layouts.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Layout '{}' requested but no layouts are defined in config",
+                name
+            )
+        })?;
+        let layout = layouts.get(name).ok_or_else(|| {
+            let mut available: Vec<_> = layouts.keys().map(|s| s.as_str()).collect();
+            available.sort();
+            anyhow::anyhow!(
+                "Layout '{}' not found. Available layouts: {}",
+                name,
+                available.join(", ")
+            )
+        })?;
+        self.panes = Some(layout.panes.clone());
+        self.windows = None; // Layout overrides session windows
+        Ok(())
+    }
+
     /// Load and merge global and project configurations.
     pub fn load(cli_agent: Option<&str>) -> anyhow::Result<Self> {
         Self::load_with_override(cli_agent, None)
@@ -1778,6 +2575,7 @@ impl Config {
         cli_agent: Option<&str>,
         config_override: Option<&Path>,
     ) -> anyhow::Result<(Self, Option<ConfigLocation>)> {
+        let _timer = crate::timings::phase(crate::timings::Phase::Config);
         debug!(start_dir = %start_dir.display(), "config:loading with location from");
         let global_config = Self::load_global()?.unwrap_or_default();
 
@@ -1838,6 +2636,8 @@ impl Config {
             &defaults_root,
         );
 
+        crate::redact::configure(config.redact.patterns.as_deref().unwrap_or_default());
+
         debug!(
             agent = ?config.agent,
             has_location = location.is_some(),
@@ -1905,14 +2705,31 @@ impl Config {
     }
 
     /// Load configuration from a specific path.
+    ///
+    /// Parsed configs are memoized per process by path and contents, so the
+    /// repeated loads nested commands do skip YAML parsing, while edits made
+    /// mid-command (e.g. by `init`) are still picked up.
     fn load_from_path(path: &Path) -> anyhow::Result<Option<Self>> {
+        static PARSED: LazyLock<Mutex<HashMap<PathBuf, (String, Config)>>> =
+            LazyLock::new(|| Mutex::new(HashMap::new()));
+
         if !path.exists() {
             return Ok(None);
         }
-        debug!(path = %path.display(), "config:reading file");
         let contents = fs::read_to_string(path)?;
+        if let Ok(parsed) = PARSED.lock()
+            && let Some((cached, config)) = parsed.get(path)
+            && *cached == contents
+        {
+            return Ok(Some(config.clone()));
+        }
+
+        debug!(path = %path.display(), "config:reading file");
         let config: Config = serde_yaml::from_str(&contents)
             .map_err(|e| anyhow::anyhow!("Failed to parse config at {}: {}", path.display(), e))?;
+        if let Ok(mut parsed) = PARSED.lock() {
+            parsed.insert(path.to_path_buf(), (contents, config.clone()));
+        }
         Ok(Some(config))
     }
 
@@ -1920,7 +2737,7 @@ impl Config {
     ///
     /// Uses `global_config_path()` which resolves via XDG_CONFIG_HOME with
     /// legacy fallback.
-    fn load_global() -> anyhow::Result<Option<Self>> {
+    pub fn load_global() -> anyhow::Result<Option<Self>> {
         if let Some(path) = global_config_path()
             && path.exists()
         {
@@ -1934,17 +2751,18 @@ impl Config {
     fn merge(self, project: Self) -> Self {
         /// Merge vectors with "<global>" placeholder expansion.
         /// When project contains "<global>", it expands to global items at that position.
-        fn merge_vec_with_placeholder(
-            global: Option<Vec<String>>,
-            project: Option<Vec<String>>,
-        ) -> Option<Vec<String>> {
+        fn merge_vec_with_placeholder<T: Clone + PartialEq + From<&'static str>>(
+            global: Option<Vec<T>>,
+            project: Option<Vec<T>>,
+        ) -> Option<Vec<T>> {
+            let placeholder = T::from("<global>");
             match (global, project) {
                 (Some(global_items), Some(project_items)) => {
-                    let has_placeholder = project_items.iter().any(|s| s == "<global>");
+                    let has_placeholder = project_items.iter().any(|s| *s == placeholder);
                     if has_placeholder {
                         let mut result = Vec::new();
                         for item in project_items {
-                            if item == "<global>" {
+                            if item == placeholder {
                                 result.extend(global_items.clone());
                             } else {
                                 result.push(item);
@@ -1983,6 +2801,7 @@ impl Config {
             window_prefix,
             agent,
             merge_strategy,
+            require_signed_commits,
             worktree_prefix,
             panes,
             windows,
@@ -2067,6 +2886,9 @@ impl Config {
 
         // Special case: mode (project wins if explicitly set)
         merged.mode = project.mode.or(self.mode);
+        merged.session_strategy = project.session_strategy.or(self.session_strategy);
+        // Global-only: the backend is detected before the project config loads
+        merged.backend_priority = self.backend_priority;
 
         // List values with "<global>" placeholder support
         merged.post_create = merge_vec_with_placeholder(self.post_create, project.post_create);
@@ -2077,6 +2899,8 @@ impl Config {
         merged.files = FileConfig {
             copy: merge_vec_with_placeholder(self.files.copy, project.files.copy),
             symlink: merge_vec_with_placeholder(self.files.symlink, project.files.symlink),
+            include: merge_vec_with_placeholder(self.files.include, project.files.include),
+            exclude: merge_vec_with_placeholder(self.files.exclude, project.files.exclude),
         };
 
         // Status icons: per-field override
@@ -2094,16 +2918,208 @@ impl Config {
                 .dashboard
                 .preview_size
                 .or(self.dashboard.preview_size),
+            preview_lines: project
+                .dashboard
+                .preview_lines
+                .or(self.dashboard.preview_lines),
             show_check_counts: project
                 .dashboard
                 .show_check_counts
                 .or(self.dashboard.show_check_counts),
+            stall_minutes: project
+                .dashboard
+                .stall_minutes
+                .or(self.dashboard.stall_minutes),
+            stale_after: project.dashboard.stale_after.or(self.dashboard.stale_after),
+            columns: project.dashboard.columns.or(self.dashboard.columns),
+        };
+
+        // Sidebar config: per-field override
+        merged.sidebar = SidebarConfig {
+            width: project.sidebar.width.or(self.sidebar.width),
+            layout: project.sidebar.layout.or(self.sidebar.layout),
+        };
+
+        // Git settings: per-field override
+        // Git config: per-field override, per-key for worktree config
+        merged.git = GitConfig {
+            maintenance: project.git.maintenance.or(self.git.maintenance),
+            config: match (self.git.config, project.git.config) {
+                (Some(mut global), Some(project)) => {
+                    global.extend(project);
+                    Some(global)
+                }
+                (global, project) => project.or(global),
+            },
+        };
+
+        // Notifications: per-field override
+        merged.notifications = NotificationsConfig {
+            waiting: project.notifications.waiting.or(self.notifications.waiting),
+            done: project.notifications.done.or(self.notifications.done),
+            sound: project.notifications.sound.or(self.notifications.sound),
+            sounds: SoundsConfig {
+                waiting: project
+                    .notifications
+                    .sounds
+                    .waiting
+                    .or(self.notifications.sounds.waiting),
+                done: project
+                    .notifications
+                    .sounds
+                    .done
+                    .or(self.notifications.sounds.done),
+                failed: project
+                    .notifications
+                    .sounds
+                    .failed
+                    .or(self.notifications.sounds.failed),
+            },
+            quiet_hours: project
+                .notifications
+                .quiet_hours
+                .or(self.notifications.quiet_hours),
+            // Security: webhooks are global-only so a repo's .workmux.yaml
+            // can't send agent activity to an endpoint of its choosing.
+            webhooks: {
+                if project.notifications.webhooks.is_some() {
+                    tracing::warn!(
+                        "notifications.webhooks in project config (.workmux.yaml) is ignored -- \
+                        move it to your global config (~/.config/workmux/config.yaml)"
+                    );
+                }
+                self.notifications.webhooks
+            },
+            // Security: same as webhooks, and the token is a secret
+            ntfy: {
+                if project.notifications.ntfy.is_some() {
+                    tracing::warn!(
+                        "notifications.ntfy in project config (.workmux.yaml) is ignored -- \
+                        move it to your global config (~/.config/workmux/config.yaml)"
+                    );
+                }
+                self.notifications.ntfy
+            },
+            // Security: holds SMTP credentials and decides where mail goes
+            email: {
+                if project.notifications.email.is_some() {
+                    tracing::warn!(
+                        "notifications.email in project config (.workmux.yaml) is ignored -- \
+                        move it to your global config (~/.config/workmux/config.yaml)"
+                    );
+                }
+                self.notifications.email
+            },
+            escalation: EscalationConfig {
+                minutes: project
+                    .notifications
+                    .escalation
+                    .minutes
+                    .or(self.notifications.escalation.minutes),
+                via: project
+                    .notifications
+                    .escalation
+                    .via
+                    .or(self.notifications.escalation.via),
+            },
+            osc: OscConfig {
+                title: project
+                    .notifications
+                    .osc
+                    .title
+                    .or(self.notifications.osc.title),
+                notify: project
+                    .notifications
+                    .osc
+                    .notify
+                    .or(self.notifications.osc.notify),
+                badge: project
+                    .notifications
+                    .osc
+                    .badge
+                    .or(self.notifications.osc.badge),
+            },
+        };
+
+        // Merge policy: global and project policies both apply, so a
+        // worktree's .workmux.yaml can add checks but not remove them
+        merged.merge_policy = MergePolicyConfig {
+            require_pr: self
+                .merge_policy
+                .require_pr
+                .max(project.merge_policy.require_pr),
+            require_checks: self
+                .merge_policy
+                .require_checks
+                .max(project.merge_policy.require_checks),
+            require_approval: self
+                .merge_policy
+                .require_approval
+                .max(project.merge_policy.require_approval),
+            protected_branches: match (
+                self.merge_policy.protected_branches,
+                project.merge_policy.protected_branches,
+            ) {
+                (Some(mut global), Some(project)) => {
+                    global.extend(project);
+                    Some(global)
+                }
+                (global, project) => project.or(global),
+            },
+        };
+
+        // Agent limits: the lower limit wins, so a project can tighten but
+        // not lift a global cap
+        let lower = |global: Option<usize>, project: Option<usize>| match (global, project) {
+            (Some(global), Some(project)) => Some(global.min(project)),
+            (global, project) => project.or(global),
+        };
+        merged.limits = LimitsConfig {
+            max_agents: lower(self.limits.max_agents, project.limits.max_agents),
+            max_agents_per_repo: lower(
+                self.limits.max_agents_per_repo,
+                project.limits.max_agents_per_repo,
+            ),
+            when_full: project.limits.when_full.or(self.limits.when_full),
+        };
+
+        // Tracker: the connection (and its token) is global-only, so a
+        // project config can't point it elsewhere; naming is per-project
+        if project.tracker.backend.is_some()
+            || project.tracker.url.is_some()
+            || project.tracker.email.is_some()
+            || project.tracker.token.is_some()
+        {
+            tracing::warn!(
+                "tracker.backend, url, email and token in project config (.workmux.yaml) are \
+                ignored -- move them to your global config (~/.config/workmux/config.yaml)"
+            );
+        }
+        merged.tracker = TrackerConfig {
+            backend: self.tracker.backend,
+            url: self.tracker.url,
+            email: self.tracker.email,
+            token: self.tracker.token,
+            branch: project.tracker.branch.or(self.tracker.branch),
+            prompt: project.tracker.prompt.or(self.tracker.prompt),
+            link_pr: project.tracker.link_pr.or(self.tracker.link_pr),
         };
 
-        // Sidebar config: per-field override
-        merged.sidebar = SidebarConfig {
-            width: project.sidebar.width.or(self.sidebar.width),
-            layout: project.sidebar.layout.or(self.sidebar.layout),
+        // Run retention: per-field override
+        merged.runs = RunsConfig {
+            max_age_days: project.runs.max_age_days.or(self.runs.max_age_days),
+            max_count: project.runs.max_count.or(self.runs.max_count),
+        };
+
+        // Redaction patterns: global and project both apply
+        merged.redact = RedactConfig {
+            patterns: match (self.redact.patterns, project.redact.patterns) {
+                (Some(mut global), Some(project)) => {
+                    global.extend(project);
+                    Some(global)
+                }
+                (global, project) => project.or(global),
+            },
         };
 
         // Sandbox config: per-field override with nested struct merging
@@ -2249,6 +3265,16 @@ impl Config {
             self.agents
         };
 
+        // Security: secrets is global-only. A project config could otherwise
+        // read any secret the user can access into the agent's environment.
+        if !project.secrets.is_empty() {
+            tracing::warn!(
+                "secrets in project config (.workmux.yaml) is ignored -- \
+                move it to your global config (~/.config/workmux/config.yaml)"
+            );
+        }
+        merged.secrets = self.secrets;
+
         merged
     }
 
@@ -2296,36 +3322,27 @@ impl Config {
         }
     }
 
+    /// Whether agent-produced commits must be signed before merging.
+    pub fn require_signed_commits(&self) -> bool {
+        self.require_signed_commits.unwrap_or(false)
+    }
+
     /// Get the mode (window or session).
     /// Returns the configured value or defaults to Window.
     pub fn mode(&self) -> MuxMode {
         self.mode.unwrap_or(MuxMode::Window)
     }
 
-    /// Create an example .workmux.yaml configuration file
-    pub fn init() -> anyhow::Result<()> {
-        use std::path::PathBuf;
-
-        let config_path = PathBuf::from(".workmux.yaml");
-
-        if config_path.exists() {
-            return Err(anyhow::anyhow!(
-                ".workmux.yaml already exists. Remove it first if you want to regenerate it."
-            ));
+    /// The session a worktree's window goes in, or None for the current
+    /// session. Always None in session mode, where each worktree gets its
+    /// own session.
+    pub fn window_session(&self, mode: MuxMode, repo: &str, branch: &str) -> Option<String> {
+        if mode == MuxMode::Session {
+            return None;
         }
-
-        fs::write(&config_path, EXAMPLE_PROJECT_CONFIG)?;
-
-        println!("✓ Created .workmux.yaml");
-        println!("\nThis file provides project-specific overrides.");
-        println!(
-            "For global settings, edit {}",
-            global_config_path()
-                .map(|p| p.display().to_string())
-                .unwrap_or_else(|| "~/.config/workmux/config.yaml".to_string())
-        );
-
-        Ok(())
+        self.session_strategy
+            .unwrap_or_default()
+            .session_name(self.window_prefix(), repo, branch)
     }
 }
 
@@ -2339,16 +3356,24 @@ pub const EXAMPLE_PROJECT_CONFIG: &str = r#"# workmux project configuration
 # Appearance
 #-------------------------------------------------------------------------------
 
-# Color scheme for the dashboard. Press T (shift+t) in the dashboard to cycle.
+# Color scheme for the dashboard and `workmux list`. Press T (shift+t) in the
+# dashboard to cycle.
 # Options: default, emberforge, glacier-signal, obsidian-pop, slate-garden,
 #          phosphor-arcade, lasergrid, mossfire, night-sorbet, graphite-code,
-#          festival-circuit, teal-drift
+#          festival-circuit, teal-drift, no-color, high-contrast
 # theme: default
 #
 # Or with explicit dark/light mode (otherwise auto-detected from terminal):
 # theme:
 #   scheme: emberforge
 #   mode: dark
+#
+# Individual colors can be overridden, including statuses and PR checks:
+# theme:
+#   custom:
+#     waiting: yellow
+#     stale: darkgray
+#     check_failure: red
 
 #-------------------------------------------------------------------------------
 # Git
@@ -2368,6 +3393,22 @@ pub const EXAMPLE_PROJECT_CONFIG: &str = r#"# workmux project configuration
 # CLI flags (--rebase, --squash) always override this.
 # merge_strategy: rebase
 
+# Refuse to merge branches containing unsigned commits, and always sign the
+# merge/squash commits workmux creates. Uses your git signing setup
+# (commit.gpgsign, gpg.format, user.signingkey).
+# Default: false
+# require_signed_commits: true
+
+# Checks `workmux merge` makes before merging, for teams that want a gate on
+# agent-driven merges. Policies in the global and project config both apply.
+# merge_policy:
+#   require_pr: true              # the branch has an open pull request
+#   require_checks: true          # its checks have passed (implies require_pr)
+#   require_approval: true        # `workmux merge --approve` was passed
+#   protected_branches:           # never merge into these (glob patterns)
+#     - main
+#     - release/*
+
 #-------------------------------------------------------------------------------
 # Naming & Paths
 #-------------------------------------------------------------------------------
@@ -2399,6 +3440,13 @@ pub const EXAMPLE_PROJECT_CONFIG: &str = r#"# workmux project configuration
 # - session: Create new tmux sessions for each worktree (useful for session-per-project workflows)
 # mode: session
 
+# Which session worktree windows go in (window mode only).
+# - single: the session you run workmux from (default)
+# - per-repo: one session per repository, named <prefix><repo>
+# - per-branch-prefix: one session per branch prefix, e.g. frontend/login and
+#   backend/api go in <prefix><repo>-frontend and <prefix><repo>-backend
+# session_strategy: per-repo
+
 # Custom tmux pane layout (mutually exclusive with 'windows').
 # Default: Two-pane layout with shell and clear command.
 # panes:
@@ -2503,6 +3551,81 @@ pub const EXAMPLE_PROJECT_CONFIG: &str = r#"# workmux project configuration
 #   symlink:
 #     - "<global>"
 #     - node_modules
+#
+#   # Rules with their own mode (copy or symlink) and exclusions.
+#   include:
+#     - certs/
+#     - path: .env
+#       mode: symlink
+#     - path: fixtures/
+#       exclude: ["*.tmp"]
+#
+#   # Gitignore-style patterns skipped by all file operations, including
+#   # files inside copied directories.
+#   exclude:
+#     - "*.log"
+
+#-------------------------------------------------------------------------------
+# Git
+#-------------------------------------------------------------------------------
+
+# git:
+#   # Run `git maintenance run --auto` in the background after merges and
+#   # worktree removals, so repos with many short-lived branches stay fast.
+#   # Default: auto. Set to "off" to disable.
+#   maintenance: auto
+#
+#   # Git config applied to each new worktree only, leaving the main worktree
+#   # and global config untouched. Project keys override global ones.
+#   config:
+#     user.email: agent@example.com
+#     core.hooksPath: .githooks
+
+#-------------------------------------------------------------------------------
+# Agent limits
+#-------------------------------------------------------------------------------
+
+# Cap how many agents run at once, so a runaway script can't start dozens of
+# sessions. Counts open workmux windows (sessions in session mode). When a
+# limit is reached, `add` and `open` refuse, or with `when_full: queue` wait
+# until an agent's window closes.
+# limits:
+#   max_agents: 8
+#   max_agents_per_repo: 4
+#   when_full: queue
+
+#-------------------------------------------------------------------------------
+# Issue tracker
+#-------------------------------------------------------------------------------
+
+# `workmux add --ticket PROJ-123` fetches the ticket, names the branch after
+# it and seeds the prompt with its description. Once the branch has an open
+# pull request, a comment linking it is posted on the ticket. The backend,
+# url, email and token are only read from this global config.
+# tracker:
+#   backend: jira            # or linear
+#   url: https://acme.atlassian.net
+#   email: me@acme.com
+#   token: keychain:jira-token  # default: $JIRA_API_TOKEN / $LINEAR_API_KEY
+#   # Ticket fields: key, title, description, url, type (Jira), slug
+#   branch: "{{ type | lower }}/{{ key }}-{{ slug }}"
+#   prompt: |
+#     Fix {{ key }}: {{ title }}
+#
+#     {{ description }}
+#   link_pr: true
+
+#-------------------------------------------------------------------------------
+# Redaction
+#-------------------------------------------------------------------------------
+
+# Common token formats (GitHub, API keys, AWS, passwords in KEY=value form)
+# are always redacted from logs and `workmux run` output. Add your own regexes
+# here; with a `secret` capture group only that part is replaced.
+# redact:
+#   patterns:
+#     - "internal-[0-9a-f]{32}"
+#     - "session_id=(?P<secret>\\S+)"
 
 #-------------------------------------------------------------------------------
 # Dashboard
@@ -2515,6 +3638,16 @@ pub const EXAMPLE_PROJECT_CONFIG: &str = r#"# workmux project configuration
 #   commit: "Commit staged changes with a descriptive message"
 #   merge: "!workmux merge"
 #   preview_size: 60
+#   # Lines of agent output kept in the preview, for scrolling back
+#   preview_lines: 200
+#   # Flag working agents whose output hasn't changed for this many minutes
+#   stall_minutes: 15
+#   # Dim agents (and hide them with the f filter) after this long without a
+#   # status change
+#   stale_after: 1h
+#   # Agents table columns, in order. Options: project, worktree, branch, git,
+#   # pr, status, elapsed, title
+#   columns: [status, project, branch, title, elapsed, pr]
 
 #-------------------------------------------------------------------------------
 # Sidebar
@@ -2663,13 +3796,16 @@ pub fn is_agent_command(command_line: &str, agent_command: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
 
     use super::{
-        Config, ContainerConfig, ContainerDevice, ExtraMount, LayoutConfig, LimaConfig,
-        NetworkConfig, NetworkPolicy, PaneConfig, SandboxConfig, SandboxRuntime, SandboxTarget,
-        SplitDirection, ToolchainMode, is_agent_command, split_first_token, validate_domain,
-        validate_group_add_entry, validate_layouts_config,
+        AgentStatus, BackendType, Config, ContainerConfig, ContainerDevice, DashboardColumn,
+        DashboardConfig, ExtraMount, FileConfig, FileMode, FileRule, GitConfig, GitMaintenance,
+        LayoutConfig, LimaConfig, LimitsConfig, MergePolicyConfig, MuxMode, NetworkConfig,
+        NetworkPolicy, NotificationEvent, PaneConfig, SandboxConfig, SandboxRuntime, SandboxTarget,
+        SessionStrategy, SplitDirection, ToolchainMode, TrackerBackend, TrackerConfig,
+        WebhookConfig, WebhookFormat, WhenFull, is_agent_command, split_first_token,
+        validate_domain, validate_group_add_entry, validate_layouts_config,
     };
 
     #[test]
@@ -2840,6 +3976,22 @@ agents:
         assert_eq!(loc.rel_dir, std::path::PathBuf::from("backend"));
     }
 
+    #[test]
+    fn load_from_path_picks_up_edits() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".workmux.yaml");
+        fs::write(&path, "agent: claude").unwrap();
+        let first = Config::load_from_path(&path).unwrap().unwrap();
+        assert_eq!(first.agent.as_deref(), Some("claude"));
+
+        // Same contents are served from the memo; new contents are re-parsed
+        let again = Config::load_from_path(&path).unwrap().unwrap();
+        assert_eq!(again.agent.as_deref(), Some("claude"));
+        fs::write(&path, "agent: codex").unwrap();
+        let edited = Config::load_from_path(&path).unwrap().unwrap();
+        assert_eq!(edited.agent.as_deref(), Some("codex"));
+    }
+
     #[test]
     fn find_project_config_nearest_wins() {
         let temp = TempDir::new().unwrap();
@@ -2867,6 +4019,220 @@ agents:
         assert!(loc.config_path.ends_with("backend/.workmux.yaml"));
     }
 
+    #[test]
+    fn git_maintenance_defaults_to_auto() {
+        assert_eq!(Config::default().git.maintenance(), GitMaintenance::Auto);
+
+        let config: Config = serde_yaml::from_str("git:\n  maintenance: off\n").unwrap();
+        assert_eq!(config.git.maintenance(), GitMaintenance::Off);
+    }
+
+    #[test]
+    fn git_maintenance_merge() {
+        let global = Config {
+            git: GitConfig {
+                maintenance: Some(GitMaintenance::Off),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = global.clone().merge(Config::default());
+        assert_eq!(merged.git.maintenance(), GitMaintenance::Off);
+
+        let project = Config {
+            git: GitConfig {
+                maintenance: Some(GitMaintenance::Auto),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = global.merge(project);
+        assert_eq!(merged.git.maintenance(), GitMaintenance::Auto);
+    }
+
+    #[test]
+    fn notifications_are_opt_in_per_status() {
+        let config = Config::default();
+        assert!(!config.notifications.enabled_for(AgentStatus::Done));
+        assert!(config.notifications.sound());
+
+        let global: Config =
+            serde_yaml::from_str("notifications:\n  done: true\n  waiting: true\n").unwrap();
+        let project: Config = serde_yaml::from_str("notifications:\n  done: false\n").unwrap();
+        let merged = global.merge(project);
+        assert!(!merged.notifications.enabled_for(AgentStatus::Done));
+        assert!(merged.notifications.enabled_for(AgentStatus::Waiting));
+        assert!(!merged.notifications.enabled_for(AgentStatus::Working));
+    }
+
+    #[test]
+    fn webhooks_are_global_only_and_infer_format() {
+        let global: Config = serde_yaml::from_str(
+            "notifications:\n  webhooks:\n    - url: https://hooks.slack.com/services/x\n      events: [done]\n",
+        )
+        .unwrap();
+        let project: Config = serde_yaml::from_str(
+            "notifications:\n  webhooks:\n    - url: https://evil.example.com\n",
+        )
+        .unwrap();
+        let merged = global.merge(project);
+        let webhooks = merged.notifications.webhooks.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0].format(), WebhookFormat::Slack);
+        assert!(webhooks[0].wants(NotificationEvent::Done));
+        assert!(!webhooks[0].wants(NotificationEvent::Merged));
+
+        let discord: WebhookConfig =
+            serde_yaml::from_str("url: https://discord.com/api/webhooks/1/abc\n").unwrap();
+        assert_eq!(discord.format(), WebhookFormat::Discord);
+        assert!(discord.wants(NotificationEvent::Merged));
+    }
+
+    #[test]
+    fn sounds_merge_per_event() {
+        let global: Config = serde_yaml::from_str(
+            "notifications:\n  sounds:\n    waiting: Ping\n    done: Glass\n  quiet_hours: \"22:00-08:00\"\n",
+        )
+        .unwrap();
+        let project: Config =
+            serde_yaml::from_str("notifications:\n  sounds:\n    done: none\n").unwrap();
+        let notifications = global.merge(project).notifications;
+        assert_eq!(notifications.sounds.waiting.as_deref(), Some("Ping"));
+        assert_eq!(notifications.sounds.done.as_deref(), Some("none"));
+        assert_eq!(notifications.quiet_hours.as_deref(), Some("22:00-08:00"));
+    }
+
+    #[test]
+    fn ntfy_is_global_only() {
+        let global: Config = serde_yaml::from_str(
+            "notifications:\n  ntfy:\n    topic: my-agents\n    events: [waiting]\n",
+        )
+        .unwrap();
+        let project: Config =
+            serde_yaml::from_str("notifications:\n  ntfy:\n    topic: elsewhere\n").unwrap();
+        let ntfy = global.merge(project).notifications.ntfy.unwrap();
+        assert_eq!(ntfy.url(), "https://ntfy.sh/my-agents");
+        assert!(ntfy.wants(NotificationEvent::Waiting));
+        assert!(!ntfy.wants(NotificationEvent::Done));
+    }
+
+    #[test]
+    fn osc_sequences_are_off_by_default_and_merge_per_field() {
+        assert!(!Config::default().notifications.osc.any_enabled());
+
+        let global: Config =
+            serde_yaml::from_str("notifications:\n  osc:\n    title: true\n    badge: true\n")
+                .unwrap();
+        let project: Config =
+            serde_yaml::from_str("notifications:\n  osc:\n    badge: false\n").unwrap();
+        let osc = global.merge(project).notifications.osc;
+        assert!(osc.title());
+        assert!(!osc.badge());
+        assert!(!osc.notify());
+        assert!(osc.any_enabled());
+    }
+
+    #[test]
+    fn runs_retention_defaults_and_disabling() {
+        let retention = Config::default().runs.retention();
+        assert_eq!(
+            retention.max_age,
+            Some(std::time::Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        assert_eq!(retention.max_count, Some(50));
+
+        let global: Config = serde_yaml::from_str("runs:\n  max_age_days: 0\n").unwrap();
+        let project: Config = serde_yaml::from_str("runs:\n  max_count: 10\n").unwrap();
+        let retention = global.merge(project).runs.retention();
+        assert_eq!(retention.max_age, None);
+        assert_eq!(retention.max_count, Some(10));
+    }
+
+    #[test]
+    fn git_worktree_config_merges_per_key() {
+        let global: Config = serde_yaml::from_str(
+            "git:\n  config:\n    user.email: me@example.com\n    commit.gpgsign: true\n",
+        )
+        .unwrap();
+        let project: Config = serde_yaml::from_str(
+            "git:\n  config:\n    user.email: bot@example.com\n    core.hooksPath: .githooks\n",
+        )
+        .unwrap();
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.git.worktree_config(),
+            vec![
+                ("commit.gpgsign".to_string(), "true".to_string()),
+                ("core.hooksPath".to_string(), ".githooks".to_string()),
+                ("user.email".to_string(), "bot@example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_rules_parse_per_entry_mode() {
+        let yaml = r#"
+files:
+  copy: [.env.example]
+  symlink: [node_modules]
+  include:
+    - certs/
+    - path: .env
+      mode: symlink
+    - path: fixtures/
+      exclude: ["*.tmp"]
+  exclude: ["*.log"]
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let rules = config.files.rules();
+        let summary: Vec<_> = rules.iter().map(|r| (r.path(), r.mode())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (".env.example", FileMode::Copy),
+                ("node_modules", FileMode::Symlink),
+                ("certs/", FileMode::Copy),
+                (".env", FileMode::Symlink),
+                ("fixtures/", FileMode::Copy),
+            ]
+        );
+        assert_eq!(rules[4].exclude(), ["*.tmp".to_string()]);
+        assert_eq!(config.files.exclude, Some(vec!["*.log".to_string()]));
+    }
+
+    #[test]
+    fn file_include_merge_expands_global_placeholder() {
+        let global = Config {
+            files: FileConfig {
+                include: Some(vec![FileRule::from("shared/")]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let project = Config {
+            files: FileConfig {
+                include: Some(vec![
+                    FileRule::from("<global>"),
+                    FileRule::Rule {
+                        path: ".env".to_string(),
+                        mode: FileMode::Symlink,
+                        exclude: Vec::new(),
+                    },
+                ]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = global.merge(project);
+        let paths: Vec<_> = merged
+            .files
+            .rules()
+            .iter()
+            .map(|r| r.path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["shared/", ".env"]);
+    }
+
     #[test]
     fn sandbox_config_defaults() {
         let config = SandboxConfig::default();
@@ -3178,6 +4544,20 @@ agents:
         assert!(merged.sandbox.host_commands().is_empty());
     }
 
+    #[test]
+    fn test_sandbox_restrict_host_commands_never_widens() {
+        let mut sandbox = SandboxConfig {
+            host_commands: Some(vec!["just".to_string(), "cargo".to_string()]),
+            ..Default::default()
+        };
+        let denied = sandbox.restrict_host_commands(&["cargo".to_string(), "rm".to_string()]);
+        assert_eq!(sandbox.host_commands(), ["cargo".to_string()]);
+        assert_eq!(denied, vec!["rm".to_string()]);
+
+        sandbox.restrict_host_commands(&[]);
+        assert!(sandbox.host_commands().is_empty());
+    }
+
     #[test]
     fn test_sandbox_host_commands_uses_global() {
         let global = Config {
@@ -3427,6 +4807,19 @@ agents:
         );
     }
 
+    #[test]
+    fn secrets_are_global_only() {
+        let global: Config =
+            serde_yaml::from_str("secrets:\n  GITHUB_TOKEN: keychain:github\n").unwrap();
+        let project: Config =
+            serde_yaml::from_str("secrets:\n  AWS_SECRET_ACCESS_KEY: pass:aws\n").unwrap();
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.secrets.into_iter().collect::<Vec<_>>(),
+            vec![("GITHUB_TOKEN".to_string(), "keychain:github".to_string())]
+        );
+    }
+
     #[test]
     fn test_sandbox_env_passthrough_global_only() {
         // Project config is ignored -- only global matters
@@ -4555,10 +5948,12 @@ sandbox:
                 | ThemeScheme::NightSorbet
                 | ThemeScheme::GraphiteCode
                 | ThemeScheme::FestivalCircuit
-                | ThemeScheme::TealDrift => {}
+                | ThemeScheme::TealDrift
+                | ThemeScheme::NoColor
+                | ThemeScheme::HighContrast => {}
             }
         }
-        assert_eq!(ThemeScheme::ALL.len(), 12);
+        assert_eq!(ThemeScheme::ALL.len(), 14);
     }
 
     #[test]
@@ -4661,6 +6056,94 @@ layouts:
         assert_eq!(layouts["review"].panes.len(), 1);
     }
 
+    #[test]
+    fn apply_layout_replaces_panes_and_windows() {
+        let yaml = r#"
+panes:
+  - command: vim
+windows:
+  - name: editor
+layouts:
+  review:
+    panes:
+      - command: "<agent:claude>"
+"#;
+        let mut config: Config = serde_yaml::from_str(yaml).unwrap();
+        config.apply_layout("review").unwrap();
+        let panes = config.panes.unwrap();
+        assert_eq!(panes.len(), 1);
+        assert_eq!(panes[0].command.as_deref(), Some("<agent:claude>"));
+        assert!(config.windows.is_none());
+    }
+
+    #[test]
+    fn apply_layout_lists_available_layouts() {
+        let yaml = r#"
+layouts:
+  review:
+    panes:
+      - command: vim
+  design:
+    panes:
+      - command: vim
+"#;
+        let mut config: Config = serde_yaml::from_str(yaml).unwrap();
+        let err = config.apply_layout("missing").unwrap_err().to_string();
+        assert!(err.contains("Available layouts: design, review"), "{err}");
+    }
+
+    #[test]
+    fn session_strategy_names_sessions() {
+        let per_repo = SessionStrategy::PerRepo;
+        let by_prefix = SessionStrategy::PerBranchPrefix;
+        assert_eq!(
+            SessionStrategy::Single.session_name("wm-", "app", "a/b"),
+            None
+        );
+        assert_eq!(
+            per_repo.session_name("wm-", "app", "frontend/login"),
+            Some("wm-app".to_string())
+        );
+        assert_eq!(
+            by_prefix.session_name("wm-", "app", "frontend/login"),
+            Some("wm-app-frontend".to_string())
+        );
+        assert_eq!(
+            by_prefix.session_name("wm-", "my.app", "fix-typo"),
+            Some("wm-my-app".to_string())
+        );
+    }
+
+    #[test]
+    fn session_strategy_ignored_in_session_mode() {
+        let config: Config =
+            serde_yaml::from_str("window_prefix: wm-\nsession_strategy: per-branch-prefix")
+                .unwrap();
+        assert_eq!(
+            config.window_session(MuxMode::Window, "app", "api/auth"),
+            Some("wm-app-api".to_string())
+        );
+        assert_eq!(
+            config.window_session(MuxMode::Session, "app", "api/auth"),
+            None
+        );
+    }
+
+    #[test]
+    fn backend_priority_is_global_only() {
+        let global: Config = serde_yaml::from_str("backend_priority: [wezterm, tmux]").unwrap();
+        assert_eq!(
+            global.backend_priority,
+            Some(vec![BackendType::WezTerm, BackendType::Tmux])
+        );
+        let project: Config = serde_yaml::from_str("backend_priority: [kitty]").unwrap();
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.backend_priority,
+            Some(vec![BackendType::WezTerm, BackendType::Tmux])
+        );
+    }
+
     #[test]
     fn deserialize_layouts_absent() {
         let yaml = "agent: claude";
@@ -4691,6 +6174,25 @@ layouts:
         assert!(validate_layouts_config(&layouts).is_ok());
     }
 
+    #[test]
+    fn pane_env_and_cwd() {
+        let config: Config = serde_yaml::from_str(
+            "panes:\n  - command: <agent>\n  - command: npm run dev\n    split: horizontal\n    cwd: web\n    env:\n      PORT: \"3001\"\n",
+        )
+        .unwrap();
+        let panes = config.panes.unwrap();
+        assert_eq!(panes[1].cwd.as_deref(), Some("web"));
+        assert_eq!(panes[1].env.get("PORT").map(String::as_str), Some("3001"));
+        assert!(super::validate_panes_config(&panes).is_ok());
+
+        let bad = vec![PaneConfig {
+            env: BTreeMap::from([("NODE-ENV".to_string(), "dev".to_string())]),
+            ..Default::default()
+        }];
+        let err = super::validate_panes_config(&bad).unwrap_err();
+        assert!(err.to_string().contains("NODE-ENV"));
+    }
+
     #[test]
     fn validate_panes_multiple_zoom_fails() {
         let panes = vec![
@@ -4942,4 +6444,130 @@ theme:
             Some("#111111".to_string())
         );
     }
+
+    #[test]
+    fn merge_policy_project_cannot_relax_global() {
+        let global = Config {
+            merge_policy: MergePolicyConfig {
+                require_checks: Some(true),
+                protected_branches: Some(vec!["main".to_string()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let project = Config {
+            merge_policy: MergePolicyConfig {
+                require_checks: Some(false),
+                require_approval: Some(true),
+                protected_branches: Some(vec!["release/*".to_string()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = global.merge(project).merge_policy;
+        assert!(merged.require_checks());
+        assert!(merged.require_pr());
+        assert!(merged.require_approval());
+        assert!(merged.is_protected("main"));
+        assert!(merged.is_protected("release/2.0"));
+        assert!(!merged.is_protected("develop"));
+    }
+
+    #[test]
+    fn limits_merge_keeps_the_lower_cap() {
+        let global = Config {
+            limits: LimitsConfig {
+                max_agents: Some(8),
+                max_agents_per_repo: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let project = Config {
+            limits: LimitsConfig {
+                max_agents: Some(20),
+                max_agents_per_repo: Some(1),
+                when_full: Some(WhenFull::Queue),
+            },
+            ..Default::default()
+        };
+        let merged = global.merge(project).limits;
+        assert_eq!(merged.max_agents, Some(8));
+        assert_eq!(merged.max_agents_per_repo, Some(1));
+        assert_eq!(merged.when_full(), WhenFull::Queue);
+        assert!(merged.reached(3, 0).is_none());
+        assert!(
+            merged
+                .reached(3, 1)
+                .unwrap()
+                .contains("max_agents_per_repo")
+        );
+        assert!(merged.reached(8, 0).unwrap().contains("max_agents: 8"));
+    }
+
+    #[test]
+    fn dashboard_stale_after_parses_duration() {
+        let dashboard = |value: &str| DashboardConfig {
+            stale_after: Some(value.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            dashboard("30m").stale_after(),
+            Some(std::time::Duration::from_secs(30 * 60))
+        );
+        assert_eq!(dashboard("0m").stale_after(), None);
+        assert_eq!(dashboard("soon").stale_after(), None);
+        assert_eq!(DashboardConfig::default().stale_after(), None);
+    }
+
+    #[test]
+    fn dashboard_columns_parse_in_order() {
+        let config: Config =
+            serde_yaml::from_str("dashboard:\n  columns: [status, project, time, status]\n")
+                .unwrap();
+        assert_eq!(
+            config.dashboard.columns(),
+            vec![
+                DashboardColumn::Status,
+                DashboardColumn::Project,
+                DashboardColumn::Elapsed,
+            ]
+        );
+        let empty = DashboardConfig {
+            columns: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert_eq!(empty.columns(), DashboardColumn::DEFAULT.to_vec());
+    }
+
+    #[test]
+    fn tracker_connection_is_global_only() {
+        let global = Config {
+            tracker: TrackerConfig {
+                backend: Some(TrackerBackend::Jira),
+                url: Some("https://acme.atlassian.net".to_string()),
+                token: Some("keychain:jira".to_string()),
+                branch: Some("{{ key }}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let project = Config {
+            tracker: TrackerConfig {
+                backend: Some(TrackerBackend::Linear),
+                url: Some("https://attacker.example".to_string()),
+                token: Some("env-file:~/.ssh/id_rsa".to_string()),
+                branch: Some("feat/{{ key }}".to_string()),
+                link_pr: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = global.merge(project).tracker;
+        assert_eq!(merged.backend, Some(TrackerBackend::Jira));
+        assert_eq!(merged.url.as_deref(), Some("https://acme.atlassian.net"));
+        assert_eq!(merged.token.as_deref(), Some("keychain:jira"));
+        assert_eq!(merged.branch.as_deref(), Some("feat/{{ key }}"));
+        assert_eq!(merged.link_pr, Some(false));
+    }
 }
@@ -1,12 +1,12 @@
 use anyhow::{Context, Result, anyhow};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::debug;
 
 use crate::cmd::Cmd;
 
 use super::repo::has_commits;
-use super::{ForkBranchSpec, RemoteBranchSpec};
+use super::{AheadBehind, BranchDivergence, ForkBranchSpec, RemoteBranchSpec};
 
 /// Get the default branch (main or master)
 pub fn get_default_branch() -> Result<String> {
@@ -69,6 +69,25 @@ pub fn branch_exists_in(branch_name: &str, workdir: Option<&Path>) -> Result<boo
     cmd.run_as_check()
 }
 
+/// Resolve a stored branch base (see `set_branch_base`) to a local branch that
+/// can be merged into. A remote-tracking base like `origin/release` maps to its
+/// local counterpart. Returns `None` when the base is a tag, a commit, or no
+/// longer exists as a local branch.
+pub fn resolve_base_merge_target(base: &str) -> Result<Option<String>> {
+    if branch_exists(&format!("refs/heads/{}", base))? {
+        return Ok(Some(base.to_string()));
+    }
+
+    if branch_exists(&format!("refs/remotes/{}", base))?
+        && let Ok(spec) = parse_remote_branch_spec(base)
+        && branch_exists(&format!("refs/heads/{}", spec.branch))?
+    {
+        return Ok(Some(spec.branch));
+    }
+
+    Ok(None)
+}
+
 /// Parse a remote branch specification in the form "<remote>/<branch>"
 pub fn parse_remote_branch_spec(spec: &str) -> Result<RemoteBranchSpec> {
     let mut parts = spec.splitn(2, '/');
@@ -194,6 +213,9 @@ pub fn rename_branch(old: &str, new: &str) -> Result<()> {
 
 /// Delete a local branch.
 pub fn delete_branch_in(branch_name: &str, force: bool, git_common_dir: &Path) -> Result<()> {
+    // Look up the fork push target before git drops the branch's config
+    let push_target = super::get_push_target_in(branch_name, Some(git_common_dir));
+
     let mut cmd = Cmd::new("git").workdir(git_common_dir).arg("branch");
 
     if force {
@@ -205,6 +227,10 @@ pub fn delete_branch_in(branch_name: &str, force: bool, git_common_dir: &Path) -
     cmd.arg(branch_name)
         .run()
         .context("Failed to delete branch")?;
+
+    if let Some((remote, push_branch)) = push_target {
+        super::remove_push_refspec_in(branch_name, &remote, &push_branch, git_common_dir);
+    }
     Ok(())
 }
 
@@ -303,6 +329,82 @@ pub fn get_gone_branches() -> Result<HashSet<String>> {
     Ok(gone)
 }
 
+/// Get ahead/behind counts for every local branch, relative to its upstream and
+/// to `main_ref`, using a single `git for-each-ref` call.
+///
+/// `%(ahead-behind:...)` needs git 2.41+. On older versions the main-branch
+/// counts are left empty and only upstream divergence is reported.
+pub fn get_branch_divergence_in(
+    workdir: Option<&Path>,
+    main_ref: &str,
+) -> Result<HashMap<String, BranchDivergence>> {
+    let run = |format: &str| {
+        let cmd = Cmd::new("git").args(&["for-each-ref", format, "refs/heads/"]);
+        let cmd = match workdir {
+            Some(path) => cmd.workdir(path),
+            None => cmd,
+        };
+        cmd.run_and_capture_stdout()
+    };
+
+    let base_format = "--format=%(refname:short)%09%(upstream)%09%(upstream:track,nobracket)";
+    let with_main = format!("{}%09%(ahead-behind:{})", base_format, main_ref);
+
+    let output = match run(&with_main) {
+        Ok(output) => output,
+        Err(e) => {
+            debug!(error = %e, "git:ahead-behind unsupported, falling back to upstream only");
+            run(base_format).context("Failed to list branch divergence")?
+        }
+    };
+
+    Ok(parse_branch_divergence(&output))
+}
+
+/// Parse tab-separated `refname, upstream, track[, ahead behind]` lines.
+fn parse_branch_divergence(output: &str) -> HashMap<String, BranchDivergence> {
+    let mut result = HashMap::new();
+    for line in output.lines() {
+        let mut fields = line.split('\t');
+        let Some(branch) = fields.next().filter(|b| !b.is_empty()) else {
+            continue;
+        };
+        let upstream_ref = fields.next().unwrap_or("");
+        let track = fields.next().unwrap_or("");
+        let main_counts = fields.next().unwrap_or("");
+
+        let upstream = if upstream_ref.is_empty() || track == "gone" {
+            None
+        } else {
+            Some(parse_track(track))
+        };
+
+        let main = main_counts.split_once(' ').and_then(|(a, b)| {
+            Some(AheadBehind {
+                ahead: a.parse().ok()?,
+                behind: b.parse().ok()?,
+            })
+        });
+
+        result.insert(branch.to_string(), BranchDivergence { upstream, main });
+    }
+    result
+}
+
+/// Parse `%(upstream:track,nobracket)` output, e.g. "ahead 1, behind 2".
+/// An empty string means the branch is in sync with its upstream.
+fn parse_track(track: &str) -> AheadBehind {
+    let mut counts = AheadBehind::default();
+    for part in track.split(", ") {
+        if let Some(n) = part.strip_prefix("ahead ") {
+            counts.ahead = n.parse().unwrap_or(0);
+        } else if let Some(n) = part.strip_prefix("behind ") {
+            counts.behind = n.parse().unwrap_or(0);
+        }
+    }
+    counts
+}
+
 /// Unset the upstream tracking for a branch
 pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     if !branch_has_upstream(branch_name)? {
@@ -375,10 +477,126 @@ pub fn get_branch_base_in(branch: &str, workdir: Option<&Path>) -> Result<String
     Ok(output)
 }
 
+/// Batch-load the workmux-base of every branch that has one, keyed by branch.
+pub fn get_all_branch_bases_in(workdir: Option<&Path>) -> HashMap<String, String> {
+    let cmd = Cmd::new("git").args(&[
+        "config",
+        "--local",
+        "--get-regexp",
+        r"^branch\..*\.workmux-base$",
+    ]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    let output = cmd.run_and_capture_stdout().unwrap_or_default();
+    parse_branch_bases(&output)
+}
+
+/// Parse `git config --get-regexp` output of `branch.<name>.workmux-base <base>`
+/// lines. Branch names may contain dots, so the name is everything between
+/// the fixed prefix and suffix.
+fn parse_branch_bases(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            let branch = key.strip_prefix("branch.")?.strip_suffix(".workmux-base")?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (branch.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Store the tracker ticket a branch was created for (`add --ticket`)
+pub fn set_branch_ticket(branch: &str, key: &str) -> Result<()> {
+    let config_key = format!("branch.{}.workmux-ticket", branch);
+    Cmd::new("git")
+        .args(&["config", "--local", &config_key, key])
+        .run()
+        .context("Failed to set workmux-ticket config")?;
+    Ok(())
+}
+
+/// Record that a branch's ticket links to pull request `number`, or clear the
+/// record with None.
+pub fn set_branch_ticket_pr_in(branch: &str, number: Option<u32>, workdir: &Path) -> Result<()> {
+    let config_key = format!("branch.{}.workmux-ticket-pr", branch);
+    let number = number.map(|n| n.to_string());
+    let cmd = Cmd::new("git").workdir(workdir);
+    let cmd = match &number {
+        Some(number) => cmd.args(&["config", "--local", &config_key, number]),
+        None => cmd.args(&["config", "--local", "--unset", &config_key]),
+    };
+    cmd.run()
+        .context("Failed to set workmux-ticket-pr config")?;
+    Ok(())
+}
+
+/// Tickets of branches not yet linked to a pull request, keyed by branch.
+pub fn unlinked_branch_tickets_in(workdir: &Path) -> HashMap<String, String> {
+    let output = Cmd::new("git")
+        .workdir(workdir)
+        .args(&[
+            "config",
+            "--local",
+            "--get-regexp",
+            r"^branch\..*\.workmux-ticket(-pr)?$",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+    parse_unlinked_tickets(&output)
+}
+
+fn parse_unlinked_tickets(output: &str) -> HashMap<String, String> {
+    let mut tickets = HashMap::new();
+    let mut linked = Vec::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(name) = key.strip_prefix("branch.") else {
+            continue;
+        };
+        if let Some(branch) = name.strip_suffix(".workmux-ticket-pr") {
+            linked.push(branch.to_string());
+        } else if let Some(branch) = name.strip_suffix(".workmux-ticket") {
+            tickets.insert(branch.to_string(), value.trim().to_string());
+        }
+    }
+    for branch in linked {
+        tickets.remove(&branch);
+    }
+    tickets
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_unlinked_tickets() {
+        let tickets = parse_unlinked_tickets(
+            "branch.proj-1-login.workmux-ticket PROJ-1\n\
+             branch.proj-2-logout.workmux-ticket PROJ-2\n\
+             branch.proj-2-logout.workmux-ticket-pr 42\n",
+        );
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets["proj-1-login"], "PROJ-1");
+    }
+
+    #[test]
+    fn test_parse_branch_bases() {
+        let bases = parse_branch_bases(
+            "branch.feature.workmux-base main\n\
+             branch.release.v1.2.workmux-base origin/release\n\
+             branch.empty.workmux-base \n",
+        );
+        assert_eq!(bases.len(), 2);
+        assert_eq!(bases["feature"], "main");
+        assert_eq!(bases["release.v1.2"], "origin/release");
+    }
+
     #[test]
     fn test_parse_fork_branch_spec_valid() {
         let spec = parse_fork_branch_spec("someuser:feature-branch").unwrap();
@@ -422,4 +640,51 @@ mod tests {
     fn test_parse_fork_branch_spec_remote_branch_format() {
         assert!(parse_fork_branch_spec("origin/feature").is_none());
     }
+
+    #[test]
+    fn test_parse_branch_divergence() {
+        let output = "feature\trefs/remotes/origin/feature\tahead 2, behind 1\t3 7\n\
+                      synced\trefs/remotes/origin/synced\t\t0 0\n\
+                      local\t\t\t1 4\n\
+                      deleted\trefs/remotes/origin/deleted\tgone\t5 0";
+        let result = parse_branch_divergence(output);
+
+        assert_eq!(
+            result["feature"],
+            BranchDivergence {
+                upstream: Some(AheadBehind {
+                    ahead: 2,
+                    behind: 1
+                }),
+                main: Some(AheadBehind {
+                    ahead: 3,
+                    behind: 7
+                }),
+            }
+        );
+        assert_eq!(result["synced"].upstream, Some(AheadBehind::default()));
+        assert_eq!(result["local"].upstream, None);
+        assert_eq!(
+            result["local"].main,
+            Some(AheadBehind {
+                ahead: 1,
+                behind: 4
+            })
+        );
+        assert_eq!(result["deleted"].upstream, None);
+    }
+
+    #[test]
+    fn test_parse_branch_divergence_without_ahead_behind_field() {
+        let output = "feature\trefs/remotes/origin/feature\tbehind 3";
+        let result = parse_branch_divergence(output);
+        assert_eq!(
+            result["feature"].upstream,
+            Some(AheadBehind {
+                ahead: 0,
+                behind: 3
+            })
+        );
+        assert_eq!(result["feature"].main, None);
+    }
 }
@@ -1,5 +1,4 @@
 use anyhow::{Context, Result};
-use nix::fcntl::{Flock, FlockArg};
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 use tracing::debug;
@@ -8,7 +7,8 @@ use tracing::debug;
 /// in the git common directory. Serializes concurrent workmux processes that
 /// write to `.git/config`.
 pub struct GitConfigLock {
-    _lock: Flock<File>,
+    /// The lock is released when the file is closed
+    _file: File,
 }
 
 impl GitConfigLock {
@@ -25,11 +25,10 @@ impl GitConfigLock {
             .open(&lock_path)
             .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
 
-        let lock = Flock::lock(file, FlockArg::LockExclusive)
-            .map_err(|(_file, errno)| errno)
+        file.lock()
             .with_context(|| format!("Failed to acquire lock: {}", lock_path.display()))?;
 
         debug!(path = %lock_path.display(), "config_lock:acquired");
-        Ok(Self { _lock: lock })
+        Ok(Self { _file: file })
     }
 }
@@ -4,13 +4,17 @@ use std::process::Command;
 
 use crate::cmd::Cmd;
 
-/// Commit staged changes in a worktree using the user's editor
-pub fn commit_with_editor(worktree_path: &Path) -> Result<()> {
-    let status = Command::new("git")
-        .current_dir(worktree_path)
-        .arg("commit")
-        .status()
-        .context("Failed to run git commit")?;
+/// Commit staged changes in a worktree using the user's editor.
+///
+/// When `sign` is true the commit is signed with `--gpg-sign`, regardless of
+/// the user's `commit.gpgsign` setting.
+pub fn commit_with_editor(worktree_path: &Path, sign: bool) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(worktree_path).arg("commit");
+    if sign {
+        cmd.arg("--gpg-sign");
+    }
+    let status = cmd.status().context("Failed to run git commit")?;
 
     if !status.success() {
         return Err(anyhow!("Commit was aborted or failed"));
@@ -20,20 +24,35 @@ pub fn commit_with_editor(worktree_path: &Path) -> Result<()> {
 }
 
 /// Merge a branch into the current branch in a specific worktree
-pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
-    Cmd::new("git")
-        .workdir(worktree_path)
-        .args(&["merge", branch_name])
-        .run()
-        .context("Failed to merge")?;
+pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str, sign: bool) -> Result<()> {
+    let mut cmd = Cmd::new("git").workdir(worktree_path).arg("merge");
+    if sign {
+        cmd = cmd.arg("--gpg-sign");
+    }
+    cmd.arg(branch_name).run().context("Failed to merge")?;
     Ok(())
 }
 
-/// Rebase the current branch in a worktree onto a base branch
-pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str) -> Result<()> {
-    Cmd::new("git")
-        .workdir(worktree_path)
-        .args(&["rebase", base_branch])
+/// Rebase the current branch in a worktree onto a base branch.
+///
+/// When `sign` is true, the commits the rebase rewrites are signed with
+/// `--gpg-sign`. `force` adds `--force-rebase`, so every commit is rewritten
+/// and re-signed even when the branch already sits on the base, where a plain
+/// rebase would leave it (and its SHAs) alone.
+pub fn rebase_branch_onto_base(
+    worktree_path: &Path,
+    base_branch: &str,
+    sign: bool,
+    force: bool,
+) -> Result<()> {
+    let mut cmd = Cmd::new("git").workdir(worktree_path).arg("rebase");
+    if force {
+        cmd = cmd.arg("--force-rebase");
+    }
+    if sign {
+        cmd = cmd.arg("--gpg-sign");
+    }
+    cmd.arg(base_branch)
         .run()
         .with_context(|| format!("Failed to rebase onto '{}'", base_branch))?;
     Ok(())
@@ -120,3 +139,75 @@ pub fn abort_merge_in_worktree(worktree_path: &Path) -> Result<()> {
         .context("Failed to abort merge. The worktree may not be in a merging state.")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn only_forced_signed_rebase_rewrites_branch_already_on_base() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let key = root.join("signing-key");
+        let keygen = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status();
+        if !keygen.is_ok_and(|s| s.success()) {
+            return; // ssh-keygen is not installed
+        }
+
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test"]);
+        git(root, &["commit", "-q", "--allow-empty", "-m", "base"]);
+        git(root, &["switch", "-q", "-c", "feature"]);
+        git(root, &["commit", "-q", "--allow-empty", "-m", "unsigned"]);
+        git(root, &["config", "gpg.format", "ssh"]);
+        git(root, &["config", "user.signingkey", key.to_str().unwrap()]);
+
+        let head = git(root, &["rev-parse", "HEAD"]);
+        rebase_branch_onto_base(root, "main", true, false).unwrap();
+        assert_eq!(git(root, &["rev-parse", "HEAD"]), head);
+
+        rebase_branch_onto_base(root, "main", true, true).unwrap();
+        assert!(git(root, &["cat-file", "commit", "HEAD"]).contains("gpgsig"));
+    }
+
+    #[test]
+    fn only_conflicting_rebase_reports_conflicts() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test"]);
+        std::fs::write(root.join("file"), "base\n").unwrap();
+        git(root, &["add", "file"]);
+        git(root, &["commit", "-q", "-m", "base"]);
+        git(root, &["switch", "-q", "-c", "feature"]);
+        std::fs::write(root.join("file"), "feature\n").unwrap();
+        git(root, &["commit", "-q", "-am", "feature"]);
+        git(root, &["switch", "-q", "main"]);
+        std::fs::write(root.join("file"), "main\n").unwrap();
+        git(root, &["commit", "-q", "-am", "main"]);
+        git(root, &["switch", "-q", "feature"]);
+
+        // Unknown base: fails without starting a rebase
+        assert!(rebase_branch_onto_base(root, "missing", false, false).is_err());
+        assert!(!crate::git::has_rebase_conflicts(root));
+
+        assert!(rebase_branch_onto_base(root, "main", false, false).is_err());
+        assert!(crate::git::has_rebase_conflicts(root));
+    }
+}
@@ -4,6 +4,7 @@ mod config_lock;
 mod merge;
 mod remote;
 mod repo;
+mod signing;
 mod status;
 mod types;
 mod worktree;
@@ -14,6 +15,7 @@ pub use config_lock::*;
 pub use merge::*;
 pub use remote::*;
 pub use repo::*;
+pub use signing::*;
 pub use status::*;
 pub use types::*;
 pub use worktree::*;
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use git_url_parse::GitUrl;
 use git_url_parse::types::provider::GenericProvider;
+use std::path::Path;
 use tracing::info;
 
 use crate::cmd::Cmd;
@@ -222,9 +223,120 @@ pub fn get_repo_owner() -> Result<String> {
         .map(|s| s.to_string())
 }
 
+/// Refspec that pushes a local branch to a differently named branch on a remote.
+pub fn push_refspec(branch: &str, push_branch: &str) -> String {
+    format!("refs/heads/{}:refs/heads/{}", branch, push_branch)
+}
+
+/// Find the remote branch a local branch is pushed to among `remote.<name>.push` refspecs.
+fn parse_push_branch(refspecs: &str, branch: &str) -> Option<String> {
+    let prefix = format!("refs/heads/{}:refs/heads/", branch);
+    refspecs
+        .lines()
+        .map(|line| line.trim().trim_start_matches('+'))
+        .find_map(|spec| spec.strip_prefix(&prefix))
+        .filter(|b| !b.is_empty())
+        .map(String::from)
+}
+
+/// Configure a triangular workflow for a branch checked out from a fork:
+/// `git pull` tracks `<upstream_remote>/<upstream_branch>` while `git push`
+/// goes to `<push_remote>/<push_branch>`, even when the local branch name
+/// differs from the fork's branch name.
+pub fn configure_triangular_branch(
+    branch: &str,
+    upstream_remote: &str,
+    upstream_branch: &str,
+    push_remote: &str,
+    push_branch: &str,
+) -> Result<()> {
+    let set = |key: String, value: &str| {
+        Cmd::new("git")
+            .args(&["config", "--local", &key, value])
+            .run()
+            .with_context(|| format!("Failed to set {}", key))
+    };
+
+    set(format!("branch.{}.remote", branch), upstream_remote)?;
+    set(
+        format!("branch.{}.merge", branch),
+        &format!("refs/heads/{}", upstream_branch),
+    )?;
+    set(format!("branch.{}.pushRemote", branch), push_remote)?;
+
+    let push_key = format!("remote.{}.push", push_remote);
+    let refspec = push_refspec(branch, push_branch);
+    let existing = Cmd::new("git")
+        .args(&["config", "--local", "--get-all", &push_key])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+    if !existing.lines().any(|line| line.trim() == refspec) {
+        Cmd::new("git")
+            .args(&["config", "--local", "--add", &push_key, &refspec])
+            .run()
+            .with_context(|| format!("Failed to add push refspec to {}", push_key))?;
+    }
+
+    info!(
+        branch,
+        upstream = %format!("{}/{}", upstream_remote, upstream_branch),
+        push = %format!("{}/{}", push_remote, push_branch),
+        "git:configured triangular workflow"
+    );
+    Ok(())
+}
+
+/// Get the remote and branch that a local branch pushes to when it was set up
+/// with `configure_triangular_branch`. Returns `None` for ordinary branches.
+pub fn get_push_target_in(branch: &str, workdir: Option<&Path>) -> Option<(String, String)> {
+    let git = || match workdir {
+        Some(path) => Cmd::new("git").workdir(path),
+        None => Cmd::new("git"),
+    };
+    let push_remote = git()
+        .args(&["config", "--get", &format!("branch.{}.pushRemote", branch)])
+        .run_and_capture_stdout()
+        .ok()?;
+    let push_remote = push_remote.trim();
+    if push_remote.is_empty() {
+        return None;
+    }
+    let refspecs = git()
+        .args(&[
+            "config",
+            "--get-all",
+            &format!("remote.{}.push", push_remote),
+        ])
+        .run_and_capture_stdout()
+        .ok()?;
+    parse_push_branch(&refspecs, branch).map(|b| (push_remote.to_string(), b))
+}
+
+/// Remove the push refspec added by `configure_triangular_branch`.
+/// Git drops `branch.<name>.*` when a branch is deleted but leaves the
+/// remote's refspecs behind, so this is called after deletion.
+pub fn remove_push_refspec_in(
+    branch: &str,
+    remote: &str,
+    push_branch: &str,
+    git_common_dir: &Path,
+) {
+    let _ = Cmd::new("git")
+        .workdir(git_common_dir)
+        .args(&[
+            "config",
+            "--local",
+            "--fixed-value",
+            "--unset-all",
+            &format!("remote.{}.push", remote),
+            &push_refspec(branch, push_branch),
+        ])
+        .run();
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_owner_from_git_url;
+    use super::{parse_owner_from_git_url, parse_push_branch};
 
     #[test]
     fn test_parse_repo_owner_https_github_com() {
@@ -320,4 +432,20 @@ mod tests {
     fn test_parse_repo_owner_file_protocol() {
         assert_eq!(parse_owner_from_git_url("file:///local/path/to/repo"), None);
     }
+
+    #[test]
+    fn test_parse_push_branch() {
+        let refspecs = "refs/heads/alice-feature:refs/heads/feature\n\
+                        +refs/heads/alice-fix:refs/heads/fix/typo\n";
+        assert_eq!(
+            parse_push_branch(refspecs, "alice-feature"),
+            Some("feature".to_string())
+        );
+        assert_eq!(
+            parse_push_branch(refspecs, "alice-fix"),
+            Some("fix/typo".to_string())
+        );
+        assert_eq!(parse_push_branch(refspecs, "alice"), None);
+        assert_eq!(parse_push_branch("", "alice-feature"), None);
+    }
 }
@@ -1,13 +1,58 @@
 use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 
 use crate::cmd::Cmd;
+use tracing::debug;
+
+/// Per-process memo of path lookups keyed by the directory they ran from.
+///
+/// Nested code paths (config loading, hooks, state) discover the same repo
+/// over and over within one command; the layout doesn't change under a
+/// running command, so one git call per directory is enough. Failures aren't
+/// cached, so a repo or worktree created mid-command is still found.
+pub(super) struct DirMemo(LazyLock<Mutex<HashMap<PathBuf, PathBuf>>>);
+
+impl DirMemo {
+    pub(super) const fn new() -> Self {
+        Self(LazyLock::new(|| Mutex::new(HashMap::new())))
+    }
+
+    pub(super) fn get_or_try(
+        &self,
+        dir: &Path,
+        lookup: impl FnOnce() -> Result<PathBuf>,
+    ) -> Result<PathBuf> {
+        if let Some(found) = self.0.lock().ok().and_then(|m| m.get(dir).cloned()) {
+            return Ok(found);
+        }
+        let found = lookup()?;
+        if let Ok(mut memo) = self.0.lock() {
+            memo.insert(dir.to_path_buf(), found.clone());
+        }
+        Ok(found)
+    }
+
+    /// Memoize a lookup that runs in the current directory.
+    pub(super) fn get_or_try_cwd(
+        &self,
+        lookup: impl FnOnce() -> Result<PathBuf>,
+    ) -> Result<PathBuf> {
+        match std::env::current_dir() {
+            Ok(cwd) => self.get_or_try(&cwd, lookup),
+            Err(_) => lookup(),
+        }
+    }
+}
+
+static REPO_ROOTS: DirMemo = DirMemo::new();
+static COMMON_DIRS: DirMemo = DirMemo::new();
 
 /// Check if a path is ignored by git (via .gitignore, global gitignore, etc.)
 pub fn is_path_ignored(repo_path: &Path, file_path: &str) -> bool {
-    std::process::Command::new("git")
-        .args(["check-ignore", "-q", file_path])
-        .current_dir(repo_path)
+    crate::transport::current()
+        .command("git", &["check-ignore", "-q", file_path], Some(repo_path))
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .status()
@@ -31,17 +76,27 @@ pub fn has_commits() -> Result<bool> {
 
 /// Get the root directory of the git repository
 pub fn get_repo_root() -> Result<PathBuf> {
-    let path = Cmd::new("git")
-        .args(&["rev-parse", "--show-toplevel"])
-        .run_and_capture_stdout()?;
-    Ok(PathBuf::from(path))
+    REPO_ROOTS.get_or_try_cwd(|| {
+        let path = Cmd::new("git")
+            .args(&["rev-parse", "--show-toplevel"])
+            .run_and_capture_stdout()?;
+        Ok(PathBuf::from(path))
+    })
 }
 
 /// Get the root directory of the git repository containing the given path.
 /// Uses `git -C <dir>` to run git from the target directory.
 pub fn get_repo_root_for(dir: &Path) -> Result<PathBuf> {
-    let output = std::process::Command::new("git")
-        .args(["-C", &dir.to_string_lossy(), "rev-parse", "--show-toplevel"])
+    REPO_ROOTS.get_or_try(dir, || query_repo_root_for(dir))
+}
+
+fn query_repo_root_for(dir: &Path) -> Result<PathBuf> {
+    let output = crate::transport::current()
+        .command(
+            "git",
+            &["-C", &dir.to_string_lossy(), "rev-parse", "--show-toplevel"],
+            None,
+        )
         .output()
         .context("Failed to run git rev-parse")?;
 
@@ -62,6 +117,10 @@ pub fn get_repo_root_for(dir: &Path) -> Result<PathBuf> {
 /// Git commands like `git worktree prune` and `git branch -D` work correctly
 /// when run from this directory, even for bare repo setups.
 pub fn get_git_common_dir() -> Result<PathBuf> {
+    COMMON_DIRS.get_or_try_cwd(query_git_common_dir)
+}
+
+fn query_git_common_dir() -> Result<PathBuf> {
     let raw = Cmd::new("git")
         .args(&["rev-parse", "--git-common-dir"])
         .run_and_capture_stdout()
@@ -86,3 +145,53 @@ pub fn get_git_common_dir() -> Result<PathBuf> {
 
     Ok(abs_path)
 }
+
+/// Start `git maintenance run --auto` as a detached background process.
+/// Git only does work once its own thresholds are exceeded and holds a lock,
+/// so overlapping runs are cheap. Failures are logged and otherwise ignored.
+pub fn spawn_background_maintenance(git_common_dir: &Path) {
+    let child = std::process::Command::new("git")
+        .current_dir(git_common_dir)
+        .args(["maintenance", "run", "--auto", "--quiet"])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    match child {
+        Ok(mut child) => {
+            debug!(pid = child.id(), "git:started background maintenance");
+            // Reap the child if we outlive it (e.g., the dashboard)
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+        Err(e) => debug!(error = %e, "git:failed to start background maintenance"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn dir_memo_caches_hits_but_not_failures() {
+        let memo = DirMemo::new();
+        let calls = Cell::new(0);
+        let lookup = |result: Result<PathBuf>| {
+            calls.set(calls.get() + 1);
+            result
+        };
+        let dir = Path::new("/repo/sub");
+
+        assert!(
+            memo.get_or_try(dir, || lookup(Err(anyhow!("not yet"))))
+                .is_err()
+        );
+        let root = memo.get_or_try(dir, || lookup(Ok(PathBuf::from("/repo"))));
+        assert_eq!(root.unwrap(), PathBuf::from("/repo"));
+        let root = memo.get_or_try(dir, || lookup(Ok(PathBuf::from("/other"))));
+        assert_eq!(root.unwrap(), PathBuf::from("/repo"));
+        assert_eq!(calls.get(), 2);
+    }
+}
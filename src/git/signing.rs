@@ -0,0 +1,166 @@
+use anyhow::{Result, anyhow};
+use std::path::Path;
+
+use crate::cmd::Cmd;
+
+/// Signature format configured via `gpg.format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningFormat {
+    OpenPgp,
+    Ssh,
+    X509,
+}
+
+impl SigningFormat {
+    fn from_config(value: &str) -> Self {
+        match value.trim() {
+            "ssh" => Self::Ssh,
+            "x509" => Self::X509,
+            _ => Self::OpenPgp,
+        }
+    }
+
+    /// Program git invokes to sign when no `gpg.<format>.program` is set.
+    fn default_program(&self) -> &'static str {
+        match self {
+            Self::OpenPgp => "gpg",
+            Self::Ssh => "ssh-keygen",
+            Self::X509 => "gpgsm",
+        }
+    }
+
+    fn program_config_key(&self) -> &'static str {
+        match self {
+            Self::OpenPgp => "gpg.program",
+            Self::Ssh => "gpg.ssh.program",
+            Self::X509 => "gpg.x509.program",
+        }
+    }
+}
+
+fn get_config_in(workdir: &Path, key: &str) -> Option<String> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["config", "--get", key])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|v| !v.is_empty())
+}
+
+/// Whether the user's git config asks for commits to be signed (`commit.gpgsign`).
+pub fn commit_signing_enabled(workdir: &Path) -> bool {
+    get_config_in(workdir, "commit.gpgsign")
+        .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "yes" | "on" | "1"))
+}
+
+/// Get the signature format configured for a repository.
+pub fn get_signing_format(workdir: &Path) -> SigningFormat {
+    get_config_in(workdir, "gpg.format")
+        .map(|v| SigningFormat::from_config(&v))
+        .unwrap_or(SigningFormat::OpenPgp)
+}
+
+/// Verify that git can produce signed commits in `workdir`.
+///
+/// Signing keys and agents are not forwarded into sandboxes, so this always
+/// fails inside a sandbox guest with a message pointing at the host.
+pub fn check_signing_available(workdir: &Path) -> Result<()> {
+    if crate::sandbox::guest::is_sandbox_guest() {
+        return Err(anyhow!(
+            "Commit signing is not available inside the sandbox (signing keys and \
+            gpg/ssh agents are not forwarded to the guest).\n\
+            Run this command on the host instead."
+        ));
+    }
+
+    let format = get_signing_format(workdir);
+    if format == SigningFormat::Ssh && get_config_in(workdir, "user.signingkey").is_none() {
+        return Err(anyhow!(
+            "Commit signing uses SSH keys (gpg.format=ssh) but user.signingkey is not set.\n\
+            Configure it with: git config --global user.signingkey ~/.ssh/id_ed25519.pub"
+        ));
+    }
+
+    let program = get_config_in(workdir, format.program_config_key())
+        .unwrap_or_else(|| format.default_program().to_string());
+    if which::which(&program).is_err() {
+        return Err(anyhow!(
+            "Commit signing is enabled but the signing program '{}' was not found in PATH.\n\
+            Install it or set {} in your git config.",
+            program,
+            format.program_config_key()
+        ));
+    }
+
+    Ok(())
+}
+
+/// List commits in `range` (e.g. `main..feature`) that have no valid signature.
+///
+/// Returns `(sha, subject)` pairs for commits that are unsigned or carry a bad
+/// signature. Signatures git cannot verify locally (missing public key, expired
+/// or revoked keys) are accepted, since the commit was still signed.
+pub fn find_unsigned_commits(workdir: &Path, range: &str) -> Result<Vec<(String, String)>> {
+    let output = Cmd::new("git")
+        .workdir(workdir)
+        .args(&["log", "--format=%h %G? %s", range])
+        .run_and_capture_stdout()?;
+    Ok(parse_unsigned_commits(&output))
+}
+
+/// Parse `git log --format='%h %G? %s'` output into unsigned commits.
+fn parse_unsigned_commits(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let sha = parts.next()?;
+            let status = parts.next()?;
+            let subject = parts.next().unwrap_or("");
+            matches!(status, "N" | "B").then(|| (sha.to_string(), subject.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unsigned_commits_flags_missing_and_bad_signatures() {
+        let output = "abc1234 G good commit\n\
+                      def5678 N agent commit\n\
+                      0123abc B tampered\n\
+                      4567def E unknown key";
+        let unsigned = parse_unsigned_commits(output);
+        assert_eq!(
+            unsigned,
+            vec![
+                ("def5678".to_string(), "agent commit".to_string()),
+                ("0123abc".to_string(), "tampered".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_unsigned_commits_empty_output() {
+        assert!(parse_unsigned_commits("").is_empty());
+    }
+
+    #[test]
+    fn parse_unsigned_commits_empty_subject() {
+        let unsigned = parse_unsigned_commits("abc1234 N");
+        assert_eq!(unsigned, vec![("abc1234".to_string(), String::new())]);
+    }
+
+    #[test]
+    fn signing_format_from_config() {
+        assert_eq!(SigningFormat::from_config("ssh"), SigningFormat::Ssh);
+        assert_eq!(SigningFormat::from_config("x509"), SigningFormat::X509);
+        assert_eq!(
+            SigningFormat::from_config("openpgp"),
+            SigningFormat::OpenPgp
+        );
+        assert_eq!(SigningFormat::from_config(""), SigningFormat::OpenPgp);
+    }
+}
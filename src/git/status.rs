@@ -257,6 +257,17 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
     }
 }
 
+/// Check if a rebase stopped on conflicts: one is in progress and the index
+/// has unmerged paths. Other rebase failures (signing, hooks) leave none.
+pub fn has_rebase_conflicts(worktree_path: &Path) -> bool {
+    is_rebasing(worktree_path)
+        && Cmd::new("git")
+            .workdir(worktree_path)
+            .args(&["diff", "--name-only", "--diff-filter=U"])
+            .run_and_capture_stdout()
+            .is_ok_and(|paths| !paths.trim().is_empty())
+}
+
 /// Check if a rebase is in progress by looking for rebase state directories in the git dir.
 /// For linked worktrees, resolves the actual gitdir from the `.git` file.
 fn is_rebasing(worktree_path: &Path) -> bool {
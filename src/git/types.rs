@@ -12,11 +12,32 @@ pub struct ForkBranchSpec {
     pub branch: String,
 }
 
+/// Commit counts a branch is ahead of and behind another ref
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AheadBehind {
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+/// How far a local branch has drifted from its upstream and from the main branch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchDivergence {
+    /// Divergence from the upstream tracking branch (None if no upstream or it's gone)
+    pub upstream: Option<AheadBehind>,
+    /// Divergence from the main branch (None if it couldn't be computed)
+    pub main: Option<AheadBehind>,
+}
+
 /// Custom error type for worktree not found
 #[derive(Debug, thiserror::Error)]
-#[error("Worktree not found: {0}")]
+#[error("Worktree '{0}' not found")]
 pub struct WorktreeNotFound(pub String);
 
+/// The current directory isn't inside a git repository
+#[derive(Debug, thiserror::Error)]
+#[error("Not in a git repository")]
+pub struct NotInRepo;
+
 /// Git status information for a worktree
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GitStatus {
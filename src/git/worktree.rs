@@ -6,21 +6,7 @@ use crate::config::MuxMode;
 
 use super::WorktreeNotFound;
 use super::branch::unset_branch_upstream;
-
-/// Check if a worktree already exists for a branch
-pub fn worktree_exists(branch_name: &str) -> Result<bool> {
-    match get_worktree_path(branch_name) {
-        Ok(_) => Ok(true),
-        Err(e) => {
-            // Check if this is a WorktreeNotFound error
-            if e.is::<WorktreeNotFound>() {
-                Ok(false)
-            } else {
-                Err(e)
-            }
-        }
-    }
-}
+use super::repo::DirMemo;
 
 /// Create a new git worktree
 pub fn create_worktree(
@@ -58,6 +44,62 @@ pub fn create_worktree(
     Ok(())
 }
 
+/// Set git config that applies to a single worktree (`git config --worktree`).
+///
+/// Enables `extensions.worktreeConfig` on first use. Per git-worktree(1), a
+/// `core.bare = true` setting then has to move from the shared config into the
+/// main worktree's `config.worktree`, so that is done too.
+pub fn set_worktree_config(
+    worktree_path: &Path,
+    git_common_dir: &Path,
+    entries: &[(String, String)],
+) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let get_bool = |key: &str| {
+        Cmd::new("git")
+            .workdir(git_common_dir)
+            .args(&["config", "--local", "--type=bool", "--get", key])
+            .run_and_capture_stdout()
+            .is_ok_and(|v| v == "true")
+    };
+
+    if !get_bool("extensions.worktreeConfig") {
+        let bare = get_bool("core.bare");
+        Cmd::new("git")
+            .workdir(git_common_dir)
+            .args(&["config", "--local", "extensions.worktreeConfig", "true"])
+            .run()
+            .context("Failed to enable extensions.worktreeConfig")?;
+        if bare {
+            let main_config = git_common_dir.join("config.worktree");
+            let main_config = main_config
+                .to_str()
+                .ok_or_else(|| anyhow!("Invalid git directory path"))?;
+            Cmd::new("git")
+                .args(&["config", "--file", main_config, "core.bare", "true"])
+                .run()
+                .context("Failed to move core.bare to config.worktree")?;
+            Cmd::new("git")
+                .workdir(git_common_dir)
+                .args(&["config", "--local", "--unset", "core.bare"])
+                .run()
+                .context("Failed to move core.bare to config.worktree")?;
+        }
+    }
+
+    for (key, value) in entries {
+        Cmd::new("git")
+            .workdir(worktree_path)
+            .args(&["config", "--worktree", key, value])
+            .run()
+            .with_context(|| format!("Failed to set git config '{}' in worktree", key))?;
+    }
+    Ok(())
+}
+
 /// Move a registered worktree to a new path using `git worktree move`.
 ///
 /// Git updates the worktree admin dir's `gitdir` file and the worktree's
@@ -317,8 +359,22 @@ pub fn remove_worktree_meta(handle: &str) -> Result<()> {
 /// For bare repositories with linked worktrees, this returns the bare repo path.
 /// For regular repositories, this returns the first worktree that exists on disk.
 pub fn get_main_worktree_root() -> Result<PathBuf> {
-    let list_str = Cmd::new("git")
-        .args(&["worktree", "list", "--porcelain"])
+    MAIN_ROOTS.get_or_try_cwd(|| query_main_worktree_root(None))
+}
+
+/// Get the main worktree root of the repository containing `dir`.
+pub fn get_main_worktree_root_for(dir: &Path) -> Result<PathBuf> {
+    MAIN_ROOTS.get_or_try(dir, || query_main_worktree_root(Some(dir)))
+}
+
+static MAIN_ROOTS: DirMemo = DirMemo::new();
+
+fn query_main_worktree_root(workdir: Option<&Path>) -> Result<PathBuf> {
+    let mut cmd = Cmd::new("git").args(&["worktree", "list", "--porcelain"]);
+    if let Some(dir) = workdir {
+        cmd = cmd.workdir(dir);
+    }
+    let list_str = cmd
         .run_and_capture_stdout()
         .context("Failed to list worktrees while locating main worktree")?;
 
@@ -1,15 +1,21 @@
 use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use tracing::debug;
 
+use crate::cmd::Cmd;
+use crate::timings::{self, Phase};
+
 #[derive(Debug, Deserialize)]
 pub struct PrDetails {
     #[serde(rename = "headRefName")]
     pub head_ref_name: String,
+    /// Branch the PR targets in the upstream repository
+    #[serde(rename = "baseRefName", default)]
+    pub base_ref_name: Option<String>,
     #[serde(rename = "headRepositoryOwner")]
     pub head_repository_owner: RepositoryOwner,
     pub state: String,
@@ -63,6 +69,10 @@ pub struct PrSummary {
     /// PR URL for opening in browser
     #[serde(default)]
     pub url: Option<String>,
+    /// Head commit the checks were read from, so a refresh can tell if they
+    /// could have changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_oid: Option<String>,
 }
 
 /// Metadata about PR checks (timing, names) separate from aggregated state
@@ -265,6 +275,7 @@ struct PrListResult {
 /// Find a PR by its head ref (e.g., "owner:branch" format).
 /// Returns None if no PR is found, or the first matching PR if found.
 pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+    let _timer = timings::phase(Phase::Forge);
     // gh pr list --head only matches branch name, not owner:branch format
     // So we query by branch and filter by owner in the results
     let output = Command::new("gh")
@@ -321,6 +332,7 @@ pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary
         checks: None,
         check_meta: None,
         url: pr.url,
+        head_oid: None,
     }))
 }
 
@@ -346,6 +358,7 @@ pub fn list_open_prs(repo_root: &Path) -> Result<Vec<PrListEntry>> {
         author: Author,
     }
 
+    let _timer = timings::phase(Phase::Forge);
     let output = Command::new("gh")
         .current_dir(repo_root)
         .args([
@@ -388,8 +401,58 @@ pub fn list_open_prs(repo_root: &Path) -> Result<Vec<PrListEntry>> {
         .collect())
 }
 
+/// A pull request opened in the report period (`workmux report`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenedPr {
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    #[serde(rename = "headRefName")]
+    pub head_ref_name: String,
+}
+
+/// PRs you opened in the repository at `repo_root` on or after `date`
+/// (`YYYY-MM-DD`).
+pub fn list_prs_opened_since(repo_root: &Path, date: &str) -> Result<Vec<OpenedPr>> {
+    let _timer = timings::phase(Phase::Forge);
+    let search = format!("created:>={}", date);
+    let output = Command::new("gh")
+        .current_dir(repo_root)
+        .args([
+            "pr",
+            "list",
+            "--state",
+            "all",
+            "--author",
+            "@me",
+            "--search",
+            &search,
+            "--json",
+            "number,title,url,headRefName",
+            "--limit",
+            "200",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!("GitHub CLI (gh) not found"));
+        }
+        Err(e) => return Err(e).context("Failed to execute gh command"),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("gh pr list failed: {}", stderr.trim()));
+    }
+
+    serde_json::from_slice(&output.stdout).context("Failed to parse gh pr list output")
+}
+
 /// Fetches pull request details using the GitHub CLI
 pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
+    let _timer = timings::phase(Phase::Forge);
     // Fetch PR details using gh CLI
     // Note: We don't pre-check with 'which' because it doesn't respect test PATH modifications
     let output = Command::new("gh")
@@ -398,7 +461,7 @@ pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
             "view",
             &pr_number.to_string(),
             "--json",
-            "headRefName,headRepositoryOwner,state,isDraft,title,author",
+            "headRefName,baseRefName,headRepositoryOwner,state,isDraft,title,author",
         ])
         .output();
 
@@ -448,9 +511,11 @@ struct PrBatchItem {
     status_check_rollup: Vec<CheckRollupItem>,
 }
 
-/// Fetch all PRs for the current repository.
-pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
+/// Fetch all PRs for the repository at `repo_root`.
+pub fn list_prs_in(repo_root: &Path) -> Result<HashMap<String, PrSummary>> {
+    let _timer = timings::phase(Phase::Forge);
     let output = Command::new("gh")
+        .current_dir(repo_root)
         .args([
             "pr",
             "list",
@@ -497,6 +562,7 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
                     checks,
                     check_meta,
                     url: Some(pr.url),
+                    head_oid: None,
                 }
             })
         })
@@ -505,17 +571,79 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
     Ok(pr_map)
 }
 
-/// Fetch PR status for specific branches using a single GraphQL query.
+/// Fetch the pull request for `branch` in the repository at `repo_root`, with
+/// its checks. gh prefers an open PR over closed ones for the same branch.
+/// None when the branch has no PR; gh failures are errors.
+pub fn view_pr_in(repo_root: &Path, branch: &str) -> Result<Option<PrSummary>> {
+    let _timer = timings::phase(Phase::Forge);
+    let output = Command::new("gh")
+        .current_dir(repo_root)
+        .args([
+            "pr",
+            "view",
+            branch,
+            "--json",
+            "number,title,state,isDraft,headRefName,url,statusCheckRollup",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!("GitHub CLI (gh) not found"));
+        }
+        Err(e) => return Err(e).context("Failed to execute gh command"),
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no pull requests found") {
+            return Ok(None);
+        }
+        return Err(anyhow!("gh pr view {} failed: {}", branch, stderr.trim()));
+    }
+
+    let pr: PrBatchItem =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh pr view output")?;
+    let (checks, check_meta) = aggregate_checks(&pr.status_check_rollup);
+    Ok(Some(PrSummary {
+        number: pr.number,
+        title: pr.title,
+        state: pr.state,
+        is_draft: pr.is_draft,
+        checks,
+        check_meta,
+        url: Some(pr.url),
+        head_oid: None,
+    }))
+}
+
+/// What a caller already knows when refreshing PR status, so PRs whose
+/// checks can't have changed skip the detailed check query.
+#[derive(Default)]
+pub struct PrRefresh<'a> {
+    /// Results of the previous refresh for this repo, by branch
+    pub previous: Option<&'a HashMap<String, PrSummary>>,
+    /// Branches on screen, the only ones worth fetching check details for
+    /// (None means all)
+    pub visible: Option<&'a HashSet<String>>,
+}
+
+/// Fetch PR status for specific branches using GraphQL: one light query for
+/// every branch, then check details only for visible PRs whose head commit
+/// moved or whose checks are still running.
 /// Falls back to per-branch REST calls if GraphQL fails.
 pub fn list_prs_for_branches(
     repo_root: &Path,
     branches: &[String],
+    refresh: &PrRefresh,
 ) -> Result<HashMap<String, PrSummary>> {
     if branches.is_empty() {
         return Ok(HashMap::new());
     }
 
-    match list_prs_for_branches_graphql(repo_root, branches) {
+    let _timer = timings::phase(Phase::Forge);
+    match list_prs_for_branches_graphql(repo_root, branches, refresh) {
         Ok(map) => Ok(map),
         Err(e) => {
             debug!("github:graphql batch failed, falling back to per-branch REST: {e}");
@@ -534,17 +662,23 @@ fn branch_to_alias(index: usize, branch: &str) -> String {
     format!("br{}_{}", index, sanitized)
 }
 
-/// Build a GraphQL query fragment for a single branch alias.
-fn build_branch_fragment(alias: &str, branch: &str) -> String {
+/// Build a GraphQL query fragment for a single branch alias. Without
+/// `with_checks` only the head commit and overall check state are asked for.
+fn build_branch_fragment(alias: &str, branch: &str, with_checks: bool) -> String {
     // Escape any quotes in branch name for safety
     let escaped = branch.replace('\\', "\\\\").replace('"', "\\\"");
+    let contexts = if with_checks {
+        " contexts(first: 100) {
+          nodes { __typename ... on CheckRun { name status conclusion startedAt } ... on StatusContext { context state createdAt } }
+        }"
+    } else {
+        ""
+    };
     format!(
         r#"    {alias}: pullRequests(headRefName: "{escaped}", first: 1, states: [OPEN, MERGED, CLOSED], orderBy: {{field: CREATED_AT, direction: DESC}}) {{
       nodes {{
         number title state isDraft headRefName url
-        commits(last: 1) {{ nodes {{ commit {{ statusCheckRollup {{ contexts(first: 100) {{
-          nodes {{ __typename ... on CheckRun {{ name status conclusion startedAt }} ... on StatusContext {{ context state createdAt }} }}
-        }} }} }} }} }}
+        commits(last: 1) {{ nodes {{ commit {{ oid statusCheckRollup {{ state{contexts} }} }} }} }}
       }}
     }}"#
     )
@@ -598,13 +732,20 @@ struct GraphqlCommitNode {
 
 #[derive(Debug, Deserialize)]
 struct GraphqlCommit {
+    #[serde(default)]
+    oid: Option<String>,
     #[serde(rename = "statusCheckRollup")]
     status_check_rollup: Option<GraphqlCheckRollup>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GraphqlCheckRollup {
-    contexts: GraphqlCheckContexts,
+    /// Overall state: SUCCESS, FAILURE, ERROR, PENDING or EXPECTED
+    #[serde(default)]
+    state: Option<String>,
+    /// Individual checks, only present when asked for
+    #[serde(default)]
+    contexts: Option<GraphqlCheckContexts>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -630,6 +771,60 @@ enum GraphqlCheckNode {
     },
 }
 
+impl GraphqlPrNode {
+    fn head_commit(&self) -> Option<&GraphqlCommit> {
+        self.commits.nodes.first().map(|n| &n.commit)
+    }
+
+    fn into_summary(self) -> PrSummary {
+        let head = self.head_commit();
+        let head_oid = head.and_then(|c| c.oid.clone());
+        let rollup = head.and_then(|c| c.status_check_rollup.as_ref());
+        let (checks, check_meta) = match rollup {
+            Some(GraphqlCheckRollup {
+                contexts: Some(contexts),
+                ..
+            }) => {
+                let items: Vec<CheckRollupItem> =
+                    contexts.nodes.iter().map(|n| n.to_rollup_item()).collect();
+                aggregate_checks(&items)
+            }
+            // Without the individual checks the counts are unknown (0/0)
+            Some(GraphqlCheckRollup {
+                state: Some(state), ..
+            }) => (rollup_state(state), None),
+            _ => (None, None),
+        };
+
+        PrSummary {
+            number: self.number,
+            title: self.title,
+            state: self.state,
+            is_draft: self.is_draft,
+            checks,
+            check_meta,
+            url: Some(self.url),
+            head_oid,
+        }
+    }
+}
+
+/// The overall check state of a rollup, without counts.
+fn rollup_state(state: &str) -> Option<CheckState> {
+    match state {
+        "SUCCESS" => Some(CheckState::Success),
+        "FAILURE" | "ERROR" => Some(CheckState::Failure {
+            passed: 0,
+            total: 0,
+        }),
+        "PENDING" | "EXPECTED" => Some(CheckState::Pending {
+            passed: 0,
+            total: 0,
+        }),
+        _ => None,
+    }
+}
+
 impl GraphqlCheckNode {
     fn to_rollup_item(&self) -> CheckRollupItem {
         match self {
@@ -697,20 +892,108 @@ fn get_repo_context(repo_root: &Path) -> Result<(String, String, String)> {
     Ok((ctx.owner.login, ctx.name, hostname))
 }
 
-/// Fetch PR status for multiple branches in a single GraphQL API call.
+/// Fetch PR status for multiple branches with GraphQL. A light query covers
+/// every branch; check details follow in a second query, only for the PRs
+/// `checks_update` says need them.
 fn list_prs_for_branches_graphql(
     repo_root: &Path,
     branches: &[String],
+    refresh: &PrRefresh,
 ) -> Result<HashMap<String, PrSummary>> {
-    let (owner, repo_name, hostname) = get_repo_context(repo_root)?;
+    let repo = get_repo_context(repo_root)?;
+
+    let mut map = HashMap::new();
+    let mut needs_checks = Vec::new();
+    for node in query_prs_graphql(repo_root, &repo, branches, false)? {
+        let rollup = node
+            .head_commit()
+            .and_then(|c| c.status_check_rollup.as_ref());
+        let update = checks_update(
+            rollup.map(|r| r.state.as_deref()),
+            node.head_commit().and_then(|c| c.oid.as_deref()),
+            refresh.previous.and_then(|p| p.get(&node.head_ref_name)),
+            refresh
+                .visible
+                .is_none_or(|visible| visible.contains(&node.head_ref_name)),
+        );
+        let branch = node.head_ref_name.clone();
+        let mut summary = node.into_summary();
+        match update {
+            ChecksUpdate::Fetch => needs_checks.push(branch.clone()),
+            ChecksUpdate::Reuse(previous) => {
+                summary.checks = previous.checks.clone();
+                summary.check_meta = previous.check_meta.clone();
+            }
+            ChecksUpdate::Keep => {}
+        }
+        map.insert(branch, summary);
+    }
 
+    if !needs_checks.is_empty() {
+        debug!(
+            branches = needs_checks.len(),
+            total = branches.len(),
+            "github:fetching check details"
+        );
+        // The light results still stand if this fails
+        match query_prs_graphql(repo_root, &repo, &needs_checks, true) {
+            Ok(nodes) => {
+                for node in nodes {
+                    map.insert(node.head_ref_name.clone(), node.into_summary());
+                }
+            }
+            Err(e) => debug!("github:check details query failed: {e}"),
+        }
+    }
+
+    Ok(map)
+}
+
+/// What a refresh does about a PR's checks after the light query.
+#[derive(Debug)]
+enum ChecksUpdate<'a> {
+    /// Use what the light query returned
+    Keep,
+    /// Same head commit and nothing running: the previous checks still hold
+    Reuse(&'a PrSummary),
+    /// Query the individual checks
+    Fetch,
+}
+
+/// Decide how to refresh a PR's checks. `rollup` is None when the head
+/// commit has no checks, otherwise its overall state.
+fn checks_update<'a>(
+    rollup: Option<Option<&str>>,
+    head_oid: Option<&str>,
+    previous: Option<&'a PrSummary>,
+    visible: bool,
+) -> ChecksUpdate<'a> {
+    let Some(state) = rollup else {
+        return ChecksUpdate::Keep;
+    };
+    let running = matches!(state, Some("PENDING" | "EXPECTED") | None);
+    let unchanged = previous.filter(|p| head_oid.is_some() && p.head_oid.as_deref() == head_oid);
+    match unchanged {
+        Some(previous) if !(running && visible) => ChecksUpdate::Reuse(previous),
+        _ if visible => ChecksUpdate::Fetch,
+        _ => ChecksUpdate::Keep,
+    }
+}
+
+/// Run one batched `pullRequests` query with an alias per branch.
+fn query_prs_graphql(
+    repo_root: &Path,
+    (owner, repo_name, hostname): &(String, String, String),
+    branches: &[String],
+    with_checks: bool,
+) -> Result<Vec<GraphqlPrNode>> {
     // Build query fragments with one alias per branch
     let fragments: Vec<String> = branches
         .iter()
         .enumerate()
         .map(|(i, branch)| {
             let alias = branch_to_alias(i, branch);
-            build_branch_fragment(&alias, branch)
+            build_branch_fragment(&alias, branch, with_checks)
         })
         .collect();
 
@@ -731,7 +1014,7 @@ fn list_prs_for_branches_graphql(
 
     let mut child = Command::new("gh")
         .current_dir(repo_root)
-        .args(["api", "graphql", "--hostname", &hostname, "--input", "-"])
+        .args(["api", "graphql", "--hostname", hostname, "--input", "-"])
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -767,95 +1050,73 @@ fn list_prs_for_branches_graphql(
     let data = response
         .data
         .ok_or_else(|| anyhow!("No data in GraphQL response"))?;
-    let repo = data.repository;
-
-    let mut map = HashMap::new();
-    for (_alias, connection) in repo {
-        for node in connection.nodes {
-            let check_items: Vec<CheckRollupItem> = node
-                .commits
-                .nodes
-                .first()
-                .and_then(|c| c.commit.status_check_rollup.as_ref())
-                .map(|rollup| {
-                    rollup
-                        .contexts
-                        .nodes
-                        .iter()
-                        .map(|n| n.to_rollup_item())
-                        .collect()
-                })
-                .unwrap_or_default();
-
-            map.insert(node.head_ref_name, {
-                let (checks, check_meta) = aggregate_checks(&check_items);
-                PrSummary {
-                    number: node.number,
-                    title: node.title,
-                    state: node.state,
-                    is_draft: node.is_draft,
-                    checks,
-                    check_meta,
-                    url: Some(node.url),
-                }
-            });
-        }
-    }
-
-    Ok(map)
+    Ok(data
+        .repository
+        .into_values()
+        .flat_map(|connection| connection.nodes)
+        .collect())
 }
 
+/// `gh pr list` calls in flight at once in the per-branch fallback.
+const REST_FALLBACK_JOBS: usize = 8;
+
 /// Fallback: fetch PR status one branch at a time using REST-style gh pr list.
+/// Up to `REST_FALLBACK_JOBS` branches are queried concurrently.
 fn list_prs_for_branches_rest(
     repo_root: &Path,
     branches: &[String],
 ) -> Result<HashMap<String, PrSummary>> {
     let mut map = HashMap::new();
 
-    for branch in branches {
-        let output = match Command::new("gh")
-            .current_dir(repo_root)
-            .args([
-                "pr",
-                "list",
-                "--head",
-                branch,
-                "--state",
-                "all",
-                "--json",
-                "number,title,state,isDraft,headRefName,url,statusCheckRollup",
-                "--limit",
-                "1",
-            ])
-            .output()
-        {
-            Ok(output) => output,
-            Err(_) => continue,
-        };
+    for chunk in branches.chunks(REST_FALLBACK_JOBS) {
+        let pending: Vec<_> = chunk
+            .iter()
+            .filter_map(|branch| {
+                Cmd::new("gh")
+                    .workdir(repo_root)
+                    .args(&[
+                        "pr",
+                        "list",
+                        "--head",
+                        branch.as_str(),
+                        "--state",
+                        "all",
+                        "--json",
+                        "number,title,state,isDraft,headRefName,url,statusCheckRollup",
+                        "--limit",
+                        "1",
+                    ])
+                    .spawn()
+                    .ok()
+            })
+            .collect();
 
-        if !output.status.success() {
-            continue;
-        }
+        for pending in pending {
+            let Ok(output) = pending.wait() else {
+                continue;
+            };
 
-        let prs: Vec<PrBatchItem> = match serde_json::from_slice(&output.stdout) {
-            Ok(prs) => prs,
-            Err(_) => continue,
-        };
+            let prs: Vec<PrBatchItem> = match serde_json::from_slice(&output.stdout) {
+                Ok(prs) => prs,
+                Err(_) => continue,
+            };
 
-        if let Some(pr) = prs.into_iter().next() {
-            let (checks, check_meta) = aggregate_checks(&pr.status_check_rollup);
-            map.insert(
-                pr.head_ref_name,
-                PrSummary {
-                    number: pr.number,
-                    title: pr.title,
-                    state: pr.state,
-                    is_draft: pr.is_draft,
-                    checks,
-                    check_meta,
-                    url: Some(pr.url),
-                },
-            );
+            if let Some(pr) = prs.into_iter().next() {
+                let (checks, check_meta) = aggregate_checks(&pr.status_check_rollup);
+                map.insert(
+                    pr.head_ref_name,
+                    PrSummary {
+                        number: pr.number,
+                        title: pr.title,
+                        state: pr.state,
+                        is_draft: pr.is_draft,
+                        checks,
+                        check_meta,
+                        url: Some(pr.url),
+                        head_oid: None,
+                    },
+                );
+            }
         }
     }
 
@@ -889,6 +1150,59 @@ pub fn save_pr_cache(statuses: &HashMap<PathBuf, HashMap<String, PrSummary>>) {
     }
 }
 
+/// Cached PRs for one repo (keyed by its main worktree path), if any.
+pub fn cached_prs(repo_root: &Path) -> Option<HashMap<String, PrSummary>> {
+    load_pr_cache().remove(repo_root)
+}
+
+/// Replace one repo's entry in the PR status cache, leaving other repos as is.
+pub fn update_pr_cache(repo_root: &Path, prs: &HashMap<String, PrSummary>) {
+    let mut cache = load_pr_cache();
+    cache.insert(repo_root.to_path_buf(), prs.clone());
+    save_pr_cache(&cache);
+}
+
+/// A `list_prs_in` call running on a background thread, so the PR fetch
+/// overlaps with local work instead of blocking it. Fresh results are also
+/// written to the PR status cache for the next caller.
+pub struct PrPrefetch {
+    rx: std::sync::mpsc::Receiver<HashMap<String, PrSummary>>,
+}
+
+impl PrPrefetch {
+    /// Start fetching PRs for the repo whose main worktree is `repo_root`.
+    pub fn spawn(repo_root: PathBuf) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            match list_prs_in(&repo_root) {
+                Ok(prs) => {
+                    // Empty also covers gh failures, which shouldn't wipe the cache
+                    if !prs.is_empty() {
+                        update_pr_cache(&repo_root, &prs);
+                    }
+                    crate::tracker::link_prs(&repo_root, &prs);
+                    let _ = tx.send(prs);
+                }
+                Err(e) => {
+                    debug!("github:background PR fetch failed: {e}");
+                    let _ = tx.send(HashMap::new());
+                }
+            }
+        });
+        Self { rx }
+    }
+
+    /// The fetched PRs if the fetch has already finished.
+    pub fn try_take(&self) -> Option<HashMap<String, PrSummary>> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Block until the fetch finishes.
+    pub fn wait(self) -> HashMap<String, PrSummary> {
+        self.rx.recv().unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1201,4 +1515,97 @@ mod tests {
         // started_at should be the pending check's time (2026-03-24T14:05:00Z)
         assert_eq!(meta.started_at, Some(1774361100));
     }
+
+    fn summary_at(head_oid: &str) -> PrSummary {
+        PrSummary {
+            number: 7,
+            title: "Fix login".to_string(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            checks: Some(CheckState::Failure {
+                passed: 2,
+                total: 3,
+            }),
+            check_meta: None,
+            url: None,
+            head_oid: Some(head_oid.to_string()),
+        }
+    }
+
+    #[test]
+    fn checks_update_reuses_finished_checks_on_same_head() {
+        let previous = summary_at("abc");
+        for visible in [true, false] {
+            assert!(matches!(
+                checks_update(Some(Some("FAILURE")), Some("abc"), Some(&previous), visible),
+                ChecksUpdate::Reuse(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn checks_update_fetches_visible_running_or_moved_checks() {
+        let previous = summary_at("abc");
+        assert!(matches!(
+            checks_update(Some(Some("PENDING")), Some("abc"), Some(&previous), true),
+            ChecksUpdate::Fetch
+        ));
+        assert!(matches!(
+            checks_update(Some(Some("FAILURE")), Some("def"), Some(&previous), true),
+            ChecksUpdate::Fetch
+        ));
+        assert!(matches!(
+            checks_update(Some(Some("SUCCESS")), Some("def"), None, true),
+            ChecksUpdate::Fetch
+        ));
+    }
+
+    #[test]
+    fn checks_update_skips_off_screen_and_checkless_prs() {
+        let previous = summary_at("abc");
+        assert!(matches!(
+            checks_update(Some(Some("FAILURE")), Some("def"), Some(&previous), false),
+            ChecksUpdate::Keep
+        ));
+        assert!(matches!(
+            checks_update(None, Some("abc"), Some(&previous), true),
+            ChecksUpdate::Keep
+        ));
+    }
+
+    #[test]
+    fn light_node_summary_maps_the_rollup_state() {
+        let node = |state: &str| -> GraphqlPrNode {
+            serde_json::from_value(serde_json::json!({
+                "number": 7,
+                "title": "Fix login",
+                "state": "OPEN",
+                "isDraft": false,
+                "headRefName": "fix-login",
+                "url": "https://github.com/o/r/pull/7",
+                "commits": { "nodes": [{ "commit": {
+                    "oid": "abc",
+                    "statusCheckRollup": { "state": state }
+                }}]}
+            }))
+            .unwrap()
+        };
+        let passed = node("SUCCESS").into_summary();
+        assert_eq!(passed.checks, Some(CheckState::Success));
+        assert_eq!(passed.head_oid.as_deref(), Some("abc"));
+        assert_eq!(
+            node("FAILURE").into_summary().checks,
+            Some(CheckState::Failure {
+                passed: 0,
+                total: 0
+            })
+        );
+        assert_eq!(
+            node("EXPECTED").into_summary().checks,
+            Some(CheckState::Pending {
+                passed: 0,
+                total: 0
+            })
+        );
+    }
 }
@@ -0,0 +1,129 @@
+//! Hints printed after an error: the closest match for a mistyped worktree
+//! name, and what to try next for common failures.
+
+use crate::git::{self, NotInRepo, WorktreeNotFound};
+use crate::workflow::types::{AgentNotFound, TargetNotOpen};
+
+/// Hint lines for `err`, empty when there's nothing useful to add.
+pub fn for_error(err: &anyhow::Error) -> Vec<String> {
+    if let Some(WorktreeNotFound(name)) = find(err) {
+        let mut hints = Vec::new();
+        if let Some(close) = closest(name, &worktree_names()) {
+            hints.push(format!("did you mean '{}'?", close));
+        }
+        hints.push("try: workmux list".to_string());
+        return hints;
+    }
+    if find::<NotInRepo>(err).is_some() {
+        return vec!["try: cd into a repository, or pass --repo <name|path>".to_string()];
+    }
+    if let Some(e) = find::<TargetNotOpen>(err) {
+        return vec![format!("try: workmux open {}", e.handle)];
+    }
+    if let Some(e) = find::<AgentNotFound>(err) {
+        return vec![
+            format!("try: workmux open {} to start its agent", e.handle),
+            "try: workmux status to see running agents".to_string(),
+        ];
+    }
+    Vec::new()
+}
+
+/// The first error of type `T` anywhere in the chain.
+fn find<T: std::error::Error + Send + Sync + 'static>(err: &anyhow::Error) -> Option<&T> {
+    err.chain().find_map(|e| e.downcast_ref::<T>())
+}
+
+/// Worktree handles and branches, excluding the main worktree.
+fn worktree_names() -> Vec<String> {
+    let mut names = Vec::new();
+    for (path, branch) in git::list_worktrees()
+        .unwrap_or_default()
+        .into_iter()
+        .skip(1)
+    {
+        if let Some(handle) = path.file_name().and_then(|n| n.to_str()) {
+            names.push(handle.to_string());
+        }
+        names.push(branch);
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The candidate closest to `input`, if it's close enough to be a typo.
+fn closest<'a>(input: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let max_distance = (input.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(input, c), c))
+        .filter(|(distance, c)| *distance <= max_distance || c.contains(input))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c.as_str())
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("auth", "auth"), 0);
+        assert_eq!(edit_distance("feature-ath", "feature-auth"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_matches_typos_and_substrings() {
+        let names = vec![
+            "feature-auth".to_string(),
+            "fix-login".to_string(),
+            "api".to_string(),
+        ];
+        assert_eq!(closest("feature-ath", &names), Some("feature-auth"));
+        assert_eq!(closest("fix-lgoin", &names), Some("fix-login"));
+        assert_eq!(closest("login", &names), Some("fix-login"));
+        assert_eq!(closest("apo", &names), Some("api"));
+        assert_eq!(closest("database", &names), None);
+    }
+
+    #[test]
+    fn hints_for_typed_errors() {
+        let err = anyhow::Error::from(NotInRepo).context("Failed to list worktrees");
+        assert_eq!(
+            for_error(&err),
+            ["try: cd into a repository, or pass --repo <name|path>"]
+        );
+
+        let err = anyhow::Error::from(TargetNotOpen {
+            kind: "window",
+            target: "wm-api".to_string(),
+            handle: "api".to_string(),
+        });
+        assert_eq!(for_error(&err), ["try: workmux open api"]);
+
+        assert!(for_error(&anyhow::anyhow!("something else")).is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+//! Non-interactive mode for scripts and CI (`--yes`, `WORKMUX_NONINTERACTIVE=1`).
+//!
+//! No prompt is shown. Confirming what the command was asked to do proceeds,
+//! while anything that would lose work is refused, and wizards and pickers
+//! are skipped.
+
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Turn on non-interactive mode for the rest of the process (`--yes`).
+pub fn enable() {
+    NON_INTERACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--yes` was passed or `WORKMUX_NONINTERACTIVE` is set.
+pub fn is_enabled() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+        || env_enabled(std::env::var("WORKMUX_NONINTERACTIVE").ok().as_deref())
+}
+
+/// Whether a prompt may be shown: stdin is a terminal and non-interactive
+/// mode is off.
+pub fn can_prompt() -> bool {
+    !is_enabled() && io::stdin().is_terminal()
+}
+
+fn env_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_values() {
+        assert!(!env_enabled(None));
+        assert!(!env_enabled(Some("")));
+        assert!(!env_enabled(Some("0")));
+        assert!(!env_enabled(Some("False")));
+        assert!(env_enabled(Some("1")));
+        assert!(env_enabled(Some("true")));
+    }
+}
@@ -42,6 +42,13 @@ pub fn generate_branch_name(
     Ok(branch_name)
 }
 
+/// Run the generator on `prompt` and return its answer, for free-form text
+/// such as the summary in `workmux report --summarize`.
+pub fn complete(prompt: &str, model: Option<&str>, command: Option<&str>) -> Result<String> {
+    let raw = run_generator_command(command, model, prompt)?;
+    Ok(strip_ansi(&raw).trim().to_string())
+}
+
 fn run_generator_command(
     command: Option<&str>,
     model: Option<&str>,
@@ -1,15 +1,22 @@
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::OnceLock;
 
 use anyhow::{Context, Result, anyhow};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_appender::rolling;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, fmt};
 
+use crate::redact::RedactingWriter;
 use crate::sandbox::guest::is_sandbox_guest;
 
+/// Log files are named `workmux.<date>.log`, one per day
+const LOG_PREFIX: &str = "workmux";
+const LOG_SUFFIX: &str = "log";
+/// Days of logs kept; older files are deleted on rotation
+const MAX_LOG_FILES: usize = 7;
+
 static INIT: OnceLock<()> = OnceLock::new();
 static GUARD: OnceLock<WorkerGuard> = OnceLock::new();
 
@@ -32,24 +39,30 @@ pub fn init() -> Result<()> {
 }
 
 fn init_inner() -> Result<()> {
-    let log_path = determine_log_path()?;
-    if let Some(parent) = log_path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create log directory at {}", parent.display()))?;
-    }
-
-    let (directory, file_name) = split_path(&log_path)?;
-    let file_appender = rolling::never(directory, file_name);
+    let directory = log_dir()?;
+    fs::create_dir_all(&directory)
+        .with_context(|| format!("Failed to create log directory at {}", directory.display()))?;
+
+    let file_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&directory)
+        .context("Failed to open log file")?;
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
     let _ = GUARD.set(guard);
 
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    // WORKMUX_LOG sets the level (e.g. debug, or workmux=trace); RUST_LOG still works
+    let env_filter = EnvFilter::try_from_env("WORKMUX_LOG")
+        .or_else(|_| EnvFilter::try_from_default_env())
+        .unwrap_or_else(|_| EnvFilter::new("info"));
 
     tracing_subscriber::registry()
         .with(env_filter)
         .with(
             fmt::layer()
-                .with_writer(non_blocking)
+                .with_writer(move || RedactingWriter(non_blocking.clone()))
                 .with_ansi(false)
                 .with_target(false),
         )
@@ -59,25 +72,55 @@ fn init_inner() -> Result<()> {
     Ok(())
 }
 
-fn determine_log_path() -> Result<PathBuf> {
+/// Directory holding the log files: the state dir, or the current
+/// directory if home cannot be determined.
+pub fn log_dir() -> Result<PathBuf> {
     if let Ok(state_dir) = crate::xdg::state_dir() {
-        return Ok(state_dir.join("workmux.log"));
+        return Ok(state_dir);
     }
+    Ok(std::env::current_dir()?)
+}
 
-    // Fallback to current directory if home cannot be determined
-    Ok(std::env::current_dir()?.join("workmux.log"))
+/// Log files, oldest first.
+pub fn log_files() -> Result<Vec<PathBuf>> {
+    let dir = log_dir()?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(anyhow!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(is_log_file)
+        })
+        .collect();
+    // Dates in the names sort chronologically
+    files.sort();
+    Ok(files)
 }
 
-fn split_path(path: &Path) -> Result<(PathBuf, &str)> {
-    let file_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow!("Invalid log file name"))?;
+fn is_log_file(name: &str) -> bool {
+    name.strip_prefix(LOG_PREFIX)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(|rest| rest.strip_suffix(LOG_SUFFIX))
+        .and_then(|date| date.strip_suffix('.'))
+        .is_some_and(|date| {
+            !date.is_empty() && date.chars().all(|c| c.is_ascii_digit() || c == '-')
+        })
+}
 
-    let dir = path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok((dir, file_name))
+    #[test]
+    fn log_file_names() {
+        assert!(is_log_file("workmux.2026-10-16.log"));
+        assert!(!is_log_file("workmux.log"));
+        assert!(!is_log_file("workmux.log.2026-10-16"));
+        assert!(!is_log_file("state.json"));
+    }
 }
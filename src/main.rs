@@ -7,23 +7,33 @@ mod command;
 mod config;
 mod git;
 mod github;
+mod hints;
+mod interactive;
 mod llm;
 mod logger;
 mod markdown;
 mod multiplexer;
 mod naming;
 mod nerdfont;
+mod notification;
+mod platform;
 mod prompt;
+mod redact;
 mod sandbox;
+mod secrets;
 mod shell;
 mod skills;
 mod spinner;
 mod state;
 mod template;
+mod timings;
 mod tips;
 mod tmux_style;
+mod tracker;
+mod transport;
 mod ui;
 mod util;
+mod vcs;
 mod workflow;
 mod xdg;
 
@@ -34,14 +44,25 @@ fn main() -> Result<()> {
     logger::init()?;
     info!(args = ?std::env::args().collect::<Vec<_>>(), "workmux start");
 
-    match cli::run() {
+    let result = cli::run();
+    timings::print_report();
+    match result {
         Ok(result) => {
             info!("workmux finished successfully");
             Ok(result)
         }
         Err(err) => {
             error!(error = ?err, "workmux failed");
-            Err(err)
+            eprintln!("Error: {:?}", err);
+            let hints = hints::for_error(&err);
+            if !hints.is_empty() {
+                eprintln!();
+                for hint in hints {
+                    eprintln!("  {}", hint);
+                }
+            }
+            // Failures scripts branch on get their own exit codes
+            std::process::exit(workflow::types::failure_exit_code(&err).unwrap_or(1));
         }
     }
 }
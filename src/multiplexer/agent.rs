@@ -259,6 +259,11 @@ static PROFILES: &[&dyn AgentProfile] = &[
     &VibeProfile,
 ];
 
+/// Names of the built-in agent profiles, e.g. for offering a choice of agent.
+pub fn profile_names() -> impl Iterator<Item = &'static str> {
+    PROFILES.iter().map(|p| p.name())
+}
+
 /// Check if a command matches a known agent profile.
 ///
 /// Returns true for commands whose executable stem matches a built-in agent
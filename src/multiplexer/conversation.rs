@@ -1,16 +1,17 @@
 //! Agent-specific conversation forking for resuming sessions across worktrees.
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Information about a conversation session
 #[derive(Debug, Clone)]
 pub struct SessionInfo {
-    /// Session UUID (stem of the .jsonl file)
+    /// Session ID (for Claude, the stem of the .jsonl file)
     pub id: String,
-    /// Full path to the .jsonl file
+    /// Full path to the session file
     pub path: PathBuf,
     /// Last modification time
     pub timestamp: SystemTime,
@@ -34,6 +35,18 @@ pub trait ConversationForker: Send + Sync {
 
     /// CLI args to resume a specific session (e.g., ["--resume", uuid])
     fn resume_args(&self, session_id: &str) -> Vec<String>;
+
+    /// Whether conversations can be copied into another worktree.
+    fn can_fork(&self) -> bool {
+        true
+    }
+
+    /// Whether the agent's continue flag may pick a conversation from another
+    /// worktree of the same repository, so continuing has to name the
+    /// worktree's latest session instead.
+    fn continue_spans_worktrees(&self) -> bool {
+        false
+    }
 }
 
 /// Claude Code conversation forker
@@ -148,23 +161,173 @@ impl ConversationForker for ClaudeForker {
     }
 }
 
+/// OpenCode conversations, read from its local session store.
+///
+/// Sessions live in `<data dir>/storage/session/<project id>/<session id>.json`.
+/// OpenCode identifies a project by its repository, so all worktrees of a
+/// repository share one project and are told apart by the session's
+/// `directory`.
+pub struct OpenCodeForker {
+    data_dir: PathBuf,
+}
+
+/// The fields of an OpenCode session file that are used here.
+#[derive(Deserialize)]
+struct OpenCodeSession {
+    id: String,
+    directory: PathBuf,
+    /// Set for sessions spawned by subagents
+    #[serde(default, rename = "parentID")]
+    parent_id: Option<String>,
+    time: OpenCodeSessionTime,
+}
+
+#[derive(Deserialize)]
+struct OpenCodeSessionTime {
+    /// Unix time in milliseconds
+    updated: u64,
+}
+
+impl OpenCodeForker {
+    pub fn new() -> Self {
+        let data_dir = std::env::var("XDG_DATA_HOME")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                home::home_dir()
+                    .expect("could not determine home directory")
+                    .join(".local/share")
+            })
+            .join("opencode");
+        Self { data_dir }
+    }
+
+    fn sessions_dir(&self) -> PathBuf {
+        self.data_dir.join("storage").join("session")
+    }
+
+    /// List the top-level sessions started in a worktree, most recently
+    /// updated first.
+    fn list_sessions(&self, worktree_path: &Path) -> Result<Vec<SessionInfo>> {
+        let sessions_dir = self.sessions_dir();
+        if !sessions_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut sessions = Vec::new();
+        for project in fs::read_dir(&sessions_dir)? {
+            let project = project?.path();
+            if !project.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&project)? {
+                let path = entry?.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                // Skip files OpenCode is writing or doesn't recognize
+                let Some(session) = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<OpenCodeSession>(&content).ok())
+                else {
+                    continue;
+                };
+                if session.parent_id.is_none() && session.directory == worktree_path {
+                    sessions.push(SessionInfo {
+                        id: session.id,
+                        path,
+                        timestamp: UNIX_EPOCH + Duration::from_millis(session.time.updated),
+                    });
+                }
+            }
+        }
+
+        sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(sessions)
+    }
+}
+
+impl ConversationForker for OpenCodeForker {
+    fn find_latest_conversation(&self, worktree_path: &Path) -> Result<Option<SessionInfo>> {
+        Ok(self.list_sessions(worktree_path)?.into_iter().next())
+    }
+
+    fn find_conversation(
+        &self,
+        worktree_path: &Path,
+        session_id: &str,
+    ) -> Result<Option<SessionInfo>> {
+        Ok(self
+            .list_sessions(worktree_path)?
+            .into_iter()
+            .find(|s| s.id == session_id || s.id.starts_with(session_id)))
+    }
+
+    fn fork_conversation(&self, _session: &SessionInfo, _target_worktree: &Path) -> Result<String> {
+        bail!("forking OpenCode conversations isn't supported")
+    }
+
+    fn resume_args(&self, session_id: &str) -> Vec<String> {
+        vec!["--session".to_string(), session_id.to_string()]
+    }
+
+    fn can_fork(&self) -> bool {
+        false
+    }
+
+    fn continue_spans_worktrees(&self) -> bool {
+        true
+    }
+}
+
 /// Resolve a conversation forker for the given agent name.
-/// Returns None if the agent doesn't support conversation forking.
+/// Returns None if workmux can't find the agent's conversations. Check
+/// `can_fork` before forking.
 pub fn resolve_forker(agent_name: &str) -> Option<Box<dyn ConversationForker>> {
     // Normalize: strip path, take basename
-    let basename = agent_name.rsplit('/').next().unwrap_or(agent_name);
+    let basename = agent_name.rsplit(['/', '\\']).next().unwrap_or(agent_name);
     let name = basename
         .split_whitespace()
         .next()
         .unwrap_or(basename)
         .to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
 
-    match name.as_str() {
+    match name {
         "claude" => Some(Box::new(ClaudeForker::new())),
+        "opencode" => Some(Box::new(OpenCodeForker::new())),
         _ => None,
     }
 }
 
+/// Args resuming the latest conversation in a worktree: the session its
+/// SessionStart hook recorded for the branch, or for agents whose continue
+/// flag doesn't stay within the worktree, the most recently updated one.
+/// None when the agent's continue flag will do, or it has no conversation
+/// there yet.
+pub fn latest_session_args(agent_name: &str, worktree_path: &Path) -> Option<String> {
+    let forker = resolve_forker(agent_name)?;
+    if let Some(id) = recorded_session(worktree_path)
+        && let Ok(Some(session)) = forker.find_conversation(worktree_path, &id)
+    {
+        return Some(forker.resume_args(&session.id).join(" "));
+    }
+    if !forker.continue_spans_worktrees() {
+        return None;
+    }
+    let session = forker.find_latest_conversation(worktree_path).ok()??;
+    Some(forker.resume_args(&session.id).join(" "))
+}
+
+/// The session ID recorded for a worktree's branch, if any.
+fn recorded_session(worktree_path: &Path) -> Option<String> {
+    let branch = crate::git::get_branch_for_worktree(worktree_path).ok()?;
+    let repo = crate::git::get_main_worktree_root_for(worktree_path).ok()?;
+    let record = crate::state::sessions::load_session(&repo, &branch).ok()??;
+    Some(record.id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +361,7 @@ mod tests {
         assert!(resolve_forker("claude").is_some());
         assert!(resolve_forker("Claude").is_some());
         assert!(resolve_forker("/usr/bin/claude --flag").is_some());
+        assert!(resolve_forker("C:\\Tools\\claude.exe --flag").is_some());
     }
 
     #[test]
@@ -296,4 +460,74 @@ mod tests {
         assert!(session.is_some());
         assert_eq!(session.unwrap().id, "abc123-def456");
     }
+
+    fn write_opencode_session(
+        forker: &OpenCodeForker,
+        id: &str,
+        directory: &str,
+        parent: Option<&str>,
+        updated: u64,
+    ) {
+        let project_dir = forker.sessions_dir().join("abc123");
+        fs::create_dir_all(&project_dir).unwrap();
+        let session = serde_json::json!({
+            "id": id,
+            "projectID": "abc123",
+            "directory": directory,
+            "parentID": parent,
+            "title": "test",
+            "time": { "created": updated, "updated": updated },
+        });
+        fs::write(
+            project_dir.join(format!("{}.json", id)),
+            session.to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_opencode_finds_latest_session_of_worktree() {
+        let tmp = tempfile::tempdir().unwrap();
+        let forker = OpenCodeForker {
+            data_dir: tmp.path().to_path_buf(),
+        };
+        let worktree = Path::new("/repo__worktrees/feature");
+        write_opencode_session(&forker, "ses_old", "/repo__worktrees/feature", None, 1000);
+        write_opencode_session(&forker, "ses_new", "/repo__worktrees/feature", None, 2000);
+        // A subagent's session and another worktree's are skipped
+        write_opencode_session(
+            &forker,
+            "ses_child",
+            "/repo__worktrees/feature",
+            Some("ses_new"),
+            3000,
+        );
+        write_opencode_session(&forker, "ses_other", "/repo", None, 4000);
+
+        let latest = forker.find_latest_conversation(worktree).unwrap().unwrap();
+        assert_eq!(latest.id, "ses_new");
+        assert_eq!(
+            forker.resume_args(&latest.id),
+            vec!["--session".to_string(), "ses_new".to_string()]
+        );
+        let old = forker
+            .find_conversation(worktree, "ses_o")
+            .unwrap()
+            .unwrap();
+        assert_eq!(old.id, "ses_old");
+        assert!(
+            forker
+                .find_latest_conversation(Path::new("/elsewhere"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_resolve_forker_opencode_resumes_but_cannot_fork() {
+        let forker = resolve_forker("/usr/local/bin/opencode").unwrap();
+        assert!(!forker.can_fork());
+        assert!(forker.continue_spans_worktrees());
+        assert!(resolve_forker("claude").unwrap().can_fork());
+    }
 }
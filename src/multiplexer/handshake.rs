@@ -7,12 +7,26 @@ use anyhow::{Context, Result, anyhow};
 #[cfg(unix)]
 use nix::sys::stat::Mode;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, trace, warn};
 
 use crate::cmd::Cmd;
 
+/// `<pid>_<nanos>_<seq>`, unique even for handshakes created at the same
+/// instant on different threads (e.g. `resurrect` setting up windows in
+/// parallel).
+fn unique_suffix() -> String {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let seq = SEQ.fetch_add(1, Ordering::Relaxed);
+    format!("{}_{}_{}", std::process::id(), nanos, seq)
+}
+
 /// Trait for pane handshake mechanisms.
 ///
 /// A handshake ensures the shell has started in a pane before sending commands.
@@ -61,12 +75,7 @@ impl TmuxHandshake {
     /// The channel must be locked before spawning the pane to ensure we don't
     /// miss the signal even if the shell starts instantly.
     pub fn new() -> Result<Self> {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        let pid = std::process::id();
-        let channel = format!("wm_ready_{}_{}", pid, nanos);
+        let channel = format!("wm_ready_{}", unique_suffix());
 
         // Lock the channel (ensures we don't miss the signal)
         Cmd::new("tmux")
@@ -111,8 +120,8 @@ impl PaneHandshake for TmuxHandshake {
     fn wait(self: Box<Self>) -> Result<()> {
         debug!(channel = %self.channel, "tmux:handshake start");
 
-        let mut child = std::process::Command::new("tmux")
-            .args(["wait-for", "-L", &self.channel])
+        let mut child = crate::transport::current()
+            .command("tmux", &["wait-for", "-L", &self.channel], None)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
             .spawn()
@@ -199,13 +208,7 @@ impl UnixPipeHandshake {
     ///
     /// Creates a named pipe (FIFO) that the shell will write to when ready.
     pub fn new() -> Result<Self> {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        let pid = std::process::id();
-
-        let pipe_path = std::env::temp_dir().join(format!("workmux_pipe_{}_{}", pid, nanos));
+        let pipe_path = std::env::temp_dir().join(format!("workmux_pipe_{}", unique_suffix()));
 
         // Create FIFO with 0o600 permissions (owner read/write only)
         let mode = Mode::S_IRUSR | Mode::S_IWUSR;
@@ -297,3 +300,79 @@ impl Drop for UnixPipeHandshake {
         let _ = std::fs::remove_file(&self.pipe_path);
     }
 }
+
+/// Handshake for backends without wait-for: a named pipe on Unix, a
+/// marker file on Windows.
+pub fn shell_handshake() -> Result<Box<dyn PaneHandshake>> {
+    #[cfg(unix)]
+    {
+        Ok(Box::new(UnixPipeHandshake::new()?))
+    }
+    #[cfg(windows)]
+    {
+        Ok(Box::new(FileHandshake::new()))
+    }
+}
+
+/// Marker file handshake for Windows, which has no FIFOs. The shell writes
+/// the file when it starts and `wait` polls for it.
+#[cfg(windows)]
+pub struct FileHandshake {
+    ready_path: PathBuf,
+}
+
+#[cfg(windows)]
+impl FileHandshake {
+    pub fn new() -> Self {
+        let ready_path = std::env::temp_dir().join(format!("workmux_ready_{}", unique_suffix()));
+        Self { ready_path }
+    }
+
+    /// The marker path as Git Bash's sh expects it (forward slashes)
+    fn sh_path(&self) -> String {
+        self.ready_path.to_string_lossy().replace('\\', "/")
+    }
+}
+
+#[cfg(windows)]
+impl PaneHandshake for FileHandshake {
+    fn wrapper_command(&self, shell: &str) -> String {
+        let escaped_shell = super::util::escape_for_sh_c_inner_single_quote(shell);
+        format!(
+            "sh -c 'echo ready > '\\''{}'\\''; exec '\\''{}'\\'' -l'",
+            self.sh_path(),
+            escaped_shell
+        )
+    }
+
+    fn script_content(&self, shell: &str) -> String {
+        format!("echo ready > '{}'; exec '{}' -l", self.sh_path(), shell)
+    }
+
+    fn wait(self: Box<Self>) -> Result<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+        let start = Instant::now();
+        let timeout = Duration::from_secs(HANDSHAKE_TIMEOUT_SECS);
+        loop {
+            // Non-empty once the shell's `echo` has finished writing
+            if std::fs::metadata(&self.ready_path).is_ok_and(|m| m.len() > 0) {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                return Err(anyhow!(
+                    "Pane handshake timed out after {}s - shell may have failed to start",
+                    HANDSHAKE_TIMEOUT_SECS
+                ));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for FileHandshake {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.ready_path);
+    }
+}
@@ -0,0 +1,281 @@
+//! Headless backend for running without a multiplexer (CI runners, cron,
+//! scripts).
+//!
+//! Selected with `WORKMUX_BACKEND=none`, or automatically when `$CI` is set
+//! and no multiplexer is detected. Worktree creation, hooks, file operations
+//! and merges work as usual; window and pane setup is skipped. Queries report
+//! no windows or panes, cleanup of windows is a no-op, and anything that needs
+//! a live pane (sending keys, capturing output) returns an error.
+
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::config::SplitDirection;
+
+use super::types::{CreateSessionParams, CreateWindowParams, LivePaneInfo};
+use super::{Multiplexer, PaneHandshake};
+
+/// Backend used when there is no multiplexer.
+pub struct HeadlessBackend {
+    _private: (),
+}
+
+impl Default for HeadlessBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeadlessBackend {
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+
+    fn no_panes(action: &str) -> anyhow::Error {
+        anyhow!(
+            "Cannot {} without a multiplexer (WORKMUX_BACKEND=none)",
+            action
+        )
+    }
+}
+
+impl Multiplexer for HeadlessBackend {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn is_headless(&self) -> bool {
+        true
+    }
+
+    fn supports_preview(&self) -> bool {
+        false
+    }
+
+    // === Server/Session ===
+
+    fn is_running(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn current_pane_id(&self) -> Option<String> {
+        None
+    }
+
+    fn active_pane_id(&self) -> Option<String> {
+        None
+    }
+
+    fn get_client_active_pane_path(&self) -> Result<PathBuf> {
+        Ok(std::env::current_dir()?)
+    }
+
+    fn instance_id(&self) -> String {
+        "headless".to_string()
+    }
+
+    // === Window/Session Management ===
+
+    fn create_window(&self, _params: CreateWindowParams) -> Result<String> {
+        Err(Self::no_panes("create a window"))
+    }
+
+    fn create_session(&self, _params: CreateSessionParams) -> Result<String> {
+        Err(Self::no_panes("create a session"))
+    }
+
+    fn switch_to_session(&self, _prefix: &str, _name: &str) -> Result<()> {
+        Err(Self::no_panes("switch sessions"))
+    }
+
+    fn session_exists(&self, _full_name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn kill_session(&self, _full_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn kill_window(&self, _full_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn schedule_window_close(&self, _full_name: &str, _delay: Duration) -> Result<()> {
+        Ok(())
+    }
+
+    fn schedule_session_close(&self, _full_name: &str, _delay: Duration) -> Result<()> {
+        Ok(())
+    }
+
+    fn run_deferred_script(&self, script: &str) -> Result<()> {
+        // No run-shell to hand it to, so run it detached from this process
+        Command::new("sh")
+            .args(["-c", script])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        Ok(())
+    }
+
+    fn shell_select_window_cmd(&self, _full_name: &str) -> Result<String> {
+        Ok("true".to_string())
+    }
+
+    fn shell_kill_window_cmd(&self, _full_name: &str) -> Result<String> {
+        Ok("true".to_string())
+    }
+
+    fn shell_switch_session_cmd(&self, _full_name: &str) -> Result<String> {
+        Ok("true".to_string())
+    }
+
+    fn shell_kill_session_cmd(&self, _full_name: &str) -> Result<String> {
+        Ok("true".to_string())
+    }
+
+    fn select_window(&self, _prefix: &str, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn window_exists(&self, _prefix: &str, _name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn window_exists_by_full_name(&self, _full_name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn current_window_name(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn get_all_window_names(&self) -> Result<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+
+    fn get_all_session_names(&self) -> Result<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+
+    fn filter_active_windows(&self, _windows: &[String]) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn find_last_window_with_prefix(&self, _prefix: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn find_last_window_with_base_handle(
+        &self,
+        _prefix: &str,
+        _base_handle: &str,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn wait_until_windows_closed(&self, _full_window_names: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    fn wait_until_session_closed(&self, _full_session_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    // === Pane Management ===
+
+    fn select_pane(&self, _pane_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn switch_to_pane(&self, _pane_id: &str, _window_hint: Option<&str>) -> Result<()> {
+        Err(Self::no_panes("switch to a pane"))
+    }
+
+    fn kill_pane(&self, _pane_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn respawn_pane(&self, _pane_id: &str, _cwd: &Path, _cmd: Option<&str>) -> Result<String> {
+        Err(Self::no_panes("respawn a pane"))
+    }
+
+    fn capture_pane(&self, _pane_id: &str, _lines: u16) -> Option<String> {
+        None
+    }
+
+    // === Text I/O ===
+
+    fn send_keys(&self, _pane_id: &str, _command: &str) -> Result<()> {
+        Err(Self::no_panes("send keys"))
+    }
+
+    fn send_keys_to_agent(
+        &self,
+        _pane_id: &str,
+        _command: &str,
+        _agent: Option<&str>,
+    ) -> Result<()> {
+        Err(Self::no_panes("send keys"))
+    }
+
+    fn send_key(&self, _pane_id: &str, _key: &str) -> Result<()> {
+        Err(Self::no_panes("send keys"))
+    }
+
+    fn paste_multiline(&self, _pane_id: &str, _content: &str) -> Result<()> {
+        Err(Self::no_panes("paste into a pane"))
+    }
+
+    // === Shell ===
+
+    fn get_default_shell(&self) -> Result<String> {
+        std::env::var("SHELL").or_else(|_| Ok("/bin/sh".to_string()))
+    }
+
+    fn create_handshake(&self) -> Result<Box<dyn PaneHandshake>> {
+        Err(Self::no_panes("start a pane"))
+    }
+
+    // === Status ===
+
+    fn set_status(&self, _pane_id: &str, _icon: &str, _auto_clear_on_focus: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn clear_status(&self, _pane_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn ensure_status_format(&self, _pane_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    // === Pane Setup ===
+
+    fn split_pane(
+        &self,
+        _target_pane_id: &str,
+        _direction: &SplitDirection,
+        _cwd: &Path,
+        _size: Option<u16>,
+        _percentage: Option<u8>,
+        _command: Option<&str>,
+    ) -> Result<String> {
+        Err(Self::no_panes("split a pane"))
+    }
+
+    // === State Reconciliation ===
+
+    fn get_live_pane_info(&self, _pane_id: &str) -> Result<Option<LivePaneInfo>> {
+        Ok(None)
+    }
+
+    fn get_all_live_pane_info(&self) -> Result<HashMap<String, LivePaneInfo>> {
+        Ok(HashMap::new())
+    }
+}
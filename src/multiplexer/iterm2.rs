@@ -0,0 +1,662 @@
+//! iTerm2 backend implementation for the Multiplexer trait.
+//!
+//! Talks to iTerm2 through its Python API: every operation runs a small
+//! bundled script (`iterm2_helper.py`) that connects over iTerm2's websocket,
+//! does one thing and prints the result. The script is written to the cache
+//! directory on first use.
+//!
+//! Note on terminology:
+//! - iTerm2 "session" = workmux "pane" (a terminal split)
+//! - iTerm2 "tab" = workmux "window" (a named tab)
+//! - iTerm2 "window" = the actual window on screen
+
+use crate::cmd::Cmd;
+use crate::config::SplitDirection;
+use crate::shell::shell_quote;
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use super::agent;
+use super::handshake;
+use super::types::*;
+use super::util;
+use super::{Multiplexer, PaneHandshake};
+
+/// Script run for every iTerm2 operation
+const HELPER: &str = include_str!("iterm2_helper.py");
+
+/// iTerm2 session (= workmux pane) from the helper's `ls`
+#[derive(Debug, Clone, Deserialize)]
+struct ItermPane {
+    window_id: String,
+    tab_id: String,
+    tab_title: String,
+    session_id: String,
+    is_focused: bool,
+    cwd: String,
+    pid: Option<u32>,
+    job_pid: Option<u32>,
+    job_name: Option<String>,
+    title: String,
+}
+
+impl ItermPane {
+    fn live_info(self) -> LivePaneInfo {
+        LivePaneInfo {
+            pid: self.job_pid.or(self.pid),
+            current_command: self.job_name.or_else(|| Some("unknown".to_string())),
+            working_dir: PathBuf::from(self.cwd),
+            title: if self.title.is_empty() {
+                None
+            } else {
+                Some(self.title)
+            },
+            session: Some(self.window_id),
+            window: Some(self.tab_title),
+        }
+    }
+}
+
+/// The session UUID from `$ITERM_SESSION_ID`, which looks like
+/// `w0t1p0:<uuid>`. The API identifies sessions by the UUID alone.
+fn session_uuid(env_value: &str) -> &str {
+    env_value
+        .split_once(':')
+        .map_or(env_value, |(_, uuid)| uuid)
+}
+
+fn parse_sessions(json: &str) -> Result<Vec<ItermPane>> {
+    serde_json::from_str(json).context("Failed to parse iTerm2 session list")
+}
+
+/// Python interpreter with the `iterm2` package installed
+pub fn python() -> String {
+    std::env::var("WORKMUX_ITERM2_PYTHON").unwrap_or_else(|_| "python3".to_string())
+}
+
+/// Path of the helper script, written (or refreshed after an upgrade) on
+/// first use.
+fn helper_path() -> Result<PathBuf> {
+    let path = crate::xdg::cache_dir()?.join("iterm2_helper.py");
+    if std::fs::read_to_string(&path).ok().as_deref() != Some(HELPER) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, HELPER)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// iTerm2 backend implementation.
+///
+/// Relies on the inherited ITERM_SESSION_ID environment variable. Requires
+/// iTerm2's Python API to be enabled and the `iterm2` Python package.
+#[derive(Debug)]
+pub struct ITerm2Backend;
+
+impl Default for ITerm2Backend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ITerm2Backend {
+    /// Create a new ITerm2Backend instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run a helper operation and return its output.
+    fn helper(&self, op: &str, args: &[&str]) -> Result<String> {
+        let script = helper_path()?;
+        let script = script.to_string_lossy();
+        let python = python();
+        Cmd::new(&python)
+            .arg(&script)
+            .arg(op)
+            .args(args)
+            .run_and_capture_stdout()
+            .with_context(|| format!("iTerm2 {} failed", op))
+    }
+
+    /// The shell command line for a helper operation, for deferred scripts.
+    fn helper_shell_cmd(&self, op: &str, session_id: &str) -> Result<String> {
+        let script = helper_path()?;
+        Ok(format!(
+            "{} {} {} {} >/dev/null 2>&1",
+            shell_quote(&python()),
+            shell_quote(&script.to_string_lossy()),
+            op,
+            shell_quote(session_id)
+        ))
+    }
+
+    /// Query all sessions as a flat list.
+    fn list_panes(&self) -> Result<Vec<ItermPane>> {
+        parse_sessions(&self.helper("ls", &[])?)
+    }
+
+    /// Current session UUID from environment.
+    fn current_session_id(&self) -> Option<String> {
+        std::env::var("ITERM_SESSION_ID")
+            .ok()
+            .map(|v| session_uuid(&v).to_string())
+    }
+
+    /// ID of the iTerm2 window containing the current session.
+    fn current_window_id(&self, panes: &[ItermPane]) -> Option<String> {
+        let current = self.current_session_id()?;
+        panes
+            .iter()
+            .find(|p| p.session_id == current)
+            .map(|p| p.window_id.clone())
+    }
+
+    /// Filter panes to the current iTerm2 window.
+    fn panes_in_current_scope<'a>(&self, panes: &'a [ItermPane]) -> Vec<&'a ItermPane> {
+        let current = self.current_window_id(panes);
+        panes
+            .iter()
+            .filter(|p| current.is_none() || Some(&p.window_id) == current.as_ref())
+            .collect()
+    }
+
+    /// A session in the tab named `full_name` in the current window.
+    fn find_tab_session(&self, full_name: &str) -> Result<Option<ItermPane>> {
+        let panes = self.list_panes()?;
+        Ok(self
+            .panes_in_current_scope(&panes)
+            .into_iter()
+            .find(|p| p.tab_title == full_name)
+            .cloned())
+    }
+
+    /// Internal split pane implementation.
+    fn split_pane_internal(
+        &self,
+        target_pane_id: &str,
+        direction: &SplitDirection,
+        cwd: &Path,
+        command: Option<&str>,
+    ) -> Result<String> {
+        // iTerm2 names the divider: a vertical divider puts panes side by
+        // side, which is tmux's horizontal split
+        let orientation = match direction {
+            SplitDirection::Horizontal => "vertical",
+            SplitDirection::Vertical => "horizontal",
+        };
+        let cwd_str = cwd.to_string_lossy();
+        let mut args = vec![target_pane_id, orientation, &*cwd_str];
+        if let Some(cmd) = command {
+            args.push(cmd);
+        }
+        let output = self
+            .helper("split", &args)
+            .context("Failed to split iTerm2 pane")?;
+        Ok(output.trim().to_string())
+    }
+
+    fn send_text(&self, pane_id: &str, text: &str) -> Result<()> {
+        self.helper("send-text", &[pane_id, text])?;
+        Ok(())
+    }
+}
+
+impl Multiplexer for ITerm2Backend {
+    fn name(&self) -> &'static str {
+        "iterm2"
+    }
+
+    fn supports_preview(&self) -> bool {
+        // Each capture starts Python and opens a websocket connection
+        false
+    }
+
+    // === Server/Session ===
+
+    fn is_running(&self) -> Result<bool> {
+        Ok(self.helper("ping", &[]).is_ok())
+    }
+
+    fn current_pane_id(&self) -> Option<String> {
+        self.current_session_id()
+    }
+
+    fn active_pane_id(&self) -> Option<String> {
+        self.list_panes().ok().and_then(|panes| {
+            panes
+                .into_iter()
+                .find(|p| p.is_focused)
+                .map(|p| p.session_id)
+        })
+    }
+
+    fn get_client_active_pane_path(&self) -> Result<PathBuf> {
+        let current = self
+            .current_session_id()
+            .ok_or_else(|| anyhow!("ITERM_SESSION_ID not set"))?;
+        let panes = self.list_panes()?;
+        let pane = panes
+            .iter()
+            .find(|p| p.session_id == current)
+            .ok_or_else(|| anyhow!("Current session {} not found", current))?;
+        if pane.cwd.is_empty() {
+            return Err(anyhow!(
+                "Empty path returned from iTerm2 (is shell integration installed?)"
+            ));
+        }
+        Ok(PathBuf::from(&pane.cwd))
+    }
+
+    // === Session Management (not supported in iTerm2) ===
+
+    fn create_session(&self, _params: CreateSessionParams) -> Result<String> {
+        Err(anyhow!(
+            "Session mode (--session) is not supported in iTerm2.\n\
+             Use the default window mode instead (omit --session flag)."
+        ))
+    }
+
+    fn switch_to_session(&self, _prefix: &str, _name: &str) -> Result<()> {
+        Err(anyhow!(
+            "Session mode is not supported in iTerm2.\n\
+             Use the default window mode instead."
+        ))
+    }
+
+    fn session_exists(&self, _full_name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn kill_session(&self, _full_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn schedule_session_close(&self, _full_name: &str, _delay: Duration) -> Result<()> {
+        Err(anyhow!(
+            "Session mode is not supported in iTerm2. Use window mode instead."
+        ))
+    }
+
+    fn get_all_session_names(&self) -> Result<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+
+    fn wait_until_session_closed(&self, _full_session_name: &str) -> Result<()> {
+        Err(anyhow!(
+            "Session mode is not supported in iTerm2. Use window mode instead."
+        ))
+    }
+
+    // === Window/Tab Management ===
+
+    fn create_window(&self, params: CreateWindowParams) -> Result<String> {
+        let full_name = util::prefixed(params.prefix, params.name);
+        let cwd_str = params.cwd.to_string_lossy();
+        let near = self.current_session_id().unwrap_or_default();
+
+        // iTerm2's API can't insert a tab at a position, so params.after_window
+        // is ignored and tabs appear at the end (same as kitty)
+        let output = self
+            .helper("create-tab", &[&near, &*cwd_str, &full_name])
+            .context("Failed to create iTerm2 tab")?;
+        Ok(output.trim().to_string())
+    }
+
+    fn kill_window(&self, full_name: &str) -> Result<()> {
+        let panes = self.list_panes()?;
+        let mut seen_tabs = HashSet::new();
+        let sessions: Vec<&str> = self
+            .panes_in_current_scope(&panes)
+            .into_iter()
+            .filter(|p| p.tab_title == full_name)
+            .filter(|p| seen_tabs.insert(p.tab_id.as_str()))
+            .map(|p| p.session_id.as_str())
+            .collect();
+
+        for session in sessions {
+            let _ = self.helper("close-tab", &[session]);
+        }
+        Ok(())
+    }
+
+    fn schedule_window_close(&self, full_name: &str, delay: Duration) -> Result<()> {
+        let Some(pane) = self.find_tab_session(full_name)? else {
+            return Ok(());
+        };
+        let close = self.helper_shell_cmd("close-tab", &pane.session_id)?;
+        let script = format!("sleep {}; {}", delay.as_secs_f64(), close);
+        self.run_deferred_script(&script)
+    }
+
+    fn run_deferred_script(&self, script: &str) -> Result<()> {
+        // Run the script in the background, detached from this process
+        let bg_script = format!(
+            "nohup sh -c '{}' >/dev/null 2>&1 &",
+            crate::shell::shell_escape(script)
+        );
+        Cmd::new("sh").args(&["-c", &bg_script]).run()?;
+        Ok(())
+    }
+
+    fn shell_select_window_cmd(&self, full_name: &str) -> Result<String> {
+        let pane = self
+            .find_tab_session(full_name)?
+            .ok_or_else(|| anyhow!("Window '{}' not found", full_name))?;
+        self.helper_shell_cmd("focus", &pane.session_id)
+    }
+
+    fn shell_kill_window_cmd(&self, full_name: &str) -> Result<String> {
+        let pane = self
+            .find_tab_session(full_name)?
+            .ok_or_else(|| anyhow!("Window '{}' not found", full_name))?;
+        self.helper_shell_cmd("close-tab", &pane.session_id)
+    }
+
+    fn shell_switch_session_cmd(&self, _full_name: &str) -> Result<String> {
+        Err(anyhow!(
+            "Session mode is not supported in iTerm2. Use window mode instead."
+        ))
+    }
+
+    fn shell_kill_session_cmd(&self, _full_name: &str) -> Result<String> {
+        Err(anyhow!(
+            "Session mode is not supported in iTerm2. Use window mode instead."
+        ))
+    }
+
+    fn select_window(&self, prefix: &str, name: &str) -> Result<()> {
+        let full_name = util::prefixed(prefix, name);
+        let pane = self
+            .find_tab_session(&full_name)?
+            .ok_or_else(|| anyhow!("Window '{}' not found", full_name))?;
+        self.helper("focus", &[&pane.session_id])
+            .context("Failed to focus tab")?;
+        Ok(())
+    }
+
+    fn window_exists(&self, prefix: &str, name: &str) -> Result<bool> {
+        let full_name = util::prefixed(prefix, name);
+        self.window_exists_by_full_name(&full_name)
+    }
+
+    fn window_exists_by_full_name(&self, full_name: &str) -> Result<bool> {
+        Ok(self.get_all_window_names()?.contains(full_name))
+    }
+
+    fn current_window_name(&self) -> Result<Option<String>> {
+        let Some(current) = self.current_session_id() else {
+            return Ok(None);
+        };
+        let panes = self.list_panes()?;
+        Ok(panes
+            .into_iter()
+            .find(|p| p.session_id == current)
+            .map(|p| p.tab_title))
+    }
+
+    fn get_all_window_names(&self) -> Result<HashSet<String>> {
+        let panes = self.list_panes()?;
+        Ok(self
+            .panes_in_current_scope(&panes)
+            .iter()
+            .filter(|p| !p.tab_title.is_empty())
+            .map(|p| p.tab_title.clone())
+            .collect())
+    }
+
+    fn filter_active_windows(&self, windows: &[String]) -> Result<Vec<String>> {
+        let all_current = self.get_all_window_names()?;
+        Ok(windows
+            .iter()
+            .filter(|w| all_current.contains(*w))
+            .cloned()
+            .collect())
+    }
+
+    fn find_last_window_with_prefix(&self, _prefix: &str) -> Result<Option<String>> {
+        // Tabs can't be inserted at a position, so ordering doesn't matter
+        Ok(None)
+    }
+
+    fn find_last_window_with_base_handle(
+        &self,
+        _prefix: &str,
+        _base_handle: &str,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn wait_until_windows_closed(&self, full_window_names: &[String]) -> Result<()> {
+        if full_window_names.is_empty() {
+            return Ok(());
+        }
+
+        let targets: HashSet<String> = full_window_names.iter().cloned().collect();
+
+        if targets.len() == 1 {
+            println!("Waiting for window '{}' to close...", full_window_names[0]);
+        } else {
+            println!("Waiting for {} windows to close...", targets.len());
+        }
+
+        loop {
+            if !self.is_running()? {
+                return Ok(());
+            }
+
+            let current_windows = self.get_all_window_names()?;
+            if !targets.iter().any(|t| current_windows.contains(t)) {
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_millis(500));
+        }
+    }
+
+    // === Pane Management ===
+
+    fn select_pane(&self, pane_id: &str) -> Result<()> {
+        self.helper("focus", &[pane_id])
+            .context("Failed to focus session")?;
+        Ok(())
+    }
+
+    fn switch_to_pane(&self, pane_id: &str, _window_hint: Option<&str>) -> Result<()> {
+        // Activating a session also selects its tab and raises its window
+        self.select_pane(pane_id)
+    }
+
+    fn kill_pane(&self, pane_id: &str) -> Result<()> {
+        self.helper("close-session", &[pane_id])?;
+        Ok(())
+    }
+
+    fn respawn_pane(&self, pane_id: &str, cwd: &Path, cmd: Option<&str>) -> Result<String> {
+        // The API can restart a session but not change its command, so split
+        // and close the original, as the kitty backend does
+        let new_pane_id = self.split_pane_internal(pane_id, &SplitDirection::Vertical, cwd, cmd)?;
+        let _ = self.kill_pane(pane_id);
+        Ok(new_pane_id)
+    }
+
+    fn capture_pane(&self, pane_id: &str, lines: u16) -> Option<String> {
+        self.helper("contents", &[pane_id, &lines.to_string()]).ok()
+    }
+
+    // === Text I/O ===
+
+    fn send_keys(&self, pane_id: &str, command: &str) -> Result<()> {
+        self.send_text(pane_id, &format!("{}\r", command))
+            .context("Failed to send text to pane")
+    }
+
+    fn send_keys_to_agent(&self, pane_id: &str, command: &str, agent: Option<&str>) -> Result<()> {
+        if agent::resolve_profile(agent).needs_bang_delay() && command.starts_with('!') {
+            self.send_text(pane_id, "!")?;
+            // Small delay to let Claude register the !
+            thread::sleep(Duration::from_millis(50));
+            self.send_keys(pane_id, &command[1..])
+        } else {
+            self.send_keys(pane_id, command)
+        }
+    }
+
+    fn send_key(&self, pane_id: &str, key: &str) -> Result<()> {
+        // Translate tmux key names to the bytes a terminal would send.
+        // The dashboard sends tmux-style names like "BSpace", "Enter", etc.
+        let translated = match key {
+            "BSpace" => "\x7f",
+            "Enter" => "\r",
+            "Tab" => "\t",
+            "Up" => "\x1b[A",
+            "Down" => "\x1b[B",
+            "Right" => "\x1b[C",
+            "Left" => "\x1b[D",
+            "Escape" => "\x1b",
+            _ => key,
+        };
+        self.send_text(pane_id, translated)
+            .context("Failed to send key to pane")
+    }
+
+    fn paste_multiline(&self, pane_id: &str, content: &str) -> Result<()> {
+        self.send_text(pane_id, &format!("\x1b[200~{}\x1b[201~", content))
+            .context("Failed to paste content to pane")?;
+
+        // Let the application process the bracketed paste before sending Enter
+        thread::sleep(Duration::from_millis(100));
+
+        self.send_text(pane_id, "\r")
+            .context("Failed to send Enter after paste")
+    }
+
+    // === Shell ===
+
+    fn get_default_shell(&self) -> Result<String> {
+        std::env::var("SHELL").or_else(|_| Ok("/bin/zsh".to_string()))
+    }
+
+    fn create_handshake(&self) -> Result<Box<dyn PaneHandshake>> {
+        handshake::shell_handshake()
+    }
+
+    // === Status ===
+
+    fn set_status(&self, pane_id: &str, icon: &str, auto_clear_on_focus: bool) -> Result<()> {
+        // Session user variables, shown by the badge set in
+        // ensure_status_format and read by the auto-clear script
+        let auto_clear = if auto_clear_on_focus { "1" } else { "" };
+        let _ = self.helper(
+            "set-vars",
+            &[
+                pane_id,
+                &format!("workmux_status={}", icon),
+                &format!("workmux_auto_clear={}", auto_clear),
+            ],
+        );
+        Ok(())
+    }
+
+    fn clear_status(&self, pane_id: &str) -> Result<()> {
+        let _ = self.helper(
+            "set-vars",
+            &[pane_id, "workmux_status=", "workmux_auto_clear="],
+        );
+        Ok(())
+    }
+
+    fn ensure_status_format(&self, pane_id: &str) -> Result<()> {
+        // The badge is an interpolated string, so it follows the variable
+        let _ = self.helper("badge", &[pane_id, "\\(user.workmux_status)"]);
+        Ok(())
+    }
+
+    // === Multi-Session/Workspace Support ===
+
+    fn current_session(&self) -> Option<String> {
+        // iTerm2 has no named sessions; use the window as a pseudo-session
+        let panes = self.list_panes().ok()?;
+        self.current_window_id(&panes)
+    }
+
+    fn get_all_window_names_all_sessions(&self) -> Result<HashSet<String>> {
+        Ok(self
+            .list_panes()?
+            .into_iter()
+            .filter(|p| !p.tab_title.is_empty())
+            .map(|p| p.tab_title)
+            .collect())
+    }
+
+    // === State Reconciliation ===
+
+    fn instance_id(&self) -> String {
+        // One iTerm2 app per user session
+        "iterm2".to_string()
+    }
+
+    fn get_live_pane_info(&self, pane_id: &str) -> Result<Option<LivePaneInfo>> {
+        Ok(self
+            .list_panes()?
+            .into_iter()
+            .find(|p| p.session_id == pane_id)
+            .map(ItermPane::live_info))
+    }
+
+    fn get_all_live_pane_info(&self) -> Result<HashMap<String, LivePaneInfo>> {
+        Ok(self
+            .list_panes()?
+            .into_iter()
+            .map(|p| (p.session_id.clone(), p.live_info()))
+            .collect())
+    }
+
+    fn split_pane(
+        &self,
+        target_pane_id: &str,
+        direction: &SplitDirection,
+        cwd: &Path,
+        _size: Option<u16>,
+        _percentage: Option<u8>,
+        command: Option<&str>,
+    ) -> Result<String> {
+        self.split_pane_internal(target_pane_id, direction, cwd, command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_uuid_strips_position() {
+        assert_eq!(
+            session_uuid("w0t1p0:8B4A0F8E-6C2D-4F0B-9A0E-2F1C3D4E5F60"),
+            "8B4A0F8E-6C2D-4F0B-9A0E-2F1C3D4E5F60"
+        );
+        assert_eq!(session_uuid("8B4A0F8E"), "8B4A0F8E");
+    }
+
+    #[test]
+    fn parses_helper_session_list() {
+        let json = r#"[{"window_id": "pty-1", "tab_id": "3", "tab_title": "wm-login",
+            "session_id": "ABC", "is_focused": true, "cwd": "/src/app",
+            "pid": 501, "job_pid": 777, "job_name": "claude", "title": "claude"},
+            {"window_id": "pty-1", "tab_id": "4", "tab_title": "",
+            "session_id": "DEF", "is_focused": false, "cwd": "",
+            "pid": null, "job_pid": null, "job_name": null, "title": ""}]"#;
+        let panes = parse_sessions(json).unwrap();
+        assert_eq!(panes.len(), 2);
+        let info = panes[0].clone().live_info();
+        assert_eq!(info.pid, Some(777));
+        assert_eq!(info.current_command.as_deref(), Some("claude"));
+        assert_eq!(info.window.as_deref(), Some("wm-login"));
+        assert_eq!(panes[1].clone().live_info().title, None);
+    }
+}
@@ -0,0 +1,138 @@
+"""Run one workmux operation through iTerm2's Python API.
+
+Usage: python3 iterm2_helper.py <op> [args...]
+
+workmux writes this file to its cache directory and runs it for every iTerm2
+call. Queries print JSON on stdout; errors go to stderr with a non-zero exit.
+Requires the `iterm2` package (pip install iterm2) and "Enable Python API"
+in iTerm2's General > Magic settings.
+
+Terminology: an iTerm2 session is a workmux pane, a tab is a workmux window.
+"""
+
+import json
+import shlex
+import sys
+
+import iterm2
+
+
+def profile(cwd, command):
+    customizations = iterm2.LocalWriteOnlyProfile()
+    customizations.set_initial_directory_mode(
+        iterm2.InitialWorkingDirectory.INITIAL_WORKING_DIRECTORY_CUSTOM
+    )
+    customizations.set_custom_directory(cwd)
+    if command:
+        customizations.set_use_custom_command("Yes")
+        customizations.set_command("/bin/sh -c " + shlex.quote(command))
+    return customizations
+
+
+def session_or_exit(app, session_id):
+    session = app.get_session_by_id(session_id)
+    if session is None:
+        sys.exit("iTerm2 session not found: " + session_id)
+    return session
+
+
+async def list_sessions(app):
+    current = app.current_terminal_window
+    panes = []
+    for window in app.terminal_windows:
+        for tab in window.tabs:
+            tab_title = await tab.async_get_variable("titleOverride") or ""
+            for session in tab.sessions:
+                panes.append(
+                    {
+                        "window_id": window.window_id,
+                        "tab_id": tab.tab_id,
+                        "tab_title": tab_title,
+                        "session_id": session.session_id,
+                        "is_focused": window is current
+                        and window.current_tab is tab
+                        and tab.current_session is session,
+                        "cwd": await session.async_get_variable("path") or "",
+                        "pid": await session.async_get_variable("pid"),
+                        "job_pid": await session.async_get_variable("jobPid"),
+                        "job_name": await session.async_get_variable("jobName"),
+                        "title": await session.async_get_variable("name") or "",
+                    }
+                )
+    return panes
+
+
+async def main(connection):
+    app = await iterm2.async_get_app(connection)
+    op, args = sys.argv[1], sys.argv[2:]
+
+    if op == "ping":
+        print("ok")
+    elif op == "ls":
+        print(json.dumps(await list_sessions(app)))
+    elif op == "create-tab":
+        # create-tab <near-session> <cwd> <title>
+        near, cwd, title = args
+        window = None
+        if near:
+            session = app.get_session_by_id(near)
+            if session is not None:
+                window, _ = app.get_window_and_tab_for_session(session)
+        window = window or app.current_terminal_window
+        if window is None:
+            window = await iterm2.Window.async_create(
+                connection, profile_customizations=profile(cwd, None)
+            )
+            tab = window.current_tab
+        else:
+            previous = window.current_tab
+            tab = await window.async_create_tab(profile_customizations=profile(cwd, None))
+            if previous is not None:
+                await previous.async_select(order_window_front=False)
+        await tab.async_set_title(title)
+        print(tab.current_session.session_id)
+    elif op == "split":
+        # split <session> <vertical|horizontal> <cwd> [command]
+        session = session_or_exit(app, args[0])
+        command = args[3] if len(args) > 3 else None
+        new = await session.async_split_pane(
+            vertical=args[1] == "vertical",
+            profile_customizations=profile(args[2], command),
+        )
+        print(new.session_id)
+    elif op == "focus":
+        session = session_or_exit(app, args[0])
+        await session.async_activate(select_tab=True, order_window_front=True)
+    elif op == "close-session":
+        session = app.get_session_by_id(args[0])
+        if session is not None:
+            await session.async_close(force=True)
+    elif op == "close-tab":
+        session = app.get_session_by_id(args[0])
+        if session is not None:
+            _, tab = app.get_window_and_tab_for_session(session)
+            await tab.async_close(force=True)
+    elif op == "send-text":
+        session = session_or_exit(app, args[0])
+        await session.async_send_text(args[1])
+    elif op == "contents":
+        session = session_or_exit(app, args[0])
+        contents = await session.async_get_screen_contents()
+        lines = [contents.line(i).string for i in range(contents.number_of_lines)]
+        print("\n".join(lines[-int(args[1]) :]))
+    elif op == "set-vars":
+        # set-vars <session> name=value...
+        session = session_or_exit(app, args[0])
+        for assignment in args[1:]:
+            name, value = assignment.split("=", 1)
+            await session.async_set_variable("user." + name, value)
+    elif op == "badge":
+        session = session_or_exit(app, args[0])
+        customizations = iterm2.LocalWriteOnlyProfile()
+        customizations.set_badge_text(args[1])
+        await session.async_set_profile_properties(customizations)
+    else:
+        sys.exit("unknown operation: " + op)
+
+
+iterm2.run_until_complete(main)
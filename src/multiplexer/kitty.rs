@@ -18,7 +18,7 @@ use std::thread;
 use std::time::Duration;
 
 use super::agent;
-use super::handshake::UnixPipeHandshake;
+use super::handshake;
 use super::types::*;
 use super::util;
 use super::{Multiplexer, PaneHandshake};
@@ -747,7 +747,7 @@ impl Multiplexer for KittyBackend {
     }
 
     fn create_handshake(&self) -> Result<Box<dyn PaneHandshake>> {
-        Ok(Box::new(UnixPipeHandshake::new()?))
+        handshake::shell_handshake()
     }
 
     // === Status ===
@@ -7,6 +7,8 @@ pub mod agent;
 pub mod conversation;
 pub mod handle;
 pub mod handshake;
+pub mod headless;
+pub mod iterm2;
 pub mod kitty;
 pub mod tmux;
 pub mod types;
@@ -17,7 +19,7 @@ pub mod zellij;
 use anyhow::{Result, anyhow};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 pub use handle::MuxHandle;
@@ -34,6 +36,12 @@ pub trait Multiplexer: Send + Sync {
     /// Returns the name of this backend (e.g., "tmux", "wezterm")
     fn name(&self) -> &'static str;
 
+    /// Whether this backend has no windows or panes at all (`WORKMUX_BACKEND=none`).
+    /// Workflows skip window setup instead of failing.
+    fn is_headless(&self) -> bool {
+        false
+    }
+
     // === Server/Session ===
 
     /// Check if the multiplexer server is running
@@ -201,6 +209,31 @@ pub trait Multiplexer: Send + Sync {
     /// switch by pane ID directly (tmux, WezTerm) ignore this parameter.
     fn switch_to_pane(&self, pane_id: &str, window_hint: Option<&str>) -> Result<()>;
 
+    /// Show a pane over the current one without leaving it (`workmux peek`).
+    /// `title` labels the overlay where the backend supports it.
+    fn peek_pane(&self, pane_id: &str, title: &str) -> Result<()> {
+        let _ = (pane_id, title);
+        Err(anyhow!(
+            "peek is not supported by the {} backend; use `workmux capture` to read an agent's output",
+            self.name()
+        ))
+    }
+
+    /// The layout of the window containing `pane_id`: the backend's geometry
+    /// string and the window's pane IDs in layout order. None if the backend
+    /// can't describe or restore layouts.
+    fn window_geometry(&self, pane_id: &str) -> Result<Option<(String, Vec<String>)>> {
+        let _ = pane_id;
+        Ok(None)
+    }
+
+    /// Apply geometry from [`window_geometry`](Self::window_geometry) to the
+    /// window containing `pane_id`, which must have as many panes.
+    fn restore_window_geometry(&self, pane_id: &str, geometry: &str) -> Result<()> {
+        let _ = (pane_id, geometry);
+        Ok(())
+    }
+
     /// Whether jumping to a pane should exit the dashboard.
     /// Defaults to true. Override to return false to keep the dashboard open after jumping.
     fn should_exit_on_jump(&self) -> bool {
@@ -305,12 +338,14 @@ pub trait Multiplexer: Send + Sync {
             return Ok(PaneSetupResult {
                 focus_pane_id: initial_pane_id.to_string(),
                 zoom_pane_id: None,
+                panes: vec![(initial_pane_id.to_string(), None)],
             });
         }
 
         let mut focus_pane_id: Option<String> = None;
         let mut zoom_pane_id: Option<String> = None;
         let mut pane_ids: Vec<String> = vec![initial_pane_id.to_string()];
+        let mut pane_commands: Vec<Option<String>> = vec![None];
         // Resolve agent name through the agents map
         let resolved_task_agent = task_agent.map(|a| {
             config
@@ -330,12 +365,15 @@ pub trait Multiplexer: Send + Sync {
                 continue;
             }
 
+            let pane_dir = util::pane_dir(working_dir, pane_config.cwd.as_deref());
+            let env_exports = util::env_exports(&pane_config.env);
+
             // Resolve command: handle <agent> placeholder and prompt injection
             let adjusted_command = util::resolve_pane_command(
                 pane_config.command.as_deref(),
                 options.run_commands,
                 options.prompt_file_path,
-                working_dir,
+                &pane_dir,
                 effective_agent,
                 &shell,
                 config.agent_type.as_deref(),
@@ -347,10 +385,10 @@ pub trait Multiplexer: Send + Sync {
 
                 // Spawn with handshake so we can send the command after shell is ready
                 let handshake = self.create_handshake()?;
-                let script = handshake.script_content(&shell);
+                let script = format!("{}{}", env_exports, handshake.script_content(&shell));
 
                 let spawned_id = if is_first {
-                    self.respawn_pane(&pane_ids[0], working_dir, Some(&script))?
+                    self.respawn_pane(&pane_ids[0], &pane_dir, Some(&script))?
                 } else {
                     let direction = pane_config.split.as_ref().unwrap();
                     let target_idx = pane_config.target.unwrap_or(pane_ids.len() - 1);
@@ -360,7 +398,7 @@ pub trait Multiplexer: Send + Sync {
                     self.split_pane(
                         target,
                         direction,
-                        working_dir,
+                        &pane_dir,
                         pane_config.size,
                         pane_config.percentage,
                         Some(&script),
@@ -384,7 +422,16 @@ pub trait Multiplexer: Send + Sync {
                                 pane_agent,
                                 config.agent_type.as_deref(),
                             );
-                            if let Some(flag) = profile.continue_flag() {
+                            let agent_name =
+                                pane_agent.or(config.agent.as_deref()).unwrap_or("claude");
+                            if let Some(session_args) =
+                                conversation::latest_session_args(agent_name, &pane_dir)
+                            {
+                                resolved.command = util::inject_skip_permissions_flag(
+                                    &resolved.command,
+                                    &session_args,
+                                );
+                            } else if let Some(flag) = profile.continue_flag() {
                                 resolved.command =
                                     util::inject_skip_permissions_flag(&resolved.command, flag);
                             } else {
@@ -417,6 +464,16 @@ pub trait Multiplexer: Send + Sync {
                     }
                 }
 
+                // Unsandboxed agents read secrets through a wrapper; the
+                // sandbox supervisor injects them into the guest itself
+                let host_command = || {
+                    if is_agent_pane && !config.secrets.is_empty() {
+                        crate::secrets::wrap_command(&resolved.command)
+                    } else {
+                        resolved.command.clone()
+                    }
+                };
+
                 // Apply sandbox wrapping if enabled for this pane type
                 let final_command = if config.sandbox.is_enabled() {
                     let should_wrap = match config.sandbox.target() {
@@ -424,8 +481,14 @@ pub trait Multiplexer: Send + Sync {
                         crate::config::SandboxTarget::Agent => is_agent_pane,
                     };
                     if should_wrap {
-                        // Use worktree_root for mounting, working_dir for cwd
+                        // Use worktree_root for mounting, the pane's dir for cwd
+                        // when the guest can see it
                         let wt_root = options.worktree_root.unwrap_or(working_dir);
+                        let guest_dir = if pane_dir.starts_with(wt_root) {
+                            pane_dir.as_path()
+                        } else {
+                            working_dir
+                        };
 
                         // Inject skip-permissions flag for agent panes only
                         // (sandbox provides the security boundary, so permission
@@ -451,7 +514,7 @@ pub trait Multiplexer: Send + Sync {
                                     &command_to_wrap,
                                     &config.sandbox,
                                     wt_root,
-                                    working_dir,
+                                    guest_dir,
                                 )
                             }
                             crate::config::SandboxBackend::Lima => {
@@ -465,7 +528,7 @@ pub trait Multiplexer: Send + Sync {
                                     &command_to_wrap,
                                     config,
                                     vm_name,
-                                    working_dir,
+                                    guest_dir,
                                 )
                             }
                         };
@@ -482,10 +545,10 @@ pub trait Multiplexer: Send + Sync {
                             }
                         }
                     } else {
-                        resolved.command.clone()
+                        host_command()
                     }
                 } else {
-                    resolved.command.clone()
+                    host_command()
                 };
 
                 let _ = self.clear_pane(&spawned_id);
@@ -504,30 +567,40 @@ pub trait Multiplexer: Send + Sync {
                 }
 
                 spawned_id
-            } else if is_first {
-                // No command for first pane - keep as-is
-                pane_ids[0].clone()
             } else {
-                // No command - just split
-                let direction = pane_config.split.as_ref().unwrap();
-                let target_idx = pane_config.target.unwrap_or(pane_ids.len() - 1);
-                let target = pane_ids
-                    .get(target_idx)
-                    .ok_or_else(|| anyhow!("Invalid target pane index: {}", target_idx))?;
-                self.split_pane(
-                    target,
-                    direction,
-                    working_dir,
-                    pane_config.size,
-                    pane_config.percentage,
-                    None,
-                )?
+                // No command: a plain shell, started with the pane's env
+                let shell_script = (!env_exports.is_empty())
+                    .then(|| format!("{}exec '{}' -l", env_exports, shell));
+                if is_first {
+                    if shell_script.is_none() && pane_config.cwd.is_none() {
+                        // Nothing to change - keep as-is
+                        pane_ids[0].clone()
+                    } else {
+                        self.respawn_pane(&pane_ids[0], &pane_dir, shell_script.as_deref())?
+                    }
+                } else {
+                    let direction = pane_config.split.as_ref().unwrap();
+                    let target_idx = pane_config.target.unwrap_or(pane_ids.len() - 1);
+                    let target = pane_ids
+                        .get(target_idx)
+                        .ok_or_else(|| anyhow!("Invalid target pane index: {}", target_idx))?;
+                    self.split_pane(
+                        target,
+                        direction,
+                        &pane_dir,
+                        pane_config.size,
+                        pane_config.percentage,
+                        shell_script.as_deref(),
+                    )?
+                }
             };
 
             if is_first {
                 pane_ids[0] = pane_id.clone();
+                pane_commands[0] = pane_config.command.clone();
             } else {
                 pane_ids.push(pane_id.clone());
+                pane_commands.push(pane_config.command.clone());
             }
 
             if pane_config.zoom || pane_config.focus {
@@ -542,6 +615,7 @@ pub trait Multiplexer: Send + Sync {
         Ok(PaneSetupResult {
             focus_pane_id: focus_pane_id.unwrap_or_else(|| pane_ids[0].clone()),
             zoom_pane_id,
+            panes: pane_ids.into_iter().zip(pane_commands).collect(),
         })
     }
 
@@ -623,6 +697,30 @@ pub trait Multiplexer: Send + Sync {
     }
 }
 
+static BACKEND_OVERRIDE: OnceLock<BackendType> = OnceLock::new();
+
+/// Use `backend` for the rest of the process (`--backend`), skipping
+/// detection. Only the first call counts.
+pub fn set_backend(backend: BackendType) {
+    let _ = BACKEND_OVERRIDE.set(backend);
+}
+
+/// The backend [`detect_backend_with_reason`] picked, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendChoice {
+    pub backend: BackendType,
+    pub reason: String,
+}
+
+impl BackendChoice {
+    fn new(backend: BackendType, reason: impl Into<String>) -> Self {
+        Self {
+            backend,
+            reason: reason.into(),
+        }
+    }
+}
+
 /// Detect which backend to use based on environment.
 ///
 /// Checks `$WORKMUX_BACKEND` first for an explicit override, then auto-detects
@@ -630,54 +728,166 @@ pub trait Multiplexer: Send + Sync {
 /// when inside the multiplexer) are checked before ambient variables (inherited
 /// from the parent terminal):
 ///
+/// 0. `--backend` given → that backend; `--remote` given → tmux on the remote host
 /// 1. `$WORKMUX_BACKEND` set → use that backend
 /// 2. `$TMUX` set → tmux
 /// 3. `$WEZTERM_PANE` set → WezTerm
 /// 4. `$ZELLIJ` set → Zellij
 /// 5. `$KITTY_WINDOW_ID` set → Kitty
-/// 6. None → defaults to tmux (for backward compatibility)
+/// 6. `$ITERM_SESSION_ID` set → iTerm2
+/// 7. `$CI` set → headless (no multiplexer)
+/// 8. None → defaults to tmux (for backward compatibility), or WezTerm on
+///    Windows, where tmux doesn't run natively
 ///
 /// This ordering ensures that running tmux inside kitty (or wezterm) correctly
-/// selects the innermost multiplexer.
+/// selects the innermost multiplexer. `backend_priority` in the global config
+/// overrides steps 2-6 and 8: the first listed backend that was detected wins,
+/// or the first listed one when none was.
 pub fn detect_backend() -> BackendType {
+    detect_backend_with_reason().backend
+}
+
+/// [`detect_backend`], with an explanation for `workmux doctor` and the log.
+pub fn detect_backend_with_reason() -> BackendChoice {
+    let choice = detect();
+    static LOGGED: std::sync::Once = std::sync::Once::new();
+    LOGGED.call_once(|| {
+        tracing::debug!(backend = %choice.backend, reason = %choice.reason, "selected backend");
+    });
+    choice
+}
+
+fn detect() -> BackendChoice {
+    if let Some(backend) = BACKEND_OVERRIDE.get() {
+        return BackendChoice::new(*backend, "--backend was given");
+    }
+    // `--remote` drives a remote tmux server; local terminals don't matter
+    if crate::transport::remote_host().is_some() {
+        return BackendChoice::new(BackendType::Tmux, "--remote runs tmux on the remote host");
+    }
     if let Ok(val) = std::env::var("WORKMUX_BACKEND") {
         match val.parse() {
-            Ok(bt) => return bt,
+            Ok(bt) => return BackendChoice::new(bt, format!("WORKMUX_BACKEND={val}")),
             Err(_) => {
                 eprintln!(
-                    "workmux: invalid WORKMUX_BACKEND={val:?}, expected tmux|wezterm|kitty|zellij"
+                    "workmux: invalid WORKMUX_BACKEND={val:?}, expected tmux|wezterm|kitty|zellij|iterm2|none"
                 );
             }
         }
     }
 
-    resolve_backend(
-        std::env::var("TMUX").is_ok(),
-        std::env::var("WEZTERM_PANE").is_ok(),
-        std::env::var("ZELLIJ").is_ok(),
-        std::env::var("KITTY_WINDOW_ID").is_ok(),
-    )
+    let tmux = std::env::var("TMUX").is_ok();
+    let wezterm = std::env::var("WEZTERM_PANE").is_ok();
+    let zellij = std::env::var("ZELLIJ").is_ok();
+    let kitty = std::env::var("KITTY_WINDOW_ID").is_ok();
+    let iterm2 = std::env::var("ITERM_SESSION_ID").is_ok();
+    if !(tmux || wezterm || zellij || kitty || iterm2) && is_ci(std::env::var("CI").ok().as_deref())
+    {
+        return BackendChoice::new(BackendType::None, "$CI is set and no multiplexer was found");
+    }
+    resolve_backend(tmux, wezterm, zellij, kitty, iterm2, backend_priority())
 }
 
-/// Pure auto-detection logic, separated for testability.
-fn resolve_backend(tmux: bool, wezterm: bool, zellij: bool, kitty: bool) -> BackendType {
-    if tmux {
-        return BackendType::Tmux;
-    }
+/// `backend_priority` from the global config, read once per process.
+/// Global-only: the backend is picked before the project is known.
+fn backend_priority() -> &'static [BackendType] {
+    static PRIORITY: OnceLock<Vec<BackendType>> = OnceLock::new();
+    PRIORITY.get_or_init(|| {
+        crate::config::Config::load_global()
+            .ok()
+            .flatten()
+            .and_then(|config| config.backend_priority)
+            .unwrap_or_default()
+    })
+}
 
-    if wezterm {
-        return BackendType::WezTerm;
+/// The variable that shows we're running inside `backend`.
+fn env_marker(backend: BackendType) -> &'static str {
+    match backend {
+        BackendType::Tmux => "$TMUX",
+        BackendType::WezTerm => "$WEZTERM_PANE",
+        BackendType::Zellij => "$ZELLIJ",
+        BackendType::Kitty => "$KITTY_WINDOW_ID",
+        BackendType::ITerm2 => "$ITERM_SESSION_ID",
+        BackendType::None => "$CI",
     }
+}
 
-    if zellij {
-        return BackendType::Zellij;
-    }
+/// Whether a `$CI` value means we're on a CI runner. Most CI systems set
+/// `CI=true`; an empty value or `false`/`0` opts out.
+fn is_ci(value: Option<&str>) -> bool {
+    value.is_some_and(|v| !v.is_empty() && v != "false" && v != "0")
+}
 
-    if kitty {
-        return BackendType::Kitty;
+/// Pure auto-detection logic, separated for testability. Without a
+/// `priority` list, the innermost detected multiplexer wins.
+fn resolve_backend(
+    tmux: bool,
+    wezterm: bool,
+    zellij: bool,
+    kitty: bool,
+    iterm2: bool,
+    priority: &[BackendType],
+) -> BackendChoice {
+    // Innermost first
+    let found: Vec<BackendType> = [
+        (tmux, BackendType::Tmux),
+        (wezterm, BackendType::WezTerm),
+        (zellij, BackendType::Zellij),
+        (kitty, BackendType::Kitty),
+        (iterm2, BackendType::ITerm2),
+    ]
+    .into_iter()
+    .filter_map(|(present, backend)| present.then_some(backend))
+    .collect();
+    let names = || {
+        found
+            .iter()
+            .map(|b| format!("{} ({})", b, env_marker(*b)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    if let Some(&first) = found.first() {
+        if found.len() == 1 {
+            return BackendChoice::new(first, format!("{} is set", env_marker(first)));
+        }
+        if let Some(&preferred) = priority.iter().find(|b| found.contains(b)) {
+            return BackendChoice::new(
+                preferred,
+                format!(
+                    "found {}; {} comes first in backend_priority",
+                    names(),
+                    preferred
+                ),
+            );
+        }
+        return BackendChoice::new(
+            first,
+            format!("found {}; {} is the innermost", names(), first),
+        );
     }
 
-    BackendType::Tmux
+    if let Some(&preferred) = priority.first() {
+        return BackendChoice::new(
+            preferred,
+            format!(
+                "no multiplexer found; {} comes first in backend_priority",
+                preferred
+            ),
+        );
+    }
+    if cfg!(windows) {
+        BackendChoice::new(
+            BackendType::WezTerm,
+            "no multiplexer found; defaulting to wezterm on Windows",
+        )
+    } else {
+        BackendChoice::new(
+            BackendType::Tmux,
+            "no multiplexer found; defaulting to tmux",
+        )
+    }
 }
 
 /// Create a backend instance based on the backend type.
@@ -687,6 +897,8 @@ pub fn create_backend(backend_type: BackendType) -> Arc<dyn Multiplexer> {
         BackendType::WezTerm => Arc::new(wezterm::WezTermBackend::new()),
         BackendType::Kitty => Arc::new(kitty::KittyBackend::new()),
         BackendType::Zellij => Arc::new(zellij::ZellijBackend::new()),
+        BackendType::ITerm2 => Arc::new(iterm2::ITerm2Backend::new()),
+        BackendType::None => Arc::new(headless::HeadlessBackend::new()),
     }
 }
 
@@ -694,18 +906,28 @@ pub fn create_backend(backend_type: BackendType) -> Arc<dyn Multiplexer> {
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
     #[test]
     fn no_env_defaults_to_tmux() {
         assert_eq!(
-            resolve_backend(false, false, false, false),
+            resolve_backend(false, false, false, false, false, &[]).backend,
             BackendType::Tmux
         );
     }
 
+    #[cfg(windows)]
+    #[test]
+    fn no_env_defaults_to_wezterm_on_windows() {
+        assert_eq!(
+            resolve_backend(false, false, false, false, false, &[]).backend,
+            BackendType::WezTerm
+        );
+    }
+
     #[test]
     fn tmux_only() {
         assert_eq!(
-            resolve_backend(true, false, false, false),
+            resolve_backend(true, false, false, false, false, &[]).backend,
             BackendType::Tmux
         );
     }
@@ -713,7 +935,7 @@ mod tests {
     #[test]
     fn wezterm_only() {
         assert_eq!(
-            resolve_backend(false, true, false, false),
+            resolve_backend(false, true, false, false, false, &[]).backend,
             BackendType::WezTerm
         );
     }
@@ -721,7 +943,7 @@ mod tests {
     #[test]
     fn zellij_only() {
         assert_eq!(
-            resolve_backend(false, false, true, false),
+            resolve_backend(false, false, true, false, false, &[]).backend,
             BackendType::Zellij
         );
     }
@@ -729,30 +951,39 @@ mod tests {
     #[test]
     fn kitty_only() {
         assert_eq!(
-            resolve_backend(false, false, false, true),
+            resolve_backend(false, false, false, true, false, &[]).backend,
             BackendType::Kitty
         );
     }
 
     #[test]
     fn tmux_inside_kitty() {
-        assert_eq!(resolve_backend(true, false, false, true), BackendType::Tmux);
+        assert_eq!(
+            resolve_backend(true, false, false, true, false, &[]).backend,
+            BackendType::Tmux
+        );
     }
 
     #[test]
     fn tmux_inside_wezterm() {
-        assert_eq!(resolve_backend(true, true, false, false), BackendType::Tmux);
+        assert_eq!(
+            resolve_backend(true, true, false, false, false, &[]).backend,
+            BackendType::Tmux
+        );
     }
 
     #[test]
     fn tmux_inside_zellij() {
-        assert_eq!(resolve_backend(true, false, true, false), BackendType::Tmux);
+        assert_eq!(
+            resolve_backend(true, false, true, false, false, &[]).backend,
+            BackendType::Tmux
+        );
     }
 
     #[test]
     fn wezterm_inside_kitty() {
         assert_eq!(
-            resolve_backend(false, true, false, true),
+            resolve_backend(false, true, false, true, false, &[]).backend,
             BackendType::WezTerm
         );
     }
@@ -760,13 +991,71 @@ mod tests {
     #[test]
     fn zellij_inside_kitty() {
         assert_eq!(
-            resolve_backend(false, false, true, true),
+            resolve_backend(false, false, true, true, false, &[]).backend,
             BackendType::Zellij
         );
     }
 
+    #[test]
+    fn iterm2_only() {
+        assert_eq!(
+            resolve_backend(false, false, false, false, true, &[]).backend,
+            BackendType::ITerm2
+        );
+    }
+
+    #[test]
+    fn tmux_inside_iterm2() {
+        assert_eq!(
+            resolve_backend(true, false, false, false, true, &[]).backend,
+            BackendType::Tmux
+        );
+    }
+
     #[test]
     fn all_env_vars_set() {
-        assert_eq!(resolve_backend(true, true, true, true), BackendType::Tmux);
+        assert_eq!(
+            resolve_backend(true, true, true, true, false, &[]).backend,
+            BackendType::Tmux
+        );
+    }
+
+    #[test]
+    fn ci_detection() {
+        assert!(is_ci(Some("true")));
+        assert!(is_ci(Some("1")));
+        assert!(!is_ci(Some("false")));
+        assert!(!is_ci(Some("0")));
+        assert!(!is_ci(Some("")));
+        assert!(!is_ci(None));
+    }
+
+    #[test]
+    fn priority_picks_among_detected_backends() {
+        let priority = [BackendType::WezTerm, BackendType::Tmux];
+        let choice = resolve_backend(true, true, false, false, false, &priority);
+        assert_eq!(choice.backend, BackendType::WezTerm);
+        assert_eq!(
+            choice.reason,
+            "found tmux ($TMUX), wezterm ($WEZTERM_PANE); wezterm comes first in backend_priority"
+        );
+        // Listed backends that weren't detected are skipped
+        let priority = [BackendType::Zellij, BackendType::Kitty];
+        assert_eq!(
+            resolve_backend(true, false, false, true, false, &priority).backend,
+            BackendType::Kitty
+        );
+    }
+
+    #[test]
+    fn priority_ignored_with_one_backend_and_used_as_default_with_none() {
+        let priority = [BackendType::WezTerm];
+        let choice = resolve_backend(true, false, false, false, false, &priority);
+        assert_eq!(choice.backend, BackendType::Tmux);
+        assert_eq!(choice.reason, "$TMUX is set");
+        assert_eq!(
+            resolve_backend(false, false, false, false, false, &priority).backend,
+            BackendType::WezTerm
+        );
     }
 }
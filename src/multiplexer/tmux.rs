@@ -4,10 +4,11 @@
 //! and exposes them through the Multiplexer trait interface.
 
 use anyhow::{Context, Result, anyhow};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::cmd::Cmd;
 use crate::config::SplitDirection as ConfigSplitDirection;
@@ -16,21 +17,58 @@ use super::handshake::TmuxHandshake;
 use super::types::*;
 use super::{Multiplexer, PaneHandshake, agent, util};
 
+/// Pane fields read during reconciliation, in `parse_pane_line` order.
+/// `#{start_time}` is server-wide, so the boot ID comes along for free.
+const PANE_INFO_FORMAT: &str = "#{pane_id}\t#{pane_pid}\t#{pane_current_command}\t#{pane_current_path}\t#{pane_title}\t#{session_name}\t#{window_name}\t#{start_time}";
+
+/// How long a `list-panes` snapshot is reused. Long enough to cover one
+/// reconciliation pass (batched query, per-pane fallbacks, boot ID), short
+/// enough that the next dashboard refresh sees fresh state.
+const SNAPSHOT_TTL: Duration = Duration::from_millis(250);
+
+/// Session option marking sessions created for `session_strategy`. Windows in
+/// these sessions count as workmux's even when run from another session.
+const MANAGED_SESSION_OPTION: &str = "@workmux_session";
+
+/// Fields read by [`TmuxBackend::scoped_windows`], in `parse_scoped_windows` order.
+const SCOPED_WINDOW_FORMAT: &str =
+    "#{session_name}\t#{@workmux_session}\t#{window_id}\t#{window_name}";
+
+/// A window in the current session or in a managed session.
+#[derive(Debug, PartialEq)]
+struct ScopedWindow {
+    session: String,
+    id: String,
+    name: String,
+}
+
+/// Result of one `list-panes -a` query.
+#[derive(Debug)]
+struct PaneSnapshot {
+    taken_at: Instant,
+    panes: HashMap<String, LivePaneInfo>,
+    boot_id: Option<String>,
+}
+
 /// tmux backend implementation.
 ///
 /// This struct wraps all tmux-specific operations and implements the Multiplexer
 /// trait to provide a unified interface with other backends.
 #[derive(Debug, Default)]
-pub struct TmuxBackend;
+pub struct TmuxBackend {
+    /// Cached pane snapshot, cleared by any command that changes tmux state.
+    snapshot: Mutex<Option<PaneSnapshot>>,
+}
 
 impl TmuxBackend {
     /// Create a new TmuxBackend instance.
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
     /// Run a tmux command, returning an error with context on failure.
     fn tmux_cmd(&self, args: &[&str]) -> Result<()> {
+        self.invalidate_snapshot();
         Cmd::new("tmux")
             .args(args)
             .run()
@@ -38,6 +76,51 @@ impl TmuxBackend {
         Ok(())
     }
 
+    fn invalidate_snapshot(&self) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            *snapshot = None;
+        }
+    }
+
+    /// Run `f` on a pane snapshot, querying tmux only if the cached one is
+    /// missing or older than `SNAPSHOT_TTL`.
+    fn with_snapshot<T>(&self, f: impl FnOnce(&PaneSnapshot) -> T) -> Result<T> {
+        let mut cached = self
+            .snapshot
+            .lock()
+            .map_err(|_| anyhow!("tmux pane snapshot lock poisoned"))?;
+        if let Some(snapshot) = cached.as_ref()
+            && snapshot.taken_at.elapsed() < SNAPSHOT_TTL
+        {
+            return Ok(f(snapshot));
+        }
+
+        let output = self.tmux_query(&["list-panes", "-a", "-F", PANE_INFO_FORMAT])?;
+        let mut panes = HashMap::new();
+        let mut boot_id = None;
+        for line in output.lines() {
+            if let Some((pane_id, info, start_time)) = parse_pane_line(line) {
+                boot_id = boot_id.or(start_time);
+                panes.insert(pane_id, info);
+            }
+        }
+        let snapshot = cached.insert(PaneSnapshot {
+            taken_at: Instant::now(),
+            panes,
+            boot_id,
+        });
+        Ok(f(snapshot))
+    }
+
+    /// The cached snapshot if it's still fresh, without querying tmux.
+    fn fresh_snapshot<T>(&self, f: impl FnOnce(&PaneSnapshot) -> T) -> Option<T> {
+        let cached = self.snapshot.lock().ok()?;
+        cached
+            .as_ref()
+            .filter(|snapshot| snapshot.taken_at.elapsed() < SNAPSHOT_TTL)
+            .map(f)
+    }
+
     /// Run a tmux command and capture stdout.
     fn tmux_query(&self, args: &[&str]) -> Result<String> {
         Cmd::new("tmux")
@@ -57,11 +140,116 @@ impl TmuxBackend {
         }
     }
 
+    /// The current session and the windows workmux can see: those in the
+    /// current session plus those in managed sessions.
+    fn scoped_windows(&self) -> (Option<String>, Vec<ScopedWindow>) {
+        let current = self.current_session();
+        let output = self
+            .tmux_query(&["list-windows", "-a", "-F", SCOPED_WINDOW_FORMAT])
+            .unwrap_or_default();
+        let windows = parse_scoped_windows(&output, current.as_deref());
+        (current, windows)
+    }
+
+    /// Exact target for a window by full name. Windows workmux can see are
+    /// targeted by ID, so those in managed sessions resolve from anywhere.
+    fn window_target(&self, full_name: &str) -> String {
+        let (current, windows) = self.scoped_windows();
+        match (windows.into_iter().find(|w| w.name == full_name), current) {
+            (Some(window), _) => window.id,
+            (None, Some(session)) => format!("={}:={}", session, full_name),
+            (None, None) => format!("={}", full_name),
+        }
+    }
+
+    /// Create a window in `session`, creating and marking the session if it
+    /// doesn't exist yet. Returns the new pane ID.
+    fn create_window_in_managed_session(
+        &self,
+        session: &str,
+        prefix: &str,
+        prefixed_name: &str,
+        cwd: &str,
+    ) -> Result<String> {
+        let session_target = format!("={}", session);
+        let exists = Cmd::new("tmux")
+            .args(&["has-session", "-t", &session_target])
+            .run_as_check()
+            .unwrap_or(false);
+
+        let pane_id = if exists {
+            // Keep workmux windows grouped at the end of the session
+            let windows = self
+                .tmux_query(&[
+                    "list-windows",
+                    "-t",
+                    &session_target,
+                    "-F",
+                    "#{window_id} #{window_name}",
+                ])
+                .unwrap_or_default();
+            let mut cmd = Cmd::new("tmux").args(&["new-window", "-d"]);
+            match last_window_with_prefix(&windows, prefix) {
+                Some(after) => cmd = cmd.arg("-a").args(&["-t", &after]),
+                None => cmd = cmd.args(&["-t", &format!("{}:", session_target)]),
+            }
+            cmd.args(&["-n", prefixed_name, "-c", cwd, "-P", "-F", "#{pane_id}"])
+                .run_and_capture_stdout()
+                .with_context(|| format!("Failed to create window in session '{}'", session))?
+        } else {
+            Cmd::new("tmux")
+                .args(&[
+                    "new-session",
+                    "-d",
+                    "-s",
+                    session,
+                    "-n",
+                    prefixed_name,
+                    "-c",
+                    cwd,
+                    "-P",
+                    "-F",
+                    "#{pane_id}",
+                ])
+                .run_and_capture_stdout()
+                .with_context(|| format!("Failed to create session '{}'", session))?
+        };
+
+        let _ = self.tmux_cmd(&[
+            "set-option",
+            "-t",
+            &session_target,
+            MANAGED_SESSION_OPTION,
+            "1",
+        ]);
+        Ok(pane_id.trim().to_string())
+    }
+
     /// Execute a shell script via tmux run-shell.
     fn run_shell(&self, script: &str) -> Result<()> {
         self.tmux_cmd(&["run-shell", script])
     }
 
+    /// Record focus on the window for `workmux report`. The hook has its own
+    /// index so the status auto-clear hook at `[0]` doesn't replace it.
+    fn track_focus(&self, pane_id: &str) {
+        let Some(exe) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.to_str().map(str::to_string))
+        else {
+            return;
+        };
+        let hook_cmd = format!("run-shell -b '{} _focus \"#{{pane_current_path}}\"'", exe);
+        let _ = self.tmux_cmd(&[
+            "set-hook",
+            "-w",
+            "-t",
+            pane_id,
+            "pane-focus-in[50]",
+            &hook_cmd,
+        ]);
+    }
+
     /// Clear the window status display (status bar icon).
     fn clear_window_status_internal(&self, pane_id: &str) {
         let _ = self.tmux_cmd(&["set-option", "-uw", "-t", pane_id, "@workmux_status"]);
@@ -121,6 +309,7 @@ impl TmuxBackend {
             .to_str()
             .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+        self.invalidate_snapshot();
         let mut cmd = Cmd::new("tmux").args(&[
             "split-window",
             split_arg,
@@ -170,6 +359,16 @@ impl Multiplexer for TmuxBackend {
     }
 
     fn current_pane_id(&self) -> Option<String> {
+        // Under `--remote` this process never runs inside the remote server
+        if crate::transport::remote_host().is_some() {
+            return None;
+        }
+        // Nor, under `--socket`, inside a different local server
+        if let Some(socket) = crate::transport::socket()
+            && env_socket().as_deref() != Some(socket)
+        {
+            return None;
+        }
         std::env::var("TMUX_PANE").ok()
     }
 
@@ -206,6 +405,17 @@ impl Multiplexer for TmuxBackend {
             .to_str()
             .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+        self.invalidate_snapshot();
+        if let Some(session) = params.session {
+            let pane_id = self.create_window_in_managed_session(
+                session,
+                params.prefix,
+                &prefixed_name,
+                working_dir_str,
+            )?;
+            self.track_focus(&pane_id);
+            return Ok(pane_id);
+        }
         let mut cmd = Cmd::new("tmux").args(&["new-window", "-d"]);
 
         // Insert after the target window if specified (keeps workmux windows grouped)
@@ -227,7 +437,9 @@ impl Multiplexer for TmuxBackend {
             .run_and_capture_stdout()
             .context("Failed to create tmux window and get pane ID")?;
 
-        Ok(pane_id.trim().to_string())
+        let pane_id = pane_id.trim().to_string();
+        self.track_focus(&pane_id);
+        Ok(pane_id)
     }
 
     fn create_session(&self, params: CreateSessionParams) -> Result<String> {
@@ -241,6 +453,7 @@ impl Multiplexer for TmuxBackend {
         // -d: detached (don't switch to it yet)
         // -s: session name
         // -c: start directory
+        self.invalidate_snapshot();
         // -P -F: print the pane ID of the initial window
         let mut cmd = Cmd::new("tmux").args(&[
             "new-session",
@@ -274,6 +487,7 @@ impl Multiplexer for TmuxBackend {
                 "off",
             ]);
         }
+        self.track_focus(&pane_id);
 
         Ok(pane_id)
     }
@@ -287,6 +501,7 @@ impl Multiplexer for TmuxBackend {
         // Target the specific session with trailing colon (creates window at next index)
         let target = format!("{}:", params.session_name);
 
+        self.invalidate_snapshot();
         let mut cmd =
             Cmd::new("tmux").args(&["new-window", "-d", "-t", &target, "-c", working_dir_str]);
 
@@ -313,6 +528,7 @@ impl Multiplexer for TmuxBackend {
                 "off",
             ]);
         }
+        self.track_focus(&pane_id);
 
         Ok(pane_id)
     }
@@ -334,13 +550,13 @@ impl Multiplexer for TmuxBackend {
     }
 
     fn kill_window(&self, full_name: &str) -> Result<()> {
-        let target = format!("={}", full_name);
+        let target = self.window_target(full_name);
         self.tmux_cmd(&["kill-window", "-t", &target])
     }
 
     fn rename_window(&self, old_full_name: &str, new_full_name: &str) -> Result<()> {
         // `=` prefix forces exact-name match so we don't hit similarly-named windows.
-        let target = format!("={}", old_full_name);
+        let target = self.window_target(old_full_name);
         self.tmux_cmd(&["rename-window", "-t", &target, new_full_name])
     }
 
@@ -352,7 +568,7 @@ impl Multiplexer for TmuxBackend {
 
     fn schedule_window_close(&self, full_name: &str, delay: Duration) -> Result<()> {
         let delay_secs = format!("{:.3}", delay.as_secs_f64());
-        let target = format!("={}", full_name);
+        let target = self.window_target(full_name);
         let escaped_target = format!("'{}'", target.replace('\'', r#"'\''"#));
         let script = format!(
             "sleep {delay}; tmux kill-window -t {target} >/dev/null 2>&1",
@@ -380,25 +596,13 @@ impl Multiplexer for TmuxBackend {
     }
 
     fn shell_select_window_cmd(&self, full_name: &str) -> Result<String> {
-        let session = self.current_session().unwrap_or_default();
-        let session_prefix = if session.is_empty() {
-            String::new()
-        } else {
-            format!("{}:", session)
-        };
-        let target = format!("{}={}", session_prefix, full_name);
+        let target = self.window_target(full_name);
         let escaped = format!("'{}'", target.replace('\'', r#"'\''"#));
         Ok(format!("tmux select-window -t {} >/dev/null 2>&1", escaped))
     }
 
     fn shell_kill_window_cmd(&self, full_name: &str) -> Result<String> {
-        let session = self.current_session().unwrap_or_default();
-        let session_prefix = if session.is_empty() {
-            String::new()
-        } else {
-            format!("{}:", session)
-        };
-        let target = format!("{}={}", session_prefix, full_name);
+        let target = self.window_target(full_name);
         let escaped = format!("'{}'", target.replace('\'', r#"'\''"#));
         Ok(format!("tmux kill-window -t {} >/dev/null 2>&1", escaped))
     }
@@ -419,8 +623,19 @@ impl Multiplexer for TmuxBackend {
 
     fn select_window(&self, prefix: &str, name: &str) -> Result<()> {
         let prefixed_name = util::prefixed(prefix, name);
-        let target = format!("={}", prefixed_name);
-        self.tmux_cmd(&["select-window", "-t", &target])
+        let (current, windows) = self.scoped_windows();
+        let Some(window) = windows.into_iter().find(|w| w.name == prefixed_name) else {
+            let target = format!("={}", prefixed_name);
+            return self.tmux_cmd(&["select-window", "-t", &target]);
+        };
+        self.tmux_cmd(&["select-window", "-t", &window.id])?;
+
+        // A window in another session also needs the client moved there
+        let session = window.session;
+        if current.as_ref() != Some(&session) && std::env::var("TMUX").is_ok() {
+            self.tmux_cmd(&["switch-client", "-t", &format!("={}", session)])?;
+        }
+        Ok(())
     }
 
     fn window_exists(&self, prefix: &str, name: &str) -> Result<bool> {
@@ -429,13 +644,14 @@ impl Multiplexer for TmuxBackend {
     }
 
     fn window_exists_by_full_name(&self, full_name: &str) -> Result<bool> {
-        match self.tmux_query(&["list-windows", "-F", "#{window_name}"]) {
-            Ok(output) => Ok(output.lines().any(|line| line == full_name)),
-            Err(_) => Ok(false),
-        }
+        let (_, windows) = self.scoped_windows();
+        Ok(windows.iter().any(|w| w.name == full_name))
     }
 
     fn current_window_name(&self) -> Result<Option<String>> {
+        if crate::transport::remote_host().is_some() {
+            return Ok(None);
+        }
         match self.tmux_query(&["display-message", "-p", "#{window_name}"]) {
             Ok(name) => Ok(Some(name.trim().to_string())),
             Err(_) => Ok(None),
@@ -450,10 +666,8 @@ impl Multiplexer for TmuxBackend {
     }
 
     fn get_all_window_names(&self) -> Result<HashSet<String>> {
-        let windows = self
-            .tmux_query(&["list-windows", "-F", "#{window_name}"])
-            .unwrap_or_default();
-        Ok(windows.lines().map(String::from).collect())
+        let (_, windows) = self.scoped_windows();
+        Ok(windows.into_iter().map(|w| w.name).collect())
     }
 
     fn get_all_session_names(&self) -> Result<HashSet<String>> {
@@ -478,17 +692,7 @@ impl Multiplexer for TmuxBackend {
             .tmux_query(&["list-windows", "-F", "#{window_id} #{window_name}"])
             .unwrap_or_default();
 
-        let mut last_match: Option<String> = None;
-
-        for line in output.lines() {
-            if let Some((id, name)) = line.split_once(' ')
-                && name.starts_with(prefix)
-            {
-                last_match = Some(id.to_string());
-            }
-        }
-
-        Ok(last_match)
+        Ok(last_window_with_prefix(&output, prefix))
     }
 
     fn find_last_window_with_base_handle(
@@ -582,15 +786,65 @@ impl Multiplexer for TmuxBackend {
         self.tmux_cmd(&["switch-client", "-t", pane_id])
     }
 
+    fn peek_pane(&self, pane_id: &str, title: &str) -> Result<()> {
+        // display-popup needs a client of this server to draw on
+        if crate::transport::remote_host().is_some() || std::env::var("TMUX").is_err() {
+            return Err(anyhow!("peek needs to run inside tmux"));
+        }
+        let info = self.tmux_query(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{socket_path}\t#{session_id}\t#{window_id}",
+        ])?;
+        let mut parts = info.trim().split('\t');
+        let (Some(socket), Some(session), Some(window)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow!("Failed to look up pane {}", pane_id));
+        };
+
+        let title = format!(" {} ", title);
+        let command = peek_command(socket, session, window, pane_id);
+        self.tmux_cmd(&[
+            "display-popup",
+            "-E",
+            "-w",
+            "90%",
+            "-h",
+            "90%",
+            "-T",
+            &title,
+            &command,
+        ])
+    }
+
     fn kill_pane(&self, pane_id: &str) -> Result<()> {
         self.tmux_cmd(&["kill-pane", "-t", pane_id])
     }
 
+    fn window_geometry(&self, pane_id: &str) -> Result<Option<(String, Vec<String>)>> {
+        let output = self.tmux_query(&[
+            "list-panes",
+            "-t",
+            pane_id,
+            "-F",
+            "#{window_layout}\t#{pane_id}",
+        ])?;
+        Ok(parse_window_geometry(&output))
+    }
+
+    fn restore_window_geometry(&self, pane_id: &str, geometry: &str) -> Result<()> {
+        self.tmux_cmd(&["select-layout", "-t", pane_id, geometry])
+    }
+
     fn respawn_pane(&self, pane_id: &str, cwd: &Path, cmd: Option<&str>) -> Result<String> {
         let working_dir_str = cwd
             .to_str()
             .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+        self.invalidate_snapshot();
         let mut command =
             Cmd::new("tmux").args(&["respawn-pane", "-t", pane_id, "-c", working_dir_str, "-k"]);
 
@@ -646,8 +900,8 @@ impl Multiplexer for TmuxBackend {
     fn paste_multiline(&self, pane_id: &str, content: &str) -> Result<()> {
         use std::io::Write;
 
-        let mut child = std::process::Command::new("tmux")
-            .args(["load-buffer", "-"])
+        let mut child = crate::transport::current()
+            .command("tmux", &["load-buffer", "-"], None)
             .stdin(std::process::Stdio::piped())
             .spawn()
             .context("Failed to spawn tmux load-buffer")?;
@@ -714,7 +968,14 @@ impl Multiplexer for TmuxBackend {
                 "set-option -up @workmux_pane_status ; if-shell -F \"#{{==:#{{@workmux_status}},{}}}\" \"set-option -uw @workmux_status\"",
                 icon
             );
-            let _ = self.tmux_cmd(&["set-hook", "-w", "-t", pane_id, "pane-focus-in", &hook_cmd]);
+            let _ = self.tmux_cmd(&[
+                "set-hook",
+                "-w",
+                "-t",
+                pane_id,
+                "pane-focus-in[0]",
+                &hook_cmd,
+            ]);
         }
 
         Ok(())
@@ -750,100 +1011,161 @@ impl Multiplexer for TmuxBackend {
         // TMUX env var format: /path/to/socket,pid,session_index
         // We use only the socket path, which identifies the tmux server.
         // All sessions on the same server share one socket, so instance_id
-        // is per-server, not per-session.
-        std::env::var("TMUX")
-            .ok()
-            .and_then(|tmux| tmux.split(',').next().map(String::from))
+        // is per-server, not per-session. A remote server is keyed by host.
+        let socket = crate::transport::socket().map(|s| s.to_string_lossy().into_owned());
+        if let Some(host) = crate::transport::remote_host() {
+            return match socket {
+                Some(socket) => format!("ssh://{}{}", host, socket),
+                None => format!("ssh://{}", host),
+            };
+        }
+        socket
+            .or_else(|| env_socket().map(|s| s.to_string_lossy().into_owned()))
             .unwrap_or_else(|| "default".to_string())
     }
 
     fn get_live_pane_info(&self, pane_id: &str) -> Result<Option<LivePaneInfo>> {
-        let format = "#{pane_id}\t#{pane_pid}\t#{pane_current_command}\t#{pane_current_path}\t#{pane_title}\t#{session_name}\t#{window_name}";
+        if let Some(Some(info)) =
+            self.fresh_snapshot(|snapshot| snapshot.panes.get(pane_id).cloned())
+        {
+            return Ok(Some(info));
+        }
 
         // Use display-message to query a specific pane
-        let output = self.tmux_query(&["display-message", "-t", pane_id, "-p", format]);
+        let output = self.tmux_query(&["display-message", "-t", pane_id, "-p", PANE_INFO_FORMAT]);
 
         let output = match output {
             Ok(o) => o,
             Err(_) => return Ok(None), // Pane doesn't exist or error querying
         };
 
-        let line = output.trim();
-        if line.is_empty() {
-            return Ok(None);
-        }
-
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 7 {
-            return Ok(None);
-        }
-
-        Ok(Some(LivePaneInfo {
-            pid: parts[1].parse().ok(),
-            current_command: Some(parts[2].to_string()),
-            working_dir: PathBuf::from(parts[3]),
-            title: if parts[4].is_empty() {
-                None
-            } else {
-                Some(parts[4].to_string())
-            },
-            session: Some(parts[5].to_string()),
-            window: Some(parts[6].to_string()),
-        }))
+        Ok(parse_pane_line(output.trim()).map(|(_, info, _)| info))
     }
 
     fn server_boot_id(&self) -> Result<Option<String>> {
         // #{start_time} is the Unix timestamp when the tmux server started.
         // Stable across the server's lifetime, changes on restart.
-        self.tmux_query(&["display-message", "-p", "#{start_time}"])
-            .map(|s| {
-                let trimmed = s.trim().to_string();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed)
-                }
-            })
+        self.with_snapshot(|snapshot| snapshot.boot_id.clone())
             .or_else(|_| Ok(None))
     }
 
-    fn get_all_live_pane_info(&self) -> Result<std::collections::HashMap<String, LivePaneInfo>> {
-        use std::collections::HashMap;
+    fn get_all_live_pane_info(&self) -> Result<HashMap<String, LivePaneInfo>> {
+        // One list-panes -a covers ALL panes across all sessions
+        self.with_snapshot(|snapshot| snapshot.panes.clone())
+    }
 
-        let format = "#{pane_id}\t#{pane_pid}\t#{pane_current_command}\t#{pane_current_path}\t#{pane_title}\t#{session_name}\t#{window_name}";
+    fn validate_agent_alive(&self, state: &crate::state::AgentState) -> Result<bool> {
+        // list-panes -a sees every pane on the server, so a pane missing from
+        // a fresh snapshot is gone; no need to query it again
+        if self
+            .fresh_snapshot(|snapshot| !snapshot.panes.contains_key(&state.pane_key.pane_id))
+            .unwrap_or(false)
+        {
+            return Ok(false);
+        }
+        let live_pane = self.get_live_pane_info(&state.pane_key.pane_id)?;
+        Ok(live_pane.is_some_and(|live| {
+            live.pid.is_none_or(|pid| pid == state.pane_pid)
+                && live.current_command.is_none_or(|cmd| cmd == state.command)
+        }))
+    }
+}
 
-        // Use list-panes -a to query ALL panes across all sessions at once
-        let output = self.tmux_query(&["list-panes", "-a", "-F", format])?;
+/// Parse one `PANE_INFO_FORMAT` line into the pane ID, its info, and the
+/// server start time.
+fn parse_pane_line(line: &str) -> Option<(String, LivePaneInfo, Option<String>)> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    if parts.len() < 7 || parts[0].is_empty() {
+        return None;
+    }
 
-        let mut panes = HashMap::new();
+    let info = LivePaneInfo {
+        pid: parts[1].parse().ok(),
+        current_command: Some(parts[2].to_string()),
+        working_dir: PathBuf::from(parts[3]),
+        title: if parts[4].is_empty() {
+            None
+        } else {
+            Some(parts[4].to_string())
+        },
+        session: Some(parts[5].to_string()),
+        window: Some(parts[6].to_string()),
+    };
+    let start_time = parts
+        .get(7)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    Some((parts[0].to_string(), info, start_time))
+}
 
-        for line in output.lines() {
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() < 7 {
-                continue;
-            }
+/// The socket of the tmux server this process runs in, from `$TMUX`
+/// (`/path/to/socket,pid,session_index`).
+fn env_socket() -> Option<PathBuf> {
+    std::env::var("TMUX")
+        .ok()
+        .and_then(|tmux| tmux.split(',').next().map(PathBuf::from))
+        .filter(|s| !s.as_os_str().is_empty())
+}
 
-            let pane_id = parts[0].to_string();
-            panes.insert(
-                pane_id,
-                LivePaneInfo {
-                    pid: parts[1].parse().ok(),
-                    current_command: Some(parts[2].to_string()),
-                    working_dir: PathBuf::from(parts[3]),
-                    title: if parts[4].is_empty() {
-                        None
-                    } else {
-                        Some(parts[4].to_string())
-                    },
-                    session: Some(parts[5].to_string()),
-                    window: Some(parts[6].to_string()),
-                },
-            );
-        }
+/// Shell command for the peek popup: attach to a throwaway session grouped
+/// with the agent's, so its window can be shown without changing which window
+/// the agent's session (or this client) has selected. The grouped session
+/// goes away when the popup's client detaches.
+fn peek_command(socket: &str, session: &str, window: &str, pane: &str) -> String {
+    use crate::shell::shell_quote;
+
+    format!(
+        "TMUX= tmux -S {socket} new-session -t {session} \\; set-option destroy-unattached on \\; select-window -t {window} \\; select-pane -t {pane}",
+        socket = shell_quote(socket),
+        session = shell_quote(session),
+        window = shell_quote(window),
+        pane = shell_quote(pane),
+    )
+}
 
-        Ok(panes)
+/// Parse `#{window_layout}\t#{pane_id}` lines (one per pane, in layout order).
+fn parse_window_geometry(output: &str) -> Option<(String, Vec<String>)> {
+    let mut geometry = None;
+    let mut panes = Vec::new();
+    for line in output.lines() {
+        let (layout, pane) = line.split_once('\t')?;
+        geometry.get_or_insert_with(|| layout.to_string());
+        panes.push(pane.to_string());
     }
+    geometry.map(|g| (g, panes))
+}
+
+/// Parse `SCOPED_WINDOW_FORMAT` lines, keeping windows in `current` or in a
+/// managed session.
+fn parse_scoped_windows(output: &str, current: Option<&str>) -> Vec<ScopedWindow> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, '\t');
+            let session = parts.next()?;
+            let managed = !parts.next()?.is_empty();
+            let id = parts.next()?;
+            let name = parts.next()?;
+            (managed || Some(session) == current).then(|| ScopedWindow {
+                session: session.to_string(),
+                id: id.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The ID of the last `#{window_id} #{window_name}` line whose name starts
+/// with `prefix`.
+fn last_window_with_prefix(output: &str, prefix: &str) -> Option<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter(|(_, name)| name.starts_with(prefix))
+        .map(|(id, _)| id.to_string())
+        .last()
 }
+
 /// Format string to inject into tmux window-status-format.
 const WORKMUX_STATUS_FORMAT: &str = "#{?@workmux_status, #{@workmux_status},}";
 
@@ -864,6 +1186,89 @@ fn inject_status_format(format: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn scoped_windows_include_current_and_managed_sessions() {
+        let output = "main\t\t@1\twm-a\nother\t\t@2\twm-b\nwm-app\t1\t@3\twm-c";
+        let windows = parse_scoped_windows(output, Some("main"));
+        let names: Vec<_> = windows.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["wm-a", "wm-c"]);
+        assert_eq!(windows[1].session, "wm-app");
+        assert_eq!(windows[1].id, "@3");
+    }
+
+    #[test]
+    fn peek_command_attaches_a_grouped_session() {
+        assert_eq!(
+            peek_command("/tmp/tmux-1000/default", "$3", "@7", "%12"),
+            "TMUX= tmux -S /tmp/tmux-1000/default new-session -t '$3' \\; set-option destroy-unattached on \\; select-window -t '@7' \\; select-pane -t '%12'"
+        );
+    }
+
+    #[test]
+    fn window_geometry_lists_panes_in_order() {
+        let output = "a1b2,200x50,0,0{100x50,0,0,4,99x50,101,0,5}\t%4\n\
+                      a1b2,200x50,0,0{100x50,0,0,4,99x50,101,0,5}\t%5\n";
+        let (geometry, panes) = parse_window_geometry(output).unwrap();
+        assert_eq!(geometry, "a1b2,200x50,0,0{100x50,0,0,4,99x50,101,0,5}");
+        assert_eq!(panes, ["%4", "%5"]);
+        assert!(parse_window_geometry("").is_none());
+    }
+
+    #[test]
+    fn last_window_with_prefix_picks_last_match() {
+        let output = "@1 wm-a\n@2 zsh\n@3 wm-b\n@4 vim";
+        assert_eq!(
+            last_window_with_prefix(output, "wm-"),
+            Some("@3".to_string())
+        );
+        assert_eq!(last_window_with_prefix(output, "x-"), None);
+    }
+
+    #[test]
+    fn parse_pane_line_reads_fields_and_start_time() {
+        let (pane_id, info, start_time) =
+            parse_pane_line("%3\t4242\tclaude\t/repo/wt\t✳ Fix\tmain\tfeature\t1760000000")
+                .unwrap();
+        assert_eq!(pane_id, "%3");
+        assert_eq!(info.pid, Some(4242));
+        assert_eq!(info.current_command.as_deref(), Some("claude"));
+        assert_eq!(info.working_dir, PathBuf::from("/repo/wt"));
+        assert_eq!(info.title.as_deref(), Some("✳ Fix"));
+        assert_eq!(info.session.as_deref(), Some("main"));
+        assert_eq!(info.window.as_deref(), Some("feature"));
+        assert_eq!(start_time.as_deref(), Some("1760000000"));
+    }
+
+    #[test]
+    fn parse_pane_line_handles_missing_fields() {
+        let (_, info, start_time) =
+            parse_pane_line("%3\t4242\tzsh\t/repo\t\tmain\tfeature").unwrap();
+        assert_eq!(info.title, None);
+        assert_eq!(start_time, None);
+
+        assert!(parse_pane_line("%3\t4242\tzsh").is_none());
+        assert!(parse_pane_line("").is_none());
+    }
+
+    #[test]
+    fn fresh_snapshot_expires() {
+        let backend = TmuxBackend::new();
+        assert!(backend.fresh_snapshot(|_| ()).is_none());
+
+        *backend.snapshot.lock().unwrap() = Some(PaneSnapshot {
+            taken_at: Instant::now(),
+            panes: HashMap::new(),
+            boot_id: Some("1".to_string()),
+        });
+        assert_eq!(
+            backend.fresh_snapshot(|s| s.boot_id.clone()),
+            Some(Some("1".to_string()))
+        );
+
+        backend.snapshot.lock().unwrap().as_mut().unwrap().taken_at -= SNAPSHOT_TTL;
+        assert!(backend.fresh_snapshot(|_| ()).is_none());
+    }
+
     #[test]
     fn test_inject_status_format_standard() {
         let input = "#I:#W#{?window_flags,#{window_flags}, }";
@@ -13,7 +13,7 @@ pub enum ResumeMode {
     None,
     /// Resume the most recent conversation (agent's --continue flag)
     Continue,
-    /// Resume a specific forked session by UUID
+    /// Resume a specific session by ID: a fork, or an earlier conversation
     ForkSession(String),
 }
 
@@ -48,6 +48,9 @@ pub struct AgentPane {
     pub path: PathBuf,
     /// Pane title (set by Claude Code to show session summary)
     pub pane_title: Option<String>,
+    /// What the agent is asking for while waiting
+    #[serde(default)]
+    pub waiting_reason: Option<String>,
     /// Current agent status
     pub status: Option<AgentStatus>,
     /// Unix timestamp when status was last set
@@ -69,6 +72,9 @@ pub struct CreateWindowParams<'a> {
     pub cwd: &'a std::path::Path,
     /// Optional window ID to insert after (for ordering)
     pub after_window: Option<&'a str>,
+    /// Session to create the window in, created if missing (`session_strategy`).
+    /// None means the current session. Backends without sessions ignore it.
+    pub session: Option<&'a str>,
 }
 
 /// Parameters for creating a new session
@@ -102,6 +108,8 @@ pub struct PaneSetupResult {
     pub focus_pane_id: String,
     /// The ID of the pane that should be zoomed, if any
     pub zoom_pane_id: Option<String>,
+    /// Each pane created, with the command it was configured with
+    pub panes: Vec<(String, Option<String>)>,
 }
 
 /// Options for pane setup
@@ -120,7 +128,8 @@ pub struct PaneSetupOptions<'a> {
 }
 
 /// Backend type for multiplexer selection
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BackendType {
     /// tmux backend (default)
     #[default]
@@ -131,6 +140,10 @@ pub enum BackendType {
     Kitty,
     /// Zellij backend
     Zellij,
+    /// iTerm2 backend (macOS), through its Python API
+    ITerm2,
+    /// No multiplexer: windows and panes are skipped (CI, cron, scripts)
+    None,
 }
 
 impl std::fmt::Display for BackendType {
@@ -140,6 +153,8 @@ impl std::fmt::Display for BackendType {
             BackendType::WezTerm => write!(f, "wezterm"),
             BackendType::Kitty => write!(f, "kitty"),
             BackendType::Zellij => write!(f, "zellij"),
+            BackendType::ITerm2 => write!(f, "iterm2"),
+            BackendType::None => write!(f, "none"),
         }
     }
 }
@@ -153,6 +168,8 @@ impl std::str::FromStr for BackendType {
             "wezterm" => Ok(BackendType::WezTerm),
             "kitty" => Ok(BackendType::Kitty),
             "zellij" => Ok(BackendType::Zellij),
+            "iterm2" => Ok(BackendType::ITerm2),
+            "none" => Ok(BackendType::None),
             other => Err(format!("unknown backend: {}", other)),
         }
     }
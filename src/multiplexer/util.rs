@@ -3,7 +3,10 @@
 //! These helpers are shared between tmux, WezTerm, and any future backends.
 
 use std::borrow::Cow;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::shell::shell_quote;
 
 /// Helper function to add prefix to window name.
 ///
@@ -328,10 +331,53 @@ fn inject_flag_after_agent_executable(command: &str, flag: &str) -> String {
     }
 }
 
+/// The directory a pane starts in: its `cwd` joined onto the worktree, or
+/// the worktree itself.
+pub fn pane_dir(working_dir: &Path, cwd: Option<&str>) -> PathBuf {
+    match cwd {
+        Some(cwd) => working_dir.join(crate::util::expand_tilde(cwd)),
+        None => working_dir.to_path_buf(),
+    }
+}
+
+/// Shell statements that export a pane's `env`, run before its startup
+/// script so the pane's shell and everything started in it inherit them.
+/// Names are checked when the config is validated.
+pub fn env_exports(env: &BTreeMap<String, String>) -> String {
+    env.iter()
+        .map(|(key, value)| format!("export {}={}; ", key, shell_quote(value)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+
+    // --- pane env and cwd tests ---
+
+    #[test]
+    fn test_env_exports_quotes_values() {
+        let env = BTreeMap::from([
+            ("PORT".to_string(), "3001".to_string()),
+            ("GREETING".to_string(), "it's up".to_string()),
+        ]);
+        assert_eq!(
+            env_exports(&env),
+            "export GREETING='it'\\''s up'; export PORT=3001; "
+        );
+        assert_eq!(env_exports(&BTreeMap::new()), "");
+    }
+
+    #[test]
+    fn test_pane_dir() {
+        let wt = Path::new("/repo__worktrees/feature");
+        assert_eq!(pane_dir(wt, None), wt);
+        assert_eq!(
+            pane_dir(wt, Some("web")),
+            PathBuf::from("/repo__worktrees/feature/web")
+        );
+        assert_eq!(pane_dir(wt, Some("/tmp/logs")), PathBuf::from("/tmp/logs"));
+    }
 
     // --- prefixed tests ---
 
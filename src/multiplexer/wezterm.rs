@@ -12,9 +12,10 @@ use std::time::Duration;
 
 use crate::cmd::Cmd;
 use crate::config::SplitDirection;
+use crate::platform;
 
 use super::agent;
-use super::handshake::UnixPipeHandshake;
+use super::handshake;
 use super::types::*;
 use super::util;
 use super::{Multiplexer, PaneHandshake};
@@ -129,6 +130,36 @@ impl WezTermBackend {
         Ok(())
     }
 
+    /// Set user vars on a pane. `wezterm cli` can't do this, so the
+    /// SetUserVar sequences are written to the pane's tty, where WezTerm
+    /// reads them as if the pane's program had printed them.
+    fn set_user_vars(&self, pane_id: &str, vars: &[(&str, &str)]) -> Result<()> {
+        use std::io::Write;
+
+        let id: u64 = pane_id.parse().context("Invalid WezTerm pane ID")?;
+        let tty = self
+            .list_panes()?
+            .into_iter()
+            .find(|p| p.pane_id == id)
+            .and_then(|p| p.tty_name)
+            .ok_or_else(|| anyhow!("No tty for WezTerm pane {}", pane_id))?;
+        let tty = if tty.starts_with('/') {
+            tty
+        } else {
+            format!("/dev/{}", tty)
+        };
+
+        let sequences: String = vars
+            .iter()
+            .map(|(name, value)| set_user_var_sequence(name, value))
+            .collect();
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&tty)
+            .and_then(|mut f| f.write_all(sequences.as_bytes()))
+            .with_context(|| format!("Failed to write to {}", tty))
+    }
+
     /// Split a pane with optional command.
     fn split_pane_internal(
         &self,
@@ -167,7 +198,7 @@ impl WezTermBackend {
         // both simple commands and complex shell scripts with quoting
         if let Some(cmd) = command {
             args.push("--");
-            args.push("sh");
+            args.push(platform::sh());
             args.push("-c");
             args.push(cmd);
         }
@@ -195,7 +226,13 @@ impl Multiplexer for WezTermBackend {
 
     fn current_pane_id(&self) -> Option<String> {
         // WEZTERM_PANE is reliable when WezTerm is properly configured
-        // (default_gui_startup_args = { 'connect', 'unix' })
+        // (default_gui_startup_args = { 'connect', 'unix' }), but names a pane
+        // in another mux when `--socket` points elsewhere
+        if let Some(socket) = crate::transport::socket()
+            && std::env::var_os("WEZTERM_UNIX_SOCKET").as_deref() != Some(socket.as_os_str())
+        {
+            return None;
+        }
         std::env::var("WEZTERM_PANE").ok()
     }
 
@@ -325,7 +362,7 @@ impl Multiplexer for WezTermBackend {
             kill_cmds
         );
 
-        Cmd::new("sh").args(&["-c", &script]).run()?;
+        Cmd::new(platform::sh()).args(&["-c", &script]).run()?;
         Ok(())
     }
 
@@ -338,7 +375,7 @@ impl Multiplexer for WezTermBackend {
     fn run_deferred_script(&self, script: &str) -> Result<()> {
         // Run the script in the background using nohup
         let bg_script = format!("nohup sh -c '{}' >/dev/null 2>&1 &", script);
-        Cmd::new("sh").args(&["-c", &bg_script]).run()?;
+        Cmd::new(platform::sh()).args(&["-c", &bg_script]).run()?;
         Ok(())
     }
 
@@ -605,7 +642,7 @@ impl Multiplexer for WezTermBackend {
             // Wrap in sh -c to correctly handle complex shell scripts with quoting
             if let Some(c) = cmd {
                 args.push("--");
-                args.push("sh");
+                args.push(platform::sh());
                 args.push("-c");
                 args.push(c);
             }
@@ -734,32 +771,41 @@ impl Multiplexer for WezTermBackend {
 
     fn get_default_shell(&self) -> Result<String> {
         // WezTerm doesn't have a config query CLI
-        // Use $SHELL or fall back to /bin/bash
-        std::env::var("SHELL").or_else(|_| Ok("/bin/bash".to_string()))
+        // Use $SHELL or fall back to bash (Git Bash on Windows)
+        Ok(platform::default_shell())
     }
 
     fn create_handshake(&self) -> Result<Box<dyn PaneHandshake>> {
-        Ok(Box::new(UnixPipeHandshake::new()?))
+        handshake::shell_handshake()
     }
 
     // === Status ===
 
-    fn set_status(&self, pane_id: &str, icon: &str, _auto_clear_on_focus: bool) -> Result<()> {
-        // For WezTerm, we could update the tab title to include the icon.
-        // However, agent state is now managed by StateStore, so this is just UI feedback.
-        // For now, we just log the status change - tab title remains stable.
-        // Future: could update tab title to show icon like "🔄 wm-feature"
-        let _ = (pane_id, icon); // Acknowledge parameters
+    fn set_status(&self, pane_id: &str, icon: &str, auto_clear_on_focus: bool) -> Result<()> {
+        // The tab title is how workmux finds its tabs, so the icon goes in a
+        // user var that a format-tab-title handler appends (docs/guide/wezterm.md)
+        let auto_clear = if auto_clear_on_focus { "1" } else { "" };
+        if let Err(e) = self.set_user_vars(
+            pane_id,
+            &[("workmux_status", icon), ("workmux_auto_clear", auto_clear)],
+        ) {
+            tracing::debug!(pane_id, error = %e, "wezterm:failed to set status user vars");
+        }
         Ok(())
     }
 
-    fn clear_status(&self, _pane_id: &str) -> Result<()> {
-        // No UI cleanup needed - tab title remains stable
+    fn clear_status(&self, pane_id: &str) -> Result<()> {
+        if let Err(e) = self.set_user_vars(
+            pane_id,
+            &[("workmux_status", ""), ("workmux_auto_clear", "")],
+        ) {
+            tracing::debug!(pane_id, error = %e, "wezterm:failed to clear status user vars");
+        }
         Ok(())
     }
 
     fn ensure_status_format(&self, _pane_id: &str) -> Result<()> {
-        // No-op for WezTerm - status is displayed via tab title, not tmux-style format
+        // No-op for WezTerm - the tab bar format lives in wezterm.lua
         Ok(())
     }
 
@@ -775,6 +821,9 @@ impl Multiplexer for WezTermBackend {
         // Use the unix socket path as instance ID so all workspaces on the same
         // WezTerm server share one instance — matching tmux behavior where all
         // sessions on the same server are visible in the dashboard.
+        if let Some(socket) = crate::transport::socket() {
+            return socket.to_string_lossy().into_owned();
+        }
         std::env::var("WEZTERM_UNIX_SOCKET").unwrap_or_else(|_| "default".to_string())
     }
 
@@ -807,7 +856,7 @@ impl Multiplexer for WezTermBackend {
                 // This matches what we store in state files (the agent PID, not shell PID)
                 let pid = tty_name
                     .and_then(|tty| {
-                        Cmd::new("sh")
+                        Cmd::new(platform::sh())
                             .args(&[
                                 "-c",
                                 &format!(
@@ -827,7 +876,7 @@ impl Multiplexer for WezTermBackend {
                         // Find the foreground process group and get its command
                         // ps -t <tty> -o stat=,comm= shows STAT and COMMAND
                         // Foreground processes have '+' in STAT (e.g., "S+", "R+")
-                        Cmd::new("sh")
+                        Cmd::new(platform::sh())
                             .args(&[
                                 "-c",
                                 &format!(
@@ -880,7 +929,7 @@ impl Multiplexer for WezTermBackend {
 
             let pid = tty_name
                 .and_then(|tty| {
-                    Cmd::new("sh")
+                    Cmd::new(platform::sh())
                         .args(&[
                             "-c",
                             &format!(
@@ -895,7 +944,7 @@ impl Multiplexer for WezTermBackend {
 
             let current_command = tty_name
                 .and_then(|tty| {
-                    Cmd::new("sh")
+                    Cmd::new(platform::sh())
                         .args(&[
                             "-c",
                             &format!(
@@ -949,6 +998,14 @@ impl Multiplexer for WezTermBackend {
     }
 }
 
+/// OSC 1337 sequence that sets a pane user var.
+fn set_user_var_sequence(name: &str, value: &str) -> String {
+    use base64::Engine;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(value);
+    format!("\x1b]1337;SetUserVar={}={}\x07", name, encoded)
+}
+
 /// Send escape sequence to trigger cross-workspace pane switch via WezTerm's user-var-changed event.
 ///
 /// This requires the user to have a Lua handler in their wezterm.lua.
@@ -957,7 +1014,6 @@ impl Multiplexer for WezTermBackend {
 ///
 /// Without this handler, the escape sequence is silently ignored.
 fn send_pane_switch_signal(workspace: &str, tab_title: &str) {
-    use base64::Engine;
     use std::io::Write;
 
     // Send JSON with workspace and tab_title (stable across mux contexts)
@@ -965,9 +1021,10 @@ fn send_pane_switch_signal(workspace: &str, tab_title: &str) {
         "workspace": workspace,
         "tab_title": tab_title
     });
-    let encoded = base64::engine::general_purpose::STANDARD.encode(payload.to_string());
-    // OSC 1337 ; SetUserVar=name=base64_value BEL
-    print!("\x1b]1337;SetUserVar=workmux-switch-pane={}\x07", encoded);
+    print!(
+        "{}",
+        set_user_var_sequence("workmux-switch-pane", &payload.to_string())
+    );
     // Flush to ensure it's sent immediately
     let _ = std::io::stdout().flush();
 }
@@ -976,6 +1033,18 @@ fn send_pane_switch_signal(workspace: &str, tab_title: &str) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn user_var_sequence_base64_encodes_value() {
+        assert_eq!(
+            set_user_var_sequence("workmux_status", "done"),
+            "\x1b]1337;SetUserVar=workmux_status=ZG9uZQ==\x07"
+        );
+        assert_eq!(
+            set_user_var_sequence("workmux_status", ""),
+            "\x1b]1337;SetUserVar=workmux_status=\x07"
+        );
+    }
+
     #[test]
     fn test_cwd_path_parsing() {
         let pane = WezTermPane {
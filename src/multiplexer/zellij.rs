@@ -3,21 +3,30 @@
 //! Limitations:
 //! - No percentage-based pane size control (can resize with +/- but not set exact %)
 //! - No window insertion order (tabs always append)
-//! - No visual status indicator (set_status is a no-op)
+//! - Pane capture only works for the focused pane (dump-screen has no pane target)
+//! - Status is published over `zellij pipe` for a plugin to render, not drawn
+//!   in tab titles
 
 use anyhow::{Context, Result, anyhow};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::time::Duration;
 use tracing::{debug, warn};
 
 use crate::cmd::Cmd;
 use crate::config::SplitDirection;
 
-use super::handshake::UnixPipeHandshake;
+use super::handshake;
 use super::types::{CreateWindowParams, LivePaneInfo};
 use super::{Multiplexer, PaneHandshake};
 
+/// Pipe name status updates are published on (see `set_status`)
+const STATUS_PIPE: &str = "workmux-status";
+
+/// How long a status pipe may wait for a plugin before it's killed
+const STATUS_PIPE_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Zellij multiplexer backend.
 pub struct ZellijBackend {
     _private: (),
@@ -95,6 +104,37 @@ fn parse_tab_name_from_output(output: &str) -> Option<String> {
         .map(|l| l["name: ".len()..].to_string())
 }
 
+/// Payload of a `workmux-status` pipe message. An empty `icon` clears the
+/// status.
+fn status_payload(pane_id: &str, tab: Option<&str>, icon: &str, auto_clear: bool) -> String {
+    serde_json::json!({
+        "pane_id": pane_id,
+        "tab": tab,
+        "icon": icon,
+        "auto_clear": auto_clear,
+    })
+    .to_string()
+}
+
+/// Send `payload` on the status pipe without waiting for it. `zellij pipe`
+/// blocks until a plugin picks the message up, so with no listening plugin
+/// it would hang. It runs in a detached background job that kills it after
+/// `STATUS_PIPE_TIMEOUT`, so the bound holds after this process exits.
+fn send_status_pipe(payload: &str) -> Result<()> {
+    let script = format!(
+        "{{ zellij pipe --name \"$1\" -- \"$2\" & pid=$!; sleep {}; kill $pid; }} >/dev/null 2>&1 &",
+        STATUS_PIPE_TIMEOUT.as_secs()
+    );
+    Command::new("sh")
+        .args(["-c", &script, "sh", STATUS_PIPE, payload])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("Failed to run zellij pipe")?;
+    Ok(())
+}
+
 impl Default for ZellijBackend {
     fn default() -> Self {
         Self::new()
@@ -665,11 +705,15 @@ impl Multiplexer for ZellijBackend {
         Ok(pane_id.to_string())
     }
 
-    fn capture_pane(&self, _pane_id: &str, _lines: u16) -> Option<String> {
+    fn capture_pane(&self, pane_id: &str, _lines: u16) -> Option<String> {
         // Zellij limitation: dump-screen always captures the focused pane,
-        // not the pane specified by pane_id. When the dashboard is focused,
-        // it captures itself, creating a recursive loop. We detect this and
-        // return None to prevent the recursion.
+        // not the pane specified by pane_id. Only capture when the requested
+        // pane is the focused one; otherwise we'd return another pane's
+        // content (or the dashboard capturing itself).
+        let numeric_id = parse_pane_id(pane_id)?;
+        if Self::focused_pane_id().ok() != Some(numeric_id) {
+            return None;
+        }
 
         // Use PID + thread ID + timestamp for thread-safe temp file naming
         let timestamp = std::time::SystemTime::now()
@@ -804,21 +848,37 @@ impl Multiplexer for ZellijBackend {
     }
 
     fn create_handshake(&self) -> Result<Box<dyn PaneHandshake>> {
-        // Reuse the same Unix pipe handshake as WezTerm
-        Ok(Box::new(UnixPipeHandshake::new()?))
+        // Same handshake as WezTerm
+        handshake::shell_handshake()
     }
 
     // === Status ===
 
-    fn set_status(&self, _pane_id: &str, _icon: &str, _auto_clear_on_focus: bool) -> Result<()> {
-        // No-op: can't target specific panes, and rename-pane would hijack
-        // the user's focused pane. Status is tracked in StateStore by tab name.
-        Ok(())
+    fn set_status(&self, pane_id: &str, icon: &str, auto_clear_on_focus: bool) -> Result<()> {
+        // rename-tab would hijack the user's focused tab, so publish the status
+        // on a pipe for a plugin to render. Status is also
+        // tracked in StateStore, which the dashboard reads.
+        let numeric_id =
+            parse_pane_id(pane_id).ok_or_else(|| anyhow!("Invalid pane_id format: {}", pane_id))?;
+        let tab = Self::list_panes()
+            .ok()
+            .and_then(|panes| {
+                panes
+                    .into_iter()
+                    .find(|p| p.id == numeric_id && !p.is_plugin)
+            })
+            .map(|p| p.tab_name)
+            .filter(|t| !t.is_empty());
+        send_status_pipe(&status_payload(
+            pane_id,
+            tab.as_deref(),
+            icon,
+            auto_clear_on_focus,
+        ))
     }
 
-    fn clear_status(&self, _pane_id: &str) -> Result<()> {
-        // No-op: status is managed by StateStore
-        Ok(())
+    fn clear_status(&self, pane_id: &str) -> Result<()> {
+        send_status_pipe(&status_payload(pane_id, None, "", false))
     }
 
     fn ensure_status_format(&self, _pane_id: &str) -> Result<()> {
@@ -834,8 +894,8 @@ impl Multiplexer for ZellijBackend {
     /// Split a pane in Zellij.
     ///
     /// **Zellij CLI Limitations:**
-    /// - `target_pane_id` is ignored - Zellij's `new-pane` command doesn't support
-    ///   targeting specific panes for splitting (always splits the focused pane).
+    /// - `new-pane` always splits the focused pane, so `target_pane_id` is
+    ///   focused first. If it isn't in the current tab, the focused pane is split.
     /// - `size`/`percentage` are ignored - all splits are 50/50.
     ///
     /// **Returns:** The pane ID from `new-pane` stdout (e.g., "terminal_5").
@@ -848,10 +908,13 @@ impl Multiplexer for ZellijBackend {
         _percentage: Option<u8>,
         command: Option<&str>,
     ) -> Result<String> {
-        debug!(
-            "split_pane: target_pane_id '{}' (note: new-pane splits focused pane only)",
-            target_pane_id
-        );
+        if let Err(e) = self.select_pane(target_pane_id) {
+            debug!(
+                target_pane_id,
+                error = %e,
+                "split_pane: could not focus target, splitting focused pane"
+            );
+        }
 
         let dir_arg = match direction {
             SplitDirection::Horizontal => "right", // panes side-by-side (left/right)
@@ -1194,6 +1257,27 @@ mod tests {
         assert!(panes[1].is_plugin);
     }
 
+    // === status_payload ===
+
+    #[test]
+    fn status_payload_fields() {
+        let payload: serde_json::Value =
+            serde_json::from_str(&status_payload("terminal_3", Some("wm-feat"), "🤖", true))
+                .unwrap();
+        assert_eq!(payload["pane_id"], "terminal_3");
+        assert_eq!(payload["tab"], "wm-feat");
+        assert_eq!(payload["icon"], "🤖");
+        assert_eq!(payload["auto_clear"], true);
+    }
+
+    #[test]
+    fn status_payload_clear_has_empty_icon() {
+        let payload: serde_json::Value =
+            serde_json::from_str(&status_payload("terminal_3", None, "", false)).unwrap();
+        assert_eq!(payload["icon"], "");
+        assert!(payload["tab"].is_null());
+    }
+
     // === TabInfo deserialization ===
 
     #[test]
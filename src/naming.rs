@@ -45,7 +45,7 @@ fn validate_handle(handle: &str) -> Result<()> {
     }
 
     // Slugify should have removed these, but double check for safety
-    if handle.contains("..") || handle.starts_with('/') {
+    if handle.contains("..") || handle.starts_with(['/', '\\']) || handle.contains(':') {
         bail!("Handle cannot contain path traversal");
     }
 
@@ -226,6 +226,12 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn validate_handle_rejects_windows_paths() {
+        assert!(validate_handle("\\server\\share").is_err());
+        assert!(validate_handle("C:feature").is_err());
+    }
+
     // === WorktreeNaming::derive_name tests ===
 
     #[test]
@@ -6,7 +6,7 @@
 use anyhow::Result;
 use console::style;
 use std::fs;
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
@@ -178,7 +178,7 @@ pub fn prompt_setup() -> Result<Option<bool>> {
     }
 
     // Only prompt in interactive mode
-    if !io::stdin().is_terminal() {
+    if !crate::interactive::can_prompt() {
         return Ok(None);
     }
 
@@ -0,0 +1,159 @@
+//! Email delivery (`notifications.email`), used by `workmux digest`.
+//!
+//! Mail goes through a local sendmail-compatible program by default, or
+//! through an SMTP server with curl.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use super::webhook::curl_quote;
+use crate::config::{EmailConfig, SmtpConfig};
+
+/// Seconds before curl gives up on the SMTP server
+const SMTP_TIMEOUT_SECS: &str = "30";
+
+/// Send a plain-text email to the configured recipient.
+pub fn send(config: &EmailConfig, subject: &str, body: &str) -> Result<()> {
+    let message = format_message(config, subject, body);
+    match &config.smtp {
+        Some(smtp) => send_smtp(config, smtp, &message),
+        None => send_sendmail(config, &message),
+    }
+}
+
+fn format_message(config: &EmailConfig, subject: &str, body: &str) -> String {
+    let mut message = String::new();
+    if let Some(from) = &config.from {
+        message.push_str(&format!("From: {}\r\n", from));
+    }
+    message.push_str(&format!("To: {}\r\n", config.to));
+    message.push_str(&format!("Subject: {}\r\n", subject));
+    message.push_str("MIME-Version: 1.0\r\n");
+    message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+    message.push_str(&body.replace('\n', "\r\n"));
+    message
+}
+
+fn send_sendmail(config: &EmailConfig, message: &str) -> Result<()> {
+    let program = config.sendmail.as_deref().unwrap_or("sendmail");
+    let mut child = Command::new(program)
+        // Take recipients from the To header
+        .arg("-t")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}. Is it installed?", program))?;
+    child
+        .stdin
+        .take()
+        .context("Failed to open sendmail stdin")?
+        .write_all(message.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn send_smtp(config: &EmailConfig, smtp: &SmtpConfig, message: &str) -> Result<()> {
+    let from = config
+        .from
+        .as_deref()
+        .ok_or_else(|| anyhow!("notifications.email.from is required to send through SMTP"))?;
+    let mut file = tempfile::NamedTempFile::new()?;
+    file.write_all(message.as_bytes())?;
+
+    // Credentials go in a curl config on stdin so they don't show up in `ps`
+    let output = {
+        let mut child = Command::new("curl")
+            .args(["-sS", "-m", SMTP_TIMEOUT_SECS, "-K", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl. Is curl installed?")?;
+        let config = curl_config(smtp, from, &config.to, &file.path().to_string_lossy());
+        child
+            .stdin
+            .take()
+            .context("Failed to open curl stdin")?
+            .write_all(config.as_bytes())?;
+        child.wait_with_output()?
+    };
+    if !output.status.success() {
+        bail!(
+            "SMTP delivery failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// A curl config file (`-K`) that uploads the message at `path` over SMTP.
+fn curl_config(smtp: &SmtpConfig, from: &str, to: &str, path: &str) -> String {
+    let mut lines = vec![
+        format!("url = {}", curl_quote(&smtp.url)),
+        format!("mail-from = {}", curl_quote(from)),
+        format!("mail-rcpt = {}", curl_quote(to)),
+        format!("upload-file = {}", curl_quote(path)),
+    ];
+    if let Some(username) = &smtp.username {
+        let password = smtp.password.as_deref().unwrap_or_default();
+        lines.push(format!(
+            "user = {}",
+            curl_quote(&format!("{}:{}", username, password))
+        ));
+        // Never send credentials in the clear: require STARTTLS on smtp://
+        lines.push("ssl-reqd".to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> EmailConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn format_message_headers() {
+        let message = format_message(
+            &config("to: me@example.com\nfrom: workmux@example.com\n"),
+            "workmux digest",
+            "line 1\nline 2",
+        );
+        assert!(message.starts_with(
+            "From: workmux@example.com\r\nTo: me@example.com\r\nSubject: workmux digest\r\n"
+        ));
+        assert!(message.ends_with("\r\n\r\nline 1\r\nline 2"));
+
+        let without_from = format_message(&config("to: me@example.com\n"), "s", "b");
+        assert!(without_from.starts_with("To: me@example.com\r\n"));
+    }
+
+    #[test]
+    fn curl_config_requires_tls_with_credentials() {
+        let smtp: SmtpConfig = serde_yaml::from_str(
+            "url: smtp://smtp.example.com:587\nusername: me\npassword: \"p\\\"w\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            curl_config(&smtp, "a@example.com", "b@example.com", "/tmp/msg"),
+            "url = \"smtp://smtp.example.com:587\"\n\
+             mail-from = \"a@example.com\"\n\
+             mail-rcpt = \"b@example.com\"\n\
+             upload-file = \"/tmp/msg\"\n\
+             user = \"me:p\\\"w\"\n\
+             ssl-reqd\n"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Escalation for agents left waiting (`notifications.escalation`).
+//!
+//! When an agent starts waiting, a detached `workmux _escalate` process
+//! watches its state. If the agent is still waiting since the same
+//! `status_ts` once the configured time has passed, nobody answered, so a
+//! louder notification goes out through the escalation channels (an urgent
+//! ntfy push by default).
+
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+use super::webhook::WebhookPayload;
+use super::{email, ntfy, webhook};
+use crate::config::{Config, EscalationChannel, NotificationEvent, NotificationsConfig};
+use crate::multiplexer::{AgentStatus, Multiplexer, create_backend, detect_backend};
+use crate::platform;
+use crate::state::{AgentState, PaneKey, StateStore};
+use crate::util::format_compact_age;
+
+/// How often the waiting agent's state is checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Start watching the agent in `pane_id` if it just started waiting and
+/// escalation is enabled (best-effort).
+pub fn schedule(
+    config: &NotificationsConfig,
+    mux: &dyn Multiplexer,
+    pane_id: &str,
+    previous: Option<AgentStatus>,
+    status: AgentStatus,
+) {
+    if status != AgentStatus::Waiting
+        || previous == Some(AgentStatus::Waiting)
+        || config.escalation.after().is_none()
+    {
+        return;
+    }
+    let key = PaneKey {
+        backend: mux.name().to_string(),
+        instance: mux.instance_id(),
+        pane_id: pane_id.to_string(),
+    };
+    let Some(agent) = StateStore::new()
+        .ok()
+        .and_then(|store| store.get_agent(&key).ok().flatten())
+    else {
+        return;
+    };
+    let Some(since) = agent.status_ts else {
+        return;
+    };
+
+    let spawned = std::env::current_exe().and_then(|exe| {
+        let mut command = Command::new(exe);
+        command
+            .args(["_escalate", "--backend", &key.backend, "--instance"])
+            .arg(&key.instance)
+            .args(["--pane", pane_id, "--since", &since.to_string()])
+            // Payloads name the project from the working directory
+            .current_dir(&agent.workdir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        // Outlive the hook that started it
+        platform::detach(&mut command).spawn()
+    });
+    if let Err(e) = spawned {
+        tracing::warn!(%pane_id, error = %e, "failed to schedule escalation");
+    }
+}
+
+/// Wait until the agent has been waiting for the escalation time, then
+/// escalate if it still is. Returns early once the agent moves on.
+pub fn run(key: PaneKey, since: u64) -> Result<()> {
+    let config = Config::load(None)?;
+    let Some(after) = config.notifications.escalation.after() else {
+        return Ok(());
+    };
+    let store = StateStore::new()?;
+    let waiting = || {
+        store
+            .get_agent(&key)
+            .ok()
+            .flatten()
+            .filter(|agent| still_waiting(agent, since))
+    };
+
+    let deadline = Instant::now() + after;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        std::thread::sleep(remaining.min(POLL_INTERVAL));
+        if waiting().is_none() {
+            return Ok(());
+        }
+    }
+    let Some(agent) = waiting() else {
+        return Ok(());
+    };
+
+    // The pane may have closed before reconciliation removed its state
+    let mux = create_backend(detect_backend());
+    if mux.name() == key.backend && matches!(mux.get_live_pane_info(&key.pane_id), Ok(None)) {
+        return Ok(());
+    }
+
+    let root = crate::git::get_repo_root_for(&agent.workdir).unwrap_or(agent.workdir);
+    let handle = root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.display().to_string());
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let message = escalation_message(
+        &handle,
+        now.saturating_sub(since),
+        agent.waiting_reason.as_deref(),
+    );
+    escalate(
+        &config.notifications,
+        &WebhookPayload::new(NotificationEvent::Waiting, &handle, message),
+    );
+    Ok(())
+}
+
+fn still_waiting(agent: &AgentState, since: u64) -> bool {
+    agent.status == Some(AgentStatus::Waiting) && agent.status_ts == Some(since)
+}
+
+fn escalation_message(handle: &str, waited_secs: u64, reason: Option<&str>) -> String {
+    let waited = format_compact_age(waited_secs);
+    match reason {
+        Some(reason) => format!("{} has been waiting {}: {}", handle, waited, reason),
+        None => format!("{} has been waiting {} for your input", handle, waited),
+    }
+}
+
+/// Send `payload` through every escalation channel, ignoring the channels'
+/// own `events` filters (best-effort).
+fn escalate(config: &NotificationsConfig, payload: &WebhookPayload) {
+    for channel in config.escalation.via() {
+        match channel {
+            EscalationChannel::Ntfy => match &config.ntfy {
+                Some(ntfy_config) => ntfy::send_urgent(ntfy_config, payload),
+                None => tracing::warn!("escalation via ntfy, but notifications.ntfy is not set"),
+            },
+            EscalationChannel::Webhooks => {
+                for hook in config.webhooks.iter().flatten() {
+                    if let Err(e) = webhook::send(hook, payload) {
+                        tracing::warn!(url = %hook.url, error = %e, "webhook failed");
+                    }
+                }
+            }
+            EscalationChannel::Email => match &config.email {
+                Some(email_config) => {
+                    let subject = format!("workmux: {}", payload.message);
+                    if let Err(e) = email::send(email_config, &subject, &payload.message) {
+                        tracing::warn!(error = %e, "escalation email failed");
+                    }
+                }
+                None => tracing::warn!("escalation via email, but notifications.email is not set"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escalation_messages() {
+        assert_eq!(
+            escalation_message("api", 900, None),
+            "api has been waiting 15m for your input"
+        );
+        assert_eq!(
+            escalation_message(
+                "api",
+                3_700,
+                Some("Claude needs your permission to use Bash")
+            ),
+            "api has been waiting 1h: Claude needs your permission to use Bash"
+        );
+    }
+
+    #[test]
+    fn escalation_config_defaults() {
+        let config: NotificationsConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.escalation.after(), None);
+        assert_eq!(config.escalation.via(), [EscalationChannel::Ntfy]);
+
+        let config: NotificationsConfig =
+            serde_yaml::from_str("escalation:\n  minutes: 15\n  via: [ntfy, email]\n").unwrap();
+        assert_eq!(config.escalation.after(), Some(Duration::from_secs(900)));
+        assert_eq!(
+            config.escalation.via(),
+            [EscalationChannel::Ntfy, EscalationChannel::Email]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Recent notification events, kept for `workmux digest`.
+//!
+//! Every waiting/done transition and merge is appended to
+//! `$XDG_STATE_HOME/workmux/events.jsonl`, whether or not any notification
+//! channel is configured.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::webhook::WebhookPayload;
+use crate::state::jsonl;
+use crate::state::store::get_state_dir;
+
+/// Number of events kept in the history file.
+const MAX_EVENTS: usize = 1000;
+
+fn history_path() -> Result<PathBuf> {
+    let dir = get_state_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join("events.jsonl"))
+}
+
+/// Append an event to the history, keeping the newest `MAX_EVENTS`.
+pub fn record(payload: &WebhookPayload) -> Result<()> {
+    jsonl::append(&history_path()?, payload, MAX_EVENTS)
+}
+
+/// Events at or after `since` (Unix seconds), oldest first.
+pub fn load_since(since: u64) -> Result<Vec<WebhookPayload>> {
+    let mut events: Vec<WebhookPayload> = jsonl::read(&history_path()?)?;
+    events.retain(|event| event.timestamp >= since);
+    Ok(events)
+}
@@ -0,0 +1,271 @@
+//! Desktop notifications.
+//!
+//! Backends, in order of preference:
+//! - macOS: `terminal-notifier` if installed, else the native API, else `osascript`
+//! - WSL: a Windows toast through `powershell.exe`
+//! - Linux: `notify-send` if installed, else D-Bus directly
+//! - Windows: a native toast
+//!
+//! Status changes and merges can also be sent to webhooks (see [`webhook`])
+//! or ntfy (see [`ntfy`]), and signalled with terminal escape sequences
+//! (see [`osc`]). They are recorded (see [`history`]) for `workmux digest`,
+//! which can be emailed (see [`email`]). Agents left waiting can be
+//! escalated to a louder channel (see [`escalation`]).
+
+pub mod email;
+pub mod escalation;
+pub mod history;
+pub mod ntfy;
+pub mod osc;
+pub mod sound;
+pub mod webhook;
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cmd::Cmd;
+use crate::config::{NotificationEvent, NotificationsConfig};
+use crate::multiplexer::AgentStatus;
+use sound::{Sound, SoundEvent};
+use webhook::WebhookPayload;
+
+const TITLE: &str = "workmux";
+
+/// Shows a system notification
+pub fn show(message: &str) {
+    show_with_sound(message, false);
+}
+
+/// Shows a system notification, optionally with the platform's default
+/// notification sound
+pub fn show_with_sound(message: &str, sound: bool) {
+    if let Err(e) = send(message, sound) {
+        tracing::debug!("Failed to send notification: {:?}", e);
+    }
+}
+
+/// Shows a system notification with the sound configured for `event`
+pub fn show_for(config: &NotificationsConfig, message: &str, event: SoundEvent) {
+    match sound::resolve(config, event, crate::platform::local_minute_of_day()) {
+        Sound::Default => show_with_sound(message, true),
+        Sound::Mute => show_with_sound(message, false),
+        Sound::Custom(custom) => {
+            show_with_sound(message, false);
+            sound::play(&custom);
+        }
+    }
+}
+
+/// Notify that the agent working in `dir` changed status, on the desktop, to
+/// webhooks and to ntfy, as configured for the new status. `reason` is what a
+/// waiting agent is asking for. Repeated updates with the same status don't
+/// notify again.
+pub fn agent_status_changed(
+    config: &NotificationsConfig,
+    dir: &Path,
+    previous: Option<AgentStatus>,
+    status: AgentStatus,
+    reason: Option<&str>,
+) {
+    if previous == Some(status) {
+        return;
+    }
+    let (event, sound_event) = match status {
+        AgentStatus::Working => return,
+        AgentStatus::Waiting => (NotificationEvent::Waiting, SoundEvent::Waiting),
+        AgentStatus::Done => (NotificationEvent::Done, SoundEvent::Done),
+    };
+
+    let handle = worktree_handle(dir);
+    let message = status_message(&handle, status, reason);
+    if config.enabled_for(status) {
+        show_for(config, &message, sound_event);
+    }
+    publish(config, &WebhookPayload::new(event, &handle, message));
+}
+
+/// Record an event for `workmux digest` and send it to the configured
+/// webhooks and ntfy topic (best-effort).
+pub fn publish(config: &NotificationsConfig, payload: &WebhookPayload) {
+    if let Err(e) = history::record(payload) {
+        tracing::warn!(error = %e, "failed to record notification event");
+    }
+    if let Some(webhooks) = &config.webhooks {
+        webhook::send_all(webhooks, payload);
+    }
+    if let Some(ntfy_config) = &config.ntfy {
+        ntfy::send(ntfy_config, payload);
+    }
+}
+
+/// Name of the worktree containing `dir`, as shown in notifications.
+pub fn worktree_handle(dir: &Path) -> String {
+    let worktree_path = crate::git::get_repo_root_for(dir).unwrap_or_else(|_| dir.to_path_buf());
+    worktree_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| worktree_path.display().to_string())
+}
+
+/// Notification text for an agent reaching `status`.
+pub fn status_message(handle: &str, status: AgentStatus, reason: Option<&str>) -> String {
+    match status {
+        AgentStatus::Working => format!("{} is working", handle),
+        AgentStatus::Waiting => match reason {
+            Some(reason) => format!("{}: {}", handle, reason),
+            None => format!("{} needs your input", handle),
+        },
+        AgentStatus::Done => format!("{} is done", handle),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn send(message: &str, sound: bool) -> Result<()> {
+    if which::which("terminal-notifier").is_ok() {
+        let mut args = vec!["-title", TITLE, "-message", message, "-group", TITLE];
+        if sound {
+            args.extend(["-sound", "default"]);
+        }
+        Cmd::new("terminal-notifier").args(&args).run()?;
+        return Ok(());
+    }
+
+    use mac_notification_sys::{Notification, Sound, set_application};
+    // Set application to Terminal to use its icon
+    if let Err(e) = set_application("com.apple.Terminal") {
+        tracing::debug!("Failed to set notification application: {:?}", e);
+    }
+    let mut notification = Notification::default();
+    notification.title(TITLE).message(message);
+    if sound {
+        notification.sound(Sound::Default);
+    }
+    if let Err(e) = notification.send() {
+        tracing::debug!("Native notification failed, using osascript: {:?}", e);
+        Cmd::new("osascript")
+            .args(&["-e", &osascript_script(message, sound)])
+            .run()?;
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn send(message: &str, sound: bool) -> Result<()> {
+    if is_wsl() {
+        Cmd::new("powershell.exe")
+            .args(&["-NoProfile", "-Command", &toast_script(message, sound)])
+            .run()?;
+        return Ok(());
+    }
+
+    if which::which("notify-send").is_ok() {
+        let mut args = vec!["--app-name", TITLE];
+        if sound {
+            args.extend(["--hint", "string:sound-name:complete"]);
+        }
+        args.extend([TITLE, message]);
+        Cmd::new("notify-send").args(&args).run()?;
+        return Ok(());
+    }
+
+    let mut notification = notify_rust::Notification::new();
+    notification.summary(TITLE).body(message);
+    if sound {
+        notification.sound_name("complete");
+    }
+    notification.show()?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_wsl() -> bool {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .is_ok_and(|release| release.to_lowercase().contains("microsoft"))
+}
+
+/// AppleScript for `osascript -e`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn osascript_script(message: &str, sound: bool) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut script = format!(
+        "display notification {} with title {}",
+        quote(message),
+        quote(TITLE)
+    );
+    if sound {
+        script.push_str(" sound name \"default\"");
+    }
+    script
+}
+
+/// PowerShell that shows a Windows toast, attributed to PowerShell since
+/// unregistered app IDs are dropped silently.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn toast_script(message: &str, sound: bool) -> String {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let mut script = String::from(
+        "$m = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]; \
+         $xml = $m::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $text = $xml.GetElementsByTagName('text'); ",
+    );
+    script.push_str(&format!(
+        "$text.Item(0).AppendChild($xml.CreateTextNode({})) > $null; \
+         $text.Item(1).AppendChild($xml.CreateTextNode({})) > $null; ",
+        quote(TITLE),
+        quote(message)
+    ));
+    if !sound {
+        script.push_str(
+            "$audio = $xml.CreateElement('audio'); $audio.SetAttribute('silent', 'true'); \
+             $xml.DocumentElement.AppendChild($audio) > $null; ",
+        );
+    }
+    script.push_str(
+        "$m::CreateToastNotifier('{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe')\
+         .Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+    );
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osascript_script_escapes_quotes() {
+        assert_eq!(
+            osascript_script(r#"say "hi" \o/"#, false),
+            r#"display notification "say \"hi\" \\o/" with title "workmux""#
+        );
+        assert!(osascript_script("x", true).ends_with(" sound name \"default\""));
+    }
+
+    #[test]
+    fn toast_script_quotes_message() {
+        let script = toast_script("it's done", true);
+        assert!(script.contains("CreateTextNode('it''s done')"));
+        assert!(!script.contains("silent"));
+        assert!(toast_script("x", false).contains("'silent', 'true'"));
+    }
+
+    #[test]
+    fn status_messages() {
+        assert_eq!(
+            status_message("api", AgentStatus::Waiting, None),
+            "api needs your input"
+        );
+        assert_eq!(
+            status_message(
+                "api",
+                AgentStatus::Waiting,
+                Some("Claude needs your permission to use Bash")
+            ),
+            "api: Claude needs your permission to use Bash"
+        );
+        assert_eq!(
+            status_message("api", AgentStatus::Done, Some("ignored")),
+            "api is done"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Push notifications through ntfy (`notifications.ntfy`).
+//!
+//! Messages are published to a topic on ntfy.sh or a self-hosted server, so
+//! the ntfy app on a phone can alert you away from the desk.
+
+use super::webhook::{WebhookPayload, curl_quote, spawn_curl};
+use crate::config::{NotificationEvent, NtfyConfig};
+
+/// Publish `payload` to the configured topic if it subscribes to the event
+/// (best-effort).
+pub fn send(config: &NtfyConfig, payload: &WebhookPayload) {
+    if !config.wants(payload.event) {
+        return;
+    }
+    if let Err(e) = spawn_curl(&curl_config(config, payload, false)) {
+        tracing::warn!(topic = %config.topic, error = %e, "ntfy publish failed");
+    }
+}
+
+/// Publish `payload` with urgent priority, regardless of `events`
+/// (best-effort).
+pub fn send_urgent(config: &NtfyConfig, payload: &WebhookPayload) {
+    if let Err(e) = spawn_curl(&curl_config(config, payload, true)) {
+        tracing::warn!(topic = %config.topic, error = %e, "ntfy publish failed");
+    }
+}
+
+/// A curl config file (`-K`) that publishes `payload` as an ntfy message.
+fn curl_config(config: &NtfyConfig, payload: &WebhookPayload, urgent: bool) -> String {
+    let title = if payload.project.is_empty() {
+        "workmux".to_string()
+    } else {
+        format!("workmux: {}", payload.project)
+    };
+    let (priority, tag) = match payload.event {
+        _ if urgent => ("urgent", "rotating_light"),
+        NotificationEvent::Waiting => ("high", "speech_balloon"),
+        NotificationEvent::Done => ("default", "white_check_mark"),
+        NotificationEvent::Merged => ("default", "twisted_rightwards_arrows"),
+    };
+
+    let mut lines = vec![
+        format!("url = {}", curl_quote(&config.url())),
+        format!("header = {}", curl_quote(&format!("Title: {}", title))),
+        format!(
+            "header = {}",
+            curl_quote(&format!("Priority: {}", priority))
+        ),
+        format!("header = {}", curl_quote(&format!("Tags: {}", tag))),
+    ];
+    if let Some(token) = &config.token {
+        lines.push(format!(
+            "header = {}",
+            curl_quote(&format!("Authorization: Bearer {}", token))
+        ));
+    }
+    lines.push(format!("data-raw = {}", curl_quote(&payload.message)));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(event: NotificationEvent) -> WebhookPayload {
+        WebhookPayload {
+            event,
+            handle: "api".to_string(),
+            project: "shop".to_string(),
+            branch: None,
+            target: None,
+            message: "api needs your input".to_string(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn curl_config_publishes_to_topic() {
+        let config: NtfyConfig = serde_yaml::from_str("topic: my-agents\n").unwrap();
+        assert_eq!(
+            curl_config(&config, &payload(NotificationEvent::Waiting), false),
+            "url = \"https://ntfy.sh/my-agents\"\n\
+             header = \"Title: workmux: shop\"\n\
+             header = \"Priority: high\"\n\
+             header = \"Tags: speech_balloon\"\n\
+             data-raw = \"api needs your input\"\n"
+        );
+    }
+
+    #[test]
+    fn curl_config_with_server_and_token() {
+        let config: NtfyConfig = serde_yaml::from_str(
+            "topic: agents\nserver: https://ntfy.example.com/\ntoken: tk_secret\n",
+        )
+        .unwrap();
+        let out = curl_config(&config, &payload(NotificationEvent::Done), false);
+        assert!(out.starts_with("url = \"https://ntfy.example.com/agents\"\n"));
+        assert!(out.contains("header = \"Priority: default\"\n"));
+        assert!(out.contains("header = \"Authorization: Bearer tk_secret\"\n"));
+    }
+
+    #[test]
+    fn curl_config_urgent() {
+        let config: NtfyConfig = serde_yaml::from_str("topic: agents\n").unwrap();
+        let out = curl_config(&config, &payload(NotificationEvent::Waiting), true);
+        assert!(out.contains("header = \"Priority: urgent\"\n"));
+        assert!(out.contains("header = \"Tags: rotating_light\"\n"));
+    }
+}
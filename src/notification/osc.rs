@@ -0,0 +1,102 @@
+//! Terminal escape sequences for agent status (`notifications.osc`).
+//!
+//! Lets terminals show status without tmux: the tab title carries the status
+//! icon, OSC 9 raises a native notification, and iTerm2 shows a badge.
+//! Sequences go to `/dev/tty` because agent hooks usually capture stdout.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use base64::Engine;
+
+use crate::config::OscConfig;
+use crate::multiplexer::AgentStatus;
+
+/// Emit the sequences enabled in `config` for a status change. `None` clears.
+/// The notification is only sent when `changed`, so repeating a status
+/// doesn't notify again.
+pub fn signal_status(
+    config: &OscConfig,
+    handle: &str,
+    status: Option<AgentStatus>,
+    icon: &str,
+    reason: Option<&str>,
+    changed: bool,
+) {
+    let mut out = String::new();
+    if config.title() {
+        let title = match status {
+            Some(_) => format!("{} {}", icon, handle),
+            None => handle.to_string(),
+        };
+        out.push_str(&osc(&format!("0;{}", sanitize(&title))));
+    }
+    if config.badge() {
+        let badge = if status.is_some() { icon } else { "" };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(badge);
+        out.push_str(&osc(&format!("1337;SetBadgeFormat={}", encoded)));
+    }
+    if config.notify() && changed {
+        let message = match (status, reason) {
+            (Some(AgentStatus::Waiting), Some(reason)) => Some(format!("{}: {}", handle, reason)),
+            (Some(AgentStatus::Waiting), None) => Some(format!("{} needs your input", handle)),
+            (Some(AgentStatus::Done), _) => Some(format!("{} is done", handle)),
+            _ => None,
+        };
+        if let Some(message) = message {
+            out.push_str(&osc(&format!("9;{}", sanitize(&message))));
+        }
+    }
+    if out.is_empty() {
+        return;
+    }
+
+    let out = if std::env::var_os("TMUX").is_some() {
+        tmux_passthrough(&out)
+    } else {
+        out
+    };
+    let written = OpenOptions::new()
+        .write(true)
+        .open("/dev/tty")
+        .and_then(|mut tty| tty.write_all(out.as_bytes()));
+    if let Err(e) = written {
+        tracing::debug!(error = %e, "osc:failed to write to tty");
+    }
+}
+
+fn osc(body: &str) -> String {
+    format!("\x1b]{}\x07", body)
+}
+
+/// Drop control characters, which would end the sequence early.
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Wrap sequences so tmux forwards them to the outer terminal
+/// (needs `set -g allow-passthrough on`).
+fn tmux_passthrough(sequences: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", sequences.replace('\x1b', "\x1b\x1b"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc_strips_control_characters() {
+        assert_eq!(
+            osc(&format!("0;{}", sanitize("🤖 api\x07\x1b"))),
+            "\x1b]0;🤖 api\x07"
+        );
+    }
+
+    #[test]
+    fn tmux_passthrough_doubles_escapes() {
+        assert_eq!(
+            tmux_passthrough("\x1b]9;hi\x07"),
+            "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Notification sounds (`notifications.sounds`, `notifications.quiet_hours`).
+//!
+//! Each event plays the platform's default sound, nothing, or a custom sound.
+//! Custom sounds are played separately from the notification, so they work
+//! with every backend: a file path is played with `afplay` on macOS and
+//! `paplay`/`pw-play`/`aplay` on Linux and PowerShell on Windows, and a bare
+//! name is a system sound (`/System/Library/Sounds` on macOS, the
+//! freedesktop theme on Linux, `C:\Windows\Media` on Windows).
+
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::config::NotificationsConfig;
+use crate::platform;
+
+/// What a notification is about, for choosing its sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    Waiting,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Sound {
+    /// The notification backend's default sound
+    Default,
+    Mute,
+    /// A system sound name or a file path
+    Custom(String),
+}
+
+/// The sound to play for `event` at `minute_of_day` (local time), if known.
+pub fn resolve(
+    config: &NotificationsConfig,
+    event: SoundEvent,
+    minute_of_day: Option<u16>,
+) -> Sound {
+    if !config.sound() {
+        return Sound::Mute;
+    }
+    if let (Some(range), Some(minute)) = (config.quiet_hours.as_deref(), minute_of_day) {
+        match parse_quiet_hours(range) {
+            Some((start, end)) if in_quiet_hours(start, end, minute) => return Sound::Mute,
+            Some(_) => {}
+            None => tracing::warn!(
+                quiet_hours = range,
+                "invalid notifications.quiet_hours, expected HH:MM-HH:MM"
+            ),
+        }
+    }
+
+    let setting = match event {
+        SoundEvent::Waiting => &config.sounds.waiting,
+        SoundEvent::Done => &config.sounds.done,
+        SoundEvent::Failed => &config.sounds.failed,
+    };
+    match setting.as_deref().map(str::trim) {
+        None | Some("default") => Sound::Default,
+        Some("" | "none" | "off") => Sound::Mute,
+        Some(custom) => Sound::Custom(custom.to_string()),
+    }
+}
+
+/// Parse `HH:MM-HH:MM` into minutes since midnight.
+fn parse_quiet_hours(range: &str) -> Option<(u16, u16)> {
+    let parse = |time: &str| {
+        let (hours, minutes) = time.trim().split_once(':')?;
+        let (hours, minutes): (u16, u16) = (hours.parse().ok()?, minutes.parse().ok()?);
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    };
+    let (start, end) = range.split_once('-')?;
+    Some((parse(start)?, parse(end)?))
+}
+
+/// Whether `minute` falls in `[start, end)`, wrapping past midnight.
+fn in_quiet_hours(start: u16, end: u16, minute: u16) -> bool {
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+/// Play a custom sound in the background (best-effort).
+pub fn play(sound: &str) {
+    let Some((program, args)) = player(sound) else {
+        tracing::debug!(sound, "no player found for notification sound");
+        return;
+    };
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Keep playing if the pane closes (e.g. merge cleanup)
+    let spawned = platform::detach(&mut command).spawn();
+    if let Err(e) = spawned {
+        tracing::debug!(sound, error = %e, "failed to play notification sound");
+    }
+}
+
+fn player(sound: &str) -> Option<(&'static str, Vec<String>)> {
+    let is_file = sound.contains(['/', '\\']);
+    let file = |path: PathBuf| path.to_string_lossy().into_owned();
+
+    if cfg!(target_os = "macos") {
+        let path = if is_file {
+            crate::util::expand_tilde(sound)
+        } else {
+            PathBuf::from(format!("/System/Library/Sounds/{}.aiff", sound))
+        };
+        return Some(("afplay", vec![file(path)]));
+    }
+
+    if cfg!(windows) {
+        let path = if is_file {
+            crate::util::expand_tilde(sound)
+        } else {
+            PathBuf::from(format!("C:\\Windows\\Media\\{}.wav", sound))
+        };
+        let script = format!(
+            "(New-Object Media.SoundPlayer '{}').PlaySync()",
+            file(path).replace('\'', "''")
+        );
+        return Some((
+            "powershell",
+            vec!["-NoProfile".to_string(), "-Command".to_string(), script],
+        ));
+    }
+
+    if !is_file {
+        return which::which("canberra-gtk-play").is_ok().then(|| {
+            (
+                "canberra-gtk-play",
+                vec!["-i".to_string(), sound.to_string()],
+            )
+        });
+    }
+    let path = file(crate::util::expand_tilde(sound));
+    ["paplay", "pw-play", "aplay"]
+        .into_iter()
+        .find(|p| which::which(p).is_ok())
+        .map(|p| (p, vec![path]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> NotificationsConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn resolve_per_event_sounds() {
+        let config = config("sounds:\n  waiting: Ping\n  done: none\n");
+        assert_eq!(
+            resolve(&config, SoundEvent::Waiting, None),
+            Sound::Custom("Ping".to_string())
+        );
+        assert_eq!(resolve(&config, SoundEvent::Done, None), Sound::Mute);
+        assert_eq!(resolve(&config, SoundEvent::Failed, None), Sound::Default);
+    }
+
+    #[test]
+    fn sound_false_mutes_everything() {
+        let config = config("sound: false\nsounds:\n  waiting: Ping\n");
+        assert_eq!(resolve(&config, SoundEvent::Waiting, None), Sound::Mute);
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let config = config("quiet_hours: \"22:00-07:30\"\n");
+        let at = |h: u16, m: u16| resolve(&config, SoundEvent::Done, Some(h * 60 + m));
+        assert_eq!(at(23, 0), Sound::Mute);
+        assert_eq!(at(7, 29), Sound::Mute);
+        assert_eq!(at(7, 30), Sound::Default);
+        assert_eq!(at(12, 0), Sound::Default);
+
+        assert_eq!(parse_quiet_hours("13:00-14:00"), Some((780, 840)));
+        assert!(in_quiet_hours(780, 840, 800));
+        assert!(!in_quiet_hours(780, 840, 840));
+        assert_eq!(parse_quiet_hours("25:00-07:00"), None);
+        assert_eq!(parse_quiet_hours("late"), None);
+    }
+}
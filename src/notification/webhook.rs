@@ -0,0 +1,202 @@
+//! Webhook notifications (`notifications.webhooks`).
+//!
+//! Payloads are POSTed with curl in the background so agent hooks and
+//! `workmux merge` never wait on the network.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{NotificationEvent, WebhookConfig, WebhookFormat};
+use crate::platform;
+use crate::template::create_template_env;
+
+/// Seconds before curl gives up on an endpoint
+const TIMEOUT_SECS: &str = "10";
+
+/// What happened, as exposed to payload templates
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: NotificationEvent,
+    /// Worktree handle
+    pub handle: String,
+    /// Repository name (main worktree directory)
+    pub project: String,
+    /// Branch that was merged (`merged` only)
+    pub branch: Option<String>,
+    /// Branch merged into (`merged` only)
+    pub target: Option<String>,
+    /// Human-readable summary
+    pub message: String,
+    /// Unix time in seconds
+    pub timestamp: u64,
+}
+
+impl WebhookPayload {
+    pub fn new(event: NotificationEvent, handle: &str, message: String) -> Self {
+        let project = crate::git::get_main_worktree_root()
+            .ok()
+            .and_then(|root| root.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Self {
+            event,
+            handle: handle.to_string(),
+            project,
+            branch: None,
+            target: None,
+            message,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// POST `payload` to every webhook subscribed to its event (best-effort).
+pub fn send_all(webhooks: &[WebhookConfig], payload: &WebhookPayload) {
+    for webhook in webhooks.iter().filter(|w| w.wants(payload.event)) {
+        if let Err(e) = send(webhook, payload) {
+            tracing::warn!(url = %webhook.url, error = %e, "webhook failed");
+        }
+    }
+}
+
+pub(super) fn send(webhook: &WebhookConfig, payload: &WebhookPayload) -> Result<()> {
+    let body = render_body(webhook, payload)?;
+    spawn_curl(&curl_config(&webhook.url, &body))
+}
+
+/// Run curl in the background with `config` as its config file (`-K`).
+///
+/// The config is passed on stdin rather than as arguments, since URLs and
+/// tokens are secrets and would show up in `ps`.
+pub(super) fn spawn_curl(config: &str) -> Result<()> {
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "-m", TIMEOUT_SECS, "-K", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Own process group, so closing the pane (e.g. merge cleanup) doesn't
+    // kill the request along with workmux
+    let mut child = platform::detach(&mut command)
+        .spawn()
+        .context("Failed to run curl. Is curl installed?")?;
+    let mut stdin = child.stdin.take().context("Failed to open curl stdin")?;
+    stdin.write_all(config.as_bytes())?;
+    Ok(())
+}
+
+fn render_body(webhook: &WebhookConfig, payload: &WebhookPayload) -> Result<String> {
+    if let Some(template) = &webhook.template {
+        let mut env = create_template_env();
+        env.add_filter("json", json_filter);
+        return env
+            .render_str(template, payload)
+            .context("Failed to render webhook template");
+    }
+    let body = match webhook.format() {
+        WebhookFormat::Slack => serde_json::json!({ "text": payload.message }),
+        WebhookFormat::Discord => serde_json::json!({ "content": payload.message }),
+        WebhookFormat::Json => serde_json::to_value(payload)?,
+    };
+    Ok(body.to_string())
+}
+
+/// Quote a value as a JSON string, for building payloads in templates.
+fn json_filter(value: String) -> String {
+    serde_json::Value::String(value).to_string()
+}
+
+/// A curl config file (`-K`) that POSTs `body` as JSON to `url`.
+fn curl_config(url: &str, body: &str) -> String {
+    format!(
+        "url = {}\nheader = \"Content-Type: application/json\"\ndata-raw = {}\n",
+        curl_quote(url),
+        curl_quote(body)
+    )
+}
+
+/// Quote a value for a curl config file.
+pub(crate) fn curl_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> WebhookPayload {
+        WebhookPayload {
+            event: NotificationEvent::Done,
+            handle: "api".to_string(),
+            project: "shop".to_string(),
+            branch: None,
+            target: None,
+            message: "api is \"done\"".to_string(),
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    fn webhook(url: &str, template: Option<&str>) -> WebhookConfig {
+        WebhookConfig {
+            url: url.to_string(),
+            events: None,
+            format: None,
+            template: template.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn render_body_for_each_format() {
+        let slack = render_body(
+            &webhook("https://hooks.slack.com/services/x", None),
+            &payload(),
+        );
+        assert_eq!(slack.unwrap(), r#"{"text":"api is \"done\""}"#);
+
+        let discord = render_body(
+            &webhook("https://discord.com/api/webhooks/1/abc", None),
+            &payload(),
+        );
+        assert_eq!(discord.unwrap(), r#"{"content":"api is \"done\""}"#);
+
+        let json: serde_json::Value = serde_json::from_str(
+            &render_body(&webhook("https://example.com/hook", None), &payload()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["event"], "done");
+        assert_eq!(json["project"], "shop");
+        assert_eq!(json["timestamp"], 1_700_000_000);
+    }
+
+    #[test]
+    fn render_body_from_template() {
+        let template = r#"{"msg": {{ message | json }}, "who": "{{ handle }}/{{ event }}"}"#;
+        let body = render_body(&webhook("https://example.com", Some(template)), &payload());
+        assert_eq!(
+            body.unwrap(),
+            r#"{"msg": "api is \"done\"", "who": "api/done"}"#
+        );
+    }
+
+    #[test]
+    fn curl_config_quotes_values() {
+        assert_eq!(
+            curl_config("https://x/y?a=1", "{\"text\":\"a\\nb\"}"),
+            "url = \"https://x/y?a=1\"\n\
+             header = \"Content-Type: application/json\"\n\
+             data-raw = \"{\\\"text\\\":\\\"a\\\\nb\\\"}\"\n"
+        );
+    }
+}
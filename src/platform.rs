@@ -0,0 +1,226 @@
+//! Platform-specific operations behind one interface.
+//!
+//! Unix is the primary target. On Windows each function does the closest
+//! native equivalent, and shell scripts run under the `sh` and `bash` that
+//! ship with Git for Windows.
+
+use std::io;
+use std::path::Path;
+use std::process::{Child, Command};
+
+/// Start `command` outside workmux's process group, so it keeps running
+/// when the pane that started it closes (e.g. merge cleanup).
+pub fn detach(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP)
+    }
+}
+
+/// Start `command` in a process group of its own, so `kill_process_tree`
+/// can stop everything it starts.
+pub fn new_process_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+        command.creation_flags(CREATE_NEW_PROCESS_GROUP)
+    }
+}
+
+/// Kill `child` and the processes it started. `child` must have been
+/// spawned with `new_process_group`.
+pub fn kill_process_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // The group ID is the leader's PID
+        unsafe {
+            libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+}
+
+/// Replace the current process with `command`. Only returns on failure.
+///
+/// Windows has no exec, so there the command runs as a child and workmux
+/// exits with its status.
+pub fn exec(command: &mut Command) -> io::Error {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+    #[cfg(windows)]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => e,
+    }
+}
+
+/// Create a symlink at `link` pointing to `target`, which is resolved
+/// relative to the link's directory when it isn't absolute.
+///
+/// Windows needs Developer Mode (or admin rights) to create symlinks.
+pub fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(windows)]
+    {
+        // Windows has separate file and directory symlinks
+        let resolved = match link.parent() {
+            Some(parent) => parent.join(target),
+            None => target.to_path_buf(),
+        };
+        if resolved.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+    }
+}
+
+/// Make a script or binary executable. Windows decides by extension, so
+/// this does nothing there.
+pub fn set_executable(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+    }
+    #[cfg(windows)]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
+/// Current local time as minutes since midnight.
+pub fn local_minute_of_day() -> Option<u16> {
+    #[cfg(unix)]
+    {
+        let now = unsafe { libc::time(std::ptr::null_mut()) };
+        let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+        if unsafe { libc::localtime_r(&now, &mut tm) }.is_null() {
+            return None;
+        }
+        Some((tm.tm_hour * 60 + tm.tm_min) as u16)
+    }
+    // No time zone database without an extra dependency; quiet hours are
+    // skipped rather than applied at the wrong time
+    #[cfg(windows)]
+    {
+        None
+    }
+}
+
+/// POSIX shell for hooks and pane wrappers.
+pub fn sh() -> &'static str {
+    #[cfg(unix)]
+    {
+        "sh"
+    }
+    #[cfg(windows)]
+    {
+        static SH: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        SH.get_or_init(|| git_for_windows_tool("sh").unwrap_or_else(|| "sh".to_string()))
+    }
+}
+
+/// Bash, for hooks and `workmux run` commands.
+pub fn bash() -> &'static str {
+    #[cfg(unix)]
+    {
+        "bash"
+    }
+    #[cfg(windows)]
+    {
+        // Git Bash first: a bare `bash` on PATH is often the WSL launcher,
+        // which runs in a different filesystem and environment
+        static BASH: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        BASH.get_or_init(|| git_for_windows_tool("bash").unwrap_or_else(|| "bash".to_string()))
+    }
+}
+
+/// The user's interactive shell for new panes: `$SHELL`, else bash.
+pub fn default_shell() -> String {
+    if let Ok(shell) = std::env::var("SHELL") {
+        return shell;
+    }
+    #[cfg(unix)]
+    {
+        "/bin/bash".to_string()
+    }
+    #[cfg(windows)]
+    {
+        bash().to_string()
+    }
+}
+
+/// Path to a tool bundled with Git for Windows, found relative to `git.exe`
+/// (`<root>\cmd\git.exe` with tools in `<root>\bin` and `<root>\usr\bin`).
+#[cfg(windows)]
+fn git_for_windows_tool(name: &str) -> Option<String> {
+    let git = which::which("git").ok()?;
+    let root = git.parent()?.parent()?;
+    ["bin", "usr\\bin"]
+        .iter()
+        .map(|dir| root.join(dir).join(format!("{}.exe", name)))
+        .find(|path| path.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_symlink_resolves_from_link_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("shared")).unwrap();
+        std::fs::write(dir.path().join("shared/config"), "x").unwrap();
+        std::fs::create_dir(dir.path().join("wt")).unwrap();
+
+        let link = dir.path().join("wt/shared");
+        symlink(Path::new("../shared"), &link).unwrap();
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(link.join("config")).unwrap(), "x");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn set_executable_sets_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("hello.sh");
+        std::fs::write(&script, "#!/bin/sh\necho hello\n").unwrap();
+        set_executable(&script).unwrap();
+
+        let mode = std::fs::metadata(&script).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
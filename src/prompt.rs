@@ -23,10 +23,32 @@ impl Prompt {
     }
 }
 
+/// Frontmatter of a prompt file. Everything but `foreach` describes how the
+/// worktree is set up, and yields to the matching command-line flag.
 #[derive(Debug, Deserialize, Default)]
 pub struct PromptMetadata {
     #[serde(default)]
     pub foreach: Option<BTreeMap<String, Vec<String>>>,
+
+    /// Agent to run, like `--agent`
+    #[serde(default)]
+    pub agent: Option<String>,
+
+    /// Branch, commit or tag to start from, like `--base`
+    #[serde(default)]
+    pub base: Option<String>,
+
+    /// Named pane layout from config, like `--layout`
+    #[serde(default)]
+    pub layout: Option<String>,
+
+    /// Run the agent in the sandbox (true) or on the host (false)
+    #[serde(default)]
+    pub sandbox: Option<bool>,
+
+    /// Host commands the sandbox may run, narrowing the global `host_commands`
+    #[serde(default)]
+    pub host_commands: Option<Vec<String>>,
 }
 
 #[derive(Debug)]
@@ -177,6 +199,20 @@ mod tests {
         assert_eq!(body, "---\nkey: value\nno closing fence");
     }
 
+    #[test]
+    fn parse_prompt_document_with_setup_frontmatter() {
+        let content = "---\nagent: codex\nbase: release/2.0\nlayout: review\nsandbox: false\nhost_commands: [just]\n---\nFix it";
+        let doc = parse_prompt_document(&Prompt::Inline(content.to_string())).unwrap();
+
+        assert_eq!(doc.body, "Fix it");
+        assert_eq!(doc.meta.agent.as_deref(), Some("codex"));
+        assert_eq!(doc.meta.base.as_deref(), Some("release/2.0"));
+        assert_eq!(doc.meta.layout.as_deref(), Some("review"));
+        assert_eq!(doc.meta.sandbox, Some(false));
+        assert_eq!(doc.meta.host_commands, Some(vec!["just".to_string()]));
+        assert!(doc.meta.foreach.is_none());
+    }
+
     #[test]
     fn parse_prompt_document_with_frontmatter() {
         let content = "---\nforeach:\n  platform: [iOS, Android]\n---\n\nBuild for {{ platform }}";
@@ -248,8 +248,12 @@ pub fn build_docker_run_args(
     let worktree_root_str = worktree_root.to_string_lossy();
     let pane_cwd_str = pane_cwd.to_string_lossy();
 
-    let uid = unsafe { libc::getuid() };
-    let gid = unsafe { libc::getgid() };
+    #[cfg(unix)]
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    // Docker Desktop maps bind-mount ownership itself on Windows; run as the
+    // image's first regular user
+    #[cfg(windows)]
+    let (uid, gid) = (1000, 1000);
 
     let mut args = Vec::new();
 
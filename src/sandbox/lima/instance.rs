@@ -4,8 +4,10 @@ use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
 use tracing::{debug, info};
 
+use crate::cmd::Cmd;
 use crate::config::Config;
 
 /// Lima instance information from `limactl list --json`.
@@ -48,26 +50,39 @@ pub(crate) enum VmState {
 
 /// Check the current state of a Lima VM by name.
 pub(crate) fn check_vm_state(vm_name: &str) -> Result<VmState> {
-    let instances = LimaInstance::list()?;
+    Ok(vm_state_in(&LimaInstance::list()?, vm_name))
+}
 
+/// State of `vm_name` among already listed instances.
+fn vm_state_in(instances: &[LimaInstanceInfo], vm_name: &str) -> VmState {
     match instances.iter().find(|i| i.name == vm_name) {
-        Some(info) if info.is_running() => Ok(VmState::Running),
-        Some(_) => Ok(VmState::Stopped),
-        None => Ok(VmState::NotFound),
+        Some(info) if info.is_running() => VmState::Running,
+        Some(_) => VmState::Stopped,
+        None => VmState::NotFound,
     }
 }
 
+/// Output of `limactl --version`, or None if limactl isn't installed.
+/// Runs once per process.
+pub(crate) fn limactl_version() -> Option<&'static str> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION
+        .get_or_init(|| {
+            Cmd::new("limactl")
+                .arg("--version")
+                .run_and_capture_stdout()
+                .ok()
+        })
+        .as_deref()
+}
+
 /// Lima VM operations.
 pub struct LimaInstance;
 
 impl LimaInstance {
     /// Check if limactl is available on the system.
     pub fn is_lima_available() -> bool {
-        Command::new("limactl")
-            .arg("--version")
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        limactl_version().is_some()
     }
 
     /// List all Lima instances.
@@ -104,6 +119,29 @@ impl LimaInstance {
 
         Ok(())
     }
+
+    /// Delete a Lima VM by name, stopping it first if needed, along with
+    /// workmux's state directory for it.
+    pub fn delete_by_name(name: &str) -> Result<()> {
+        let output = Command::new("limactl")
+            .arg("delete")
+            .arg(name)
+            .arg("--force")
+            .output()
+            .with_context(|| format!("Failed to execute limactl delete for '{}'", name))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to delete Lima VM '{}': {}", name, stderr.trim());
+        }
+
+        let state_dir = super::mounts::lima_state_dir_path(name)?;
+        if state_dir.exists() {
+            std::fs::remove_dir_all(&state_dir)
+                .with_context(|| format!("Failed to remove {}", state_dir.display()))?;
+        }
+        Ok(())
+    }
 }
 
 /// Ensure a Lima VM is running for the given worktree.
@@ -114,6 +152,9 @@ impl LimaInstance {
 ///
 /// Returns the VM name for use by `wrap_for_lima()`.
 pub fn ensure_vm_running(config: &Config, worktree_path: &Path) -> Result<String> {
+    // Start listing VMs now so it overlaps the version check and name lookup
+    let listing = Cmd::new("limactl").args(&["list", "--json"]).spawn();
+
     if !LimaInstance::is_lima_available() {
         bail!(
             "Lima backend is enabled but limactl is not installed.\n\
@@ -126,7 +167,10 @@ pub fn ensure_vm_running(config: &Config, worktree_path: &Path) -> Result<String
     let vm_name = super::instance_name(worktree_path, isolation.clone(), config)?;
 
     debug!(vm_name = %vm_name, "checking Lima VM state");
-    let vm_state = check_vm_state(&vm_name)?;
+    let listed = listing
+        .and_then(|pending| pending.wait())
+        .context("Failed to list Lima instances")?;
+    let vm_state = vm_state_in(&parse_lima_instances(&listed.stdout)?, &vm_name);
 
     match vm_state {
         VmState::Running => {
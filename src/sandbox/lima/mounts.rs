@@ -103,17 +103,7 @@ fn lima_guest_home() -> Option<PathBuf> {
 ///
 /// Returns "guest" for Lima >=2.1.0, "linux" for older versions.
 fn lima_guest_home_suffix() -> &'static str {
-    let version = Command::new("limactl")
-        .arg("--version")
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                String::from_utf8(o.stdout).ok()
-            } else {
-                None
-            }
-        });
+    let version = super::instance::limactl_version();
 
     match version {
         Some(v) => {
@@ -14,6 +14,7 @@ pub(crate) mod toolchain;
 pub use container::DEFAULT_IMAGE_REGISTRY;
 pub use container::DOCKERFILE_BASE;
 pub use container::KNOWN_AGENTS;
+pub(crate) use container::SandboxPaths;
 pub(crate) use container::build_docker_run_args;
 pub use container::build_image;
 pub use container::dockerfile_for_agent;
@@ -25,6 +25,8 @@ use crate::multiplexer::{AgentStatus, Multiplexer};
 pub enum RpcRequest {
     SetStatus {
         status: String,
+        #[serde(default)]
+        reason: Option<String>,
     },
     SetTitle {
         title: String,
@@ -159,7 +161,7 @@ pub fn generate_token() -> String {
 
 /// Constant-time byte comparison to prevent timing side-channel attacks.
 /// Always compares every byte regardless of where the first difference is.
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -336,7 +338,9 @@ fn write_response(writer: &mut impl Write, response: &RpcResponse) -> Result<()>
 fn dispatch_request(request: &RpcRequest, ctx: &RpcContext) -> RpcResponse {
     match request {
         RpcRequest::Heartbeat => RpcResponse::Ok,
-        RpcRequest::SetStatus { status } => handle_set_status(status, ctx),
+        RpcRequest::SetStatus { status, reason } => {
+            handle_set_status(status, reason.as_deref(), ctx)
+        }
         RpcRequest::SetTitle { title } => handle_set_title(title, ctx),
         RpcRequest::SpawnAgent {
             prompt,
@@ -362,7 +366,7 @@ fn dispatch_request(request: &RpcRequest, ctx: &RpcContext) -> RpcResponse {
 
 // ── Handlers ────────────────────────────────────────────────────────────
 
-fn handle_set_status(status: &str, ctx: &RpcContext) -> RpcResponse {
+fn handle_set_status(status: &str, reason: Option<&str>, ctx: &RpcContext) -> RpcResponse {
     // Reuse the same logic as set_window_status command
     let config = match Config::load(None) {
         Ok(c) => c,
@@ -412,11 +416,26 @@ fn handle_set_status(status: &str, ctx: &RpcContext) -> RpcResponse {
         Ok(()) => {
             // Persist agent state to StateStore so the dashboard sees this agent
             if let Some(agent_status) = agent_status {
-                crate::state::persist_agent_update(
+                let previous = crate::state::persist_agent_update(
                     &*ctx.mux,
                     &ctx.pane_id,
                     Some(agent_status),
                     None,
+                    reason.map(str::to_string),
+                );
+                crate::notification::agent_status_changed(
+                    &config.notifications,
+                    &ctx.worktree_path,
+                    previous,
+                    agent_status,
+                    reason,
+                );
+                crate::notification::escalation::schedule(
+                    &config.notifications,
+                    &*ctx.mux,
+                    &ctx.pane_id,
+                    previous,
+                    agent_status,
                 );
             }
             RpcResponse::Ok
@@ -442,6 +461,7 @@ fn handle_set_title(title: &str, ctx: &RpcContext) -> RpcResponse {
                 &ctx.pane_id,
                 None,
                 Some(title.to_string()),
+                None,
             );
             RpcResponse::Ok
         }
@@ -584,6 +604,7 @@ fn handle_merge(
     // compromised guest could plant in the bind-mounted .git/hooks/ directory.
     cmd.args(["--no-verify", "--no-hooks"]);
     disable_git_hooks(&mut cmd);
+    // --approve is never passed: an agent can't approve its own merge
 
     // Run from the worktree directory so config is found
     cmd.current_dir(worktree_path);
@@ -946,6 +967,7 @@ mod tests {
     fn test_request_serialization_set_status() {
         let req = RpcRequest::SetStatus {
             status: "working".to_string(),
+            reason: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"type\":\"SetStatus\""));
@@ -994,6 +1016,7 @@ mod tests {
         let cases = vec![
             r#"{"type":"Heartbeat"}"#,
             r#"{"type":"SetStatus","status":"working"}"#,
+            r#"{"type":"SetStatus","status":"waiting","reason":"Claude needs your permission to use Bash"}"#,
             r#"{"type":"SetTitle","title":"my agent"}"#,
             r#"{"type":"SpawnAgent","prompt":"do stuff","branch_name":null,"background":null}"#,
             r#"{"type":"Exec","command":"cargo","args":["build","--release"]}"#,
@@ -5,9 +5,10 @@
 
 use anyhow::{Context, Result};
 use std::fs;
-use std::os::unix::fs::symlink;
 use std::path::{Path, PathBuf};
 
+use crate::platform;
+
 /// Commands that are always available as shims, regardless of
 /// user `host_commands` config. Includes both host-exec commands (e.g., `afplay`)
 /// and clipboard shims (`wl-paste`, `xclip`).
@@ -136,12 +137,7 @@ pub fn create_shim_directory(state_dir: &Path, commands: &[String]) -> Result<Pa
     )
     .context("Failed to write shim dispatcher")?;
 
-    // Make executable
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        fs::set_permissions(&dispatcher, fs::Permissions::from_mode(0o755))?;
-    }
+    platform::set_executable(&dispatcher)?;
 
     // Create shims for each command
     for cmd in commands {
@@ -160,15 +156,9 @@ pub fn create_shim_directory(state_dir: &Path, commands: &[String]) -> Result<Pa
             // Custom clipboard shim: write script file
             fs::write(&tmp, script)
                 .with_context(|| format!("Failed to write clipboard shim for: {}", cmd))?;
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                fs::set_permissions(&tmp, fs::Permissions::from_mode(0o755))?;
-            }
+            platform::set_executable(&tmp)?;
         } else {
-            // Standard host-exec symlink
-            symlink("_shim", &tmp)
-                .with_context(|| format!("Failed to create temp shim symlink for: {}", cmd))?;
+            write_host_exec_shim(cmd, &tmp)?;
         }
 
         fs::rename(&tmp, &link).with_context(|| format!("Failed to rename shim for: {}", cmd))?;
@@ -177,6 +167,25 @@ pub fn create_shim_directory(state_dir: &Path, commands: &[String]) -> Result<Pa
     Ok(shim_bin)
 }
 
+/// Standard host-exec shim: a symlink to the dispatcher, which takes the
+/// command name from `$0`. Windows hosts get a one-line script instead,
+/// since symlinks there need Developer Mode and don't survive the mount
+/// into a Linux guest.
+fn write_host_exec_shim(cmd: &str, path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    platform::symlink(Path::new("_shim"), path)
+        .with_context(|| format!("Failed to create temp shim symlink for: {}", cmd))?;
+
+    #[cfg(windows)]
+    fs::write(
+        path,
+        format!("#!/bin/sh\nexec workmux host-exec {} \"$@\"\n", cmd),
+    )
+    .with_context(|| format!("Failed to write shim for: {}", cmd))?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,18 +1,26 @@
+# Candidates are computed by workmux at completion time: `workmux _complete <kind>`
+
 # Dynamic worktree handle completion (directory names)
 # Used for open/remove/merge/path/close - repo-scoped lifecycle commands
 _workmux_handles() {
-    workmux _complete-handles 2>/dev/null
+    workmux _complete handles 2>/dev/null
+}
+
+# Dynamic worktree branch completion
+# Used for list filters
+_workmux_branches() {
+    workmux _complete branches 2>/dev/null
 }
 
 # Dynamic agent target completion (local handles + cross-project agents)
 # Used for send/capture/status/wait/run - agent communication commands
 _workmux_agent_targets() {
-    workmux _complete-agent-targets 2>/dev/null
+    workmux _complete agent-targets 2>/dev/null
 }
 
-# Dynamic git branch completion for add command
+# Dynamic git branch completion for add/set-base and merge --into
 _workmux_git_branches() {
-    workmux _complete-git-branches 2>/dev/null
+    workmux _complete git-branches 2>/dev/null
 }
 
 # Wrapper that adds dynamic completion
@@ -35,9 +43,9 @@ _workmux_dynamic() {
         local cmd="${words[1]}"
         case "$cmd" in
             merge)
-                # Handle --into flag (takes worktree handle)
+                # Handle --into flag (takes a git branch)
                 if [[ "$prev" == "--into" ]]; then
-                    COMPREPLY=($(compgen -W "$(_workmux_handles)" -- "$cur"))
+                    COMPREPLY=($(compgen -W "$(_workmux_git_branches)" -- "$cur"))
                     return
                 fi
                 # Positional arg: handles
@@ -53,6 +61,20 @@ _workmux_dynamic() {
                     return
                 fi
                 ;;
+            list|ls)
+                # Positional arg: worktree branches
+                if [[ "$cur" != -* ]]; then
+                    COMPREPLY=($(compgen -W "$(_workmux_branches)" -- "$cur"))
+                    return
+                fi
+                ;;
+            set-base)
+                # Positional arg: git branches
+                if [[ "$cur" != -* ]]; then
+                    COMPREPLY=($(compgen -W "$(_workmux_git_branches)" -- "$cur"))
+                    return
+                fi
+                ;;
             send|capture|status|wait|run)
                 # Positional arg: agent targets (local + cross-project)
                 if [[ "$cur" != -* ]]; then
@@ -60,7 +82,7 @@ _workmux_dynamic() {
                     return
                 fi
                 ;;
-            add)
+            add|new)
                 # Handle flags that take specific argument types
                 case "$prev" in
                     --base|-b)
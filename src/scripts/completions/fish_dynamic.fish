@@ -1,23 +1,35 @@
+# Candidates are computed by workmux at completion time: `workmux _complete <kind>`
+
 # Dynamic worktree handle completion (directory names)
 # Used for open/remove/merge/path/close - repo-scoped lifecycle commands
 function __workmux_handles
-    workmux _complete-handles 2>/dev/null
+    workmux _complete handles 2>/dev/null
+end
+
+# Dynamic worktree branch completion
+# Used for list filters
+function __workmux_branches
+    workmux _complete branches 2>/dev/null
 end
 
 # Dynamic agent target completion (local handles + cross-project agents)
 # Used for send/capture/status/wait/run - agent communication commands
 function __workmux_agent_targets
-    workmux _complete-agent-targets 2>/dev/null
+    workmux _complete agent-targets 2>/dev/null
 end
 
-# Dynamic git branch completion for add command
+# Dynamic git branch completion for add/set-base and merge --into
 function __workmux_git_branches
-    workmux _complete-git-branches 2>/dev/null
+    workmux _complete git-branches 2>/dev/null
 end
 
 # Lifecycle commands: local handles only
 complete -c workmux -n '__fish_seen_subcommand_from open remove rm rename path merge close' -f -a '(__workmux_handles)'
 # Agent commands: local + cross-project targets
 complete -c workmux -n '__fish_seen_subcommand_from send capture status wait run' -f -a '(__workmux_agent_targets)'
-# Add command: git branches
-complete -c workmux -n '__fish_seen_subcommand_from add' -f -a '(__workmux_git_branches)'
+# Add and set-base: git branches
+complete -c workmux -n '__fish_seen_subcommand_from add new set-base' -f -a '(__workmux_git_branches)'
+# Merge target branch
+complete -c workmux -n '__fish_seen_subcommand_from merge' -l into -f -r -a '(__workmux_git_branches)'
+# List filters: worktree branches
+complete -c workmux -n '__fish_seen_subcommand_from list ls' -f -a '(__workmux_branches)'
@@ -0,0 +1,194 @@
+//! Secrets injected as environment variables into agent panes and sandbox
+//! guests at launch, read from a password manager instead of a `.env` file
+//! copied into every worktree.
+//!
+//! `secrets` in the global config maps variable names to references:
+//!
+//! - `keychain:<service>`: a macOS Keychain generic password
+//! - `pass:<path>`: the first line of `pass show <path>`
+//! - `op://<vault>/<item>/<field>`: read with the 1Password CLI
+//! - `env-file:<path>[#KEY]`: a dotenv file (KEY defaults to the variable)
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::cmd::Cmd;
+use crate::shell::shell_escape;
+use crate::util::expand_tilde;
+
+/// Where a secret is read from.
+#[derive(Debug, PartialEq)]
+pub enum SecretSource {
+    Keychain(String),
+    Pass(String),
+    OnePassword(String),
+    EnvFile { path: PathBuf, key: Option<String> },
+}
+
+impl SecretSource {
+    pub fn parse(reference: &str) -> Result<Self> {
+        if reference.starts_with("op://") {
+            return Ok(Self::OnePassword(reference.to_string()));
+        }
+        let (scheme, rest) = reference
+            .split_once(':')
+            .filter(|(_, rest)| !rest.is_empty())
+            .ok_or_else(|| invalid_reference(reference))?;
+        match scheme {
+            "keychain" => Ok(Self::Keychain(rest.to_string())),
+            "pass" => Ok(Self::Pass(rest.to_string())),
+            "env-file" => {
+                let (path, key) = match rest.rsplit_once('#') {
+                    Some((path, key)) => (path, Some(key.to_string())),
+                    None => (rest, None),
+                };
+                Ok(Self::EnvFile {
+                    path: expand_tilde(path),
+                    key,
+                })
+            }
+            _ => Err(invalid_reference(reference)),
+        }
+    }
+
+    /// Read the secret for environment variable `var`.
+    pub fn read(&self, var: &str) -> Result<String> {
+        match self {
+            Self::Keychain(service) => Cmd::new("security")
+                .args(&["find-generic-password", "-s", service.as_str(), "-w"])
+                .run_and_capture_stdout(),
+            Self::Pass(path) => {
+                let output = Cmd::new("pass").args(&["show", path.as_str()]).run()?;
+                let content = String::from_utf8(output.stdout)?;
+                Ok(content.lines().next().unwrap_or_default().to_string())
+            }
+            Self::OnePassword(reference) => Cmd::new("op")
+                .args(&["read", reference.as_str()])
+                .run_and_capture_stdout(),
+            Self::EnvFile { path, key } => read_env_file(path, key.as_deref().unwrap_or(var)),
+        }
+    }
+}
+
+fn invalid_reference(reference: &str) -> anyhow::Error {
+    anyhow!(
+        "Unknown secret reference '{}': expected keychain:<service>, pass:<path>, \
+         op://<vault>/<item>/<field> or env-file:<path>[#KEY]",
+        reference
+    )
+}
+
+/// Value of `key` in a dotenv file (`KEY=value`, optionally exported or
+/// quoted).
+fn read_env_file(path: &Path, key: &str) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .lines()
+        .find_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key).then(|| unquote(value.trim()).to_string())
+        })
+        .ok_or_else(|| anyhow!("{} not found in {}", key, path.display()))
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Read every configured secret. One that can't be read is skipped with a
+/// warning, so the agent still starts.
+pub fn resolve_env(secrets: &BTreeMap<String, String>) -> Vec<(String, String)> {
+    secrets
+        .iter()
+        .filter_map(|(var, reference)| {
+            match SecretSource::parse(reference).and_then(|source| source.read(var)) {
+                Ok(value) => Some((var.clone(), value)),
+                Err(e) => {
+                    tracing::warn!(var = %var, error = %e, "secrets:read failed");
+                    eprintln!("workmux: skipping secret {}: {:#}", var, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Wrap an agent pane's command so it starts with the secrets in its
+/// environment, without the values passing through the pane's shell.
+pub fn wrap_command(command: &str) -> String {
+    // The leading space that keeps the command out of shell history goes on
+    // the wrapper instead
+    let command = command.strip_prefix(' ').unwrap_or(command);
+    format!(" workmux _with-secrets -- '{}'", shell_escape(command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_references() {
+        assert_eq!(
+            SecretSource::parse("keychain:github-token").unwrap(),
+            SecretSource::Keychain("github-token".to_string())
+        );
+        assert_eq!(
+            SecretSource::parse("pass:api/openai").unwrap(),
+            SecretSource::Pass("api/openai".to_string())
+        );
+        assert_eq!(
+            SecretSource::parse("op://Private/Anthropic/credential").unwrap(),
+            SecretSource::OnePassword("op://Private/Anthropic/credential".to_string())
+        );
+        assert_eq!(
+            SecretSource::parse("env-file:/srv/app/.env#DB_URL").unwrap(),
+            SecretSource::EnvFile {
+                path: PathBuf::from("/srv/app/.env"),
+                key: Some("DB_URL".to_string()),
+            }
+        );
+        assert!(SecretSource::parse("vault:secret/data/x").is_err());
+        assert!(SecretSource::parse("pass:").is_err());
+        assert!(SecretSource::parse("plain-value").is_err());
+    }
+
+    #[test]
+    fn reads_env_file_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "# comment\nexport API_KEY=\"abc 123\"\nDB_URL=postgres://x\nTOKEN='t0k'\n",
+        )
+        .unwrap();
+        assert_eq!(read_env_file(&path, "API_KEY").unwrap(), "abc 123");
+        assert_eq!(read_env_file(&path, "DB_URL").unwrap(), "postgres://x");
+        assert_eq!(read_env_file(&path, "TOKEN").unwrap(), "t0k");
+        assert!(read_env_file(&path, "MISSING").is_err());
+    }
+
+    #[test]
+    fn wraps_command_for_the_pane() {
+        assert_eq!(
+            wrap_command(" claude \"$(cat .prompt)\""),
+            " workmux _with-secrets -- 'claude \"$(cat .prompt)\"'"
+        );
+        assert_eq!(
+            wrap_command("echo 'hi'"),
+            " workmux _with-secrets -- 'echo '\\''hi'\\'''"
+        );
+    }
+}
@@ -26,6 +26,16 @@ pub fn shell_quote(s: &str) -> String {
     }
 }
 
+/// Whether `name` can be used as an environment variable name in a POSIX
+/// shell: a letter or underscore, then letters, digits and underscores.
+pub fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +74,19 @@ pub fn needs_install(agent: Agent) -> bool {
         .any(|skill| !base_dir.join(skill.name).join("SKILL.md").exists())
 }
 
+/// Directories of the bundled skills installed for the given agent.
+pub fn installed_skill_dirs(agent: Agent) -> Vec<PathBuf> {
+    let Some(base_dir) = skills_dir(agent) else {
+        return Vec::new();
+    };
+
+    BUNDLED_SKILLS
+        .iter()
+        .map(|skill| base_dir.join(skill.name))
+        .filter(|dir| dir.join("SKILL.md").exists())
+        .collect()
+}
+
 enum InstallOutcome {
     Installed,
     AlreadyUpToDate,
@@ -0,0 +1,378 @@
+//! Per-worktree activity log for `workmux report`: agent status transitions,
+//! window focus and prompts, one JSON Lines file per day under
+//! `$XDG_STATE_HOME/workmux/activity/`.
+//!
+//! Active time is derived from it when the report is built: a worktree is
+//! active while its agent is working and while one of its windows has focus.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::jsonl;
+use super::store::get_state_dir;
+use crate::cmd::Cmd;
+use crate::multiplexer::AgentStatus;
+
+/// Days of activity kept
+const RETENTION_DAYS: u64 = 90;
+
+/// Longest an agent counts as working without another status update, for
+/// agents that died mid-task
+const MAX_WORKING_SECS: u64 = 2 * 60 * 60;
+
+/// How long focusing a window counts as active when nothing else happens
+const MAX_FOCUS_SECS: u64 = 15 * 60;
+
+/// Characters of a prompt kept in the log
+const MAX_PROMPT_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Working,
+    Waiting,
+    Done,
+    Focus,
+    /// A prompt given with `add` or `send`
+    Prompt,
+}
+
+impl From<AgentStatus> for ActivityKind {
+    fn from(status: AgentStatus) -> Self {
+        match status {
+            AgentStatus::Working => ActivityKind::Working,
+            AgentStatus::Waiting => ActivityKind::Waiting,
+            AgentStatus::Done => ActivityKind::Done,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    /// Unix time in seconds
+    pub ts: u64,
+    /// Repository name (main worktree directory)
+    pub project: String,
+    /// Branch checked out in the worktree, or its directory name when detached
+    pub branch: String,
+    pub kind: ActivityKind,
+    /// First line of the prompt (`prompt` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+fn activity_dir() -> Result<PathBuf> {
+    Ok(get_state_dir()?.join("activity"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Record `kind` for the worktree containing `workdir`. Best-effort: paths
+/// outside a git worktree and write failures are skipped.
+pub fn record(workdir: &Path, kind: ActivityKind) {
+    if let Err(e) = try_record(workdir, kind, None) {
+        debug!(path = %workdir.display(), error = %e, "activity:record failed");
+    }
+}
+
+/// Record a prompt sent to the agent in the worktree containing `workdir`.
+/// Only its first line is kept, with secrets redacted.
+pub fn record_prompt(workdir: &Path, prompt: &str) {
+    let summary = prompt_summary(prompt);
+    if let Err(e) = try_record(workdir, ActivityKind::Prompt, Some(summary)) {
+        debug!(path = %workdir.display(), error = %e, "activity:record failed");
+    }
+}
+
+fn prompt_summary(prompt: &str) -> String {
+    let line = prompt
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    let line = crate::redact::redact(line);
+    if line.chars().count() > MAX_PROMPT_CHARS {
+        let cut: String = line.chars().take(MAX_PROMPT_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.into_owned()
+    }
+}
+
+fn try_record(workdir: &Path, kind: ActivityKind, prompt: Option<String>) -> Result<()> {
+    let root = crate::git::get_repo_root_for(workdir)?;
+    let common_dir = Cmd::new("git")
+        .workdir(&root)
+        .args(&["rev-parse", "--path-format=absolute", "--git-common-dir"])
+        .run_and_capture_stdout()?;
+    let branch = crate::git::get_current_branch_in(&root)
+        .ok()
+        .filter(|b| !b.is_empty() && b != "HEAD")
+        .unwrap_or_else(|| dir_name(&root));
+    let event = ActivityEvent {
+        ts: now_secs(),
+        project: project_name(Path::new(common_dir.trim())),
+        branch,
+        kind,
+        prompt,
+    };
+
+    let dir = activity_dir()?;
+    fs::create_dir_all(&dir)?;
+    let day = event.ts / 86_400;
+    let path = dir.join(format!("{}.jsonl", day));
+    let is_new = !path.exists();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(format!("{}\n", serde_json::to_string(&event)?).as_bytes())?;
+    if is_new {
+        prune(&dir, day);
+    }
+    Ok(())
+}
+
+/// Repository name from its git common dir (`<repo>/.git`, `<repo>/.bare`
+/// or a bare `<repo>.git`).
+fn project_name(common_dir: &Path) -> String {
+    match common_dir.file_name().and_then(|n| n.to_str()) {
+        Some(".git" | ".bare") => common_dir.parent().map(dir_name).unwrap_or_default(),
+        Some(name) => name.strip_suffix(".git").unwrap_or(name).to_string(),
+        None => String::new(),
+    }
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Delete day files past the retention window.
+fn prune(dir: &Path, today: u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let day = name
+            .to_str()
+            .and_then(|n| n.strip_suffix(".jsonl"))
+            .and_then(|d| d.parse::<u64>().ok());
+        if day.is_some_and(|day| day + RETENTION_DAYS < today) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Events at or after `since` (Unix seconds), oldest first. Events from just
+/// before `since` are included so time running into the period counts.
+pub fn load_since(since: u64) -> Result<Vec<ActivityEvent>> {
+    let dir = activity_dir()?;
+    let lookback = since.saturating_sub(MAX_WORKING_SECS);
+    let mut events = Vec::new();
+    for day in lookback / 86_400..=now_secs() / 86_400 {
+        events.extend(jsonl::read::<ActivityEvent>(
+            &dir.join(format!("{}.jsonl", day)),
+        )?);
+    }
+    events.retain(|event| event.ts >= lookback);
+    events.sort_by_key(|event| event.ts);
+    Ok(events)
+}
+
+/// Time spent on one branch in the report period
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct BranchTime {
+    pub project: String,
+    pub branch: String,
+    /// Seconds the agent was working or a window was focused, overlaps
+    /// counted once
+    pub active_secs: u64,
+    /// Seconds the agent was working
+    pub agent_secs: u64,
+    /// Seconds one of the worktree's windows had focus
+    pub focus_secs: u64,
+}
+
+/// Active time per branch between `since` and `until`, from `events` sorted
+/// oldest first. Sorted by project, then most active first.
+pub fn summarize(events: &[ActivityEvent], since: u64, until: u64) -> Vec<BranchTime> {
+    type Key = (String, String);
+    let mut agent: BTreeMap<Key, Vec<(u64, u64)>> = BTreeMap::new();
+    let mut focus: BTreeMap<Key, Vec<(u64, u64)>> = BTreeMap::new();
+    let mut working_since: BTreeMap<Key, u64> = BTreeMap::new();
+
+    for (i, event) in events.iter().enumerate() {
+        let key = (event.project.clone(), event.branch.clone());
+        match event.kind {
+            ActivityKind::Focus => {
+                // Focus lasts until another window gets it
+                let end = events[i + 1..]
+                    .iter()
+                    .find(|e| e.kind == ActivityKind::Focus)
+                    .map_or(until, |e| e.ts)
+                    .min(event.ts + MAX_FOCUS_SECS);
+                focus.entry(key).or_default().push((event.ts, end));
+            }
+            ActivityKind::Working => {
+                working_since.entry(key).or_insert(event.ts);
+            }
+            ActivityKind::Waiting | ActivityKind::Done => {
+                if let Some(start) = working_since.remove(&key) {
+                    let end = event.ts.min(start + MAX_WORKING_SECS);
+                    agent.entry(key).or_default().push((start, end));
+                }
+            }
+            ActivityKind::Prompt => {}
+        }
+    }
+    for (key, start) in working_since {
+        let end = until.min(start + MAX_WORKING_SECS);
+        agent.entry(key).or_default().push((start, end));
+    }
+
+    let mut keys: Vec<&Key> = agent.keys().chain(focus.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    let mut times: Vec<BranchTime> = keys
+        .into_iter()
+        .map(|key| {
+            let agent = agent.get(key).map(Vec::as_slice).unwrap_or_default();
+            let focus = focus.get(key).map(Vec::as_slice).unwrap_or_default();
+            let both: Vec<(u64, u64)> = agent.iter().chain(focus).copied().collect();
+            BranchTime {
+                project: key.0.clone(),
+                branch: key.1.clone(),
+                active_secs: covered_secs(&both, since, until),
+                agent_secs: covered_secs(agent, since, until),
+                focus_secs: covered_secs(focus, since, until),
+            }
+        })
+        .filter(|time| time.active_secs > 0)
+        .collect();
+    times.sort_by(|a, b| {
+        a.project
+            .cmp(&b.project)
+            .then(b.active_secs.cmp(&a.active_secs))
+    });
+    times
+}
+
+/// Seconds covered by the union of `intervals`, clipped to `since..until`.
+fn covered_secs(intervals: &[(u64, u64)], since: u64, until: u64) -> u64 {
+    let mut clipped: Vec<(u64, u64)> = intervals
+        .iter()
+        .map(|&(start, end)| (start.max(since), end.min(until)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    clipped.sort();
+
+    let mut total = 0;
+    let mut current: Option<(u64, u64)> = None;
+    for (start, end) in clipped {
+        current = match current {
+            Some((cur_start, cur_end)) if start <= cur_end => Some((cur_start, cur_end.max(end))),
+            Some((cur_start, cur_end)) => {
+                total += cur_end - cur_start;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    total + current.map_or(0, |(start, end)| end - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(ts: u64, branch: &str, kind: ActivityKind) -> ActivityEvent {
+        ActivityEvent {
+            ts,
+            project: "shop".to_string(),
+            branch: branch.to_string(),
+            kind,
+            prompt: None,
+        }
+    }
+
+    #[test]
+    fn prompts_keep_their_first_line() {
+        assert_eq!(
+            prompt_summary("\n  Fix the login redirect\nDetails follow"),
+            "Fix the login redirect"
+        );
+        let long = prompt_summary(&"word ".repeat(50));
+        assert_eq!(long.chars().count(), MAX_PROMPT_CHARS);
+        assert!(long.ends_with("word…"));
+    }
+
+    #[test]
+    fn project_names_from_common_dir() {
+        assert_eq!(project_name(Path::new("/src/shop/.git")), "shop");
+        assert_eq!(project_name(Path::new("/src/shop/.bare")), "shop");
+        assert_eq!(project_name(Path::new("/srv/git/shop.git")), "shop");
+    }
+
+    #[test]
+    fn overlapping_intervals_count_once() {
+        assert_eq!(covered_secs(&[(0, 10), (5, 20), (30, 40)], 0, 100), 30);
+        assert_eq!(covered_secs(&[(0, 10), (5, 20)], 8, 15), 7);
+        assert_eq!(covered_secs(&[], 0, 100), 0);
+    }
+
+    #[test]
+    fn agent_and_focus_time_per_branch() {
+        use ActivityKind::*;
+        let events = [
+            event(1000, "auth", Working),
+            event(1100, "auth", Focus),
+            event(1300, "api", Focus),
+            event(1600, "auth", Waiting),
+            event(1700, "api", Working),
+            event(2000, "api", Done),
+        ];
+        let times = summarize(&events, 0, 5000);
+        assert_eq!(
+            times,
+            vec![
+                BranchTime {
+                    project: "shop".to_string(),
+                    branch: "api".to_string(),
+                    // Focus runs its 15 minutes, covering the agent's work
+                    active_secs: 900,
+                    agent_secs: 300,
+                    focus_secs: 900,
+                },
+                BranchTime {
+                    project: "shop".to_string(),
+                    branch: "auth".to_string(),
+                    active_secs: 600,
+                    agent_secs: 600,
+                    focus_secs: 200,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn working_without_an_update_is_capped() {
+        let events = [event(0, "auth", ActivityKind::Working)];
+        let times = summarize(&events, 0, 100_000);
+        assert_eq!(times[0].agent_secs, MAX_WORKING_SECS);
+    }
+}
@@ -0,0 +1,70 @@
+//! Append-only JSON Lines files with a size cap (run and event history).
+//!
+//! Appends and trims take a lock file next to the history, so concurrent
+//! workmux processes don't drop each other's records.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::lock::StateLock;
+
+/// Read all records, oldest first. A missing file is empty.
+pub fn read<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    // Skip lines that fail to parse (e.g. a write cut short by a crash)
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append a record, keeping the newest `max`.
+pub fn append<T: Serialize + DeserializeOwned>(path: &Path, record: &T, max: usize) -> Result<()> {
+    let line = format!("{}\n", serde_json::to_string(record)?);
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let _lock = StateLock::acquire_in(dir, &name)?;
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))?;
+
+    let records: Vec<serde_json::Value> = read(path)?;
+    if records.len() > max {
+        let mut content = String::new();
+        for record in &records[records.len() - max..] {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+        let tmp_path = path.with_extension("jsonl.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_keeps_newest_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        for id in ["a1", "a2", "a3"] {
+            append(&path, &id.to_string(), 2).unwrap();
+        }
+        let ids: Vec<String> = read(&path).unwrap();
+        assert_eq!(ids, vec!["a2", "a3"]);
+    }
+}
@@ -0,0 +1,69 @@
+//! Advisory file locks in the state directory, for check-then-act sequences
+//! that concurrent workmux processes would otherwise race on.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use super::store::get_state_dir;
+
+/// RAII guard that holds an exclusive advisory lock on `<name>.lock` in the
+/// state directory.
+pub struct StateLock {
+    /// The lock is released when the file is closed
+    _file: File,
+}
+
+impl StateLock {
+    /// Acquire an exclusive lock on `<state dir>/<name>.lock`, blocking until
+    /// available.
+    pub fn acquire(name: &str) -> Result<Self> {
+        Self::acquire_in(&get_state_dir()?, name)
+    }
+
+    /// Acquire an exclusive lock on `<dir>/<name>.lock`, blocking until
+    /// available.
+    pub fn acquire_in(dir: &Path, name: &str) -> Result<Self> {
+        let (file, lock_path) = open_lock_file(dir, name)?;
+        debug!(path = %lock_path.display(), "state_lock:acquiring");
+
+        file.lock()
+            .with_context(|| format!("Failed to acquire lock: {}", lock_path.display()))?;
+
+        debug!(path = %lock_path.display(), "state_lock:acquired");
+        Ok(Self { _file: file })
+    }
+
+    /// Acquire an exclusive lock on `<state dir>/<name>.lock` if no other
+    /// process holds it. Returns `None` instead of waiting.
+    pub fn try_acquire(name: &str) -> Result<Option<Self>> {
+        let (file, lock_path) = open_lock_file(&get_state_dir()?, name)?;
+        match file.try_lock() {
+            Ok(()) => {
+                debug!(path = %lock_path.display(), "state_lock:acquired");
+                Ok(Some(Self { _file: file }))
+            }
+            Err(TryLockError::WouldBlock) => {
+                debug!(path = %lock_path.display(), "state_lock:busy");
+                Ok(None)
+            }
+            Err(TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Failed to acquire lock: {}", lock_path.display()))
+            }
+        }
+    }
+}
+
+fn open_lock_file(dir: &Path, name: &str) -> Result<(File, PathBuf)> {
+    fs::create_dir_all(dir).context("Failed to create state directory")?;
+    let lock_path = dir.join(format!("{}.lock", name));
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+    Ok((file, lock_path))
+}
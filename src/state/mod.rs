@@ -3,10 +3,15 @@
 //! This module provides persistent state storage that works across all
 //! terminal multiplexer backends (tmux, WezTerm, Zellij).
 
+pub mod activity;
+pub mod jsonl;
+pub mod lock;
 pub mod run;
+pub mod sessions;
 pub mod store;
 mod types;
 
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use tracing::warn;
@@ -14,7 +19,10 @@ use tracing::warn;
 use crate::multiplexer::{AgentStatus, Multiplexer};
 
 pub use store::StateStore;
-pub use types::{AgentState, LastDoneCycleState, PaneKey, RuntimeState};
+pub use types::{
+    AgentState, LastDoneCycleState, PaneKey, RuntimeState, SavedPane, StatusChange, StatusTimeline,
+    WindowLayout,
+};
 
 /// Persist an agent state update to the StateStore.
 ///
@@ -22,14 +30,18 @@ pub use types::{AgentState, LastDoneCycleState, PaneKey, RuntimeState};
 /// - If `status` is Some, updates the agent's status. If None, preserves existing.
 /// - If `title_override` is Some, uses it. If None, preserves existing stored title,
 ///   falling back to the live pane title.
+/// - `waiting_reason` replaces the stored reason whenever `status` is Some, and is
+///   only kept while the agent is waiting.
 ///
 /// Logs warnings on failure without propagating errors (best-effort persistence).
+/// Returns the status stored before this update, so callers can detect transitions.
 pub fn persist_agent_update(
     mux: &dyn Multiplexer,
     pane_id: &str,
     status: Option<AgentStatus>,
     title_override: Option<String>,
-) {
+    waiting_reason: Option<String>,
+) -> Option<AgentStatus> {
     let pane_key = PaneKey {
         backend: mux.name().to_string(),
         instance: mux.instance_id(),
@@ -40,11 +52,11 @@ pub fn persist_agent_update(
         Ok(Some(info)) => info,
         Ok(None) => {
             warn!(%pane_id, "pane not found, skipping state persist");
-            return;
+            return None;
         }
         Err(e) => {
             warn!(error = %e, "failed to get live pane info, skipping state persist");
-            return;
+            return None;
         }
     };
 
@@ -59,15 +71,24 @@ pub fn persist_agent_update(
         .and_then(|store| store.get_agent(&pane_key).ok().flatten());
 
     // Resolve status: explicit update wins, otherwise preserve existing
-    let final_status = status.or(existing.as_ref().and_then(|e| e.status));
+    let previous_status = existing.as_ref().and_then(|e| e.status);
+    let final_status = status.or(previous_status);
 
     // Preserve existing status_ts if status hasn't changed (avoids resetting timer)
-    let status_ts = if final_status == existing.as_ref().and_then(|e| e.status) {
+    let status_ts = if final_status == previous_status {
         existing.as_ref().and_then(|e| e.status_ts).unwrap_or(now)
     } else {
         now
     };
 
+    // Resolve waiting reason: a status update replaces it, a title update keeps it
+    let waiting_reason = if status.is_some() {
+        waiting_reason
+    } else {
+        existing.as_ref().and_then(|e| e.waiting_reason.clone())
+    }
+    .filter(|_| final_status == Some(AgentStatus::Waiting));
+
     // Resolve title: explicit override wins, then existing stored title, then live
     let pane_title = title_override
         .or(existing.and_then(|e| e.pane_title))
@@ -82,6 +103,7 @@ pub fn persist_agent_update(
         status: final_status,
         status_ts: Some(status_ts),
         pane_title,
+        waiting_reason,
         pane_pid: live_info.pid.unwrap_or(0),
         command: live_info.current_command.unwrap_or_default(),
         updated_ts: now,
@@ -90,9 +112,62 @@ pub fn persist_agent_update(
         boot_id,
     };
 
-    if let Ok(store) = StateStore::new()
+    let store = StateStore::new();
+    if let Ok(ref store) = store
         && let Err(e) = store.upsert_agent(&state)
     {
         warn!(error = %e, "failed to persist agent state");
     }
+    if let Some(status) = final_status
+        && final_status != previous_status
+    {
+        activity::record(&state.workdir, status.into());
+        if let Ok(ref store) = store
+            && let Err(e) = store.append_status_history(&state.pane_key, status, now)
+        {
+            warn!(error = %e, "failed to record status history");
+        }
+    }
+    if status.is_some() {
+        refresh_window_layout(mux, pane_id, &state.workdir, now);
+    }
+    previous_status
+}
+
+/// Update the saved layout of the window containing `pane_id` with its
+/// current shape, so `resurrect` rebuilds splits and sizes as they were.
+/// Best-effort, and a no-op for windows workmux didn't save a layout for.
+///
+/// The layout is found by the pane's worktree, not its pane ID: tmux reuses
+/// pane IDs after a server restart, so an ID alone could match the layout of
+/// an unrelated window that is waiting to be resurrected.
+fn refresh_window_layout(mux: &dyn Multiplexer, pane_id: &str, workdir: &Path, now: u64) {
+    let Ok(store) = StateStore::new() else {
+        return;
+    };
+    let Ok(worktree) = crate::git::get_repo_root_for(workdir) else {
+        return;
+    };
+    let instance = mux.instance_id();
+    let Some(saved) = store
+        .get_layout(mux.name(), &instance, &worktree)
+        .filter(|saved| saved.panes.iter().any(|p| p.pane_id == pane_id))
+    else {
+        return;
+    };
+    let Ok(Some((geometry, pane_ids))) = mux.window_geometry(pane_id) else {
+        return;
+    };
+    if saved.geometry.as_deref() == Some(geometry.as_str())
+        && saved.panes.iter().map(|p| &p.pane_id).eq(pane_ids.iter())
+    {
+        return;
+    }
+    if let Err(e) = store.save_layout(
+        mux.name(),
+        &instance,
+        &saved.reshaped(geometry, pane_ids, now),
+    ) {
+        warn!(error = %e, "failed to save window layout");
+    }
 }
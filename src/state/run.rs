@@ -2,16 +2,78 @@
 
 use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
+use super::jsonl;
 use super::store::get_state_dir;
 
+/// Number of completed runs kept in the history file.
+const MAX_HISTORY: usize = 200;
+
 /// Specification for a command to execute.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RunSpec {
     pub command: String,
     pub worktree_path: PathBuf,
+    /// Worktree name as given on the command line, used to re-run.
+    #[serde(default)]
+    pub target: String,
+    /// Extra environment variables from `--env` / `--env-file`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Run inside the worktree's sandbox instead of on the host
+    #[serde(default)]
+    pub sandbox: bool,
+    /// Keep re-running the command on file changes
+    #[serde(default)]
+    pub watch: bool,
+    /// Set a pass/fail status icon on the window after each watch run
+    #[serde(default)]
+    pub status_icon: bool,
+    /// What to do when the command fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_fail: Option<OnFail>,
+    /// Show a desktop notification when the command finishes
+    #[serde(default)]
+    pub notify: bool,
+    /// Pane of the worktree's agent, for `--on-fail prompt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_pane: Option<String>,
+}
+
+/// Follow-up action for a failed run (`--on-fail`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnFail {
+    /// Send this message and the failure output to the worktree's agent
+    Prompt(String),
+    /// Run a shell command in the worktree
+    Hook(String),
+}
+
+impl FromStr for OnFail {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s == "prompt" {
+            return Ok(OnFail::Prompt(String::new()));
+        }
+        if let Some(text) = s.strip_prefix("prompt:") {
+            return Ok(OnFail::Prompt(text.to_string()));
+        }
+        match s.strip_prefix("hook:") {
+            Some(command) if !command.trim().is_empty() => Ok(OnFail::Hook(command.to_string())),
+            _ => Err(format!(
+                "expected 'prompt', 'prompt:<message>' or 'hook:<command>', got '{}'",
+                s
+            )),
+        }
+    }
 }
 
 /// Result of command execution.
@@ -21,6 +83,20 @@ pub struct RunResult {
     pub signal: Option<i32>,
 }
 
+/// A completed run, as kept in the run history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub id: String,
+    pub target: String,
+    pub command: String,
+    pub worktree_path: PathBuf,
+    /// Unix timestamp (seconds) when the command started
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+}
+
 /// Get the base directory for run artifacts.
 fn runs_base_dir() -> Result<PathBuf> {
     let dir = get_state_dir()?.join("runs");
@@ -57,9 +133,18 @@ pub fn create_run(run_id: &str, spec: &RunSpec) -> Result<PathBuf> {
     let dir = runs_base_dir()?.join(run_id);
     fs::create_dir_all(&dir).context("Failed to create run directory")?;
 
+    // The spec may carry credentials from --env, so keep it private
     let spec_path = dir.join("spec.json");
     let content = serde_json::to_string_pretty(spec)?;
-    fs::write(&spec_path, content)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // On Windows the state dir is under the user's profile, already private
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&spec_path)
+        .context("Failed to write run spec")?;
+    file.write_all(content.as_bytes())?;
 
     // Pre-create output files (empty)
     fs::write(dir.join("stdout"), "")?;
@@ -96,6 +181,46 @@ pub fn write_result(run_dir: &Path, result: &RunResult) -> Result<()> {
     Ok(())
 }
 
+/// Record the `_exec` process running in `run_dir`, so pruning leaves the
+/// run alone while it's alive (a `--watch` run can sit idle for days).
+pub fn write_pid(run_dir: &Path) -> Result<()> {
+    fs::write(run_dir.join("pid"), std::process::id().to_string())
+        .context("Failed to write run pid")
+}
+
+/// Forget the recorded process once a one-shot run is done, so pruning
+/// doesn't depend on the liveness check.
+pub fn clear_pid(run_dir: &Path) {
+    let _ = fs::remove_file(run_dir.join("pid"));
+}
+
+/// Whether the process recorded by [`write_pid`] is still alive.
+fn is_active(run_dir: &Path) -> bool {
+    fs::read_to_string(run_dir.join("pid"))
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .is_some_and(process_alive)
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks the process exists; EPERM means it does but
+    // belongs to someone else
+    pid > 0
+        && (unsafe { libc::kill(pid, 0) } == 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM))
+}
+
+// No liveness check here: assume the process is alive, so pruning never
+// removes a run that may still be executing
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
 /// Clean up a run directory.
 pub fn cleanup_run(run_dir: &Path) -> Result<()> {
     if run_dir.exists() {
@@ -103,3 +228,232 @@ pub fn cleanup_run(run_dir: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+/// Limits on how many finished run directories are kept, and for how long.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunRetention {
+    /// Remove run directories untouched for longer than this
+    pub max_age: Option<Duration>,
+    /// Keep at most this many finished run directories
+    pub max_count: Option<usize>,
+}
+
+/// Remove run directories outside `retention`. Returns how many were removed.
+pub fn prune_runs(retention: RunRetention) -> Result<usize> {
+    prune_runs_in(&runs_base_dir()?, retention, SystemTime::now())
+}
+
+/// Finished runs (those with a result) count towards `max_count`, newest
+/// first. Unfinished runs are only removed by age, which catches runs whose
+/// `_exec` died before writing a result. Runs whose `_exec` is still alive
+/// are never removed.
+fn prune_runs_in(base: &Path, retention: RunRetention, now: SystemTime) -> Result<usize> {
+    let mut runs: Vec<(PathBuf, SystemTime, bool)> = Vec::new();
+    for entry in fs::read_dir(base).context("Failed to read runs directory")? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let finished = path.join("result.json").exists();
+        runs.push((path.clone(), last_modified(&path), finished));
+    }
+    runs.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut removed = 0;
+    let mut finished_kept = 0;
+    for (path, modified, finished) in runs {
+        let expired = retention
+            .max_age
+            .is_some_and(|max| now.duration_since(modified).unwrap_or_default() > max);
+        let over_count = finished && retention.max_count.is_some_and(|max| finished_kept >= max);
+        if (expired || over_count) && !is_active(&path) {
+            match fs::remove_dir_all(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "failed to remove run")
+                }
+            }
+        } else if finished {
+            finished_kept += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Most recent modification time of a run directory or the files in it.
+fn last_modified(dir: &Path) -> SystemTime {
+    let mtime = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    ["stdout", "stderr", "result.json"]
+        .iter()
+        .filter_map(|name| mtime(&dir.join(name)))
+        .chain(mtime(dir))
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn history_path() -> Result<PathBuf> {
+    Ok(runs_base_dir()?.join("history.jsonl"))
+}
+
+/// Append a completed run to the history, keeping the newest `MAX_HISTORY`.
+pub fn record_run(record: &RunRecord) -> Result<()> {
+    jsonl::append(&history_path()?, record, MAX_HISTORY).context("Failed to update run history")
+}
+
+/// Load the run history, oldest first.
+pub fn load_history() -> Result<Vec<RunRecord>> {
+    jsonl::read(&history_path()?).context("Failed to read run history")
+}
+
+/// Find a run by ID or unique ID prefix. `None` picks the most recent run.
+pub fn find_record(history: &[RunRecord], id: Option<&str>) -> Result<RunRecord> {
+    let Some(id) = id else {
+        return history
+            .last()
+            .cloned()
+            .ok_or_else(|| anyhow!("No runs in history"));
+    };
+    if let Some(exact) = history.iter().find(|r| r.id == id) {
+        return Ok(exact.clone());
+    }
+    let matches: Vec<&RunRecord> = history.iter().filter(|r| r.id.starts_with(id)).collect();
+    match matches.as_slice() {
+        [] => Err(anyhow!("No run found with ID '{}'", id)),
+        [record] => Ok((*record).clone()),
+        _ => Err(anyhow!(
+            "Run ID '{}' is ambiguous ({} matches); use more characters",
+            id,
+            matches.len()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str) -> RunRecord {
+        RunRecord {
+            id: id.to_string(),
+            target: "feature".to_string(),
+            command: "just test".to_string(),
+            worktree_path: PathBuf::from("/wt/feature"),
+            started_at: 0,
+            duration_ms: 10,
+            exit_code: Some(0),
+            signal: None,
+        }
+    }
+
+    #[test]
+    fn find_record_matches_prefix_and_defaults_to_latest() {
+        let history = vec![record("18f0-1"), record("18f1-2"), record("29a0-3")];
+        assert_eq!(find_record(&history, None).unwrap().id, "29a0-3");
+        assert_eq!(find_record(&history, Some("29")).unwrap().id, "29a0-3");
+        assert_eq!(find_record(&history, Some("18f1-2")).unwrap().id, "18f1-2");
+        assert!(find_record(&history, Some("18f")).is_err());
+        assert!(find_record(&history, Some("ff")).is_err());
+        assert!(find_record(&[], None).is_err());
+    }
+
+    #[test]
+    fn on_fail_parses_prompt_and_hook() {
+        assert_eq!(
+            "prompt".parse::<OnFail>().unwrap(),
+            OnFail::Prompt(String::new())
+        );
+        assert_eq!(
+            "prompt:tests failed, see output".parse::<OnFail>().unwrap(),
+            OnFail::Prompt("tests failed, see output".to_string())
+        );
+        assert_eq!(
+            "hook:notify-send fail".parse::<OnFail>().unwrap(),
+            OnFail::Hook("notify-send fail".to_string())
+        );
+        assert!("hook:".parse::<OnFail>().is_err());
+        assert!("email".parse::<OnFail>().is_err());
+    }
+
+    fn make_run(base: &Path, id: &str, finished: bool, age: Duration) {
+        let dir = base.join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("stdout"), "").unwrap();
+        if finished {
+            fs::write(dir.join("result.json"), "{}").unwrap();
+        }
+        let mtime = SystemTime::now() - age;
+        for name in ["stdout", "result.json"] {
+            if let Ok(file) = fs::File::options().write(true).open(dir.join(name)) {
+                file.set_modified(mtime).unwrap();
+            }
+        }
+        fs::File::open(&dir).unwrap().set_modified(mtime).unwrap();
+    }
+
+    #[test]
+    fn prune_runs_applies_age_and_count_limits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path();
+        let hour = Duration::from_secs(3600);
+        make_run(base, "new", true, hour);
+        make_run(base, "mid", true, 2 * hour);
+        make_run(base, "old", true, 3 * hour);
+        make_run(base, "running", false, 3 * hour);
+        make_run(base, "abandoned", false, 48 * hour);
+        fs::write(base.join("history.jsonl"), "").unwrap();
+
+        let retention = RunRetention {
+            max_age: Some(24 * hour),
+            max_count: Some(2),
+        };
+        assert_eq!(
+            prune_runs_in(base, retention, SystemTime::now()).unwrap(),
+            2
+        );
+        let mut left: Vec<String> = fs::read_dir(base)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["history.jsonl", "mid", "new", "running"]);
+
+        // No limits keeps everything
+        assert_eq!(
+            prune_runs_in(base, RunRetention::default(), SystemTime::now()).unwrap(),
+            0
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prune_runs_keeps_runs_with_a_live_process() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path();
+        let hour = Duration::from_secs(3600);
+        make_run(base, "watching", true, 48 * hour);
+        fs::write(base.join("watching/pid"), std::process::id().to_string()).unwrap();
+        make_run(base, "stale", true, 48 * hour);
+        fs::write(base.join("stale/pid"), "0").unwrap();
+
+        let retention = RunRetention {
+            max_age: Some(24 * hour),
+            max_count: Some(0),
+        };
+        assert_eq!(
+            prune_runs_in(base, retention, SystemTime::now()).unwrap(),
+            1
+        );
+        assert!(base.join("watching").exists());
+        assert!(!base.join("stale").exists());
+    }
+
+    #[test]
+    fn run_spec_without_target_still_parses() {
+        let spec: RunSpec =
+            serde_json::from_str(r#"{"command": "ls", "worktree_path": "/wt"}"#).unwrap();
+        assert_eq!(spec.target, "");
+        assert!(spec.env.is_empty());
+        assert!(!spec.sandbox);
+        assert!(spec.on_fail.is_none());
+    }
+}
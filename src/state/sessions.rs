@@ -0,0 +1,314 @@
+//! Agent conversations captured per branch, so reopening a worktree resumes
+//! the conversation that ran in it rather than whichever is newest.
+//!
+//! Claude Code's SessionStart hook reports each session it starts through
+//! `workmux _session-started`. Each branch keeps its sessions as JSON Lines,
+//! oldest first, under `$XDG_STATE_HOME/workmux/sessions/<repo>/<branch>.jsonl`,
+//! with the repository's main worktree path and the branch percent-encoded.
+//!
+//! `workmux session export` and `import` move the whole tree between machines
+//! as one JSON document.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+use serde::{Deserialize, Serialize};
+
+use super::jsonl;
+use super::lock::StateLock;
+use super::store::{get_state_dir, write_atomic};
+use super::types::FILENAME_ENCODE_SET;
+
+/// Sessions kept per branch
+const MAX_SESSIONS: usize = 100;
+
+/// A conversation started on a branch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionRecord {
+    /// Session ID, as the agent's resume flag takes it
+    pub id: String,
+    /// Agent that started it (e.g. "claude")
+    pub agent: String,
+    /// Unix time the session started
+    pub ts: u64,
+    /// tmux session (or WezTerm workspace) the agent ran in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mux_session: Option<String>,
+}
+
+/// The sessions of one branch, as exported.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BranchSessions {
+    /// Main worktree of the repository
+    pub repo: PathBuf,
+    pub branch: String,
+    /// Oldest first
+    pub sessions: Vec<SessionRecord>,
+}
+
+impl SessionRecord {
+    pub fn new(id: String, agent: &str) -> Self {
+        Self {
+            id,
+            agent: agent.to_string(),
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            mux_session: None,
+        }
+    }
+}
+
+fn sessions_dir() -> Result<PathBuf> {
+    Ok(get_state_dir()?.join("sessions"))
+}
+
+fn session_path(base: &Path, repo: &Path, branch: &str) -> PathBuf {
+    let encode = |s: &str| utf8_percent_encode(s, FILENAME_ENCODE_SET).to_string();
+    base.join(encode(&repo.to_string_lossy()))
+        .join(format!("{}.jsonl", encode(branch)))
+}
+
+/// Record the session just started on `branch` of the repository whose main
+/// worktree is `repo`. A session resumed right after it ran isn't recorded
+/// twice.
+pub fn store_session(repo: &Path, branch: &str, record: &SessionRecord) -> Result<()> {
+    store_session_in(&sessions_dir()?, repo, branch, record)
+}
+
+/// The session last started on a branch, if one was captured.
+pub fn load_session(repo: &Path, branch: &str) -> Result<Option<SessionRecord>> {
+    Ok(load_history(repo, branch)?.pop())
+}
+
+/// The sessions started on a branch, oldest first.
+pub fn load_history(repo: &Path, branch: &str) -> Result<Vec<SessionRecord>> {
+    load_history_in(&sessions_dir()?, repo, branch)
+}
+
+/// The `n`th most recent session on a branch, counting from 1.
+pub fn nth_session(repo: &Path, branch: &str, n: usize) -> Result<SessionRecord> {
+    let mut history = load_history(repo, branch)?;
+    if history.is_empty() {
+        bail!("No sessions recorded for branch '{}'", branch);
+    }
+    if n == 0 || n > history.len() {
+        bail!(
+            "Branch '{}' has {} recorded session(s); pick 1 to {}",
+            branch,
+            history.len(),
+            history.len()
+        );
+    }
+    Ok(history.swap_remove(history.len() - n))
+}
+
+fn store_session_in(base: &Path, repo: &Path, branch: &str, record: &SessionRecord) -> Result<()> {
+    let path = session_path(base, repo, branch);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let history: Vec<SessionRecord> = jsonl::read(&path)?;
+    if history.last().is_some_and(|last| last.id == record.id) {
+        return Ok(());
+    }
+    jsonl::append(&path, record, MAX_SESSIONS)
+}
+
+fn load_history_in(base: &Path, repo: &Path, branch: &str) -> Result<Vec<SessionRecord>> {
+    jsonl::read(&session_path(base, repo, branch))
+}
+
+/// Every branch's sessions, sorted by repository and branch.
+pub fn export_all() -> Result<Vec<BranchSessions>> {
+    export_all_in(&sessions_dir()?)
+}
+
+/// Merge exported sessions into the recorded ones. Sessions already recorded
+/// are kept once. Returns how many were added.
+pub fn import_all(branches: &[BranchSessions]) -> Result<usize> {
+    import_all_in(&sessions_dir()?, branches)
+}
+
+fn export_all_in(base: &Path) -> Result<Vec<BranchSessions>> {
+    let decode = |s: &str| percent_decode_str(s).decode_utf8_lossy().into_owned();
+    let repo_dirs = match fs::read_dir(base) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut branches = Vec::new();
+    for repo_dir in repo_dirs.flatten() {
+        if !repo_dir.path().is_dir() {
+            continue;
+        }
+        let repo = PathBuf::from(decode(&repo_dir.file_name().to_string_lossy()));
+        for file in fs::read_dir(repo_dir.path())?.flatten() {
+            let name = file.file_name().to_string_lossy().into_owned();
+            let Some(branch) = name.strip_suffix(".jsonl") else {
+                continue;
+            };
+            let sessions: Vec<SessionRecord> = jsonl::read(&file.path())?;
+            if !sessions.is_empty() {
+                branches.push(BranchSessions {
+                    repo: repo.clone(),
+                    branch: decode(branch),
+                    sessions,
+                });
+            }
+        }
+    }
+    branches.sort_by(|a, b| (&a.repo, &a.branch).cmp(&(&b.repo, &b.branch)));
+    Ok(branches)
+}
+
+/// Session IDs end up in the agent's resume command line, so only plain
+/// ASCII identifiers are accepted from an export file.
+fn is_valid_session_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn import_all_in(base: &Path, branches: &[BranchSessions]) -> Result<usize> {
+    // Check everything first, so a bad file imports nothing
+    for imported in branches {
+        if !imported.repo.is_absolute() {
+            bail!(
+                "Invalid repository path in import: {}",
+                imported.repo.display()
+            );
+        }
+        if let Some(record) = imported
+            .sessions
+            .iter()
+            .find(|record| !is_valid_session_id(&record.id))
+        {
+            bail!(
+                "Invalid session ID in import for branch '{}': {:?}",
+                imported.branch,
+                record.id
+            );
+        }
+    }
+
+    let mut added = 0;
+    for imported in branches {
+        let path = session_path(base, &imported.repo, &imported.branch);
+        // The same lock `jsonl::append` takes, so a session recorded while
+        // importing isn't lost between the read and the write
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let _lock = StateLock::acquire_in(dir, &name)?;
+        let mut sessions = load_history_in(base, &imported.repo, &imported.branch)?;
+        let before = sessions.len();
+        for record in &imported.sessions {
+            if !sessions.iter().any(|s| s.id == record.id) {
+                sessions.push(record.clone());
+            }
+        }
+        if sessions.len() == before {
+            continue;
+        }
+        added += sessions.len() - before;
+        sessions.sort_by_key(|s| s.ts);
+        let keep = sessions.len().saturating_sub(MAX_SESSIONS);
+
+        let mut content = String::new();
+        for record in &sessions[keep..] {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+        write_atomic(&path, content.as_bytes()).with_context(|| {
+            format!(
+                "Failed to write sessions for {} in {}",
+                imported.branch,
+                imported.repo.display()
+            )
+        })?;
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_appends_to_the_branch_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Path::new("/src/app");
+        assert!(
+            load_history_in(dir.path(), repo, "feature/login")
+                .unwrap()
+                .is_empty()
+        );
+
+        let first = SessionRecord::new("abc".to_string(), "claude");
+        let second = SessionRecord::new("def".to_string(), "claude");
+        store_session_in(dir.path(), repo, "feature/login", &first).unwrap();
+        store_session_in(dir.path(), repo, "feature/login", &second).unwrap();
+        // Resuming the latest session doesn't record it again
+        store_session_in(dir.path(), repo, "feature/login", &second).unwrap();
+        store_session_in(dir.path(), repo, "main", &first).unwrap();
+
+        assert_eq!(
+            load_history_in(dir.path(), repo, "feature/login").unwrap(),
+            vec![first.clone(), second]
+        );
+        assert_eq!(
+            load_history_in(dir.path(), repo, "main").unwrap(),
+            vec![first]
+        );
+    }
+
+    #[test]
+    fn export_and_import_round_trip() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+        let repo = Path::new("/src/app");
+        let mut older = SessionRecord::new("abc".to_string(), "claude");
+        older.ts -= 60;
+        older.mux_session = Some("app".to_string());
+        let newer = SessionRecord::new("def".to_string(), "claude");
+        store_session_in(from.path(), repo, "feature/login", &older).unwrap();
+        store_session_in(from.path(), repo, "feature/login", &newer).unwrap();
+        store_session_in(from.path(), repo, "main", &older).unwrap();
+
+        let exported = export_all_in(from.path()).unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0].repo, repo);
+        assert_eq!(exported[0].branch, "feature/login");
+
+        // The target already has one of the sessions
+        store_session_in(to.path(), repo, "feature/login", &newer).unwrap();
+        assert_eq!(import_all_in(to.path(), &exported).unwrap(), 2);
+        assert_eq!(import_all_in(to.path(), &exported).unwrap(), 0);
+        assert_eq!(export_all_in(to.path()).unwrap(), exported);
+    }
+
+    #[test]
+    fn import_rejects_unsafe_ids_and_relative_repos() {
+        let dir = tempfile::tempdir().unwrap();
+        let branch = |repo: &str, id: &str| BranchSessions {
+            repo: PathBuf::from(repo),
+            branch: "main".to_string(),
+            sessions: vec![SessionRecord::new(id.to_string(), "claude")],
+        };
+
+        let unsafe_id = [branch("/src/app", "abc"), branch("/src/app", "x; rm -rf ~")];
+        assert!(import_all_in(dir.path(), &unsafe_id).is_err());
+        assert!(import_all_in(dir.path(), &[branch("..", "abc")]).is_err());
+        // Nothing was written
+        assert!(export_all_in(dir.path()).unwrap().is_empty());
+
+        let ok = [branch("/src/app", "0b1c-2d_3e")];
+        assert_eq!(import_all_in(dir.path(), &ok).unwrap(), 1);
+    }
+}
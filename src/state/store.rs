@@ -6,8 +6,13 @@ use std::io;
 use std::path::{Path, PathBuf};
 use tracing::{info, trace, warn};
 
-use super::types::{AgentState, GlobalSettings, PaneKey};
+use super::jsonl;
+use super::types::{AgentState, GlobalSettings, PaneKey, StatusChange, WindowLayout};
 use crate::config::SandboxRuntime;
+use crate::multiplexer::AgentStatus;
+
+/// Status transitions kept per agent
+const MAX_STATUS_HISTORY: usize = 500;
 
 /// Manages filesystem-based state persistence for workmux agents.
 ///
@@ -15,9 +20,11 @@ use crate::config::SandboxRuntime;
 /// ```text
 /// $XDG_STATE_HOME/workmux/           # ~/.local/state/workmux/
 /// ├── settings.json                   # Global dashboard settings
-/// └── agents/
-///     ├── tmux__default__%1.json     # {backend}__{instance}__{pane_id}.json
-///     └── wezterm__main__3.json
+/// ├── agents/
+/// │   ├── tmux__default__%1.json     # {backend}__{instance}__{pane_id}.json
+/// │   └── wezterm__main__3.json
+/// └── history/
+///     └── tmux__default__%1.jsonl    # Status transitions of the same agent
 /// ```
 pub struct StateStore {
     base_path: PathBuf,
@@ -47,6 +54,11 @@ impl StateStore {
         self.base_path.join("agents")
     }
 
+    /// Path to the status history directory.
+    fn history_dir(&self) -> PathBuf {
+        self.base_path.join("history")
+    }
+
     /// Path to containers directory.
     fn containers_dir(&self) -> PathBuf {
         self.base_path.join("containers")
@@ -57,6 +69,24 @@ impl StateStore {
         self.base_path.join("runtime")
     }
 
+    /// Path to layouts directory (saved window layouts for resurrect).
+    fn layouts_dir(&self) -> PathBuf {
+        self.base_path.join("layouts")
+    }
+
+    /// Path to the saved layout of a worktree's window.
+    fn layout_path(&self, backend: &str, instance: &str, worktree: &Path) -> PathBuf {
+        let encode = |s: &str| {
+            percent_encoding::utf8_percent_encode(s, super::types::FILENAME_ENCODE_SET).to_string()
+        };
+        self.layouts_dir().join(format!(
+            "{}__{}__{}.json",
+            backend,
+            encode(instance),
+            encode(&worktree.to_string_lossy())
+        ))
+    }
+
     /// Path to settings file.
     fn settings_path(&self) -> PathBuf {
         self.base_path.join("settings.json")
@@ -67,6 +97,13 @@ impl StateStore {
         self.agents_dir().join(key.to_filename())
     }
 
+    /// Path to an agent's status history log.
+    fn history_path(&self, key: &PaneKey) -> PathBuf {
+        self.history_dir()
+            .join(key.to_filename())
+            .with_extension("jsonl")
+    }
+
     /// Create or update agent state.
     ///
     /// Uses atomic write (temp file + rename) for crash safety.
@@ -110,10 +147,11 @@ impl StateStore {
         Ok(agents)
     }
 
-    /// Delete agent state.
+    /// Delete agent state, along with its status history.
     ///
     /// No-op if the file doesn't exist.
     pub fn delete_agent(&self, key: &PaneKey) -> Result<()> {
+        let _ = fs::remove_file(self.history_path(key));
         let path = self.agent_path(key);
         match fs::remove_file(&path) {
             Ok(()) => Ok(()),
@@ -122,6 +160,22 @@ impl StateStore {
         }
     }
 
+    /// Record a status transition in the agent's history log.
+    pub fn append_status_history(&self, key: &PaneKey, status: AgentStatus, ts: u64) -> Result<()> {
+        fs::create_dir_all(self.history_dir()).context("Failed to create history directory")?;
+        jsonl::append(
+            &self.history_path(key),
+            &StatusChange { ts, status },
+            MAX_STATUS_HISTORY,
+        )
+    }
+
+    /// An agent's status transitions, oldest first. Empty if none were
+    /// recorded.
+    pub fn load_status_history(&self, key: &PaneKey) -> Result<Vec<StatusChange>> {
+        jsonl::read(&self.history_path(key))
+    }
+
     /// Load global settings.
     ///
     /// Returns defaults if the file is missing or corrupted.
@@ -211,6 +265,28 @@ impl StateStore {
             .collect()
     }
 
+    /// List registered containers for every worktree handle.
+    ///
+    /// Returns `(handle, container_name, runtime)`, sorted by handle.
+    pub fn list_all_containers(&self) -> Vec<(String, String, SandboxRuntime)> {
+        let mut handles: Vec<String> = fs::read_dir(self.containers_dir())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        handles.sort();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                self.list_containers(&handle)
+                    .into_iter()
+                    .map(move |(name, runtime)| (handle.clone(), name, runtime))
+            })
+            .collect()
+    }
+
     /// Rename the container markers directory from `<old_handle>` to `<new_handle>`.
     ///
     /// No-op if the old directory doesn't exist. Returns an error if the
@@ -343,6 +419,32 @@ impl StateStore {
         let _ = fs::remove_file(path);
     }
 
+    // ── Window layouts ──────────────────────────────────────────────────────
+
+    /// Save a worktree window's layout, replacing any earlier one.
+    pub fn save_layout(&self, backend: &str, instance: &str, layout: &WindowLayout) -> Result<()> {
+        fs::create_dir_all(self.layouts_dir()).context("Failed to create layouts directory")?;
+        let path = self.layout_path(backend, instance, &layout.worktree);
+        let content = serde_json::to_string_pretty(layout)?;
+        write_atomic(&path, content.as_bytes())
+    }
+
+    /// The saved layout of a worktree's window, if any.
+    pub fn get_layout(
+        &self,
+        backend: &str,
+        instance: &str,
+        worktree: &Path,
+    ) -> Option<WindowLayout> {
+        let content = fs::read_to_string(self.layout_path(backend, instance, worktree)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Forget a worktree window's layout. No-op if none is saved.
+    pub fn delete_layout(&self, backend: &str, instance: &str, worktree: &Path) {
+        let _ = fs::remove_file(self.layout_path(backend, instance, worktree));
+    }
+
     /// Load agents with reconciliation against live multiplexer state.
     ///
     /// Uses batched pane queries for performance, with backend-specific fallback validation.
@@ -352,6 +454,7 @@ impl StateStore {
         &self,
         mux: &dyn crate::multiplexer::Multiplexer,
     ) -> Result<Vec<crate::multiplexer::AgentPane>> {
+        let started = std::time::Instant::now();
         let all_agents = self.list_all_agents()?;
 
         // Fetch all live pane info in a single batched query
@@ -458,6 +561,11 @@ impl StateStore {
             }
         }
 
+        trace!(
+            agents = valid_agents.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "reconcile: done"
+        );
         Ok(valid_agents)
     }
 }
@@ -465,7 +573,7 @@ impl StateStore {
 /// Write content atomically using temp file + rename.
 ///
 /// This ensures the target file is never partially written.
-fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+pub(super) fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
     let tmp = path.with_extension("json.tmp");
     fs::write(&tmp, content).context("Failed to write temp file")?;
     fs::rename(&tmp, path).context("Failed to rename temp file")?;
@@ -520,7 +628,6 @@ fn read_agent_file(path: &Path) -> Result<Option<AgentState>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::multiplexer::AgentStatus;
     use tempfile::TempDir;
 
     fn test_store() -> (StateStore, TempDir) {
@@ -544,6 +651,7 @@ mod tests {
             status: Some(AgentStatus::Working),
             status_ts: Some(1234567890),
             pane_title: Some("Implementing feature X".to_string()),
+            waiting_reason: None,
             pane_pid: 12345,
             command: "node".to_string(),
             updated_ts: 1234567890,
@@ -612,6 +720,38 @@ mod tests {
         assert!(store.get_agent(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_status_history_roundtrip_and_delete() {
+        let (store, _dir) = test_store();
+        let key = test_pane_key();
+        store.upsert_agent(&test_agent_state(key.clone())).unwrap();
+        assert!(store.load_status_history(&key).unwrap().is_empty());
+
+        store
+            .append_status_history(&key, AgentStatus::Working, 100)
+            .unwrap();
+        store
+            .append_status_history(&key, AgentStatus::Done, 160)
+            .unwrap();
+        let history = store.load_status_history(&key).unwrap();
+        assert_eq!(
+            history,
+            vec![
+                StatusChange {
+                    ts: 100,
+                    status: AgentStatus::Working
+                },
+                StatusChange {
+                    ts: 160,
+                    status: AgentStatus::Done
+                },
+            ]
+        );
+
+        store.delete_agent(&key).unwrap();
+        assert!(store.load_status_history(&key).unwrap().is_empty());
+    }
+
     #[test]
     fn test_delete_nonexistent_agent() {
         let (store, _dir) = test_store();
@@ -717,6 +857,36 @@ mod tests {
         assert_eq!(agents.len(), 1);
     }
 
+    #[test]
+    fn test_layout_roundtrip_and_delete() {
+        let (store, _dir) = test_store();
+        let worktree = PathBuf::from("/home/user/project__worktrees/feature");
+        let layout = WindowLayout {
+            worktree: worktree.clone(),
+            geometry: None,
+            panes: vec![super::super::types::SavedPane {
+                pane_id: "%7".to_string(),
+                command: Some("<agent>".to_string()),
+                focus: true,
+            }],
+            updated_ts: 1,
+        };
+        store
+            .save_layout("tmux", "/tmp/tmux-1000/default", &layout)
+            .unwrap();
+
+        let loaded = store.get_layout("tmux", "/tmp/tmux-1000/default", &worktree);
+        assert_eq!(loaded.as_ref(), Some(&layout));
+        assert!(store.get_layout("tmux", "other", &worktree).is_none());
+
+        store.delete_layout("tmux", "/tmp/tmux-1000/default", &worktree);
+        assert!(
+            store
+                .get_layout("tmux", "/tmp/tmux-1000/default", &worktree)
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_register_container_stores_runtime() {
         let (store, _dir) = test_store();
@@ -756,6 +926,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_all_containers_across_handles() {
+        let (store, _dir) = test_store();
+        assert!(store.list_all_containers().is_empty());
+
+        store
+            .register_container("web", "wm-web-1", &SandboxRuntime::Podman)
+            .unwrap();
+        store
+            .register_container("api", "wm-api-1", &SandboxRuntime::Docker)
+            .unwrap();
+
+        assert_eq!(
+            store.list_all_containers(),
+            vec![
+                (
+                    "api".to_string(),
+                    "wm-api-1".to_string(),
+                    SandboxRuntime::Docker
+                ),
+                (
+                    "web".to_string(),
+                    "wm-web-1".to_string(),
+                    SandboxRuntime::Podman
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_migrate_worktree_paths_rewrites_root_and_subdirs() {
         let (store, _dir) = test_store();
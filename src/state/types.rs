@@ -87,6 +87,10 @@ pub struct AgentState {
     /// Pane title (set by Claude Code to show session summary)
     pub pane_title: Option<String>,
 
+    /// What the agent is asking for while waiting (from its Notification hook)
+    #[serde(default)]
+    pub waiting_reason: Option<String>,
+
     /// PID of the pane's shell process (for pane ID recycling detection).
     /// This is the shell PID, not the agent PID.
     pub pane_pid: u32,
@@ -130,6 +134,7 @@ impl AgentState {
             window_id: String::new(),
             path: self.workdir.clone(),
             pane_title: self.pane_title.clone(),
+            waiting_reason: self.waiting_reason.clone(),
             status: self.status,
             status_ts: self.status_ts,
             updated_ts: Some(self.updated_ts),
@@ -167,6 +172,15 @@ pub struct GlobalSettings {
     /// Sidebar layout mode: "compact" or "tiles"
     #[serde(default)]
     pub sidebar_layout: Option<String>,
+
+    /// Seconds without a status change before an agent counts as stale,
+    /// from the last loaded `dashboard.stale_after`
+    #[serde(default)]
+    pub stale_after_secs: Option<u64>,
+
+    /// Projects folded away in the dashboard's project sort mode
+    #[serde(default)]
+    pub collapsed_projects: Vec<String>,
 }
 
 /// Tracks which pane last-done navigated to, so repeated presses cycle
@@ -199,10 +213,164 @@ pub struct RuntimeState {
     pub updated_ts: u64,
 }
 
+/// One status transition of an agent, appended to its history log.
+///
+/// Persisted to `history/<backend>__<instance>__<pane_id>.jsonl`, one record
+/// per line, oldest first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusChange {
+    /// Unix timestamp of the transition
+    pub ts: u64,
+    pub status: AgentStatus,
+}
+
+/// Summary of an agent's history for the dashboard's timeline view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusTimeline {
+    /// When the first status was recorded
+    pub started: Option<u64>,
+    /// When the agent first asked for input
+    pub first_waiting: Option<u64>,
+    /// When the agent finished, if done is its latest status
+    pub done: Option<u64>,
+    /// Seconds spent working, counting an ongoing stretch up to now
+    pub working_secs: u64,
+}
+
+impl StatusTimeline {
+    /// Summarize transitions, oldest first.
+    pub fn from_changes(changes: &[StatusChange], now: u64) -> Self {
+        let mut working_secs = 0;
+        for (i, change) in changes.iter().enumerate() {
+            if change.status == AgentStatus::Working {
+                let end = changes.get(i + 1).map_or(now, |next| next.ts);
+                working_secs += end.saturating_sub(change.ts);
+            }
+        }
+        let last = changes.last();
+        Self {
+            started: changes.first().map(|c| c.ts),
+            first_waiting: changes
+                .iter()
+                .find(|c| c.status == AgentStatus::Waiting)
+                .map(|c| c.ts),
+            done: last.filter(|c| c.status == AgentStatus::Done).map(|c| c.ts),
+            working_secs,
+        }
+    }
+}
+
+/// A worktree window's panes, saved so `resurrect` can rebuild the window as
+/// it was rather than from the `panes` config.
+///
+/// Persisted to `layouts/<backend>__<instance>__<worktree path>.json`. Written
+/// when the window is created and refreshed with its geometry whenever an
+/// agent in it reports a status.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowLayout {
+    /// Worktree root the window was opened for
+    pub worktree: PathBuf,
+    /// tmux layout string (`#{window_layout}`), once captured
+    #[serde(default)]
+    pub geometry: Option<String>,
+    /// Panes in layout order
+    pub panes: Vec<SavedPane>,
+    /// Unix timestamp of the last write
+    pub updated_ts: u64,
+}
+
+/// One pane of a [`WindowLayout`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedPane {
+    pub pane_id: String,
+    /// Command the pane was set up with (`<agent>` for the agent). None for
+    /// a plain shell, including panes split by hand.
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub focus: bool,
+}
+
+impl WindowLayout {
+    /// The same panes after the window changed shape: `pane_ids` in layout
+    /// order, keeping the command of each pane that was already known.
+    pub fn reshaped(&self, geometry: String, pane_ids: Vec<String>, now: u64) -> Self {
+        let panes = pane_ids
+            .into_iter()
+            .map(|pane_id| {
+                let known = self.panes.iter().find(|p| p.pane_id == pane_id);
+                SavedPane {
+                    command: known.and_then(|p| p.command.clone()),
+                    focus: known.is_some_and(|p| p.focus),
+                    pane_id,
+                }
+            })
+            .collect();
+        Self {
+            worktree: self.worktree.clone(),
+            geometry: Some(geometry),
+            panes,
+            updated_ts: now,
+        }
+    }
+
+    /// Pane config that recreates these panes. Splits are placeholders: the
+    /// geometry is applied afterwards.
+    pub fn to_pane_configs(&self) -> Vec<crate::config::PaneConfig> {
+        self.panes
+            .iter()
+            .enumerate()
+            .map(|(i, pane)| crate::config::PaneConfig {
+                command: pane.command.clone(),
+                focus: pane.focus,
+                split: (i > 0).then_some(crate::config::SplitDirection::Horizontal),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn reshaped_layout_keeps_known_commands() {
+        let layout = WindowLayout {
+            worktree: PathBuf::from("/repo/wt"),
+            geometry: None,
+            panes: vec![
+                SavedPane {
+                    pane_id: "%1".to_string(),
+                    command: Some("<agent>".to_string()),
+                    focus: true,
+                },
+                SavedPane {
+                    pane_id: "%2".to_string(),
+                    command: Some("npm run dev".to_string()),
+                    focus: false,
+                },
+            ],
+            updated_ts: 1,
+        };
+        let reshaped = layout.reshaped(
+            "b25f,200x50,0,0{100x50,0,0,1,99x50,101,0,3}".to_string(),
+            vec!["%1".to_string(), "%3".to_string()],
+            2,
+        );
+        let commands: Vec<_> = reshaped
+            .panes
+            .iter()
+            .map(|p| p.command.as_deref())
+            .collect();
+        assert_eq!(commands, [Some("<agent>"), None]);
+        assert!(reshaped.panes[0].focus);
+
+        let configs = reshaped.to_pane_configs();
+        assert!(configs[0].split.is_none());
+        assert!(configs[1].split.is_some());
+    }
+
     #[test]
     fn test_pane_key_to_filename() {
         let key = PaneKey {
@@ -270,4 +438,30 @@ mod tests {
         assert_eq!(parsed.instance, "/private/tmp/tmux-501/default");
         assert_eq!(parsed.pane_id, "%79");
     }
+
+    #[test]
+    fn test_status_timeline_from_changes() {
+        let change = |ts, status| StatusChange { ts, status };
+        let changes = [
+            change(100, AgentStatus::Working),
+            change(160, AgentStatus::Waiting),
+            change(200, AgentStatus::Working),
+            change(230, AgentStatus::Done),
+        ];
+        let timeline = StatusTimeline::from_changes(&changes, 500);
+        assert_eq!(timeline.started, Some(100));
+        assert_eq!(timeline.first_waiting, Some(160));
+        assert_eq!(timeline.done, Some(230));
+        assert_eq!(timeline.working_secs, 90);
+
+        // Still working: counts up to now, and isn't done
+        let timeline = StatusTimeline::from_changes(&changes[..3], 260);
+        assert_eq!(timeline.done, None);
+        assert_eq!(timeline.working_secs, 120);
+
+        assert_eq!(
+            StatusTimeline::from_changes(&[], 500),
+            StatusTimeline::default()
+        );
+    }
 }
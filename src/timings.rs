@@ -0,0 +1,159 @@
+//! `--timings`: how long a command spent loading config, running git, talking
+//! to the multiplexer and calling the forge, printed when it finishes.
+//!
+//! Time is attributed to the outermost timed operation on each thread, so a
+//! git call made while loading config counts as config. Calls on different
+//! threads overlap, so phases can add up to more than the wall time.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static TOTALS: Mutex<[(Duration, u32); Phase::ALL.len()]> =
+    Mutex::new([(Duration::ZERO, 0); Phase::ALL.len()]);
+
+thread_local! {
+    static IN_PHASE: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Config,
+    Git,
+    Mux,
+    Forge,
+}
+
+impl Phase {
+    const ALL: [Phase; 4] = [Phase::Config, Phase::Git, Phase::Mux, Phase::Forge];
+
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Config => "config",
+            Phase::Git => "git",
+            Phase::Mux => "multiplexer",
+            Phase::Forge => "forge",
+        }
+    }
+
+    /// The phase a subprocess belongs to, by program name.
+    pub fn for_program(program: &str) -> Option<Phase> {
+        match program {
+            "git" => Some(Phase::Git),
+            "tmux" | "wezterm" | "kitten" | "kitty" | "zellij" => Some(Phase::Mux),
+            "gh" => Some(Phase::Forge),
+            _ => None,
+        }
+    }
+}
+
+/// Turn on timing for the rest of the process (`--timings`).
+pub fn enable() {
+    STARTED.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Time `phase` until the returned guard drops. Does nothing unless
+/// `--timings` is on or when another phase is already being timed on this
+/// thread.
+pub fn phase(phase: Phase) -> Timer {
+    if !is_enabled() || IN_PHASE.with(|p| p.replace(true)) {
+        return Timer(None);
+    }
+    Timer(Some((phase, Instant::now())))
+}
+
+/// Guard returned by [`phase`].
+pub struct Timer(Option<(Phase, Instant)>);
+
+impl Timer {
+    /// A guard that times nothing.
+    pub fn none() -> Self {
+        Timer(None)
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let Some((phase, started)) = self.0.take() else {
+            return;
+        };
+        IN_PHASE.with(|p| p.set(false));
+        let mut totals = TOTALS.lock().unwrap_or_else(|e| e.into_inner());
+        let (total, count) = &mut totals[phase as usize];
+        *total += started.elapsed();
+        *count += 1;
+    }
+}
+
+/// Print the breakdown to stderr, if `--timings` is on.
+pub fn print_report() {
+    if !is_enabled() {
+        return;
+    }
+    let wall = STARTED.get().map(|s| s.elapsed()).unwrap_or_default();
+    let totals = *TOTALS.lock().unwrap_or_else(|e| e.into_inner());
+    eprint!("{}", format_report(wall, &totals));
+}
+
+fn format_report(wall: Duration, totals: &[(Duration, u32)]) -> String {
+    let mut out = format!("\nTimings ({} total):\n", format_duration(wall));
+    for (phase, (total, count)) in Phase::ALL.iter().zip(totals) {
+        if *count == 0 {
+            continue;
+        }
+        out.push_str(&format!(
+            "  {:<12} {:>8}  {} call{}\n",
+            phase.label(),
+            format_duration(*total),
+            count,
+            if *count == 1 { "" } else { "s" }
+        ));
+    }
+    out
+}
+
+fn format_duration(d: Duration) -> String {
+    if d >= Duration::from_secs(1) {
+        format!("{:.2}s", d.as_secs_f64())
+    } else {
+        format!("{}ms", d.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_phases_that_ran() {
+        let totals = [
+            (Duration::from_millis(4), 1),
+            (Duration::from_millis(1250), 12),
+            (Duration::ZERO, 0),
+            (Duration::from_millis(800), 2),
+        ];
+        assert_eq!(
+            format_report(Duration::from_millis(2100), &totals),
+            "\nTimings (2.10s total):\n\
+             \x20 config            4ms  1 call\n\
+             \x20 git             1.25s  12 calls\n\
+             \x20 forge           800ms  2 calls\n"
+        );
+    }
+
+    #[test]
+    fn programs_map_to_phases() {
+        assert_eq!(Phase::for_program("git"), Some(Phase::Git));
+        assert_eq!(Phase::for_program("tmux"), Some(Phase::Mux));
+        assert_eq!(Phase::for_program("gh"), Some(Phase::Forge));
+        assert_eq!(Phase::for_program("limactl"), None);
+    }
+}
@@ -0,0 +1,93 @@
+//! Jira (Cloud or Server) over the REST v2 API.
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Ticket, Tracker, request, token};
+use crate::config::TrackerConfig;
+
+pub struct Jira {
+    base_url: String,
+    auth: String,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    key: String,
+    fields: Fields,
+}
+
+#[derive(Deserialize)]
+struct Fields {
+    summary: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    issuetype: Option<IssueType>,
+}
+
+#[derive(Deserialize)]
+struct IssueType {
+    name: String,
+}
+
+impl Jira {
+    pub fn new(config: &TrackerConfig) -> Result<Self> {
+        let Some(url) = &config.url else {
+            bail!("tracker.url is required for Jira, e.g. https://acme.atlassian.net");
+        };
+        let token = token(config, "JIRA_API_TOKEN")?;
+        // Jira Cloud takes the account email with an API token; Server and
+        // Data Center take a personal access token on its own
+        let auth = match &config.email {
+            Some(email) => format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", email, token))
+            ),
+            None => format!("Bearer {}", token),
+        };
+        Ok(Self {
+            base_url: url.trim_end_matches('/').to_string(),
+            auth,
+        })
+    }
+
+    fn headers(&self) -> Vec<String> {
+        vec![
+            format!("Authorization: {}", self.auth),
+            "Accept: application/json".to_string(),
+            "Content-Type: application/json".to_string(),
+        ]
+    }
+}
+
+impl Tracker for Jira {
+    fn fetch(&self, key: &str) -> Result<Ticket> {
+        let url = format!(
+            "{}/rest/api/2/issue/{}?fields=summary,description,issuetype",
+            self.base_url, key
+        );
+        let response = request(&url, &self.headers(), None)
+            .with_context(|| format!("Failed to fetch {} from Jira", key))?;
+        let issue: Issue =
+            serde_json::from_str(&response).context("Unexpected response from Jira")?;
+        Ok(Ticket {
+            url: format!("{}/browse/{}", self.base_url, issue.key),
+            title: issue.fields.summary,
+            description: issue.fields.description.unwrap_or_default(),
+            kind: issue.fields.issuetype.map(|t| t.name),
+            id: issue.key.clone(),
+            key: issue.key,
+        })
+    }
+
+    fn comment(&self, ticket: &Ticket, body: &str) -> Result<()> {
+        let url = format!("{}/rest/api/2/issue/{}/comment", self.base_url, ticket.id);
+        let payload = json!({ "body": body }).to_string();
+        request(&url, &self.headers(), Some(&payload))
+            .with_context(|| format!("Failed to comment on {}", ticket.key))?;
+        Ok(())
+    }
+}
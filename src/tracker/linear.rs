@@ -0,0 +1,86 @@
+//! Linear over its GraphQL API.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{Ticket, Tracker, request, token};
+use crate::config::TrackerConfig;
+
+const API_URL: &str = "https://api.linear.app/graphql";
+
+pub struct Linear {
+    api_key: String,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    id: String,
+    identifier: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    url: String,
+}
+
+impl Linear {
+    pub fn new(config: &TrackerConfig) -> Result<Self> {
+        Ok(Self {
+            api_key: token(config, "LINEAR_API_KEY")?,
+        })
+    }
+
+    /// Run a GraphQL query and return its `data`.
+    fn query(&self, query: &str, variables: Value) -> Result<Value> {
+        let headers = [
+            format!("Authorization: {}", self.api_key),
+            "Content-Type: application/json".to_string(),
+        ];
+        let payload = json!({ "query": query, "variables": variables }).to_string();
+        let response = request(API_URL, &headers, Some(&payload))?;
+        let mut response: Value =
+            serde_json::from_str(&response).context("Unexpected response from Linear")?;
+        if let Some(message) = response
+            .pointer("/errors/0/message")
+            .and_then(Value::as_str)
+        {
+            bail!("Linear: {}", message);
+        }
+        Ok(response["data"].take())
+    }
+}
+
+impl Tracker for Linear {
+    fn fetch(&self, key: &str) -> Result<Ticket> {
+        let data = self
+            .query(
+                "query($id: String!) { issue(id: $id) { id identifier title description url } }",
+                json!({ "id": key }),
+            )
+            .with_context(|| format!("Failed to fetch {} from Linear", key))?;
+        let issue: Issue = serde_json::from_value(data["issue"].clone())
+            .with_context(|| format!("Linear returned no issue {}", key))?;
+        Ok(Ticket {
+            key: issue.identifier,
+            title: issue.title,
+            description: issue.description.unwrap_or_default(),
+            url: issue.url,
+            kind: None,
+            id: issue.id,
+        })
+    }
+
+    fn comment(&self, ticket: &Ticket, body: &str) -> Result<()> {
+        let data = self
+            .query(
+                "mutation($issueId: String!, $body: String!) { \
+                 commentCreate(input: { issueId: $issueId, body: $body }) { success } }",
+                json!({ "issueId": ticket.id, "body": body }),
+            )
+            .with_context(|| format!("Failed to comment on {}", ticket.key))?;
+        if data.pointer("/commentCreate/success") != Some(&Value::Bool(true)) {
+            bail!("Linear did not create the comment on {}", ticket.key);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+//! Issue trackers for `workmux add --ticket`: the ticket names the branch and
+//! seeds the prompt, and gets a comment linking the branch's pull request
+//! once one is open.
+//!
+//! Requests go through curl, like webhooks, with the token passed in a curl
+//! config on stdin so it doesn't show up in `ps`.
+
+mod jira;
+mod linear;
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::config::{Config, TrackerBackend, TrackerConfig};
+use crate::github::PrSummary;
+use crate::notification::webhook::curl_quote;
+use crate::secrets::SecretSource;
+use crate::state::lock::StateLock;
+use crate::template::create_template_env;
+
+/// Seconds before curl gives up on the tracker
+const TIMEOUT_SECS: &str = "20";
+
+/// Longest title slug used in the default branch name
+const MAX_SLUG_LEN: usize = 50;
+
+const DEFAULT_BRANCH_TEMPLATE: &str = "{{ key | lower }}-{{ slug }}";
+
+const DEFAULT_PROMPT_TEMPLATE: &str = "{{ key }}: {{ title }}\n{{ url }}\n\n{{ description }}";
+
+/// A ticket, as exposed to the branch and prompt templates
+#[derive(Debug, Clone, Serialize)]
+pub struct Ticket {
+    /// Human-readable key, e.g. `PROJ-123`
+    pub key: String,
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    /// Issue type (Jira only), e.g. `Bug`
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// The tracker's own ID, used to comment
+    #[serde(skip)]
+    id: String,
+}
+
+trait Tracker {
+    fn fetch(&self, key: &str) -> Result<Ticket>;
+    fn comment(&self, ticket: &Ticket, body: &str) -> Result<()>;
+}
+
+fn backend(config: &TrackerConfig) -> Result<Box<dyn Tracker>> {
+    match config.backend {
+        Some(TrackerBackend::Jira) => Ok(Box::new(jira::Jira::new(config)?)),
+        Some(TrackerBackend::Linear) => Ok(Box::new(linear::Linear::new(config)?)),
+        None => bail!(
+            "No issue tracker configured. Set tracker.backend (jira or linear) in your global config"
+        ),
+    }
+}
+
+/// Fetch ticket `key` from the configured tracker.
+pub fn fetch(config: &TrackerConfig, key: &str) -> Result<Ticket> {
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Invalid ticket key '{}', expected e.g. PROJ-123", key);
+    }
+    backend(config)?.fetch(key)
+}
+
+/// The API token: `tracker.token` (a secret reference), or `default_var`
+/// from the environment.
+fn token(config: &TrackerConfig, default_var: &str) -> Result<String> {
+    let token = match &config.token {
+        Some(reference) => SecretSource::parse(reference)?.read(default_var)?,
+        None => std::env::var(default_var).unwrap_or_default(),
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        bail!(
+            "No tracker token. Set {} or tracker.token in your global config",
+            default_var
+        );
+    }
+    Ok(token.to_string())
+}
+
+/// Branch name for `ticket` from `tracker.branch`.
+pub fn branch_name(config: &TrackerConfig, ticket: &Ticket) -> Result<String> {
+    let template = config.branch.as_deref().unwrap_or(DEFAULT_BRANCH_TEMPLATE);
+    let name = render(template, ticket).context("Failed to render tracker.branch")?;
+    let name = name.trim();
+    if name.is_empty() {
+        bail!(
+            "tracker.branch rendered an empty branch name for {}",
+            ticket.key
+        );
+    }
+    Ok(name.to_string())
+}
+
+/// Prompt seeded from `ticket` with `tracker.prompt`.
+pub fn prompt(config: &TrackerConfig, ticket: &Ticket) -> Result<String> {
+    let template = config.prompt.as_deref().unwrap_or(DEFAULT_PROMPT_TEMPLATE);
+    let prompt = render(template, ticket).context("Failed to render tracker.prompt")?;
+    Ok(prompt.trim().to_string())
+}
+
+fn render(template: &str, ticket: &Ticket) -> Result<String> {
+    let mut context = serde_json::to_value(ticket)?;
+    context["slug"] = json!(title_slug(&ticket.title));
+    Ok(create_template_env().render_str(template, &context)?)
+}
+
+/// `title` in kebab-case, cut at a word boundary to `MAX_SLUG_LEN`.
+fn title_slug(title: &str) -> String {
+    let slug = slug::slugify(title);
+    if slug.len() <= MAX_SLUG_LEN {
+        return slug;
+    }
+    match slug[..=MAX_SLUG_LEN].rfind('-') {
+        Some(cut) if cut > 0 => slug[..cut].to_string(),
+        _ => slug[..MAX_SLUG_LEN].to_string(),
+    }
+}
+
+/// Comment on the tickets of branches in `repo_root` that have an open pull
+/// request in `prs`, once per branch. Best-effort; runs wherever PR status is
+/// refreshed (`list`, `status`, the dashboard).
+///
+/// Only one process links at a time; the others skip it. A branch is marked
+/// as linked only after its comment is posted, so a process that exits
+/// mid-request leaves it for the next refresh.
+pub fn link_prs(repo_root: &Path, prs: &HashMap<String, PrSummary>) {
+    let pending = || -> Vec<(String, String, &PrSummary)> {
+        crate::git::unlinked_branch_tickets_in(repo_root)
+            .into_iter()
+            .filter_map(|(branch, key)| {
+                let pr = prs.get(&branch).filter(|pr| pr.state == "OPEN")?;
+                Some((branch, key, pr))
+            })
+            .collect()
+    };
+    if pending().is_empty() {
+        return;
+    }
+    let Ok((config, _)) = Config::load_with_location_from(repo_root, None) else {
+        return;
+    };
+    if !config.tracker.link_pr.unwrap_or(true) {
+        return;
+    }
+    let tracker = match backend(&config.tracker) {
+        Ok(tracker) => tracker,
+        Err(e) => {
+            tracing::debug!(error = %e, "tracker:link skipped");
+            return;
+        }
+    };
+    let _lock = match StateLock::try_acquire("tracker-link") {
+        Ok(Some(lock)) => lock,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::debug!(error = %e, "tracker:link skipped");
+            return;
+        }
+    };
+
+    // Re-read under the lock: another process may have just linked some
+    for (branch, key, pr) in pending() {
+        let link = pr.url.clone().unwrap_or_else(|| format!("#{}", pr.number));
+        let body = format!("Pull request opened: {} ({})", pr.title, link);
+        if let Err(e) = tracker
+            .fetch(&key)
+            .and_then(|ticket| tracker.comment(&ticket, &body))
+        {
+            tracing::warn!(ticket = %key, error = %e, "tracker:link failed");
+            continue;
+        }
+        match crate::git::set_branch_ticket_pr_in(&branch, Some(pr.number), repo_root) {
+            Ok(()) => tracing::info!(ticket = %key, pr = pr.number, "tracker:linked pr"),
+            Err(e) => tracing::warn!(ticket = %key, error = %e, "tracker:link not recorded"),
+        }
+    }
+}
+
+/// Send a request with curl and return the response body. `body` makes it a
+/// POST.
+fn request(url: &str, headers: &[String], body: Option<&str>) -> Result<String> {
+    let mut config = format!("url = {}\n", curl_quote(url));
+    for header in headers {
+        config.push_str(&format!("header = {}\n", curl_quote(header)));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-raw = {}\n", curl_quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["-sS", "-m", TIMEOUT_SECS, "-K", "-", "-w", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run curl. Is curl installed?")?;
+    let mut stdin = child.stdin.take().context("Failed to open curl stdin")?;
+    stdin.write_all(config.as_bytes())?;
+    drop(stdin);
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        bail!(
+            "Request to {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (response, status) = split_status(&stdout);
+    if !(200..300).contains(&status) {
+        let snippet: String = response.chars().take(300).collect();
+        return Err(anyhow!(
+            "{} returned HTTP {}: {}",
+            url,
+            status,
+            snippet.trim()
+        ));
+    }
+    Ok(response.to_string())
+}
+
+/// Split curl output written with `-w "\n%{http_code}"` into the body and
+/// the status code.
+fn split_status(output: &str) -> (&str, u16) {
+    match output.rsplit_once('\n') {
+        Some((body, status)) => (body, status.trim().parse().unwrap_or(0)),
+        None => ("", output.trim().parse().unwrap_or(0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(title: &str) -> Ticket {
+        Ticket {
+            key: "PROJ-123".to_string(),
+            title: title.to_string(),
+            description: "Users land on / after logging in.".to_string(),
+            url: "https://acme.atlassian.net/browse/PROJ-123".to_string(),
+            kind: Some("Bug".to_string()),
+            id: "PROJ-123".to_string(),
+        }
+    }
+
+    #[test]
+    fn default_branch_and_prompt() {
+        let config = TrackerConfig::default();
+        let ticket = ticket("Fix login redirect");
+        assert_eq!(
+            branch_name(&config, &ticket).unwrap(),
+            "proj-123-fix-login-redirect"
+        );
+        assert_eq!(
+            prompt(&config, &ticket).unwrap(),
+            "PROJ-123: Fix login redirect\n\
+             https://acme.atlassian.net/browse/PROJ-123\n\n\
+             Users land on / after logging in."
+        );
+    }
+
+    #[test]
+    fn branch_template_uses_ticket_fields() {
+        let config = TrackerConfig {
+            branch: Some("{{ type | lower }}/{{ key }}-{{ title | slugify }}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            branch_name(&config, &ticket("Fix login")).unwrap(),
+            "bug/PROJ-123-fix-login"
+        );
+    }
+
+    #[test]
+    fn long_titles_are_cut_at_a_word() {
+        let slug = title_slug(
+            "Make the checkout page remember the shipping address between visits for everyone",
+        );
+        assert_eq!(slug, "make-the-checkout-page-remember-the-shipping");
+        assert!(slug.len() <= MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn rejects_keys_that_are_not_plain() {
+        let config = TrackerConfig::default();
+        assert!(
+            fetch(&config, "../../admin")
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid ticket key")
+        );
+    }
+
+    #[test]
+    fn splits_curl_status() {
+        assert_eq!(split_status("{\"ok\":true}\n200"), ("{\"ok\":true}", 200));
+        assert_eq!(split_status("\n404"), ("", 404));
+        assert_eq!(split_status("000"), ("", 0));
+    }
+}
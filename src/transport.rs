@@ -0,0 +1,201 @@
+//! Where tmux and git commands run: on this machine, or on another one over
+//! SSH (`workmux --remote <host>`), and which tmux or WezTerm server they talk
+//! to (`workmux --socket <path>`).
+//!
+//! The transport is chosen once at startup. [`Cmd`](crate::cmd::Cmd) asks it
+//! to build every process, so tmux and git calls go to the remote host while
+//! everything else (editors, `gh`, notifications) stays local.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::shell::shell_quote;
+
+static TRANSPORT: OnceLock<Transport> = OnceLock::new();
+static SOCKET: OnceLock<PathBuf> = OnceLock::new();
+
+/// Programs that run on the remote host under `--remote`
+const REMOTE_PROGRAMS: &[&str] = &["tmux", "git"];
+
+/// How long an idle SSH connection is kept open for the next command
+const CONTROL_PERSIST_SECS: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Transport {
+    #[default]
+    Local,
+    /// Run tmux and git on `host` over SSH, in `dir` unless a command sets
+    /// its own working directory
+    Ssh { host: String, dir: Option<String> },
+}
+
+/// Use `transport` for the rest of the process. Only the first call counts.
+pub fn set(transport: Transport) {
+    let _ = TRANSPORT.set(transport);
+}
+
+pub fn current() -> &'static Transport {
+    static LOCAL: Transport = Transport::Local;
+    TRANSPORT.get().unwrap_or(&LOCAL)
+}
+
+/// Talk to the tmux or WezTerm server at `socket` for the rest of the
+/// process. Only the first call counts.
+pub fn set_socket(socket: PathBuf) {
+    let _ = SOCKET.set(socket);
+}
+
+/// The server socket given with `--socket`, if any.
+pub fn socket() -> Option<&'static Path> {
+    SOCKET.get().map(PathBuf::as_path)
+}
+
+/// The SSH host given with `--remote`, if any.
+pub fn remote_host() -> Option<&'static str> {
+    match current() {
+        Transport::Ssh { host, .. } => Some(host),
+        Transport::Local => None,
+    }
+}
+
+impl Transport {
+    /// Build the process that runs `program args` in `workdir`.
+    pub fn command(&self, program: &str, args: &[&str], workdir: Option<&Path>) -> Command {
+        let socket_str = socket().map(|s| s.to_string_lossy());
+        let tmux_args;
+        let args = match (program, &socket_str) {
+            ("tmux", Some(socket)) => {
+                tmux_args = with_tmux_socket(socket, args);
+                &tmux_args[..]
+            }
+            _ => args,
+        };
+        let mut cmd = self.build(program, args, workdir);
+        if let (Transport::Local, "wezterm", Some(socket)) = (self, program, socket()) {
+            cmd.env("WEZTERM_UNIX_SOCKET", socket);
+        }
+        cmd
+    }
+
+    fn build(&self, program: &str, args: &[&str], workdir: Option<&Path>) -> Command {
+        match self {
+            Transport::Ssh { host, dir } if REMOTE_PROGRAMS.contains(&program) => {
+                let dir = workdir
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .or_else(|| dir.clone());
+                let mut cmd = Command::new("ssh");
+                cmd.args(ssh_options())
+                    .arg(host)
+                    .arg("--")
+                    .arg(remote_script(program, args, dir.as_deref()));
+                cmd
+            }
+            _ => {
+                let mut cmd = Command::new(program);
+                cmd.args(args);
+                if let Some(dir) = workdir {
+                    cmd.current_dir(dir);
+                }
+                cmd
+            }
+        }
+    }
+}
+
+/// `args` prefixed with tmux's socket option.
+fn with_tmux_socket<'a>(socket: &'a str, args: &[&'a str]) -> Vec<&'a str> {
+    ["-S", socket]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect()
+}
+
+/// Options shared by every SSH call. Commands reuse one connection, since
+/// a single workmux command can make dozens of tmux and git calls, and never
+/// prompt, since stdin isn't connected.
+fn ssh_options() -> Vec<String> {
+    let control_path = std::env::temp_dir().join("workmux-ssh-%C");
+    vec![
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        "ControlMaster=auto".to_string(),
+        "-o".to_string(),
+        format!("ControlPath={}", control_path.display()),
+        "-o".to_string(),
+        format!("ControlPersist={}", CONTROL_PERSIST_SECS),
+    ]
+}
+
+/// The shell command line sent to the remote host.
+fn remote_script(program: &str, args: &[&str], dir: Option<&str>) -> String {
+    let command = std::iter::once(program)
+        .chain(args.iter().copied())
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ");
+    match dir {
+        Some(dir) => format!("cd {} && {}", quote_dir(dir), command),
+        None => command,
+    }
+}
+
+/// Quote a directory, leaving a leading `~/` unquoted so the remote shell
+/// expands it.
+fn quote_dir(dir: &str) -> String {
+    match dir.strip_prefix("~/") {
+        Some(rest) => format!("~/{}", shell_quote(rest)),
+        None if dir == "~" => "~".to_string(),
+        None => shell_quote(dir),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_script_quotes_arguments() {
+        assert_eq!(
+            remote_script("tmux", &["display-message", "-p", "#{pane_id}"], None),
+            "tmux display-message -p '#{pane_id}'"
+        );
+        assert_eq!(
+            remote_script("git", &["status"], Some("/home/me/my repo")),
+            "cd '/home/me/my repo' && git status"
+        );
+    }
+
+    #[test]
+    fn home_relative_dirs_expand_remotely() {
+        assert_eq!(quote_dir("~/src/api"), "~/src/api");
+        assert_eq!(quote_dir("~/my src"), "~/'my src'");
+        assert_eq!(quote_dir("~"), "~");
+        assert_eq!(quote_dir("/srv/~x"), "'/srv/~x'");
+    }
+
+    #[test]
+    fn tmux_socket_goes_before_the_subcommand() {
+        assert_eq!(
+            with_tmux_socket("/tmp/ci.sock", &["list-panes", "-a"]),
+            ["-S", "/tmp/ci.sock", "list-panes", "-a"]
+        );
+    }
+
+    #[test]
+    fn only_tmux_and_git_go_remote() {
+        let ssh = Transport::Ssh {
+            host: "dev-box".to_string(),
+            dir: None,
+        };
+        assert_eq!(
+            ssh.command("tmux", &["ls"], None).get_program(),
+            std::ffi::OsStr::new("ssh")
+        );
+        assert_eq!(
+            ssh.command("gh", &["pr", "list"], None).get_program(),
+            std::ffi::OsStr::new("gh")
+        );
+    }
+}
@@ -1,3 +1,16 @@
 //! Shared UI components used across commands.
 
+pub mod picker;
 pub mod theme;
+
+/// Fuzzy subsequence match: every character in `query` must appear in `target` in order.
+/// "mergefail" matches "merge-fail-delete" because m-e-r-g-e-f-a-i-l appear in sequence.
+pub fn fuzzy_match(query: &str, target: &str) -> bool {
+    let mut target_chars = target.chars();
+    for qc in query.chars() {
+        if !target_chars.any(|tc| tc == qc) {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,157 @@
+//! Inline fuzzy picker: type to filter a list, Enter to choose.
+//!
+//! Draws below the cursor instead of taking over the screen, so the shell
+//! scrollback stays intact and the picker disappears once it's done.
+
+use std::io;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState};
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use unicode_width::UnicodeWidthStr;
+
+use super::fuzzy_match;
+
+/// Most rows shown at once; the list scrolls past that.
+const MAX_VISIBLE: usize = 10;
+
+pub struct PickerItem {
+    pub label: String,
+    /// Shown dimmed after the label, e.g. agent status and PR
+    pub detail: String,
+}
+
+/// Let the user pick one of `items`. Returns its index, or `None` if the
+/// picker was cancelled with Esc or Ctrl-C.
+pub fn pick(prompt: &str, items: &[PickerItem]) -> Result<Option<usize>> {
+    let height = items.len().min(MAX_VISIBLE) as u16 + 1;
+    enable_raw_mode()?;
+    let result = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )
+    .map_err(anyhow::Error::from)
+    .and_then(|mut terminal| {
+        let picked = run_picker(&mut terminal, prompt, items);
+        terminal.clear()?;
+        picked
+    });
+    disable_raw_mode()?;
+    result
+}
+
+fn run_picker(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    prompt: &str,
+    items: &[PickerItem],
+) -> Result<Option<usize>> {
+    let mut query = String::new();
+    let mut state = ListState::default().with_selected(Some(0));
+
+    loop {
+        let matches = filter(items, &query);
+        terminal.draw(|frame| {
+            let [input_area, list_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(frame.area());
+            let prefix = format!("{} > ", prompt);
+            let cursor_x = input_area.x + (prefix.width() + query.width()) as u16;
+            frame.render_widget(
+                Line::from(vec![
+                    Span::styled(prefix, Style::new().add_modifier(Modifier::BOLD)),
+                    Span::raw(query.as_str()),
+                ]),
+                input_area,
+            );
+            frame.set_cursor_position(Position::new(cursor_x, input_area.y));
+
+            let rows: Vec<ListItem> = matches
+                .iter()
+                .map(|&i| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(items[i].label.as_str()),
+                        Span::raw("  "),
+                        Span::styled(
+                            items[i].detail.as_str(),
+                            Style::new().add_modifier(Modifier::DIM),
+                        ),
+                    ]))
+                })
+                .collect();
+            let list = List::new(rows)
+                .highlight_symbol("> ")
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(list, list_area, &mut state);
+        })?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => {
+                if let Some(&index) = state.selected().and_then(|s| matches.get(s)) {
+                    return Ok(Some(index));
+                }
+            }
+            KeyCode::Up => state.select_previous(),
+            KeyCode::Char('p') if ctrl => state.select_previous(),
+            KeyCode::Down => state.select_next(),
+            KeyCode::Char('n') if ctrl => state.select_next(),
+            KeyCode::Backspace => {
+                query.pop();
+                state.select(Some(0));
+            }
+            KeyCode::Char(c) if !ctrl => {
+                query.push(c);
+                state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Indices of the items whose label fuzzy-matches `query`, ignoring case.
+fn filter(items: &[PickerItem], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| fuzzy_match(&query, &item.label.to_lowercase()))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: &str) -> PickerItem {
+        PickerItem {
+            label: label.to_string(),
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn filter_matches_subsequences_ignoring_case() {
+        let items = [item("user-auth"), item("Fix-Login"), item("api")];
+        assert_eq!(filter(&items, ""), vec![0, 1, 2]);
+        assert_eq!(filter(&items, "ua"), vec![0]);
+        assert_eq!(filter(&items, "FL"), vec![1]);
+        assert_eq!(filter(&items, "a"), vec![0, 2]);
+        assert!(filter(&items, "zzz").is_empty());
+    }
+}
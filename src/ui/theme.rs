@@ -1,6 +1,6 @@
-//! Theme palette for UI colors, shared by dashboard and sidebar.
+//! Theme palette for UI colors, shared by dashboard, sidebar and `workmux list`.
 
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 
 use crate::config::{CustomThemeColors, ThemeConfig, ThemeMode, ThemeScheme};
 
@@ -39,6 +39,59 @@ pub struct ThemePalette {
     pub danger: Color,
     /// Patch mode, merged PRs, waiting status, diff icons
     pub accent: Color,
+
+    // --- Role overrides ---
+    /// Status, stale and check colors set in the `custom` block
+    pub roles: RoleColors,
+}
+
+/// Colors for specific roles. Unset roles use the semantic color they're
+/// drawn with by default.
+#[derive(Default)]
+pub struct RoleColors {
+    pub working: Option<Color>,
+    pub waiting: Option<Color>,
+    pub done: Option<Color>,
+    pub stale: Option<Color>,
+    pub check_success: Option<Color>,
+    pub check_failure: Option<Color>,
+    pub check_pending: Option<Color>,
+}
+
+/// Resolve dark or light mode: the config override, or the terminal
+/// background.
+pub fn resolve_mode(config: &ThemeConfig) -> ThemeMode {
+    config.mode.unwrap_or_else(|| match terminal_light::luma() {
+        Ok(luma) if luma > 0.6 => ThemeMode::Light,
+        _ => ThemeMode::Dark,
+    })
+}
+
+/// Wrap `text` in the ANSI foreground escape for `color`, for plain CLI
+/// output. `Color::Reset` leaves it unstyled.
+pub fn paint(text: &str, color: Color) -> String {
+    let code = match color {
+        Color::Reset => return text.to_string(),
+        Color::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+        Color::Indexed(i) => format!("38;5;{}", i),
+        Color::Black => "30".to_string(),
+        Color::Red => "31".to_string(),
+        Color::Green => "32".to_string(),
+        Color::Yellow => "33".to_string(),
+        Color::Blue => "34".to_string(),
+        Color::Magenta => "35".to_string(),
+        Color::Cyan => "36".to_string(),
+        Color::Gray => "37".to_string(),
+        Color::DarkGray => "90".to_string(),
+        Color::LightRed => "91".to_string(),
+        Color::LightGreen => "92".to_string(),
+        Color::LightYellow => "93".to_string(),
+        Color::LightBlue => "94".to_string(),
+        Color::LightMagenta => "95".to_string(),
+        Color::LightCyan => "96".to_string(),
+        Color::White => "97".to_string(),
+    };
+    format!("\x1b[{}m{}\x1b[0m", code, text)
 }
 
 impl ThemePalette {
@@ -78,6 +131,59 @@ impl ThemePalette {
         apply_color!(warning);
         apply_color!(danger);
         apply_color!(accent);
+
+        macro_rules! apply_role {
+            ($field:ident) => {
+                if let Some(color) = custom.$field.as_ref().and_then(|s| s.parse::<Color>().ok()) {
+                    self.roles.$field = Some(color);
+                }
+            };
+        }
+        apply_role!(working);
+        apply_role!(waiting);
+        apply_role!(done);
+        apply_role!(stale);
+        apply_role!(check_success);
+        apply_role!(check_failure);
+        apply_role!(check_pending);
+    }
+
+    pub fn working(&self) -> Color {
+        self.roles.working.unwrap_or(self.info)
+    }
+
+    pub fn waiting(&self) -> Color {
+        self.roles.waiting.unwrap_or(self.accent)
+    }
+
+    pub fn done(&self) -> Color {
+        self.roles.done.unwrap_or(self.success)
+    }
+
+    pub fn stale(&self) -> Color {
+        self.roles.stale.unwrap_or(self.dimmed)
+    }
+
+    pub fn check_success(&self) -> Color {
+        self.roles.check_success.unwrap_or(self.success)
+    }
+
+    pub fn check_failure(&self) -> Color {
+        self.roles.check_failure.unwrap_or(self.danger)
+    }
+
+    pub fn check_pending(&self) -> Color {
+        self.roles.check_pending.unwrap_or(self.accent)
+    }
+
+    /// Style for the selected row. Without a background color (the no-color
+    /// scheme) the row is shown in reverse video instead.
+    pub fn highlight_style(&self) -> Style {
+        if self.highlight_row_bg == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.highlight_row_bg)
+        }
     }
 
     /// Build a palette for the given scheme and mode.
@@ -107,6 +213,9 @@ impl ThemePalette {
             (ThemeScheme::FestivalCircuit, ThemeMode::Light) => Self::festival_circuit_light(),
             (ThemeScheme::TealDrift, ThemeMode::Dark) => Self::teal_drift_dark(),
             (ThemeScheme::TealDrift, ThemeMode::Light) => Self::teal_drift_light(),
+            (ThemeScheme::NoColor, _) => Self::no_color(),
+            (ThemeScheme::HighContrast, ThemeMode::Dark) => Self::high_contrast_dark(),
+            (ThemeScheme::HighContrast, ThemeMode::Light) => Self::high_contrast_light(),
         }
     }
 
@@ -129,6 +238,7 @@ impl ThemePalette {
             warning: Color::Rgb(249, 226, 175),
             danger: Color::Rgb(237, 135, 150),
             accent: Color::Rgb(203, 166, 247),
+            roles: RoleColors::default(),
         }
     }
 
@@ -149,6 +259,7 @@ impl ThemePalette {
             warning: Color::Rgb(223, 142, 29),
             danger: Color::Rgb(210, 15, 57),
             accent: Color::Rgb(136, 57, 239),
+            roles: RoleColors::default(),
         }
     }
 
@@ -171,6 +282,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 189, 92),
             danger: Color::Rgb(228, 108, 92),
             accent: Color::Rgb(220, 136, 108),
+            roles: RoleColors::default(),
         }
     }
 
@@ -191,6 +303,7 @@ impl ThemePalette {
             warning: Color::Rgb(180, 120, 20),
             danger: Color::Rgb(190, 60, 45),
             accent: Color::Rgb(175, 85, 55),
+            roles: RoleColors::default(),
         }
     }
 
@@ -213,6 +326,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 197, 108),
             danger: Color::Rgb(255, 122, 122),
             accent: Color::Rgb(153, 170, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -233,6 +347,7 @@ impl ThemePalette {
             warning: Color::Rgb(185, 130, 30),
             danger: Color::Rgb(200, 55, 55),
             accent: Color::Rgb(80, 90, 200),
+            roles: RoleColors::default(),
         }
     }
 
@@ -255,6 +370,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 179, 0),
             danger: Color::Rgb(255, 75, 75),
             accent: Color::Rgb(166, 97, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -275,6 +391,7 @@ impl ThemePalette {
             warning: Color::Rgb(190, 125, 0),
             danger: Color::Rgb(210, 35, 35),
             accent: Color::Rgb(110, 50, 210),
+            roles: RoleColors::default(),
         }
     }
 
@@ -297,6 +414,7 @@ impl ThemePalette {
             warning: Color::Rgb(206, 177, 115),
             danger: Color::Rgb(186, 132, 135),
             accent: Color::Rgb(161, 149, 188),
+            roles: RoleColors::default(),
         }
     }
 
@@ -317,6 +435,7 @@ impl ThemePalette {
             warning: Color::Rgb(155, 125, 50),
             danger: Color::Rgb(150, 75, 80),
             accent: Color::Rgb(105, 90, 145),
+            roles: RoleColors::default(),
         }
     }
 
@@ -339,6 +458,7 @@ impl ThemePalette {
             warning: Color::Rgb(248, 191, 89),
             danger: Color::Rgb(255, 110, 87),
             accent: Color::Rgb(132, 209, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -359,6 +479,7 @@ impl ThemePalette {
             warning: Color::Rgb(175, 125, 20),
             danger: Color::Rgb(200, 55, 35),
             accent: Color::Rgb(45, 130, 195),
+            roles: RoleColors::default(),
         }
     }
 
@@ -381,6 +502,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 167, 46),
             danger: Color::Rgb(255, 74, 138),
             accent: Color::Rgb(202, 79, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -401,6 +523,7 @@ impl ThemePalette {
             warning: Color::Rgb(185, 115, 10),
             danger: Color::Rgb(205, 30, 85),
             accent: Color::Rgb(140, 35, 210),
+            roles: RoleColors::default(),
         }
     }
 
@@ -423,6 +546,7 @@ impl ThemePalette {
             warning: Color::Rgb(218, 169, 88),
             danger: Color::Rgb(191, 111, 88),
             accent: Color::Rgb(166, 131, 173),
+            roles: RoleColors::default(),
         }
     }
 
@@ -443,6 +567,7 @@ impl ThemePalette {
             warning: Color::Rgb(165, 118, 30),
             danger: Color::Rgb(160, 65, 45),
             accent: Color::Rgb(115, 75, 125),
+            roles: RoleColors::default(),
         }
     }
 
@@ -465,6 +590,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 196, 160),
             danger: Color::Rgb(255, 155, 173),
             accent: Color::Rgb(205, 180, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -485,6 +611,7 @@ impl ThemePalette {
             warning: Color::Rgb(185, 125, 55),
             danger: Color::Rgb(200, 65, 85),
             accent: Color::Rgb(130, 95, 200),
+            roles: RoleColors::default(),
         }
     }
 
@@ -507,6 +634,7 @@ impl ThemePalette {
             warning: Color::Rgb(171, 179, 187),
             danger: Color::Rgb(146, 153, 161),
             accent: Color::Rgb(232, 236, 240),
+            roles: RoleColors::default(),
         }
     }
 
@@ -527,6 +655,7 @@ impl ThemePalette {
             warning: Color::Rgb(100, 108, 118),
             danger: Color::Rgb(120, 128, 138),
             accent: Color::Rgb(60, 66, 76),
+            roles: RoleColors::default(),
         }
     }
 
@@ -549,6 +678,7 @@ impl ThemePalette {
             warning: Color::Rgb(255, 156, 63),
             danger: Color::Rgb(255, 92, 132),
             accent: Color::Rgb(178, 101, 255),
+            roles: RoleColors::default(),
         }
     }
 
@@ -569,6 +699,7 @@ impl ThemePalette {
             warning: Color::Rgb(190, 105, 15),
             danger: Color::Rgb(205, 40, 75),
             accent: Color::Rgb(120, 50, 210),
+            roles: RoleColors::default(),
         }
     }
 
@@ -592,6 +723,7 @@ impl ThemePalette {
             warning: Color::Rgb(200, 180, 120),
             danger: Color::Rgb(220, 120, 120),
             accent: Color::Rgb(180, 140, 200),
+            roles: RoleColors::default(),
         }
     }
 
@@ -612,6 +744,74 @@ impl ThemePalette {
             warning: Color::Rgb(140, 105, 30),
             danger: Color::Rgb(180, 50, 50),
             accent: Color::Rgb(115, 75, 145),
+            roles: RoleColors::default(),
+        }
+    }
+
+    // ── No Color ────────────────────────────────────────────────
+
+    fn no_color() -> Self {
+        Self {
+            current_row_bg: Color::Reset,
+            highlight_row_bg: Color::Reset,
+            current_worktree_fg: Color::Reset,
+            dimmed: Color::Reset,
+            text: Color::Reset,
+            border: Color::Reset,
+            help_border: Color::Reset,
+            help_muted: Color::Reset,
+            header: Color::Reset,
+            keycap: Color::Reset,
+            info: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            danger: Color::Reset,
+            accent: Color::Reset,
+            roles: RoleColors::default(),
+        }
+    }
+
+    // ── High Contrast ───────────────────────────────────────────
+
+    fn high_contrast_dark() -> Self {
+        Self {
+            current_row_bg: Color::Rgb(38, 38, 38),
+            highlight_row_bg: Color::Rgb(0, 70, 140),
+            current_worktree_fg: Color::Rgb(255, 255, 255),
+            dimmed: Color::Rgb(170, 170, 170),
+            text: Color::Rgb(255, 255, 255),
+            border: Color::Rgb(200, 200, 200),
+            help_border: Color::Rgb(255, 255, 255),
+            help_muted: Color::Rgb(200, 200, 200),
+            header: Color::Rgb(255, 255, 0),
+            keycap: Color::Rgb(255, 255, 0),
+            info: Color::Rgb(0, 255, 255),
+            success: Color::Rgb(0, 255, 0),
+            warning: Color::Rgb(255, 200, 0),
+            danger: Color::Rgb(255, 80, 80),
+            accent: Color::Rgb(255, 120, 255),
+            roles: RoleColors::default(),
+        }
+    }
+
+    fn high_contrast_light() -> Self {
+        Self {
+            current_row_bg: Color::Rgb(225, 225, 225),
+            highlight_row_bg: Color::Rgb(170, 210, 255),
+            current_worktree_fg: Color::Rgb(0, 0, 0),
+            dimmed: Color::Rgb(80, 80, 80),
+            text: Color::Rgb(0, 0, 0),
+            border: Color::Rgb(40, 40, 40),
+            help_border: Color::Rgb(0, 0, 0),
+            help_muted: Color::Rgb(60, 60, 60),
+            header: Color::Rgb(0, 0, 160),
+            keycap: Color::Rgb(120, 60, 0),
+            info: Color::Rgb(0, 90, 140),
+            success: Color::Rgb(0, 110, 0),
+            warning: Color::Rgb(140, 80, 0),
+            danger: Color::Rgb(180, 0, 0),
+            accent: Color::Rgb(120, 0, 160),
+            roles: RoleColors::default(),
         }
     }
 }
@@ -675,6 +875,34 @@ mod tests {
         assert_eq!(palette.current_row_bg, Color::Rgb(32, 22, 18));
     }
 
+    #[test]
+    fn apply_custom_roles_fall_back_to_semantic_colors() {
+        let mut palette = ThemePalette::for_scheme(ThemeScheme::Default, ThemeMode::Dark);
+        assert_eq!(palette.waiting(), palette.accent);
+        assert_eq!(palette.check_failure(), palette.danger);
+        let custom = CustomThemeColors {
+            waiting: Some("yellow".to_string()),
+            stale: Some("#444444".to_string()),
+            ..Default::default()
+        };
+        palette.apply_custom(&custom);
+        assert_eq!(palette.waiting(), Color::Yellow);
+        assert_eq!(palette.stale(), Color::Rgb(68, 68, 68));
+        assert_eq!(palette.done(), palette.success);
+    }
+
+    #[test]
+    fn no_color_scheme_is_plain() {
+        let palette = ThemePalette::for_scheme(ThemeScheme::NoColor, ThemeMode::Light);
+        assert_eq!(palette.waiting(), Color::Reset);
+        assert_eq!(paint("done", palette.done()), "done");
+        assert_eq!(
+            palette.highlight_style(),
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+        assert_eq!(paint("x", Color::Rgb(1, 2, 3)), "\x1b[38;2;1;2;3mx\x1b[0m");
+    }
+
     #[test]
     fn from_config_without_custom() {
         let config = ThemeConfig {
@@ -135,6 +135,37 @@ pub fn format_compact_age(secs: u64) -> String {
     }
 }
 
+/// Parse a compact duration such as "30s", "15m", "1h", "2d" or "1w".
+/// A bare number is seconds.
+pub fn parse_compact_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: u64 = number.parse().map_err(|_| {
+        anyhow!(
+            "Invalid duration '{}', expected e.g. 30s, 15m, 1h, 2d",
+            input
+        )
+    })?;
+    let unit_secs = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        "w" => 7 * 86400,
+        _ => {
+            return Err(anyhow!(
+                "Invalid duration unit '{}' in '{}', expected s, m, h, d or w",
+                unit,
+                input
+            ));
+        }
+    };
+    Ok(Duration::from_secs(number * unit_secs))
+}
+
 /// Format a duration as a human-readable elapsed time string.
 /// Used by `status` and `wait` commands.
 pub fn format_elapsed_secs(secs: u64) -> String {
@@ -170,10 +201,171 @@ pub fn format_elapsed_duration(d: Duration) -> String {
     }
 }
 
+/// UTC calendar date of a Unix timestamp, as `YYYY-MM-DD`.
+pub fn format_utc_date(secs: u64) -> String {
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Total on-disk size of a directory tree in bytes.
+///
+/// Includes hidden and gitignored files (build artifacts, node_modules), walks
+/// in parallel, and does not follow symlinks. Directories in `skip` (e.g.
+/// worktrees nested inside the main worktree) are not descended into.
+pub fn disk_usage(root: &Path, skip: &[PathBuf]) -> u64 {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    let total = Arc::new(AtomicU64::new(0));
+    let skip = Arc::new(skip.to_vec());
+
+    let mut builder = ignore::WalkBuilder::new(root);
+    builder.standard_filters(false).follow_links(false);
+    builder.filter_entry(move |entry| !skip.iter().any(|p| p == entry.path()));
+
+    builder.build_parallel().run(|| {
+        let total = Arc::clone(&total);
+        Box::new(move |entry| {
+            if let Ok(entry) = entry
+                && let Ok(meta) = entry.metadata()
+                && !meta.is_dir()
+            {
+                total.fetch_add(allocated_size(&meta), Ordering::Relaxed);
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    total.load(Ordering::Relaxed)
+}
+
+/// Bytes actually allocated on disk (sparse files and small files differ from `len()`).
+#[cfg(unix)]
+fn allocated_size(meta: &std::fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn allocated_size(meta: &std::fs::Metadata) -> u64 {
+    meta.len()
+}
+
+/// Map `f` over `items` on up to `workers` threads, returning results in
+/// input order. For fanning out per-worktree git calls.
+pub fn parallel_map<T, R, F>(items: Vec<T>, workers: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    let len = items.len();
+    let workers = workers.clamp(1, len.max(1));
+    if workers == 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate().collect::<VecDeque<_>>());
+    let results = Mutex::new(Vec::with_capacity(len));
+    std::thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| {
+                loop {
+                    let Some((index, item)) = queue.lock().unwrap().pop_front() else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap().push((index, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Format a byte count as a compact human-readable size (e.g., "512B", "4.0K", "1.2G").
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if size >= 100.0 {
+        format!("{:.0}{}", size, UNITS[unit])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parallel_map_keeps_input_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let doubled = parallel_map(items, 8, |n| {
+            std::thread::sleep(Duration::from_millis((50 - n) % 7));
+            n * 2
+        });
+        assert_eq!(doubled, (0..50).map(|n| n * 2).collect::<Vec<_>>());
+        assert!(parallel_map(Vec::<u64>::new(), 8, |n| n).is_empty());
+    }
+
+    #[test]
+    fn format_utc_date_days() {
+        assert_eq!(format_utc_date(0), "1970-01-01");
+        assert_eq!(format_utc_date(951_782_400), "2000-02-29");
+        assert_eq!(format_utc_date(1_791_158_399), "2026-10-04");
+    }
+
+    #[test]
+    fn format_size_units() {
+        assert_eq!(format_size(0), "0B");
+        assert_eq!(format_size(1023), "1023B");
+        assert_eq!(format_size(1024), "1.0K");
+        assert_eq!(format_size(1536), "1.5K");
+        assert_eq!(format_size(250 * 1024 * 1024), "250M");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024 / 2), "1.5G");
+    }
+
+    #[test]
+    fn disk_usage_includes_ignored_files_and_skips_nested() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target/artifact"), vec![1u8; 64 * 1024]).unwrap();
+        std::fs::create_dir_all(root.join("nested")).unwrap();
+        std::fs::write(root.join("nested/big"), vec![1u8; 256 * 1024]).unwrap();
+
+        let with_nested = disk_usage(root, &[]);
+        let without_nested = disk_usage(root, &[root.join("nested")]);
+
+        assert!(without_nested >= 64 * 1024);
+        assert!(with_nested >= without_nested + 256 * 1024);
+    }
+
     #[test]
     fn expand_worktree_dir_tilde_and_project() {
         let home = PathBuf::from("/home/alice");
@@ -327,6 +519,37 @@ mod tests {
         assert_eq!(format_compact_age(59), "<1m");
     }
 
+    #[test]
+    fn parse_compact_duration_units() {
+        assert_eq!(
+            parse_compact_duration("45").unwrap(),
+            Duration::from_secs(45)
+        );
+        assert_eq!(
+            parse_compact_duration("30s").unwrap(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            parse_compact_duration("15m").unwrap(),
+            Duration::from_secs(900)
+        );
+        assert_eq!(
+            parse_compact_duration("1h").unwrap(),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            parse_compact_duration("2d").unwrap(),
+            Duration::from_secs(172800)
+        );
+        assert_eq!(
+            parse_compact_duration("1w").unwrap(),
+            Duration::from_secs(604800)
+        );
+        assert!(parse_compact_duration("h").is_err());
+        assert!(parse_compact_duration("5y").is_err());
+        assert!(parse_compact_duration("").is_err());
+    }
+
     #[test]
     fn format_compact_age_minutes() {
         assert_eq!(format_compact_age(60), "1m");
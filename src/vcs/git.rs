@@ -0,0 +1,47 @@
+//! Git backend: worktrees and branches.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::{Vcs, VcsKind};
+use crate::git;
+
+/// Git worktree backend
+pub struct GitVcs;
+
+impl Vcs for GitVcs {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    fn kind(&self) -> VcsKind {
+        VcsKind::Git
+    }
+
+    fn list_workspaces_in(&self, workdir: Option<&Path>) -> Result<Vec<(PathBuf, String)>> {
+        git::list_worktrees_in(workdir)
+    }
+
+    fn create_workspace(
+        &self,
+        path: &Path,
+        branch: &str,
+        create_branch: bool,
+        base: Option<&str>,
+        track_upstream: bool,
+    ) -> Result<()> {
+        git::create_worktree(path, branch, create_branch, base, track_upstream)
+    }
+
+    fn has_uncommitted_changes(&self, path: &Path) -> Result<bool> {
+        git::has_uncommitted_changes(path)
+    }
+
+    fn prune_workspace(&self, _handle: &str, git_common_dir: &Path) -> Result<()> {
+        git::prune_worktrees_in(git_common_dir)
+    }
+
+    fn delete_branch(&self, branch: &str, force: bool, git_common_dir: &Path) -> Result<()> {
+        git::delete_branch_in(branch, force, git_common_dir)
+    }
+}
@@ -0,0 +1,350 @@
+//! Jujutsu backend: workspaces and bookmarks.
+//!
+//! Only colocated repositories are supported, since workmux keeps its
+//! per-worktree metadata and branch bases in git config. jj workspaces are not
+//! git worktrees, so each one records its path and bookmark under
+//! `workmux.worktree.<handle>.jj-path` / `.jj-bookmark`.
+
+use anyhow::{Context, Result, anyhow};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use super::{Vcs, VcsKind};
+use crate::cmd::Cmd;
+use crate::git;
+
+/// jj workspace backend
+pub struct JjVcs;
+
+impl Vcs for JjVcs {
+    fn name(&self) -> &'static str {
+        "jj"
+    }
+
+    fn kind(&self) -> VcsKind {
+        VcsKind::Jj
+    }
+
+    fn list_workspaces_in(&self, workdir: Option<&Path>) -> Result<Vec<(PathBuf, String)>> {
+        // Git worktrees created before switching to jj keep working.
+        let mut workspaces = git::list_worktrees_in(workdir)?;
+
+        // jj leaves git's HEAD detached in the default workspace, so show the
+        // main branch there instead.
+        if let Some((_, branch)) = workspaces.first_mut()
+            && branch == "(detached)"
+            && let Ok(main) = git::get_default_branch_in(workdir)
+        {
+            *branch = main;
+        }
+
+        workspaces.extend(list_jj_workspaces_in(workdir));
+        Ok(workspaces)
+    }
+
+    fn create_workspace(
+        &self,
+        path: &Path,
+        branch: &str,
+        create_branch: bool,
+        base: Option<&str>,
+        _track_upstream: bool,
+    ) -> Result<()> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| anyhow!("Invalid workspace path"))?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow!("Invalid workspace path: no directory name"))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory '{}'", parent.display()))?;
+        }
+
+        // Resolve through git so remote refs like `origin/main` work; jj would
+        // spell that `main@origin`.
+        let start = if create_branch {
+            base.unwrap_or("HEAD")
+        } else {
+            branch
+        };
+        let revision = resolve_commit(start)?;
+
+        Cmd::new("jj")
+            .args(&[
+                "workspace",
+                "add",
+                "--name",
+                name,
+                "-r",
+                &revision,
+                path_str,
+            ])
+            .run()
+            .context("Failed to create jj workspace")?;
+
+        if create_branch {
+            let working_copy = format!("{}@", name);
+            Cmd::new("jj")
+                .args(&["bookmark", "create", branch, "-r", &working_copy])
+                .run()
+                .with_context(|| format!("Failed to create bookmark '{}'", branch))?;
+        }
+
+        git::set_worktree_meta(name, "jj-path", path_str)?;
+        git::set_worktree_meta(name, "jj-bookmark", branch)?;
+        debug!(workspace = name, bookmark = branch, "jj:workspace created");
+        Ok(())
+    }
+
+    /// jj records working-copy edits as a change once it snapshots the
+    /// workspace, so take a snapshot and report nothing to lose.
+    fn has_uncommitted_changes(&self, path: &Path) -> Result<bool> {
+        // Git worktrees in a jj repo still hold real uncommitted changes
+        if path.join(".git").exists() {
+            return git::has_uncommitted_changes(path);
+        }
+        Cmd::new("jj")
+            .workdir(path)
+            .arg("status")
+            .run()
+            .context("Failed to snapshot jj workspace")?;
+        Ok(false)
+    }
+
+    fn prune_workspace(&self, handle: &str, git_common_dir: &Path) -> Result<()> {
+        git::prune_worktrees_in(git_common_dir)?;
+        if is_jj_workspace(handle) {
+            Cmd::new("jj")
+                .workdir(repo_root(git_common_dir))
+                .args(&["workspace", "forget", handle])
+                .run()
+                .with_context(|| format!("Failed to forget jj workspace '{}'", handle))?;
+        }
+        Ok(())
+    }
+
+    /// jj has no notion of an unmerged bookmark, so `force` is ignored.
+    /// Callers check merge status up front.
+    fn delete_branch(&self, branch: &str, _force: bool, git_common_dir: &Path) -> Result<()> {
+        Cmd::new("jj")
+            .workdir(repo_root(git_common_dir))
+            .args(&["bookmark", "delete", branch])
+            .run()
+            .with_context(|| format!("Failed to delete bookmark '{}'", branch))?;
+        Ok(())
+    }
+}
+
+/// Whether `handle` was created as a jj workspace (rather than a git worktree
+/// left over from before the repo used jj).
+pub fn is_jj_workspace(handle: &str) -> bool {
+    git::get_worktree_meta(handle, "jj-path").is_some()
+}
+
+/// The workspace root of a colocated repo is the parent of its `.git` dir.
+fn repo_root(git_common_dir: &Path) -> &Path {
+    git_common_dir.parent().unwrap_or(git_common_dir)
+}
+
+fn resolve_commit(rev: &str) -> Result<String> {
+    let spec = format!("{}^{{commit}}", rev);
+    Cmd::new("git")
+        .args(&["rev-parse", "--verify", "--quiet", &spec])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Could not resolve '{}' to a commit", rev))
+}
+
+/// Quote a bookmark name so jj parses it as a single revset symbol.
+fn revset_symbol(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn list_jj_workspaces_in(workdir: Option<&Path>) -> Vec<(PathBuf, String)> {
+    let cmd = Cmd::new("git").args(&[
+        "config",
+        "--local",
+        "--get-regexp",
+        r"^workmux\.worktree\..*\.jj-(path|bookmark)$",
+    ]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    parse_jj_workspace_meta(&cmd.run_and_capture_stdout().unwrap_or_default())
+}
+
+/// Parse `git config --get-regexp` output into (path, bookmark) pairs.
+fn parse_jj_workspace_meta(output: &str) -> Vec<(PathBuf, String)> {
+    let mut entries: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(rest) = key.strip_prefix("workmux.worktree.") else {
+            continue;
+        };
+        if let Some(handle) = rest.strip_suffix(".jj-path") {
+            entries.entry(handle).or_default().0 = Some(value.trim());
+        } else if let Some(handle) = rest.strip_suffix(".jj-bookmark") {
+            entries.entry(handle).or_default().1 = Some(value.trim());
+        }
+    }
+    entries
+        .into_values()
+        .filter_map(|(path, bookmark)| Some((PathBuf::from(path?), bookmark?.to_string())))
+        .collect()
+}
+
+fn is_true(output: &str) -> bool {
+    output.trim() == "true"
+}
+
+/// Point `bookmark` at the latest change in workspace `handle`.
+///
+/// Runs inside the workspace so jj snapshots its working copy first. An empty
+/// working-copy change (the usual state after `jj commit`) is skipped.
+pub fn advance_bookmark(workspace_path: &Path, handle: &str, bookmark: &str) -> Result<()> {
+    let working_copy = format!("{}@", handle);
+    let empty = Cmd::new("jj")
+        .workdir(workspace_path)
+        .args(&["log", "--no-graph", "-r", &working_copy, "-T", "empty"])
+        .run_and_capture_stdout()
+        .context("Failed to inspect jj workspace")?;
+    let revision = if is_true(&empty) {
+        format!("{}-", working_copy)
+    } else {
+        working_copy
+    };
+    Cmd::new("jj")
+        .workdir(workspace_path)
+        .args(&["bookmark", "set", bookmark, "-r", &revision])
+        .run()
+        .with_context(|| format!("Failed to move bookmark '{}'", bookmark))?;
+    Ok(())
+}
+
+/// Undo the last jj operation if `revset` contains conflicted changes.
+/// Returns whether it did.
+fn undo_if_conflicted(repo_root: &Path, revset: &str) -> Result<bool> {
+    let conflicted = format!("conflicts() & ({})", revset);
+    let output = Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&[
+            "log",
+            "--no-graph",
+            "-r",
+            &conflicted,
+            "-T",
+            "change_id ++ \"\\n\"",
+        ])
+        .run_and_capture_stdout()
+        .context("Failed to check for conflicts")?;
+    if output.trim().is_empty() {
+        return Ok(false);
+    }
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .arg("undo")
+        .run()
+        .context("Failed to undo conflicted jj operation")?;
+    Ok(true)
+}
+
+/// Create a merge change of `target` and `bookmark`, then move `target` to it.
+///
+/// Returns false without touching `target` if the merge has conflicts.
+pub fn merge_bookmark(repo_root: &Path, bookmark: &str, target: &str) -> Result<bool> {
+    let message = format!("Merge bookmark '{}' into '{}'", bookmark, target);
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&[
+            "new",
+            &revset_symbol(target),
+            &revset_symbol(bookmark),
+            "-m",
+            &message,
+        ])
+        .run()
+        .context("Failed to create merge change")?;
+
+    if undo_if_conflicted(repo_root, "@")? {
+        return Ok(false);
+    }
+
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&["bookmark", "set", target, "-r", "@"])
+        .run()
+        .with_context(|| format!("Failed to move bookmark '{}'", target))?;
+    // Leave the merge change alone and keep working on top of it
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .arg("new")
+        .run()
+        .context("Failed to create new working-copy change")?;
+    info!(bookmark, target, "jj:merge complete");
+    Ok(true)
+}
+
+/// Rebase `bookmark` onto `target`, then fast-forward `target` to it.
+///
+/// Returns false and restores the previous state if the rebase conflicts.
+pub fn rebase_bookmark(repo_root: &Path, bookmark: &str, target: &str) -> Result<bool> {
+    let target_sym = revset_symbol(target);
+    let bookmark_sym = revset_symbol(bookmark);
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&["rebase", "-b", &bookmark_sym, "-d", &target_sym])
+        .run()
+        .with_context(|| format!("Failed to rebase onto '{}'", target))?;
+
+    let rebased = format!("{}::{}", target_sym, bookmark_sym);
+    if undo_if_conflicted(repo_root, &rebased)? {
+        return Ok(false);
+    }
+
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&["bookmark", "set", target, "-r", &bookmark_sym])
+        .run()
+        .with_context(|| format!("Failed to move bookmark '{}'", target))?;
+    Cmd::new("jj")
+        .workdir(repo_root)
+        .args(&["new", &target_sym])
+        .run()
+        .context("Failed to create new working-copy change")?;
+    info!(bookmark, target, "jj:rebase complete");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_jj_workspace_meta_pairs_path_and_bookmark() {
+        let output = "workmux.worktree.feature.mode window\n\
+                      workmux.worktree.feature.jj-path /wt/feature\n\
+                      workmux.worktree.feature.jj-bookmark feature/login\n\
+                      workmux.worktree.half.jj-path /wt/half\n\
+                      workmux.worktree.api.jj-bookmark api\n\
+                      workmux.worktree.api.jj-path /wt/api\n";
+        assert_eq!(
+            parse_jj_workspace_meta(output),
+            vec![
+                (PathBuf::from("/wt/api"), "api".to_string()),
+                (PathBuf::from("/wt/feature"), "feature/login".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn revset_symbol_quotes_names() {
+        assert_eq!(revset_symbol("feature/x"), "\"feature/x\"");
+        assert_eq!(revset_symbol("a\"b"), "\"a\\\"b\"");
+    }
+}
@@ -0,0 +1,172 @@
+//! Version control abstraction layer.
+//!
+//! workmux manages git worktrees by default. Colocated Jujutsu repositories
+//! (a `.jj` directory alongside `.git`) get jj workspaces and bookmarks
+//! instead, while branch metadata keeps living in git config.
+
+pub mod git;
+pub mod jj;
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub use git::GitVcs;
+pub use jj::JjVcs;
+
+use crate::git::WorktreeNotFound;
+
+/// Supported version control backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcsKind {
+    Git,
+    Jj,
+}
+
+/// Main trait for version control backends.
+///
+/// Implementations must be Send + Sync to allow sharing via Arc<dyn Vcs>.
+pub trait Vcs: Send + Sync {
+    /// Returns the name of this backend (e.g., "git", "jj")
+    fn name(&self) -> &'static str;
+
+    /// Returns the kind of this backend
+    fn kind(&self) -> VcsKind;
+
+    /// List all worktrees/workspaces as (path, branch) pairs, optionally in a
+    /// specific workdir. The main worktree is always first.
+    fn list_workspaces_in(&self, workdir: Option<&Path>) -> Result<Vec<(PathBuf, String)>>;
+
+    /// List all worktrees/workspaces in the current repository
+    fn list_workspaces(&self) -> Result<Vec<(PathBuf, String)>> {
+        self.list_workspaces_in(None)
+    }
+
+    /// Create a worktree/workspace at `path` with `branch` checked out.
+    ///
+    /// The directory name of `path` is the worktree handle.
+    fn create_workspace(
+        &self,
+        path: &Path,
+        branch: &str,
+        create_branch: bool,
+        base: Option<&str>,
+        track_upstream: bool,
+    ) -> Result<()>;
+
+    /// Check whether a worktree has changes that removing it would lose.
+    fn has_uncommitted_changes(&self, path: &Path) -> Result<bool>;
+
+    /// Drop the VCS's bookkeeping for a worktree whose directory is gone.
+    fn prune_workspace(&self, handle: &str, git_common_dir: &Path) -> Result<()>;
+
+    /// Delete a local branch (bookmark for jj).
+    fn delete_branch(&self, branch: &str, force: bool, git_common_dir: &Path) -> Result<()>;
+}
+
+/// Detect the version control backend for the current repository.
+///
+/// Checks the main worktree so linked git worktrees of a jj repo see its jj
+/// workspaces too. Falls back to the current directory, which is the only
+/// option inside a secondary jj workspace (it has no `.git`).
+pub fn detect_vcs() -> VcsKind {
+    if let Ok(root) = crate::git::get_main_worktree_root() {
+        return detect_vcs_in(&root);
+    }
+    match std::env::current_dir() {
+        Ok(dir) => detect_vcs_in(&dir),
+        Err(_) => VcsKind::Git,
+    }
+}
+
+/// Detect the version control backend for `dir`.
+///
+/// The nearest repository root decides, so a git worktree nested inside a jj
+/// repository is still treated as git.
+pub fn detect_vcs_in(dir: &Path) -> VcsKind {
+    for ancestor in dir.ancestors() {
+        if ancestor.join(".jj").is_dir() {
+            return VcsKind::Jj;
+        }
+        if ancestor.join(".git").exists() {
+            return VcsKind::Git;
+        }
+    }
+    VcsKind::Git
+}
+
+/// Create a VCS backend instance
+pub fn create_vcs(kind: VcsKind) -> Arc<dyn Vcs> {
+    match kind {
+        VcsKind::Git => Arc::new(GitVcs),
+        VcsKind::Jj => Arc::new(JjVcs),
+    }
+}
+
+/// List all worktrees/workspaces using the detected backend
+pub fn list_workspaces() -> Result<Vec<(PathBuf, String)>> {
+    create_vcs(detect_vcs()).list_workspaces()
+}
+
+/// Find a worktree/workspace by handle (directory name) or branch name.
+/// Tries handle first, then falls back to branch lookup.
+pub fn find_workspace(name: &str) -> Result<(PathBuf, String)> {
+    let workspaces = list_workspaces()?;
+    match_workspace(workspaces, name).ok_or_else(|| WorktreeNotFound(name.to_string()).into())
+}
+
+fn match_workspace(workspaces: Vec<(PathBuf, String)>, name: &str) -> Option<(PathBuf, String)> {
+    if let Some(found) = workspaces.iter().find(|(path, _)| {
+        path.file_name()
+            .is_some_and(|dir_name| dir_name.to_string_lossy() == name)
+    }) {
+        return Some(found.clone());
+    }
+    workspaces.into_iter().find(|(_, branch)| branch == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_vcs_prefers_nearest_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        assert_eq!(detect_vcs_in(root), VcsKind::Git);
+
+        std::fs::create_dir(root.join(".jj")).unwrap();
+        let nested = root.join("src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(detect_vcs_in(&nested), VcsKind::Jj);
+
+        // A git worktree inside the jj repo has a `.git` file and no `.jj`
+        let worktree = root.join(".worktrees/feature");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            "gitdir: ../../.git/worktrees/feature",
+        )
+        .unwrap();
+        assert_eq!(detect_vcs_in(&worktree), VcsKind::Git);
+    }
+
+    #[test]
+    fn match_workspace_prefers_handle_over_branch() {
+        let workspaces = vec![
+            (PathBuf::from("/repo"), "main".to_string()),
+            (PathBuf::from("/wt/feature"), "other".to_string()),
+            (PathBuf::from("/wt/other-dir"), "feature".to_string()),
+        ];
+        assert_eq!(
+            match_workspace(workspaces.clone(), "feature").unwrap().0,
+            PathBuf::from("/wt/feature")
+        );
+        assert_eq!(
+            match_workspace(workspaces.clone(), "main").unwrap().0,
+            PathBuf::from("/repo")
+        );
+        assert!(match_workspace(workspaces, "missing").is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! `limits`: caps on concurrently running agents, checked before `add` and
+//! `open` start another one.
+//!
+//! Agents are counted as open workmux windows (sessions in session mode),
+//! since each one runs an agent and appears as soon as it's created. `add`
+//! and `open` check once up front, so a full request fails (or queues) before
+//! creating anything, then again under a lock right before the window is
+//! created, so parallel calls can't all see the same free slot. The lock
+//! covers only the window and pane creation, not file operations or hooks.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use tracing::info;
+
+use crate::config::WhenFull;
+use crate::multiplexer::LivePaneInfo;
+use crate::state::lock::StateLock;
+
+use super::context::WorkflowContext;
+
+/// How often a queued request checks for a free slot
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Name of the lock file held from the count until the window exists
+const SLOT_LOCK: &str = "agent-limits";
+
+/// Return once another agent may start: right away when under the limits,
+/// otherwise an error, or after waiting with `when_full: queue`.
+///
+/// The returned lock must be held until the agent's window has been created,
+/// so other workmux processes count it; drop it right away for an up-front
+/// check. None when no limits are set.
+pub fn wait_for_slot(context: &WorkflowContext) -> Result<Option<StateLock>> {
+    let limits = &context.config.limits;
+    if !limits.is_set() {
+        return Ok(None);
+    }
+
+    let mut queued = false;
+    loop {
+        let lock = StateLock::acquire(SLOT_LOCK)?;
+        let panes = context.mux.get_all_live_pane_info()?;
+        let worktrees: Vec<PathBuf> = context
+            .vcs
+            .list_workspaces()?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        let (total, in_repo) = count_agents(panes.values(), &context.prefix, &worktrees);
+        let Some(reached) = limits.reached(total, in_repo) else {
+            if queued {
+                info!(total, in_repo, "limits:slot free");
+            }
+            return Ok(Some(lock));
+        };
+        // Let others count (and windows close) while this request waits
+        drop(lock);
+
+        match limits.when_full() {
+            WhenFull::Refuse => bail!(
+                "Agent limit reached: {}.\n\
+                 Close an agent first, or set limits.when_full: queue to wait for one to finish.",
+                reached
+            ),
+            WhenFull::Queue => {
+                if !queued {
+                    info!(total, in_repo, "limits:queued");
+                    eprintln!(
+                        "workmux: agent limit reached: {}. Waiting for a slot (Ctrl-C to cancel)...",
+                        reached
+                    );
+                    queued = true;
+                }
+                thread::sleep(QUEUE_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Workmux windows (or sessions) with live panes, in total and in the given
+/// worktrees.
+fn count_agents<'a>(
+    panes: impl Iterator<Item = &'a LivePaneInfo>,
+    prefix: &str,
+    worktrees: &[PathBuf],
+) -> (usize, usize) {
+    let mut all = HashSet::new();
+    let mut in_repo = HashSet::new();
+    for pane in panes {
+        let session = pane.session.as_deref().unwrap_or_default();
+        let window = pane.window.as_deref().unwrap_or_default();
+        let target = if session.starts_with(prefix) {
+            session.to_string()
+        } else if window.starts_with(prefix) {
+            format!("{}:{}", session, window)
+        } else {
+            continue;
+        };
+        if worktrees.iter().any(|w| pane.working_dir.starts_with(w)) {
+            in_repo.insert(target.clone());
+        }
+        all.insert(target);
+    }
+    (all.len(), in_repo.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(session: &str, window: &str, dir: &str) -> LivePaneInfo {
+        LivePaneInfo {
+            pid: None,
+            current_command: None,
+            working_dir: PathBuf::from(dir),
+            title: None,
+            session: Some(session.to_string()),
+            window: Some(window.to_string()),
+        }
+    }
+
+    #[test]
+    fn counts_workmux_targets_once() {
+        let panes = [
+            // Two panes in one window
+            pane("main", "wm-auth", "/src/app__worktrees/auth"),
+            pane("main", "wm-auth", "/src/app__worktrees/auth/web"),
+            pane("main", "wm-other", "/src/lib__worktrees/other"),
+            // A session-mode worktree with two windows
+            pane("wm-api", "agent", "/src/app__worktrees/api"),
+            pane("wm-api", "tests", "/src/app__worktrees/api"),
+            // Not workmux
+            pane("main", "zsh", "/src/app"),
+        ];
+        let worktrees = [
+            PathBuf::from("/src/app"),
+            PathBuf::from("/src/app__worktrees/auth"),
+            PathBuf::from("/src/app__worktrees/api"),
+        ];
+        assert_eq!(count_agents(panes.iter(), "wm-", &worktrees), (3, 2));
+    }
+}
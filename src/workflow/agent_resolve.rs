@@ -7,6 +7,8 @@ use crate::multiplexer::{AgentPane, Multiplexer};
 use crate::state::StateStore;
 use crate::util::canon_or_self;
 
+use super::types::AgentNotFound;
+
 /// Parsed agent target selector.
 enum AgentSelector {
     /// Plain name, resolved locally first then globally.
@@ -155,10 +157,14 @@ fn resolve_global_agents(
     }
 
     match by_root.len() {
-        0 => Err(anyhow!(
-            "No agent found matching '{}'",
-            format_selector(handle, project)
-        )),
+        0 => Err(AgentNotFound {
+            message: format!(
+                "No agent found matching '{}'",
+                format_selector(handle, project)
+            ),
+            handle: handle.to_string(),
+        }
+        .into()),
         1 => {
             let (root, agents) = by_root.into_iter().next().unwrap();
             Ok((root, agents.into_iter().cloned().collect()))
@@ -194,10 +200,13 @@ fn format_selector(handle: &str, project: Option<&str>) -> String {
 /// Returns an error if no agent is running in the worktree.
 pub fn resolve_worktree_agent(name: &str, mux: &dyn Multiplexer) -> Result<(PathBuf, AgentPane)> {
     let (path, agents) = resolve_worktree_agents(name, mux)?;
-    let agent = agents
-        .into_iter()
-        .next()
-        .ok_or_else(|| anyhow!("No agent running in worktree '{}'", name))?;
+    let agent = agents.into_iter().next().ok_or_else(|| AgentNotFound {
+        message: format!("No agent running in worktree '{}'", name),
+        handle: match AgentSelector::parse(name) {
+            AgentSelector::Qualified { handle, .. } => handle,
+            AgentSelector::Local(handle) => handle,
+        },
+    })?;
     Ok((path, agent))
 }
 
@@ -4,10 +4,11 @@ use std::path::Path;
 use std::time::SystemTime;
 use std::{thread, time::Duration};
 
-use crate::config::MuxMode;
+use crate::config::{GitMaintenance, MuxMode};
 use crate::multiplexer::{Multiplexer, util::prefixed};
 use crate::shell::shell_quote;
-use crate::{cmd, git};
+use crate::state::StateStore;
+use crate::{cmd, git, vcs};
 use tracing::{debug, info, warn};
 
 // Re-export for use by other modules in the workflow
@@ -310,12 +311,17 @@ pub fn cleanup(
 
         // 3. Prune worktrees to clean up git's metadata.
         // Git will see the original path as missing since we renamed it.
-        git::prune_worktrees_in(&context.git_common_dir).context("Failed to prune worktrees")?;
-        debug!("cleanup:git worktrees pruned");
+        context
+            .vcs
+            .prune_workspace(handle, &context.git_common_dir)
+            .context("Failed to prune worktrees")?;
+        debug!(vcs = context.vcs.name(), "cleanup:worktrees pruned");
 
         // 4. Delete the local branch (unless keeping it).
         if !keep_branch {
-            git::delete_branch_in(branch_name, force, &context.git_common_dir)
+            context
+                .vcs
+                .delete_branch(branch_name, force, &context.git_common_dir)
                 .context("Failed to delete local branch")?;
             result.local_branch_deleted = true;
             info!(branch = branch_name, "cleanup:local branch deleted");
@@ -470,6 +476,9 @@ pub fn cleanup(
                 force,
                 git_common_dir: context.git_common_dir.clone(),
                 worktree_admin_dir,
+                push_target: git::get_push_target_in(branch_name, Some(&context.git_common_dir)),
+                run_maintenance: context.config.git.maintenance() == GitMaintenance::Auto,
+                jj_workspace: vcs::jj::is_jj_workspace(handle),
             });
             debug!(
                 worktree = %worktree_path.display(),
@@ -549,6 +558,22 @@ pub fn cleanup(
         warn!(handle = handle, error = %e, "cleanup:failed to remove worktree metadata");
     }
 
+    // The window is gone for good, so resurrect has nothing to rebuild
+    if let Ok(store) = StateStore::new() {
+        store.delete_layout(
+            context.mux.name(),
+            &context.mux.instance_id(),
+            worktree_path,
+        );
+    }
+
+    // Keep repos with heavy worktree churn healthy. The deferred script
+    // starts maintenance itself once the branch is actually gone.
+    if result.deferred_cleanup.is_none() && context.config.git.maintenance() == GitMaintenance::Auto
+    {
+        git::spawn_background_maintenance(&context.git_common_dir);
+    }
+
     Ok(result)
 }
 
@@ -557,10 +582,11 @@ pub fn cleanup(
 /// Generates a semicolon-separated sequence of shell commands that:
 /// 1. Renames the worktree directory to a trash path (frees the original path)
 /// 2. Removes any worktree lock (so prune can clean up the metadata)
-/// 3. Prunes git worktree metadata
-/// 4. Deletes the local branch (unless `keep_branch` is set)
+/// 3. Prunes git worktree metadata (and forgets the jj workspace)
+/// 4. Deletes the local branch or jj bookmark (unless `keep_branch` is set)
 /// 5. Removes workmux worktree metadata from git config
 /// 6. Deletes the trash directory
+/// 7. Starts background git maintenance (if enabled)
 ///
 /// The returned string starts with "; " so it can be appended to other commands.
 fn build_deferred_cleanup_script(dc: &DeferredCleanup) -> String {
@@ -580,14 +606,44 @@ fn build_deferred_cleanup_script(dc: &DeferredCleanup) -> String {
     }
     // 3. Prune git worktrees
     cmds.push(format!("git -C {} worktree prune >/dev/null 2>&1", git_dir));
+    let jj_repo = dc
+        .git_common_dir
+        .parent()
+        .map(|root| shell_quote(&root.to_string_lossy()));
+    if dc.jj_workspace
+        && let Some(ref repo) = jj_repo
+    {
+        cmds.push(format!(
+            "jj -R {} workspace forget {} >/dev/null 2>&1",
+            repo,
+            shell_quote(&dc.handle)
+        ));
+    }
     // 4. Delete branch (if not keeping)
     if !dc.keep_branch {
         let branch = shell_quote(&dc.branch_name);
-        let force_flag = if dc.force { "-D" } else { "-d" };
-        cmds.push(format!(
-            "git -C {} branch {} {} >/dev/null 2>&1",
-            git_dir, force_flag, branch
-        ));
+        if dc.jj_workspace
+            && let Some(ref repo) = jj_repo
+        {
+            cmds.push(format!(
+                "jj -R {} bookmark delete {} >/dev/null 2>&1",
+                repo, branch
+            ));
+        } else {
+            let force_flag = if dc.force { "-D" } else { "-d" };
+            cmds.push(format!(
+                "git -C {} branch {} {} >/dev/null 2>&1",
+                git_dir, force_flag, branch
+            ));
+        }
+        if let Some((ref remote, ref push_branch)) = dc.push_target {
+            cmds.push(format!(
+                "git -C {} config --local --fixed-value --unset-all {} {} >/dev/null 2>&1",
+                git_dir,
+                shell_quote(&format!("remote.{}.push", remote)),
+                shell_quote(&git::push_refspec(&dc.branch_name, push_branch))
+            ));
+        }
     }
     // 5. Remove worktree metadata from git config
     let handle = shell_quote(&dc.handle);
@@ -597,6 +653,13 @@ fn build_deferred_cleanup_script(dc: &DeferredCleanup) -> String {
     ));
     // 6. Delete trash
     cmds.push(format!("rm -rf {} >/dev/null 2>&1", trash));
+    // 7. Background git maintenance
+    if dc.run_maintenance {
+        cmds.push(format!(
+            "(git -C {} maintenance run --auto --quiet >/dev/null 2>&1 &)",
+            git_dir
+        ));
+    }
 
     format!("; {}", cmds.join("; "))
 }
@@ -815,6 +878,9 @@ mod tests {
             force,
             git_common_dir: PathBuf::from(git_dir),
             worktree_admin_dir: None,
+            push_target: None,
+            run_maintenance: false,
+            jj_workspace: false,
         }
     }
 
@@ -1048,4 +1114,74 @@ mod tests {
             "Should not have lock removal without admin dir: {script}"
         );
     }
+
+    #[test]
+    fn deferred_cleanup_script_removes_fork_push_refspec() {
+        let mut dc = make_deferred_cleanup(
+            "/repo/worktrees/alice-feature",
+            "/repo/worktrees/.trash",
+            "alice-feature",
+            "alice-feature",
+            "/repo/.git",
+            false,
+            false,
+        );
+        dc.push_target = Some(("fork-alice".to_string(), "feature".to_string()));
+
+        let script = build_deferred_cleanup_script(&dc);
+
+        assert!(script.contains(
+            "git -C /repo/.git config --local --fixed-value --unset-all remote.fork-alice.push \
+             'refs/heads/alice-feature:refs/heads/feature' >/dev/null 2>&1"
+        ));
+    }
+
+    #[test]
+    fn deferred_cleanup_script_starts_maintenance_last() {
+        let mut dc = make_deferred_cleanup(
+            "/repo/worktrees/feature",
+            "/repo/worktrees/.trash",
+            "feature",
+            "feature",
+            "/repo/.git",
+            false,
+            false,
+        );
+        let script = build_deferred_cleanup_script(&dc);
+        assert!(!script.contains("maintenance"));
+
+        dc.run_maintenance = true;
+        let script = build_deferred_cleanup_script(&dc);
+        assert!(
+            script.ends_with(
+                "; (git -C /repo/.git maintenance run --auto --quiet >/dev/null 2>&1 &)"
+            )
+        );
+    }
+
+    #[test]
+    fn deferred_cleanup_script_forgets_jj_workspace() {
+        let mut dc = make_deferred_cleanup(
+            "/repo/worktrees/feature",
+            "/repo/worktrees/.trash",
+            "feature",
+            "feature",
+            "/repo/.git",
+            false,
+            false,
+        );
+        dc.jj_workspace = true;
+
+        let script = build_deferred_cleanup_script(&dc);
+
+        assert!(script.contains("jj -R /repo workspace forget feature >/dev/null 2>&1"));
+        assert!(script.contains("jj -R /repo bookmark delete feature >/dev/null 2>&1"));
+        assert!(!script.contains("branch -d"));
+        let forget_pos = script.find("workspace forget").unwrap();
+        let delete_pos = script.find("bookmark delete").unwrap();
+        assert!(
+            forget_pos < delete_pos,
+            "forget should precede bookmark delete"
+        );
+    }
 }
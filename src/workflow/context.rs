@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::multiplexer::Multiplexer;
+use crate::vcs::{Vcs, create_vcs, detect_vcs_in};
 use crate::{config, git};
 use tracing::debug;
 
@@ -17,6 +18,7 @@ pub struct WorkflowContext {
     pub prefix: String,
     pub config: config::Config,
     pub mux: Arc<dyn Multiplexer>,
+    pub vcs: Arc<dyn Vcs>,
     /// Relative path from repo root to config directory.
     /// Empty if config is at repo root or using defaults.
     pub config_rel_dir: PathBuf,
@@ -37,7 +39,7 @@ impl WorkflowContext {
         config_location: Option<config::ConfigLocation>,
     ) -> Result<Self> {
         if !git::is_git_repo()? {
-            return Err(anyhow!("Not in a git repository"));
+            return Err(git::NotInRepo.into());
         }
 
         let main_worktree_root =
@@ -53,6 +55,7 @@ impl WorkflowContext {
         };
 
         let prefix = config.window_prefix().to_string();
+        let vcs = create_vcs(detect_vcs_in(&main_worktree_root));
 
         let (config_rel_dir, config_source_dir) = match config_location {
             Some(loc) => (loc.rel_dir, loc.config_dir),
@@ -65,6 +68,7 @@ impl WorkflowContext {
             main_branch = %main_branch,
             prefix = %prefix,
             backend = mux.name(),
+            vcs = vcs.name(),
             config_rel_dir = %config_rel_dir.display(),
             config_source_dir = %config_source_dir.display(),
             "workflow_context:created"
@@ -77,6 +81,7 @@ impl WorkflowContext {
             prefix,
             config,
             mux,
+            vcs,
             config_rel_dir,
             config_source_dir,
         })
@@ -3,19 +3,20 @@ use std::path::Path;
 
 use crate::config::MuxMode;
 use crate::multiplexer::MuxHandle;
+use crate::vcs::{Vcs, VcsKind};
 use crate::{git, spinner};
 use tracing::{debug, info, warn};
 
-/// Check if a path is registered as a git worktree.
+/// Check if a path is registered as a worktree (or jj workspace).
 /// Uses canonicalize() to handle symlinks, case sensitivity, and relative paths.
-fn is_registered_worktree(path: &Path) -> Result<bool> {
+fn is_registered_worktree(vcs: &dyn Vcs, path: &Path) -> Result<bool> {
     // Canonicalize the input path for reliable comparison
     let abs_path = match std::fs::canonicalize(path) {
         Ok(p) => p,
         Err(_) => return Ok(false), // Can't canonicalize = not a valid worktree
     };
 
-    let worktrees = git::list_worktrees()?;
+    let worktrees = vcs.list_workspaces()?;
     for (wt_path, _) in worktrees {
         // Canonicalize git's reported path as well
         if let Ok(abs_wt) = std::fs::canonicalize(&wt_path) {
@@ -43,6 +44,7 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         base_branch,
         remote_branch,
         pr_number,
+        is_fork,
         prompt,
         mut options,
         mode_override,
@@ -50,6 +52,7 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         is_explicit_name,
         prompt_file_only,
         fork_source,
+        host_commands,
     } = args;
 
     info!(
@@ -81,7 +84,10 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     context.ensure_mux_running()?;
 
     // Validate backend supports session mode before creating any git state
-    if options.mode == MuxMode::Session && context.mux.name() != "tmux" {
+    if options.mode == MuxMode::Session
+        && context.mux.name() != "tmux"
+        && !context.mux.is_headless()
+    {
         return Err(anyhow!(
             "Session mode (--mode session / --session) is only supported with tmux.\n\
              Current backend: {}. Use window mode instead.",
@@ -93,7 +99,11 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     let target = MuxHandle::new(context.mux.as_ref(), options.mode, &context.prefix, handle);
     let full_target_name = target.full_name();
     let mut target_exists = target.exists()?;
-    let worktree_exists = git::worktree_exists(branch_name)?;
+    let worktree_exists = context
+        .vcs
+        .list_workspaces()?
+        .iter()
+        .any(|(_, branch)| branch == branch_name);
 
     // Detect cross-repo collision: mux target exists but local worktree does not.
     // This means the target belongs to a different repository. Auto-suffix with the
@@ -164,6 +174,8 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
             open_if_exists: false,
             mode: options.mode,
             resume_mode: options.resume_mode.clone(),
+            layout: options.layout.clone(),
+            saved_layout: None,
         };
 
         // In file-only mode, pass the prompt so open can write it to the worktree
@@ -205,6 +217,13 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         ));
     }
 
+    // Fail (or queue) before creating anything; setup_environment checks
+    // again under the lock when it creates the window
+    let limits = options.run_pane_commands.then_some(context);
+    if let Some(context) = limits {
+        drop(super::agent_limits::wait_for_slot(context)?);
+    }
+
     // Auto-detect: create branch if it doesn't exist
     let branch_exists = git::branch_exists(branch_name)?;
     if branch_exists && remote_branch.is_some() {
@@ -316,7 +335,7 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         // Check if this is an orphan directory (exists on disk but not registered with git).
         // This can happen when cleanup renames a worktree but a background process (build tool,
         // file watcher, shell prompt) recreates the directory structure using stale $PWD.
-        if is_registered_worktree(&worktree_path)? {
+        if is_registered_worktree(context.vcs.as_ref(), &worktree_path)? {
             return Err(anyhow!(
                 "Worktree directory '{}' already exists and is registered with git.\n\
                  This may be from another branch with the same handle.\n\
@@ -367,17 +386,47 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     let _config_lock = git::GitConfigLock::acquire(&context.git_common_dir)
         .context("Failed to acquire git config lock")?;
 
-    git::create_worktree(
-        &worktree_path,
-        branch_name,
-        create_new,
-        base_branch_for_creation.as_deref(),
-        track_upstream,
-    )
-    .context("Failed to create git worktree")?;
+    context
+        .vcs
+        .create_workspace(
+            &worktree_path,
+            branch_name,
+            create_new,
+            base_branch_for_creation.as_deref(),
+            track_upstream,
+        )
+        .with_context(|| format!("Failed to create {} worktree", context.vcs.name()))?;
+
+    // For remote checkouts, an explicit base is the branch the work targets
+    // (e.g. a PR's base branch) rather than a starting point.
+    let mut stored_base = match (remote_branch, base_branch) {
+        (Some(_), Some(base)) => Some(base.to_string()),
+        _ => base_branch_for_creation.clone(),
+    };
+
+    // Fork checkouts use a triangular workflow: pull from the target branch on
+    // origin, push back to the branch on the fork.
+    if is_fork && let Some(remote_spec) = remote_branch {
+        let spec = git::parse_remote_branch_spec(remote_spec)?;
+        if spec.remote != "origin" && git::remote_exists("origin")? {
+            let upstream_branch = match base_branch {
+                Some(base) => base.to_string(),
+                None => git::get_default_branch()?,
+            };
+            git::configure_triangular_branch(
+                branch_name,
+                "origin",
+                &upstream_branch,
+                &spec.remote,
+                &spec.branch,
+            )
+            .context("Failed to configure push remote for fork branch")?;
+            stored_base = Some(upstream_branch);
+        }
+    }
 
     // Store the base branch in git config for future reference (used during removal checks)
-    if let Some(ref base) = base_branch_for_creation {
+    if let Some(ref base) = stored_base {
         git::set_branch_base(branch_name, base).with_context(|| {
             format!(
                 "Failed to store base branch '{}' for branch '{}'",
@@ -409,12 +458,45 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         "create:stored tmux mode in git config"
     );
 
+    // Remember the layout so reopening the worktree gets the same panes
+    if let Some(layout) = &options.layout {
+        git::set_worktree_meta(&current_handle, "layout", layout)
+            .context("Failed to store layout for the worktree")?;
+    }
+
+    // Record the prompt's host command limit for `sandbox run`, which loads
+    // its config afresh
+    if let Some(commands) = host_commands {
+        git::set_worktree_meta(
+            &current_handle,
+            "host-commands",
+            &serde_json::to_string(commands)?,
+        )
+        .context("Failed to store host commands for the worktree")?;
+    }
+
+    // Apply per-worktree git config (identity, hooks path, ...) so commits made
+    // in this worktree pick it up without touching shared config.
+    let worktree_config = context.config.git.worktree_config();
+    if !worktree_config.is_empty() {
+        if context.vcs.kind() == VcsKind::Jj {
+            warn!("git.config is not applied to jj workspaces");
+        } else {
+            git::set_worktree_config(&worktree_path, &context.git_common_dir, &worktree_config)
+                .context("Failed to apply git.config to the new worktree")?;
+            debug!(
+                count = worktree_config.len(),
+                "create:applied worktree git config"
+            );
+        }
+    }
+
     // Release the config lock before proceeding to non-git operations
     // (prompt files, tmux setup, hooks, etc.)
     drop(_config_lock);
 
     // Fork conversation into the new worktree if requested.
-    // Must happen after git::create_worktree() (path is finalized) and before
+    // Must happen after the worktree is created (path is finalized) and before
     // setup_environment() (which launches the agent with resume args).
     if let Some(fork) = fork_source {
         let session_id = fork
@@ -440,6 +522,11 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     } else {
         None
     };
+    if let Some(path) = &prompt_file_path
+        && let Ok(text) = std::fs::read_to_string(path)
+    {
+        crate::state::activity::record_prompt(&worktree_path, &text);
+    }
 
     // In file-only mode, the prompt file is written but not passed to setup.
     // This skips agent validation and prompt injection into pane commands.
@@ -489,6 +576,8 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         &options_with_prompt,
         agent,
         None,
+        None,
+        limits,
     )?;
     result.base_branch = base_branch_for_creation.clone();
     info!(
@@ -555,6 +644,7 @@ pub fn create_with_changes(
             base_branch: None,
             remote_branch: None,
             pr_number: None,
+            is_fork: false,
             prompt: None,
             options,
             mode_override: None,
@@ -562,6 +652,7 @@ pub fn create_with_changes(
             is_explicit_name: false,
             prompt_file_only: false,
             fork_source: None,
+            host_commands: None,
         },
     ) {
         Ok(result) => result,
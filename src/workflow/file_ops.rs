@@ -2,129 +2,91 @@ use anyhow::{Context, Result, anyhow};
 use std::fs;
 use std::path::{Component, Path};
 
-use crate::{config, git};
+use crate::config::FileMode;
+use crate::{config, git, platform};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use tracing::info;
 
+/// Gitignore-style patterns that file operations skip
+struct Excludes(Vec<Gitignore>);
+
+impl Excludes {
+    fn new(repo_root: &Path, patterns: &[&[String]]) -> Result<Self> {
+        let mut matchers = Vec::new();
+        for set in patterns.iter().filter(|set| !set.is_empty()) {
+            let mut builder = GitignoreBuilder::new(repo_root);
+            for pattern in *set {
+                builder
+                    .add_line(None, pattern)
+                    .with_context(|| format!("Invalid exclude pattern '{}'", pattern))?;
+            }
+            matchers.push(builder.build()?);
+        }
+        Ok(Self(matchers))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `path` must be inside the repo root.
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        self.0
+            .iter()
+            .any(|g| g.matched_path_or_any_parents(path, is_dir).is_ignore())
+    }
+}
+
 /// Performs copy and symlink operations from the repo root to the worktree
 pub fn handle_file_operations(
     repo_root: &Path,
     worktree_path: &Path,
     file_config: &config::FileConfig,
 ) -> Result<()> {
+    let rules = file_config.rules();
+    let global_exclude = file_config.exclude.as_deref().unwrap_or(&[]);
     tracing::debug!(
         repo = %repo_root.display(),
         worktree = %worktree_path.display(),
-        copy_patterns = file_config.copy.as_ref().map(|v| v.len()).unwrap_or(0),
-        symlink_patterns = file_config.symlink.as_ref().map(|v| v.len()).unwrap_or(0),
+        rules = rules.len(),
+        exclude_patterns = global_exclude.len(),
         "file_operations:start"
     );
 
     let mut copy_count = 0;
     let mut symlink_count = 0;
 
-    // Handle copies
-    if let Some(copy_patterns) = &file_config.copy {
-        for pattern in copy_patterns {
-            let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
-            for entry in glob::glob(&full_pattern)? {
-                let source_path = entry?;
-
-                validate_path_within_repo(&source_path, repo_root, "copy", pattern)?;
-
-                let relative_path = source_path.strip_prefix(repo_root)?;
-                let dest_path = worktree_path.join(relative_path);
-
-                if source_path.is_dir() {
-                    // Recursively copy directory contents
-                    copy_dir_recursive(&source_path, &dest_path).with_context(|| {
-                        format!(
-                            "Failed to copy directory {:?} to {:?}",
-                            source_path, dest_path
-                        )
-                    })?;
-                } else {
-                    // Copy single file
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directory for {:?}", dest_path)
-                        })?;
-                    }
-                    fs::copy(&source_path, &dest_path).with_context(|| {
-                        format!("Failed to copy file {:?} to {:?}", source_path, dest_path)
-                    })?;
-                }
-                copy_count += 1;
-            }
-        }
-    }
+    for rule in &rules {
+        let pattern = rule.path();
+        let op = match rule.mode() {
+            FileMode::Copy => "copy",
+            FileMode::Symlink => "symlink",
+        };
+        let excludes = Excludes::new(repo_root, &[global_exclude, rule.exclude()])?;
 
-    // Handle symlinks
-    if let Some(symlink_patterns) = &file_config.symlink {
-        for pattern in symlink_patterns {
-            let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
-            for entry in glob::glob(&full_pattern)? {
-                let source_path = entry?;
+        let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
+        for entry in glob::glob(&full_pattern)? {
+            let source_path = entry?;
 
-                validate_path_within_repo(&source_path, repo_root, "symlink", pattern)?;
+            validate_path_within_repo(&source_path, repo_root, op, pattern)?;
 
-                let relative_path = source_path.strip_prefix(repo_root)?;
-                let dest_path = worktree_path.join(relative_path);
+            if excludes.matches(&source_path, source_path.is_dir()) {
+                tracing::debug!(path = %source_path.display(), "file_operations:excluded");
+                continue;
+            }
 
-                if let Some(parent) = dest_path.parent() {
-                    fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directory for {:?}", dest_path)
-                    })?;
-                }
+            let relative_path = source_path.strip_prefix(repo_root)?;
+            let dest_path = worktree_path.join(relative_path);
 
-                // Critical: create a relative path for the symlink
-                let dest_parent = dest_path.parent().ok_or_else(|| {
-                    anyhow!(
-                        "Could not determine parent directory for destination path: {:?}",
-                        dest_path
-                    )
-                })?;
-
-                let relative_source = pathdiff::diff_paths(&source_path, dest_parent)
-                    .ok_or_else(|| anyhow!("Could not create relative path for symlink"))?;
-
-                // Remove existing file/symlink at destination to avoid errors
-                // IMPORTANT: Use symlink_metadata to avoid following symlinks
-                if let Ok(metadata) = dest_path.symlink_metadata() {
-                    if metadata.is_dir() {
-                        fs::remove_dir_all(&dest_path).with_context(|| {
-                            format!("Failed to remove existing directory at {:?}", &dest_path)
-                        })?;
-                    } else {
-                        // Handles both files and symlinks
-                        fs::remove_file(&dest_path).with_context(|| {
-                            format!("Failed to remove existing file/symlink at {:?}", &dest_path)
-                        })?;
-                    }
+            match rule.mode() {
+                FileMode::Copy => {
+                    copy_path(&source_path, &dest_path, &excludes)?;
+                    copy_count += 1;
                 }
-
-                #[cfg(unix)]
-                std::os::unix::fs::symlink(&relative_source, &dest_path).with_context(|| {
-                    format!(
-                        "Failed to create symlink from {:?} to {:?}",
-                        relative_source, dest_path
-                    )
-                })?;
-
-                #[cfg(windows)]
-                {
-                    if source_path.is_dir() {
-                        std::os::windows::fs::symlink_dir(&relative_source, &dest_path)
-                    } else {
-                        std::os::windows::fs::symlink_file(&relative_source, &dest_path)
-                    }
-                    .with_context(|| {
-                        format!(
-                            "Failed to create symlink from {:?} to {:?}",
-                            relative_source, dest_path
-                        )
-                    })?;
+                FileMode::Symlink => {
+                    symlink_path(&source_path, &dest_path)?;
+                    symlink_count += 1;
                 }
-                symlink_count += 1;
             }
         }
     }
@@ -140,6 +102,69 @@ pub fn handle_file_operations(
     Ok(())
 }
 
+fn copy_path(source_path: &Path, dest_path: &Path, excludes: &Excludes) -> Result<()> {
+    if source_path.is_dir() {
+        // Recursively copy directory contents
+        copy_dir(source_path, dest_path, excludes).with_context(|| {
+            format!(
+                "Failed to copy directory {:?} to {:?}",
+                source_path, dest_path
+            )
+        })?;
+    } else {
+        // Copy single file
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create parent directory for {:?}", dest_path)
+            })?;
+        }
+        copy_file(source_path, dest_path)
+            .with_context(|| format!("Failed to copy file {:?} to {:?}", source_path, dest_path))?;
+    }
+    Ok(())
+}
+
+fn symlink_path(source_path: &Path, dest_path: &Path) -> Result<()> {
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create parent directory for {:?}", dest_path))?;
+    }
+
+    // Critical: create a relative path for the symlink
+    let dest_parent = dest_path.parent().ok_or_else(|| {
+        anyhow!(
+            "Could not determine parent directory for destination path: {:?}",
+            dest_path
+        )
+    })?;
+
+    let relative_source = pathdiff::diff_paths(source_path, dest_parent)
+        .ok_or_else(|| anyhow!("Could not create relative path for symlink"))?;
+
+    // Remove existing file/symlink at destination to avoid errors
+    // IMPORTANT: Use symlink_metadata to avoid following symlinks
+    if let Ok(metadata) = dest_path.symlink_metadata() {
+        if metadata.is_dir() {
+            fs::remove_dir_all(dest_path).with_context(|| {
+                format!("Failed to remove existing directory at {:?}", dest_path)
+            })?;
+        } else {
+            // Handles both files and symlinks
+            fs::remove_file(dest_path).with_context(|| {
+                format!("Failed to remove existing file/symlink at {:?}", dest_path)
+            })?;
+        }
+    }
+
+    platform::symlink(&relative_source, dest_path).with_context(|| {
+        format!(
+            "Failed to create symlink from {:?} to {:?}",
+            relative_source, dest_path
+        )
+    })?;
+    Ok(())
+}
+
 /// Symlink CLAUDE.local.md from main worktree if it exists and is gitignored.
 pub fn symlink_claude_local_md(repo_root: &Path, worktree_path: &Path) -> Result<()> {
     let source = repo_root.join("CLAUDE.local.md");
@@ -160,13 +185,7 @@ pub fn symlink_claude_local_md(repo_root: &Path, worktree_path: &Path) -> Result
     let relative_source = pathdiff::diff_paths(&source, worktree_path)
         .ok_or_else(|| anyhow!("Could not create relative path for CLAUDE.local.md symlink"))?;
 
-    #[cfg(unix)]
-    std::os::unix::fs::symlink(&relative_source, &dest)
-        .context("Failed to symlink CLAUDE.local.md")?;
-
-    #[cfg(windows)]
-    std::os::windows::fs::symlink_file(&relative_source, &dest)
-        .context("Failed to symlink CLAUDE.local.md")?;
+    platform::symlink(&relative_source, &dest).context("Failed to symlink CLAUDE.local.md")?;
 
     info!("Symlinked CLAUDE.local.md to worktree");
     Ok(())
@@ -204,6 +223,12 @@ fn validate_path_within_repo(
 /// Symlinks are preserved rather than followed to avoid infinite recursion on symlink loops.
 /// Special files (sockets, FIFOs) are skipped to avoid blocking.
 pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+    copy_dir_excluding(src, dst, &|_, _| false)
+}
+
+/// Like [`copy_dir_recursive`], skipping source entries for which `skip`
+/// returns true (given the path and whether it is a directory).
+fn copy_dir_excluding(src: &Path, dst: &Path, skip: &dyn Fn(&Path, bool) -> bool) -> Result<()> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
         let entry = entry?;
@@ -211,6 +236,10 @@ pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
         let dst_path = dst.join(entry.file_name());
         let file_type = entry.file_type()?;
 
+        if skip(&src_path, file_type.is_dir()) {
+            continue;
+        }
+
         // Remove existing entry at destination to support overwrite
         if let Ok(meta) = dst_path.symlink_metadata() {
             if meta.is_dir() && file_type.is_dir() {
@@ -224,12 +253,149 @@ pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
 
         if file_type.is_symlink() {
             let target = fs::read_link(&src_path)?;
-            std::os::unix::fs::symlink(&target, &dst_path)?;
+            platform::symlink(&target, &dst_path)?;
         } else if file_type.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir_excluding(&src_path, &dst_path, skip)?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            copy_file(&src_path, &dst_path)?;
         }
     }
     Ok(())
 }
+
+/// Copy a directory, cloning the whole tree in one call where the filesystem
+/// supports it (APFS) and falling back to a recursive copy otherwise.
+fn copy_dir(src: &Path, dst: &Path, excludes: &Excludes) -> Result<()> {
+    if excludes.is_empty() && dst.symlink_metadata().is_err() && clone_path(src, dst) {
+        return Ok(());
+    }
+    copy_dir_excluding(src, dst, &|path, is_dir| excludes.matches(path, is_dir))
+}
+
+/// Copy a file, overwriting the destination. Uses a copy-on-write clone
+/// (reflink on btrfs/xfs, clonefile on APFS) when available so large files
+/// copy instantly without taking extra space.
+pub(crate) fn copy_file(src: &Path, dst: &Path) -> Result<()> {
+    if clone_file(src, dst) {
+        return Ok(());
+    }
+    fs::copy(src, dst)?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn clone_file(src: &Path, dst: &Path) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    let Ok(src_file) = fs::File::open(src) else {
+        return false;
+    };
+    let Ok(metadata) = src_file.metadata() else {
+        return false;
+    };
+    let Ok(dst_file) = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dst)
+    else {
+        return false;
+    };
+    let ret = unsafe {
+        libc::ioctl(
+            dst_file.as_raw_fd(),
+            libc::FICLONE as _,
+            src_file.as_raw_fd(),
+        )
+    };
+    if ret != 0 {
+        // Not supported here (e.g. ext4, tmpfs, cross-device); fs::copy takes over
+        return false;
+    }
+    let _ = dst_file.set_permissions(metadata.permissions());
+    true
+}
+
+#[cfg(target_os = "macos")]
+fn clone_file(src: &Path, dst: &Path) -> bool {
+    // clonefile refuses to overwrite
+    if dst.symlink_metadata().is_ok() && fs::remove_file(dst).is_err() {
+        return false;
+    }
+    clone_path(src, dst)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn clone_file(_src: &Path, _dst: &Path) -> bool {
+    false
+}
+
+/// Clone a file or directory tree with clonefile(2). `dst` must not exist.
+#[cfg(target_os = "macos")]
+fn clone_path(src: &Path, dst: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let (Ok(src_c), Ok(dst_c)) = (
+        CString::new(src.as_os_str().as_bytes()),
+        CString::new(dst.as_os_str().as_bytes()),
+    ) else {
+        return false;
+    };
+    if let Some(parent) = dst.parent()
+        && fs::create_dir_all(parent).is_err()
+    {
+        return false;
+    }
+    unsafe { libc::clonefile(src_c.as_ptr(), dst_c.as_ptr(), libc::CLONE_NOFOLLOW) == 0 }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn clone_path(_src: &Path, _dst: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_file_overwrites_and_keeps_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.sh");
+        let dst = dir.path().join("dst.sh");
+        fs::write(&src, "echo new").unwrap();
+        fs::write(&dst, "old contents that are longer").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&src, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        copy_file(&src, &dst).unwrap();
+
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "echo new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&dst).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn copy_dir_copies_nested_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("node_modules");
+        fs::create_dir_all(src.join("pkg/lib")).unwrap();
+        fs::write(src.join("pkg/lib/index.js"), "module.exports = 1").unwrap();
+
+        let dst = dir.path().join("wt/node_modules");
+        copy_dir(&src, &dst, &Excludes(Vec::new())).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dst.join("pkg/lib/index.js")).unwrap(),
+            "module.exports = 1"
+        );
+    }
+}
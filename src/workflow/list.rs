@@ -1,12 +1,13 @@
-use anyhow::{Result, anyhow};
-use std::collections::HashSet;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::config::MuxMode;
-use crate::multiplexer::{Multiplexer, util};
+use crate::git::BranchDivergence;
+use crate::multiplexer::{AgentPane, Multiplexer, util};
 use crate::state::StateStore;
-use crate::util::canon_or_self;
-use crate::{config, git, github, spinner};
+use crate::util::{canon_or_self, parallel_map};
+use crate::{config, git, github, spinner, vcs};
 
 use super::types::{AgentStatusSummary, WorktreeInfo};
 
@@ -68,14 +69,56 @@ pub fn list_in(
     filter: &[String],
     repo: Option<&Path>,
 ) -> Result<Vec<WorktreeInfo>> {
+    let pr_mode = if fetch_pr_status {
+        PrMode::Wait
+    } else {
+        PrMode::Skip
+    };
+    list_impl(config, mux, pr_mode, filter, repo).map(|(worktrees, _)| worktrees)
+}
+
+/// List all worktrees, using cached PR status when the fresh fetch isn't
+/// done by the time local status is. The still-running fetch is returned so
+/// the caller can apply fresh results when they arrive; dropping it leaves
+/// it to refresh the cache in the background.
+pub fn list_with_cached_prs(
+    config: &config::Config,
+    mux: &dyn Multiplexer,
+    filter: &[String],
+) -> Result<(Vec<WorktreeInfo>, Option<github::PrPrefetch>)> {
+    list_impl(config, mux, PrMode::PreferCached, filter, None)
+}
+
+/// Where `list_impl` gets PR status from.
+#[derive(Clone, Copy, PartialEq)]
+enum PrMode {
+    /// No PR status
+    Skip,
+    /// Fresh PR status, waiting for the fetch if needed
+    Wait,
+    /// Fresh PR status if ready in time, otherwise cached
+    PreferCached,
+}
+
+fn list_impl(
+    config: &config::Config,
+    mux: &dyn Multiplexer,
+    pr_mode: PrMode,
+    filter: &[String],
+    repo: Option<&Path>,
+) -> Result<(Vec<WorktreeInfo>, Option<github::PrPrefetch>)> {
     if repo.is_none() && !git::is_git_repo()? {
-        return Err(anyhow!("Not in a git repository"));
+        return Err(git::NotInRepo.into());
     }
 
-    let worktrees_data = git::list_worktrees_in(repo)?;
+    let vcs_kind = match repo {
+        Some(path) => vcs::detect_vcs_in(path),
+        None => vcs::detect_vcs(),
+    };
+    let worktrees_data = vcs::create_vcs(vcs_kind).list_workspaces_in(repo)?;
 
     if worktrees_data.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), None));
     }
 
     // The first worktree from `git worktree list` is always the main worktree
@@ -85,50 +128,58 @@ pub fn list_in(
     let worktrees_data = filter_worktrees(worktrees_data, filter);
 
     if worktrees_data.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), None));
     }
 
-    // Check mux status and get all windows/sessions once to avoid repeated process calls
-    let mux_running = mux.is_running().unwrap_or(false);
-    let mux_windows: HashSet<String> = if mux_running {
-        mux.get_all_window_names().unwrap_or_default()
-    } else {
-        HashSet::new()
-    };
-    let mux_sessions: HashSet<String> = if mux_running {
-        mux.get_all_session_names().unwrap_or_default()
-    } else {
-        HashSet::new()
+    // Start the PR fetch first so the gh round trip overlaps the local queries
+    let pr_fetch = match (pr_mode, &main_worktree_path) {
+        (PrMode::Skip, _) | (_, None) => None,
+        (_, Some(root)) => Some(github::PrPrefetch::spawn(root.clone())),
     };
 
-    // Get the main branch for unmerged checks
-    let main_branch = git::get_default_branch_in(repo).ok();
-
-    // Get all unmerged branches in one go for efficiency
-    // Prefer checking against remote tracking branch for more accurate results
-    let unmerged_branches = main_branch
-        .as_deref()
-        .and_then(|main| git::get_merge_base_in(repo, main).ok())
-        .and_then(|base| git::get_unmerged_branches_in(repo, &base).ok())
-        .unwrap_or_default(); // Use an empty set on failure
-
-    // Batch fetch all PRs if requested (single API call)
-    let pr_map = if fetch_pr_status {
-        spinner::with_spinner("Fetching PR status", || {
-            Ok(github::list_prs().unwrap_or_default())
-        })?
-    } else {
-        std::collections::HashMap::new()
-    };
-
-    // Load reconciled agent states (only if multiplexer is running)
-    let agent_panes = if mux_running {
-        StateStore::new()
-            .ok()
-            .and_then(|store| store.load_reconciled_agents(mux).ok())
-            .unwrap_or_default()
-    } else {
-        Vec::new()
+    // Repo-wide queries are independent of each other, so run them side by
+    // side; each is a single batched call rather than one per worktree
+    let (mux_state, git_state, worktree_modes, branch_bases) = std::thread::scope(|s| {
+        let mux_state = s.spawn(|| query_mux_state(mux));
+        let git_state = s.spawn(|| query_branch_state(repo));
+        let worktree_modes = s.spawn(|| git::get_all_worktree_modes_in(repo));
+        let branch_bases = s.spawn(|| git::get_all_branch_bases_in(repo));
+        (
+            mux_state.join().unwrap_or_default(),
+            git_state.join().unwrap_or_default(),
+            worktree_modes.join().unwrap_or_default(),
+            branch_bases.join().unwrap_or_default(),
+        )
+    });
+    let MuxState {
+        windows: mux_windows,
+        sessions: mux_sessions,
+        agent_panes,
+    } = mux_state;
+    let BranchState {
+        main_branch,
+        unmerged_branches,
+        divergence_map,
+    } = git_state;
+
+    let (pr_map, pending_prs) = match pr_fetch {
+        None => (HashMap::new(), None),
+        Some(fetch) => match fetch.try_take() {
+            Some(prs) => (prs, None),
+            None => {
+                let cached = main_worktree_path
+                    .as_deref()
+                    .filter(|_| pr_mode == PrMode::PreferCached)
+                    .and_then(github::cached_prs);
+                match cached {
+                    Some(prs) => (prs, Some(fetch)),
+                    None => (
+                        spinner::with_spinner("Fetching PR status", || Ok(fetch.wait()))?,
+                        None,
+                    ),
+                }
+            }
+        },
     };
 
     // Pre-calculate canonical paths for agents to avoid repeated syscalls
@@ -137,93 +188,170 @@ pub fn list_in(
         .map(|a| (canon_or_self(&a.path), a.status))
         .collect();
 
-    // Batch-load all worktree modes in a single git config call
-    let worktree_modes = git::get_all_worktree_modes_in(repo);
-
     let prefix = config.window_prefix();
-    let worktrees: Vec<WorktreeInfo> = worktrees_data
-        .into_iter()
-        .map(|(path, branch)| {
-            // Extract handle from worktree path basename (the source of truth)
-            let handle = path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or(&branch)
-                .to_string();
-
-            // Check if mux target exists (window or session based on stored mode)
-            let prefixed_name = util::prefixed(prefix, &handle);
-            let mode = worktree_modes
-                .get(&handle)
-                .copied()
-                .unwrap_or(MuxMode::Window);
-            let has_mux_window = if mode == MuxMode::Session {
-                mux_sessions.contains(&prefixed_name)
-            } else {
-                mux_windows.contains(&prefixed_name)
-            };
-
-            // Check for unmerged commits, but only if this isn't the main branch
-            let has_unmerged = if let Some(ref main) = main_branch {
-                if branch == *main || branch == "(detached)" {
-                    false
-                } else {
-                    unmerged_branches.contains(&branch)
-                }
-            } else {
+    // Per-worktree work (path canonicalization, push target lookups) fans out
+    // over a bounded pool so large worktree counts don't run serially
+    let worktrees: Vec<WorktreeInfo> = parallel_map(worktrees_data, LIST_JOBS, |(path, branch)| {
+        // Extract handle from worktree path basename (the source of truth)
+        let handle = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&branch)
+            .to_string();
+
+        // Check if mux target exists (window or session based on stored mode)
+        let prefixed_name = util::prefixed(prefix, &handle);
+        let mode = worktree_modes
+            .get(&handle)
+            .copied()
+            .unwrap_or(MuxMode::Window);
+        let has_mux_window = if mode == MuxMode::Session {
+            mux_sessions.contains(&prefixed_name)
+        } else {
+            mux_windows.contains(&prefixed_name)
+        };
+
+        // Check for unmerged commits, but only if this isn't the main branch
+        let has_unmerged = if let Some(ref main) = main_branch {
+            if branch == *main || branch == "(detached)" {
                 false
-            };
-
-            // Lookup PR info from batch fetch
-            let pr_info = pr_map.get(&branch).cloned();
-
-            // Match agents to this worktree by comparing canonicalized paths.
-            // An agent's workdir should be within the worktree directory.
-            let canon_wt_path = canon_or_self(&path);
-            let matching_statuses: Vec<_> = agent_panes_canon
-                .iter()
-                .filter(|(canon_agent_path, _)| {
-                    *canon_agent_path == canon_wt_path
-                        || canon_agent_path.starts_with(&canon_wt_path)
-                })
-                .filter_map(|(_, status)| *status)
-                .collect();
-
-            let agent_status = if matching_statuses.is_empty() {
-                None
             } else {
-                Some(AgentStatusSummary {
-                    statuses: matching_statuses,
-                })
-            };
-
-            let is_main = main_worktree_path
-                .as_ref()
-                .is_some_and(|main_path| *main_path == path);
-
-            let created_at = std::fs::metadata(&path)
-                .ok()
-                .and_then(|m| m.created().ok())
-                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                .map(|d| d.as_secs());
-
-            let base_branch = git::get_branch_base_in(&branch, repo).ok();
-
-            WorktreeInfo {
-                handle,
-                branch,
-                path,
-                is_main,
-                mode,
-                has_mux_window,
-                has_unmerged,
-                pr_info,
-                agent_status,
-                created_at,
-                base_branch,
+                unmerged_branches.contains(&branch)
             }
-        })
-        .collect();
+        } else {
+            false
+        };
+
+        let pr_info = pr_for_branch(&pr_map, &branch, repo);
+
+        // Match agents to this worktree by comparing canonicalized paths.
+        // An agent's workdir should be within the worktree directory.
+        let canon_wt_path = canon_or_self(&path);
+        let matching_statuses: Vec<_> = agent_panes_canon
+            .iter()
+            .filter(|(canon_agent_path, _)| {
+                *canon_agent_path == canon_wt_path || canon_agent_path.starts_with(&canon_wt_path)
+            })
+            .filter_map(|(_, status)| *status)
+            .collect();
+
+        let agent_status = if matching_statuses.is_empty() {
+            None
+        } else {
+            Some(AgentStatusSummary {
+                statuses: matching_statuses,
+            })
+        };
+
+        let is_main = main_worktree_path
+            .as_ref()
+            .is_some_and(|main_path| *main_path == path);
+
+        let created_at = std::fs::metadata(&path)
+            .ok()
+            .and_then(|m| m.created().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+
+        let base_branch = branch_bases.get(&branch).cloned();
+
+        let mut divergence = divergence_map.get(&branch).copied().unwrap_or_default();
+        if is_main || main_branch.as_deref() == Some(branch.as_str()) {
+            divergence.main = None;
+        }
+
+        WorktreeInfo {
+            handle,
+            branch,
+            path,
+            is_main,
+            mode,
+            has_mux_window,
+            has_unmerged,
+            pr_info,
+            agent_status,
+            created_at,
+            base_branch,
+            divergence,
+        }
+    });
+
+    Ok((worktrees, pending_prs))
+}
+
+/// Look up a branch's PR in a batch fetch. Fork checkouts push to a
+/// differently named branch, which is the PR's head ref.
+pub fn pr_for_branch(
+    pr_map: &HashMap<String, github::PrSummary>,
+    branch: &str,
+    repo: Option<&Path>,
+) -> Option<github::PrSummary> {
+    pr_map.get(branch).cloned().or_else(|| {
+        if pr_map.is_empty() {
+            return None;
+        }
+        git::get_push_target_in(branch, repo)
+            .and_then(|(_, push_branch)| pr_map.get(&push_branch).cloned())
+    })
+}
+
+/// Upper bound on threads used for per-worktree status work.
+pub const LIST_JOBS: usize = 8;
+
+/// Multiplexer windows, sessions and agents, gathered once per listing.
+#[derive(Default)]
+struct MuxState {
+    windows: HashSet<String>,
+    sessions: HashSet<String>,
+    agent_panes: Vec<AgentPane>,
+}
+
+fn query_mux_state(mux: &dyn Multiplexer) -> MuxState {
+    if !mux.is_running().unwrap_or(false) {
+        return MuxState::default();
+    }
+    MuxState {
+        windows: mux.get_all_window_names().unwrap_or_default(),
+        sessions: mux.get_all_session_names().unwrap_or_default(),
+        // Load reconciled agent states
+        agent_panes: StateStore::new()
+            .ok()
+            .and_then(|store| store.load_reconciled_agents(mux).ok())
+            .unwrap_or_default(),
+    }
+}
+
+/// Unmerged branches and ahead/behind counts relative to the main branch.
+#[derive(Default)]
+struct BranchState {
+    main_branch: Option<String>,
+    unmerged_branches: HashSet<String>,
+    divergence_map: HashMap<String, BranchDivergence>,
+}
+
+fn query_branch_state(repo: Option<&Path>) -> BranchState {
+    // Get the main branch for unmerged checks
+    let main_branch = git::get_default_branch_in(repo).ok();
 
-    Ok(worktrees)
+    // Get all unmerged branches in one go for efficiency
+    // Prefer checking against remote tracking branch for more accurate results
+    let merge_base = main_branch
+        .as_deref()
+        .and_then(|main| git::get_merge_base_in(repo, main).ok());
+    let unmerged_branches = merge_base
+        .as_deref()
+        .and_then(|base| git::get_unmerged_branches_in(repo, base).ok())
+        .unwrap_or_default(); // Use an empty set on failure
+
+    // Ahead/behind counts for every branch in a single for-each-ref call
+    let divergence_map = merge_base
+        .as_deref()
+        .and_then(|base| git::get_branch_divergence_in(repo, base).ok())
+        .unwrap_or_default();
+
+    BranchState {
+        main_branch,
+        unmerged_branches,
+        divergence_map,
+    }
 }
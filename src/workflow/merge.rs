@@ -1,11 +1,14 @@
 use anyhow::{Context, Result, anyhow};
 
+use crate::config::NotificationEvent;
+use crate::notification::webhook::WebhookPayload;
+use crate::vcs::{self, VcsKind};
 use crate::{cmd, git};
 use tracing::{debug, info};
 
 use super::cleanup::{self, get_worktree_mode};
 use super::context::WorkflowContext;
-use super::types::MergeResult;
+use super::types::{MergeConflict, MergeResult, UncommittedChanges};
 
 /// Merge a branch into the target branch and clean up
 #[allow(clippy::too_many_arguments)]
@@ -19,6 +22,7 @@ pub fn merge(
     no_verify: bool,
     no_hooks: bool,
     notification: bool,
+    approve: bool,
     context: &WorkflowContext,
 ) -> Result<MergeResult> {
     info!(
@@ -30,6 +34,7 @@ pub fn merge(
         keep,
         no_verify,
         no_hooks,
+        approve,
         "merge:start"
     );
 
@@ -38,12 +43,8 @@ pub fn merge(
     context.chdir_to_main_worktree()?;
 
     // Smart resolution: try handle first, then branch name
-    let (worktree_path, branch_to_merge) = git::find_worktree(name).map_err(|_| {
-        anyhow!(
-            "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-            name
-        )
-    })?;
+    let (worktree_path, branch_to_merge) =
+        vcs::find_workspace(name).map_err(|_| git::WorktreeNotFound(name.to_string()))?;
 
     // The handle is the basename of the worktree directory (used for tmux operations)
     let handle = worktree_path
@@ -76,21 +77,27 @@ pub fn merge(
     } else {
         match git::get_branch_base(&branch_to_merge) {
             Ok(base) => {
-                // Verify the base branch still exists
-                if git::branch_exists(&base)? {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:auto-detected base branch"
-                    );
-                    Some(base)
-                } else {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:base branch not found, defaulting to main"
-                    );
-                    None
+                // Only merge into a local branch. Tags and commits used as a
+                // starting point aren't merge targets, and a remote-tracking
+                // base maps to its local branch if one exists.
+                match git::resolve_base_merge_target(&base)? {
+                    Some(target) if target != branch_to_merge => {
+                        info!(
+                            branch = %branch_to_merge,
+                            base = %base,
+                            target = %target,
+                            "merge:auto-detected base branch"
+                        );
+                        Some(target)
+                    }
+                    _ => {
+                        info!(
+                            branch = %branch_to_merge,
+                            base = %base,
+                            "merge:base is not a local branch, defaulting to main"
+                        );
+                        None
+                    }
                 }
             }
             Err(_) => {
@@ -109,6 +116,30 @@ pub fn merge(
         .unwrap_or_else(|| context.main_branch.clone());
     let target_branch = target_branch.as_str();
 
+    super::merge_policy::check(
+        &context.config.merge_policy,
+        &branch_to_merge,
+        target_branch,
+        approve,
+        &context.main_worktree_root,
+    )?;
+
+    // jj snapshots working-copy edits into a change, so there is nothing to
+    // stage or commit first, and merges happen on bookmarks. Git worktrees
+    // from before the repo used jj keep the git flow.
+    let is_jj = context.vcs.kind() == VcsKind::Jj && vcs::jj::is_jj_workspace(handle);
+    if is_jj && squash {
+        return Err(anyhow!(
+            "--squash is not supported in jj repositories. Use 'jj squash' in the workspace, then merge."
+        ));
+    }
+    if is_jj && context.config.require_signed_commits() {
+        return Err(anyhow!(
+            "require_signed_commits is not supported in jj repositories. \
+             Configure signing in jj instead."
+        ));
+    }
+
     // Resolve the worktree path and window handle for the TARGET branch.
     // We prioritize finding an existing worktree for the target branch to support
     // workflows where 'main' is checked out in a linked worktree (issue #29).
@@ -145,8 +176,8 @@ pub fn merge(
     // Handle changes in the source worktree
     // Only check for unstaged/untracked when worktree will be deleted (!keep)
     // With --keep, the worktree persists so no data loss risk
-    let has_unstaged = !keep && git::has_unstaged_changes(&worktree_path)?;
-    let has_untracked = !keep && git::has_untracked_files(&worktree_path)?;
+    let has_unstaged = !keep && !is_jj && git::has_unstaged_changes(&worktree_path)?;
+    let has_untracked = !keep && !is_jj && git::has_untracked_files(&worktree_path)?;
 
     if (has_unstaged || has_untracked) && !ignore_uncommitted {
         let mut issues = Vec::new();
@@ -156,18 +187,28 @@ pub fn merge(
         if has_untracked {
             issues.push("untracked files (will be lost)");
         }
-        return Err(anyhow!(
+        return Err(UncommittedChanges(format!(
             "Worktree for '{}' has {}. Please stage or stash them, or use --ignore-uncommitted.",
             branch_to_merge,
             issues.join(" and ")
-        ));
+        ))
+        .into());
     }
 
-    let had_staged_changes = git::has_staged_changes(&worktree_path)?;
+    // Sign commits created by the merge when the user's git config asks for it
+    // or when signatures are required. Check up front so we fail before touching
+    // either worktree.
+    let require_signed = context.config.require_signed_commits();
+    let sign = !is_jj && (require_signed || git::commit_signing_enabled(&target_worktree_path));
+    if sign {
+        git::check_signing_available(&target_worktree_path).context("Cannot sign merge commits")?;
+    }
+
+    let had_staged_changes = !is_jj && git::has_staged_changes(&worktree_path)?;
     if had_staged_changes && !ignore_uncommitted {
         // Commit using git's editor (respects $EDITOR or git config)
         info!(path = %worktree_path.display(), "merge:committing staged changes");
-        git::commit_with_editor(&worktree_path).context("Failed to commit staged changes")?;
+        git::commit_with_editor(&worktree_path, sign).context("Failed to commit staged changes")?;
     }
 
     if branch_to_merge == target_branch {
@@ -185,16 +226,26 @@ pub fn merge(
     // Safety check: Abort if the target worktree has uncommitted tracked changes.
     // Untracked files are allowed; git will fail safely if they collide with merged files.
     if git::has_tracked_changes(&target_worktree_path)? {
-        return Err(anyhow!(
+        return Err(UncommittedChanges(format!(
             "Target worktree ({}) has uncommitted changes. Please commit or stash them before merging.",
             target_worktree_path.display()
-        ));
+        ))
+        .into());
+    }
+
+    // Reject unsigned commits before merging anything. Squash merges only land
+    // the (signed) squash commit, and --rebase re-signs commits, so it is checked
+    // after rebasing instead.
+    if require_signed && !squash && !rebase {
+        ensure_commits_signed(&worktree_path, target_branch, &branch_to_merge)?;
     }
 
     // Explicitly switch the target worktree to the target branch.
     // This ensures that if we are reusing the main worktree for a feature branch merge,
     // it is checked out to the correct branch.
-    git::switch_branch_in_worktree(&target_worktree_path, target_branch)?;
+    if !is_jj {
+        git::switch_branch_in_worktree(&target_worktree_path, target_branch)?;
+    }
 
     // Run pre-merge hooks after all validations pass but before any merge operations begin.
     // Skip hooks if --no-verify or --no-hooks flag is passed.
@@ -230,14 +281,18 @@ pub fn merge(
         }
     }
 
-    // Helper closure to generate the error message for merge conflicts
-    let conflict_err = |branch: &str| -> anyhow::Error {
-        let retry_cmd = if into_branch.is_some() {
+    let retry_cmd = |branch: &str| -> String {
+        if into_branch.is_some() {
             format!("workmux merge {} --into {}", branch, target_branch)
         } else {
             format!("workmux merge {}", branch)
-        };
-        anyhow!(
+        }
+    };
+
+    // Helper closure to generate the error message for merge conflicts
+    let conflict_err = |branch: &str| -> anyhow::Error {
+        let retry_cmd = retry_cmd(branch);
+        MergeConflict(format!(
             "Merge failed due to conflicts. Target worktree kept clean.\n\n\
             To resolve, update your branch in worktree at {}:\n\
               git rebase {}  (recommended)\n\
@@ -248,10 +303,37 @@ pub fn merge(
             target_branch,
             target_branch,
             retry_cmd
-        )
+        ))
+        .into()
     };
 
-    if rebase {
+    if is_jj {
+        // Pick up the workspace's latest change before merging the bookmark
+        vcs::jj::advance_bookmark(&worktree_path, handle, &branch_to_merge)?;
+        let merged = if rebase {
+            println!(
+                "Rebasing '{}' onto '{}'...",
+                &branch_to_merge, target_branch
+            );
+            vcs::jj::rebase_bookmark(&target_worktree_path, &branch_to_merge, target_branch)?
+        } else {
+            vcs::jj::merge_bookmark(&target_worktree_path, &branch_to_merge, target_branch)?
+        };
+        if !merged {
+            return Err(MergeConflict(format!(
+                "Merge failed due to conflicts. The jj operation was undone.\n\n\
+                To resolve, rebase your bookmark in the workspace at {}:\n\
+                  jj rebase -b {} -d {}\n\n\
+                After resolving conflicts, retry: {}",
+                worktree_path.display(),
+                branch_to_merge,
+                target_branch,
+                retry_cmd(&branch_to_merge)
+            ))
+            .into());
+        }
+        info!(branch = %branch_to_merge, rebase, "merge:jj merge complete");
+    } else if rebase {
         // Rebase the feature branch on top of target inside its own worktree.
         // This is where conflicts will be detected.
         println!(
@@ -263,17 +345,30 @@ pub fn merge(
             base = target_branch,
             "merge:rebase start"
         );
-        git::rebase_branch_onto_base(&worktree_path, target_branch).with_context(|| {
-            format!(
-                "Rebase failed, likely due to conflicts.\n\n\
+        // Only rewrite commits already on the target when signatures are
+        // required; signing by default shouldn't change pushed SHAs
+        if let Err(e) =
+            git::rebase_branch_onto_base(&worktree_path, target_branch, sign, require_signed)
+        {
+            // Only conflicts exit with the conflict code; signing, hook or I/O
+            // failures are plain errors
+            if !git::has_rebase_conflicts(&worktree_path) {
+                return Err(e);
+            }
+            return Err(e.context(MergeConflict(format!(
+                "Rebase stopped on conflicts.\n\n\
                 Please resolve them manually inside the worktree at '{}'.\n\
                 Then, run 'git rebase --continue' to proceed or 'git rebase --abort' to cancel.",
                 worktree_path.display()
-            )
-        })?;
+            ))));
+        }
+
+        if require_signed {
+            ensure_commits_signed(&worktree_path, target_branch, &branch_to_merge)?;
+        }
 
         // After a successful rebase, merge into target. This will be a fast-forward.
-        git::merge_in_worktree(&target_worktree_path, &branch_to_merge)
+        git::merge_in_worktree(&target_worktree_path, &branch_to_merge, sign)
             .context("Failed to merge rebased branch. This should have been a fast-forward.")?;
         info!(branch = %branch_to_merge, "merge:fast-forward complete");
     } else if squash {
@@ -287,12 +382,12 @@ pub fn merge(
 
         // Prompt the user to provide a commit message for the squashed changes.
         println!("Staged squashed changes. Please provide a commit message in your editor.");
-        git::commit_with_editor(&target_worktree_path)
+        git::commit_with_editor(&target_worktree_path, sign)
             .context("Failed to commit squashed changes. You may need to commit them manually.")?;
         info!(branch = %branch_to_merge, "merge:squash merge committed");
     } else {
         // Default merge commit workflow
-        if let Err(e) = git::merge_in_worktree(&target_worktree_path, &branch_to_merge) {
+        if let Err(e) = git::merge_in_worktree(&target_worktree_path, &branch_to_merge, sign) {
             info!(branch = %branch_to_merge, error = %e, "merge:standard merge failed, aborting merge in target worktree");
             // Best effort to abort; ignore failure as the user message is the priority.
             let _ = git::abort_merge_in_worktree(&target_worktree_path);
@@ -303,11 +398,13 @@ pub fn merge(
 
     // Show notification before cleanup or early return (--keep),
     // since cleanup may kill the window and terminate this process
+    let message = format!("Merged '{}' into '{}'", branch_to_merge, target_branch);
+    let mut payload = WebhookPayload::new(NotificationEvent::Merged, handle, message.clone());
+    payload.branch = Some(branch_to_merge.clone());
+    payload.target = Some(target_branch.to_string());
+    crate::notification::publish(&context.config.notifications, &payload);
     if notification {
-        show_notification(&format!(
-            "Merged '{}' into '{}'",
-            branch_to_merge, target_branch
-        ));
+        crate::notification::show(&message);
     }
 
     // Skip cleanup if --keep flag is used
@@ -349,32 +446,31 @@ pub fn merge(
     })
 }
 
-/// Shows a system notification on macOS or Linux
-fn show_notification(message: &str) {
-    #[cfg(target_os = "macos")]
-    {
-        use mac_notification_sys::{Notification, set_application};
-        // Set application to Terminal to use its icon
-        if let Err(e) = set_application("com.apple.Terminal") {
-            tracing::debug!("Failed to set notification application: {:?}", e);
-        }
-        if let Err(e) = Notification::default()
-            .title("workmux")
-            .message(message)
-            .send()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
-        }
+/// Fail if any commit on `branch` that is not yet on `target` is unsigned.
+fn ensure_commits_signed(
+    worktree_path: &std::path::Path,
+    target: &str,
+    branch: &str,
+) -> Result<()> {
+    let range = format!("{}..{}", target, branch);
+    let unsigned = git::find_unsigned_commits(worktree_path, &range)?;
+    if unsigned.is_empty() {
+        return Ok(());
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        if let Err(e) = notify_rust::Notification::new()
-            .summary("workmux")
-            .body(message)
-            .show()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
-        }
-    }
+    let list = unsigned
+        .iter()
+        .map(|(sha, subject)| format!("  {} {}", sha, subject))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(anyhow!(
+        "Branch '{}' has {} unsigned commit(s) and require_signed_commits is enabled:\n{}\n\n\
+        Commits made inside a sandbox are not signed. To re-sign them, run in {}:\n\
+          git rebase --force-rebase --gpg-sign {}",
+        branch,
+        unsigned.len(),
+        list,
+        worktree_path.display(),
+        target
+    ))
 }
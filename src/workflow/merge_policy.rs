@@ -0,0 +1,179 @@
+//! `merge_policy`: checks a merge has to pass before `workmux merge` touches
+//! anything.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use tracing::info;
+
+use crate::config::MergePolicyConfig;
+use crate::git;
+use crate::github::{self, CheckState, PrSummary};
+
+/// Fail with every policy violation if merging `branch` into `target` isn't
+/// allowed. `approved` is the `--approve` flag.
+pub fn check(
+    policy: &MergePolicyConfig,
+    branch: &str,
+    target: &str,
+    approved: bool,
+    repo_root: &Path,
+) -> Result<()> {
+    let pr = if policy.require_pr() {
+        open_pr(branch, repo_root)
+            .context("Failed to look up the pull request (merge_policy.require_pr)")?
+    } else {
+        None
+    };
+
+    let violations = violations(policy, branch, target, approved, pr.as_ref());
+    if violations.is_empty() {
+        return Ok(());
+    }
+    info!(
+        branch = branch,
+        target = target,
+        ?violations,
+        "merge:blocked by policy"
+    );
+    bail!(
+        "Merge blocked by merge_policy:\n{}",
+        violations
+            .iter()
+            .map(|v| format!("  - {}", v))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+/// The open pull request for `branch`. Fork checkouts push to a differently
+/// named branch, which is the PR's head ref.
+fn open_pr(branch: &str, repo_root: &Path) -> Result<Option<PrSummary>> {
+    let mut pr = github::view_pr_in(repo_root, branch)?;
+    if pr.is_none()
+        && let Some((_, push_branch)) = git::get_push_target_in(branch, Some(repo_root))
+        && push_branch != branch
+    {
+        pr = github::view_pr_in(repo_root, &push_branch)?;
+    }
+    Ok(pr.filter(|pr| pr.state == "OPEN"))
+}
+
+fn violations(
+    policy: &MergePolicyConfig,
+    branch: &str,
+    target: &str,
+    approved: bool,
+    pr: Option<&PrSummary>,
+) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if policy.is_protected(target) {
+        violations.push(format!(
+            "'{}' is a protected branch (protected_branches); merge it through a pull request",
+            target
+        ));
+    }
+
+    if policy.require_pr() {
+        match pr {
+            None => violations.push(format!(
+                "'{}' has no open pull request (require_pr); open one with `gh pr create`",
+                branch
+            )),
+            Some(pr) if policy.require_checks() => match &pr.checks {
+                Some(CheckState::Success) => {}
+                Some(CheckState::Failure { passed, total }) => violations.push(format!(
+                    "PR #{} has failing checks, {}/{} passed (require_checks)",
+                    pr.number, passed, total
+                )),
+                Some(CheckState::Pending { passed, total }) => violations.push(format!(
+                    "PR #{} checks are still running, {}/{} passed (require_checks)",
+                    pr.number, passed, total
+                )),
+                None => violations.push(format!(
+                    "PR #{} has no checks reported (require_checks)",
+                    pr.number
+                )),
+            },
+            Some(_) => {}
+        }
+    }
+
+    if policy.require_approval() && !approved {
+        violations.push(
+            "a human has to approve this merge (require_approval); rerun with --approve"
+                .to_string(),
+        );
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(checks: Option<CheckState>) -> PrSummary {
+        PrSummary {
+            number: 42,
+            title: "Add feature".to_string(),
+            state: "OPEN".to_string(),
+            is_draft: false,
+            checks,
+            check_meta: None,
+            url: None,
+            head_oid: None,
+        }
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        let policy = MergePolicyConfig::default();
+        assert!(violations(&policy, "feature", "main", false, None).is_empty());
+    }
+
+    #[test]
+    fn protected_branches_match_globs() {
+        let policy = MergePolicyConfig {
+            protected_branches: Some(vec!["release/*".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            violations(&policy, "feature", "release/1.2", false, None).len(),
+            1
+        );
+        assert!(violations(&policy, "feature", "main", false, None).is_empty());
+    }
+
+    #[test]
+    fn checks_must_pass() {
+        let policy = MergePolicyConfig {
+            require_checks: Some(true),
+            ..Default::default()
+        };
+        assert!(
+            violations(&policy, "feature", "main", false, None)[0].contains("no open pull request")
+        );
+        let pending = pr(Some(CheckState::Pending {
+            passed: 3,
+            total: 5,
+        }));
+        assert_eq!(
+            violations(&policy, "feature", "main", false, Some(&pending)),
+            vec!["PR #42 checks are still running, 3/5 passed (require_checks)"]
+        );
+        let green = pr(Some(CheckState::Success));
+        assert!(violations(&policy, "feature", "main", false, Some(&green)).is_empty());
+    }
+
+    #[test]
+    fn approval_needs_the_flag() {
+        let policy = MergePolicyConfig {
+            require_approval: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(violations(&policy, "feature", "main", false, None).len(), 1);
+        assert!(violations(&policy, "feature", "main", true, None).is_empty());
+    }
+}
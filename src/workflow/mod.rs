@@ -1,4 +1,5 @@
 // Module declarations
+mod agent_limits;
 mod agent_resolve;
 mod cleanup;
 mod context;
@@ -6,11 +7,13 @@ mod create;
 pub mod file_ops;
 mod list;
 mod merge;
+mod merge_policy;
 mod open;
 pub mod pr;
 pub mod prompt_loader;
 mod remove;
 mod rename;
+pub mod repos;
 pub mod resurrect;
 mod setup;
 pub mod types;
@@ -20,12 +23,12 @@ pub use agent_resolve::{
     find_worktree_root, match_agents_to_worktree, resolve_worktree_agent, resolve_worktree_agents,
 };
 pub use create::{create, create_with_changes};
-pub use list::{list, list_in};
+pub use list::{LIST_JOBS, list, list_in, list_with_cached_prs, pr_for_branch};
 pub use merge::merge;
-pub use open::open;
+pub use open::{open, open_in_turn};
 pub use remove::remove;
 pub use rename::rename;
-pub use setup::write_prompt_file;
+pub use setup::{WindowTurns, write_prompt_file};
 
 // Re-export commonly used types for convenience
 pub use context::WorkflowContext;
@@ -1,11 +1,11 @@
 use anyhow::{Context, Result, anyhow};
 use regex::Regex;
 
-use crate::git;
 use crate::multiplexer::MuxHandle;
 use crate::multiplexer::util::prefixed;
 use crate::prompt::Prompt;
-use tracing::info;
+use crate::{git, vcs};
+use tracing::{info, warn};
 
 use super::context::WorkflowContext;
 use super::setup;
@@ -20,6 +20,38 @@ pub fn open(
     new_window: bool,
     mode_override: Option<MuxMode>,
     prompt_file_only: Option<&Prompt>,
+) -> Result<CreateResult> {
+    open_impl(
+        name,
+        context,
+        options,
+        new_window,
+        mode_override,
+        prompt_file_only,
+        None,
+    )
+}
+
+/// Open an existing worktree as one of several being opened concurrently
+/// (e.g. by `resurrect`). Only window creation waits for `turn`, so windows
+/// still appear in a fixed order.
+pub fn open_in_turn(
+    name: &str,
+    context: &WorkflowContext,
+    options: SetupOptions,
+    turn: &setup::WindowTurn,
+) -> Result<CreateResult> {
+    open_impl(name, context, options, false, None, None, Some(turn))
+}
+
+fn open_impl(
+    name: &str,
+    context: &WorkflowContext,
+    options: SetupOptions,
+    new_window: bool,
+    mode_override: Option<MuxMode>,
+    prompt_file_only: Option<&Prompt>,
+    window_turn: Option<&setup::WindowTurn>,
 ) -> Result<CreateResult> {
     info!(
         name = name,
@@ -43,12 +75,8 @@ pub fn open(
 
     // This command requires the worktree to already exist
     // Smart resolution: try handle first, then branch name
-    let (worktree_path, branch_name) = git::find_worktree(name).map_err(|_| {
-        anyhow!(
-            "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-            name
-        )
-    })?;
+    let (worktree_path, branch_name) =
+        vcs::find_workspace(name).map_err(|_| git::WorktreeNotFound(name.to_string()))?;
 
     // Derive base handle from the worktree path (in case user provided branch name)
     let base_handle = worktree_path
@@ -62,7 +90,7 @@ pub fn open(
     let stored_mode = git::get_worktree_mode_opt(&base_handle);
     let mode = mode_override.or(stored_mode).unwrap_or(options.mode);
 
-    if mode == MuxMode::Session && context.mux.name() != "tmux" {
+    if mode == MuxMode::Session && context.mux.name() != "tmux" && !context.mux.is_headless() {
         anyhow::bail!(
             "Session mode (--mode session / --session) is only supported with tmux.\n\
              Current backend: {}. Use window mode instead.",
@@ -119,6 +147,35 @@ pub fn open(
         }
     }
 
+    // Layout: an explicit one (remembered for next time), else the one the
+    // worktree was created with
+    let layout = match options.layout.clone() {
+        Some(name) => {
+            if git::get_worktree_meta(&base_handle, "layout").as_deref() != Some(name.as_str()) {
+                git::set_worktree_meta(&base_handle, "layout", &name)
+                    .context("Failed to store layout for the worktree")?;
+            }
+            Some(name)
+        }
+        None => git::get_worktree_meta(&base_handle, "layout"),
+    };
+    let layout_config = match &layout {
+        Some(name) => {
+            let mut config = context.config.clone();
+            match config.apply_layout(name) {
+                Ok(()) => Some(config),
+                // A stored layout may since have been removed from config
+                Err(e) if options.layout.is_none() => {
+                    warn!(handle = base_handle, error = %e, "open:ignoring stored layout");
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None => None,
+    };
+    let config = layout_config.as_ref().unwrap_or(&context.config);
+
     // Update options with the resolved mode
     let options = SetupOptions { mode, ..options };
 
@@ -165,6 +222,14 @@ pub fn open(
         ));
     }
 
+    // Resurrect restores windows that were already running, so it isn't capped.
+    // Fail (or queue) before changing anything; setup_environment checks again
+    // under the lock when it creates the window
+    let limits = (options.run_pane_commands && window_turn.is_none()).then_some(context);
+    if let Some(context) = limits {
+        drop(super::agent_limits::wait_for_slot(context)?);
+    }
+
     // Persist mode metadata if it's missing or changing (backfill legacy worktrees).
     // Placed after early-exit checks to avoid side effects on failed commands.
     if stored_mode != Some(mode) {
@@ -173,6 +238,8 @@ pub fn open(
         } else {
             "window"
         };
+        let _config_lock = git::GitConfigLock::acquire(&context.git_common_dir)
+            .context("Failed to acquire git config lock")?;
         git::set_worktree_meta(&base_handle, "mode", mode_str)
             .context("Failed to persist worktree mode")?;
         info!(
@@ -216,8 +283,18 @@ pub fn open(
 
     // In file-only mode, write prompt file to the worktree before pane setup
     // so editors/plugins can detect it on startup.
-    if let Some(prompt) = prompt_file_only {
-        setup::write_prompt_file(Some(&worktree_path), &branch_name, prompt)?;
+    let prompt_file_path = match prompt_file_only {
+        Some(prompt) => Some(setup::write_prompt_file(
+            Some(&worktree_path),
+            &branch_name,
+            prompt,
+        )?),
+        None => options.prompt_file_path.clone(),
+    };
+    if let Some(path) = &prompt_file_path
+        && let Ok(text) = std::fs::read_to_string(path)
+    {
+        crate::state::activity::record_prompt(&worktree_path, &text);
     }
 
     let options_with_workdir = SetupOptions {
@@ -232,10 +309,12 @@ pub fn open(
         &branch_name,
         &handle,
         &worktree_path,
-        &context.config,
+        config,
         &options_with_workdir,
         None,
         after_window,
+        window_turn,
+        limits,
     )?;
     info!(
         handle = handle,
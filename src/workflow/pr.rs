@@ -46,6 +46,10 @@ fn fork_local_branch_name(owner: &str, branch: &str) -> String {
 pub struct PrCheckoutResult {
     pub local_branch: String,
     pub remote_branch: String,
+    /// Branch the PR targets (e.g., "main"), used as the merge target
+    pub base_branch: Option<String>,
+    /// True if the PR head lives on a fork rather than origin
+    pub is_fork: bool,
 }
 
 /// Resolve a PR reference and prepare for checkout.
@@ -108,6 +112,8 @@ pub fn resolve_pr_ref(
     Ok(PrCheckoutResult {
         local_branch,
         remote_branch,
+        base_branch: pr_details.base_ref_name,
+        is_fork,
     })
 }
 
@@ -4,8 +4,10 @@
 //! making it reusable and testable.
 
 use crate::prompt::{Prompt, PromptDocument, PromptMetadata, parse_prompt_document};
-use anyhow::{Context, Result, anyhow};
+use crate::template::{create_template_env, render_prompt_body, validate_template_variables};
+use anyhow::{Context, Result, anyhow, bail};
 use edit::Builder;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Arguments for loading a prompt.
@@ -13,6 +15,7 @@ pub struct PromptLoadArgs<'a> {
     pub prompt_editor: bool,
     pub prompt_inline: Option<&'a str>,
     pub prompt_file: Option<&'a PathBuf>,
+    pub prompt_template: Option<&'a str>,
 }
 
 /// Load a prompt from the provided arguments (editor, inline, or file).
@@ -31,7 +34,21 @@ pub fn load_prompt(args: &PromptLoadArgs) -> Result<Option<Prompt>> {
         Ok(match (args.prompt_inline, args.prompt_file) {
             (Some(inline), None) => Some(Prompt::Inline(inline.to_string())),
             (None, Some(path)) => Some(Prompt::FromFile(path.clone())),
-            (None, None) => None,
+            (None, None) => match args.prompt_template {
+                Some(name) => {
+                    let path = template_path(name)?;
+                    if !path.is_file() {
+                        bail!(
+                            "No prompt template '{}' in {}. Create it with: workmux prompts edit {}",
+                            name,
+                            library_dir()?.display(),
+                            name
+                        );
+                    }
+                    Some(Prompt::FromFile(path))
+                }
+                None => None,
+            },
             _ => None, // clap enforces exclusivity; this is unreachable
         })
     }
@@ -58,3 +75,121 @@ pub fn parse_prompt_with_frontmatter(
         })
     }
 }
+
+/// Directory holding the prompt template library.
+pub fn library_dir() -> Result<PathBuf> {
+    Ok(crate::xdg::config_dir()?.join("prompts"))
+}
+
+/// Path of library template `name`, which may not exist yet.
+pub fn template_path(name: &str) -> Result<PathBuf> {
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    if name.is_empty()
+        || name.starts_with('.')
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!(
+            "Invalid prompt template name '{}': use letters, digits, '-', '_' and '.'",
+            name
+        );
+    }
+    Ok(library_dir()?.join(format!("{}.md", name)))
+}
+
+/// Names of the templates in the library, sorted.
+pub fn list_templates() -> Result<Vec<String>> {
+    let dir = library_dir()?;
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(".md").map(str::to_string)
+        })
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Parse `--var KEY=VALUE` arguments.
+pub fn parse_vars(vars: &[String]) -> Result<BTreeMap<String, String>> {
+    vars.iter()
+        .map(|var| {
+            let (key, value) = var
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid --var '{}', expected KEY=VALUE", var))?;
+            let key = key.trim();
+            let valid = key
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                bail!(
+                    "Invalid --var name '{}': use letters, digits and '_', not starting with a digit",
+                    key
+                );
+            }
+            Ok((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Render `prompt` with `--var` values, dropping any frontmatter. For
+/// commands that have no template context of their own.
+pub fn render_with_vars(prompt: &Prompt, vars: &BTreeMap<String, String>) -> Result<Prompt> {
+    let body = parse_prompt_document(prompt)?.body;
+    let env = create_template_env();
+    let context = serde_json::to_value(vars)?;
+    validate_template_variables(&env, &body, &context)
+        .context("Prompt template uses undefined variables")?;
+    Ok(Prompt::Inline(render_prompt_body(&body, &env, &context)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_vars_splits_on_first_equals() {
+        let vars = parse_vars(&["issue=123".to_string(), "query=a=b".to_string()]).unwrap();
+        assert_eq!(vars["issue"], "123");
+        assert_eq!(vars["query"], "a=b");
+    }
+
+    #[test]
+    fn parse_vars_rejects_bad_input() {
+        assert!(parse_vars(&["issue".to_string()]).is_err());
+        assert!(parse_vars(&["1st=x".to_string()]).is_err());
+        assert!(parse_vars(&["a-b=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn template_names_stay_in_the_library() {
+        let path = template_path("bugfix.md").unwrap();
+        assert!(path.ends_with("prompts/bugfix.md"));
+        assert!(template_path("../config").is_err());
+        assert!(template_path("a/b").is_err());
+        assert!(template_path("").is_err());
+    }
+
+    #[test]
+    fn render_with_vars_requires_every_variable() {
+        let prompt = Prompt::Inline("---\nforeach: {}\n---\nFix #{{ issue }}".to_string());
+        let mut vars = BTreeMap::new();
+        assert!(render_with_vars(&prompt, &vars).is_err());
+        vars.insert("issue".to_string(), "123".to_string());
+        match render_with_vars(&prompt, &vars).unwrap() {
+            Prompt::Inline(text) => assert_eq!(text.trim(), "Fix #123"),
+            Prompt::FromFile(_) => panic!("expected an inline prompt"),
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use anyhow::{Result, anyhow};
 
-use crate::git;
-use crate::sandbox;
+use crate::{git, sandbox, vcs};
 use tracing::{debug, info};
 
 use super::cleanup::{self, get_worktree_mode};
@@ -19,12 +18,8 @@ pub fn remove(
 
     // Get worktree path and branch - this also validates that the worktree exists
     // Smart resolution: try handle first, then branch name
-    let (worktree_path, branch_name) = git::find_worktree(handle).map_err(|_| {
-        anyhow!(
-            "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-            handle
-        )
-    })?;
+    let (worktree_path, branch_name) =
+        vcs::find_workspace(handle).map_err(|_| git::WorktreeNotFound(handle.to_string()))?;
 
     // Extract actual handle from worktree path (directory name)
     // User may have provided branch name (with slashes) but window names use handle (with dashes)
@@ -78,7 +73,7 @@ pub fn remove(
         ));
     }
 
-    if worktree_path.exists() && git::has_uncommitted_changes(&worktree_path)? && !force {
+    if worktree_path.exists() && context.vcs.has_uncommitted_changes(&worktree_path)? && !force {
         return Err(anyhow!(
             "Worktree has uncommitted changes. Use --force to delete anyway."
         ));
@@ -23,12 +23,8 @@ pub fn rename(
     //    `find_worktree` handles both. Always derive the authoritative handle
     //    from the worktree's directory basename to keep metadata/tmux/state
     //    migrations consistent regardless of what the user typed.
-    let (old_path, branch_name) = git::find_worktree(user_target).with_context(|| {
-        format!(
-            "Worktree '{}' not found. Use 'workmux list' to see available worktrees.",
-            user_target
-        )
-    })?;
+    let (old_path, branch_name) = git::find_worktree(user_target)
+        .map_err(|_| git::WorktreeNotFound(user_target.to_string()))?;
 
     let old_handle = old_path
         .file_name()
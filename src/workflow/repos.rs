@@ -0,0 +1,123 @@
+//! Repositories workmux knows about, for `workmux --repo <name>`.
+//!
+//! There is no explicit registry: a repository is known once an agent has
+//! run in one of its worktrees or `workmux run` has run there, since both
+//! leave its path in the state directory.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow, bail};
+
+use crate::agent_display::extract_project_name;
+use crate::git;
+use crate::state::StateStore;
+use crate::state::run::load_history;
+
+/// Resolve `--repo`: an existing directory, or the name of a known repository.
+/// Returns the main worktree root.
+pub fn resolve_repo(target: &str) -> Result<PathBuf> {
+    let path = Path::new(target);
+    if path.is_dir() {
+        return main_worktree_root(path)
+            .ok_or_else(|| anyhow!("Not a git repository: {}", path.display()));
+    }
+    match_repo(&known_repos(), target)
+}
+
+/// Main worktree roots of the repositories recorded in state, with their
+/// project names, sorted by name.
+pub fn known_repos() -> Vec<(String, PathBuf)> {
+    let mut workdirs: Vec<PathBuf> = StateStore::new()
+        .and_then(|store| store.list_all_agents())
+        .map(|agents| agents.into_iter().map(|a| a.workdir).collect())
+        .unwrap_or_default();
+    workdirs.extend(
+        load_history()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|record| record.worktree_path),
+    );
+
+    let roots: BTreeSet<PathBuf> = workdirs
+        .iter()
+        .filter(|dir| dir.is_dir())
+        .filter_map(|dir| main_worktree_root(dir))
+        .collect();
+    let mut repos: Vec<(String, PathBuf)> = roots
+        .into_iter()
+        .map(|root| (extract_project_name(&root), root))
+        .collect();
+    repos.sort();
+    repos
+}
+
+fn main_worktree_root(dir: &Path) -> Option<PathBuf> {
+    git::list_worktrees_in(Some(dir))
+        .ok()?
+        .into_iter()
+        .next()
+        .map(|(path, _branch)| path)
+}
+
+fn match_repo(repos: &[(String, PathBuf)], name: &str) -> Result<PathBuf> {
+    let matches: Vec<&PathBuf> = repos
+        .iter()
+        .filter(|(repo, _)| repo == name)
+        .map(|(_, path)| path)
+        .collect();
+    match matches.as_slice() {
+        [path] => Ok((*path).clone()),
+        [] if repos.is_empty() => bail!(
+            "Unknown repository '{}'. Pass a path, or run an agent there first so workmux knows it.",
+            name
+        ),
+        [] => {
+            let names: BTreeSet<&str> = repos.iter().map(|(repo, _)| repo.as_str()).collect();
+            bail!(
+                "Unknown repository '{}'. Known repositories: {}",
+                name,
+                names.into_iter().collect::<Vec<_>>().join(", ")
+            )
+        }
+        paths => {
+            let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+            bail!(
+                "Repository name '{}' is ambiguous, pass a path instead: {}",
+                name,
+                paths.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repos() -> Vec<(String, PathBuf)> {
+        vec![
+            ("api".to_string(), PathBuf::from("/code/api")),
+            ("web".to_string(), PathBuf::from("/code/web")),
+            ("web".to_string(), PathBuf::from("/forks/web")),
+        ]
+    }
+
+    #[test]
+    fn match_repo_by_name() {
+        assert_eq!(
+            match_repo(&repos(), "api").unwrap(),
+            PathBuf::from("/code/api")
+        );
+
+        let err = match_repo(&repos(), "cli").unwrap_err().to_string();
+        assert!(err.contains("Known repositories: api, web"), "{}", err);
+
+        let err = match_repo(&repos(), "web").unwrap_err().to_string();
+        assert!(err.contains("ambiguous"), "{}", err);
+        assert!(err.contains("/forks/web"), "{}", err);
+
+        let err = match_repo(&[], "api").unwrap_err().to_string();
+        assert!(err.contains("Pass a path"), "{}", err);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use tracing::info;
 
 use crate::config::{self, MuxMode};
@@ -23,6 +23,8 @@ pub struct ResurrectCandidate {
     pub action: ResurrectAction,
     pub stale_pane_keys: Vec<PaneKey>,
     pub mode: MuxMode,
+    /// Canonical worktree path, which saved window layouts are keyed by
+    pub worktree_path: PathBuf,
 }
 
 pub struct ResurrectPlan {
@@ -56,8 +58,20 @@ pub fn plan(store: &StateStore, mux: &dyn Multiplexer) -> Result<ResurrectPlan>
         "resurrect:plan filtered to current backend/instance"
     );
 
-    // Get worktrees for current repo
-    let worktrees = git::list_worktrees()?;
+    // Worktrees, live mux state (for skip detection) and stored modes are
+    // independent, so query them side by side
+    let (worktrees, mux_names, worktree_modes) = std::thread::scope(|s| {
+        let worktrees = s.spawn(git::list_worktrees);
+        let mux_names = s.spawn(|| -> Result<_> {
+            Ok((mux.get_all_window_names()?, mux.get_all_session_names()?))
+        });
+        let worktree_modes = git::get_all_worktree_modes_in(None);
+        (worktrees.join(), mux_names.join(), worktree_modes)
+    });
+    let panicked = |what: &str| anyhow!("{} panicked", what);
+    let worktrees = worktrees.map_err(|_| panicked("Listing worktrees"))??;
+    let (mux_windows, mux_sessions) =
+        mux_names.map_err(|_| panicked("Querying the multiplexer"))??;
     let main_root = git::get_main_worktree_root()?;
     let canon_main = canon_or_self(&main_root);
 
@@ -74,9 +88,6 @@ pub fn plan(store: &StateStore, mux: &dyn Multiplexer) -> Result<ResurrectPlan>
         })
         .collect();
 
-    // Get live mux state for skip detection
-    let mux_windows = mux.get_all_window_names()?;
-    let mux_sessions = mux.get_all_session_names()?;
     let config = config::Config::load(None)?;
     let prefix = config.window_prefix();
 
@@ -107,7 +118,7 @@ pub fn plan(store: &StateStore, mux: &dyn Multiplexer) -> Result<ResurrectPlan>
                     status = ?agent.status,
                     "resurrect:plan matched agent to worktree"
                 );
-                let mode = git::get_worktree_mode_opt(handle).unwrap_or(default_mode);
+                let mode = worktree_modes.get(handle).copied().unwrap_or(default_mode);
                 by_handle
                     .entry(handle.clone())
                     .or_insert_with(|| (mode, Vec::new()))
@@ -163,6 +174,7 @@ pub fn plan(store: &StateStore, mux: &dyn Multiplexer) -> Result<ResurrectPlan>
             action,
             stale_pane_keys: pane_keys,
             mode,
+            worktree_path: canon_wt,
         });
     }
 
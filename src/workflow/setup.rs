@@ -1,14 +1,17 @@
 use anyhow::{Context, Result, anyhow};
+use std::cell::Cell;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
 
 use crate::config::{MuxMode, WindowConfig};
 use crate::multiplexer::{
     CreateSessionParams, CreateWindowInSessionParams, CreateWindowParams, Multiplexer,
-    PaneSetupOptions,
+    PaneSetupOptions, PaneSetupResult,
 };
+use crate::state::{SavedPane, StateStore, WindowLayout};
 use crate::{cmd, config, git, prompt::Prompt};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use super::file_ops::{handle_file_operations, symlink_claude_local_md};
 use super::types::CreateResult;
@@ -25,6 +28,9 @@ use super::types::CreateResult;
 /// * `options` - Setup options (hooks, file ops, etc.)
 /// * `agent` - Optional agent override
 /// * `after_window` - Optional window ID to insert after (for grouping duplicates)
+/// * `window_turn` - Optional turn to create the window in (resurrect)
+/// * `limits` - Workflow context to check `limits` against before creating
+///   the window, when the agent counts toward them
 #[allow(clippy::too_many_arguments)]
 pub fn setup_environment(
     mux: &dyn Multiplexer,
@@ -35,6 +41,8 @@ pub fn setup_environment(
     options: &super::types::SetupOptions,
     agent: Option<&str>,
     after_window: Option<String>,
+    window_turn: Option<&WindowTurn>,
+    limits: Option<&super::context::WorkflowContext>,
 ) -> Result<CreateResult> {
     // Resolve agent name through the agents map once, use everywhere
     let agent = agent.map(|a| {
@@ -121,6 +129,24 @@ pub fn setup_environment(
         );
     }
 
+    // Without a multiplexer there is nothing to open: the worktree is ready
+    if mux.is_headless() {
+        info!(
+            branch = branch_name,
+            handle = handle,
+            "setup_environment:headless, skipping windows and panes"
+        );
+        return Ok(CreateResult {
+            worktree_path: worktree_path.to_path_buf(),
+            branch_name: branch_name.to_string(),
+            post_create_hooks_run: hooks_run,
+            base_branch: None,
+            did_switch: false,
+            resolved_handle: handle.to_string(),
+            mode: options.mode,
+        });
+    }
+
     // Build window plans: normalize windows/panes config into a list of window configs.
     // In window mode, we always use a single window from panes config.
     // In session mode, we can use multiple windows from windows config.
@@ -167,27 +193,52 @@ pub fn setup_environment(
         resume_mode: options.resume_mode.clone(),
     };
 
+    // Held until the windows and panes exist, so other workmux processes
+    // count this agent
+    let slot = match limits {
+        Some(context) => super::agent_limits::wait_for_slot(context)?,
+        None => None,
+    };
+
     // Track the focus and zoom pane across all windows
     let mut focus_pane_id: Option<String> = None;
     let mut zoom_pane_id: Option<String> = None;
 
     match options.mode {
         MuxMode::Window => {
-            // Window mode: single window, use panes config (window_plans always has 1 entry)
-            let panes = window_plans[0].panes.as_deref().unwrap_or(&[]);
+            // Window mode: single window, use panes config (window_plans always has 1 entry),
+            // or the panes the window had before, when resurrecting it
+            let saved_panes = options.saved_layout.as_ref().map(|l| l.to_pane_configs());
+            let panes = match &saved_panes {
+                Some(panes) => panes.as_slice(),
+                None => window_plans[0].panes.as_deref().unwrap_or(&[]),
+            };
             let resolved_panes = resolve_pane_configuration(panes, agent);
 
-            let last_wm_window =
-                after_window.or_else(|| mux.find_last_window_with_prefix(prefix).unwrap_or(None));
+            // Finding the last window and inserting after it must happen
+            // together, or concurrent restores would interleave windows
+            let repo_name = repo_root
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let session = config.window_session(options.mode, &repo_name, branch_name);
+            let initial_pane_id = in_turn(window_turn, || {
+                // The tmux backend orders windows within a target session itself
+                let last_wm_window = match session {
+                    Some(_) => None,
+                    None => after_window
+                        .or_else(|| mux.find_last_window_with_prefix(prefix).unwrap_or(None)),
+                };
 
-            let initial_pane_id = mux
-                .create_window(CreateWindowParams {
+                mux.create_window(CreateWindowParams {
                     prefix,
                     name: handle,
                     cwd: effective_working_dir,
                     after_window: last_wm_window.as_deref(),
+                    session: session.as_deref(),
                 })
-                .context("Failed to create window")?;
+            })
+            .context("Failed to create window")?;
             info!(
                 branch = branch_name,
                 handle = handle,
@@ -206,6 +257,17 @@ pub fn setup_environment(
                 )
                 .context("Failed to setup panes")?;
 
+            if let Some(geometry) = options
+                .saved_layout
+                .as_ref()
+                .and_then(|l| l.geometry.as_deref())
+                && result.panes.len() == resolved_panes.len()
+                && let Err(e) = mux.restore_window_geometry(&result.focus_pane_id, geometry)
+            {
+                warn!(handle, error = %e, "setup_environment:failed to restore pane geometry");
+            }
+            save_window_layout(mux, worktree_path, &result);
+
             focus_pane_id = Some(result.focus_pane_id);
             zoom_pane_id = result.zoom_pane_id;
         }
@@ -218,14 +280,15 @@ pub fn setup_environment(
 
                 let initial_pane_id = if i == 0 {
                     // First window: create the session
-                    let pane_id = mux
-                        .create_session(CreateSessionParams {
+                    let pane_id = in_turn(window_turn, || {
+                        mux.create_session(CreateSessionParams {
                             prefix,
                             name: handle,
                             cwd: effective_working_dir,
                             initial_window_name: window_plan.name.as_deref(),
                         })
-                        .context("Failed to create session")?;
+                    })
+                    .context("Failed to create session")?;
                     info!(
                         branch = branch_name,
                         handle = handle,
@@ -279,6 +342,8 @@ pub fn setup_environment(
         }
     }
 
+    drop(slot);
+
     let focus_pane_id = focus_pane_id.expect("at least one window must be created");
     debug!(
         branch = branch_name,
@@ -321,6 +386,111 @@ pub fn setup_environment(
     })
 }
 
+/// Remember a new window's panes so `resurrect` can rebuild it as it is.
+/// Best-effort: a window that can't be saved is rebuilt from config instead.
+fn save_window_layout(mux: &dyn Multiplexer, worktree_path: &Path, result: &PaneSetupResult) {
+    let geometry = match mux.window_geometry(&result.focus_pane_id) {
+        Ok(Some(geometry)) => geometry,
+        Ok(None) => return,
+        Err(e) => {
+            debug!(error = %e, "setup_environment:could not read window geometry");
+            return;
+        }
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let configured = WindowLayout {
+        worktree: crate::util::canon_or_self(worktree_path),
+        geometry: None,
+        panes: result
+            .panes
+            .iter()
+            .map(|(pane_id, command)| SavedPane {
+                pane_id: pane_id.clone(),
+                command: command.clone(),
+                focus: *pane_id == result.focus_pane_id,
+            })
+            .collect(),
+        updated_ts: now,
+    };
+    let (geometry, pane_ids) = geometry;
+    let layout = configured.reshaped(geometry, pane_ids, now);
+    if let Err(e) = StateStore::new()
+        .and_then(|store| store.save_layout(mux.name(), &mux.instance_id(), &layout))
+    {
+        warn!(error = %e, "setup_environment:failed to save window layout");
+    }
+}
+
+/// Hands out turns for window creation so worktrees set up concurrently
+/// still get their windows in a fixed order. Everything else (hooks, file
+/// operations, pane commands) runs freely in parallel.
+#[derive(Default)]
+pub struct WindowTurns {
+    next: Mutex<usize>,
+    turn_done: Condvar,
+}
+
+impl WindowTurns {
+    /// The `index`th turn. Every index from 0 up must be handed out, or
+    /// later turns wait forever.
+    pub fn turn(&self, index: usize) -> WindowTurn<'_> {
+        WindowTurn {
+            turns: self,
+            index,
+            used: Cell::new(false),
+        }
+    }
+}
+
+/// One worktree's place in line for window creation. Dropping an unused
+/// turn (e.g. setup failed before creating a window) still passes it on.
+pub struct WindowTurn<'a> {
+    turns: &'a WindowTurns,
+    index: usize,
+    used: Cell<bool>,
+}
+
+impl WindowTurn<'_> {
+    /// Wait for this turn, run `f`, then let the next turn go.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let mut next = self.wait();
+        let result = f();
+        self.used.set(true);
+        *next += 1;
+        drop(next);
+        self.turns.turn_done.notify_all();
+        result
+    }
+
+    fn wait(&self) -> std::sync::MutexGuard<'_, usize> {
+        let next = self.turns.next.lock().unwrap_or_else(|e| e.into_inner());
+        self.turns
+            .turn_done
+            .wait_while(next, |next| *next != self.index)
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for WindowTurn<'_> {
+    fn drop(&mut self) {
+        if !self.used.get() {
+            *self.wait() += 1;
+            self.turns.turn_done.notify_all();
+        }
+    }
+}
+
+/// Run `f` in `turn`, or right away without one.
+fn in_turn<T>(turn: Option<&WindowTurn>, f: impl FnOnce() -> T) -> T {
+    match turn {
+        Some(turn) => turn.run(f),
+        None => f(),
+    }
+}
+
 /// Pre-boot a Lima VM if sandbox is enabled with the Lima backend and any
 /// pane requires sandboxing. Must be called BEFORE creating the tmux window
 /// so the user sees VM boot progress in their terminal.
@@ -584,6 +754,8 @@ mod tests {
             open_if_exists: false,
             mode: crate::config::MuxMode::default(),
             resume_mode: crate::multiplexer::types::ResumeMode::default(),
+            layout: None,
+            saved_layout: None,
         }
     }
 
@@ -815,6 +987,25 @@ mod tests {
         let content = std::fs::read_to_string(&path).unwrap();
         assert_eq!(content, "test prompt");
     }
+
+    #[test]
+    fn window_turns_run_in_index_order() {
+        let turns = WindowTurns::default();
+        let order = Mutex::new(Vec::new());
+        // Workers pick indices in reverse; index 2 never creates a window
+        std::thread::scope(|s| {
+            for i in (0..4).rev() {
+                let (turns, order) = (&turns, &order);
+                s.spawn(move || {
+                    let turn = turns.turn(i);
+                    if i != 2 {
+                        turn.run(|| order.lock().unwrap().push(i));
+                    }
+                });
+            }
+        });
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 3]);
+    }
 }
 
 /// Validates that a prompt will actually be consumed by an agent pane.
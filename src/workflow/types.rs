@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use crate::config::MuxMode;
+use crate::git::BranchDivergence;
 use crate::github::PrSummary;
 use crate::multiplexer::AgentStatus;
 use crate::multiplexer::conversation::{ConversationForker, SessionInfo};
@@ -15,6 +16,9 @@ pub struct CreateArgs<'a> {
     pub remote_branch: Option<&'a str>,
     /// PR number for PR checkout flow (fetches refs/pull/N/head from origin)
     pub pr_number: Option<u32>,
+    /// The remote branch lives on a fork: pull from and merge into `base_branch`
+    /// on origin, push back to the fork
+    pub is_fork: bool,
     pub prompt: Option<&'a Prompt>,
     pub options: SetupOptions,
     pub mode_override: Option<MuxMode>,
@@ -27,6 +31,9 @@ pub struct CreateArgs<'a> {
     pub prompt_file_only: bool,
     /// Fork a conversation from another worktree into this one
     pub fork_source: Option<ForkSource>,
+    /// Host commands this worktree's sandbox is limited to, from prompt
+    /// frontmatter. Recorded so `sandbox run` applies them too.
+    pub host_commands: Option<&'a [String]>,
 }
 
 /// Result of creating a worktree
@@ -50,6 +57,47 @@ pub struct MergeResult {
     pub had_staged_changes: bool,
 }
 
+/// A merge stopped on conflicts (`workmux merge` exits with 3)
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct MergeConflict(pub String);
+
+/// A merge refused to run over uncommitted changes (`workmux merge` exits with 4)
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct UncommittedChanges(pub String);
+
+/// A worktree exists, but its window or session isn't open
+#[derive(Debug, thiserror::Error)]
+#[error("No active {kind} found for '{target}'. The worktree exists but has no open {kind}.")]
+pub struct TargetNotOpen {
+    /// "window" or "session"
+    pub kind: &'static str,
+    /// Full window or session name, with the prefix
+    pub target: String,
+    pub handle: String,
+}
+
+/// No running agent matches the worktree or `project:handle` asked for
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct AgentNotFound {
+    pub message: String,
+    /// Handle as typed, without any project qualifier
+    pub handle: String,
+}
+
+/// The documented exit code for a failed command, when it isn't the generic 1.
+pub fn failure_exit_code(err: &anyhow::Error) -> Option<i32> {
+    if err.downcast_ref::<MergeConflict>().is_some() {
+        Some(3)
+    } else if err.downcast_ref::<UncommittedChanges>().is_some() {
+        Some(4)
+    } else {
+        None
+    }
+}
+
 /// Result of removing a worktree
 pub struct RemoveResult {
     pub branch_removed: String,
@@ -83,6 +131,13 @@ pub struct DeferredCleanup {
     /// Path to the git worktree admin directory (e.g., $GIT_COMMON_DIR/worktrees/<name>/).
     /// Used to remove lock files before pruning, since `git worktree prune` skips locked entries.
     pub worktree_admin_dir: Option<PathBuf>,
+    /// Fork push remote and branch (see `git::configure_triangular_branch`), whose
+    /// refspec is removed along with the branch.
+    pub push_target: Option<(String, String)>,
+    /// Run `git maintenance run --auto` in the background afterwards
+    pub run_maintenance: bool,
+    /// The worktree is a jj workspace: forget it and delete its bookmark with jj
+    pub jj_workspace: bool,
 }
 
 /// Result of cleanup operations
@@ -118,6 +173,11 @@ pub struct SetupOptions {
     pub mode: MuxMode,
     /// How to resume a conversation (continue last, fork specific session, or none).
     pub resume_mode: ResumeMode,
+    /// Named pane layout from `layouts:`, remembered so `open` reuses it
+    pub layout: Option<String>,
+    /// Panes and geometry saved from the window's last run, used instead of
+    /// the pane config when resurrecting it (window mode only)
+    pub saved_layout: Option<crate::state::WindowLayout>,
 }
 
 impl SetupOptions {
@@ -135,6 +195,8 @@ impl SetupOptions {
             open_if_exists: false,
             mode: MuxMode::default(),
             resume_mode: ResumeMode::default(),
+            layout: None,
+            saved_layout: None,
         }
     }
 
@@ -151,6 +213,8 @@ impl SetupOptions {
             open_if_exists: false,
             mode: MuxMode::default(),
             resume_mode: ResumeMode::default(),
+            layout: None,
+            saved_layout: None,
         }
     }
 
@@ -173,6 +237,8 @@ impl SetupOptions {
             open_if_exists: false,
             mode: MuxMode::default(),
             resume_mode: ResumeMode::default(),
+            layout: None,
+            saved_layout: None,
         }
     }
 }
@@ -205,4 +271,29 @@ pub struct WorktreeInfo {
     pub created_at: Option<u64>,
     /// The base branch this worktree was created from (from git config)
     pub base_branch: Option<String>,
+    /// Ahead/behind counts versus the branch's upstream and the main branch
+    pub divergence: BranchDivergence,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn failure_exit_code_sees_through_context() {
+        let conflict: anyhow::Result<()> = Err(MergeConflict("conflicts".to_string()).into());
+        let err = conflict.context("Failed to merge worktree").unwrap_err();
+        assert_eq!(failure_exit_code(&err), Some(3));
+
+        let rebase: anyhow::Result<()> = Err(anyhow::anyhow!("git rebase failed"));
+        let err = rebase
+            .with_context(|| MergeConflict("Rebase failed".to_string()))
+            .unwrap_err();
+        assert_eq!(failure_exit_code(&err), Some(3));
+
+        let dirty = anyhow::Error::from(UncommittedChanges("dirty".to_string()));
+        assert_eq!(failure_exit_code(&dirty), Some(4));
+        assert_eq!(failure_exit_code(&anyhow::anyhow!("other")), None);
+    }
 }
@@ -87,10 +87,10 @@ def run_workmux_in_session(
         if f.exists():
             f.unlink()
 
-    # Clear any previous log file so we only see output from this run
-    log_file = state_dir / "workmux" / "workmux.log"
-    if log_file.exists():
-        log_file.unlink()
+    # Clear any previous log files so we only see output from this run
+    log_dir = state_dir / "workmux"
+    for old_log in log_dir.glob("workmux.*.log"):
+        old_log.unlink()
 
     workdir = working_dir or repo_path
 
@@ -131,10 +131,11 @@ cd {shlex.quote(str(workdir))}
     stderr_content = stderr_file.read_text() if stderr_file.exists() else ""
 
     # Append workmux log file content to stderr when RUST_LOG was set.
-    # workmux writes tracing output to $XDG_STATE_HOME/workmux/workmux.log
+    # workmux writes tracing output to $XDG_STATE_HOME/workmux/workmux.<date>.log
     # (not stderr), so we read it back and include it in the result.
-    if rust_log and log_file.exists():
-        stderr_content += log_file.read_text()
+    if rust_log:
+        for log_file in sorted(log_dir.glob("workmux.*.log")):
+            stderr_content += log_file.read_text()
 
     return WorkmuxCommandResult(
         exit_code=int(exit_code_file.read_text().strip()),
@@ -543,3 +543,39 @@ class TestWorktreeDirPlaceholders:
         handle = slugify(branch_name)
         expected = env.home_path / ".workmux" / mux_repo_path.name / handle
         assert expected.is_dir(), f"expected worktree at {expected}"
+
+
+class TestGitWorktreeConfig:
+    """Tests for git.config applied to new worktrees."""
+
+    def test_git_config_applies_to_new_worktree_only(
+        self,
+        mux_server: MuxEnvironment,
+        workmux_exe_path: Path,
+        mux_repo_path: Path,
+    ):
+        """git.config values are set with --worktree, leaving the main worktree alone."""
+        env = mux_server
+        branch_name = "feature-git-config"
+
+        (mux_repo_path / ".workmux.yaml").write_text(
+            yaml.dump(
+                {
+                    "nerdfont": False,
+                    "git": {"config": {"user.email": "bot@example.com"}},
+                }
+            )
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            env, workmux_exe_path, mux_repo_path, branch_name
+        )
+
+        def git_get(cwd: Path, key: str) -> str:
+            return env.run_command(
+                ["git", "config", "--get", key], check=False, cwd=cwd
+            ).stdout.strip()
+
+        assert git_get(worktree_path, "user.email") == "bot@example.com"
+        assert git_get(mux_repo_path, "user.email") != "bot@example.com"
+        assert git_get(mux_repo_path, "extensions.worktreeConfig") == "true"
@@ -78,3 +78,32 @@ def test_init_fails_in_readonly_directory(
 
     finally:
         os.chmod(test_dir, original_mode)
+
+
+def test_init_proposes_detected_settings(
+    mux_server: MuxEnvironment, workmux_exe_path: Path
+):
+    """Verifies `workmux init` writes hooks for the detected package manager and test script."""
+    env = mux_server
+    config_file_path = env.tmp_path / ".workmux.yaml"
+    (env.tmp_path / "pnpm-lock.yaml").write_text("")
+    (env.tmp_path / "package.json").write_text(
+        '{"scripts": {"test": "vitest run", "dev": "vite"}}'
+    )
+
+    result = env.run_command([str(workmux_exe_path), "--yes", "init"])
+
+    assert result.returncode == 0
+    assert "✓ Created .workmux.yaml" in result.stdout
+
+    content = config_file_path.read_text()
+    config = yaml.safe_load(content)
+    assert config["post_create"] == ["pnpm install"]
+    assert config["pre_merge"] == ["pnpm run test"]
+    assert [pane.get("command") for pane in config["panes"]] == [
+        "<agent>",
+        None,
+        "pnpm run dev",
+    ]
+    # The full reference is still there, commented out
+    assert "# pre_remove:" in content
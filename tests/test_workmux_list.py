@@ -213,6 +213,38 @@ def test_list_with_unmerged_commits(
     assert worktree_entry["UNMERGED"] == "●"
 
 
+def test_list_shows_divergence_from_main(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Verifies `list` shows ahead/behind counts versus main in the MAIN column."""
+    env = mux_server
+    branch_name = "feature-diverged"
+    worktree_path = get_worktree_path(mux_repo_path, branch_name)
+    write_workmux_config(mux_repo_path)
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, branch_name)
+
+    create_commit(env, worktree_path, "Feature commit")
+    create_commit(env, mux_repo_path, "Main commit 1")
+    create_commit(env, mux_repo_path, "Main commit 2")
+
+    output = run_workmux_list(env, workmux_exe_path, mux_repo_path)
+    parsed_output = parse_list_output(output)
+    worktree_entry = next(
+        (r for r in parsed_output if r["BRANCH"] == branch_name), None
+    )
+    assert worktree_entry is not None
+    assert worktree_entry["MAIN"] == "↑1 ↓2"
+    # No upstream configured for a freshly created local branch
+    assert worktree_entry["UPSTREAM"] == "-"
+
+    output = run_workmux_list(
+        env, workmux_exe_path, mux_repo_path, f"--json {branch_name}"
+    )
+    data = json.loads(output)
+    assert data[0]["main"] == {"ahead": 1, "behind": 2}
+    assert data[0]["upstream"] is None
+
+
 def test_list_with_detached_window(
     mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
 ):
@@ -391,6 +423,8 @@ def test_list_json_output(
         "has_uncommitted_changes",
         "is_open",
         "created_at",
+        "upstream",
+        "main",
     }
     for entry in data:
         assert set(entry.keys()) == expected_fields
@@ -503,6 +503,34 @@ def test_merge_falls_back_to_main_when_base_branch_deleted(
     )
 
 
+def test_merge_falls_back_to_main_when_base_is_a_tag(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies merge targets main, not the tag, when the branch was created from a tag."""
+    env = mux_server
+    branch_name = "feature/from-tag"
+    write_workmux_config(repo_path, env=env)
+
+    env.run_command(["git", "tag", "v1.0"], cwd=repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name, base="v1.0")
+    worktree_path = get_worktree_path(repo_path, branch_name)
+
+    create_commit(env, worktree_path, "feat: work based on tag")
+    commit_hash = env.run_command(
+        ["git", "rev-parse", "--short", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+    main_log_result = env.run_command(
+        ["git", "log", "--oneline", "main"], cwd=repo_path
+    )
+    assert commit_hash in main_log_result.stdout, (
+        "Commit should be on main when the stored base is a tag"
+    )
+
+
 def test_merge_succeeds_when_target_branch_checked_out_in_another_worktree(
     mux_server: MuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
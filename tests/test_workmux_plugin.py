@@ -0,0 +1,99 @@
+from pathlib import Path
+
+from .conftest import (
+    MuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def install_plugin(env: MuxEnvironment, tmp_path: Path) -> dict:
+    """Put a `workmux-hello` plugin on PATH that echoes its args and env."""
+    bin_dir = tmp_path / "plugin-bin"
+    bin_dir.mkdir()
+    plugin = bin_dir / "workmux-hello"
+    plugin.write_text(
+        "#!/bin/sh\n"
+        'echo "args=$*"\n'
+        'echo "repo=$WORKMUX_REPO_ROOT"\n'
+        'echo "worktree=$WORKMUX_WORKTREE"\n'
+        'echo "handle=$WORKMUX_HANDLE"\n'
+        'echo "state=$WORKMUX_STATE_DIR"\n'
+        'echo "bin=$WORKMUX_BIN"\n'
+    )
+    plugin.chmod(0o755)
+    return {"PATH": f"{bin_dir}:{env.env['PATH']}"}
+
+
+def parse_output(stdout: str) -> dict:
+    return dict(line.split("=", 1) for line in stdout.strip().splitlines())
+
+
+def test_unknown_subcommand_runs_plugin(
+    mux_server: MuxEnvironment,
+    workmux_exe_path: Path,
+    mux_repo_path: Path,
+    tmp_path: Path,
+):
+    """`workmux hello` runs `workmux-hello` with the remaining args and repo env."""
+    env = mux_server
+    plugin_env = install_plugin(env, tmp_path)
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "hello --flag value",
+        pre_run_env=plugin_env,
+    )
+
+    out = parse_output(result.stdout)
+    assert out["args"] == "--flag value"
+    assert Path(out["repo"]).resolve() == mux_repo_path.resolve()
+    assert out["worktree"] == ""
+    assert out["state"] != ""
+    assert Path(out["bin"]).name.startswith("workmux")
+
+
+def test_plugin_sees_current_worktree(
+    mux_server: MuxEnvironment,
+    workmux_exe_path: Path,
+    mux_repo_path: Path,
+    tmp_path: Path,
+):
+    """Run from a worktree, the plugin gets its path and handle."""
+    env = mux_server
+    write_workmux_config(mux_repo_path)
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, "feature-plugin")
+    worktree = get_worktree_path(mux_repo_path, "feature-plugin")
+    plugin_env = install_plugin(env, tmp_path)
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "hello",
+        working_dir=worktree,
+        pre_run_env=plugin_env,
+    )
+
+    out = parse_output(result.stdout)
+    assert Path(out["worktree"]).resolve() == worktree.resolve()
+    assert out["handle"] == "feature-plugin"
+
+
+def test_unknown_subcommand_without_plugin_fails(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Without a matching plugin, the usual unknown-subcommand error remains."""
+    result = run_workmux_command(
+        mux_server,
+        workmux_exe_path,
+        mux_repo_path,
+        "no-such-plugin",
+        expect_fail=True,
+    )
+
+    assert "unrecognized subcommand" in result.stderr
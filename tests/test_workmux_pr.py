@@ -296,6 +296,7 @@ def test_add_pr_fork_with_main_branch(mux_server, workmux_exe_path, remote_repo_
     # PR data: fork PR where head branch is "main" from a different owner
     pr_data = {
         "headRefName": "main",
+        "baseRefName": "main",
         "headRepositoryOwner": {"login": "forkowner"},
         "state": "OPEN",
         "isDraft": False,
@@ -320,6 +321,18 @@ def test_add_pr_fork_with_main_branch(mux_server, workmux_exe_path, remote_repo_
     windows = env.list_windows()
     assert window_name in windows
 
+    # Triangular workflow: pull from origin's base branch, push to the fork
+    def git_config(*args: str) -> str:
+        return env.run_command(["git", "config", *args], cwd=repo_path).stdout.strip()
+
+    assert git_config("branch.forkowner-main.remote") == "origin"
+    assert git_config("branch.forkowner-main.merge") == "refs/heads/main"
+    assert git_config("branch.forkowner-main.pushRemote") == "fork-forkowner"
+    assert git_config("branch.forkowner-main.workmux-base") == "main"
+    assert "refs/heads/forkowner-main:refs/heads/main" in git_config(
+        "--get-all", "remote.fork-forkowner.push"
+    )
+
 
 def test_add_pr_fails_when_worktree_exists(
     mux_server, workmux_exe_path, remote_repo_path
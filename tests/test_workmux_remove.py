@@ -10,6 +10,7 @@ from .conftest import (
     get_window_name,
     get_worktree_path,
     run_workmux_add,
+    run_workmux_command,
     run_workmux_remove,
     write_workmux_config,
 )
@@ -86,6 +87,37 @@ def test_remove_unmerged_branch_aborted(
     assert branch_name in branch_list_result.stdout
 
 
+def test_remove_unmerged_branch_refused_when_non_interactive(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Verifies `workmux --yes remove` refuses an unmerged branch instead of prompting."""
+    env = mux_server
+    branch_name = "unmerged-noninteractive"
+    write_workmux_config(mux_repo_path)
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, branch_name)
+
+    worktree_path = get_worktree_path(mux_repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: unmerged work")
+
+    for command, pre_run_env in [
+        (f"--yes remove {branch_name}", None),
+        (f"remove {branch_name}", {"WORKMUX_NONINTERACTIVE": "1"}),
+    ]:
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            mux_repo_path,
+            command,
+            expect_fail=True,
+            pre_run_env=pre_run_env,
+        )
+        assert "--force" in result.stderr
+
+    assert worktree_path.exists(), "Worktree should NOT be removed without --force"
+    branch_list_result = env.run_command(["git", "branch", "--list", branch_name])
+    assert branch_name in branch_list_result.stdout
+
+
 def test_remove_fails_on_uncommitted_changes(
     mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
 ):
@@ -208,6 +208,55 @@ def test_run_keep_preserves_artifacts(
     assert "KEPT_OUTPUT" in stdout_content
 
 
+def test_runs_clean_all_removes_kept_artifacts(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """`runs clean --all` removes artifacts kept with --keep."""
+    env = mux_server
+    setup_worktree_with_agent(env, workmux_exe_path, mux_repo_path, "feature-clean")
+
+    result = run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "run feature-clean --keep -- echo CLEAN_ME",
+    )
+    assert result.exit_code == 0
+    run_dir = extract_artifacts_path(result.stderr)
+    assert run_dir.exists()
+
+    clean = run_workmux_run(env, workmux_exe_path, mux_repo_path, "runs clean --all")
+    assert clean.exit_code == 0
+    assert "Removed" in clean.stdout
+    assert not run_dir.exists()
+
+
+def test_run_json_reports_result(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """--json prints a result object instead of the output, and keeps the files."""
+    env = mux_server
+    setup_worktree_with_agent(env, workmux_exe_path, mux_repo_path, "feature-json")
+
+    result = run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "run feature-json --wait --json -- sh -c 'echo JSON_OUT; exit 3'",
+    )
+    assert result.exit_code == 3
+    assert "JSON_OUT" not in result.stdout
+
+    report = json.loads(result.stdout)
+    assert report["worktree"] == "feature-json"
+    assert report["exit_code"] == 3
+    assert report["timed_out"] is False
+    assert report["pane_id"]
+    assert isinstance(report["duration_ms"], int)
+    assert "JSON_OUT" in Path(report["stdout"]).read_text()
+    assert Path(report["stderr"]).exists()
+
+
 def test_run_timeout_exits_124(
     mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
 ):
@@ -224,3 +273,96 @@ def test_run_timeout_exits_124(
     )
     assert result.exit_code == 124
     assert "Timeout" in result.stderr
+
+
+def test_run_all_prints_summary_and_fails_on_any_failure(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Run --all runs in every worktree, prefixes output, and summarizes results."""
+    env = mux_server
+    write_workmux_config(mux_repo_path, panes=[{"focus": True}])
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, "feature-all-ok")
+    run_workmux_add(env, workmux_exe_path, mux_repo_path, "feature-all-bad")
+
+    result = run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "run --all -j 2 -- sh -c "
+        "'echo \"IN $(basename $PWD)\"; case $PWD in *bad) exit 3;; esac'",
+        expect_fail=True,
+    )
+    assert result.exit_code == 1
+
+    ok_line = next(
+        line for line in result.stdout.splitlines() if "IN " in line and "ok" in line
+    )
+    assert re.match(r"^\S*feature-all-ok\s+│ IN \S*feature-all-ok$", ok_line)
+
+    summary = result.stdout.split("WORKTREE", 1)[1]
+    assert re.search(r"feature-all-ok\s+feature-all-ok\s+✓ pass", summary)
+    assert re.search(r"feature-all-bad\s+feature-all-bad\s+✗ fail \(3\)", summary)
+    assert "1 of 2 worktrees failed" in result.stderr
+
+
+def test_runs_list_and_rerun(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Completed runs are listed by `runs list` and repeated by `runs rerun`."""
+    env = mux_server
+    setup_worktree_with_agent(env, workmux_exe_path, mux_repo_path, "feature-runs")
+
+    run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "run feature-runs -- echo HISTORY_MARKER",
+    )
+
+    listing = run_workmux_run(env, workmux_exe_path, mux_repo_path, "runs list")
+    assert re.search(r"feature-runs\s+0\s+.*echo HISTORY_MARKER", listing.stdout)
+
+    rerun = run_workmux_run(env, workmux_exe_path, mux_repo_path, "runs rerun")
+    assert "HISTORY_MARKER" in rerun.stdout
+    assert "Re-running in feature-runs" in rerun.stderr
+
+
+def test_run_env_and_env_file_reach_command(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """--env overrides --env-file, and both reach the executed command."""
+    env = mux_server
+    setup_worktree_with_agent(env, workmux_exe_path, mux_repo_path, "feature-run-env")
+
+    env_file = env.tmp_path / "run.env"
+    env_file.write_text("# test vars\nexport FROM_FILE='file value'\nOVERRIDE=file\n")
+
+    result = run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        f"run feature-run-env --env-file {shlex.quote(str(env_file))} "
+        "-e OVERRIDE=flag -- sh -c 'echo \"$FROM_FILE|$OVERRIDE\"'",
+    )
+    assert "file value|flag" in result.stdout
+
+
+def test_run_on_fail_hook_receives_run_details(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """--on-fail hook runs after a failure with the exit code and output path."""
+    env = mux_server
+    setup_worktree_with_agent(env, workmux_exe_path, mux_repo_path, "feature-run-fail")
+
+    marker = env.tmp_path / "on-fail.txt"
+    hook = f'hook:echo "$WM_RUN_EXIT_CODE $(cat "$WM_RUN_STDOUT")" > {shlex.quote(str(marker))}'
+    result = run_workmux_run(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        f"run feature-run-fail --on-fail {shlex.quote(hook)} -- "
+        "sh -c 'echo BROKEN; exit 5'",
+        expect_fail=True,
+    )
+    assert result.exit_code == 5
+    assert marker.read_text().strip() == "5 BROKEN"
@@ -107,3 +107,43 @@ def test_wait_succeeds_when_already_in_target_status(
         "wait feature-wait-ok --status done --timeout 5",
     )
     assert result.exit_code == 0
+
+
+def test_wait_multiple_prints_summary(
+    mux_server: MuxEnvironment, workmux_exe_path: Path, mux_repo_path: Path
+):
+    """Waiting on several worktrees prints a summary, including pending ones."""
+    env = mux_server
+    write_workmux_config(mux_repo_path, panes=[{"focus": True}])
+    for branch_name, status in [("feature-wait-a", "done"), ("feature-wait-b", "working")]:
+        window_name = get_window_name(branch_name)
+        run_workmux_add(env, workmux_exe_path, mux_repo_path, branch_name)
+        wait_for_window_ready(env, window_name)
+        before = len(list_agent_state_files(env))
+        env.send_keys(window_name, build_status_cmd(env, workmux_exe_path, status))
+        assert poll_until(
+            lambda: len(list_agent_state_files(env)) > before, timeout=5.0
+        ), "Agent state file not created"
+
+    # --any is satisfied by the finished agent
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "wait feature-wait-a feature-wait-b --any --timeout 5",
+    )
+    assert result.exit_code == 0
+    assert "feature-wait-a" in result.stdout
+    assert "pending" in result.stdout
+
+    # Waiting for all times out, but still reports what finished
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        mux_repo_path,
+        "wait --all --status done,waiting --timeout 2",
+        expect_fail=True,
+    )
+    assert result.exit_code == 1
+    assert "done" in result.stdout
+    assert "pending" in result.stdout